@@ -1,9 +1,9 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use geozero::error::Result;
-use geozero::mvt::{Message, Tile};
+use geozero::mvt::{tile, Message, MvtWriter, Tile};
 use geozero::{
     ColumnValue, CoordDimensions, FeatureProcessor, GeomProcessor, GeozeroDatasource,
-    PropertyProcessor,
+    GeozeroGeometry, PropertyProcessor,
 };
 
 struct Proc {
@@ -296,6 +296,33 @@ fn run_parse(mut proc: Proc) {
     }
 }
 
+fn tile_features() -> Vec<tile::Feature> {
+    let data = &include_bytes!("../../geozero/tests/data/tile.mvt")[..];
+    let tile = Tile::decode(data).unwrap();
+    tile.layers
+        .into_iter()
+        .flat_map(|layer| layer.features)
+        .collect()
+}
+
+/// Encode every feature with a new writer
+fn run_encode_new(features: &[tile::Feature]) {
+    for feature in features {
+        let mut writer = MvtWriter::default();
+        feature.process_geom(&mut writer).unwrap();
+        black_box(writer.geometry());
+    }
+}
+
+/// Encode every feature with a reused writer
+fn run_encode_reset(features: &[tile::Feature], writer: &mut MvtWriter) {
+    for feature in features {
+        writer.reset();
+        feature.process_geom(writer).unwrap();
+        black_box(writer.geometry());
+    }
+}
+
 fn mvt_benchmark(c: &mut Criterion) {
     c.bench_function("mvt decoding xy", |b| {
         b.iter(|| run_parse(Proc::new(CoordDimensions::xy(), false)))
@@ -304,6 +331,16 @@ fn mvt_benchmark(c: &mut Criterion) {
     c.bench_function("mvt decoding xy multi_dim", |b| {
         b.iter(|| run_parse(Proc::new(CoordDimensions::xy(), true)))
     });
+
+    let features = tile_features();
+    c.bench_function("mvt encoding new writer", |b| {
+        b.iter(|| run_encode_new(&features))
+    });
+
+    let mut writer = MvtWriter::default();
+    c.bench_function("mvt encoding reused writer", |b| {
+        b.iter(|| run_encode_reset(&features, &mut writer))
+    });
 }
 
 criterion_group!(benches, mvt_benchmark);
@@ -0,0 +1,142 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use flatgeobuf::{FgbReader, FgbWriter, GeometryType};
+use geozero::csv::{Csv, CsvWriter};
+use geozero::error::Result;
+use geozero::geojson::GeoJson;
+use geozero::gpkg::{GpkgReader, GpkgWriter};
+use geozero::{ColumnValue, FeatureProcessor, GeomProcessor, GeozeroDatasource, PropertyProcessor};
+use sqlx::sqlite::SqliteConnection;
+use sqlx::Connection;
+use std::fmt::Write;
+use std::io::Cursor;
+
+const FEATURE_COUNT: usize = 10_000;
+/// Number of columns of each type (integer, double, string, boolean)
+const COLUMNS_PER_TYPE: usize = 8;
+
+/// Processor consuming all property values, ignoring geometries
+#[derive(Default)]
+struct PropertyConsumer {
+    count: usize,
+    len: usize,
+}
+
+impl PropertyProcessor for PropertyConsumer {
+    fn property(&mut self, _idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        self.count += 1;
+        self.len += name.len();
+        if let ColumnValue::String(s) = value {
+            self.len += s.len();
+        }
+        Ok(false)
+    }
+}
+
+impl GeomProcessor for PropertyConsumer {}
+impl FeatureProcessor for PropertyConsumer {}
+
+/// GeoJSON point features with 32 property columns
+fn geojson_dataset() -> String {
+    let mut json = String::from(r#"{"type": "FeatureCollection", "features": ["#);
+    for idx in 0..FEATURE_COUNT {
+        if idx > 0 {
+            json.push(',');
+        }
+        let x = (idx % 360) as f64 - 180.0;
+        let y = (idx % 180) as f64 - 90.0;
+        write!(
+            json,
+            r#"{{"type": "Feature", "geometry": {{"type": "Point", "coordinates": [{x}, {y}]}}, "properties": {{"#
+        )
+        .unwrap();
+        for col in 0..COLUMNS_PER_TYPE {
+            if col > 0 {
+                json.push(',');
+            }
+            write!(
+                json,
+                r#""int_{col}": {}, "double_{col}": {}, "string_{col}": "value {idx}-{col}", "bool_{col}": {}"#,
+                idx * col,
+                idx as f64 / (col + 1) as f64,
+                (idx + col) % 2 == 0
+            )
+            .unwrap();
+        }
+        json.push_str("}}");
+    }
+    json.push_str("]}");
+    json
+}
+
+fn to_fgb(geojson: &str) -> Vec<u8> {
+    let mut fgb = FgbWriter::create("properties", GeometryType::Point).unwrap();
+    GeoJson(geojson).process(&mut fgb).unwrap();
+    let mut out = Vec::new();
+    fgb.write(&mut out).unwrap();
+    out
+}
+
+fn to_csv(geojson: &str) -> String {
+    let mut out = Vec::new();
+    GeoJson(geojson)
+        .process(&mut CsvWriter::new(&mut out))
+        .unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+async fn to_gpkg(geojson: &str) -> SqliteConnection {
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+    let mut writer = GpkgWriter::new(&mut conn, "properties");
+    writer.set_spatial_index(false);
+    writer.write(&mut GeoJson(geojson)).await.unwrap();
+    conn
+}
+
+fn process<D: GeozeroDatasource>(datasource: &mut D) {
+    let mut consumer = PropertyConsumer::default();
+    datasource.process(&mut consumer).unwrap();
+    assert_eq!(consumer.count, FEATURE_COUNT * COLUMNS_PER_TYPE * 4);
+    black_box(consumer.len);
+}
+
+async fn process_gpkg(conn: &mut SqliteConnection) {
+    let mut consumer = PropertyConsumer::default();
+    let mut reader = GpkgReader::new("properties");
+    reader.read(conn, &mut consumer).await.unwrap();
+    assert_eq!(consumer.count, FEATURE_COUNT * COLUMNS_PER_TYPE * 4);
+    black_box(consumer.len);
+}
+
+fn process_fgb(fgb: &[u8]) {
+    let mut consumer = PropertyConsumer::default();
+    let mut reader = FgbReader::open(Cursor::new(fgb))
+        .unwrap()
+        .select_all()
+        .unwrap();
+    reader.process_features(&mut consumer).unwrap();
+    assert_eq!(consumer.count, FEATURE_COUNT * COLUMNS_PER_TYPE * 4);
+    black_box(consumer.len);
+}
+
+fn wide_properties_benchmark(c: &mut Criterion) {
+    let geojson = geojson_dataset();
+    let fgb = to_fgb(&geojson);
+    let csv = to_csv(&geojson);
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let mut gpkg = rt.block_on(to_gpkg(&geojson));
+
+    let mut group = c.benchmark_group("wide_properties");
+    group.bench_function("1-geojson", |b| b.iter(|| process(&mut GeoJson(&geojson))));
+    group.bench_function("2-fgb", |b| b.iter(|| process_fgb(&fgb)));
+    group.bench_function("3-gpkg", |b| {
+        b.iter(|| rt.block_on(process_gpkg(&mut gpkg)))
+    });
+    group.bench_function("4-csv", |b| {
+        b.iter(|| process(&mut Csv::new("geometry", &csv)))
+    });
+    group.finish();
+}
+
+criterion_group!(name=benches; config=Criterion::default().sample_size(10);
+                 targets=wide_properties_benchmark);
+criterion_main!(benches);
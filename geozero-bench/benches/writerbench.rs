@@ -0,0 +1,240 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use flatgeobuf::{FallibleStreamingIterator, FgbReader};
+use geozero::error::Result;
+use geozero::geo_types::GeoWriter;
+use geozero::geojson::GeoJsonWriter;
+use geozero::mvt::MvtWriter;
+use geozero::wkb::{WkbDialect, WkbWriter};
+use geozero::wkt::WktWriter;
+use geozero::{
+    ColumnDef, ColumnValue, ColumnValueOwned, FeatureProcessor, FeatureProperties, GeozeroGeometry,
+    PropertyProcessor, ToGeo,
+};
+use seek_bufread::BufReader;
+use std::fs::File;
+use std::io::Seek;
+
+/// Read all geometries of a FlatGeobuf file into memory
+fn load_geometries(fpath: &str) -> Vec<geo_types::Geometry<f64>> {
+    let mut filein = BufReader::new(File::open(fpath).unwrap());
+    let mut fgb = FgbReader::open(&mut filein).unwrap().select_all().unwrap();
+    let mut geometries = Vec::new();
+    while let Some(feature) = fgb.next().unwrap() {
+        geometries.push(feature.to_geo().unwrap());
+    }
+    geometries
+}
+
+/// Property columns and values of all features
+#[derive(Default)]
+struct PropertyTable {
+    columns: Vec<ColumnDef>,
+    /// Column index and value of each property
+    rows: Vec<Vec<(usize, ColumnValueOwned)>>,
+}
+
+impl PropertyProcessor for PropertyTable {
+    fn property(&mut self, _idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        let column = match self.columns.iter().position(|col| col.name == name) {
+            Some(column) => column,
+            None => {
+                self.columns.push(ColumnDef::new(name, value.column_type()));
+                self.columns.len() - 1
+            }
+        };
+        if let Some(row) = self.rows.last_mut() {
+            row.push((column, value.into()));
+        }
+        Ok(false)
+    }
+}
+
+/// Read all feature properties of a FlatGeobuf file into memory
+fn load_properties(fpath: &str) -> PropertyTable {
+    let mut filein = BufReader::new(File::open(fpath).unwrap());
+    let mut fgb = FgbReader::open(&mut filein).unwrap().select_all().unwrap();
+    let mut table = PropertyTable::default();
+    while let Some(feature) = fgb.next().unwrap() {
+        table.rows.push(Vec::new());
+        feature.process_properties(&mut table).unwrap();
+    }
+    table
+}
+
+/// Write properties as GeoJSON, passing column indexes declared with `schema_begin`, if `by_column` is set
+fn write_geojson_properties(table: &PropertyTable, out: &mut Vec<u8>, by_column: bool) {
+    out.clear();
+    let mut writer = GeoJsonWriter::new(&mut *out);
+    writer.dataset_begin(None).unwrap();
+    if by_column {
+        writer.schema_begin(&table.columns).unwrap();
+    }
+    for (idx, row) in table.rows.iter().enumerate() {
+        writer.feature_begin(idx as u64).unwrap();
+        writer.properties_begin().unwrap();
+        for (i, (column, value)) in row.iter().enumerate() {
+            let name = &table.columns[*column].name;
+            let value = value.as_column_value();
+            if by_column {
+                writer.column_property(i, *column, name, &value).unwrap();
+            } else {
+                writer.property(i, name, &value).unwrap();
+            }
+        }
+        writer.properties_end().unwrap();
+        writer.feature_end(idx as u64).unwrap();
+    }
+    writer.dataset_end().unwrap();
+    black_box(out);
+}
+
+fn write_wkb(geometries: &[geo_types::Geometry<f64>], out: &mut Vec<u8>) {
+    for geom in geometries {
+        out.clear();
+        let mut writer = WkbWriter::new(&mut *out, WkbDialect::Wkb);
+        geom.process_geom(&mut writer).unwrap();
+    }
+    black_box(out);
+}
+
+/// Write WKB into an unbuffered file
+fn write_wkb_file(geometries: &[geo_types::Geometry<f64>], file: &mut File) {
+    file.set_len(0).unwrap();
+    file.rewind().unwrap();
+    for geom in geometries {
+        let mut writer = WkbWriter::new(&mut *file, WkbDialect::Wkb);
+        geom.process_geom(&mut writer).unwrap();
+    }
+}
+
+fn write_wkt(geometries: &[geo_types::Geometry<f64>], out: &mut Vec<u8>) {
+    for geom in geometries {
+        out.clear();
+        let mut writer = WktWriter::new(&mut *out);
+        geom.process_geom(&mut writer).unwrap();
+    }
+    black_box(out);
+}
+
+fn write_geojson(geometries: &[geo_types::Geometry<f64>], out: &mut Vec<u8>) {
+    for geom in geometries {
+        out.clear();
+        let mut writer = GeoJsonWriter::new(&mut *out);
+        geom.process_geom(&mut writer).unwrap();
+    }
+    black_box(out);
+}
+
+/// Convert into geo-types with a reused writer
+fn write_geo(geometries: &[geo_types::Geometry<f64>], writer: &mut GeoWriter) {
+    for geom in geometries {
+        geom.process_geom(writer).unwrap();
+        black_box(writer.take_geometry());
+    }
+}
+
+fn write_mvt(geometries: &[geo_types::Geometry<f64>], writer: &mut MvtWriter) {
+    for geom in geometries {
+        writer.reset();
+        geom.process_geom(writer).unwrap();
+        black_box(writer.geometry());
+    }
+}
+
+fn bench_writers(c: &mut Criterion, name: &str, fpath: &str, bounds: (f64, f64, f64, f64)) {
+    let geometries = load_geometries(fpath);
+    let mut out = Vec::new();
+    let mut group = c.benchmark_group(name);
+    group.bench_function("1-wkb", |b| b.iter(|| write_wkb(&geometries, &mut out)));
+    group.bench_function("2-wkt", |b| b.iter(|| write_wkt(&geometries, &mut out)));
+    group.bench_function("3-geojson", |b| {
+        b.iter(|| write_geojson(&geometries, &mut out))
+    });
+    let (left, bottom, right, top) = bounds;
+    let mut mvt = MvtWriter::new(4096, left, bottom, right, top);
+    group.bench_function("4-mvt", |b| b.iter(|| write_mvt(&geometries, &mut mvt)));
+    let fpath = std::env::temp_dir().join(format!("geozero-{name}.wkb"));
+    let mut file = File::create(&fpath).unwrap();
+    group.bench_function("5-wkb-file", |b| {
+        b.iter(|| write_wkb_file(&geometries, &mut file))
+    });
+    let _ = std::fs::remove_file(fpath);
+    let mut geo = GeoWriter::new();
+    group.bench_function("6-geo", |b| b.iter(|| write_geo(&geometries, &mut geo)));
+    group.finish();
+}
+
+/// Linestring with many coordinates with full precision
+fn large_linestring(size: usize) -> geo_types::Geometry<f64> {
+    let coords: Vec<(f64, f64)> = (0..size)
+        .map(|i| {
+            let i = i as f64;
+            (659_000.0 + i * 0.123_456_789, 5_740_000.0 + i.sqrt() * 17.3)
+        })
+        .collect();
+    geo_types::LineString::from(coords).into()
+}
+
+/// Reference implementation writing coordinates with `std::fmt`
+fn write_coords_fmt(geom: &geo_types::Geometry<f64>, out: &mut Vec<u8>) {
+    out.clear();
+    if let geo_types::Geometry::LineString(line) = geom {
+        out.extend_from_slice(br#"{"type": "LineString", "coordinates": ["#);
+        for (i, c) in line.coords().enumerate() {
+            if i > 0 {
+                out.push(b',');
+            }
+            out.extend_from_slice(format!("[{},{}]", c.x, c.y).as_bytes());
+        }
+        out.extend_from_slice(b"]}");
+    }
+    black_box(out);
+}
+
+fn coordinate_arrays_benchmark(c: &mut Criterion) {
+    let geometries = [large_linestring(1_000_000)];
+    let mut out = Vec::new();
+    let mut group = c.benchmark_group("coordinate_arrays");
+    group.bench_function("1-fmt", |b| {
+        b.iter(|| write_coords_fmt(&geometries[0], &mut out))
+    });
+    group.bench_function("2-geojson", |b| {
+        b.iter(|| write_geojson(&geometries, &mut out))
+    });
+    group.finish();
+}
+
+fn countries_writer_benchmark(c: &mut Criterion) {
+    bench_writers(
+        c,
+        "countries_writer",
+        "tests/data/countries.fgb",
+        (-180.0, -90.0, 180.0, 90.0),
+    );
+}
+
+fn buildings_writer_benchmark(c: &mut Criterion) {
+    bench_writers(
+        c,
+        "buildings_writer",
+        "tests/data/osm-buildings-3857-ch.fgb",
+        (659_000.0, 5_740_000.0, 1_170_000.0, 6_080_000.0),
+    );
+}
+
+fn buildings_properties_benchmark(c: &mut Criterion) {
+    let table = load_properties("tests/data/osm-buildings-3857-ch.fgb");
+    let mut out = Vec::new();
+    let mut group = c.benchmark_group("buildings_properties");
+    group.bench_function("1-geojson-name", |b| {
+        b.iter(|| write_geojson_properties(&table, &mut out, false))
+    });
+    group.bench_function("2-geojson-column", |b| {
+        b.iter(|| write_geojson_properties(&table, &mut out, true))
+    });
+    group.finish();
+}
+
+criterion_group!(name=benches; config=Criterion::default().sample_size(10);
+                 targets=countries_writer_benchmark,buildings_writer_benchmark,buildings_properties_benchmark,coordinate_arrays_benchmark);
+criterion_main!(benches);
@@ -1,33 +1,78 @@
+mod mvt;
+mod remote;
+
+use crate::mvt::{lonlat_to_mercator, parse_tile, MvtLayerWriter, TileId};
 use clap::Parser;
 use flatgeobuf::{FgbReader, FgbWriter, GeometryType, HttpFgbReader};
 use geozero::csv::{CsvReader, CsvWriter};
+use geozero::dxf::DxfReader;
 use geozero::error::{GeozeroError, Result};
-use geozero::geojson::{GeoJsonLineReader, GeoJsonReader, GeoJsonWriter};
+use geozero::geojson::{GeoJsonLineReader, GeoJsonLineWriter, GeoJsonReader, GeoJsonWriter};
+use geozero::geoparquet::{process_batches, GeoArrowWriter, GeoParquetMetadata, GeoParquetReader};
+use geozero::mvt::{Message, Tile};
 use geozero::svg::SvgWriter;
 use geozero::wkt::{WktReader, WktWriter};
-use geozero::{FeatureProcessor, GeozeroDatasource};
+use geozero::{
+    FeatureProcessor, GeozeroDatasource, Progress, ProgressProcessor, ProgressReader, SwapXY,
+};
+use log::{error, info};
 use std::ffi::OsStr;
 use std::fs::File;
-use std::io::{BufReader, BufWriter};
+use std::io::{BufReader, BufWriter, IsTerminal, Write};
 use std::num::ParseFloatError;
 use std::path::{Path, PathBuf};
 use std::process::exit;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
 
-#[derive(Parser)]
+#[derive(Parser, Clone)]
 #[command(about, version)]
 struct Cli {
     /// When processing CSV, the name of the column holding a WKT geometry.
     #[arg(long)]
     csv_geometry_column: Option<String>,
 
+    /// Field delimiter of CSV input, e.g. `;` or `\t` for tab-separated values
+    #[arg(long, value_parser = parse_delimiter)]
+    csv_delimiter: Option<u8>,
+
+    /// CSV input has no header row. Columns are named `field_1`, `field_2`, etc.
+    #[arg(long)]
+    csv_no_header: bool,
+
     /// Geometries within extent
     #[arg(short, long, value_parser = parse_extent)]
     extent: Option<Extent>,
 
-    /// The path or URL to the FlatGeobuf file to read
+    /// Tile to write for MVT output (z/x/y). Input coordinates are expected in WGS84.
+    #[arg(long, value_parser = parse_tile)]
+    tile: Option<TileId>,
+
+    /// Size of MVT tile in tile coordinate space
+    #[arg(long, default_value_t = 4096)]
+    mvt_extent: u32,
+
+    /// Extension of the files to write when converting a directory or glob pattern, e.g. `fgb`
+    #[arg(long)]
+    output_format: Option<String>,
+
+    /// Number of files converted in parallel when converting a directory or glob pattern
+    #[arg(short, long, default_value_t = 1)]
+    jobs: usize,
+
+    /// Display a progress bar when converting a single file
+    #[arg(long)]
+    progress: bool,
+
+    /// Swap X and Y coordinates of the input, e.g. for sources in latitude/longitude axis order
+    #[arg(long)]
+    swap_xy: bool,
+
+    /// The path or URL to the file to read, or a directory or glob pattern of files to read
     input: String,
 
-    /// The path to the file to write
+    /// The path to the file to write, or the output directory when reading multiple files
     dest: PathBuf,
 }
 
@@ -55,11 +100,43 @@ fn parse_extent(src: &str) -> std::result::Result<Extent, ParseFloatError> {
     })
 }
 
-async fn transform<P: FeatureProcessor>(args: Cli, processor: &mut P) -> Result<()> {
+fn parse_delimiter(src: &str) -> std::result::Result<u8, String> {
+    match src {
+        "\\t" | "tab" => Ok(b'\t'),
+        _ if src.len() == 1 && src.is_ascii() => Ok(src.as_bytes()[0]),
+        _ => Err("delimiter must be a single ASCII character".to_string()),
+    }
+}
+
+async fn transform<P: FeatureProcessor>(
+    args: Cli,
+    processor: &mut P,
+    bytes_read: Arc<AtomicU64>,
+) -> Result<()> {
     let path_in = Path::new(&args.input);
     if path_in.starts_with("http:") || path_in.starts_with("https:") {
-        if path_in.extension().and_then(OsStr::to_str) != Some("fgb") {
-            panic!("Remote access is only supported for .fgb input")
+        let bbox = args
+            .extent
+            .map(|bbox| (bbox.minx, bbox.miny, bbox.maxx, bbox.maxy));
+        match path_in.extension().and_then(OsStr::to_str) {
+            Some("fgb") => {}
+            Some("parquet") | Some("geoparquet") => {
+                return remote::process_geoparquet(&args.input, bbox, processor).await;
+            }
+            Some("json") | Some("geojson") => {
+                let data = remote::get(&args.input).await?;
+                return GeozeroDatasource::process(&mut GeoJsonReader(data.as_ref()), processor);
+            }
+            Some("jsonl") | Some("geojsonl") | Some("geojsons") => {
+                let data = remote::get(&args.input).await?;
+                return GeozeroDatasource::process(
+                    &mut GeoJsonLineReader::new(data.as_ref()),
+                    processor,
+                );
+            }
+            _ => panic!(
+                "Remote access is only supported for FlatGeobuf, GeoParquet and GeoJSON input"
+            ),
         }
         let ds = HttpFgbReader::open(&args.input)
             .await
@@ -73,19 +150,27 @@ async fn transform<P: FeatureProcessor>(args: Cli, processor: &mut P) -> Result<
         };
         ds.process_features(processor).await
     } else {
-        let mut filein = BufReader::new(File::open(path_in)?);
+        let mut filein = BufReader::new(ProgressReader::with_counter(
+            File::open(path_in)?,
+            bytes_read,
+        ));
         match path_in.extension().and_then(OsStr::to_str) {
             Some("csv") => {
                 let geometry_column_name = args
                     .csv_geometry_column
                     .expect("must specify --csv-geometry-column=<column name> when parsing CSV");
                 let mut ds = CsvReader::new(&geometry_column_name, &mut filein);
+                if let Some(delimiter) = args.csv_delimiter {
+                    ds.set_delimiter(delimiter);
+                }
+                ds.set_has_headers(!args.csv_no_header);
                 GeozeroDatasource::process(&mut ds, processor)
             }
+            Some("dxf") => GeozeroDatasource::process(&mut DxfReader::new(filein), processor),
             Some("json") | Some("geojson") => {
                 GeozeroDatasource::process(&mut GeoJsonReader(filein), processor)
             }
-            Some("jsonl") | Some("geojsonl") => {
+            Some("jsonl") | Some("geojsonl") | Some("geojsons") => {
                 GeozeroDatasource::process(&mut GeoJsonLineReader::new(filein), processor)
             }
             Some("fgb") => {
@@ -98,35 +183,214 @@ async fn transform<P: FeatureProcessor>(args: Cli, processor: &mut P) -> Result<
                 };
                 ds.process_features(processor)
             }
+            Some("parquet") | Some("geoparquet") => {
+                let mut ds = GeoParquetReader::new(File::open(path_in)?);
+                if let Some(bbox) = &args.extent {
+                    ds = ds.select_bbox(bbox.minx, bbox.miny, bbox.maxx, bbox.maxy);
+                }
+                GeozeroDatasource::process(&mut ds, processor)
+            }
+            Some("arrow") | Some("feather") => {
+                let reader = arrow_ipc::reader::FileReader::try_new(File::open(path_in)?, None)?;
+                let geo = match reader.schema().metadata().get("geo") {
+                    Some(geo) => GeoParquetMetadata::from_json(geo)?,
+                    None => GeoParquetMetadata::wkb("geometry"),
+                };
+                process_batches(reader, &geo, processor)
+            }
             Some("wkt") => GeozeroDatasource::process(&mut WktReader(&mut filein), processor),
             _ => panic!("Unknown input file extension"),
         }
     }
 }
 
-async fn process(args: Cli) -> Result<()> {
+async fn process(mut args: Cli) -> Result<()> {
+    if let (Some(tile), None) = (args.tile, args.extent) {
+        // Read only features within tile
+        let (minx, miny, maxx, maxy) = tile.wgs84_bounds();
+        args.extent = Some(Extent {
+            minx,
+            miny,
+            maxx,
+            maxy,
+        });
+    }
     let mut fout = BufWriter::new(File::create(&args.dest)?);
     match args.dest.extension().and_then(OsStr::to_str) {
-        Some("csv") => transform(args, &mut CsvWriter::new(&mut fout)).await?,
-        Some("wkt") => transform(args, &mut WktWriter::new(&mut fout)).await?,
+        Some("csv") => {
+            run(args, CsvWriter::new(&mut fout)).await?;
+        }
+        Some("wkt") => {
+            run(args, WktWriter::new(&mut fout)).await?;
+        }
         Some("json") | Some("geojson") => {
-            transform(args, &mut GeoJsonWriter::new(&mut fout)).await?
+            run(args, GeoJsonWriter::new(&mut fout)).await?;
+        }
+        Some("jsonl") | Some("geojsonl") => {
+            run(args, GeoJsonLineWriter::new(&mut fout)).await?;
+        }
+        Some("geojsons") => {
+            run(args, GeoJsonLineWriter::new_text_sequence(&mut fout)).await?;
         }
         Some("fgb") => {
-            let mut fgb =
+            let fgb =
                 FgbWriter::create("fgb", GeometryType::Unknown).map_err(fgb_to_geozero_err)?;
-            transform(args, &mut fgb).await?;
+            let fgb = run(args, fgb).await?;
             fgb.write(&mut fout).map_err(fgb_to_geozero_err)?;
         }
+        Some("arrow") | Some("feather") => {
+            let writer = run(args, GeoArrowWriter::new()).await?;
+            let batch = writer.finish()?;
+            let mut ipc = arrow_ipc::writer::FileWriter::try_new(&mut fout, &batch.schema())?;
+            ipc.write(&batch)?;
+            ipc.finish()?;
+        }
         Some("svg") => {
             let mut processor = SvgWriter::new(&mut fout, true);
             set_dimensions(&mut processor, args.extent);
-            transform(args, &mut processor).await?;
+            run(args, processor).await?;
+        }
+        Some("mvt") | Some("pbf") => {
+            let bounds = match (args.tile, args.extent) {
+                (Some(tile), _) => tile.mercator_bounds(),
+                (None, Some(extent)) => {
+                    let (minx, miny) = lonlat_to_mercator(extent.minx, extent.miny);
+                    let (maxx, maxy) = lonlat_to_mercator(extent.maxx, extent.maxy);
+                    (minx, miny, maxx, maxy)
+                }
+                (None, None) => panic!("MVT output requires --tile or --extent"),
+            };
+            let layer_name = Path::new(&args.input)
+                .file_stem()
+                .and_then(OsStr::to_str)
+                .unwrap_or("layer")
+                .to_string();
+            let writer = MvtLayerWriter::new(&layer_name, args.mvt_extent, bounds);
+            let writer = run(args, writer).await?;
+            let tile = Tile {
+                layers: vec![writer.into_layer()],
+            };
+            fout.write_all(&tile.encode_to_vec())?;
         }
         _ => panic!("Unknown output file extension"),
     }
     Ok(())
 }
+
+/// Transform input into `processor`, swapping coordinates and displaying progress if requested
+async fn run<P: FeatureProcessor>(args: Cli, processor: P) -> Result<P> {
+    if args.swap_xy {
+        run_with_progress(args, SwapXY::new(processor))
+            .await
+            .map(SwapXY::into_inner)
+    } else {
+        run_with_progress(args, processor).await
+    }
+}
+
+async fn run_with_progress<P: FeatureProcessor>(args: Cli, processor: P) -> Result<P> {
+    let bytes_read = Arc::new(AtomicU64::new(0));
+    let show_progress = args.progress && std::io::stderr().is_terminal();
+    let total_bytes = std::fs::metadata(&args.input).ok().map(|meta| meta.len());
+    let mut processor = ProgressProcessor::new(processor, |progress: &Progress| {
+        if show_progress {
+            print_progress(progress);
+        }
+    });
+    processor.set_bytes_read(bytes_read.clone(), total_bytes);
+    let result = transform(args, &mut processor, bytes_read).await;
+    if show_progress {
+        eprintln!();
+    }
+    result.map(|()| processor.into_inner())
+}
+
+fn print_progress(progress: &Progress) {
+    const WIDTH: usize = 40;
+    let features = progress.features;
+    if let Some(fraction) = progress.fraction() {
+        let done = (fraction * WIDTH as f64) as usize;
+        let percent = fraction * 100.0;
+        eprint!(
+            "\r[{}{}] {percent:3.0}% {features} features",
+            "=".repeat(done),
+            " ".repeat(WIDTH - done)
+        );
+    } else {
+        eprint!("\r{features} features");
+    }
+}
+
+/// Input files of a directory or glob pattern, `None` for a single input
+fn batch_inputs(input: &str) -> Option<Vec<PathBuf>> {
+    if input.starts_with("http:") || input.starts_with("https:") {
+        return None;
+    }
+    let pattern = if Path::new(input).is_dir() {
+        format!("{}/*", input.trim_end_matches('/'))
+    } else if input.contains(['*', '?', '[']) {
+        input.to_string()
+    } else {
+        return None;
+    };
+    let paths = glob::glob(&pattern)
+        .expect("Invalid glob pattern")
+        .filter_map(|path| path.ok())
+        .filter(|path| path.is_file())
+        .collect();
+    Some(paths)
+}
+
+/// Convert multiple input files into the output directory
+fn process_batch(args: Cli, inputs: Vec<PathBuf>) -> Result<()> {
+    let ext = args
+        .output_format
+        .clone()
+        .expect("must specify --output-format=<extension> when converting multiple files");
+    std::fs::create_dir_all(&args.dest)?;
+    let total = inputs.len();
+    let next = AtomicUsize::new(0);
+    let done = AtomicUsize::new(0);
+    let failed = AtomicUsize::new(0);
+    thread::scope(|scope| {
+        for _ in 0..args.jobs.max(1) {
+            scope.spawn(|| {
+                let rt = tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .expect("Error creating tokio runtime");
+                while let Some(input) = inputs.get(next.fetch_add(1, Ordering::SeqCst)) {
+                    let mut file_args = args.clone();
+                    file_args.input = input.to_string_lossy().to_string();
+                    file_args.progress = false;
+                    let stem = input.file_stem().unwrap_or_default().to_string_lossy();
+                    file_args.dest = args.dest.join(format!("{stem}.{ext}"));
+                    let dest = file_args.dest.clone();
+                    let result = rt.block_on(process(file_args));
+                    let count = done.fetch_add(1, Ordering::SeqCst) + 1;
+                    match result {
+                        Ok(()) => info!(
+                            "[{count}/{total}] {} -> {}",
+                            input.display(),
+                            dest.display()
+                        ),
+                        Err(e) => {
+                            failed.fetch_add(1, Ordering::SeqCst);
+                            error!("[{count}/{total}] {} failed: {e}", input.display());
+                        }
+                    }
+                }
+            });
+        }
+    });
+    match failed.into_inner() {
+        0 => Ok(()),
+        failed => Err(GeozeroError::Dataset(format!(
+            "{failed} of {total} conversions failed"
+        ))),
+    }
+}
+
 fn set_dimensions(processor: &mut SvgWriter<&mut BufWriter<File>>, extent: Option<Extent>) {
     if let Some(extent) = extent {
         processor.set_dimensions(extent.minx, extent.miny, extent.maxx, extent.maxy, 800, 600);
@@ -138,23 +402,9 @@ fn set_dimensions(processor: &mut SvgWriter<&mut BufWriter<File>>, extent: Optio
 
 fn fgb_to_geozero_err(fgb_err: flatgeobuf::Error) -> GeozeroError {
     match fgb_err {
-        flatgeobuf::Error::MissingMagicBytes => {
-            GeozeroError::Dataset("Malformed FGB - missing Magic Bytes".to_string())
-        }
-        flatgeobuf::Error::NoIndex => GeozeroError::Dataset(
-            "No Index: Index operations are not supported for this FGB".to_string(),
-        ),
         flatgeobuf::Error::HttpClient(e) => GeozeroError::HttpError(e.to_string()),
-        flatgeobuf::Error::IllegalHeaderSize(size) => {
-            GeozeroError::Dataset(format!("Malformed FGB - Illegal header size: {size}"))
-        }
-        flatgeobuf::Error::InvalidFlatbuffer(e) => {
-            GeozeroError::Dataset(format!("Invalid Flatbuffer: {e}"))
-        }
         flatgeobuf::Error::IO(io) => GeozeroError::IoError(io),
-        flatgeobuf::Error::UnsupportedGeometryType(error_message) => {
-            GeozeroError::Dataset(error_message)
-        }
+        other => GeozeroError::External(Box::new(other)),
     }
 }
 
@@ -165,7 +415,10 @@ async fn main() {
 
     let args = Cli::parse();
 
-    let result = process(args).await;
+    let result = match batch_inputs(&args.input) {
+        Some(inputs) => tokio::task::block_in_place(|| process_batch(args, inputs)),
+        None => process(args).await,
+    };
 
     if let Err(msg) = result {
         println!("Processing failed: {msg}");
@@ -0,0 +1,248 @@
+//! MVT tile output.
+use geo::{
+    BooleanOps, Coord, Geometry, GeometryCollection, MapCoords, MultiLineString, MultiPoint,
+    MultiPolygon, Rect,
+};
+use geozero::error::Result;
+use geozero::geo_types::GeoWriter;
+use geozero::mvt::{tile, MvtLayerBuilder};
+use geozero::{
+    ColumnDef, ColumnValue, CoordDimensions, FeatureId, FeatureProcessor, GeomProcessor,
+    GeozeroGeometry, PropertyProcessor,
+};
+use std::f64::consts::PI;
+use std::num::ParseIntError;
+
+/// Web Mercator (EPSG:3857) earth radius
+const EARTH_RADIUS: f64 = 6378137.0;
+
+/// Tile buffer in tile coordinate units, for clipping geometries
+const TILE_BUFFER: f64 = 64.0;
+
+/// Tile address in the Web Mercator tiling scheme
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TileId {
+    pub z: u8,
+    pub x: u32,
+    pub y: u32,
+}
+
+pub fn parse_tile(src: &str) -> std::result::Result<TileId, ParseIntError> {
+    let parts: Vec<&str> = src.split('/').collect();
+    if parts.len() != 3 {
+        // Produce a ParseIntError for a malformed tile address
+        "".parse::<u32>()?;
+    }
+    Ok(TileId {
+        z: parts[0].parse()?,
+        x: parts[1].parse()?,
+        y: parts[2].parse()?,
+    })
+}
+
+impl TileId {
+    /// Tile bounds (minx, miny, maxx, maxy) in WGS84 coordinates
+    pub fn wgs84_bounds(&self) -> (f64, f64, f64, f64) {
+        let n = 2f64.powi(self.z as i32);
+        let lon = |x: f64| x / n * 360.0 - 180.0;
+        let lat = |y: f64| (PI * (1.0 - 2.0 * y / n)).sinh().atan().to_degrees();
+        (
+            lon(self.x as f64),
+            lat(self.y as f64 + 1.0),
+            lon(self.x as f64 + 1.0),
+            lat(self.y as f64),
+        )
+    }
+    /// Tile bounds (minx, miny, maxx, maxy) in Web Mercator coordinates
+    pub fn mercator_bounds(&self) -> (f64, f64, f64, f64) {
+        let size = 2.0 * PI * EARTH_RADIUS / 2f64.powi(self.z as i32);
+        let origin = PI * EARTH_RADIUS;
+        (
+            -origin + self.x as f64 * size,
+            origin - (self.y as f64 + 1.0) * size,
+            -origin + (self.x as f64 + 1.0) * size,
+            origin - self.y as f64 * size,
+        )
+    }
+}
+
+/// Project WGS84 coordinates to Web Mercator
+pub fn lonlat_to_mercator(lon: f64, lat: f64) -> (f64, f64) {
+    let lat = lat.clamp(-85.051_128_78, 85.051_128_78);
+    let x = EARTH_RADIUS * lon.to_radians();
+    let y = EARTH_RADIUS * (PI / 4.0 + lat.to_radians() / 2.0).tan().ln();
+    (x, y)
+}
+
+/// Writes features with WGS84 geometries into an MVT layer.
+///
+/// Geometries are projected to Web Mercator, clipped to the buffered tile bounds and
+/// encoded with [MvtLayerBuilder].
+pub struct MvtLayerWriter {
+    layer: MvtLayerBuilder,
+    clip_rect: Rect,
+    clip: MultiPolygon,
+    geom: GeoWriter,
+}
+
+impl MvtLayerWriter {
+    /// Create layer writer for tile `bounds` (minx, miny, maxx, maxy) in Web Mercator coordinates.
+    pub fn new(name: &str, extent: u32, bounds: (f64, f64, f64, f64)) -> Self {
+        let (minx, miny, maxx, maxy) = bounds;
+        let buffer = (maxx - minx) * TILE_BUFFER / extent as f64;
+        let clip = Rect::new(
+            Coord {
+                x: minx - buffer,
+                y: miny - buffer,
+            },
+            Coord {
+                x: maxx + buffer,
+                y: maxy + buffer,
+            },
+        );
+        MvtLayerWriter {
+            layer: MvtLayerBuilder::new(name, extent, minx, miny, maxx, maxy),
+            clip_rect: clip,
+            clip: MultiPolygon::new(vec![clip.to_polygon()]),
+            geom: GeoWriter::new(),
+        }
+    }
+
+    /// Finished layer with features and tag tables
+    pub fn into_layer(self) -> tile::Layer {
+        self.layer.into_layer()
+    }
+
+    fn clip_geometry(&self, geom: Geometry) -> Option<Geometry> {
+        match geom {
+            Geometry::Point(p) => self.contains(p.0).then_some(Geometry::Point(p)),
+            Geometry::MultiPoint(mp) => {
+                let points: Vec<_> = mp.into_iter().filter(|p| self.contains(p.0)).collect();
+                (!points.is_empty()).then(|| Geometry::MultiPoint(MultiPoint::new(points)))
+            }
+            Geometry::Line(l) => self.clip_lines(MultiLineString::new(vec![l.into()])),
+            Geometry::LineString(ls) => self.clip_lines(MultiLineString::new(vec![ls])),
+            Geometry::MultiLineString(mls) => self.clip_lines(mls),
+            Geometry::Polygon(p) => self.clip_polygons(MultiPolygon::new(vec![p])),
+            Geometry::MultiPolygon(mp) => self.clip_polygons(mp),
+            Geometry::Rect(r) => self.clip_polygons(MultiPolygon::new(vec![r.to_polygon()])),
+            Geometry::Triangle(t) => self.clip_polygons(MultiPolygon::new(vec![t.to_polygon()])),
+            Geometry::GeometryCollection(gc) => {
+                let geoms: Vec<_> = gc
+                    .into_iter()
+                    .filter_map(|g| self.clip_geometry(g))
+                    .collect();
+                (!geoms.is_empty()).then(|| Geometry::GeometryCollection(GeometryCollection(geoms)))
+            }
+        }
+    }
+
+    fn contains(&self, c: Coord) -> bool {
+        let (min, max) = (self.clip_rect.min(), self.clip_rect.max());
+        c.x >= min.x && c.x <= max.x && c.y >= min.y && c.y <= max.y
+    }
+
+    fn clip_lines(&self, lines: MultiLineString) -> Option<Geometry> {
+        let clipped = self.clip.clip(&lines, false);
+        (!clipped.0.is_empty()).then_some(Geometry::MultiLineString(clipped))
+    }
+
+    fn clip_polygons(&self, polygons: MultiPolygon) -> Option<Geometry> {
+        let clipped = polygons.intersection(&self.clip);
+        (!clipped.0.is_empty()).then_some(Geometry::MultiPolygon(clipped))
+    }
+}
+
+impl GeomProcessor for MvtLayerWriter {
+    fn dimensions(&self) -> CoordDimensions {
+        self.geom.dimensions()
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.geom.xy(x, y, idx)
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.geom.empty_point(idx)
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.geom.point_begin(idx)
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.geom.point_end(idx)
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom.multipoint_begin(size, idx)
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.geom.multipoint_end(idx)
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.geom.linestring_begin(tagged, size, idx)
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.geom.linestring_end(tagged, idx)
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom.multilinestring_begin(size, idx)
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.geom.multilinestring_end(idx)
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.geom.polygon_begin(tagged, size, idx)
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.geom.polygon_end(tagged, idx)
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom.multipolygon_begin(size, idx)
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.geom.multipolygon_end(idx)
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom.geometrycollection_begin(size, idx)
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.geom.geometrycollection_end(idx)
+    }
+}
+
+impl PropertyProcessor for MvtLayerWriter {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        self.layer.property(idx, name, value)
+    }
+    fn column_property(
+        &mut self,
+        idx: usize,
+        column: usize,
+        name: &str,
+        value: &ColumnValue,
+    ) -> Result<bool> {
+        self.layer.column_property(idx, column, name, value)
+    }
+}
+
+impl FeatureProcessor for MvtLayerWriter {
+    fn schema_begin(&mut self, columns: &[ColumnDef]) -> Result<()> {
+        self.layer.schema_begin(columns)
+    }
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        self.layer.feature_begin(idx)
+    }
+    fn feature_id(&mut self, id: &FeatureId) -> Result<()> {
+        self.layer.feature_id(id)
+    }
+    fn feature_end(&mut self, idx: u64) -> Result<()> {
+        // Features without geometry within the tile are skipped by the layer builder
+        if let Some(geom) = self.geom.take_geometry() {
+            let geom = geom.map_coords(|Coord { x, y }| {
+                let (x, y) = lonlat_to_mercator(x, y);
+                Coord { x, y }
+            });
+            if let Some(geom) = self.clip_geometry(geom) {
+                geom.process_geom(&mut self.layer)?;
+            }
+        }
+        self.layer.feature_end(idx)
+    }
+}
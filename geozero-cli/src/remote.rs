@@ -0,0 +1,26 @@
+//! Reading remote files over HTTP(S).
+use geozero::error::Result;
+use geozero::geoparquet::{AsyncGeoParquetReader, Bbox};
+use geozero::range_client::{RangeClient, ReqwestRangeClient};
+use geozero::FeatureProcessor;
+
+/// Download the whole object
+pub async fn get(url: &str) -> Result<Vec<u8>> {
+    let data = ReqwestRangeClient::new(url).get_all().await?;
+    Ok(data.to_vec())
+}
+
+/// Read a remote GeoParquet file with range requests.
+///
+/// Only the row groups intersecting `bbox` are requested.
+pub async fn process_geoparquet<P: FeatureProcessor>(
+    url: &str,
+    bbox: Option<Bbox>,
+    processor: &mut P,
+) -> Result<()> {
+    let mut reader = AsyncGeoParquetReader::open(ReqwestRangeClient::new(url)).await?;
+    if let Some((minx, miny, maxx, maxy)) = bbox {
+        reader = reader.select_bbox(minx, miny, maxx, maxy);
+    }
+    reader.process(processor).await
+}
@@ -0,0 +1,298 @@
+//! Injection of constant and computed feature properties.
+use crate::bounds::GeomStats;
+use crate::error::Result;
+use crate::wrap::{delegate_coordinates, delegate_geometry_types};
+use crate::{
+    ColumnDef, ColumnType, ColumnValue, ColumnValueOwned, CoordDimensions, Crs, FeatureId,
+    FeatureProcessor, GeomProcessor, PropertyProcessor,
+};
+
+/// Value of a property added by [AddProperties].
+#[derive(Clone, PartialEq, Debug)]
+pub enum AddedValue {
+    /// Same value for all features
+    Constant(ColumnValueOwned),
+    /// Sequential id as `ULong`, starting with the given value for the first feature
+    Sequence(u64),
+    /// Planar area of the feature geometry, see [GeomStats::area]
+    Area,
+    /// Planar length of the feature geometry, see [GeomStats::length]
+    Length,
+    /// X coordinate of the centroid, see [GeomStats::centroid]
+    CentroidX,
+    /// Y coordinate of the centroid, see [GeomStats::centroid]
+    CentroidY,
+}
+
+impl AddedValue {
+    fn column_type(&self) -> ColumnType {
+        match self {
+            AddedValue::Constant(value) => value.as_column_value().column_type(),
+            AddedValue::Sequence(_) => ColumnType::ULong,
+            _ => ColumnType::Double,
+        }
+    }
+}
+
+/// Wraps another [FeatureProcessor], adding constant and computed properties to each feature.
+///
+/// Geometry derived values are computed from the streamed coordinates. Therefore the
+/// properties of a feature are buffered and forwarded after its geometry, together with the
+/// added properties. Features without coordinates get no area, length or centroid properties.
+/// Added properties replace input properties with the same name.
+///
+/// # Usage example
+///
+/// ```
+/// # #[cfg(feature = "with-geojson")]
+/// # {
+/// use geozero::geojson::{GeoJson, GeoJsonWriter};
+/// use geozero::{AddProperties, AddedValue, ColumnValueOwned, GeozeroDatasource};
+///
+/// let mut geojson = GeoJson(r#"{"type": "Feature", "properties": {"name": "field"}, "geometry": {"type": "Polygon", "coordinates": [[[0,0],[2,0],[2,2],[0,2],[0,0]]]}}"#);
+/// let mut out = Vec::new();
+/// let mut processor = AddProperties::new(GeoJsonWriter::new(&mut out));
+/// processor.add("fid", AddedValue::Sequence(1));
+/// processor.add("area", AddedValue::Area);
+/// processor.add("source", AddedValue::Constant(ColumnValueOwned::String("survey".to_string())));
+/// geojson.process(&mut processor).unwrap();
+/// # }
+/// ```
+pub struct AddProperties<P> {
+    inner: P,
+    added: Vec<(String, AddedValue)>,
+    /// Schema columns of the added properties, if a schema is declared
+    added_columns: Option<Vec<usize>>,
+    /// Number of processed features
+    count: u64,
+    stats: GeomStats,
+    /// Buffered input properties with their schema column
+    properties: Vec<(String, Option<usize>, ColumnValueOwned)>,
+}
+
+impl<P> AddProperties<P> {
+    pub fn new(inner: P) -> Self {
+        AddProperties {
+            inner,
+            added: Vec::new(),
+            added_columns: None,
+            count: 0,
+            stats: GeomStats::new(),
+            properties: Vec::new(),
+        }
+    }
+    /// Add property `name` to each feature
+    pub fn add(&mut self, name: &str, value: AddedValue) {
+        self.added.retain(|(added, _)| added != name);
+        self.added.push((name.to_string(), value));
+    }
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+
+    fn added_value(&self, value: &AddedValue) -> Option<ColumnValueOwned> {
+        let geometry = || Some(&self.stats).filter(|stats| stats.vertex_count() > 0);
+        match value {
+            AddedValue::Constant(value) => Some(value.clone()),
+            AddedValue::Sequence(start) => Some(ColumnValueOwned::ULong(start + self.count)),
+            AddedValue::Area => Some(ColumnValueOwned::Double(geometry()?.area())),
+            AddedValue::Length => Some(ColumnValueOwned::Double(geometry()?.length())),
+            AddedValue::CentroidX => Some(ColumnValueOwned::Double(geometry()?.centroid()?.0)),
+            AddedValue::CentroidY => Some(ColumnValueOwned::Double(geometry()?.centroid()?.1)),
+        }
+    }
+}
+
+impl<P: PropertyProcessor> AddProperties<P> {
+    /// Forward buffered and added properties
+    fn process_properties(&mut self) -> Result<()> {
+        let properties = std::mem::take(&mut self.properties);
+        let mut idx = 0;
+        for (name, column, value) in &properties {
+            if self.added.iter().any(|(added, _)| added == name) {
+                continue;
+            }
+            let value = value.as_column_value();
+            let abort = match column {
+                Some(column) => self.inner.column_property(idx, *column, name, &value)?,
+                None => self.inner.property(idx, name, &value)?,
+            };
+            if abort {
+                return Ok(());
+            }
+            idx += 1;
+        }
+        for (i, (name, value)) in self.added.iter().enumerate() {
+            let Some(value) = self.added_value(value) else {
+                continue;
+            };
+            let value = value.as_column_value();
+            let abort = match &self.added_columns {
+                Some(columns) => self.inner.column_property(idx, columns[i], name, &value)?,
+                None => self.inner.property(idx, name, &value)?,
+            };
+            if abort {
+                return Ok(());
+            }
+            idx += 1;
+        }
+        self.properties = properties;
+        self.properties.clear();
+        Ok(())
+    }
+}
+
+// Geometry events are processed by the stats and the inner processor
+impl<P: GeomProcessor> GeomProcessor for AddProperties<P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.inner.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.inner.multi_dim()
+    }
+    delegate_coordinates!(stats, inner);
+    delegate_geometry_types!(stats, inner);
+}
+
+impl<P: PropertyProcessor> PropertyProcessor for AddProperties<P> {
+    fn property(&mut self, _idx: usize, name: &str, value: &ColumnValue<'_>) -> Result<bool> {
+        self.properties.push((name.to_string(), None, value.into()));
+        Ok(false)
+    }
+    fn column_property(
+        &mut self,
+        _idx: usize,
+        column: usize,
+        name: &str,
+        value: &ColumnValue<'_>,
+    ) -> Result<bool> {
+        self.properties
+            .push((name.to_string(), Some(column), value.into()));
+        Ok(false)
+    }
+}
+
+impl<P: FeatureProcessor> FeatureProcessor for AddProperties<P> {
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.inner.dataset_begin(name)
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        self.inner.dataset_end()
+    }
+    fn crs(&mut self, crs: &Crs) -> Result<()> {
+        self.inner.crs(crs)
+    }
+    fn schema_begin(&mut self, columns: &[ColumnDef]) -> Result<()> {
+        // Added columns replace input columns with the same name or are appended
+        let mut columns = columns.to_vec();
+        let mut added_columns = Vec::with_capacity(self.added.len());
+        for (name, value) in &self.added {
+            let column = ColumnDef::new(name, value.column_type());
+            match columns.iter().position(|col| col.name == *name) {
+                Some(idx) => {
+                    columns[idx] = column;
+                    added_columns.push(idx);
+                }
+                None => {
+                    added_columns.push(columns.len());
+                    columns.push(column);
+                }
+            }
+        }
+        self.added_columns = Some(added_columns);
+        self.inner.schema_begin(&columns)
+    }
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        self.stats = GeomStats::new();
+        self.properties.clear();
+        self.inner.feature_begin(idx)
+    }
+    fn feature_id(&mut self, id: &FeatureId) -> Result<()> {
+        self.inner.feature_id(id)
+    }
+    fn feature_end(&mut self, idx: u64) -> Result<()> {
+        self.inner.properties_begin()?;
+        self.process_properties()?;
+        self.inner.properties_end()?;
+        self.count += 1;
+        self.inner.feature_end(idx)
+    }
+    fn properties_begin(&mut self) -> Result<()> {
+        Ok(())
+    }
+    fn properties_end(&mut self) -> Result<()> {
+        Ok(())
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        self.inner.geometry_begin()
+    }
+    fn geometry_end(&mut self) -> Result<()> {
+        self.inner.geometry_end()
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::GeoJson;
+    use crate::{FeatureSpool, GeozeroDatasource};
+
+    #[test]
+    fn add_properties() -> Result<()> {
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"name": "square", "area": "large"}, "geometry": {"type": "Polygon", "coordinates": [[[0,0],[2,0],[2,2],[0,2],[0,0]]]}},
+            {"type": "Feature", "geometry": {"type": "LineString", "coordinates": [[0,0],[3,4]]}, "properties": {"name": "line"}},
+            {"type": "Feature", "properties": {"name": "none"}, "geometry": null}
+        ]}"#;
+        let mut out = Vec::new();
+        let mut processor = AddProperties::new(crate::geojson::GeoJsonWriter::new(&mut out));
+        processor.add("fid", AddedValue::Sequence(1));
+        processor.add(
+            "source",
+            AddedValue::Constant(ColumnValueOwned::String("survey".to_string())),
+        );
+        processor.add("area", AddedValue::Area);
+        processor.add("length", AddedValue::Length);
+        processor.add("cx", AddedValue::CentroidX);
+        GeoJson(geojson).process(&mut processor)?;
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        let properties = |idx: usize| json["features"][idx]["properties"].clone();
+        assert_eq!(
+            properties(0),
+            serde_json::json!({"name": "square", "fid": 1, "source": "survey", "area": 4, "length": 0, "cx": 1})
+        );
+        assert_eq!(
+            properties(1),
+            serde_json::json!({"name": "line", "fid": 2, "source": "survey", "area": 0, "length": 5, "cx": 1.5})
+        );
+        assert_eq!(
+            properties(2),
+            serde_json::json!({"name": "none", "fid": 3, "source": "survey"})
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn added_schema() -> Result<()> {
+        let mut processor = AddProperties::new(FeatureSpool::new());
+        processor.add("fid", AddedValue::Sequence(0));
+        processor.add("area", AddedValue::Area);
+        processor.schema_begin(&[
+            ColumnDef::new("area", ColumnType::String),
+            ColumnDef::new("name", ColumnType::String),
+        ])?;
+        assert_eq!(processor.added_columns, Some(vec![2, 0]));
+        let spool = processor.into_inner();
+        // The replaced input column is dropped
+        assert_eq!(
+            spool.schema().unwrap(),
+            &[
+                ColumnDef::new("area", ColumnType::Double),
+                ColumnDef::new("name", ColumnType::String),
+                ColumnDef::new("fid", ColumnType::ULong)
+            ]
+        );
+        Ok(())
+    }
+}
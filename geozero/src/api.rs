@@ -4,10 +4,12 @@
 //! The current feature can be processed with `FeatureAccess` processing API methods.
 //! Some datasources process features during consumption (e.g. reading from file).
 
+use crate::bounds::GeomStats;
+use crate::crs::Crs;
 use crate::error::{GeozeroError, Result};
 use crate::feature_processor::FeatureProcessor;
 use crate::property_processor::{
-    PropertyProcessor, PropertyReadType, PropertyReader, PropertyReaderIdx,
+    ColumnValueOwned, PropertyProcessor, PropertyReadType, PropertyReader, PropertyReaderIdx,
 };
 use crate::{CoordDimensions, GeomProcessor};
 use std::collections::HashMap;
@@ -26,6 +28,15 @@ pub trait GeozeroGeometry {
     fn srid(&self) -> Option<i32> {
         None
     }
+    /// 2D bounding box `[minx, miny, maxx, maxy]`. `None` for empty geometries.
+    fn bounds(&self) -> Result<Option<[f64; 4]>>
+    where
+        Self: Sized,
+    {
+        let mut stats = GeomStats::new();
+        self.process_geom(&mut stats)?;
+        Ok(stats.bounds().map(|b| [b.min_x, b.min_y, b.max_x, b.max_y]))
+    }
 }
 
 /// Datasource feature consumer trait.
@@ -37,6 +48,25 @@ pub trait GeozeroDatasource {
         let mut geom_processor = DatasourceGeomProcessor(processor);
         self.process(&mut geom_processor)
     }
+    /// Consume and process all selected features, continuing after errors in single features.
+    ///
+    /// Errors of a feature are passed to `on_error` together with the feature index, and
+    /// processing continues with the next feature. Returning an error from `on_error` aborts
+    /// processing. The processor never receives events of a failed feature, errors of the
+    /// processor itself abort processing. [GeozeroError::Cancelled] is never passed to `on_error` and always aborts processing.
+    ///
+    /// Datasources without support for recovery abort at the first error like [GeozeroDatasource::process].
+    fn process_with_recovery<P, F>(&mut self, processor: &mut P, _on_error: F) -> Result<()>
+    where
+        P: FeatureProcessor,
+        F: FnMut(u64, GeozeroError) -> Result<()>,
+    {
+        self.process(processor)
+    }
+    /// Coordinate reference system of the dataset, if known before processing.
+    fn dataset_crs(&self) -> Option<Crs> {
+        None
+    }
 }
 
 /// Feature processing API
@@ -91,6 +121,18 @@ pub trait FeatureProperties {
         let _ = self.process_properties(&mut properties)?;
         Ok(properties)
     }
+    /// Return all properties with owned values in a `HashMap`
+    fn properties_map(&self) -> Result<HashMap<String, ColumnValueOwned>> {
+        let mut properties = HashMap::new();
+        let _ = self.process_properties(&mut properties)?;
+        Ok(properties)
+    }
+    /// Return all properties as JSON object
+    fn properties_json(&self) -> Result<serde_json::Value> {
+        let mut properties = serde_json::Map::new();
+        let _ = self.process_properties(&mut properties)?;
+        Ok(serde_json::Value::Object(properties))
+    }
 }
 
 // Newtype for GeomProcessor impl for adding no-op PropertyProcessor/FeatureProcessor impl
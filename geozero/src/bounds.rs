@@ -0,0 +1,482 @@
+//! Geometry bounds and statistics.
+use crate::error::Result;
+use crate::{CoordDimensions, FeatureProcessor, GeomProcessor, GeozeroGeometry, PropertyProcessor};
+
+/// Axis-aligned 2D bounding box.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Bounds {
+    pub min_x: f64,
+    pub min_y: f64,
+    pub max_x: f64,
+    pub max_y: f64,
+}
+
+impl Bounds {
+    /// Bounds of a single point
+    pub fn from_point(x: f64, y: f64) -> Self {
+        Bounds {
+            min_x: x,
+            min_y: y,
+            max_x: x,
+            max_y: y,
+        }
+    }
+
+    /// Extend bounds to include the given point
+    pub fn extend(&mut self, x: f64, y: f64) {
+        self.min_x = self.min_x.min(x);
+        self.min_y = self.min_y.min(y);
+        self.max_x = self.max_x.max(x);
+        self.max_y = self.max_y.max(y);
+    }
+
+    /// Extend bounds to include `other`
+    pub fn merge(&mut self, other: &Bounds) {
+        self.extend(other.min_x, other.min_y);
+        self.extend(other.max_x, other.max_y);
+    }
+
+    /// Bounds intersect or touch `other`
+    pub fn intersects(&self, other: &Bounds) -> bool {
+        self.min_x <= other.max_x
+            && self.max_x >= other.min_x
+            && self.min_y <= other.max_y
+            && self.max_y >= other.min_y
+    }
+
+    pub fn width(&self) -> f64 {
+        self.max_x - self.min_x
+    }
+
+    pub fn height(&self) -> f64 {
+        self.max_y - self.min_y
+    }
+}
+
+/// Geometry statistics computed in a single pass.
+///
+/// Collects statistics of all processed geometries. Can be used as [FeatureProcessor]
+/// to get statistics of a whole dataset.
+#[derive(Clone, Default, Debug)]
+pub struct GeomStats {
+    bounds: Option<Bounds>,
+    z_range: Option<(f64, f64)>,
+    vertex_count: usize,
+    ring_count: usize,
+    sum_x: f64,
+    sum_y: f64,
+    area: f64,
+    /// First moments of the area
+    area_moment: (f64, f64),
+    length: f64,
+    /// First moments of the length
+    length_moment: (f64, f64),
+    /// `true` for each open geometry which is a surface
+    surface_stack: Vec<bool>,
+    /// Number of rings of the current surface
+    surface_rings: usize,
+    /// Number of open curves
+    curve_depth: usize,
+    /// Previous vertex of the current curve, if not part of a ring
+    line_prev: Option<(f64, f64)>,
+    ring: Option<Ring>,
+}
+
+/// Current surface ring
+#[derive(Clone, Debug)]
+struct Ring {
+    /// Nesting level of the ring
+    depth: usize,
+    exterior: bool,
+    first: Option<(f64, f64)>,
+    prev: Option<(f64, f64)>,
+    /// Twice the signed area
+    sum: f64,
+    /// Six times the signed first moments
+    moment: (f64, f64),
+}
+
+impl Ring {
+    fn add_segment(&mut self, (px, py): (f64, f64), (x, y): (f64, f64)) {
+        let cross = px * y - x * py;
+        self.sum += cross;
+        self.moment.0 += (px + x) * cross;
+        self.moment.1 += (py + y) * cross;
+    }
+}
+
+impl GeomStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 2D bounding box. `None` for empty geometries.
+    pub fn bounds(&self) -> Option<Bounds> {
+        self.bounds
+    }
+
+    /// Minimal and maximal Z value. `None` for 2D geometries.
+    pub fn z_range(&self) -> Option<(f64, f64)> {
+        self.z_range
+    }
+
+    /// Number of processed coordinates
+    pub fn vertex_count(&self) -> usize {
+        self.vertex_count
+    }
+
+    /// Number of polygon rings, including interior rings
+    pub fn ring_count(&self) -> usize {
+        self.ring_count
+    }
+
+    /// Planar area of surfaces, with holes subtracted.
+    ///
+    /// Curved segments are approximated by straight lines between their vertices.
+    pub fn area(&self) -> f64 {
+        self.area
+    }
+
+    /// Planar length of curves which are not surface rings.
+    ///
+    /// Curved segments are approximated by straight lines between their vertices.
+    pub fn length(&self) -> f64 {
+        self.length
+    }
+
+    /// Centroid of the surfaces weighted by area, of the curves weighted by length if there
+    /// are no surfaces, or else the average of all vertices. `None` for empty geometries.
+    ///
+    /// Curved segments are approximated by straight lines between their vertices.
+    pub fn centroid(&self) -> Option<(f64, f64)> {
+        if self.area > 0.0 {
+            let (mx, my) = self.area_moment;
+            Some((mx / self.area, my / self.area))
+        } else if self.length > 0.0 {
+            let (mx, my) = self.length_moment;
+            Some((mx / self.length, my / self.length))
+        } else if self.vertex_count > 0 {
+            let n = self.vertex_count as f64;
+            Some((self.sum_x / n, self.sum_y / n))
+        } else {
+            None
+        }
+    }
+
+    fn add_xy(&mut self, x: f64, y: f64) {
+        match &mut self.bounds {
+            Some(bounds) => bounds.extend(x, y),
+            None => self.bounds = Some(Bounds::from_point(x, y)),
+        }
+        self.vertex_count += 1;
+        self.sum_x += x;
+        self.sum_y += y;
+        if let Some(ring) = &mut self.ring {
+            match ring.prev {
+                Some(prev) => ring.add_segment(prev, (x, y)),
+                None => ring.first = Some((x, y)),
+            }
+            ring.prev = Some((x, y));
+        } else if self.curve_depth > 0 {
+            if let Some((px, py)) = self.line_prev {
+                let length = (x - px).hypot(y - py);
+                self.length += length;
+                self.length_moment.0 += (px + x) / 2.0 * length;
+                self.length_moment.1 += (py + y) / 2.0 * length;
+            }
+            self.line_prev = Some((x, y));
+        }
+    }
+
+    fn add_z(&mut self, z: f64) {
+        let (min_z, max_z) = self.z_range.get_or_insert((z, z));
+        *min_z = min_z.min(z);
+        *max_z = max_z.max(z);
+    }
+
+    fn begin(&mut self, surface: bool) {
+        if surface {
+            self.surface_rings = 0;
+        }
+        self.surface_stack.push(surface);
+    }
+
+    /// Begin of a curve, which is a ring if it is a direct member of a surface
+    fn begin_curve(&mut self) {
+        if self.surface_stack.last() == Some(&true) {
+            self.ring_count += 1;
+            self.ring = Some(Ring {
+                depth: self.surface_stack.len() + 1,
+                exterior: self.surface_rings == 0,
+                first: None,
+                prev: None,
+                sum: 0.0,
+                moment: (0.0, 0.0),
+            });
+            self.surface_rings += 1;
+        }
+        self.curve_depth += 1;
+        self.line_prev = None;
+        self.begin(false);
+    }
+
+    fn end_curve(&mut self) {
+        let depth = self.surface_stack.len();
+        if self.ring.as_ref().is_some_and(|ring| ring.depth == depth) {
+            if let Some(mut ring) = self.ring.take() {
+                if let (Some(prev), Some(first)) = (ring.prev, ring.first) {
+                    ring.add_segment(prev, first);
+                }
+                // Holes are subtracted, regardless of the ring orientation
+                let sign = ring.sum.signum() * if ring.exterior { 1.0 } else { -1.0 };
+                self.area += sign * ring.sum / 2.0;
+                self.area_moment.0 += sign * ring.moment.0 / 6.0;
+                self.area_moment.1 += sign * ring.moment.1 / 6.0;
+            }
+        }
+        self.curve_depth -= 1;
+        self.line_prev = None;
+        self.end();
+    }
+
+    fn end(&mut self) {
+        self.surface_stack.pop();
+    }
+}
+
+impl GeomProcessor for GeomStats {
+    fn dimensions(&self) -> CoordDimensions {
+        CoordDimensions::xyz()
+    }
+    fn xy(&mut self, x: f64, y: f64, _idx: usize) -> Result<()> {
+        self.add_xy(x, y);
+        Ok(())
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        _m: Option<f64>,
+        _t: Option<f64>,
+        _tm: Option<u64>,
+        _idx: usize,
+    ) -> Result<()> {
+        self.add_xy(x, y);
+        if let Some(z) = z {
+            self.add_z(z);
+        }
+        Ok(())
+    }
+    fn empty_point(&mut self, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    fn point_begin(&mut self, _idx: usize) -> Result<()> {
+        self.begin(false);
+        Ok(())
+    }
+    fn point_end(&mut self, _idx: usize) -> Result<()> {
+        self.end();
+        Ok(())
+    }
+    fn multipoint_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(false);
+        Ok(())
+    }
+    fn multipoint_end(&mut self, _idx: usize) -> Result<()> {
+        self.end();
+        Ok(())
+    }
+    fn linestring_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        self.begin_curve();
+        Ok(())
+    }
+    fn linestring_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        self.end_curve();
+        Ok(())
+    }
+    fn multilinestring_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(false);
+        Ok(())
+    }
+    fn multilinestring_end(&mut self, _idx: usize) -> Result<()> {
+        self.end();
+        Ok(())
+    }
+    fn polygon_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(true);
+        Ok(())
+    }
+    fn polygon_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        self.end();
+        Ok(())
+    }
+    fn multipolygon_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(false);
+        Ok(())
+    }
+    fn multipolygon_end(&mut self, _idx: usize) -> Result<()> {
+        self.end();
+        Ok(())
+    }
+    fn geometrycollection_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(false);
+        Ok(())
+    }
+    fn geometrycollection_end(&mut self, _idx: usize) -> Result<()> {
+        self.end();
+        Ok(())
+    }
+    fn circularstring_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin_curve();
+        Ok(())
+    }
+    fn circularstring_end(&mut self, _idx: usize) -> Result<()> {
+        self.end_curve();
+        Ok(())
+    }
+    fn compoundcurve_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin_curve();
+        Ok(())
+    }
+    fn compoundcurve_end(&mut self, _idx: usize) -> Result<()> {
+        self.end_curve();
+        Ok(())
+    }
+    fn curvepolygon_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(true);
+        Ok(())
+    }
+    fn curvepolygon_end(&mut self, _idx: usize) -> Result<()> {
+        self.end();
+        Ok(())
+    }
+    fn multicurve_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(false);
+        Ok(())
+    }
+    fn multicurve_end(&mut self, _idx: usize) -> Result<()> {
+        self.end();
+        Ok(())
+    }
+    fn multisurface_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(false);
+        Ok(())
+    }
+    fn multisurface_end(&mut self, _idx: usize) -> Result<()> {
+        self.end();
+        Ok(())
+    }
+    fn triangle_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(true);
+        Ok(())
+    }
+    fn triangle_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        self.end();
+        Ok(())
+    }
+    fn polyhedralsurface_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(false);
+        Ok(())
+    }
+    fn polyhedralsurface_end(&mut self, _idx: usize) -> Result<()> {
+        self.end();
+        Ok(())
+    }
+    fn tin_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(false);
+        Ok(())
+    }
+    fn tin_end(&mut self, _idx: usize) -> Result<()> {
+        self.end();
+        Ok(())
+    }
+}
+
+impl PropertyProcessor for GeomStats {}
+
+impl FeatureProcessor for GeomStats {}
+
+/// Compute geometry statistics.
+pub trait ToGeomStats {
+    /// Compute bounds, vertex count, ring count, area, length, centroid and Z range in a single pass.
+    fn geometry_stats(&self) -> Result<GeomStats>;
+}
+
+impl<T: GeozeroGeometry> ToGeomStats for T {
+    fn geometry_stats(&self) -> Result<GeomStats> {
+        let mut stats = GeomStats::new();
+        self.process_geom(&mut stats)?;
+        Ok(stats)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use super::*;
+    use crate::wkt::Wkt;
+
+    #[test]
+    fn polygon_stats() {
+        let stats = Wkt(
+            "MULTIPOLYGON(((0 0,4 0,4 4,0 4,0 0),(1 1,2 1,2 2,1 1)),((10 10,11 10,11 11,10 10)))",
+        )
+        .geometry_stats()
+        .unwrap();
+        assert_eq!(
+            stats.bounds(),
+            Some(Bounds {
+                min_x: 0.0,
+                min_y: 0.0,
+                max_x: 11.0,
+                max_y: 11.0
+            })
+        );
+        assert_eq!(stats.vertex_count(), 13);
+        assert_eq!(stats.ring_count(), 3);
+        assert_eq!(stats.z_range(), None);
+        assert_eq!(stats.area(), 16.0 - 0.5 + 0.5);
+        assert_eq!(stats.length(), 0.0);
+
+        // The closing vertex doesn't shift the centroid
+        let stats = Wkt("POLYGON((0 0,2 0,2 2,0 2,0 0))")
+            .geometry_stats()
+            .unwrap();
+        assert_eq!(stats.centroid(), Some((1.0, 1.0)));
+        let stats = Wkt("POLYGON((0 0,0 4,4 4,4 0,0 0),(0 0,2 0,2 4,0 4,0 0))")
+            .geometry_stats()
+            .unwrap();
+        assert_eq!(stats.centroid(), Some((3.0, 2.0)));
+    }
+
+    #[test]
+    fn linestring_z_stats() {
+        let stats = Wkt("MULTILINESTRING Z((0 0 5,2 0 -1),(2 2 3,0 2 0))")
+            .geometry_stats()
+            .unwrap();
+        assert_eq!(stats.ring_count(), 0);
+        assert_eq!(stats.vertex_count(), 4);
+        assert_eq!(stats.centroid(), Some((1.0, 1.0)));
+        assert_eq!(stats.z_range(), Some((-1.0, 5.0)));
+        assert_eq!(stats.length(), 4.0);
+        assert_eq!(stats.area(), 0.0);
+    }
+
+    #[test]
+    fn empty_stats() {
+        let stats = Wkt("GEOMETRYCOLLECTION EMPTY").geometry_stats().unwrap();
+        assert_eq!(stats.bounds(), None);
+        assert_eq!(stats.centroid(), None);
+        assert_eq!(stats.vertex_count(), 0);
+    }
+
+    #[test]
+    fn geometry_bounds() {
+        assert_eq!(
+            Wkt("LINESTRING(3 -1,0 4,2 2)").bounds().unwrap(),
+            Some([0.0, -1.0, 3.0, 4.0])
+        );
+        assert_eq!(Wkt("POINT EMPTY").bounds().unwrap(), None);
+    }
+}
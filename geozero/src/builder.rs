@@ -0,0 +1,680 @@
+//! Programmatic construction of geometries.
+//!
+//! [GeometryBuilder] assembles geometries with a type-state API, where the geometry structure
+//! is checked at compile time, or imperatively with runtime checks, when the structure is
+//! only known at runtime. The resulting [BuiltGeometry] can be processed by any
+//! [GeomProcessor] or converted with [ToWkt](crate::ToWkt), [ToWkb](crate::ToWkb), etc.
+//!
+//! # Usage example:
+//!
+//! ```
+//! # #[cfg(feature = "with-wkt")]
+//! # {
+//! use geozero::builder::GeometryBuilder;
+//! use geozero::ToWkt;
+//!
+//! let polygon = GeometryBuilder::polygon()
+//!     .ring()
+//!     .coord(0.0, 0.0)
+//!     .coord(1.0, 0.0)
+//!     .coord(1.0, 1.0)
+//!     .coord(0.0, 0.0)
+//!     .end()
+//!     .end();
+//! assert_eq!(polygon.to_wkt().unwrap(), "POLYGON((0 0,1 0,1 1,0 0))");
+//!
+//! let mut builder = GeometryBuilder::new();
+//! builder.begin_polygon().unwrap();
+//! builder.add_ring().unwrap();
+//! for (x, y) in [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 0.0)] {
+//!     builder.push_coord(x, y).unwrap();
+//! }
+//! let polygon = builder.finish().unwrap();
+//! assert_eq!(polygon.to_wkt().unwrap(), "POLYGON((0 0,1 0,1 1,0 0))");
+//! # }
+//! ```
+use crate::error::{GeozeroError, Result};
+use crate::{CoordDimensions, GeomProcessor, GeozeroGeometry};
+
+mod sealed {
+    /// Coordinate with optional z value
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    pub struct Coord {
+        pub x: f64,
+        pub y: f64,
+        pub z: Option<f64>,
+    }
+
+    /// Geometry tree
+    #[derive(Clone, PartialEq, Debug)]
+    pub enum Part {
+        Point(Coord),
+        LineString(Vec<Coord>),
+        Polygon(Vec<Vec<Coord>>),
+        MultiPoint(Vec<Coord>),
+        MultiLineString(Vec<Vec<Coord>>),
+        MultiPolygon(Vec<Vec<Vec<Coord>>>),
+        GeometryCollection(Vec<Part>),
+    }
+
+    /// Builder receiving a finished child geometry
+    pub trait Parent {
+        type Output;
+        fn add(self, part: Part) -> Self::Output;
+    }
+}
+
+use sealed::{Coord, Parent, Part};
+
+/// Geometry assembled by a [GeometryBuilder].
+#[derive(Clone, PartialEq, Debug)]
+pub struct BuiltGeometry(Part);
+
+impl BuiltGeometry {
+    /// Emit geometry events into `processor`
+    pub fn process_into<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
+        self.process_geom(processor)
+    }
+}
+
+/// Geometry builder.
+///
+/// The associated functions like [GeometryBuilder::polygon] start type-state builders.
+/// Each builder returns its parent builder on `end`, the outermost builder returns
+/// the [BuiltGeometry].
+///
+/// An instance created with [GeometryBuilder::new] builds a geometry imperatively with
+/// `begin_*`, `add_ring`, `push_coord` and `end` calls. Invalid call sequences return
+/// [GeozeroError::Geometry].
+#[derive(Default, Debug)]
+pub struct GeometryBuilder {
+    open: Vec<Open>,
+    built: Option<Part>,
+}
+
+/// Geometry under construction
+#[derive(Debug)]
+enum Open {
+    Point(Option<Coord>),
+    Part(Part),
+}
+
+impl GeometryBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn begin_point(&mut self) -> Result<()> {
+        self.begin(Open::Point(None))
+    }
+    pub fn begin_linestring(&mut self) -> Result<()> {
+        self.begin(Open::Part(Part::LineString(Vec::new())))
+    }
+    pub fn begin_polygon(&mut self) -> Result<()> {
+        self.begin(Open::Part(Part::Polygon(Vec::new())))
+    }
+    pub fn begin_multipoint(&mut self) -> Result<()> {
+        self.begin(Open::Part(Part::MultiPoint(Vec::new())))
+    }
+    pub fn begin_multilinestring(&mut self) -> Result<()> {
+        self.begin(Open::Part(Part::MultiLineString(Vec::new())))
+    }
+    pub fn begin_multipolygon(&mut self) -> Result<()> {
+        self.begin(Open::Part(Part::MultiPolygon(Vec::new())))
+    }
+    pub fn begin_geometrycollection(&mut self) -> Result<()> {
+        self.begin(Open::Part(Part::GeometryCollection(Vec::new())))
+    }
+
+    /// Start a new ring of the current polygon
+    pub fn add_ring(&mut self) -> Result<()> {
+        match self.open.last_mut() {
+            Some(Open::Part(Part::Polygon(rings))) => {
+                rings.push(Vec::new());
+                Ok(())
+            }
+            _ => Err(builder_error("add_ring outside of polygon")),
+        }
+    }
+
+    /// Add a coordinate to the current geometry
+    pub fn push_coord(&mut self, x: f64, y: f64) -> Result<()> {
+        self.push(Coord { x, y, z: None })
+    }
+    /// Add a coordinate with z value to the current geometry
+    pub fn push_coord_z(&mut self, x: f64, y: f64, z: f64) -> Result<()> {
+        self.push(Coord { x, y, z: Some(z) })
+    }
+
+    /// Finish the current geometry
+    pub fn end(&mut self) -> Result<()> {
+        let part = match self.open.pop() {
+            Some(Open::Point(Some(coord))) => Part::Point(coord),
+            Some(Open::Point(None)) => return Err(builder_error("point without coordinate")),
+            Some(Open::Part(part)) => part,
+            None => return Err(builder_error("end without open geometry")),
+        };
+        match self.open.last_mut() {
+            None => self.built = Some(part),
+            Some(Open::Part(Part::MultiLineString(lines))) => match part {
+                Part::LineString(coords) => lines.push(coords),
+                _ => return Err(builder_error("multilinestring member is not a linestring")),
+            },
+            Some(Open::Part(Part::MultiPolygon(polygons))) => match part {
+                Part::Polygon(rings) => polygons.push(rings),
+                _ => return Err(builder_error("multipolygon member is not a polygon")),
+            },
+            Some(Open::Part(Part::GeometryCollection(parts))) => parts.push(part),
+            Some(_) => return Err(builder_error("geometry can not contain other geometries")),
+        }
+        Ok(())
+    }
+
+    /// End all open geometries and return the built geometry
+    pub fn finish(mut self) -> Result<BuiltGeometry> {
+        while !self.open.is_empty() {
+            self.end()?;
+        }
+        self.built
+            .map(BuiltGeometry)
+            .ok_or_else(|| builder_error("no geometry built"))
+    }
+
+    fn begin(&mut self, geom: Open) -> Result<()> {
+        if self.built.is_some() {
+            return Err(builder_error("geometry already finished"));
+        }
+        match (self.open.last(), &geom) {
+            (None, _)
+            | (Some(Open::Part(Part::GeometryCollection(_))), _)
+            | (Some(Open::Part(Part::MultiLineString(_))), Open::Part(Part::LineString(_)))
+            | (Some(Open::Part(Part::MultiPolygon(_))), Open::Part(Part::Polygon(_))) => {
+                self.open.push(geom);
+                Ok(())
+            }
+            _ => Err(builder_error("invalid nested geometry")),
+        }
+    }
+
+    fn push(&mut self, coord: Coord) -> Result<()> {
+        match self.open.last_mut() {
+            Some(Open::Point(point @ None)) => *point = Some(coord),
+            Some(Open::Part(Part::LineString(coords) | Part::MultiPoint(coords))) => {
+                coords.push(coord)
+            }
+            Some(Open::Part(Part::Polygon(rings))) => match rings.last_mut() {
+                Some(ring) => ring.push(coord),
+                None => return Err(builder_error("polygon coordinate without ring")),
+            },
+            _ => return Err(builder_error("unexpected coordinate")),
+        }
+        Ok(())
+    }
+
+    pub fn point(x: f64, y: f64) -> BuiltGeometry {
+        BuiltGeometry(Part::Point(Coord { x, y, z: None }))
+    }
+    pub fn point_z(x: f64, y: f64, z: f64) -> BuiltGeometry {
+        BuiltGeometry(Part::Point(Coord { x, y, z: Some(z) }))
+    }
+    pub fn linestring() -> LineStringBuilder<Root> {
+        LineStringBuilder::new(Root)
+    }
+    pub fn polygon() -> PolygonBuilder<Root> {
+        PolygonBuilder::new(Root)
+    }
+    pub fn multipoint() -> MultiPointBuilder<Root> {
+        MultiPointBuilder {
+            parent: Root,
+            points: Vec::new(),
+        }
+    }
+    pub fn multilinestring() -> MultiLineStringBuilder<Root> {
+        MultiLineStringBuilder {
+            parent: Root,
+            linestrings: Vec::new(),
+        }
+    }
+    pub fn multipolygon() -> MultiPolygonBuilder<Root> {
+        MultiPolygonBuilder {
+            parent: Root,
+            polygons: Vec::new(),
+        }
+    }
+    pub fn geometrycollection() -> GeometryCollectionBuilder<Root> {
+        GeometryCollectionBuilder::new(Root)
+    }
+}
+
+/// Parent of the outermost builder, returning the [BuiltGeometry].
+pub struct Root;
+
+impl Parent for Root {
+    type Output = BuiltGeometry;
+    fn add(self, part: Part) -> BuiltGeometry {
+        BuiltGeometry(part)
+    }
+}
+
+/// Builder of a linestring or polygon ring.
+pub struct LineStringBuilder<P> {
+    parent: P,
+    coords: Vec<Coord>,
+}
+
+impl<P: Parent> LineStringBuilder<P> {
+    fn new(parent: P) -> Self {
+        LineStringBuilder {
+            parent,
+            coords: Vec::new(),
+        }
+    }
+    pub fn coord(mut self, x: f64, y: f64) -> Self {
+        self.coords.push(Coord { x, y, z: None });
+        self
+    }
+    pub fn coord_z(mut self, x: f64, y: f64, z: f64) -> Self {
+        self.coords.push(Coord { x, y, z: Some(z) });
+        self
+    }
+    /// Finish linestring and return to the parent builder
+    pub fn end(self) -> P::Output {
+        self.parent.add(Part::LineString(self.coords))
+    }
+}
+
+/// Builder of a polygon.
+pub struct PolygonBuilder<P> {
+    parent: P,
+    rings: Vec<Vec<Coord>>,
+}
+
+impl<P: Parent> PolygonBuilder<P> {
+    fn new(parent: P) -> Self {
+        PolygonBuilder {
+            parent,
+            rings: Vec::new(),
+        }
+    }
+    /// Begin exterior ring or hole
+    pub fn ring(self) -> LineStringBuilder<Self> {
+        LineStringBuilder::new(self)
+    }
+    /// Finish polygon and return to the parent builder
+    pub fn end(self) -> P::Output {
+        self.parent.add(Part::Polygon(self.rings))
+    }
+}
+
+impl<P> Parent for PolygonBuilder<P> {
+    type Output = Self;
+    fn add(mut self, part: Part) -> Self {
+        if let Part::LineString(ring) = part {
+            self.rings.push(ring);
+        }
+        self
+    }
+}
+
+/// Builder of a multipoint.
+pub struct MultiPointBuilder<P> {
+    parent: P,
+    points: Vec<Coord>,
+}
+
+impl<P: Parent> MultiPointBuilder<P> {
+    pub fn point(mut self, x: f64, y: f64) -> Self {
+        self.points.push(Coord { x, y, z: None });
+        self
+    }
+    pub fn point_z(mut self, x: f64, y: f64, z: f64) -> Self {
+        self.points.push(Coord { x, y, z: Some(z) });
+        self
+    }
+    /// Finish multipoint and return to the parent builder
+    pub fn end(self) -> P::Output {
+        self.parent.add(Part::MultiPoint(self.points))
+    }
+}
+
+/// Builder of a multilinestring.
+pub struct MultiLineStringBuilder<P> {
+    parent: P,
+    linestrings: Vec<Vec<Coord>>,
+}
+
+impl<P: Parent> MultiLineStringBuilder<P> {
+    pub fn linestring(self) -> LineStringBuilder<Self> {
+        LineStringBuilder::new(self)
+    }
+    /// Finish multilinestring and return to the parent builder
+    pub fn end(self) -> P::Output {
+        self.parent.add(Part::MultiLineString(self.linestrings))
+    }
+}
+
+impl<P> Parent for MultiLineStringBuilder<P> {
+    type Output = Self;
+    fn add(mut self, part: Part) -> Self {
+        if let Part::LineString(linestring) = part {
+            self.linestrings.push(linestring);
+        }
+        self
+    }
+}
+
+/// Builder of a multipolygon.
+pub struct MultiPolygonBuilder<P> {
+    parent: P,
+    polygons: Vec<Vec<Vec<Coord>>>,
+}
+
+impl<P: Parent> MultiPolygonBuilder<P> {
+    pub fn polygon(self) -> PolygonBuilder<Self> {
+        PolygonBuilder::new(self)
+    }
+    /// Finish multipolygon and return to the parent builder
+    pub fn end(self) -> P::Output {
+        self.parent.add(Part::MultiPolygon(self.polygons))
+    }
+}
+
+impl<P> Parent for MultiPolygonBuilder<P> {
+    type Output = Self;
+    fn add(mut self, part: Part) -> Self {
+        if let Part::Polygon(polygon) = part {
+            self.polygons.push(polygon);
+        }
+        self
+    }
+}
+
+/// Builder of a geometry collection.
+pub struct GeometryCollectionBuilder<P> {
+    parent: P,
+    geometries: Vec<Part>,
+}
+
+impl<P: Parent> GeometryCollectionBuilder<P> {
+    fn new(parent: P) -> Self {
+        GeometryCollectionBuilder {
+            parent,
+            geometries: Vec::new(),
+        }
+    }
+    pub fn point(mut self, x: f64, y: f64) -> Self {
+        self.geometries.push(Part::Point(Coord { x, y, z: None }));
+        self
+    }
+    pub fn point_z(mut self, x: f64, y: f64, z: f64) -> Self {
+        self.geometries
+            .push(Part::Point(Coord { x, y, z: Some(z) }));
+        self
+    }
+    pub fn linestring(self) -> LineStringBuilder<Self> {
+        LineStringBuilder::new(self)
+    }
+    pub fn polygon(self) -> PolygonBuilder<Self> {
+        PolygonBuilder::new(self)
+    }
+    pub fn multipoint(self) -> MultiPointBuilder<Self> {
+        MultiPointBuilder {
+            parent: self,
+            points: Vec::new(),
+        }
+    }
+    pub fn multilinestring(self) -> MultiLineStringBuilder<Self> {
+        MultiLineStringBuilder {
+            parent: self,
+            linestrings: Vec::new(),
+        }
+    }
+    pub fn multipolygon(self) -> MultiPolygonBuilder<Self> {
+        MultiPolygonBuilder {
+            parent: self,
+            polygons: Vec::new(),
+        }
+    }
+    pub fn geometrycollection(self) -> GeometryCollectionBuilder<Self> {
+        GeometryCollectionBuilder::new(self)
+    }
+    /// Add a built geometry
+    pub fn geometry(mut self, geometry: BuiltGeometry) -> Self {
+        self.geometries.push(geometry.0);
+        self
+    }
+    /// Finish geometry collection and return to the parent builder
+    pub fn end(self) -> P::Output {
+        self.parent.add(Part::GeometryCollection(self.geometries))
+    }
+}
+
+impl<P> Parent for GeometryCollectionBuilder<P> {
+    type Output = Self;
+    fn add(mut self, part: Part) -> Self {
+        self.geometries.push(part);
+        self
+    }
+}
+
+impl GeozeroGeometry for BuiltGeometry {
+    fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
+        process_part(&self.0, true, 0, processor)
+    }
+    fn dims(&self) -> CoordDimensions {
+        if has_z(&self.0) {
+            CoordDimensions::xyz()
+        } else {
+            CoordDimensions::xy()
+        }
+    }
+}
+
+fn builder_error(msg: &str) -> GeozeroError {
+    GeozeroError::Geometry(msg.to_string())
+}
+
+fn has_z(part: &Part) -> bool {
+    let any_z = |coords: &[Coord]| coords.iter().any(|c| c.z.is_some());
+    match part {
+        Part::Point(coord) => coord.z.is_some(),
+        Part::LineString(coords) | Part::MultiPoint(coords) => any_z(coords),
+        Part::Polygon(lines) | Part::MultiLineString(lines) => lines.iter().any(|l| any_z(l)),
+        Part::MultiPolygon(polygons) => polygons.iter().flatten().any(|l| any_z(l)),
+        Part::GeometryCollection(parts) => parts.iter().any(has_z),
+    }
+}
+
+fn process_coord<P: GeomProcessor>(coord: &Coord, idx: usize, processor: &mut P) -> Result<()> {
+    if processor.multi_dim() {
+        processor.coordinate(coord.x, coord.y, coord.z, None, None, None, idx)
+    } else {
+        processor.xy(coord.x, coord.y, idx)
+    }
+}
+
+fn process_linestring<P: GeomProcessor>(
+    coords: &[Coord],
+    tagged: bool,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    processor.linestring_begin(tagged, coords.len(), idx)?;
+    for (i, coord) in coords.iter().enumerate() {
+        process_coord(coord, i, processor)?;
+    }
+    processor.linestring_end(tagged, idx)
+}
+
+fn process_polygon<P: GeomProcessor>(
+    rings: &[Vec<Coord>],
+    tagged: bool,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    processor.polygon_begin(tagged, rings.len(), idx)?;
+    for (i, ring) in rings.iter().enumerate() {
+        process_linestring(ring, false, i, processor)?;
+    }
+    processor.polygon_end(tagged, idx)
+}
+
+fn process_part<P: GeomProcessor>(
+    part: &Part,
+    tagged: bool,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    match part {
+        Part::Point(coord) => {
+            processor.point_begin(idx)?;
+            process_coord(coord, 0, processor)?;
+            processor.point_end(idx)
+        }
+        Part::LineString(coords) => process_linestring(coords, tagged, idx, processor),
+        Part::Polygon(rings) => process_polygon(rings, tagged, idx, processor),
+        Part::MultiPoint(coords) => {
+            processor.multipoint_begin(coords.len(), idx)?;
+            for (i, coord) in coords.iter().enumerate() {
+                process_coord(coord, i, processor)?;
+            }
+            processor.multipoint_end(idx)
+        }
+        Part::MultiLineString(lines) => {
+            processor.multilinestring_begin(lines.len(), idx)?;
+            for (i, line) in lines.iter().enumerate() {
+                process_linestring(line, false, i, processor)?;
+            }
+            processor.multilinestring_end(idx)
+        }
+        Part::MultiPolygon(polygons) => {
+            processor.multipolygon_begin(polygons.len(), idx)?;
+            for (i, polygon) in polygons.iter().enumerate() {
+                process_polygon(polygon, false, i, processor)?;
+            }
+            processor.multipolygon_end(idx)
+        }
+        Part::GeometryCollection(parts) => {
+            processor.geometrycollection_begin(parts.len(), idx)?;
+            for (i, part) in parts.iter().enumerate() {
+                process_part(part, true, i, processor)?;
+            }
+            processor.geometrycollection_end(idx)
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use super::*;
+    use crate::ToWkt;
+
+    #[test]
+    fn build_geometries() -> Result<()> {
+        assert_eq!(GeometryBuilder::point(1.0, 2.0).to_wkt()?, "POINT(1 2)");
+        let line = GeometryBuilder::linestring()
+            .coord_z(1.0, 2.0, 3.0)
+            .coord_z(4.0, 5.0, 6.0)
+            .end();
+        assert_eq!(line.dims(), CoordDimensions::xyz());
+        assert_eq!(
+            line.to_wkt_ndim(CoordDimensions::xyz())?,
+            "LINESTRING(1 2 3,4 5 6)"
+        );
+
+        let multipolygon = GeometryBuilder::multipolygon()
+            .polygon()
+            .ring()
+            .coord(0.0, 0.0)
+            .coord(2.0, 0.0)
+            .coord(0.0, 2.0)
+            .coord(0.0, 0.0)
+            .end()
+            .end()
+            .polygon()
+            .end()
+            .end();
+        assert_eq!(
+            multipolygon.to_wkt()?,
+            "MULTIPOLYGON(((0 0,2 0,0 2,0 0)),EMPTY)"
+        );
+
+        let collection = GeometryBuilder::geometrycollection()
+            .point(1.0, 2.0)
+            .multipoint()
+            .point(3.0, 4.0)
+            .end()
+            .linestring()
+            .coord(5.0, 6.0)
+            .coord(7.0, 8.0)
+            .end()
+            .geometry(GeometryBuilder::point(9.0, 9.0))
+            .end();
+        assert_eq!(
+            collection.to_wkt()?,
+            "GEOMETRYCOLLECTION(POINT(1 2),MULTIPOINT(3 4),LINESTRING(5 6,7 8),POINT(9 9))"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn runtime_builder() -> Result<()> {
+        let mut builder = GeometryBuilder::new();
+        builder.begin_multipolygon()?;
+        builder.begin_polygon()?;
+        builder.add_ring()?;
+        for (x, y) in [(0.0, 0.0), (2.0, 0.0), (0.0, 2.0), (0.0, 0.0)] {
+            builder.push_coord(x, y)?;
+        }
+        builder.end()?;
+        builder.begin_polygon()?;
+        let multipolygon = builder.finish()?;
+        assert_eq!(
+            multipolygon,
+            GeometryBuilder::multipolygon()
+                .polygon()
+                .ring()
+                .coord(0.0, 0.0)
+                .coord(2.0, 0.0)
+                .coord(0.0, 2.0)
+                .coord(0.0, 0.0)
+                .end()
+                .end()
+                .polygon()
+                .end()
+                .end()
+        );
+
+        let mut builder = GeometryBuilder::new();
+        builder.begin_geometrycollection()?;
+        builder.begin_point()?;
+        builder.push_coord(1.0, 2.0)?;
+        builder.end()?;
+        builder.begin_multilinestring()?;
+        builder.begin_linestring()?;
+        builder.push_coord(3.0, 4.0)?;
+        builder.push_coord(5.0, 6.0)?;
+        let mut wkt_data: Vec<u8> = Vec::new();
+        let mut writer = crate::wkt::WktWriter::new(&mut wkt_data);
+        builder.finish()?.process_into(&mut writer)?;
+        assert_eq!(
+            std::str::from_utf8(&wkt_data).unwrap(),
+            "GEOMETRYCOLLECTION(POINT(1 2),MULTILINESTRING((3 4,5 6)))"
+        );
+
+        let mut builder = GeometryBuilder::new();
+        builder.begin_linestring()?;
+        assert!(builder.add_ring().is_err());
+        assert!(builder.begin_polygon().is_err());
+        builder.end()?;
+        assert!(builder.end().is_err());
+        assert!(builder.begin_point().is_err());
+
+        let mut builder = GeometryBuilder::new();
+        builder.begin_point()?;
+        assert!(builder.finish().is_err());
+        assert!(GeometryBuilder::new().finish().is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,287 @@
+//! Cooperative cancellation of processing.
+use crate::error::{GeozeroError, Result};
+use crate::{
+    ColumnDef, ColumnValue, CoordDimensions, Crs, FeatureId, FeatureProcessor, GeomProcessor,
+    PropertyProcessor,
+};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Token for cancelling processing from another thread.
+///
+/// Clones share the same cancellation state.
+#[derive(Clone, Default, Debug)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Request cancellation
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+    /// Return [GeozeroError::Cancelled] if cancellation was requested
+    pub fn check(&self) -> Result<()> {
+        if self.is_cancelled() {
+            Err(GeozeroError::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl From<Arc<AtomicBool>> for CancelToken {
+    fn from(flag: Arc<AtomicBool>) -> Self {
+        CancelToken(flag)
+    }
+}
+
+/// Wraps another [FeatureProcessor], aborting with [GeozeroError::Cancelled] at the begin of the
+/// next feature after cancellation was requested.
+pub struct CancellableProcessor<P> {
+    inner: P,
+    token: CancelToken,
+}
+
+impl<P> CancellableProcessor<P> {
+    pub fn new(inner: P, token: CancelToken) -> Self {
+        CancellableProcessor { inner, token }
+    }
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+}
+
+// The trait has many default implementations, but every single call must be specified here to
+// delegate
+impl<P: GeomProcessor> GeomProcessor for CancellableProcessor<P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.inner.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.inner.multi_dim()
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.inner.srid(srid)
+    }
+    fn envelope(&mut self, envelope: &[f64], dims: CoordDimensions, idx: usize) -> Result<()> {
+        self.inner.envelope(envelope, dims, idx)
+    }
+    fn size_hint_total_coords(&mut self, count: usize) -> Result<()> {
+        self.inner.size_hint_total_coords(count)
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.inner.xy(x, y, idx)
+    }
+    fn coords(&mut self, coords: &[f64], dims: CoordDimensions, idx: usize) -> Result<()> {
+        self.inner.coords(coords, dims, idx)
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.inner.coordinate(x, y, z, m, t, tm, idx)
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.inner.empty_point(idx)
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.inner.point_begin(idx)
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.point_end(idx)
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.multipoint_begin(size, idx)
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.multipoint_end(idx)
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.inner.linestring_begin(tagged, size, idx)
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.inner.linestring_end(tagged, idx)
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.multilinestring_begin(size, idx)
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.multilinestring_end(idx)
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.inner.polygon_begin(tagged, size, idx)
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.inner.polygon_end(tagged, idx)
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.multipolygon_begin(size, idx)
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.multipolygon_end(idx)
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.geometrycollection_begin(size, idx)
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.geometrycollection_end(idx)
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.circularstring_begin(size, idx)
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.circularstring_end(idx)
+    }
+    fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.compoundcurve_begin(size, idx)
+    }
+    fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.compoundcurve_end(idx)
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.curvepolygon_begin(size, idx)
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.curvepolygon_end(idx)
+    }
+    fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.multicurve_begin(size, idx)
+    }
+    fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.multicurve_end(idx)
+    }
+    fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.multisurface_begin(size, idx)
+    }
+    fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.multisurface_end(idx)
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.inner.triangle_begin(tagged, size, idx)
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.inner.triangle_end(tagged, idx)
+    }
+    fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.polyhedralsurface_begin(size, idx)
+    }
+    fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.polyhedralsurface_end(idx)
+    }
+    fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.tin_begin(size, idx)
+    }
+    fn tin_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.tin_end(idx)
+    }
+}
+
+impl<P: PropertyProcessor> PropertyProcessor for CancellableProcessor<P> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue<'_>) -> Result<bool> {
+        self.inner.property(idx, name, value)
+    }
+    fn column_property(
+        &mut self,
+        idx: usize,
+        column: usize,
+        name: &str,
+        value: &ColumnValue<'_>,
+    ) -> Result<bool> {
+        self.inner.column_property(idx, column, name, value)
+    }
+}
+
+impl<P: FeatureProcessor> FeatureProcessor for CancellableProcessor<P> {
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.inner.dataset_begin(name)
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        self.inner.dataset_end()
+    }
+    fn crs(&mut self, crs: &Crs) -> Result<()> {
+        self.inner.crs(crs)
+    }
+    fn schema_begin(&mut self, columns: &[ColumnDef]) -> Result<()> {
+        self.inner.schema_begin(columns)
+    }
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        self.token.check()?;
+        self.inner.feature_begin(idx)
+    }
+    fn feature_id(&mut self, id: &FeatureId) -> Result<()> {
+        self.inner.feature_id(id)
+    }
+    fn feature_end(&mut self, idx: u64) -> Result<()> {
+        self.inner.feature_end(idx)
+    }
+    fn properties_begin(&mut self) -> Result<()> {
+        self.inner.properties_begin()
+    }
+    fn properties_end(&mut self) -> Result<()> {
+        self.inner.properties_end()
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        self.inner.geometry_begin()
+    }
+    fn geometry_end(&mut self) -> Result<()> {
+        self.inner.geometry_end()
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::GeoJson;
+    use crate::GeozeroDatasource;
+
+    struct CancelAfter {
+        token: CancelToken,
+        features: u64,
+    }
+    impl FeatureProcessor for CancelAfter {
+        fn feature_end(&mut self, _idx: u64) -> Result<()> {
+            self.features += 1;
+            if self.features == 2 {
+                self.token.cancel();
+            }
+            Ok(())
+        }
+    }
+    impl GeomProcessor for CancelAfter {}
+    impl PropertyProcessor for CancelAfter {}
+
+    #[test]
+    fn cancel_between_features() {
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [1, 2]}},
+            {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [3, 4]}},
+            {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [5, 6]}}
+        ]}"#;
+        let token = CancelToken::new();
+        let inner = CancelAfter {
+            token: token.clone(),
+            features: 0,
+        };
+        let mut processor = CancellableProcessor::new(inner, token.clone());
+        let result = GeoJson(geojson).process(&mut processor);
+        assert!(matches!(result, Err(GeozeroError::Cancelled)));
+        assert_eq!(processor.into_inner().features, 2);
+
+        // Cancellation is not passed to the recovery handler
+        let mut processor = CancellableProcessor::new(crate::ProcessorSink, token);
+        let result = GeoJson(geojson).process_with_recovery(&mut processor, |_, _| Ok(()));
+        assert!(matches!(result, Err(GeozeroError::Cancelled)));
+    }
+}
@@ -0,0 +1,91 @@
+use crate::error::Result;
+use crate::{CoordDimensions, FeatureProcessor, GeomProcessor, GeozeroGeometry, PropertyProcessor};
+
+/// Calls a closure with `(x, y, z, m)` of every processed vertex.
+///
+/// Z and M values are requested from the source, but are `None` if not available.
+///
+/// # Usage example:
+///
+/// ```rust
+/// use geozero::wkt::Wkt;
+/// use geozero::{CoordVisitor, GeozeroGeometry};
+///
+/// let mut count = 0;
+/// let mut visitor = CoordVisitor::new(|_x, _y, _z, _m| count += 1);
+/// Wkt("LINESTRING(1 2,3 4)").process_geom(&mut visitor).unwrap();
+/// assert_eq!(count, 2);
+/// ```
+pub struct CoordVisitor<F: FnMut(f64, f64, Option<f64>, Option<f64>)> {
+    visit: F,
+}
+
+impl<F: FnMut(f64, f64, Option<f64>, Option<f64>)> CoordVisitor<F> {
+    pub fn new(visit: F) -> Self {
+        CoordVisitor { visit }
+    }
+}
+
+impl<F: FnMut(f64, f64, Option<f64>, Option<f64>)> GeomProcessor for CoordVisitor<F> {
+    fn dimensions(&self) -> CoordDimensions {
+        CoordDimensions::xyzm()
+    }
+    fn xy(&mut self, x: f64, y: f64, _idx: usize) -> Result<()> {
+        (self.visit)(x, y, None, None);
+        Ok(())
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        _t: Option<f64>,
+        _tm: Option<u64>,
+        _idx: usize,
+    ) -> Result<()> {
+        (self.visit)(x, y, z, m);
+        Ok(())
+    }
+}
+
+impl<F: FnMut(f64, f64, Option<f64>, Option<f64>)> PropertyProcessor for CoordVisitor<F> {}
+
+impl<F: FnMut(f64, f64, Option<f64>, Option<f64>)> FeatureProcessor for CoordVisitor<F> {}
+
+/// Iterate over geometry coordinates.
+pub trait ForEachCoord {
+    /// Call `visit` with `(x, y, z, m)` of every vertex.
+    fn for_each_coord<F: FnMut(f64, f64, Option<f64>, Option<f64>)>(&self, visit: F) -> Result<()>;
+}
+
+impl<T: GeozeroGeometry> ForEachCoord for T {
+    fn for_each_coord<F: FnMut(f64, f64, Option<f64>, Option<f64>)>(&self, visit: F) -> Result<()> {
+        self.process_geom(&mut CoordVisitor::new(visit))
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use super::*;
+    use crate::wkt::Wkt;
+
+    #[test]
+    fn for_each_coord() -> Result<()> {
+        let mut coords = Vec::new();
+        Wkt("GEOMETRYCOLLECTION(POINT Z(1 2 3),POLYGON((0 0,1 0,1 1,0 0)))")
+            .for_each_coord(|x, y, z, _m| coords.push((x, y, z)))?;
+        assert_eq!(
+            coords,
+            [
+                (1.0, 2.0, Some(3.0)),
+                (0.0, 0.0, None),
+                (1.0, 0.0, None),
+                (1.0, 1.0, None),
+                (0.0, 0.0, None)
+            ]
+        );
+        Ok(())
+    }
+}
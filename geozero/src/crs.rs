@@ -0,0 +1,84 @@
+use std::fmt;
+
+/// Coordinate reference system of a dataset.
+///
+/// Identified by authority and code (e.g. `EPSG:4326`) and/or by a WKT definition.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Crs {
+    /// Authority name, e.g. `EPSG` or `OGC`
+    pub authority: Option<String>,
+    /// Code within authority, e.g. `4326` or `CRS84`
+    pub code: Option<String>,
+    /// WKT definition, preferably WKT2
+    pub wkt: Option<String>,
+}
+
+impl Crs {
+    pub fn new(authority: &str, code: &str) -> Self {
+        Crs {
+            authority: Some(authority.to_string()),
+            code: Some(code.to_string()),
+            wkt: None,
+        }
+    }
+    /// CRS with EPSG code
+    pub fn epsg(code: i32) -> Self {
+        Crs::new("EPSG", &code.to_string())
+    }
+    /// CRS with WKT definition only
+    pub fn from_wkt(wkt: &str) -> Self {
+        Crs {
+            wkt: Some(wkt.to_string()),
+            ..Default::default()
+        }
+    }
+    /// Longitude/latitude on WGS84 (`OGC:CRS84`)
+    pub fn crs84() -> Self {
+        Crs::new("OGC", "CRS84")
+    }
+    /// Set WKT definition
+    pub fn set_wkt(&mut self, wkt: &str) {
+        self.wkt = Some(wkt.to_string());
+    }
+    /// EPSG code, if identified by EPSG authority
+    pub fn srid(&self) -> Option<i32> {
+        match (&self.authority, &self.code) {
+            (Some(authority), Some(code)) if authority.eq_ignore_ascii_case("EPSG") => {
+                code.parse().ok()
+            }
+            _ => None,
+        }
+    }
+    /// OGC URN, e.g. `urn:ogc:def:crs:EPSG::4326`
+    pub fn urn(&self) -> Option<String> {
+        match (&self.authority, &self.code) {
+            (Some(authority), Some(code)) => Some(format!("urn:ogc:def:crs:{authority}::{code}")),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Crs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.authority, &self.code, &self.wkt) {
+            (Some(authority), Some(code), _) => write!(f, "{authority}:{code}"),
+            (_, _, Some(wkt)) => f.write_str(wkt),
+            _ => f.write_str("unknown"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn identifiers() {
+        let crs = Crs::epsg(2056);
+        assert_eq!(crs.srid(), Some(2056));
+        assert_eq!(crs.urn().as_deref(), Some("urn:ogc:def:crs:EPSG::2056"));
+        assert_eq!(crs.to_string(), "EPSG:2056");
+        assert_eq!(Crs::crs84().srid(), None);
+        assert_eq!(Crs::from_wkt("GEOGCRS[...]").urn(), None);
+    }
+}
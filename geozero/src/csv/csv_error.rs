@@ -7,4 +7,6 @@ pub enum CsvError {
     ColumnNotFound,
     #[error("error parsing to WKT `{0}`")]
     WktError(&'static str),
+    #[error("CSV error `{0}`")]
+    Csv(#[from] csv::Error),
 }
@@ -1,5 +1,9 @@
 use crate::error::{GeozeroError, Result};
-use crate::{ColumnValue, FeatureProcessor, GeomProcessor, GeozeroDatasource, GeozeroGeometry};
+use crate::spool::{replay_feature, FeatureEncoder};
+use crate::{
+    ColumnDef, ColumnType, ColumnValue, FeatureProcessor, GeomProcessor, GeozeroDatasource,
+    GeozeroGeometry,
+};
 
 use crate::csv::csv_error::CsvError;
 
@@ -28,6 +32,18 @@ impl GeozeroDatasource for Csv<'_> {
             &self.geometry_column_name,
         )
     }
+    fn process_with_recovery<P, F>(&mut self, processor: &mut P, on_error: F) -> Result<()>
+    where
+        P: FeatureProcessor,
+        F: FnMut(u64, GeozeroError) -> Result<()>,
+    {
+        process_csv_features_with_recovery(
+            self.csv_text.as_bytes(),
+            processor,
+            &self.geometry_column_name,
+            on_error,
+        )
+    }
 }
 
 impl GeozeroGeometry for Csv<'_> {
@@ -62,6 +78,18 @@ impl GeozeroDatasource for CsvString {
             &self.geometry_column_name,
         )
     }
+    fn process_with_recovery<P, F>(&mut self, processor: &mut P, on_error: F) -> Result<()>
+    where
+        P: FeatureProcessor,
+        F: FnMut(u64, GeozeroError) -> Result<()>,
+    {
+        process_csv_features_with_recovery(
+            self.csv_text.as_bytes(),
+            processor,
+            &self.geometry_column_name,
+            on_error,
+        )
+    }
 }
 
 impl GeozeroGeometry for CsvString {
@@ -74,9 +102,68 @@ impl GeozeroGeometry for CsvString {
     }
 }
 
+/// Property types of CSV columns
+#[derive(Clone, Debug, Default)]
+enum ColumnTypes {
+    /// All properties are strings
+    #[default]
+    Strings,
+    /// Types inferred from the given number of records
+    Infer(usize),
+    /// Types of named columns, other columns are strings
+    Schema(Vec<ColumnDef>),
+}
+
+/// CSV reader options
+#[derive(Clone, Debug)]
+pub(crate) struct CsvOptions {
+    column_types: ColumnTypes,
+    delimiter: u8,
+    quote: u8,
+    escape: Option<u8>,
+    has_headers: bool,
+    /// Column names replacing the header row
+    column_names: Option<Vec<String>>,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions {
+            column_types: ColumnTypes::default(),
+            delimiter: b',',
+            quote: b'"',
+            escape: None,
+            has_headers: true,
+            column_names: None,
+        }
+    }
+}
+
+impl CsvOptions {
+    /// CSV reader and column names
+    fn reader<R: Read>(&self, input: R) -> Result<(csv::Reader<R>, csv::StringRecord)> {
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(self.delimiter)
+            .quote(self.quote)
+            .escape(self.escape)
+            .has_headers(self.has_headers)
+            .from_reader(input);
+        let headers = match &self.column_names {
+            Some(names) => csv::StringRecord::from(names.clone()),
+            None if self.has_headers => reader.headers()?.clone(),
+            // Returns the first record without consuming it
+            None => (1..=reader.headers()?.len())
+                .map(|i| format!("field_{i}"))
+                .collect(),
+        };
+        Ok((reader, headers))
+    }
+}
+
 pub struct CsvReader<R: Read> {
     inner: R,
     geometry_column_name: String,
+    options: CsvOptions,
 }
 
 impl<R: Read> CsvReader<R> {
@@ -84,19 +171,84 @@ impl<R: Read> CsvReader<R> {
         Self {
             inner,
             geometry_column_name: geometry_column_name.to_string(),
+            options: CsvOptions::default(),
         }
     }
+    /// Infer property types from the first `rows` records.
+    ///
+    /// Detects integers (`Long`), floats (`Double`), booleans (`true`/`false`) and
+    /// ISO 8601 dates (`DateTime`). Empty values are ignored for inference and skipped
+    /// in typed columns. Integers with leading zeros are kept as strings.
+    pub fn set_infer_types(&mut self, rows: usize) {
+        self.options.column_types = ColumnTypes::Infer(rows);
+    }
+    /// Set property types of columns matched by name. Other columns are read as strings.
+    ///
+    /// Values not matching the column type are processed as strings with a warning.
+    pub fn set_schema(&mut self, columns: Vec<ColumnDef>) {
+        self.options.column_types = ColumnTypes::Schema(columns);
+    }
+    /// Set field delimiter, e.g. `b'\t'` for TSV or `b';'` (default: `b','`)
+    pub fn set_delimiter(&mut self, delimiter: u8) {
+        self.options.delimiter = delimiter;
+    }
+    /// Set quote character (default: `b'"'`)
+    pub fn set_quote(&mut self, quote: u8) {
+        self.options.quote = quote;
+    }
+    /// Set escape character for quotes in quoted fields, e.g. `b'\\'`.
+    /// Quotes are escaped by doubling them by default.
+    pub fn set_escape(&mut self, escape: Option<u8>) {
+        self.options.escape = escape;
+    }
+    /// Input has a header row (default: true).
+    ///
+    /// Columns of input without header row are named `field_1`, `field_2`, etc.
+    /// unless names are set with [CsvReader::set_column_names].
+    pub fn set_has_headers(&mut self, has_headers: bool) {
+        self.options.has_headers = has_headers;
+    }
+    /// Set column names, replacing the names of the header row
+    pub fn set_column_names(&mut self, names: Vec<String>) {
+        self.options.column_names = Some(names);
+    }
 }
 
 impl<R: Read> GeozeroDatasource for CsvReader<R> {
     fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
-        process_csv_features(&mut self.inner, processor, &self.geometry_column_name)
+        process_csv_features_with_options(
+            &mut self.inner,
+            processor,
+            &self.geometry_column_name,
+            &self.options,
+            false,
+            |_, error| Err(error),
+        )
+    }
+    fn process_with_recovery<P, F>(&mut self, processor: &mut P, on_error: F) -> Result<()>
+    where
+        P: FeatureProcessor,
+        F: FnMut(u64, GeozeroError) -> Result<()>,
+    {
+        process_csv_features_with_options(
+            &mut self.inner,
+            processor,
+            &self.geometry_column_name,
+            &self.options,
+            true,
+            on_error,
+        )
     }
 }
 
 impl<R: Read + Clone> GeozeroGeometry for CsvReader<R> {
     fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
-        process_csv_geom(self.inner.clone(), processor, &self.geometry_column_name)
+        process_csv_geom_with_options(
+            self.inner.clone(),
+            processor,
+            &self.geometry_column_name,
+            &self.options,
+        )
     }
 }
 
@@ -105,8 +257,16 @@ pub fn process_csv_geom(
     processor: &mut impl GeomProcessor,
     geometry_column: &str,
 ) -> Result<()> {
-    let mut reader = csv::Reader::from_reader(input);
-    let headers = reader.headers()?.clone();
+    process_csv_geom_with_options(input, processor, geometry_column, &CsvOptions::default())
+}
+
+fn process_csv_geom_with_options(
+    input: impl Read,
+    processor: &mut impl GeomProcessor,
+    geometry_column: &str,
+    options: &CsvOptions,
+) -> Result<()> {
+    let (reader, headers) = options.reader(input)?;
 
     let geometry_idx = headers
         .iter()
@@ -133,8 +293,7 @@ pub fn process_csv_geom(
 
         crate::wkt::wkt_reader::process_wkt_geom_n(&wkt, record_idx, processor).inspect_err(
             |_e| {
-                // +2 to start at line 1 and to account for the header row
-                let line = record_idx + 2;
+                let line = record_line(&record);
                 log::warn!("line {line}: invalid WKT: '{geometry_field}', record: {record:?}");
             },
         )?;
@@ -152,8 +311,46 @@ pub fn process_csv_features(
     processor: &mut impl FeatureProcessor,
     geometry_column: &str,
 ) -> Result<()> {
-    let mut reader = csv::Reader::from_reader(input);
-    let headers = reader.headers()?.clone();
+    process_csv_features_with_options(
+        input,
+        processor,
+        geometry_column,
+        &CsvOptions::default(),
+        false,
+        |_, error| Err(error),
+    )
+}
+
+/// Process CSV features, passing errors of single records to `on_error` and continuing with the
+/// next record.
+///
+/// Records are buffered and only passed to `processor` if they are read without errors.
+/// Errors of `processor` abort processing.
+pub fn process_csv_features_with_recovery(
+    input: impl Read,
+    processor: &mut impl FeatureProcessor,
+    geometry_column: &str,
+    on_error: impl FnMut(u64, GeozeroError) -> Result<()>,
+) -> Result<()> {
+    process_csv_features_with_options(
+        input,
+        processor,
+        geometry_column,
+        &CsvOptions::default(),
+        true,
+        on_error,
+    )
+}
+
+pub(crate) fn process_csv_features_with_options(
+    input: impl Read,
+    processor: &mut impl FeatureProcessor,
+    geometry_column: &str,
+    options: &CsvOptions,
+    recover: bool,
+    mut on_error: impl FnMut(u64, GeozeroError) -> Result<()>,
+) -> Result<()> {
+    let (reader, headers) = options.reader(input)?;
     processor.dataset_begin(None)?;
 
     let geometry_idx = headers
@@ -161,45 +358,236 @@ pub fn process_csv_features(
         .position(|f| f == geometry_column)
         .ok_or(CsvError::ColumnNotFound)?;
 
-    for (feature_idx, record) in reader.into_records().enumerate() {
-        let record = record?;
-        processor.feature_begin(feature_idx as u64)?;
-
-        processor.properties_begin()?;
+    let mut records = reader.into_records().enumerate();
+    // Records used for type inference
+    let mut sample = Vec::new();
+    if let ColumnTypes::Infer(rows) = options.column_types {
+        sample.extend(records.by_ref().take(rows));
+    }
 
-        let properties_iter = headers
-            .iter()
-            .zip(record.iter())
-            .enumerate()
-            // skip the geometry field -  we process it after all the "properties"
-            .filter(|(input_idx, _)| *input_idx != geometry_idx)
-            .map(|(_input_idx, (header, value))| (header, value));
-
-        for (output_idx, (header, field)) in properties_iter.enumerate() {
-            let value = &ColumnValue::String(field);
-            processor.property(output_idx, header, value)?;
+    let columns: Vec<ColumnDef> = headers
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| *idx != geometry_idx)
+        .map(|(idx, header)| {
+            let column_type =
+                match &options.column_types {
+                    ColumnTypes::Strings => ColumnType::String,
+                    ColumnTypes::Infer(_) => infer_type(
+                        sample
+                            .iter()
+                            .filter_map(|(_, record)| record.as_ref().ok())
+                            .filter_map(|record| record.get(idx)),
+                    ),
+                    ColumnTypes::Schema(schema) => schema
+                        .iter()
+                        .find(|col| col.name == header)
+                        .map_or(ColumnType::String, |col| match col.column_type {
+                            // Not supported in CSV
+                            ColumnType::Binary | ColumnType::Uuid | ColumnType::Array => {
+                                ColumnType::String
+                            }
+                            column_type => column_type,
+                        }),
+                };
+            ColumnDef::new(header, column_type)
+        })
+        .collect();
+    processor.schema_begin(&columns)?;
+
+    // Records are buffered when recovering, so that failed records don't leave partial events
+    let mut buffer = recover.then(|| FeatureEncoder::new(processor.dimensions()));
+    for (feature_idx, record) in sample.into_iter().chain(records) {
+        let record = match record {
+            Ok(record) => record,
+            Err(error) => {
+                on_error(feature_idx as u64, error.into())?;
+                continue;
+            }
+        };
+        let result = match &mut buffer {
+            Some(buffer) => process_csv_record(
+                &record,
+                &headers,
+                geometry_idx,
+                &columns,
+                feature_idx,
+                buffer,
+            ),
+            None => process_csv_record(
+                &record,
+                &headers,
+                geometry_idx,
+                &columns,
+                feature_idx,
+                processor,
+            ),
+        };
+        match (result, &buffer) {
+            (Err(GeozeroError::Cancelled), _) => return Err(GeozeroError::Cancelled),
+            (Err(error), _) => on_error(feature_idx as u64, error)?,
+            // Errors of the processor abort processing
+            (Ok(()), Some(buffer)) => replay_feature(buffer.data(), feature_idx as u64, processor)?,
+            (Ok(()), None) => {}
         }
+    }
 
-        processor.properties_end()?;
+    processor.dataset_end()
+}
 
-        let geometry_field = record.get(geometry_idx).ok_or(CsvError::ColumnNotFound)?;
+fn process_csv_record(
+    record: &csv::StringRecord,
+    headers: &csv::StringRecord,
+    geometry_idx: usize,
+    columns: &[ColumnDef],
+    feature_idx: usize,
+    processor: &mut impl FeatureProcessor,
+) -> Result<()> {
+    processor.feature_begin(feature_idx as u64)?;
+
+    processor.properties_begin()?;
 
-        // Do all formats allow empty geometries?
-        if !geometry_field.is_empty() {
-            processor.geometry_begin()?;
-            crate::wkt::wkt_reader::read_wkt(&mut geometry_field.as_bytes(), processor)
-                .inspect_err(|_e| {
-                    // +2 to start at line 1 and to account for the header row
-                    let line = feature_idx + 2;
-                    log::warn!("line {line}: invalid WKT: '{geometry_field}', record: {record:?}");
-                })?;
-            processor.geometry_end()?;
+    let properties_iter = headers
+        .iter()
+        .zip(record.iter())
+        .enumerate()
+        // skip the geometry field -  we process it after all the "properties"
+        .filter(|(input_idx, _)| *input_idx != geometry_idx)
+        .map(|(_input_idx, (header, value))| (header, value));
+
+    for (output_idx, (header, field)) in properties_iter.enumerate() {
+        let column_type = columns
+            .get(output_idx)
+            .map_or(ColumnType::String, |col| col.column_type);
+        if field.is_empty() && column_type != ColumnType::String {
+            continue;
         }
+        let value = typed_value(field, column_type).unwrap_or_else(|| {
+            let line = record_line(record);
+            log::warn!("line {line}: invalid {column_type:?} value '{field}' of column '{header}', processed as string");
+            ColumnValue::String(field)
+        });
+        processor.column_property(output_idx, output_idx, header, &value)?;
+    }
 
-        processor.feature_end(feature_idx as u64)?;
+    processor.properties_end()?;
+
+    let geometry_field = record.get(geometry_idx).ok_or(CsvError::ColumnNotFound)?;
+
+    // Do all formats allow empty geometries?
+    if !geometry_field.is_empty() {
+        processor.geometry_begin()?;
+        crate::wkt::wkt_reader::read_wkt_buffered(geometry_field.as_bytes(), processor)
+            .inspect_err(|_e| {
+                let line = record_line(record);
+                log::warn!("line {line}: invalid WKT: '{geometry_field}', record: {record:?}");
+            })?;
+        processor.geometry_end()?;
     }
 
-    processor.dataset_end()
+    processor.feature_end(feature_idx as u64)
+}
+
+/// Line number of a record, starting at 1 and including the header row
+fn record_line(record: &csv::StringRecord) -> u64 {
+    record.position().map_or(0, |pos| pos.line())
+}
+
+/// Infer column type from sample values, ignoring empty values
+fn infer_type<'a>(values: impl Iterator<Item = &'a str>) -> ColumnType {
+    let mut inferred = None;
+    for value in values.filter(|v| !v.is_empty()) {
+        let value_type = if is_number(value) && value.parse::<i64>().is_ok() {
+            ColumnType::Long
+        } else if is_number(value)
+            && value.contains(['.', 'e', 'E'])
+            && value.parse::<f64>().is_ok()
+        {
+            ColumnType::Double
+        } else if parse_bool(value).is_some() {
+            ColumnType::Bool
+        } else if is_iso_date(value) {
+            ColumnType::DateTime
+        } else {
+            return ColumnType::String;
+        };
+        inferred = match (inferred, value_type) {
+            (None, value_type) => Some(value_type),
+            (Some(column_type), value_type) if column_type == value_type => Some(column_type),
+            (Some(ColumnType::Long), ColumnType::Double)
+            | (Some(ColumnType::Double), ColumnType::Long) => Some(ColumnType::Double),
+            _ => return ColumnType::String,
+        };
+    }
+    inferred.unwrap_or(ColumnType::String)
+}
+
+/// Decimal number without leading zeros, e.g. not a ZIP code like `01234`
+fn is_number(value: &str) -> bool {
+    let digits = value.strip_prefix(['-', '+']).unwrap_or(value).as_bytes();
+    match digits {
+        [b'0', b'0'..=b'9', ..] => false,
+        [b'0'..=b'9', ..] | [b'.', b'0'..=b'9', ..] => true,
+        _ => false,
+    }
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    if value.eq_ignore_ascii_case("true") {
+        Some(true)
+    } else if value.eq_ignore_ascii_case("false") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// ISO 8601 date (`YYYY-MM-DD`), optionally followed by a time
+fn is_iso_date(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    let date = bytes.len() >= 10
+        && bytes[..10].iter().enumerate().all(|(i, b)| {
+            if i == 4 || i == 7 {
+                *b == b'-'
+            } else {
+                b.is_ascii_digit()
+            }
+        });
+    let time = match bytes.get(10..) {
+        Some([]) => true,
+        Some([b'T' | b' ', h1, h2, b':', m1, m2, ..]) => {
+            [h1, h2, m1, m2].iter().all(|b| b.is_ascii_digit())
+        }
+        _ => false,
+    };
+    date && time
+}
+
+/// Property value of `column_type`, or None for empty or invalid values of non-string columns
+fn typed_value(field: &str, column_type: ColumnType) -> Option<ColumnValue<'_>> {
+    if field.is_empty() && column_type != ColumnType::String {
+        return None;
+    }
+    let value = match column_type {
+        ColumnType::Byte => ColumnValue::Byte(field.parse().ok()?),
+        ColumnType::UByte => ColumnValue::UByte(field.parse().ok()?),
+        ColumnType::Bool => ColumnValue::Bool(parse_bool(field)?),
+        ColumnType::Short => ColumnValue::Short(field.parse().ok()?),
+        ColumnType::UShort => ColumnValue::UShort(field.parse().ok()?),
+        ColumnType::Int => ColumnValue::Int(field.parse().ok()?),
+        ColumnType::UInt => ColumnValue::UInt(field.parse().ok()?),
+        ColumnType::Long => ColumnValue::Long(field.parse().ok()?),
+        ColumnType::ULong => ColumnValue::ULong(field.parse().ok()?),
+        ColumnType::Float => ColumnValue::Float(field.parse().ok()?),
+        ColumnType::Double => ColumnValue::Double(field.parse().ok()?),
+        ColumnType::Json => ColumnValue::Json(field),
+        ColumnType::DateTime => ColumnValue::DateTime(field),
+        ColumnType::Decimal => ColumnValue::Decimal(field),
+        ColumnType::String | ColumnType::Binary | ColumnType::Uuid | ColumnType::Array => {
+            ColumnValue::String(field)
+        }
+    };
+    Some(value)
 }
 
 impl From<csv::Error> for GeozeroError {
@@ -210,7 +598,7 @@ impl From<csv::Error> for GeozeroError {
                 _ => unreachable!(),
             }
         } else {
-            GeozeroError::Dataset(error.to_string())
+            GeozeroError::CsvError(CsvError::Csv(error))
         }
     }
 }
@@ -365,6 +753,170 @@ mod tests {
         assert_eq!(expected_geojson, actual_geojson)
     }
 
+    #[test]
+    fn infer_types() -> Result<()> {
+        use crate::geojson::conversion::ProcessToJson;
+        use crate::FeatureSpool;
+
+        let input = "name,zip,count,ratio,flag,date,geom
+a,01234,3,1,true,2024-03-01,POINT (1 2)
+b,04567,,2.5,False,2024-03-02T10:15:00Z,POINT (3 4)
+c,05678,12,3,,,POINT (5 6)
+d,06789,x,4,true,2024-03-03,POINT (7 8)
+";
+        let mut csv = CsvReader::new("geom", input.as_bytes());
+        csv.set_infer_types(3);
+        let mut spool = FeatureSpool::new();
+        csv.process(&mut spool)?;
+        let types: Vec<_> = spool
+            .schema()
+            .unwrap()
+            .iter()
+            .map(|col| col.column_type)
+            .collect();
+        assert_eq!(
+            types,
+            [
+                ColumnType::String,
+                ColumnType::String,
+                ColumnType::Long,
+                ColumnType::Double,
+                ColumnType::Bool,
+                ColumnType::DateTime
+            ]
+        );
+
+        let json: serde_json::Value = serde_json::from_str(&spool.to_json()?).unwrap();
+        assert_eq!(
+            json["features"][1]["properties"],
+            serde_json::json!({"name": "b", "zip": "04567", "ratio": 2.5, "flag": false, "date": "2024-03-02T10:15:00Z"})
+        );
+        // Invalid value after the inferred records is processed as string
+        assert_eq!(
+            json["features"][3]["properties"],
+            serde_json::json!({"name": "d", "zip": "06789", "count": "x", "ratio": 4, "flag": true, "date": "2024-03-03"})
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn user_schema() -> Result<()> {
+        use crate::geojson::conversion::ProcessToJson;
+
+        let mut csv = CsvReader::new("geom", "name,count,geom\na,7,POINT (1 2)\n".as_bytes());
+        csv.set_schema(vec![ColumnDef::new("count", ColumnType::Int)]);
+        let json: serde_json::Value = serde_json::from_str(&csv.to_json()?).unwrap();
+        assert_eq!(
+            json["features"][0]["properties"],
+            serde_json::json!({"name": "a", "count": 7})
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn delimiter_and_quoting() -> Result<()> {
+        use crate::geojson::conversion::ProcessToJson;
+
+        let mut csv = CsvReader::new("geom", "name\tgeom\n'a\tb'\tPOINT (1 2)\n".as_bytes());
+        csv.set_delimiter(b'\t');
+        csv.set_quote(b'\'');
+        let json: serde_json::Value = serde_json::from_str(&csv.to_json()?).unwrap();
+        assert_eq!(json["features"][0]["properties"]["name"], "a\tb");
+
+        let mut csv = CsvReader::new(
+            "geom",
+            r#"name;geom
+"say \"hi\"";POINT (1 2)
+"#
+            .as_bytes(),
+        );
+        csv.set_delimiter(b';');
+        csv.set_escape(Some(b'\\'));
+        let json: serde_json::Value = serde_json::from_str(&csv.to_json()?).unwrap();
+        assert_eq!(json["features"][0]["properties"]["name"], r#"say "hi""#);
+        Ok(())
+    }
+
+    #[test]
+    fn headerless() -> Result<()> {
+        use crate::geojson::conversion::ProcessToJson;
+
+        let input = "a,POINT (1 2)\nb,POINT (3 4)\n";
+        let mut csv = CsvReader::new("geom", input.as_bytes());
+        csv.set_has_headers(false);
+        csv.set_column_names(vec!["name".to_string(), "geom".to_string()]);
+        let json: serde_json::Value = serde_json::from_str(&csv.to_json()?).unwrap();
+        assert_eq!(json["features"][0]["properties"]["name"], "a");
+        assert_eq!(json["features"][1]["properties"]["name"], "b");
+
+        let mut csv = CsvReader::new("field_2", input.as_bytes());
+        csv.set_has_headers(false);
+        let json: serde_json::Value = serde_json::from_str(&csv.to_json()?).unwrap();
+        assert_eq!(json["features"].as_array().unwrap().len(), 2);
+        assert_eq!(json["features"][0]["properties"]["field_1"], "a");
+
+        // Line numbers of messages don't count a header row
+        let options = CsvOptions {
+            has_headers: false,
+            ..Default::default()
+        };
+        let (reader, _) = options.reader(input.as_bytes())?;
+        let lines = reader
+            .into_records()
+            .map(|record| Ok(record_line(&record?)))
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(lines, [1, 2]);
+        Ok(())
+    }
+
+    #[test]
+    fn feature_recovery() -> Result<()> {
+        use crate::{GeomProcessor, PropertyProcessor};
+
+        #[derive(Default)]
+        struct FeatureCounter(Vec<u64>);
+        impl FeatureProcessor for FeatureCounter {
+            fn feature_end(&mut self, idx: u64) -> Result<()> {
+                self.0.push(idx);
+                Ok(())
+            }
+        }
+        impl GeomProcessor for FeatureCounter {}
+        impl PropertyProcessor for FeatureCounter {}
+
+        let mut csv = Csv::new(
+            "geom",
+            "name,geom\na,POINT (1 2)\nb,POINT (oops)\nc,POINT (3 4)\nd,POINT (5 6),extra\n",
+        );
+        let mut errors = Vec::new();
+        let mut counter = FeatureCounter::default();
+        csv.process_with_recovery(&mut counter, |idx, error| {
+            errors.push((idx, error));
+            Ok(())
+        })?;
+        assert_eq!(counter.0, vec![0, 2]);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].0, 1);
+        assert!(matches!(
+            errors[1],
+            (3, GeozeroError::CsvError(CsvError::Csv(_)))
+        ));
+
+        // Without recovery, processing aborts at the first error
+        assert!(csv.process(&mut FeatureCounter::default()).is_err());
+
+        // Failed records don't leave partial features in the output
+        let mut out: Vec<u8> = Vec::new();
+        csv.process_with_recovery(&mut crate::geojson::GeoJsonWriter::new(&mut out), |_, _| {
+            Ok(())
+        })?;
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        let features = json["features"].as_array().unwrap();
+        assert_eq!(features.len(), 2);
+        assert_eq!(features[1]["properties"]["name"], "c");
+        Ok(())
+    }
+
     #[test]
     fn geom_processor() {
         use crate::geojson::conversion::ToJson;
@@ -1,12 +1,16 @@
 use crate::error::Result;
 use crate::wkt::WktWriter;
-use crate::{ColumnValue, CoordDimensions, FeatureProcessor, GeomProcessor, PropertyProcessor};
+use crate::{
+    ColumnDef, ColumnValue, CoordDimensions, FeatureProcessor, GeomProcessor, PropertyProcessor,
+};
 
 use std::io::Write;
 
 pub struct CsvWriter<W: Write> {
     csv: csv::Writer<W>,
     headers: Vec<String>,
+    /// Headers were declared with `schema_begin`
+    has_schema: bool,
     has_written_first_record: bool,
     current_row_props: Vec<String>,
     wkt_writer: WktWriter<Vec<u8>>,
@@ -21,12 +25,18 @@ impl<W: Write> CsvWriter<W> {
         Self {
             csv: csv::Writer::from_writer(out),
             headers: vec!["geometry".to_string()],
+            has_schema: false,
             has_written_first_record: false,
             current_row_props: vec![],
             wkt_writer: WktWriter::with_dims(vec![], dims),
         }
     }
 
+    /// Round coordinates to the given number of decimal places (default: no rounding)
+    pub fn set_coordinate_precision(&mut self, decimals: Option<u32>) {
+        self.wkt_writer.set_coordinate_precision(decimals);
+    }
+
     fn has_started_writing_geometry_in_this_row(&self) -> bool {
         !self.wkt_writer.out.is_empty()
     }
@@ -48,6 +58,15 @@ impl<W: Write> FeatureProcessor for CsvWriter<W> {
     }
     fn dataset_end(&mut self) -> Result<()> {
         self.headers = vec!["geometry".to_string()];
+        self.has_schema = false;
+        Ok(())
+    }
+    fn schema_begin(&mut self, columns: &[ColumnDef]) -> Result<()> {
+        if !self.has_written_first_record {
+            self.headers
+                .extend(columns.iter().map(|column| column.name.clone()));
+            self.has_schema = true;
+        }
         Ok(())
     }
     fn feature_begin(&mut self, _idx: u64) -> Result<()> {
@@ -61,6 +80,12 @@ impl<W: Write> FeatureProcessor for CsvWriter<W> {
             self.csv.write_record(self.headers.clone())?;
         }
 
+        if self.has_schema {
+            // Missing properties are written as empty fields
+            self.current_row_props
+                .resize(self.headers.len() - 1, String::new());
+        }
+
         let geom = &self.wkt_writer.out;
         self.csv.write_field(geom)?;
         self.wkt_writer.out.clear();
@@ -91,6 +116,18 @@ impl<W: Write> FeatureProcessor for CsvWriter<W> {
 
 impl<W: Write> PropertyProcessor for CsvWriter<W> {
     fn property(&mut self, i: usize, colname: &str, colval: &ColumnValue) -> Result<bool> {
+        if self.has_schema {
+            // Properties are written in schema order, undeclared properties are ignored
+            if let Some(pos) = self.headers[1..].iter().position(|h| h == colname) {
+                if self.current_row_props.len() <= pos {
+                    self.current_row_props
+                        .resize(self.headers.len() - 1, String::new());
+                }
+                self.current_row_props[pos] = colval.to_string();
+            }
+            return Ok(false);
+        }
+
         // TODO: support mis-ordered properties?
         if self.has_written_first_record {
             assert_eq!(
@@ -246,7 +283,8 @@ impl<W: Write> GeomProcessor for CsvWriter<W> {
 
 #[cfg(test)]
 mod tests {
-    use crate::ProcessToCsv;
+    use super::*;
+    use crate::{ColumnType, ProcessToCsv};
     use serde_json::json;
 
     #[test]
@@ -371,4 +409,40 @@ POINT(1 45),904 7th Av,05/22/2019 12:55:00 PM,F190051945,Car Fire
 
         assert_eq!(expected_output, actual_output);
     }
+
+    #[test]
+    fn schema_columns() -> Result<()> {
+        let mut out: Vec<u8> = Vec::new();
+        {
+            let mut writer = CsvWriter::new(&mut out);
+            writer.dataset_begin(None)?;
+            writer.schema_begin(&[
+                ColumnDef::new("name", ColumnType::String),
+                ColumnDef::new("population", ColumnType::Long),
+            ])?;
+            writer.feature_begin(0)?;
+            writer.properties_begin()?;
+            writer.property(0, "population", &ColumnValue::Long(100))?;
+            writer.properties_end()?;
+            writer.geometry_begin()?;
+            writer.point_begin(0)?;
+            writer.xy(1.0, 2.0, 0)?;
+            writer.point_end(0)?;
+            writer.geometry_end()?;
+            writer.feature_end(0)?;
+            writer.feature_begin(1)?;
+            writer.properties_begin()?;
+            writer.property(0, "population", &ColumnValue::Long(200))?;
+            writer.property(1, "name", &ColumnValue::String("second"))?;
+            writer.property(2, "ignored", &ColumnValue::Bool(true))?;
+            writer.properties_end()?;
+            writer.feature_end(1)?;
+            writer.dataset_end()?;
+        }
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            "geometry,name,population\nPOINT(1 2),,100\n,second,200\n"
+        );
+        Ok(())
+    }
 }
@@ -31,3 +31,62 @@ pub(crate) mod conversion {
         }
     }
 }
+
+#[cfg(feature = "with-wkb")]
+mod wkb {
+    use crate::csv::{CsvString, CsvWriter};
+    use crate::error::{GeozeroError, Result};
+    use crate::wkb::{FromWkb, WkbDialect};
+    use crate::FeatureProcessor;
+    use std::io::Read;
+
+    /// CSV with a single `geometry` column
+    impl FromWkb for CsvString {
+        fn from_wkb<R: Read>(rdr: &mut R, dialect: WkbDialect) -> Result<Self> {
+            let mut out: Vec<u8> = Vec::new();
+            {
+                let mut writer = CsvWriter::new(&mut out);
+                writer.dataset_begin(None)?;
+                writer.feature_begin(0)?;
+                writer.geometry_begin()?;
+                crate::wkb::process_wkb_type_geom(rdr, &mut writer, dialect)?;
+                writer.geometry_end()?;
+                writer.feature_end(0)?;
+                writer.dataset_end()?;
+            }
+            let csv = String::from_utf8(out)
+                .map_err(|_| GeozeroError::Geometry("Invalid UTF-8 encoding".to_string()))?;
+            Ok(CsvString::new("geometry", csv))
+        }
+    }
+
+    #[cfg(feature = "with-postgis-postgres")]
+    mod postgis_postgres {
+        use crate::csv::CsvString;
+
+        crate::impl_postgres_postgis_decode!(CsvString);
+    }
+
+    #[cfg(feature = "with-postgis-sqlx")]
+    mod postgis_sqlx {
+        use crate::csv::CsvString;
+
+        crate::impl_sqlx_postgis_type_info!(CsvString);
+        crate::impl_sqlx_postgis_decode!(CsvString);
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+        use crate::ProcessToCsv;
+
+        #[test]
+        fn csv_from_wkb() -> Result<()> {
+            // SELECT 'POINT(1 2)'::geometry
+            let wkb = hex::decode("0101000000000000000000F03F0000000000000040").unwrap();
+            let mut csv = CsvString::from_wkb(&mut wkb.as_slice(), WkbDialect::Wkb)?;
+            assert_eq!(csv.to_csv()?, "geometry\nPOINT(1 2)\n");
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,509 @@
+use crate::error::{GeozeroError, Result};
+use crate::linearize::{check_positive, MAX_ARC_SEGMENTS};
+use crate::{ColumnValue, FeatureProcessor, GeomProcessor, GeozeroDatasource};
+use std::f64::consts::PI;
+use std::io::{BufRead, BufReader, Read};
+
+/// DXF reader.
+///
+/// Reads `POINT`, `LINE`, `LWPOLYLINE`, `POLYLINE`, `CIRCLE` and `ARC` entities of the
+/// `ENTITIES` section of ASCII DXF files as features with a `layer` property.
+/// Polylines, circles and arcs are read as linestrings, with arcs and polyline bulges
+/// linearized. Other entities are skipped.
+pub struct DxfReader<R: Read> {
+    reader: R,
+    max_angle: f64,
+}
+
+impl<R: Read> DxfReader<R> {
+    /// Linearize arcs with a maximal angle of 2.8125° (32 segments per quarter circle) per segment.
+    pub fn new(reader: R) -> Self {
+        DxfReader {
+            reader,
+            max_angle: PI / 2.0 / 32.0,
+        }
+    }
+
+    /// Set maximal angle in radians between the vertices of a linearized arc
+    pub fn set_max_angle(&mut self, max_angle: f64) -> Result<()> {
+        check_positive("max_angle", max_angle)?;
+        self.max_angle = max_angle;
+        Ok(())
+    }
+}
+
+impl<R: Read> GeozeroDatasource for DxfReader<R> {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        process_dxf(&mut self.reader, self.max_angle, processor)
+    }
+}
+
+/// Read DXF entities as features.
+pub fn read_dxf<R: Read, P: FeatureProcessor>(reader: R, processor: &mut P) -> Result<()> {
+    DxfReader::new(reader).process(processor)
+}
+
+fn process_dxf<R: Read, P: FeatureProcessor>(
+    reader: R,
+    max_angle: f64,
+    processor: &mut P,
+) -> Result<()> {
+    let mut groups = Groups::new(BufReader::new(reader));
+    let mut in_entities = false;
+    let mut idx = 0;
+    processor.dataset_begin(None)?;
+    while let Some((code, value)) = groups.next()? {
+        if code != 0 {
+            continue;
+        }
+        match value.as_str() {
+            "SECTION" => {
+                in_entities = groups.entity()?.value(2) == Some("ENTITIES");
+            }
+            "ENDSEC" => in_entities = false,
+            "EOF" => break,
+            kind if in_entities => {
+                let entity = groups.entity()?;
+                let coords = match kind {
+                    "POINT" => vec![entity.coord(10)?],
+                    "LINE" => vec![entity.coord(10)?, entity.coord(11)?],
+                    "LWPOLYLINE" => {
+                        let z = entity.float(38)?;
+                        let mut vertices: Vec<Vertex> = Vec::new();
+                        for (code, value) in &entity.0 {
+                            match (*code, vertices.last_mut()) {
+                                (10, _) => vertices.push(Vertex {
+                                    coord: Coord {
+                                        x: parse_float(value)?,
+                                        y: 0.0,
+                                        z,
+                                    },
+                                    bulge: 0.0,
+                                }),
+                                (20, Some(vertex)) => vertex.coord.y = parse_float(value)?,
+                                (42, Some(vertex)) => vertex.bulge = parse_float(value)?,
+                                _ => {}
+                            }
+                        }
+                        polyline(&vertices, entity.flags()? & 1 != 0, max_angle)
+                    }
+                    "POLYLINE" => {
+                        let mut vertices = Vec::new();
+                        while let Some((code, value)) = groups.next()? {
+                            match (code, value.as_str()) {
+                                (0, "VERTEX") => {
+                                    let vertex = groups.entity()?;
+                                    // Skip polyface mesh face records
+                                    if vertex.flags()? & 128 == 0 {
+                                        vertices.push(Vertex {
+                                            coord: vertex.coord(10)?,
+                                            bulge: vertex.float(42)?.unwrap_or(0.0),
+                                        });
+                                    }
+                                }
+                                (0, "SEQEND") => {
+                                    groups.entity()?;
+                                    break;
+                                }
+                                _ => {
+                                    groups.peeked = Some((code, value));
+                                    break;
+                                }
+                            }
+                        }
+                        polyline(&vertices, entity.flags()? & 1 != 0, max_angle)
+                    }
+                    "CIRCLE" => arc(
+                        entity.coord(10)?,
+                        entity.float(40)?.unwrap_or(0.0),
+                        0.0,
+                        2.0 * PI,
+                        max_angle,
+                    ),
+                    "ARC" => {
+                        let start = entity.float(50)?.unwrap_or(0.0).to_radians();
+                        let end = entity.float(51)?.unwrap_or(0.0).to_radians();
+                        let sweep = (end - start).rem_euclid(2.0 * PI);
+                        let sweep = if sweep == 0.0 { 2.0 * PI } else { sweep };
+                        arc(
+                            entity.coord(10)?,
+                            entity.float(40)?.unwrap_or(0.0),
+                            start,
+                            sweep,
+                            max_angle,
+                        )
+                    }
+                    _ => continue,
+                };
+                processor.feature_begin(idx)?;
+                processor.properties_begin()?;
+                processor.property(
+                    0,
+                    "layer",
+                    &ColumnValue::String(entity.value(8).unwrap_or("0")),
+                )?;
+                processor.properties_end()?;
+                processor.geometry_begin()?;
+                if kind == "POINT" {
+                    processor.point_begin(0)?;
+                    process_coord(&coords[0], 0, processor)?;
+                    processor.point_end(0)?;
+                } else {
+                    processor.linestring_begin(true, coords.len(), 0)?;
+                    for (i, coord) in coords.iter().enumerate() {
+                        process_coord(coord, i, processor)?;
+                    }
+                    processor.linestring_end(true, 0)?;
+                }
+                processor.geometry_end()?;
+                processor.feature_end(idx)?;
+                idx += 1;
+            }
+            _ => {}
+        }
+    }
+    processor.dataset_end()
+}
+
+fn dxf_error(msg: String) -> GeozeroError {
+    GeozeroError::Geometry(msg)
+}
+
+fn parse_float(value: &str) -> Result<f64> {
+    value
+        .parse()
+        .map_err(|_| dxf_error(format!("invalid DXF number `{value}`")))
+}
+
+/// Reader of DXF group code/value pairs
+struct Groups<R: BufRead> {
+    reader: R,
+    peeked: Option<(i32, String)>,
+}
+
+impl<R: BufRead> Groups<R> {
+    fn new(reader: R) -> Self {
+        Groups {
+            reader,
+            peeked: None,
+        }
+    }
+
+    fn next(&mut self) -> Result<Option<(i32, String)>> {
+        if let Some(group) = self.peeked.take() {
+            return Ok(Some(group));
+        }
+        let Some(code) = self.read_line()? else {
+            return Ok(None);
+        };
+        let code = code
+            .trim()
+            .parse()
+            .map_err(|_| dxf_error(format!("invalid DXF group code `{}`", code.trim())))?;
+        let value = self
+            .read_line()?
+            .ok_or_else(|| dxf_error("unexpected end of DXF file".to_string()))?;
+        Ok(Some((code, value.trim().to_string())))
+    }
+
+    fn read_line(&mut self) -> Result<Option<String>> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            Ok(None)
+        } else {
+            Ok(Some(line))
+        }
+    }
+
+    /// Read groups up to the next entity
+    fn entity(&mut self) -> Result<Entity> {
+        let mut groups = Vec::new();
+        while let Some(group) = self.next()? {
+            if group.0 == 0 {
+                self.peeked = Some(group);
+                break;
+            }
+            groups.push(group);
+        }
+        Ok(Entity(groups))
+    }
+}
+
+/// Groups of a DXF entity
+struct Entity(Vec<(i32, String)>);
+
+impl Entity {
+    fn value(&self, code: i32) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(c, _)| *c == code)
+            .map(|(_, value)| value.as_str())
+    }
+    fn float(&self, code: i32) -> Result<Option<f64>> {
+        self.value(code).map(parse_float).transpose()
+    }
+    fn flags(&self) -> Result<i64> {
+        self.value(70).map_or(Ok(0), |value| {
+            value
+                .parse()
+                .map_err(|_| dxf_error(format!("invalid DXF flags `{value}`")))
+        })
+    }
+    /// Coordinate with x, y and z in the groups `code`, `code + 10` and `code + 20`
+    fn coord(&self, code: i32) -> Result<Coord> {
+        Ok(Coord {
+            x: self.float(code)?.unwrap_or(0.0),
+            y: self.float(code + 10)?.unwrap_or(0.0),
+            z: self.float(code + 20)?,
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct Coord {
+    x: f64,
+    y: f64,
+    z: Option<f64>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Vertex {
+    coord: Coord,
+    /// Tangent of a quarter of the arc angle to the next vertex
+    bulge: f64,
+}
+
+fn process_coord<P: GeomProcessor>(coord: &Coord, idx: usize, processor: &mut P) -> Result<()> {
+    if processor.multi_dim() {
+        processor.coordinate(coord.x, coord.y, coord.z, None, None, None, idx)
+    } else {
+        processor.xy(coord.x, coord.y, idx)
+    }
+}
+
+fn arc_segments(sweep: f64, max_angle: f64) -> usize {
+    ((sweep.abs() / max_angle).ceil() as usize).clamp(1, MAX_ARC_SEGMENTS)
+}
+
+/// Linearize an arc counterclockwise from `start` by `sweep` radians
+fn arc(center: Coord, radius: f64, start: f64, sweep: f64, max_angle: f64) -> Vec<Coord> {
+    let n = arc_segments(sweep, max_angle);
+    (0..=n)
+        .map(|i| {
+            let angle = start + sweep * i as f64 / n as f64;
+            Coord {
+                x: center.x + radius * angle.cos(),
+                y: center.y + radius * angle.sin(),
+                z: center.z,
+            }
+        })
+        .collect()
+}
+
+fn polyline(vertices: &[Vertex], closed: bool, max_angle: f64) -> Vec<Coord> {
+    let mut coords = Vec::with_capacity(vertices.len() + usize::from(closed));
+    let segments = if closed {
+        vertices.len()
+    } else {
+        vertices.len().saturating_sub(1)
+    };
+    for i in 0..segments {
+        let (from, to) = (vertices[i], vertices[(i + 1) % vertices.len()]);
+        coords.push(from.coord);
+        if from.bulge != 0.0 {
+            bulge_arc(&from.coord, &to.coord, from.bulge, max_angle, &mut coords);
+        }
+    }
+    match (closed, vertices.first(), vertices.last()) {
+        (true, Some(first), _) => coords.push(first.coord),
+        (false, _, Some(last)) => coords.push(last.coord),
+        _ => {}
+    }
+    coords
+}
+
+/// Add the inner vertices of the arc segment between `from` and `to`
+fn bulge_arc(from: &Coord, to: &Coord, bulge: f64, max_angle: f64, coords: &mut Vec<Coord>) {
+    let sweep = 4.0 * bulge.atan();
+    let chord = (to.x - from.x).hypot(to.y - from.y);
+    // Signed radius, the center is left of the chord for counterclockwise arcs
+    let radius = chord / (2.0 * (sweep / 2.0).sin());
+    let direction = (to.y - from.y).atan2(to.x - from.x) + PI / 2.0 - sweep / 2.0;
+    let center = Coord {
+        x: from.x + radius * direction.cos(),
+        y: from.y + radius * direction.sin(),
+        z: from.z,
+    };
+    let start = (from.y - center.y).atan2(from.x - center.x);
+    let n = arc_segments(sweep, max_angle);
+    let radius = radius.abs();
+    coords.extend((1..n).map(|i| {
+        let angle = start + sweep * i as f64 / n as f64;
+        Coord {
+            x: center.x + radius * angle.cos(),
+            y: center.y + radius * angle.sin(),
+            z: from.z,
+        }
+    }));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn dxf(groups: &[(i32, &str)]) -> String {
+        groups
+            .iter()
+            .map(|(code, value)| format!("{code:>3}\n{value}\n"))
+            .collect()
+    }
+
+    fn assert_coords(coords: &[Coord], expected: &[(f64, f64)]) {
+        assert_eq!(coords.len(), expected.len());
+        for (coord, (x, y)) in coords.iter().zip(expected) {
+            assert!((coord.x - x).abs() < 1e-9 && (coord.y - y).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn bulges() {
+        let vertex = |x, y, bulge| Vertex {
+            coord: Coord { x, y, z: None },
+            bulge,
+        };
+        let semicircle = polyline(
+            &[vertex(0.0, 0.0, 1.0), vertex(2.0, 0.0, 0.0)],
+            false,
+            PI / 2.0,
+        );
+        assert_coords(&semicircle, &[(0.0, 0.0), (1.0, -1.0), (2.0, 0.0)]);
+        let clockwise = polyline(
+            &[vertex(0.0, 0.0, -1.0), vertex(2.0, 0.0, 0.0)],
+            false,
+            PI / 2.0,
+        );
+        assert_coords(&clockwise, &[(0.0, 0.0), (1.0, 1.0), (2.0, 0.0)]);
+        let closed = polyline(
+            &[
+                vertex(0.0, 0.0, 0.0),
+                vertex(1.0, 0.0, 0.0),
+                vertex(1.0, 1.0, 0.0),
+            ],
+            true,
+            PI / 2.0,
+        );
+        assert_coords(&closed, &[(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 0.0)]);
+    }
+
+    #[test]
+    fn max_angle() {
+        let mut reader = DxfReader::new("".as_bytes());
+        assert!(reader.set_max_angle(0.0).is_err());
+        assert!(reader.set_max_angle(-1.0).is_err());
+        assert!(reader.set_max_angle(f64::NAN).is_err());
+        assert_eq!(arc_segments(2.0 * PI, 1e-300), MAX_ARC_SEGMENTS);
+        assert_eq!(arc_segments(f64::NAN, PI / 2.0), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "with-geojson")]
+    fn read_entities() -> Result<()> {
+        use crate::ProcessToJson;
+
+        let data = dxf(&[
+            (0, "SECTION"),
+            (2, "BLOCKS"),
+            (0, "LINE"),
+            (8, "block"),
+            (0, "ENDSEC"),
+            (0, "SECTION"),
+            (2, "ENTITIES"),
+            (0, "POINT"),
+            (8, "points"),
+            (10, "1.5"),
+            (20, "2"),
+            (0, "LINE"),
+            (8, "lines"),
+            (10, "0"),
+            (20, "0"),
+            (30, "1"),
+            (11, "1"),
+            (21, "1"),
+            (31, "2"),
+            (0, "TEXT"),
+            (8, "labels"),
+            (1, "skipped"),
+            (0, "LWPOLYLINE"),
+            (8, "lines"),
+            (90, "3"),
+            (70, "1"),
+            (10, "0"),
+            (20, "0"),
+            (10, "1"),
+            (20, "0"),
+            (10, "1"),
+            (20, "1"),
+            (0, "POLYLINE"),
+            (8, "lines"),
+            (66, "1"),
+            (0, "VERTEX"),
+            (10, "5"),
+            (20, "5"),
+            (0, "VERTEX"),
+            (10, "6"),
+            (20, "5"),
+            (0, "SEQEND"),
+            (0, "CIRCLE"),
+            (10, "0"),
+            (20, "0"),
+            (40, "1"),
+            (0, "ARC"),
+            (10, "0"),
+            (20, "0"),
+            (40, "1"),
+            (50, "0"),
+            (51, "90"),
+            (0, "ENDSEC"),
+            (0, "EOF"),
+        ]);
+        let mut reader = DxfReader::new(data.as_bytes());
+        reader.set_max_angle(PI / 2.0)?;
+        let json: serde_json::Value = serde_json::from_str(&reader.to_json()?).unwrap();
+        let features = json["features"].as_array().unwrap();
+        assert_eq!(features.len(), 6);
+        let layers: Vec<_> = features
+            .iter()
+            .map(|f| f["properties"]["layer"].as_str().unwrap())
+            .collect();
+        assert_eq!(layers, ["points", "lines", "lines", "lines", "0", "0"]);
+        let coords: Vec<_> = features
+            .iter()
+            .map(|f| f["geometry"]["coordinates"].to_string())
+            .collect();
+        assert_eq!(coords[0], "[1.5,2]");
+        assert_eq!(coords[1], "[[0,0],[1,1]]");
+        assert_eq!(coords[2], "[[0,0],[1,0],[1,1],[0,0]]");
+        assert_eq!(coords[3], "[[5,5],[6,5]]");
+        assert_eq!(
+            features[4]["geometry"]["coordinates"]
+                .as_array()
+                .unwrap()
+                .len(),
+            5
+        );
+        assert_eq!(
+            features[5]["geometry"]["coordinates"]
+                .as_array()
+                .unwrap()
+                .len(),
+            2
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_group_code() {
+        let data = dxf(&[(0, "SECTION"), (2, "ENTITIES")]) + "foo\nPOINT\n";
+        assert!(read_dxf(data.as_bytes(), &mut crate::ProcessorSink).is_err());
+    }
+}
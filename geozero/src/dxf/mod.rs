@@ -0,0 +1,4 @@
+//! DXF conversions.
+mod dxf_reader;
+
+pub use dxf_reader::{read_dxf, DxfReader};
@@ -1,7 +1,13 @@
 //! Error and Result types.
+use std::fmt;
 use thiserror::Error;
 
+/// Geozero error type.
+///
+/// Errors of underlying libraries are kept as [source](std::error::Error::source)
+/// in typed variants, so callers can match on the error category.
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum GeozeroError {
     // Geometry access errors
     #[error("spatial index access")]
@@ -37,18 +43,104 @@ pub enum GeozeroError {
     #[error("processing geometry `{0}`")]
     Geometry(String),
     // General
+    #[error("processing cancelled")]
+    Cancelled,
+    #[error("errors in {} processors", .0.len())]
+    Multiple(Vec<GeozeroError>),
+    #[error("{error} at {position}")]
+    Positioned {
+        position: ErrorPosition,
+        #[source]
+        error: Box<GeozeroError>,
+    },
     #[error("I/O error `{0}`")]
     IoError(#[from] std::io::Error),
+    #[error("JSON error `{0}`")]
+    JsonError(#[from] serde_json::Error),
+    #[error(transparent)]
+    External(Box<dyn std::error::Error + Send + Sync>),
     // Format Specific
+    #[cfg(feature = "with-wkb")]
+    #[error("WKB error `{0}`")]
+    WkbError(#[from] crate::wkb::WkbError),
+    #[cfg(feature = "with-geojson")]
+    #[error("GeoJSON error `{0}`")]
+    GeoJsonError(#[source] geojson::Error),
     #[cfg(feature = "with-csv")]
     #[error("CSV error `{0}`")]
     CsvError(#[from] crate::csv::CsvError),
     #[cfg(feature = "with-mvt")]
     #[error("MVT error `{0}`")]
     MvtError(#[from] crate::mvt::MvtError),
+    #[cfg(feature = "with-postgis-postgres")]
+    #[error("PostgreSQL error `{0}`")]
+    PostgresError(#[from] postgres::Error),
+    #[cfg(any(
+        feature = "with-gpkg",
+        feature = "with-mysql-sqlx",
+        feature = "with-postgis-sqlx"
+    ))]
+    #[error("SQLx error `{0}`")]
+    SqlxError(#[from] sqlx::Error),
+    #[cfg(feature = "with-geoparquet")]
+    #[error("Parquet error `{0}`")]
+    ParquetError(#[from] parquet::errors::ParquetError),
+    #[cfg(feature = "with-geoparquet")]
+    #[error("Arrow error `{0}`")]
+    ArrowError(#[from] arrow_schema::ArrowError),
     #[cfg(feature = "with-gdal")]
     #[error("GDAL error `{0}`")]
     GdalError(#[from] crate::gdal::GdalError),
+    #[cfg(feature = "with-geos")]
+    #[error("GEOS error `{0}`")]
+    GeosError(#[from] geos::Error),
+}
+
+impl GeozeroError {
+    /// Add position context to error
+    pub fn at(self, position: ErrorPosition) -> Self {
+        GeozeroError::Positioned {
+            position,
+            error: Box::new(self),
+        }
+    }
+
+    /// Position in input where the error occurred, if known
+    pub fn position(&self) -> Option<ErrorPosition> {
+        match self {
+            GeozeroError::Positioned { position, .. } => Some(*position),
+            _ => None,
+        }
+    }
+}
+
+/// Position of an error in the input
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ErrorPosition {
+    /// Byte offset in binary input
+    Offset(u64),
+    /// Line and column in text input, both starting at 1
+    LineColumn { line: usize, column: usize },
+}
+
+impl ErrorPosition {
+    /// Line and column of byte offset `pos` in `text`
+    pub fn from_text_offset(text: &str, pos: usize) -> Self {
+        let before = text.get(..pos).unwrap_or(text);
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let column = before[line_start..].chars().count() + 1;
+        ErrorPosition::LineColumn { line, column }
+    }
+}
+
+impl fmt::Display for ErrorPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorPosition::Offset(offset) => write!(f, "byte offset {offset}"),
+            ErrorPosition::LineColumn { line, column } => write!(f, "line {line} column {column}"),
+        }
+    }
 }
 
 pub type Result<T> = std::result::Result<T, GeozeroError>;
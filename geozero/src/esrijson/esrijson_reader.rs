@@ -0,0 +1,577 @@
+use crate::error::{GeozeroError, Result};
+use crate::{
+    ColumnDef, ColumnType, ColumnValue, FeatureId, FeatureProcessor, GeomProcessor,
+    GeozeroDatasource, GeozeroGeometry,
+};
+use serde_json::map::Map;
+use serde_json::value::Value as JsonValue;
+use std::io::Read;
+
+/// Esri JSON geometry or featureSet String.
+#[derive(Debug)]
+pub struct EsriJson<B: AsRef<[u8]>>(pub B);
+
+impl<B: AsRef<[u8]>> GeozeroGeometry for EsriJson<B> {
+    fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
+        let geometry = parse(self.0.as_ref())?;
+        process_esrijson_geom(&geometry, processor)
+    }
+}
+
+impl<B: AsRef<[u8]>> GeozeroDatasource for EsriJson<B> {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        read_esrijson(self.0.as_ref(), processor)
+    }
+}
+
+/// Esri JSON featureSet reader.
+pub struct EsriJsonReader<R: Read>(pub R);
+
+impl<R: Read> GeozeroDatasource for EsriJsonReader<R> {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        read_esrijson(&mut self.0, processor)
+    }
+}
+
+fn parse(json: &[u8]) -> Result<JsonValue> {
+    serde_json::from_slice(json).map_err(|e| GeozeroError::Geometry(e.to_string()))
+}
+
+/// Read and process Esri JSON featureSet.
+///
+/// A single feature or geometry object is processed as dataset with one feature.
+pub fn read_esrijson<R: Read, P: FeatureProcessor>(reader: R, processor: &mut P) -> Result<()> {
+    let json: JsonValue =
+        serde_json::from_reader(reader).map_err(|e| GeozeroError::Geometry(e.to_string()))?;
+    let object = json
+        .as_object()
+        .ok_or_else(|| GeozeroError::Geometry("Esri JSON object expected".to_string()))?;
+    processor.dataset_begin(None)?;
+    if let Some(features) = object.get("features") {
+        let features = features
+            .as_array()
+            .ok_or_else(|| GeozeroError::Geometry("features array expected".to_string()))?;
+        let fields = object
+            .get("fields")
+            .and_then(JsonValue::as_array)
+            .map(|fields| fields.iter().filter_map(field_def).collect::<Vec<_>>());
+        if let Some(ref fields) = fields {
+            processor.schema_begin(fields)?;
+        }
+        let schema = FeatureSchema {
+            fields: fields.unwrap_or_default(),
+            oid_field: object
+                .get("objectIdFieldName")
+                .and_then(JsonValue::as_str)
+                .map(str::to_string),
+            srid: spatial_reference(object),
+        };
+        for (idx, feature) in features.iter().enumerate() {
+            let feature = feature
+                .as_object()
+                .ok_or_else(|| GeozeroError::Geometry("feature object expected".to_string()))?;
+            process_feature(feature, idx, &schema, processor)?;
+        }
+    } else if object.contains_key("attributes") || object.contains_key("geometry") {
+        process_feature(object, 0, &FeatureSchema::default(), processor)?;
+    } else {
+        processor.feature_begin(0)?;
+        processor.geometry_begin()?;
+        process_esrijson_geom(&json, processor)?;
+        processor.geometry_end()?;
+        processor.feature_end(0)?;
+    }
+    processor.dataset_end()
+}
+
+/// Field declarations of a featureSet
+#[derive(Default)]
+struct FeatureSchema {
+    fields: Vec<ColumnDef>,
+    /// Attribute used as feature id
+    oid_field: Option<String>,
+    /// Spatial reference of the featureSet
+    srid: Option<i32>,
+}
+
+fn field_def(field: &JsonValue) -> Option<ColumnDef> {
+    let name = field.get("name")?.as_str()?;
+    let column_type = match field.get("type")?.as_str()? {
+        "esriFieldTypeSmallInteger" => ColumnType::Short,
+        "esriFieldTypeInteger" => ColumnType::Int,
+        "esriFieldTypeOID" | "esriFieldTypeBigInteger" => ColumnType::Long,
+        // milliseconds since epoch
+        "esriFieldTypeDate" => ColumnType::Long,
+        "esriFieldTypeSingle" => ColumnType::Float,
+        "esriFieldTypeDouble" => ColumnType::Double,
+        // geometry and raster fields are not processed as properties
+        "esriFieldTypeGeometry" | "esriFieldTypeRaster" => return None,
+        _ => ColumnType::String,
+    };
+    Some(ColumnDef::new(name, column_type))
+}
+
+fn spatial_reference(object: &Map<String, JsonValue>) -> Option<i32> {
+    let sr = object.get("spatialReference")?;
+    sr.get("latestWkid")
+        .or_else(|| sr.get("wkid"))
+        .and_then(JsonValue::as_i64)
+        .and_then(|wkid| i32::try_from(wkid).ok())
+}
+
+fn process_feature<P: FeatureProcessor>(
+    feature: &Map<String, JsonValue>,
+    idx: usize,
+    schema: &FeatureSchema,
+    processor: &mut P,
+) -> Result<()> {
+    processor.feature_begin(idx as u64)?;
+    let attributes = feature.get("attributes").and_then(JsonValue::as_object);
+    if let (Some(attributes), Some(oid_field)) = (attributes, &schema.oid_field) {
+        if let Some(id) = attributes.get(oid_field).and_then(JsonValue::as_i64) {
+            processor.feature_id(&FeatureId::Long(id))?;
+        }
+    }
+    if let Some(attributes) = attributes {
+        processor.properties_begin()?;
+        process_attributes(attributes, &schema.fields, processor)?;
+        processor.properties_end()?;
+    }
+    if let Some(geometry) = feature.get("geometry").filter(|g| !g.is_null()) {
+        processor.geometry_begin()?;
+        let srid = geometry
+            .as_object()
+            .and_then(spatial_reference)
+            .or(schema.srid);
+        process_geom_n(geometry, 0, srid, processor)?;
+        processor.geometry_end()?;
+    }
+    processor.feature_end(idx as u64)
+}
+
+fn process_attributes<P: FeatureProcessor>(
+    attributes: &Map<String, JsonValue>,
+    fields: &[ColumnDef],
+    processor: &mut P,
+) -> Result<()> {
+    let mut i = 0;
+    for (name, value) in attributes {
+        let column_type = fields
+            .iter()
+            .find(|field| field.name == *name)
+            .map(|field| field.column_type);
+        let abort = match (value, column_type) {
+            (JsonValue::Null, _) => continue,
+            (JsonValue::Number(v), Some(ColumnType::Short)) if v.as_i64().is_some() => {
+                let v = v.as_i64().unwrap_or_default();
+                match i16::try_from(v) {
+                    Ok(v) => processor.property(i, name, &ColumnValue::Short(v))?,
+                    Err(_) => processor.property(i, name, &ColumnValue::Long(v))?,
+                }
+            }
+            (JsonValue::Number(v), Some(ColumnType::Int)) if v.as_i64().is_some() => {
+                let v = v.as_i64().unwrap_or_default();
+                match i32::try_from(v) {
+                    Ok(v) => processor.property(i, name, &ColumnValue::Int(v))?,
+                    Err(_) => processor.property(i, name, &ColumnValue::Long(v))?,
+                }
+            }
+            (JsonValue::Number(v), Some(ColumnType::Float)) => {
+                let v = v.as_f64().unwrap_or(f64::NAN) as f32;
+                processor.property(i, name, &ColumnValue::Float(v))?
+            }
+            (JsonValue::Number(v), Some(ColumnType::Double)) => {
+                let v = v.as_f64().unwrap_or(f64::NAN);
+                processor.property(i, name, &ColumnValue::Double(v))?
+            }
+            (JsonValue::Number(v), _) => {
+                if let Some(v) = v.as_i64() {
+                    processor.property(i, name, &ColumnValue::Long(v))?
+                } else if let Some(v) = v.as_u64() {
+                    processor.property(i, name, &ColumnValue::ULong(v))?
+                } else {
+                    let v = v.as_f64().unwrap_or(f64::NAN);
+                    processor.property(i, name, &ColumnValue::Double(v))?
+                }
+            }
+            (JsonValue::String(v), _) => processor.property(i, name, &ColumnValue::String(v))?,
+            (JsonValue::Bool(v), _) => processor.property(i, name, &ColumnValue::Bool(*v))?,
+            (JsonValue::Array(_) | JsonValue::Object(_), _) => {
+                processor.property(i, name, &ColumnValue::Json(&value.to_string()))?
+            }
+        };
+        if abort {
+            break;
+        }
+        i += 1;
+    }
+    Ok(())
+}
+
+/// Process Esri JSON geometry object.
+pub fn process_esrijson_geom<P: GeomProcessor>(
+    geometry: &JsonValue,
+    processor: &mut P,
+) -> Result<()> {
+    let srid = geometry.as_object().and_then(spatial_reference);
+    process_geom_n(geometry, 0, srid, processor)
+}
+
+#[derive(Clone, Copy)]
+struct Coord {
+    x: f64,
+    y: f64,
+    z: Option<f64>,
+    m: Option<f64>,
+}
+
+fn process_geom_n<P: GeomProcessor>(
+    geometry: &JsonValue,
+    idx: usize,
+    srid: Option<i32>,
+    processor: &mut P,
+) -> Result<()> {
+    let geometry = geometry
+        .as_object()
+        .ok_or_else(|| GeozeroError::Geometry("geometry object expected".to_string()))?;
+    if srid.is_some() {
+        processor.srid(srid)?;
+    }
+    let has_z = geometry.get("hasZ").and_then(JsonValue::as_bool);
+    let has_m = geometry.get("hasM").and_then(JsonValue::as_bool);
+    let multi_dim = processor.multi_dim();
+    if let Some(x) = geometry.get("x") {
+        let x = number(x);
+        let y = geometry.get("y").and_then(number);
+        match (x, y) {
+            (Some(x), Some(y)) => {
+                let coord = Coord {
+                    x,
+                    y,
+                    z: geometry.get("z").and_then(number),
+                    m: geometry.get("m").and_then(number),
+                };
+                processor.point_begin(idx)?;
+                process_coord(&coord, multi_dim, 0, processor)?;
+                processor.point_end(idx)
+            }
+            _ => processor.empty_point(idx),
+        }
+    } else if let Some(points) = geometry.get("points") {
+        let points = coords(points, has_z, has_m)?;
+        processor.multipoint_begin(points.len(), idx)?;
+        for (i, coord) in points.iter().enumerate() {
+            process_coord(coord, multi_dim, i, processor)?;
+        }
+        processor.multipoint_end(idx)
+    } else if let Some(paths) = geometry.get("paths") {
+        let paths = parts(paths, has_z, has_m)?;
+        if paths.len() == 1 {
+            process_linestring(&paths[0], true, idx, processor)
+        } else {
+            processor.multilinestring_begin(paths.len(), idx)?;
+            for (i, path) in paths.iter().enumerate() {
+                process_linestring(path, false, i, processor)?;
+            }
+            processor.multilinestring_end(idx)
+        }
+    } else if let Some(rings) = geometry.get("rings") {
+        let polygons = group_rings(parts(rings, has_z, has_m)?);
+        if polygons.len() == 1 {
+            process_polygon(&polygons[0], true, idx, processor)
+        } else {
+            processor.multipolygon_begin(polygons.len(), idx)?;
+            for (i, polygon) in polygons.iter().enumerate() {
+                process_polygon(polygon, false, i, processor)?;
+            }
+            processor.multipolygon_end(idx)
+        }
+    } else if let Some(xmin) = geometry.get("xmin") {
+        let bounds = [
+            number(xmin),
+            geometry.get("ymin").and_then(number),
+            geometry.get("xmax").and_then(number),
+            geometry.get("ymax").and_then(number),
+        ];
+        let ring = match bounds {
+            [Some(xmin), Some(ymin), Some(xmax), Some(ymax)] => [
+                (xmin, ymin),
+                (xmin, ymax),
+                (xmax, ymax),
+                (xmax, ymin),
+                (xmin, ymin),
+            ]
+            .iter()
+            .map(|&(x, y)| Coord {
+                x,
+                y,
+                z: None,
+                m: None,
+            })
+            .collect(),
+            _ => Vec::new(),
+        };
+        let polygon = if ring.is_empty() { vec![] } else { vec![ring] };
+        process_polygon(&polygon, true, idx, processor)
+    } else {
+        Err(GeozeroError::Geometry(
+            "unsupported Esri JSON geometry".to_string(),
+        ))
+    }
+}
+
+/// Number value, `null` and `"NaN"` are treated as missing
+fn number(value: &JsonValue) -> Option<f64> {
+    value.as_f64()
+}
+
+fn coord(value: &JsonValue, has_z: Option<bool>, has_m: Option<bool>) -> Result<Coord> {
+    let ordinates = value
+        .as_array()
+        .ok_or_else(|| GeozeroError::Geometry("coordinate array expected".to_string()))?;
+    let ordinate = |i: usize| ordinates.get(i).and_then(number);
+    let (Some(x), Some(y)) = (ordinate(0), ordinate(1)) else {
+        return Err(GeozeroError::Geometry(
+            "coordinate requires x and y".to_string(),
+        ));
+    };
+    let (z, m) = match (has_z, has_m) {
+        (Some(true), Some(true)) => (ordinate(2), ordinate(3)),
+        (_, Some(true)) => (None, ordinate(2)),
+        (Some(false), _) => (None, None),
+        // without flags, a third ordinate is z
+        _ => (ordinate(2), None),
+    };
+    Ok(Coord { x, y, z, m })
+}
+
+fn coords(value: &JsonValue, has_z: Option<bool>, has_m: Option<bool>) -> Result<Vec<Coord>> {
+    value
+        .as_array()
+        .ok_or_else(|| GeozeroError::Geometry("coordinate list expected".to_string()))?
+        .iter()
+        .map(|c| coord(c, has_z, has_m))
+        .collect()
+}
+
+fn parts(value: &JsonValue, has_z: Option<bool>, has_m: Option<bool>) -> Result<Vec<Vec<Coord>>> {
+    value
+        .as_array()
+        .ok_or_else(|| GeozeroError::Geometry("list of parts expected".to_string()))?
+        .iter()
+        .map(|part| coords(part, has_z, has_m))
+        .collect()
+}
+
+/// Twice the signed area, negative for clockwise rings
+fn signed_area(ring: &[Coord]) -> f64 {
+    ring.iter()
+        .zip(ring.iter().skip(1))
+        .map(|(a, b)| a.x * b.y - b.x * a.y)
+        .sum()
+}
+
+fn contains(ring: &[Coord], point: &Coord) -> bool {
+    let mut inside = false;
+    for (a, b) in ring.iter().zip(ring.iter().skip(1)) {
+        if (a.y > point.y) != (b.y > point.y)
+            && point.x < (b.x - a.x) * (point.y - a.y) / (b.y - a.y) + a.x
+        {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+/// Whether `point` lies on a segment of `ring`
+fn on_boundary(ring: &[Coord], point: &Coord) -> bool {
+    ring.iter().zip(ring.iter().skip(1)).any(|(a, b)| {
+        (b.x - a.x) * (point.y - a.y) == (point.x - a.x) * (b.y - a.y)
+            && point.x >= a.x.min(b.x)
+            && point.x <= a.x.max(b.x)
+            && point.y >= a.y.min(b.y)
+            && point.y <= a.y.max(b.y)
+    })
+}
+
+/// Whether `hole` lies inside `exterior`, tested with a hole vertex not on the exterior boundary
+fn contains_ring(exterior: &[Coord], hole: &[Coord]) -> bool {
+    hole.iter()
+        .find(|point| !on_boundary(exterior, point))
+        .is_some_and(|point| contains(exterior, point))
+}
+
+/// Group rings into polygons.
+///
+/// Clockwise rings are exterior rings, counterclockwise rings are holes of the smallest
+/// exterior ring containing them.
+fn group_rings(rings: Vec<Vec<Coord>>) -> Vec<Vec<Vec<Coord>>> {
+    let mut polygons: Vec<Vec<Vec<Coord>>> = Vec::new();
+    let mut holes = Vec::new();
+    for ring in rings {
+        if signed_area(&ring) <= 0.0 {
+            polygons.push(vec![ring]);
+        } else {
+            holes.push(ring);
+        }
+    }
+    for hole in holes {
+        let outer = polygons
+            .iter()
+            .enumerate()
+            .filter(|(_, polygon)| contains_ring(&polygon[0], &hole))
+            .min_by(|(_, a), (_, b)| {
+                signed_area(&a[0])
+                    .abs()
+                    .total_cmp(&signed_area(&b[0]).abs())
+            })
+            .map(|(i, _)| i);
+        match outer {
+            Some(i) => polygons[i].push(hole),
+            // hole without exterior ring
+            None => polygons.push(vec![hole]),
+        }
+    }
+    polygons
+}
+
+fn process_coord<P: GeomProcessor>(
+    coord: &Coord,
+    multi_dim: bool,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    if multi_dim {
+        processor.coordinate(coord.x, coord.y, coord.z, coord.m, None, None, idx)
+    } else {
+        processor.xy(coord.x, coord.y, idx)
+    }
+}
+
+fn process_linestring<P: GeomProcessor>(
+    coords: &[Coord],
+    tagged: bool,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    processor.linestring_begin(tagged, coords.len(), idx)?;
+    let multi_dim = processor.multi_dim();
+    for (i, coord) in coords.iter().enumerate() {
+        process_coord(coord, multi_dim, i, processor)?;
+    }
+    processor.linestring_end(tagged, idx)
+}
+
+fn process_polygon<P: GeomProcessor>(
+    rings: &[Vec<Coord>],
+    tagged: bool,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    processor.polygon_begin(tagged, rings.len(), idx)?;
+    for (i, ring) in rings.iter().enumerate() {
+        process_linestring(ring, false, i, processor)?;
+    }
+    processor.polygon_end(tagged, idx)
+}
+
+#[cfg(test)]
+#[cfg(all(feature = "with-geojson", feature = "with-wkt"))]
+mod test {
+    use super::*;
+    use crate::wkt::WktWriter;
+    use crate::ToWkt;
+
+    #[test]
+    fn point() -> Result<()> {
+        let esri = EsriJson(r#"{"x": -118.15, "y": 33.80, "spatialReference": {"wkid": 4326}}"#);
+        assert_eq!(esri.to_wkt()?, "POINT(-118.15 33.8)");
+        assert_eq!(EsriJson(r#"{"x": null}"#).to_wkt()?, "POINT EMPTY");
+        Ok(())
+    }
+
+    #[test]
+    fn polyline() -> Result<()> {
+        let esri = EsriJson(r#"{"hasM": true, "paths": [[[1, 2, 5], [3, 4, 6]]]}"#);
+        let mut out: Vec<u8> = Vec::new();
+        esri.process_geom(&mut WktWriter::with_dims(
+            &mut out,
+            crate::CoordDimensions::xym(),
+        ))?;
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            "LINESTRING(1 2 5,3 4 6)"
+        );
+        let esri = EsriJson(r#"{"paths": [[[1, 2], [3, 4]], [[5, 6], [7, 8]]]}"#);
+        assert_eq!(esri.to_wkt()?, "MULTILINESTRING((1 2,3 4),(5 6,7 8))");
+        Ok(())
+    }
+
+    #[test]
+    fn polygon_rings() -> Result<()> {
+        // exterior ring (clockwise), hole (counterclockwise), second exterior ring
+        let esri = EsriJson(
+            r#"{"rings": [
+                [[0, 0], [0, 10], [10, 10], [10, 0], [0, 0]],
+                [[2, 2], [4, 2], [4, 4], [2, 4], [2, 2]],
+                [[20, 0], [20, 10], [30, 10], [30, 0], [20, 0]]
+            ]}"#,
+        );
+        assert_eq!(
+            esri.to_wkt()?,
+            "MULTIPOLYGON(((0 0,0 10,10 10,10 0,0 0),(2 2,4 2,4 4,2 4,2 2)),((20 0,20 10,30 10,30 0,20 0)))"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn nested_polygon_rings() -> Result<()> {
+        // island with a hole inside the hole of an exterior ring, hole touching the exterior ring
+        let esri = EsriJson(
+            r#"{"rings": [
+                [[0, 0], [0, 10], [10, 10], [10, 0], [0, 0]],
+                [[1, 1], [9, 1], [9, 9], [1, 9], [1, 1]],
+                [[2, 2], [2, 8], [8, 8], [8, 2], [2, 2]],
+                [[4, 4], [6, 4], [6, 6], [4, 6], [4, 4]],
+                [[20, 0], [20, 10], [30, 10], [30, 0], [20, 0]],
+                [[30, 5], [27, 6], [27, 4], [30, 5]]
+            ]}"#,
+        );
+        assert_eq!(
+            esri.to_wkt()?,
+            "MULTIPOLYGON(((0 0,0 10,10 10,10 0,0 0),(1 1,9 1,9 9,1 9,1 1)),((2 2,2 8,8 8,8 2,2 2),(4 4,6 4,6 6,4 6,4 4)),((20 0,20 10,30 10,30 0,20 0),(30 5,27 6,27 4,30 5)))"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn feature_set() -> Result<()> {
+        let esri = r#"{
+            "objectIdFieldName": "OBJECTID",
+            "geometryType": "esriGeometryPoint",
+            "spatialReference": {"wkid": 102100, "latestWkid": 3857},
+            "fields": [
+                {"name": "OBJECTID", "type": "esriFieldTypeOID"},
+                {"name": "NAME", "type": "esriFieldTypeString"},
+                {"name": "POP", "type": "esriFieldTypeInteger"}
+            ],
+            "features": [
+                {"attributes": {"OBJECTID": 1, "NAME": "Zurich", "POP": 421878}, "geometry": {"x": 949000, "y": 6004000}},
+                {"attributes": {"OBJECTID": 2, "NAME": "Bern", "POP": null}, "geometry": null}
+            ]
+        }"#;
+        let mut out: Vec<u8> = Vec::new();
+        read_esrijson(
+            esri.as_bytes(),
+            &mut crate::geojson::GeoJsonWriter::new(&mut out),
+        )?;
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            r#"{
+"type": "FeatureCollection",
+"features": [{"type": "Feature", "id": 1, "properties": {"NAME": "Zurich", "OBJECTID": 1, "POP": 421878}, "geometry": {"type": "Point", "coordinates": [949000,6004000]}},
+{"type": "Feature", "id": 2, "properties": {"NAME": "Bern", "OBJECTID": 2}}]}"#
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,511 @@
+use crate::error::{GeozeroError, Result};
+use crate::{
+    ColumnDef, ColumnType, ColumnValue, CoordDimensions, FeatureProcessor, GeomProcessor,
+    PropertyProcessor,
+};
+use std::io::Write;
+
+/// Esri JSON writer.
+///
+/// Writes a featureSet for datasources and a geometry object for single geometries.
+/// Polygon rings are oriented as required by Esri JSON (exterior rings clockwise).
+/// Geometry collections are not supported.
+pub struct EsriJsonWriter<W: Write> {
+    dims: CoordDimensions,
+    pub(crate) out: W,
+    /// SRID of current geometry
+    srid: Option<i32>,
+    /// SRID of first feature, written as featureSet spatial reference
+    dataset_srid: Option<i32>,
+    /// Geometry type of first feature
+    geometry_type: Option<GeometryType>,
+    /// Declared or collected fields
+    fields: Vec<ColumnDef>,
+    has_schema: bool,
+    /// First member of current feature or attribute object
+    first_member: bool,
+    // current geometry
+    kind: Option<GeometryType>,
+    parts: Vec<Vec<Coord>>,
+    /// Parts which are interior rings
+    interior: Vec<bool>,
+    depth: usize,
+    in_polygon: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum GeometryType {
+    Point,
+    Multipoint,
+    Polyline,
+    Polygon,
+}
+
+impl GeometryType {
+    fn esri_name(self) -> &'static str {
+        match self {
+            GeometryType::Point => "esriGeometryPoint",
+            GeometryType::Multipoint => "esriGeometryMultipoint",
+            GeometryType::Polyline => "esriGeometryPolyline",
+            GeometryType::Polygon => "esriGeometryPolygon",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Coord {
+    x: f64,
+    y: f64,
+    z: Option<f64>,
+    m: Option<f64>,
+}
+
+impl<W: Write> EsriJsonWriter<W> {
+    pub fn new(out: W) -> Self {
+        Self::with_dims(out, CoordDimensions::default())
+    }
+    pub fn with_dims(out: W, dims: CoordDimensions) -> Self {
+        EsriJsonWriter {
+            dims,
+            out,
+            srid: None,
+            dataset_srid: None,
+            geometry_type: None,
+            fields: Vec::new(),
+            has_schema: false,
+            first_member: true,
+            kind: None,
+            parts: Vec::new(),
+            interior: Vec::new(),
+            depth: 0,
+            in_polygon: false,
+        }
+    }
+
+    fn member(&mut self, name: &str) -> Result<()> {
+        if !self.first_member {
+            self.out.write_all(b", ")?;
+        }
+        self.first_member = false;
+        write!(self.out, "\"{name}\": ")?;
+        Ok(())
+    }
+
+    fn begin(&mut self, kind: GeometryType) -> Result<()> {
+        if self.depth == 0 {
+            self.kind = Some(kind);
+            self.parts.clear();
+            self.interior.clear();
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    fn end(&mut self) -> Result<()> {
+        self.depth -= 1;
+        if self.depth == 0 {
+            self.write_geometry()?;
+        }
+        Ok(())
+    }
+
+    fn push_coord(&mut self, coord: Coord) {
+        match self.parts.last_mut() {
+            Some(part) => part.push(coord),
+            None => {
+                self.parts.push(vec![coord]);
+                self.interior.push(false);
+            }
+        }
+    }
+
+    fn write_geometry(&mut self) -> Result<()> {
+        let Some(kind) = self.kind.take() else {
+            return Ok(());
+        };
+        if self.geometry_type.is_none() {
+            self.geometry_type = Some(kind);
+            self.dataset_srid = self.srid;
+        }
+        let coords = || self.parts.iter().flatten();
+        let has_z = self.dims.z && coords().next().is_some() && coords().all(|c| c.z.is_some());
+        let has_m = self.dims.m && coords().next().is_some() && coords().all(|c| c.m.is_some());
+        self.out.write_all(b"{")?;
+        match kind {
+            GeometryType::Point => match self.parts.first().and_then(|part| part.first()) {
+                Some(coord) => {
+                    write!(self.out, "\"x\": {}, \"y\": {}", coord.x, coord.y)?;
+                    if let (true, Some(z)) = (has_z, coord.z) {
+                        write!(self.out, ", \"z\": {z}")?;
+                    }
+                    if let (true, Some(m)) = (has_m, coord.m) {
+                        write!(self.out, ", \"m\": {m}")?;
+                    }
+                }
+                None => self.out.write_all(br#""x": null"#)?,
+            },
+            _ => {
+                if has_z {
+                    self.out.write_all(br#""hasZ": true, "#)?;
+                }
+                if has_m {
+                    self.out.write_all(br#""hasM": true, "#)?;
+                }
+                let member = match kind {
+                    GeometryType::Multipoint => "points",
+                    GeometryType::Polyline => "paths",
+                    _ => "rings",
+                };
+                write!(self.out, "\"{member}\": [")?;
+                if kind == GeometryType::Multipoint {
+                    let points: Vec<Coord> = self.parts.iter().flatten().copied().collect();
+                    write_coords(&mut self.out, &points, has_z, has_m)?;
+                } else {
+                    for (i, part) in self.parts.iter_mut().enumerate() {
+                        if kind == GeometryType::Polygon {
+                            // exterior rings clockwise, interior rings counterclockwise
+                            let clockwise = signed_area(part) < 0.0;
+                            if clockwise == self.interior[i] {
+                                part.reverse();
+                            }
+                        }
+                        if i > 0 {
+                            self.out.write_all(b",")?;
+                        }
+                        self.out.write_all(b"[")?;
+                        write_coords(&mut self.out, part, has_z, has_m)?;
+                        self.out.write_all(b"]")?;
+                    }
+                }
+                self.out.write_all(b"]")?;
+            }
+        }
+        if let Some(srid) = self.srid {
+            write!(self.out, ", \"spatialReference\": {{\"wkid\": {srid}}}")?;
+        }
+        self.out.write_all(b"}")?;
+        self.parts.clear();
+        self.interior.clear();
+        Ok(())
+    }
+
+    fn write_fields(&mut self) -> Result<()> {
+        self.out.write_all(b", \"fields\": [")?;
+        for (i, field) in self.fields.iter().enumerate() {
+            if i > 0 {
+                self.out.write_all(b", ")?;
+            }
+            let field_type = match field.column_type {
+                ColumnType::Bool | ColumnType::Byte | ColumnType::UByte | ColumnType::Short => {
+                    "esriFieldTypeSmallInteger"
+                }
+                ColumnType::UShort | ColumnType::Int => "esriFieldTypeInteger",
+                ColumnType::UInt | ColumnType::Long | ColumnType::ULong => {
+                    "esriFieldTypeBigInteger"
+                }
+                ColumnType::Float => "esriFieldTypeSingle",
+                ColumnType::Double => "esriFieldTypeDouble",
+                ColumnType::Binary => "esriFieldTypeBlob",
+                ColumnType::Uuid => "esriFieldTypeGUID",
+                ColumnType::String
+                | ColumnType::Json
+                | ColumnType::DateTime
+                | ColumnType::Decimal
+                | ColumnType::Array => "esriFieldTypeString",
+            };
+            write!(
+                self.out,
+                "{{\"name\": {}, \"type\": \"{field_type}\"}}",
+                json_string(&field.name)?
+            )?;
+        }
+        self.out.write_all(b"]")?;
+        Ok(())
+    }
+}
+
+fn json_string(s: &str) -> Result<String> {
+    serde_json::to_string(s).map_err(|e| GeozeroError::Property(e.to_string()))
+}
+
+fn write_coords<W: Write>(out: &mut W, coords: &[Coord], has_z: bool, has_m: bool) -> Result<()> {
+    for (i, coord) in coords.iter().enumerate() {
+        if i > 0 {
+            out.write_all(b",")?;
+        }
+        write!(out, "[{},{}", coord.x, coord.y)?;
+        if has_z {
+            write!(out, ",{}", coord.z.unwrap_or_default())?;
+        }
+        if has_m {
+            write!(out, ",{}", coord.m.unwrap_or_default())?;
+        }
+        out.write_all(b"]")?;
+    }
+    Ok(())
+}
+
+/// Twice the signed area, negative for clockwise rings
+fn signed_area(ring: &[Coord]) -> f64 {
+    ring.iter()
+        .zip(ring.iter().skip(1))
+        .map(|(a, b)| a.x * b.y - b.x * a.y)
+        .sum()
+}
+
+impl<W: Write> FeatureProcessor for EsriJsonWriter<W> {
+    fn dataset_begin(&mut self, _name: Option<&str>) -> Result<()> {
+        self.out.write_all(b"{\"features\": [\n")?;
+        Ok(())
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        self.out.write_all(b"]")?;
+        if let Some(geometry_type) = self.geometry_type {
+            write!(
+                self.out,
+                ", \"geometryType\": \"{}\"",
+                geometry_type.esri_name()
+            )?;
+        }
+        if let Some(srid) = self.dataset_srid {
+            write!(self.out, ", \"spatialReference\": {{\"wkid\": {srid}}}")?;
+        }
+        self.write_fields()?;
+        self.out.write_all(b"}")?;
+        Ok(())
+    }
+    fn schema_begin(&mut self, columns: &[ColumnDef]) -> Result<()> {
+        self.fields = columns
+            .iter()
+            .filter(|column| column.column_type != ColumnType::Binary)
+            .cloned()
+            .collect();
+        self.has_schema = true;
+        Ok(())
+    }
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        if idx > 0 {
+            self.out.write_all(b",\n")?;
+        }
+        self.out.write_all(b"{")?;
+        self.first_member = true;
+        self.srid = None;
+        Ok(())
+    }
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        self.out.write_all(b"}")?;
+        Ok(())
+    }
+    fn properties_begin(&mut self) -> Result<()> {
+        self.member("attributes")?;
+        self.out.write_all(b"{")?;
+        self.first_member = true;
+        Ok(())
+    }
+    fn properties_end(&mut self) -> Result<()> {
+        self.out.write_all(b"}")?;
+        self.first_member = false;
+        Ok(())
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        self.member("geometry")?;
+        Ok(())
+    }
+}
+
+impl<W: Write> PropertyProcessor for EsriJsonWriter<W> {
+    fn property(&mut self, _idx: usize, colname: &str, colval: &ColumnValue) -> Result<bool> {
+        if matches!(colval, ColumnValue::Binary(_)) {
+            // no JSON representation
+            return Ok(false);
+        }
+        if !self.has_schema && !self.fields.iter().any(|field| field.name == colname) {
+            self.fields
+                .push(ColumnDef::new(colname, colval.column_type()));
+        }
+        if !self.first_member {
+            self.out.write_all(b", ")?;
+        }
+        self.first_member = false;
+        write!(self.out, "{}: ", json_string(colname)?)?;
+        match colval {
+            ColumnValue::Bool(v) => write!(self.out, "{}", u8::from(*v))?,
+            ColumnValue::String(v) | ColumnValue::DateTime(v) => {
+                self.out.write_all(json_string(v)?.as_bytes())?
+            }
+            ColumnValue::Json(v) | ColumnValue::Decimal(v) => {
+                self.out.write_all(json_string(v)?.as_bytes())?
+            }
+            // Esri GUID format
+            ColumnValue::Uuid(_) => {
+                write!(self.out, "\"{{{}}}\"", colval.to_string().to_uppercase())?
+            }
+            ColumnValue::Array(_) => self
+                .out
+                .write_all(json_string(&colval.to_string())?.as_bytes())?,
+            _ => write!(self.out, "{colval}")?,
+        }
+        Ok(false)
+    }
+}
+
+impl<W: Write> GeomProcessor for EsriJsonWriter<W> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.dims
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.srid = srid.filter(|srid| *srid != 0);
+        Ok(())
+    }
+    fn xy(&mut self, x: f64, y: f64, _idx: usize) -> Result<()> {
+        self.push_coord(Coord {
+            x,
+            y,
+            z: None,
+            m: None,
+        });
+        Ok(())
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        _idx: usize,
+    ) -> Result<()> {
+        if (self.dims.t && t.is_some()) || (self.dims.tm && tm.is_some()) {
+            return Err(GeozeroError::Geometry(
+                "Esri JSON doesn't support T and TM dimensions".to_string(),
+            ));
+        }
+        self.push_coord(Coord { x, y, z, m });
+        Ok(())
+    }
+    fn empty_point(&mut self, _idx: usize) -> Result<()> {
+        self.begin(GeometryType::Point)?;
+        self.end()
+    }
+    fn point_begin(&mut self, _idx: usize) -> Result<()> {
+        self.begin(GeometryType::Point)
+    }
+    fn point_end(&mut self, _idx: usize) -> Result<()> {
+        self.end()
+    }
+    fn multipoint_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(GeometryType::Multipoint)
+    }
+    fn multipoint_end(&mut self, _idx: usize) -> Result<()> {
+        self.end()
+    }
+    fn linestring_begin(&mut self, _tagged: bool, _size: usize, idx: usize) -> Result<()> {
+        self.begin(GeometryType::Polyline)?;
+        self.parts.push(Vec::new());
+        self.interior.push(self.in_polygon && idx > 0);
+        Ok(())
+    }
+    fn linestring_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        self.end()
+    }
+    fn multilinestring_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(GeometryType::Polyline)
+    }
+    fn multilinestring_end(&mut self, _idx: usize) -> Result<()> {
+        self.end()
+    }
+    fn polygon_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        self.in_polygon = true;
+        self.begin(GeometryType::Polygon)
+    }
+    fn polygon_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        self.in_polygon = false;
+        self.end()
+    }
+    fn multipolygon_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(GeometryType::Polygon)
+    }
+    fn multipolygon_end(&mut self, _idx: usize) -> Result<()> {
+        self.end()
+    }
+    fn geometrycollection_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        Err(GeozeroError::Geometry(
+            "Esri JSON doesn't support geometry collections".to_string(),
+        ))
+    }
+}
+
+#[cfg(test)]
+#[cfg(all(feature = "with-geojson", feature = "with-wkt"))]
+mod test {
+    use super::*;
+    use crate::esrijson::EsriJson;
+    use crate::geojson::{GeoJson, GeoJsonWriter};
+    use crate::wkt::Wkt;
+    use crate::{GeozeroDatasource, GeozeroGeometry, ToEsriJson};
+
+    #[test]
+    fn geometries() -> Result<()> {
+        assert_eq!(Wkt("POINT(1 2)").to_esrijson()?, r#"{"x": 1, "y": 2}"#);
+        assert_eq!(Wkt("POINT EMPTY").to_esrijson()?, r#"{"x": null}"#);
+        assert_eq!(
+            Wkt("LINESTRING(1 2,3 4)").to_esrijson()?,
+            r#"{"paths": [[[1,2],[3,4]]]}"#
+        );
+        assert_eq!(
+            Wkt("MULTIPOINT(1 2,3 4)").to_esrijson()?,
+            r#"{"points": [[1,2],[3,4]]}"#
+        );
+        assert!(Wkt("GEOMETRYCOLLECTION(POINT(1 2))").to_esrijson().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn ring_orientation() -> Result<()> {
+        // counterclockwise exterior ring, clockwise hole
+        let wkt = Wkt("POLYGON((0 0,10 0,10 10,0 10,0 0),(2 2,2 4,4 4,4 2,2 2))");
+        assert_eq!(
+            wkt.to_esrijson()?,
+            r#"{"rings": [[[0,0],[0,10],[10,10],[10,0],[0,0]],[[2,2],[4,2],[4,4],[2,4],[2,2]]]}"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn z_values() -> Result<()> {
+        let mut out: Vec<u8> = Vec::new();
+        Wkt("LINESTRING Z(1 2 3,4 5 6)").process_geom(&mut EsriJsonWriter::with_dims(
+            &mut out,
+            CoordDimensions::xyz(),
+        ))?;
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            r#"{"hasZ": true, "paths": [[[1,2,3],[4,5,6]]]}"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn feature_set_roundtrip() -> Result<()> {
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"name": "A \"quoted\" name", "pop": 10}, "geometry": {"type": "Point", "coordinates": [1, 2]}},
+            {"type": "Feature", "properties": {"name": "B", "pop": 20}, "geometry": {"type": "Point", "coordinates": [3, 4]}}
+        ]}"#;
+        let mut esri: Vec<u8> = Vec::new();
+        GeoJson(geojson).process(&mut EsriJsonWriter::new(&mut esri))?;
+        assert_eq!(
+            std::str::from_utf8(&esri).unwrap(),
+            r#"{"features": [
+{"attributes": {"name": "A \"quoted\" name", "pop": 10}, "geometry": {"x": 1, "y": 2}},
+{"attributes": {"name": "B", "pop": 20}, "geometry": {"x": 3, "y": 4}}], "geometryType": "esriGeometryPoint", "fields": [{"name": "name", "type": "esriFieldTypeString"}, {"name": "pop", "type": "esriFieldTypeBigInteger"}]}"#
+        );
+
+        let mut json: Vec<u8> = Vec::new();
+        EsriJson(&esri).process(&mut GeoJsonWriter::new(&mut json))?;
+        let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        let expected: serde_json::Value = serde_json::from_str(geojson).unwrap();
+        assert_eq!(json, expected);
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+//! Esri JSON conversions.
+//!
+//! Geometry and featureSet encoding of the ArcGIS REST API.
+//! <https://developers.arcgis.com/documentation/common-data-types/geometry-objects.htm>
+pub(crate) mod esrijson_reader;
+pub(crate) mod esrijson_writer;
+
+pub use esrijson_reader::*;
+pub use esrijson_writer::*;
+
+pub(crate) mod conversion {
+    use crate::error::Result;
+    use crate::esrijson::EsriJsonWriter;
+    use crate::{GeozeroDatasource, GeozeroGeometry};
+
+    /// Convert to Esri JSON.
+    pub trait ToEsriJson {
+        /// Convert to Esri JSON geometry String.
+        fn to_esrijson(&self) -> Result<String>;
+    }
+
+    impl<T: GeozeroGeometry> ToEsriJson for T {
+        fn to_esrijson(&self) -> Result<String> {
+            let mut out: Vec<u8> = Vec::new();
+            let mut p = EsriJsonWriter::new(&mut out);
+            self.process_geom(&mut p)?;
+            String::from_utf8(out).map_err(|_| {
+                crate::error::GeozeroError::Geometry("Invalid UTF-8 encoding".to_string())
+            })
+        }
+    }
+
+    /// Consume features as Esri JSON.
+    pub trait ProcessToEsriJson {
+        /// Consume features as Esri JSON featureSet String.
+        fn to_esrijson(&mut self) -> Result<String>;
+    }
+
+    impl<T: GeozeroDatasource> ProcessToEsriJson for T {
+        fn to_esrijson(&mut self) -> Result<String> {
+            let mut out: Vec<u8> = Vec::new();
+            let mut p = EsriJsonWriter::new(&mut out);
+            self.process(&mut p)?;
+            String::from_utf8(out).map_err(|_| {
+                crate::error::GeozeroError::Geometry("Invalid UTF-8 encoding".to_string())
+            })
+        }
+    }
+}
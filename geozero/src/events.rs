@@ -0,0 +1,613 @@
+//! Event based processing API.
+//!
+//! Instead of implementing one method per geometry event like [GeomProcessor], a
+//! [GeomEventProcessor] receives all events as [Event] values. The adapters
+//! [EventToGeomProcessor], [EventToFeatureProcessor] and [GeomProcessorToEvent] bridge between
+//! both APIs.
+use crate::error::Result;
+use crate::{
+    ColumnDef, ColumnValue, CoordDimensions, Crs, FeatureId, FeatureProcessor, GeomProcessor,
+    PropertyProcessor,
+};
+
+/// Processing event
+///
+/// Each variant corresponds to a [FeatureProcessor], [PropertyProcessor] or [GeomProcessor]
+/// method with the same parameters.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Event<'a> {
+    DatasetBegin(Option<&'a str>),
+    DatasetEnd,
+    Crs(&'a Crs),
+    SchemaBegin(&'a [ColumnDef]),
+    FeatureBegin(u64),
+    FeatureId(FeatureId<'a>),
+    FeatureEnd(u64),
+    PropertiesBegin,
+    Property {
+        idx: usize,
+        name: &'a str,
+        value: &'a ColumnValue<'a>,
+    },
+    PropertiesEnd,
+    GeometryBegin,
+    GeometryEnd,
+    Srid(Option<i32>),
+    Envelope {
+        envelope: &'a [f64],
+        dims: CoordDimensions,
+        idx: usize,
+    },
+    SizeHintTotalCoords(usize),
+    Xy {
+        x: f64,
+        y: f64,
+        idx: usize,
+    },
+    Coordinate {
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    },
+    EmptyPoint(usize),
+    PointBegin(usize),
+    PointEnd(usize),
+    MultiPointBegin {
+        size: usize,
+        idx: usize,
+    },
+    MultiPointEnd(usize),
+    LineStringBegin {
+        tagged: bool,
+        size: usize,
+        idx: usize,
+    },
+    LineStringEnd {
+        tagged: bool,
+        idx: usize,
+    },
+    MultiLineStringBegin {
+        size: usize,
+        idx: usize,
+    },
+    MultiLineStringEnd(usize),
+    PolygonBegin {
+        tagged: bool,
+        size: usize,
+        idx: usize,
+    },
+    PolygonEnd {
+        tagged: bool,
+        idx: usize,
+    },
+    MultiPolygonBegin {
+        size: usize,
+        idx: usize,
+    },
+    MultiPolygonEnd(usize),
+    GeometryCollectionBegin {
+        size: usize,
+        idx: usize,
+    },
+    GeometryCollectionEnd(usize),
+    CircularStringBegin {
+        size: usize,
+        idx: usize,
+    },
+    CircularStringEnd(usize),
+    CompoundCurveBegin {
+        size: usize,
+        idx: usize,
+    },
+    CompoundCurveEnd(usize),
+    CurvePolygonBegin {
+        size: usize,
+        idx: usize,
+    },
+    CurvePolygonEnd(usize),
+    MultiCurveBegin {
+        size: usize,
+        idx: usize,
+    },
+    MultiCurveEnd(usize),
+    MultiSurfaceBegin {
+        size: usize,
+        idx: usize,
+    },
+    MultiSurfaceEnd(usize),
+    TriangleBegin {
+        tagged: bool,
+        size: usize,
+        idx: usize,
+    },
+    TriangleEnd {
+        tagged: bool,
+        idx: usize,
+    },
+    PolyhedralSurfaceBegin {
+        size: usize,
+        idx: usize,
+    },
+    PolyhedralSurfaceEnd(usize),
+    TinBegin {
+        size: usize,
+        idx: usize,
+    },
+    TinEnd(usize),
+}
+
+impl Event<'_> {
+    /// Call the corresponding [FeatureProcessor] method
+    ///
+    /// Returns `true` if a [Event::Property] requests aborting property processing.
+    pub fn process_feature<P: FeatureProcessor>(&self, processor: &mut P) -> Result<bool> {
+        match *self {
+            Event::DatasetBegin(name) => processor.dataset_begin(name)?,
+            Event::DatasetEnd => processor.dataset_end()?,
+            Event::Crs(crs) => processor.crs(crs)?,
+            Event::SchemaBegin(columns) => processor.schema_begin(columns)?,
+            Event::FeatureBegin(idx) => processor.feature_begin(idx)?,
+            Event::FeatureId(id) => processor.feature_id(&id)?,
+            Event::FeatureEnd(idx) => processor.feature_end(idx)?,
+            Event::PropertiesBegin => processor.properties_begin()?,
+            Event::Property { idx, name, value } => return processor.property(idx, name, value),
+            Event::PropertiesEnd => processor.properties_end()?,
+            Event::GeometryBegin => processor.geometry_begin()?,
+            Event::GeometryEnd => processor.geometry_end()?,
+            _ => self.process(processor)?,
+        }
+        Ok(false)
+    }
+
+    /// Call the corresponding [GeomProcessor] method
+    ///
+    /// Feature and property events are ignored.
+    pub fn process<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
+        match *self {
+            Event::DatasetBegin(_)
+            | Event::DatasetEnd
+            | Event::Crs(_)
+            | Event::SchemaBegin(_)
+            | Event::FeatureBegin(_)
+            | Event::FeatureId(_)
+            | Event::FeatureEnd(_)
+            | Event::PropertiesBegin
+            | Event::Property { .. }
+            | Event::PropertiesEnd
+            | Event::GeometryBegin
+            | Event::GeometryEnd => Ok(()),
+            Event::Srid(srid) => processor.srid(srid),
+            Event::Envelope {
+                envelope,
+                dims,
+                idx,
+            } => processor.envelope(envelope, dims, idx),
+            Event::SizeHintTotalCoords(count) => processor.size_hint_total_coords(count),
+            Event::Xy { x, y, idx } => processor.xy(x, y, idx),
+            Event::Coordinate {
+                x,
+                y,
+                z,
+                m,
+                t,
+                tm,
+                idx,
+            } => processor.coordinate(x, y, z, m, t, tm, idx),
+            Event::EmptyPoint(idx) => processor.empty_point(idx),
+            Event::PointBegin(idx) => processor.point_begin(idx),
+            Event::PointEnd(idx) => processor.point_end(idx),
+            Event::MultiPointBegin { size, idx } => processor.multipoint_begin(size, idx),
+            Event::MultiPointEnd(idx) => processor.multipoint_end(idx),
+            Event::LineStringBegin { tagged, size, idx } => {
+                processor.linestring_begin(tagged, size, idx)
+            }
+            Event::LineStringEnd { tagged, idx } => processor.linestring_end(tagged, idx),
+            Event::MultiLineStringBegin { size, idx } => processor.multilinestring_begin(size, idx),
+            Event::MultiLineStringEnd(idx) => processor.multilinestring_end(idx),
+            Event::PolygonBegin { tagged, size, idx } => processor.polygon_begin(tagged, size, idx),
+            Event::PolygonEnd { tagged, idx } => processor.polygon_end(tagged, idx),
+            Event::MultiPolygonBegin { size, idx } => processor.multipolygon_begin(size, idx),
+            Event::MultiPolygonEnd(idx) => processor.multipolygon_end(idx),
+            Event::GeometryCollectionBegin { size, idx } => {
+                processor.geometrycollection_begin(size, idx)
+            }
+            Event::GeometryCollectionEnd(idx) => processor.geometrycollection_end(idx),
+            Event::CircularStringBegin { size, idx } => processor.circularstring_begin(size, idx),
+            Event::CircularStringEnd(idx) => processor.circularstring_end(idx),
+            Event::CompoundCurveBegin { size, idx } => processor.compoundcurve_begin(size, idx),
+            Event::CompoundCurveEnd(idx) => processor.compoundcurve_end(idx),
+            Event::CurvePolygonBegin { size, idx } => processor.curvepolygon_begin(size, idx),
+            Event::CurvePolygonEnd(idx) => processor.curvepolygon_end(idx),
+            Event::MultiCurveBegin { size, idx } => processor.multicurve_begin(size, idx),
+            Event::MultiCurveEnd(idx) => processor.multicurve_end(idx),
+            Event::MultiSurfaceBegin { size, idx } => processor.multisurface_begin(size, idx),
+            Event::MultiSurfaceEnd(idx) => processor.multisurface_end(idx),
+            Event::TriangleBegin { tagged, size, idx } => {
+                processor.triangle_begin(tagged, size, idx)
+            }
+            Event::TriangleEnd { tagged, idx } => processor.triangle_end(tagged, idx),
+            Event::PolyhedralSurfaceBegin { size, idx } => {
+                processor.polyhedralsurface_begin(size, idx)
+            }
+            Event::PolyhedralSurfaceEnd(idx) => processor.polyhedralsurface_end(idx),
+            Event::TinBegin { size, idx } => processor.tin_begin(size, idx),
+            Event::TinEnd(idx) => processor.tin_end(idx),
+        }
+    }
+}
+
+/// Event based processing trait
+///
+/// # Usage example:
+///
+/// ```rust
+/// use geozero::events::{Event, GeomEventProcessor};
+/// use geozero::error::Result;
+///
+/// struct CoordPrinter;
+///
+/// impl GeomEventProcessor for CoordPrinter {
+///     fn event(&mut self, event: Event) -> Result<()> {
+///         if let Event::Xy { x, y, .. } = event {
+///             println!("({x} {y})");
+///         }
+///         Ok(())
+///     }
+/// }
+/// ```
+pub trait GeomEventProcessor {
+    /// Additional dimensions requested when processing coordinates
+    fn dimensions(&self) -> CoordDimensions {
+        CoordDimensions::xy()
+    }
+
+    /// Process event
+    fn event(&mut self, event: Event) -> Result<()>;
+}
+
+/// Drives a [GeomProcessor] from events.
+pub struct EventToGeomProcessor<P: GeomProcessor> {
+    /// The underlying GeomProcessor
+    pub inner: P,
+}
+
+impl<P: GeomProcessor> EventToGeomProcessor<P> {
+    pub fn new(inner: P) -> Self {
+        Self { inner }
+    }
+
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+}
+
+impl<P: GeomProcessor> GeomEventProcessor for EventToGeomProcessor<P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.inner.dimensions()
+    }
+    fn event(&mut self, event: Event) -> Result<()> {
+        event.process(&mut self.inner)
+    }
+}
+
+/// Drives a [FeatureProcessor] from events.
+///
+/// When the processor requests aborting property processing, following property events of the
+/// feature are skipped.
+pub struct EventToFeatureProcessor<P: FeatureProcessor> {
+    /// The underlying FeatureProcessor
+    pub inner: P,
+    properties_aborted: bool,
+}
+
+impl<P: FeatureProcessor> EventToFeatureProcessor<P> {
+    pub fn new(inner: P) -> Self {
+        Self {
+            inner,
+            properties_aborted: false,
+        }
+    }
+
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+}
+
+impl<P: FeatureProcessor> GeomEventProcessor for EventToFeatureProcessor<P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.inner.dimensions()
+    }
+    fn event(&mut self, event: Event) -> Result<()> {
+        match event {
+            Event::PropertiesBegin => self.properties_aborted = false,
+            Event::Property { .. } if self.properties_aborted => return Ok(()),
+            _ => {}
+        }
+        if event.process_feature(&mut self.inner)? {
+            self.properties_aborted = true;
+        }
+        Ok(())
+    }
+}
+
+/// Emits [FeatureProcessor] and [GeomProcessor] calls as events to a [GeomEventProcessor].
+///
+/// Property processing is never aborted.
+pub struct GeomProcessorToEvent<P: GeomEventProcessor> {
+    /// The underlying GeomEventProcessor
+    pub inner: P,
+}
+
+impl<P: GeomEventProcessor> GeomProcessorToEvent<P> {
+    pub fn new(inner: P) -> Self {
+        Self { inner }
+    }
+
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+}
+
+impl<P: GeomEventProcessor> GeomProcessor for GeomProcessorToEvent<P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.inner.dimensions()
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.inner.event(Event::Srid(srid))
+    }
+    fn envelope(&mut self, envelope: &[f64], dims: CoordDimensions, idx: usize) -> Result<()> {
+        self.inner.event(Event::Envelope {
+            envelope,
+            dims,
+            idx,
+        })
+    }
+    fn size_hint_total_coords(&mut self, count: usize) -> Result<()> {
+        self.inner.event(Event::SizeHintTotalCoords(count))
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.inner.event(Event::Xy { x, y, idx })
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.inner.event(Event::Coordinate {
+            x,
+            y,
+            z,
+            m,
+            t,
+            tm,
+            idx,
+        })
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.inner.event(Event::EmptyPoint(idx))
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.inner.event(Event::PointBegin(idx))
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.event(Event::PointEnd(idx))
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.event(Event::MultiPointBegin { size, idx })
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.event(Event::MultiPointEnd(idx))
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.inner
+            .event(Event::LineStringBegin { tagged, size, idx })
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.inner.event(Event::LineStringEnd { tagged, idx })
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.event(Event::MultiLineStringBegin { size, idx })
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.event(Event::MultiLineStringEnd(idx))
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.inner.event(Event::PolygonBegin { tagged, size, idx })
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.inner.event(Event::PolygonEnd { tagged, idx })
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.event(Event::MultiPolygonBegin { size, idx })
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.event(Event::MultiPolygonEnd(idx))
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner
+            .event(Event::GeometryCollectionBegin { size, idx })
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.event(Event::GeometryCollectionEnd(idx))
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.event(Event::CircularStringBegin { size, idx })
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.event(Event::CircularStringEnd(idx))
+    }
+    fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.event(Event::CompoundCurveBegin { size, idx })
+    }
+    fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.event(Event::CompoundCurveEnd(idx))
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.event(Event::CurvePolygonBegin { size, idx })
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.event(Event::CurvePolygonEnd(idx))
+    }
+    fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.event(Event::MultiCurveBegin { size, idx })
+    }
+    fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.event(Event::MultiCurveEnd(idx))
+    }
+    fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.event(Event::MultiSurfaceBegin { size, idx })
+    }
+    fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.event(Event::MultiSurfaceEnd(idx))
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.inner.event(Event::TriangleBegin { tagged, size, idx })
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.inner.event(Event::TriangleEnd { tagged, idx })
+    }
+    fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner
+            .event(Event::PolyhedralSurfaceBegin { size, idx })
+    }
+    fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.event(Event::PolyhedralSurfaceEnd(idx))
+    }
+    fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.event(Event::TinBegin { size, idx })
+    }
+    fn tin_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.event(Event::TinEnd(idx))
+    }
+}
+
+impl<P: GeomEventProcessor> PropertyProcessor for GeomProcessorToEvent<P> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        self.inner.event(Event::Property { idx, name, value })?;
+        Ok(false)
+    }
+}
+
+impl<P: GeomEventProcessor> FeatureProcessor for GeomProcessorToEvent<P> {
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.inner.event(Event::DatasetBegin(name))
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        self.inner.event(Event::DatasetEnd)
+    }
+    fn crs(&mut self, crs: &Crs) -> Result<()> {
+        self.inner.event(Event::Crs(crs))
+    }
+    fn schema_begin(&mut self, columns: &[ColumnDef]) -> Result<()> {
+        self.inner.event(Event::SchemaBegin(columns))
+    }
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        self.inner.event(Event::FeatureBegin(idx))
+    }
+    fn feature_id(&mut self, id: &FeatureId) -> Result<()> {
+        self.inner.event(Event::FeatureId(*id))
+    }
+    fn feature_end(&mut self, idx: u64) -> Result<()> {
+        self.inner.event(Event::FeatureEnd(idx))
+    }
+    fn properties_begin(&mut self) -> Result<()> {
+        self.inner.event(Event::PropertiesBegin)
+    }
+    fn properties_end(&mut self) -> Result<()> {
+        self.inner.event(Event::PropertiesEnd)
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        self.inner.event(Event::GeometryBegin)
+    }
+    fn geometry_end(&mut self) -> Result<()> {
+        self.inner.event(Event::GeometryEnd)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use super::*;
+    use crate::wkt::{Wkt, WktWriter};
+    use crate::GeozeroGeometry;
+
+    struct EventCollector(Vec<String>);
+
+    impl GeomEventProcessor for EventCollector {
+        fn event(&mut self, event: Event) -> Result<()> {
+            self.0.push(format!("{event:?}"));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn to_events() -> Result<()> {
+        let mut processor = GeomProcessorToEvent::new(EventCollector(Vec::new()));
+        Wkt("LINESTRING(1 2,3 4)").process_geom(&mut processor)?;
+        assert_eq!(
+            processor.into_inner().0,
+            [
+                "LineStringBegin { tagged: true, size: 2, idx: 0 }",
+                "Xy { x: 1.0, y: 2.0, idx: 0 }",
+                "Xy { x: 3.0, y: 4.0, idx: 1 }",
+                "LineStringEnd { tagged: true, idx: 0 }",
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn roundtrip() -> Result<()> {
+        let wkt = "GEOMETRYCOLLECTION(POINT(1 2),POLYGON((0 0,1 0,1 1,0 0)),MULTIPOINT(1 1,2 2))";
+        let mut out = Vec::new();
+        let writer = EventToGeomProcessor::new(WktWriter::new(&mut out));
+        let mut processor = GeomProcessorToEvent::new(writer);
+        Wkt(wkt).process_geom(&mut processor)?;
+        assert_eq!(std::str::from_utf8(&out).unwrap(), wkt);
+        Ok(())
+    }
+
+    #[test]
+    fn coordinate_times() -> Result<()> {
+        let dims = CoordDimensions {
+            t: true,
+            tm: true,
+            ..CoordDimensions::xyz()
+        };
+        let wkt = "LINESTRING(1 2 3 T=2020.5 TM=1000,4 5 6)";
+        let mut out = Vec::new();
+        let writer = EventToGeomProcessor::new(WktWriter::with_dims(&mut out, dims));
+        let mut processor = GeomProcessorToEvent::new(writer);
+        crate::wkt::read_wkt(&mut wkt.as_bytes(), &mut processor)?;
+        assert_eq!(std::str::from_utf8(&out).unwrap(), wkt);
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "with-geojson")]
+    fn feature_roundtrip() -> Result<()> {
+        use crate::geojson::{read_geojson, GeoJsonWriter};
+
+        let geojson = r#"{"type": "FeatureCollection", "features": [{"type": "Feature", "properties": {"name": "a", "num": 1}, "geometry": {"type": "Point", "coordinates": [1,2]}}]}"#;
+        let mut out = Vec::new();
+        let writer = EventToFeatureProcessor::new(GeoJsonWriter::new(&mut out));
+        let mut processor = GeomProcessorToEvent::new(writer);
+        read_geojson(geojson.as_bytes(), &mut processor)?;
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            r#"{
+"type": "FeatureCollection",
+"features": [{"type": "Feature", "properties": {"name": "a", "num": 1}, "geometry": {"type": "Point", "coordinates": [1,2]}}]}"#
+        );
+        Ok(())
+    }
+}
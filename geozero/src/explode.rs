@@ -0,0 +1,490 @@
+//! Splitting of multi-geometries into single-part features.
+use crate::error::Result;
+use crate::events::Event;
+use crate::{
+    ColumnDef, ColumnValue, ColumnValueOwned, CoordDimensions, Crs, FeatureId, FeatureProcessor,
+    GeomProcessor, PropertyProcessor,
+};
+
+/// Buffered property
+struct Property {
+    idx: usize,
+    /// Schema column index, if received with `column_property`
+    column: Option<usize>,
+    name: String,
+    value: ColumnValueOwned,
+}
+
+/// Owned feature id
+enum Id {
+    ULong(u64),
+    Long(i64),
+    String(String),
+}
+
+/// Wraps another feature processor, splitting multi-geometries into single-part features.
+///
+/// Each part of a `MultiPoint`, `MultiLineString`, `MultiPolygon`, `MultiCurve`, `MultiSurface`
+/// or `GeometryCollection` is emitted as its own feature with duplicated properties,
+/// like `ogr2ogr -explodecollections`. Features with single-part geometries are passed unchanged,
+/// empty multi-geometries are emitted as one feature.
+///
+/// Features are buffered and renumbered. Feature ids are only forwarded for features
+/// resulting in a single output feature, since ids of exploded features would not be unique.
+/// Geometry envelopes and size hints are not forwarded.
+///
+/// # Usage example:
+///
+/// ```
+/// # #[cfg(feature = "with-geojson")]
+/// # {
+/// use geozero::geojson::{GeoJson, GeoJsonWriter};
+/// use geozero::{ExplodeMultiGeometries, GeozeroDatasource};
+///
+/// let mut geojson = GeoJson(
+///     r#"{"type": "Feature", "properties": {"name": "islands"}, "geometry": {"type": "MultiPoint", "coordinates": [[1,2],[3,4]]}}"#,
+/// );
+/// let mut out = Vec::new();
+/// let mut processor = ExplodeMultiGeometries::new(GeoJsonWriter::new(&mut out));
+/// geojson.process(&mut processor).unwrap();
+/// assert_eq!(processor.feature_count(), 2);
+/// # }
+/// ```
+pub struct ExplodeMultiGeometries<P> {
+    /// The underlying processor
+    pub inner: P,
+    /// Index of the next output feature
+    feature_idx: u64,
+    id: Option<Id>,
+    properties: Vec<Property>,
+    srid: Option<Option<i32>>,
+    /// Geometry events of the single-part geometries
+    parts: Vec<Vec<Event<'static>>>,
+    /// Begin event of the top-level multi-geometry, if exploding
+    collection: Option<Event<'static>>,
+    depth: usize,
+}
+
+impl<P: FeatureProcessor> ExplodeMultiGeometries<P> {
+    pub fn new(inner: P) -> Self {
+        ExplodeMultiGeometries {
+            inner,
+            feature_idx: 0,
+            id: None,
+            properties: Vec::new(),
+            srid: None,
+            parts: Vec::new(),
+            collection: None,
+            depth: 0,
+        }
+    }
+    /// Number of emitted features
+    pub fn feature_count(&self) -> u64 {
+        self.feature_idx
+    }
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+
+    /// Record begin event. `event` is called with `true`, if the geometry starts a single part
+    /// geometry of an exploded collection.
+    fn begin(
+        &mut self,
+        collection: bool,
+        idx: usize,
+        event: impl Fn(bool, usize) -> Event<'static>,
+    ) -> Result<()> {
+        if self.depth == 0 {
+            if collection {
+                self.collection = Some(event(false, idx));
+            } else {
+                self.parts.push(vec![event(false, idx)]);
+            }
+        } else if self.depth == 1 && self.collection.is_some() {
+            self.parts.push(vec![event(true, 0)]);
+        } else {
+            self.push(event(false, idx));
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    /// Record end event
+    fn end(&mut self, idx: usize, event: impl Fn(bool, usize) -> Event<'static>) -> Result<()> {
+        self.depth = self.depth.saturating_sub(1);
+        if self.depth == 0 {
+            if let Some(begin) = self.collection.take() {
+                if self.parts.is_empty() {
+                    self.parts.push(vec![begin, event(false, idx)]);
+                }
+                return Ok(());
+            }
+        }
+        if self.depth == 1 && self.collection.is_some() {
+            self.push(event(true, 0));
+        } else {
+            self.push(event(false, idx));
+        }
+        Ok(())
+    }
+
+    fn push(&mut self, event: Event<'static>) {
+        if let Some(part) = self.parts.last_mut() {
+            part.push(event);
+        }
+    }
+
+    /// Record coordinate, wrapping points of an exploded collection into a point geometry
+    fn push_coord(&mut self, event: impl Fn(usize) -> Event<'static>, idx: usize) {
+        if self.depth == 1 && self.collection.is_some() {
+            self.parts
+                .push(vec![Event::PointBegin(0), event(0), Event::PointEnd(0)]);
+        } else {
+            self.push(event(idx));
+        }
+    }
+
+    fn emit_feature(&mut self, part: Option<&[Event<'static>]>, with_id: bool) -> Result<()> {
+        let idx = self.feature_idx;
+        self.feature_idx += 1;
+        self.inner.feature_begin(idx)?;
+        if with_id {
+            match &self.id {
+                Some(Id::ULong(id)) => self.inner.feature_id(&FeatureId::ULong(*id))?,
+                Some(Id::Long(id)) => self.inner.feature_id(&FeatureId::Long(*id))?,
+                Some(Id::String(id)) => self.inner.feature_id(&FeatureId::String(id))?,
+                None => {}
+            }
+        }
+        self.inner.properties_begin()?;
+        for prop in &self.properties {
+            let value = prop.value.as_column_value();
+            let abort = match prop.column {
+                Some(column) => self
+                    .inner
+                    .column_property(prop.idx, column, &prop.name, &value)?,
+                None => self.inner.property(prop.idx, &prop.name, &value)?,
+            };
+            if abort {
+                break;
+            }
+        }
+        self.inner.properties_end()?;
+        if let Some(part) = part {
+            self.inner.geometry_begin()?;
+            if let Some(srid) = self.srid {
+                self.inner.srid(srid)?;
+            }
+            for event in part {
+                event.process(&mut self.inner)?;
+            }
+            self.inner.geometry_end()?;
+        }
+        self.inner.feature_end(idx)
+    }
+}
+
+impl<P: FeatureProcessor> GeomProcessor for ExplodeMultiGeometries<P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.inner.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.inner.multi_dim()
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.srid = Some(srid);
+        Ok(())
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.push_coord(|idx| Event::Xy { x, y, idx }, idx);
+        Ok(())
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.push_coord(
+            |idx| Event::Coordinate {
+                x,
+                y,
+                z,
+                m,
+                t,
+                tm,
+                idx,
+            },
+            idx,
+        );
+        Ok(())
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        if self.depth == 0 {
+            self.parts.push(vec![Event::EmptyPoint(idx)]);
+        } else if self.depth == 1 && self.collection.is_some() {
+            self.parts.push(vec![Event::EmptyPoint(0)]);
+        } else {
+            self.push(Event::EmptyPoint(idx));
+        }
+        Ok(())
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.begin(false, idx, |_, idx| Event::PointBegin(idx))
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.end(idx, |_, idx| Event::PointEnd(idx))
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(true, idx, |_, idx| Event::MultiPointBegin { size, idx })
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.end(idx, |_, idx| Event::MultiPointEnd(idx))
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.begin(false, idx, |single, idx| Event::LineStringBegin {
+            tagged: tagged || single,
+            size,
+            idx,
+        })
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.end(idx, |single, idx| Event::LineStringEnd {
+            tagged: tagged || single,
+            idx,
+        })
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(true, idx, |_, idx| Event::MultiLineStringBegin {
+            size,
+            idx,
+        })
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.end(idx, |_, idx| Event::MultiLineStringEnd(idx))
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.begin(false, idx, |single, idx| Event::PolygonBegin {
+            tagged: tagged || single,
+            size,
+            idx,
+        })
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.end(idx, |single, idx| Event::PolygonEnd {
+            tagged: tagged || single,
+            idx,
+        })
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(true, idx, |_, idx| Event::MultiPolygonBegin { size, idx })
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.end(idx, |_, idx| Event::MultiPolygonEnd(idx))
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(true, idx, |_, idx| Event::GeometryCollectionBegin {
+            size,
+            idx,
+        })
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.end(idx, |_, idx| Event::GeometryCollectionEnd(idx))
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(false, idx, |_, idx| Event::CircularStringBegin {
+            size,
+            idx,
+        })
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        self.end(idx, |_, idx| Event::CircularStringEnd(idx))
+    }
+    fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(false, idx, |_, idx| Event::CompoundCurveBegin { size, idx })
+    }
+    fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        self.end(idx, |_, idx| Event::CompoundCurveEnd(idx))
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(false, idx, |_, idx| Event::CurvePolygonBegin { size, idx })
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.end(idx, |_, idx| Event::CurvePolygonEnd(idx))
+    }
+    fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(true, idx, |_, idx| Event::MultiCurveBegin { size, idx })
+    }
+    fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        self.end(idx, |_, idx| Event::MultiCurveEnd(idx))
+    }
+    fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(true, idx, |_, idx| Event::MultiSurfaceBegin { size, idx })
+    }
+    fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        self.end(idx, |_, idx| Event::MultiSurfaceEnd(idx))
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.begin(false, idx, |single, idx| Event::TriangleBegin {
+            tagged: tagged || single,
+            size,
+            idx,
+        })
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.end(idx, |single, idx| Event::TriangleEnd {
+            tagged: tagged || single,
+            idx,
+        })
+    }
+    fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(false, idx, |_, idx| Event::PolyhedralSurfaceBegin {
+            size,
+            idx,
+        })
+    }
+    fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        self.end(idx, |_, idx| Event::PolyhedralSurfaceEnd(idx))
+    }
+    fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(false, idx, |_, idx| Event::TinBegin { size, idx })
+    }
+    fn tin_end(&mut self, idx: usize) -> Result<()> {
+        self.end(idx, |_, idx| Event::TinEnd(idx))
+    }
+}
+
+impl<P: FeatureProcessor> PropertyProcessor for ExplodeMultiGeometries<P> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue<'_>) -> Result<bool> {
+        self.properties.push(Property {
+            idx,
+            column: None,
+            name: name.to_string(),
+            value: value.into(),
+        });
+        Ok(false)
+    }
+    fn column_property(
+        &mut self,
+        idx: usize,
+        column: usize,
+        name: &str,
+        value: &ColumnValue<'_>,
+    ) -> Result<bool> {
+        self.properties.push(Property {
+            idx,
+            column: Some(column),
+            name: name.to_string(),
+            value: value.into(),
+        });
+        Ok(false)
+    }
+}
+
+impl<P: FeatureProcessor> FeatureProcessor for ExplodeMultiGeometries<P> {
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.inner.dataset_begin(name)
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        self.inner.dataset_end()
+    }
+    fn crs(&mut self, crs: &Crs) -> Result<()> {
+        self.inner.crs(crs)
+    }
+    fn schema_begin(&mut self, columns: &[ColumnDef]) -> Result<()> {
+        self.inner.schema_begin(columns)
+    }
+    fn feature_begin(&mut self, _idx: u64) -> Result<()> {
+        self.id = None;
+        self.properties.clear();
+        self.srid = None;
+        self.parts.clear();
+        self.collection = None;
+        self.depth = 0;
+        Ok(())
+    }
+    fn feature_id(&mut self, id: &FeatureId) -> Result<()> {
+        self.id = Some(match id {
+            FeatureId::ULong(id) => Id::ULong(*id),
+            FeatureId::Long(id) => Id::Long(*id),
+            FeatureId::String(id) => Id::String(id.to_string()),
+        });
+        Ok(())
+    }
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        let parts = std::mem::take(&mut self.parts);
+        match parts.as_slice() {
+            [] => self.emit_feature(None, true),
+            [part] => self.emit_feature(Some(part), true),
+            parts => {
+                for part in parts {
+                    self.emit_feature(Some(part), false)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::{GeoJson, GeoJsonWriter};
+    use crate::GeozeroDatasource;
+    use serde_json::json;
+
+    fn explode(geojson: &str) -> serde_json::Value {
+        let mut out = Vec::new();
+        let mut processor = ExplodeMultiGeometries::new(GeoJsonWriter::new(&mut out));
+        GeoJson(geojson).process(&mut processor).unwrap();
+        serde_json::from_slice(&out).unwrap()
+    }
+
+    #[test]
+    fn explode_multi_geometries() {
+        let collection = explode(
+            r#"{"type": "FeatureCollection", "features": [
+                {"type": "Feature", "properties": {"name": "lines"}, "geometry": {"type": "MultiLineString", "coordinates": [[[1,1],[2,2]],[[3,3],[4,4]]]}},
+                {"type": "Feature", "id": 7, "properties": {"name": "point"}, "geometry": {"type": "Point", "coordinates": [5,5]}},
+                {"type": "Feature", "properties": {"name": "polygons"}, "geometry": {"type": "MultiPolygon", "coordinates": [[[[0,0],[1,0],[1,1],[0,0]]],[[[2,2],[3,2],[3,3],[2,2]]]]}}
+            ]}"#,
+        );
+        let features = collection["features"].as_array().unwrap();
+        assert_eq!(features.len(), 5);
+        let names: Vec<_> = features.iter().map(|f| &f["properties"]["name"]).collect();
+        assert_eq!(
+            names,
+            ["lines", "lines", "point", "polygons", "polygons"].map(|name| json!(name))
+        );
+        assert_eq!(
+            features[1]["geometry"],
+            json!({"type": "LineString", "coordinates": [[3,3],[4,4]]})
+        );
+        assert_eq!(features[2]["id"], json!(7));
+        assert_eq!(
+            features[3]["geometry"],
+            json!({"type": "Polygon", "coordinates": [[[0,0],[1,0],[1,1],[0,0]]]})
+        );
+    }
+
+    #[test]
+    fn explode_multipoint() {
+        let collection = explode(
+            r#"{"type": "FeatureCollection", "features": [
+                {"type": "Feature", "properties": {"n": 1}, "geometry": {"type": "MultiPoint", "coordinates": [[1,2],[3,4]]}}
+            ]}"#,
+        );
+        let features = collection["features"].as_array().unwrap();
+        assert_eq!(features.len(), 2);
+        assert_eq!(
+            features[1]["geometry"],
+            json!({"type": "Point", "coordinates": [3,4]})
+        );
+        assert_eq!(features[1]["properties"], json!({"n": 1}));
+    }
+}
@@ -1,6 +1,26 @@
+use crate::crs::Crs;
 use crate::error::Result;
 use crate::geometry_processor::GeomProcessor;
-use crate::property_processor::PropertyProcessor;
+use crate::property_processor::{ColumnDef, PropertyProcessor};
+use std::fmt;
+
+/// Feature identifier
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum FeatureId<'a> {
+    ULong(u64),
+    Long(i64),
+    String(&'a str),
+}
+
+impl fmt::Display for FeatureId<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FeatureId::ULong(id) => id.fmt(f),
+            FeatureId::Long(id) => id.fmt(f),
+            FeatureId::String(id) => id.fmt(f),
+        }
+    }
+}
 
 /// Feature processing trait
 #[allow(unused_variables)]
@@ -25,6 +45,20 @@ pub trait FeatureProcessor: GeomProcessor + PropertyProcessor {
     fn dataset_end(&mut self) -> Result<()> {
         Ok(())
     }
+    /// Coordinate reference system of dataset
+    ///
+    /// - Called after `dataset_begin` and before `schema_begin` by readers knowing their CRS.
+    fn crs(&mut self, crs: &Crs) -> Result<()> {
+        Ok(())
+    }
+    /// Column schema of dataset
+    ///
+    /// - Called after `dataset_begin` and before the first `feature_begin` by readers
+    ///   knowing their schema in advance.
+    /// - Schema-less formats like GeoJSON don't emit this event.
+    fn schema_begin(&mut self, columns: &[ColumnDef]) -> Result<()> {
+        Ok(())
+    }
     /// Begin of feature processing
     ///
     /// - `idx`: the positional row index in the dataset. For the `n`th row, `idx` will be
@@ -33,6 +67,12 @@ pub trait FeatureProcessor: GeomProcessor + PropertyProcessor {
     fn feature_begin(&mut self, idx: u64) -> Result<()> {
         Ok(())
     }
+    /// Feature identifier
+    ///
+    /// - Called after `feature_begin` and before `properties_begin`, if the feature has an id.
+    fn feature_id(&mut self, id: &FeatureId) -> Result<()> {
+        Ok(())
+    }
     /// End of feature processing
     ///
     /// - `idx`: the positional row index in the dataset. For the `n`th row, `idx` will be
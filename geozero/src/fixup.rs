@@ -0,0 +1,631 @@
+//! Repair of degenerate geometries.
+use crate::error::Result;
+use crate::{
+    ColumnDef, ColumnValue, CoordDimensions, Crs, FeatureId, FeatureProcessor, GeomProcessor,
+    PropertyProcessor,
+};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct Coord {
+    x: f64,
+    y: f64,
+    z: Option<f64>,
+    m: Option<f64>,
+    t: Option<f64>,
+    tm: Option<u64>,
+    /// Received with `coordinate` instead of `xy`
+    multi_dim: bool,
+}
+
+impl Coord {
+    fn same_xy(&self, other: &Coord) -> bool {
+        self.x == other.x && self.y == other.y
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Kind {
+    Point,
+    MultiPoint,
+    LineString,
+    MultiLineString,
+    Polygon,
+    MultiPolygon,
+    GeometryCollection,
+    CircularString,
+    CompoundCurve,
+    CurvePolygon,
+    MultiCurve,
+    MultiSurface,
+    Triangle,
+    PolyhedralSurface,
+    Tin,
+}
+
+/// Buffered geometry part
+#[derive(Debug)]
+struct Node {
+    kind: Kind,
+    tagged: bool,
+    /// Exterior ring was dropped
+    invalid: bool,
+    coords: Vec<Coord>,
+    children: Vec<Node>,
+}
+
+impl Node {
+    fn new(kind: Kind, tagged: bool) -> Self {
+        Node {
+            kind,
+            tagged,
+            invalid: false,
+            coords: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+}
+
+/// Twice the signed area of a ring, positive if counterclockwise
+fn signed_area(ring: &[Coord]) -> f64 {
+    ring.windows(2)
+        .map(|w| w[0].x * w[1].y - w[1].x * w[0].y)
+        .sum()
+}
+
+/// Wraps another processor, repairing degenerate geometries.
+///
+/// Each geometry is buffered and the following repairs are applied, before the
+/// repaired geometry is forwarded to the inner processor:
+/// * Consecutive duplicate vertices of linestrings and rings are removed
+/// * Unclosed polygon rings are closed
+/// * Linestrings without length and empty rings, rings with less than 4 points or
+///   without area are dropped. Polygons without a valid exterior ring are dropped as well.
+///
+/// Curves and points are passed unchanged. A degenerate top-level geometry
+/// is replaced by an empty geometry of the same type.
+/// Useful for normalizing Shapefile or CSV input before writing to stricter formats.
+///
+/// # Usage example:
+///
+/// ```
+/// # #[cfg(feature = "with-wkt")]
+/// # {
+/// use geozero::wkt::{WktStr, WktWriter};
+/// use geozero::{FixupProcessor, GeozeroGeometry};
+///
+/// let mut out = Vec::new();
+/// let mut processor = FixupProcessor::new(WktWriter::new(&mut out));
+/// WktStr("POLYGON((0 0,1 0,1 0,1 1))").process_geom(&mut processor).unwrap();
+/// assert_eq!(String::from_utf8(out).unwrap(), "POLYGON((0 0,1 0,1 1,0 0))");
+/// # }
+/// ```
+pub struct FixupProcessor<P> {
+    /// The underlying processor
+    pub inner: P,
+    close_rings: bool,
+    drop_degenerate: bool,
+    remove_duplicates: bool,
+    parts: Vec<Node>,
+}
+
+impl<P: GeomProcessor> FixupProcessor<P> {
+    /// Fixup processor with all repairs enabled.
+    pub fn new(inner: P) -> Self {
+        FixupProcessor {
+            inner,
+            close_rings: true,
+            drop_degenerate: true,
+            remove_duplicates: true,
+            parts: Vec::new(),
+        }
+    }
+    /// Close unclosed polygon rings (default: true)
+    pub fn set_close_rings(&mut self, close_rings: bool) {
+        self.close_rings = close_rings;
+    }
+    /// Drop linestrings without length, collapsed rings and their polygons (default: true)
+    pub fn set_drop_degenerate(&mut self, drop_degenerate: bool) {
+        self.drop_degenerate = drop_degenerate;
+    }
+    /// Remove consecutive duplicate vertices (default: true)
+    pub fn set_remove_duplicates(&mut self, remove_duplicates: bool) {
+        self.remove_duplicates = remove_duplicates;
+    }
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+
+    fn begin(&mut self, kind: Kind, tagged: bool) {
+        self.parts.push(Node::new(kind, tagged));
+    }
+
+    fn end(&mut self, idx: usize) -> Result<()> {
+        let Some(mut node) = self.parts.pop() else {
+            return Ok(());
+        };
+        let parent = self.parts.last().map(|parent| parent.kind);
+        let keep = self.fix(&mut node, parent);
+        match self.parts.last_mut() {
+            Some(parent) => {
+                let surface = matches!(
+                    parent.kind,
+                    Kind::Polygon | Kind::CurvePolygon | Kind::Triangle
+                );
+                if !keep && surface && parent.children.is_empty() {
+                    parent.invalid = true;
+                }
+                if keep && !parent.invalid {
+                    parent.children.push(node);
+                }
+                Ok(())
+            }
+            None => {
+                if !keep {
+                    node.coords.clear();
+                    node.children.clear();
+                }
+                self.emit(&node, idx)
+            }
+        }
+    }
+
+    fn push_coord(&mut self, coord: Coord) -> bool {
+        if let Some(node) = self.parts.last_mut() {
+            node.coords.push(coord);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Repair node. Returns `false` if the node should be dropped.
+    fn fix(&self, node: &mut Node, parent: Option<Kind>) -> bool {
+        match node.kind {
+            Kind::LineString => {
+                let ring = matches!(
+                    parent,
+                    Some(Kind::Polygon | Kind::CurvePolygon | Kind::Triangle)
+                );
+                if parent == Some(Kind::CompoundCurve) {
+                    return true;
+                }
+                if node.coords.is_empty() {
+                    return !(ring && self.drop_degenerate);
+                }
+                if self.remove_duplicates {
+                    node.coords.dedup_by(|a, b| a.same_xy(b));
+                }
+                if ring && self.close_rings {
+                    let first = node.coords[0];
+                    if !node.coords[node.coords.len() - 1].same_xy(&first) {
+                        node.coords.push(first);
+                    }
+                }
+                if !self.drop_degenerate {
+                    return true;
+                }
+                if ring {
+                    // Collapsed rings have no area
+                    node.coords.len() >= 4 && signed_area(&node.coords) != 0.0
+                } else {
+                    node.coords.iter().any(|c| !c.same_xy(&node.coords[0]))
+                }
+            }
+            Kind::Polygon | Kind::Triangle | Kind::CurvePolygon => {
+                !node.invalid && !(self.drop_degenerate && node.children.is_empty())
+            }
+            _ => true,
+        }
+    }
+
+    fn emit(&mut self, node: &Node, idx: usize) -> Result<()> {
+        let size = node.children.len();
+        match node.kind {
+            Kind::Point => {
+                if node.coords.is_empty() {
+                    return self.inner.empty_point(idx);
+                }
+                self.inner.point_begin(idx)?;
+                self.emit_coords(&node.coords)?;
+                self.inner.point_end(idx)
+            }
+            Kind::MultiPoint => {
+                self.inner.multipoint_begin(node.coords.len() + size, idx)?;
+                self.emit_coords(&node.coords)?;
+                self.emit_children(node)?;
+                self.inner.multipoint_end(idx)
+            }
+            Kind::LineString => {
+                self.inner
+                    .linestring_begin(node.tagged, node.coords.len(), idx)?;
+                self.emit_coords(&node.coords)?;
+                self.inner.linestring_end(node.tagged, idx)
+            }
+            Kind::CircularString => {
+                self.inner.circularstring_begin(node.coords.len(), idx)?;
+                self.emit_coords(&node.coords)?;
+                self.inner.circularstring_end(idx)
+            }
+            Kind::MultiLineString => {
+                self.inner.multilinestring_begin(size, idx)?;
+                self.emit_children(node)?;
+                self.inner.multilinestring_end(idx)
+            }
+            Kind::Polygon => {
+                self.inner.polygon_begin(node.tagged, size, idx)?;
+                self.emit_children(node)?;
+                self.inner.polygon_end(node.tagged, idx)
+            }
+            Kind::Triangle => {
+                self.inner.triangle_begin(node.tagged, size, idx)?;
+                self.emit_children(node)?;
+                self.inner.triangle_end(node.tagged, idx)
+            }
+            Kind::MultiPolygon => {
+                self.inner.multipolygon_begin(size, idx)?;
+                self.emit_children(node)?;
+                self.inner.multipolygon_end(idx)
+            }
+            Kind::GeometryCollection => {
+                self.inner.geometrycollection_begin(size, idx)?;
+                self.emit_children(node)?;
+                self.inner.geometrycollection_end(idx)
+            }
+            Kind::CompoundCurve => {
+                self.inner.compoundcurve_begin(size, idx)?;
+                self.emit_children(node)?;
+                self.inner.compoundcurve_end(idx)
+            }
+            Kind::CurvePolygon => {
+                self.inner.curvepolygon_begin(size, idx)?;
+                self.emit_children(node)?;
+                self.inner.curvepolygon_end(idx)
+            }
+            Kind::MultiCurve => {
+                self.inner.multicurve_begin(size, idx)?;
+                self.emit_children(node)?;
+                self.inner.multicurve_end(idx)
+            }
+            Kind::MultiSurface => {
+                self.inner.multisurface_begin(size, idx)?;
+                self.emit_children(node)?;
+                self.inner.multisurface_end(idx)
+            }
+            Kind::PolyhedralSurface => {
+                self.inner.polyhedralsurface_begin(size, idx)?;
+                self.emit_children(node)?;
+                self.inner.polyhedralsurface_end(idx)
+            }
+            Kind::Tin => {
+                self.inner.tin_begin(size, idx)?;
+                self.emit_children(node)?;
+                self.inner.tin_end(idx)
+            }
+        }
+    }
+
+    fn emit_children(&mut self, node: &Node) -> Result<()> {
+        for (idx, child) in node.children.iter().enumerate() {
+            self.emit(child, idx)?;
+        }
+        Ok(())
+    }
+
+    fn emit_coords(&mut self, coords: &[Coord]) -> Result<()> {
+        for (idx, c) in coords.iter().enumerate() {
+            if c.multi_dim {
+                self.inner.coordinate(c.x, c.y, c.z, c.m, c.t, c.tm, idx)?;
+            } else {
+                self.inner.xy(c.x, c.y, idx)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<P: GeomProcessor> GeomProcessor for FixupProcessor<P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.inner.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.inner.multi_dim()
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.inner.srid(srid)
+    }
+    fn envelope(&mut self, envelope: &[f64], dims: CoordDimensions, idx: usize) -> Result<()> {
+        self.inner.envelope(envelope, dims, idx)
+    }
+    fn size_hint_total_coords(&mut self, count: usize) -> Result<()> {
+        self.inner.size_hint_total_coords(count)
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        let coord = Coord {
+            x,
+            y,
+            z: None,
+            m: None,
+            t: None,
+            tm: None,
+            multi_dim: false,
+        };
+        if self.push_coord(coord) {
+            Ok(())
+        } else {
+            self.inner.xy(x, y, idx)
+        }
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        let coord = Coord {
+            x,
+            y,
+            z,
+            m,
+            t,
+            tm,
+            multi_dim: true,
+        };
+        if self.push_coord(coord) {
+            Ok(())
+        } else {
+            self.inner.coordinate(x, y, z, m, t, tm, idx)
+        }
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        match self.parts.last_mut() {
+            Some(parent) => {
+                parent.children.push(Node::new(Kind::Point, true));
+                Ok(())
+            }
+            None => self.inner.empty_point(idx),
+        }
+    }
+    fn point_begin(&mut self, _idx: usize) -> Result<()> {
+        self.begin(Kind::Point, true);
+        Ok(())
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.end(idx)
+    }
+    fn multipoint_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(Kind::MultiPoint, true);
+        Ok(())
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.end(idx)
+    }
+    fn linestring_begin(&mut self, tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(Kind::LineString, tagged);
+        Ok(())
+    }
+    fn linestring_end(&mut self, _tagged: bool, idx: usize) -> Result<()> {
+        self.end(idx)
+    }
+    fn multilinestring_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(Kind::MultiLineString, true);
+        Ok(())
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.end(idx)
+    }
+    fn polygon_begin(&mut self, tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(Kind::Polygon, tagged);
+        Ok(())
+    }
+    fn polygon_end(&mut self, _tagged: bool, idx: usize) -> Result<()> {
+        self.end(idx)
+    }
+    fn multipolygon_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(Kind::MultiPolygon, true);
+        Ok(())
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.end(idx)
+    }
+    fn geometrycollection_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(Kind::GeometryCollection, true);
+        Ok(())
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.end(idx)
+    }
+    fn circularstring_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(Kind::CircularString, true);
+        Ok(())
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        self.end(idx)
+    }
+    fn compoundcurve_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(Kind::CompoundCurve, true);
+        Ok(())
+    }
+    fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        self.end(idx)
+    }
+    fn curvepolygon_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(Kind::CurvePolygon, true);
+        Ok(())
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.end(idx)
+    }
+    fn multicurve_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(Kind::MultiCurve, true);
+        Ok(())
+    }
+    fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        self.end(idx)
+    }
+    fn multisurface_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(Kind::MultiSurface, true);
+        Ok(())
+    }
+    fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        self.end(idx)
+    }
+    fn triangle_begin(&mut self, tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(Kind::Triangle, tagged);
+        Ok(())
+    }
+    fn triangle_end(&mut self, _tagged: bool, idx: usize) -> Result<()> {
+        self.end(idx)
+    }
+    fn polyhedralsurface_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(Kind::PolyhedralSurface, true);
+        Ok(())
+    }
+    fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        self.end(idx)
+    }
+    fn tin_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin(Kind::Tin, true);
+        Ok(())
+    }
+    fn tin_end(&mut self, idx: usize) -> Result<()> {
+        self.end(idx)
+    }
+}
+
+impl<P: PropertyProcessor> PropertyProcessor for FixupProcessor<P> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue<'_>) -> Result<bool> {
+        self.inner.property(idx, name, value)
+    }
+    fn column_property(
+        &mut self,
+        idx: usize,
+        column: usize,
+        name: &str,
+        value: &ColumnValue<'_>,
+    ) -> Result<bool> {
+        self.inner.column_property(idx, column, name, value)
+    }
+}
+
+impl<P: FeatureProcessor> FeatureProcessor for FixupProcessor<P> {
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.inner.dataset_begin(name)
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        self.inner.dataset_end()
+    }
+    fn crs(&mut self, crs: &Crs) -> Result<()> {
+        self.inner.crs(crs)
+    }
+    fn schema_begin(&mut self, columns: &[ColumnDef]) -> Result<()> {
+        self.inner.schema_begin(columns)
+    }
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        self.inner.feature_begin(idx)
+    }
+    fn feature_id(&mut self, id: &FeatureId) -> Result<()> {
+        self.inner.feature_id(id)
+    }
+    fn feature_end(&mut self, idx: u64) -> Result<()> {
+        self.inner.feature_end(idx)
+    }
+    fn properties_begin(&mut self) -> Result<()> {
+        self.inner.properties_begin()
+    }
+    fn properties_end(&mut self) -> Result<()> {
+        self.inner.properties_end()
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        self.parts.clear();
+        self.inner.geometry_begin()
+    }
+    fn geometry_end(&mut self) -> Result<()> {
+        self.inner.geometry_end()
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use super::*;
+    use crate::wkt::{WktStr, WktWriter};
+    use crate::GeozeroGeometry;
+
+    fn fixup(wkt: &str) -> String {
+        let mut out = Vec::new();
+        let mut processor = FixupProcessor::new(WktWriter::new(&mut out));
+        WktStr(wkt).process_geom(&mut processor).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn close_rings() {
+        assert_eq!(
+            fixup("POLYGON((0 0,1 0,1 1,0 1),(0.2 0.2,0.4 0.2,0.4 0.4))"),
+            "POLYGON((0 0,1 0,1 1,0 1,0 0),(0.2 0.2,0.4 0.2,0.4 0.4,0.2 0.2))"
+        );
+        assert_eq!(
+            fixup("POLYGON((0 0,1 0,1 1,0 0))"),
+            "POLYGON((0 0,1 0,1 1,0 0))"
+        );
+    }
+
+    #[test]
+    fn remove_duplicates() {
+        assert_eq!(
+            fixup("LINESTRING(0 0,0 0,1 1,1 1,2 2)"),
+            "LINESTRING(0 0,1 1,2 2)"
+        );
+    }
+
+    #[test]
+    fn drop_degenerate() {
+        assert_eq!(
+            fixup("MULTILINESTRING((0 0,1 1),(2 2,2 2))"),
+            "MULTILINESTRING((0 0,1 1))"
+        );
+        assert_eq!(
+            fixup("POLYGON((0 0,1 0,1 1,0 0),(0.2 0.2,0.4 0.2,0.2 0.2))"),
+            "POLYGON((0 0,1 0,1 1,0 0))"
+        );
+        assert_eq!(
+            fixup("MULTIPOLYGON(((0 0,1 0,0 0)),((0 0,1 0,1 1,0 0)))"),
+            "MULTIPOLYGON(((0 0,1 0,1 1,0 0)))"
+        );
+        assert_eq!(fixup("LINESTRING(1 1,1 1)"), "LINESTRING EMPTY");
+    }
+
+    #[test]
+    fn drop_collapsed_rings() {
+        // Hole without area
+        assert_eq!(
+            fixup("POLYGON((0 0,4 0,4 4,0 0),(1 1,2 1,3 1,1 1))"),
+            "POLYGON((0 0,4 0,4 4,0 0))"
+        );
+        // Exterior without area, holes are dropped with the polygon
+        assert_eq!(
+            fixup("POLYGON((0 0,1 0,2 0,0 0),(0.2 0.2,0.4 0.2,0.4 0.4,0.2 0.2))"),
+            "POLYGON EMPTY"
+        );
+        assert_eq!(
+            fixup("MULTIPOLYGON(((0 0,1 1,2 2,0 0)),((0 0,1 0,1 1,0 0)))"),
+            "MULTIPOLYGON(((0 0,1 0,1 1,0 0)))"
+        );
+    }
+
+    #[test]
+    fn disabled_repairs() {
+        let mut out = Vec::new();
+        let mut processor = FixupProcessor::new(WktWriter::new(&mut out));
+        processor.set_close_rings(false);
+        processor.set_drop_degenerate(false);
+        WktStr("POLYGON((0 0,1 0,1 1))")
+            .process_geom(&mut processor)
+            .unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "POLYGON((0 0,1 0,1 1))");
+    }
+}
@@ -0,0 +1,109 @@
+//! Plain decimal formatting of floating point numbers.
+use std::io::{self, Write};
+
+/// Formatter for coordinate values.
+///
+/// Values are always written in plain decimal notation without exponent, which is required
+/// by some WKT and GeoJSON consumers. An optional precision limits the number of decimal places.
+///
+/// ```
+/// use geozero::FloatFormatter;
+///
+/// let formatter = FloatFormatter::with_precision(Some(3));
+/// assert_eq!(formatter.format(1.23456), "1.235");
+/// assert_eq!(formatter.format(1e-20), "0");
+/// assert_eq!(FloatFormatter::new().format(1e21), "1000000000000000000000");
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct FloatFormatter {
+    precision: Option<u32>,
+}
+
+impl FloatFormatter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Formatter rounding to `decimals` decimal places (`None`: shortest exact representation)
+    pub fn with_precision(decimals: Option<u32>) -> Self {
+        FloatFormatter {
+            precision: decimals,
+        }
+    }
+
+    /// Maximal number of decimal places
+    pub fn precision(&self) -> Option<u32> {
+        self.precision
+    }
+
+    /// Round value to the configured number of decimal places
+    pub fn round(&self, v: f64) -> f64 {
+        let Some(precision) = self.precision else {
+            return v;
+        };
+        let factor = 10f64.powi(precision as i32);
+        let rounded = (v * factor).round() / factor;
+        if !rounded.is_finite() {
+            v
+        } else if rounded == 0.0 {
+            // Avoid negative zero
+            0.0
+        } else {
+            rounded
+        }
+    }
+
+    /// Format value in plain decimal notation
+    pub fn format(&self, v: f64) -> String {
+        // `Display` of f64 never uses exponent notation
+        self.round(v).to_string()
+    }
+}
+
+/// Reusable buffer for writing floats in plain decimal notation without allocation.
+///
+/// Produces the same output as the `Display` implementation of `f64`.
+#[cfg_attr(not(feature = "with-geojson"), allow(dead_code))]
+#[derive(Default)]
+pub(crate) struct FloatBuffer(ryu::Buffer);
+
+impl FloatBuffer {
+    pub fn write<W: Write>(&mut self, out: &mut W, v: f64) -> io::Result<()> {
+        if v.is_finite() {
+            let s = self.0.format_finite(v);
+            // ryu uses exponent notation for very large and small values
+            if !s.contains('e') {
+                return out.write_all(s.strip_suffix(".0").unwrap_or(s).as_bytes());
+            }
+        }
+        write!(out, "{v}")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn buffer_like_display() {
+        let mut buf = FloatBuffer::default();
+        for v in [
+            0.0,
+            -0.0,
+            1.0,
+            -12.5,
+            0.1,
+            1e-7,
+            123456.789,
+            1e15,
+            1e16,
+            1e21,
+            2.5e-300,
+            f64::NAN,
+        ] {
+            let mut out = Vec::new();
+            buf.write(&mut out, v).unwrap();
+            assert_eq!(String::from_utf8(out).unwrap(), v.to_string());
+        }
+    }
+}
@@ -0,0 +1,392 @@
+use crate::error::{GeozeroError, Result};
+use crate::gdal::GdalWriter;
+use crate::{ColumnValue, CoordDimensions, FeatureProcessor, GeomProcessor, PropertyProcessor};
+use gdal::vector::{FieldValue, Geometry, LayerAccess, LayerOptions};
+use gdal::Dataset;
+use gdal_sys::{OGRErr, OGRFieldType, OGRwkbGeometryType};
+use std::ffi::CString;
+use std::mem;
+use std::os::raw::c_int;
+
+/// OGR feature writer.
+///
+/// Writes features into a layer of a GDAL vector dataset, which is created on the first feature.
+/// The spatial reference of the layer is taken from the SRID of the first geometry.
+/// OGR fields are created from the property values, when a property name is seen for the first time.
+///
+/// # Usage example:
+///
+/// ```rust,ignore
+/// use gdal::DriverManager;
+/// use geozero::gdal::GdalDatasetWriter;
+/// use geozero::GeozeroDatasource;
+///
+/// let driver = DriverManager::get_driver_by_name("GPKG")?;
+/// let dataset = driver.create_vector_only("places.gpkg")?;
+/// let mut writer = GdalDatasetWriter::new(dataset, "places");
+/// geojson.process(&mut writer)?;
+/// ```
+pub struct GdalDatasetWriter {
+    dataset: Dataset,
+    layer_name: String,
+    geometry_type: OGRwkbGeometryType::Type,
+    dims: CoordDimensions,
+    layer_created: bool,
+    /// OGR fields
+    fields: Vec<(String, OGRFieldType::Type)>,
+    /// Number of fields created in the layer
+    created_fields: usize,
+    /// Property names and values of the current feature
+    names: Vec<String>,
+    values: Vec<FieldValue>,
+    geom: GdalWriter,
+}
+
+impl GdalDatasetWriter {
+    pub fn new(dataset: Dataset, layer_name: &str) -> Self {
+        Self::with_dims(dataset, layer_name, CoordDimensions::default())
+    }
+    pub fn with_dims(dataset: Dataset, layer_name: &str, dims: CoordDimensions) -> Self {
+        GdalDatasetWriter {
+            dataset,
+            layer_name: layer_name.to_string(),
+            geometry_type: OGRwkbGeometryType::wkbUnknown,
+            dims,
+            layer_created: false,
+            fields: Vec::new(),
+            created_fields: 0,
+            names: Vec::new(),
+            values: Vec::new(),
+            geom: GdalWriter::with_dims(dims),
+        }
+    }
+    /// Set geometry type of the created layer (default: `wkbUnknown`)
+    pub fn set_geometry_type(&mut self, geometry_type: OGRwkbGeometryType::Type) {
+        self.geometry_type = geometry_type;
+    }
+    pub fn dataset(&self) -> &Dataset {
+        &self.dataset
+    }
+    pub fn into_dataset(self) -> Dataset {
+        self.dataset
+    }
+
+    /// Change an integer field to a 64 bit integer field
+    fn widen_field(&mut self, field_idx: usize) -> Result<()> {
+        let name = &self.fields[field_idx].0;
+        if field_idx < self.created_fields {
+            let layer = self.dataset.layer_by_name(&self.layer_name)?;
+            let c_name =
+                CString::new(name.as_str()).map_err(|e| GeozeroError::Property(e.to_string()))?;
+            let rv = unsafe {
+                let field_defn =
+                    gdal_sys::OGR_Fld_Create(c_name.as_ptr(), OGRFieldType::OFTInteger64);
+                let rv = gdal_sys::OGR_L_AlterFieldDefn(
+                    layer.c_layer(),
+                    field_idx as c_int,
+                    field_defn,
+                    gdal_sys::ALTER_TYPE_FLAG as c_int,
+                );
+                gdal_sys::OGR_Fld_Destroy(field_defn);
+                rv
+            };
+            if rv != OGRErr::OGRERR_NONE {
+                return Err(GeozeroError::Property(format!(
+                    "changing type of field `{name}` to Integer64 failed"
+                )));
+            }
+        }
+        self.fields[field_idx].1 = OGRFieldType::OFTInteger64;
+        Ok(())
+    }
+
+    fn create_layer(&mut self) -> Result<()> {
+        if !self.layer_created {
+            self.dataset.create_layer(LayerOptions {
+                name: &self.layer_name,
+                srs: self.geom.spatial_ref.as_ref(),
+                ty: self.geometry_type,
+                ..Default::default()
+            })?;
+            self.layer_created = true;
+        }
+        Ok(())
+    }
+}
+
+/// OGR field type derived from a property value
+fn ogr_field_type(value: &ColumnValue) -> OGRFieldType::Type {
+    match value {
+        ColumnValue::Byte(_)
+        | ColumnValue::UByte(_)
+        | ColumnValue::Bool(_)
+        | ColumnValue::Short(_)
+        | ColumnValue::UShort(_)
+        | ColumnValue::Int(_) => OGRFieldType::OFTInteger,
+        ColumnValue::UInt(_) | ColumnValue::Long(_) | ColumnValue::ULong(_) => {
+            OGRFieldType::OFTInteger64
+        }
+        ColumnValue::Float(_) | ColumnValue::Double(_) => OGRFieldType::OFTReal,
+        ColumnValue::String(_)
+        | ColumnValue::Json(_)
+        | ColumnValue::DateTime(_)
+        | ColumnValue::Binary(_)
+        | ColumnValue::Uuid(_)
+        | ColumnValue::Decimal(_)
+        | ColumnValue::Array(_) => OGRFieldType::OFTString,
+    }
+}
+
+/// Integer value of a property, if it is an integral number
+fn integer_value(value: &ColumnValue) -> Option<i64> {
+    let float_integer = |v: f64| {
+        (v.fract() == 0.0 && v >= i64::MIN as f64 && v < i64::MAX as f64).then_some(v as i64)
+    };
+    match value {
+        ColumnValue::Byte(v) => Some(i64::from(*v)),
+        ColumnValue::UByte(v) => Some(i64::from(*v)),
+        ColumnValue::Bool(v) => Some(i64::from(*v)),
+        ColumnValue::Short(v) => Some(i64::from(*v)),
+        ColumnValue::UShort(v) => Some(i64::from(*v)),
+        ColumnValue::Int(v) => Some(i64::from(*v)),
+        ColumnValue::UInt(v) => Some(i64::from(*v)),
+        ColumnValue::Long(v) => Some(*v),
+        ColumnValue::ULong(v) => i64::try_from(*v).ok(),
+        ColumnValue::Float(v) => float_integer(f64::from(*v)),
+        ColumnValue::Double(v) => float_integer(*v),
+        ColumnValue::String(v) | ColumnValue::Decimal(v) => v.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Convert property value to a value of the given field type
+fn field_value(field_type: OGRFieldType::Type, value: &ColumnValue) -> Result<FieldValue> {
+    let type_error = || GeozeroError::ColumnType(format!("{field_type}"), format!("{value:?}"));
+    let integer = || integer_value(value).ok_or_else(type_error);
+    let number = || -> Result<f64> {
+        match value {
+            ColumnValue::Byte(v) => Ok(f64::from(*v)),
+            ColumnValue::UByte(v) => Ok(f64::from(*v)),
+            ColumnValue::Bool(v) => Ok(f64::from(u8::from(*v))),
+            ColumnValue::Short(v) => Ok(f64::from(*v)),
+            ColumnValue::UShort(v) => Ok(f64::from(*v)),
+            ColumnValue::Int(v) => Ok(f64::from(*v)),
+            ColumnValue::UInt(v) => Ok(f64::from(*v)),
+            ColumnValue::Long(v) => Ok(*v as f64),
+            ColumnValue::ULong(v) => Ok(*v as f64),
+            ColumnValue::Float(v) => Ok(f64::from(*v)),
+            ColumnValue::Double(v) => Ok(*v),
+            ColumnValue::String(v) | ColumnValue::Decimal(v) => v.parse().map_err(|_| type_error()),
+            _ => Err(type_error()),
+        }
+    };
+    let field_value = match field_type {
+        OGRFieldType::OFTInteger => {
+            FieldValue::IntegerValue(integer()?.try_into().map_err(|_| type_error())?)
+        }
+        OGRFieldType::OFTInteger64 => FieldValue::Integer64Value(integer()?),
+        OGRFieldType::OFTReal => FieldValue::RealValue(number()?),
+        _ => FieldValue::StringValue(value.to_string()),
+    };
+    Ok(field_value)
+}
+
+impl FeatureProcessor for GdalDatasetWriter {
+    fn feature_begin(&mut self, _idx: u64) -> Result<()> {
+        self.names.clear();
+        self.values.clear();
+        Ok(())
+    }
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        self.create_layer()?;
+        let layer = self.dataset.layer_by_name(&self.layer_name)?;
+        let defined = layer.defn().fields().count();
+        let new_fields: Vec<(&str, OGRFieldType::Type)> = self
+            .fields
+            .iter()
+            .skip(defined)
+            .map(|(name, field_type)| (name.as_str(), *field_type))
+            .collect();
+        if !new_fields.is_empty() {
+            layer.create_defn_fields(&new_fields)?;
+        }
+        self.created_fields = self.fields.len();
+        let mut layer = self.dataset.layer_by_name(&self.layer_name)?;
+        let geom = mem::replace(
+            &mut self.geom.geom,
+            Geometry::empty(OGRwkbGeometryType::wkbPoint)?,
+        );
+        let names: Vec<&str> = self.names.iter().map(String::as_str).collect();
+        layer.create_feature_fields(geom, &names, &self.values)?;
+        Ok(())
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        let spatial_ref = self.geom.spatial_ref.take();
+        self.geom = GdalWriter::with_dims(self.dims);
+        self.geom.spatial_ref = spatial_ref;
+        Ok(())
+    }
+}
+
+impl PropertyProcessor for GdalDatasetWriter {
+    fn property(&mut self, _idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        let field_idx = match self
+            .fields
+            .iter()
+            .position(|(field_name, _)| field_name == name)
+        {
+            Some(field_idx) => field_idx,
+            None => {
+                self.fields.push((name.to_string(), ogr_field_type(value)));
+                self.fields.len() - 1
+            }
+        };
+        // Widen integer fields instead of truncating values
+        if self.fields[field_idx].1 == OGRFieldType::OFTInteger
+            && integer_value(value).is_some_and(|v| i32::try_from(v).is_err())
+        {
+            self.widen_field(field_idx)?;
+        }
+        let field_type = self.fields[field_idx].1;
+        self.names.push(name.to_string());
+        self.values.push(field_value(field_type, value)?);
+        Ok(false)
+    }
+}
+
+impl GeomProcessor for GdalDatasetWriter {
+    fn dimensions(&self) -> CoordDimensions {
+        self.geom.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.geom.multi_dim()
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.geom.srid(srid)
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.geom.xy(x, y, idx)
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.geom.coordinate(x, y, z, m, t, tm, idx)
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.geom.empty_point(idx)
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.geom.point_begin(idx)
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.geom.point_end(idx)
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom.multipoint_begin(size, idx)
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.geom.multipoint_end(idx)
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.geom.linestring_begin(tagged, size, idx)
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.geom.linestring_end(tagged, idx)
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom.multilinestring_begin(size, idx)
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.geom.multilinestring_end(idx)
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.geom.polygon_begin(tagged, size, idx)
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.geom.polygon_end(tagged, idx)
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom.multipolygon_begin(size, idx)
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.geom.multipolygon_end(idx)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::GeoJson;
+    use crate::GeozeroDatasource;
+    use gdal::DriverManager;
+
+    #[test]
+    fn write_features() -> Result<()> {
+        let mut geojson = GeoJson(
+            r#"{"type": "FeatureCollection", "features": [
+                {"type": "Feature", "properties": {"name": "Bern", "population": 121631}, "geometry": {"type": "Point", "coordinates": [7.4, 46.9]}},
+                {"type": "Feature", "properties": {"name": "Zurich", "population": 402762, "area": 87.88}, "geometry": {"type": "Point", "coordinates": [8.5, 47.4]}}
+            ]}"#,
+        );
+        let driver = DriverManager::get_driver_by_name("Memory")?;
+        let dataset = driver.create_vector_only("")?;
+        let mut writer = GdalDatasetWriter::new(dataset, "places");
+        geojson.process(&mut writer)?;
+
+        let dataset = writer.into_dataset();
+        let mut layer = dataset.layer_by_name("places")?;
+        assert_eq!(layer.feature_count(), 2);
+        let field_names: Vec<String> = layer.defn().fields().map(|field| field.name()).collect();
+        assert_eq!(field_names, vec!["name", "population", "area"]);
+        let feature = layer.features().nth(1).unwrap();
+        assert_eq!(
+            feature.field("name")?,
+            Some(FieldValue::StringValue("Zurich".to_string()))
+        );
+        assert_eq!(
+            feature.geometry().unwrap().wkt().unwrap(),
+            "POINT (8.5 47.4)"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn widen_integer_field() -> Result<()> {
+        let driver = DriverManager::get_driver_by_name("Memory")?;
+        let dataset = driver.create_vector_only("")?;
+        let mut writer = GdalDatasetWriter::new(dataset, "counts");
+        let values = [ColumnValue::Int(1), ColumnValue::Long(1 << 40)];
+        for (idx, value) in values.iter().enumerate() {
+            writer.feature_begin(idx as u64)?;
+            writer.property(0, "count", value)?;
+            writer.feature_end(idx as u64)?;
+        }
+        writer.feature_begin(2)?;
+        assert!(writer
+            .property(0, "count", &ColumnValue::Double(1.5))
+            .is_err());
+
+        let dataset = writer.into_dataset();
+        let mut layer = dataset.layer_by_name("counts")?;
+        let field_types: Vec<_> = layer
+            .defn()
+            .fields()
+            .map(|field| field.field_type())
+            .collect();
+        assert_eq!(field_types, vec![OGRFieldType::OFTInteger64]);
+        let feature = layer.features().nth(1).unwrap();
+        assert_eq!(
+            feature.field("count")?,
+            Some(FieldValue::Integer64Value(1 << 40))
+        );
+        Ok(())
+    }
+}
@@ -6,4 +6,6 @@ use thiserror::Error;
 pub enum GdalError {
     #[error("Unsupported geometry type: {0}")]
     UnsupportedGeometryType(OGRwkbGeometryType::Type),
+    #[error("GDAL error `{0}`")]
+    Gdal(#[from] gdal::errors::GdalError),
 }
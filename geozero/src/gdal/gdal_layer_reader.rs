@@ -0,0 +1,106 @@
+use crate::error::Result;
+use crate::gdal::process_geom;
+use crate::{ColumnValue, FeatureProcessor, GeozeroDatasource};
+use gdal::vector::{FieldValue, LayerAccess};
+
+/// OGR layer reader.
+///
+/// Makes every OGR vector format available as [GeozeroDatasource].
+/// All fields are emitted as properties, the SRID is taken from the EPSG code of the layer.
+///
+/// # Usage example:
+///
+/// ```rust,ignore
+/// use gdal::Dataset;
+/// use geozero::gdal::GdalLayerReader;
+/// use geozero::{geojson::GeoJsonWriter, GeozeroDatasource};
+///
+/// let dataset = Dataset::open("places.gml")?;
+/// let mut reader = GdalLayerReader::new(dataset.layer(0)?);
+/// reader.process(&mut GeoJsonWriter::new(&mut out))?;
+/// ```
+pub struct GdalLayerReader<L: LayerAccess> {
+    layer: L,
+}
+
+impl<L: LayerAccess> GdalLayerReader<L> {
+    pub fn new(layer: L) -> Self {
+        GdalLayerReader { layer }
+    }
+    pub fn into_inner(self) -> L {
+        self.layer
+    }
+    fn srid(&self) -> Option<i32> {
+        self.layer
+            .spatial_ref()
+            .and_then(|spatial_ref| spatial_ref.auth_code().ok())
+    }
+}
+
+impl<L: LayerAccess> GeozeroDatasource for GdalLayerReader<L> {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        let srid = self.srid();
+        let name = self.layer.name();
+        processor.dataset_begin(Some(&name))?;
+        for (idx, feature) in self.layer.features().enumerate() {
+            let idx = idx as u64;
+            processor.feature_begin(idx)?;
+            processor.properties_begin()?;
+            let mut prop_idx = 0;
+            for (name, value) in feature.fields() {
+                if let Some(value) = value {
+                    if process_field(prop_idx, &name, &value, processor)? {
+                        break;
+                    }
+                    prop_idx += 1;
+                }
+            }
+            processor.properties_end()?;
+            if let Some(geometry) = feature.geometry() {
+                processor.geometry_begin()?;
+                processor.srid(srid)?;
+                process_geom(geometry, processor)?;
+                processor.geometry_end()?;
+            }
+            processor.feature_end(idx)?;
+        }
+        processor.dataset_end()
+    }
+}
+
+/// Process field value as property. Returns `true` if processing should be aborted.
+fn process_field<P: FeatureProcessor>(
+    idx: usize,
+    name: &str,
+    value: &FieldValue,
+    processor: &mut P,
+) -> Result<bool> {
+    match value {
+        FieldValue::IntegerValue(v) => processor.property(idx, name, &ColumnValue::Int(*v)),
+        FieldValue::Integer64Value(v) => processor.property(idx, name, &ColumnValue::Long(*v)),
+        FieldValue::RealValue(v) => processor.property(idx, name, &ColumnValue::Double(*v)),
+        FieldValue::StringValue(v) => processor.property(idx, name, &ColumnValue::String(v)),
+        FieldValue::DateValue(v) => {
+            processor.property(idx, name, &ColumnValue::DateTime(&v.to_string()))
+        }
+        FieldValue::DateTimeValue(v) => {
+            processor.property(idx, name, &ColumnValue::DateTime(&v.to_rfc3339()))
+        }
+        FieldValue::IntegerListValue(v) => {
+            processor.property(idx, name, &ColumnValue::Json(&json_list(v)))
+        }
+        FieldValue::Integer64ListValue(v) => {
+            processor.property(idx, name, &ColumnValue::Json(&json_list(v)))
+        }
+        FieldValue::RealListValue(v) => {
+            processor.property(idx, name, &ColumnValue::Json(&json_list(v)))
+        }
+        FieldValue::StringListValue(v) => {
+            processor.property(idx, name, &ColumnValue::Json(&json_list(v)))
+        }
+    }
+}
+
+fn json_list<T: Clone + Into<serde_json::Value>>(values: &[T]) -> String {
+    serde_json::Value::from(values.to_vec()).to_string()
+}
@@ -2,15 +2,21 @@ use crate::error::{GeozeroError, Result};
 use crate::gdal::gdal_error::GdalError;
 use crate::{CoordDimensions, FeatureProcessor, GeomProcessor, PropertyProcessor};
 
+use gdal::spatial_ref::SpatialRef;
 use gdal::vector::Geometry;
 use gdal_sys::OGRwkbGeometryType;
 
 /// Generator for GDAL geometry type.
+///
+/// The spatial reference of the output geometry is set from the SRID of the processed geometry.
 pub struct GdalWriter {
     dims: CoordDimensions,
+    pub(crate) spatial_ref: Option<SpatialRef>,
     pub(crate) geom: Geometry,
     // current line/ring of geom (non-owned)
     line: Geometry,
+    // number of points written to the current line
+    line_points: usize,
 }
 
 impl GdalWriter {
@@ -26,6 +32,10 @@ impl GdalWriter {
     pub fn geometry(&self) -> &Geometry {
         &self.geom
     }
+    /// Spatial reference of the processed geometry
+    pub fn spatial_ref(&self) -> Option<&SpatialRef> {
+        self.spatial_ref.as_ref()
+    }
     fn wkb_type(&mut self, base: OGRwkbGeometryType::Type) -> OGRwkbGeometryType::Type {
         let mut type_id = base;
         if self.dims.z {
@@ -39,25 +49,41 @@ impl GdalWriter {
     fn empty_geom(&mut self, base: OGRwkbGeometryType::Type) -> Result<Geometry> {
         Geometry::empty(self.wkb_type(base)).map_err(|e| e.into())
     }
+    /// Start new top-level geometry
+    fn begin_geom(&mut self, base: OGRwkbGeometryType::Type) -> Result<()> {
+        self.geom = self.empty_geom(base)?;
+        if let Some(spatial_ref) = &self.spatial_ref {
+            self.geom.set_spatial_ref(spatial_ref.clone());
+        }
+        Ok(())
+    }
 }
 
 impl Default for GdalWriter {
     fn default() -> Self {
         GdalWriter {
             dims: CoordDimensions::default(),
+            spatial_ref: None,
             geom: Geometry::empty(OGRwkbGeometryType::wkbPoint).unwrap(),
             line: Geometry::empty(OGRwkbGeometryType::wkbLineString).unwrap(),
+            line_points: 0,
         }
     }
 }
 
+/// Set number of points of a line geometry, preallocating or removing points
+fn set_point_count(geom: &Geometry, size: usize) {
+    let size = i32::try_from(size).unwrap_or(i32::MAX);
+    unsafe { gdal_sys::OGR_G_SetPointCount(geom.c_geometry(), size) };
+}
+
 fn wkb_base_type(wkb_type: OGRwkbGeometryType::Type) -> OGRwkbGeometryType::Type {
     wkb_type % 1000
 }
 
 impl From<gdal::errors::GdalError> for GeozeroError {
     fn from(error: gdal::errors::GdalError) -> Self {
-        GeozeroError::Geometry(error.to_string())
+        GeozeroError::GdalError(GdalError::Gdal(error))
     }
 }
 
@@ -65,10 +91,24 @@ impl GeomProcessor for GdalWriter {
     fn dimensions(&self) -> CoordDimensions {
         self.dims
     }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.spatial_ref = match srid {
+            None | Some(0) => None,
+            Some(srid) => match SpatialRef::from_epsg(srid as u32) {
+                Ok(spatial_ref) => Some(spatial_ref),
+                Err(e) => {
+                    log::warn!("ignoring SRID {srid} without EPSG spatial reference: {e}");
+                    None
+                }
+            },
+        };
+        Ok(())
+    }
     fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
         match self.geom.geometry_type() {
             OGRwkbGeometryType::wkbPoint | OGRwkbGeometryType::wkbLineString => {
                 self.geom.set_point_2d(idx, (x, y));
+                self.line_points = self.line_points.max(idx + 1);
             }
             OGRwkbGeometryType::wkbMultiPoint => {
                 let mut point = self.empty_geom(OGRwkbGeometryType::wkbPoint)?;
@@ -79,6 +119,7 @@ impl GeomProcessor for GdalWriter {
             | OGRwkbGeometryType::wkbPolygon
             | OGRwkbGeometryType::wkbMultiPolygon => {
                 self.line.set_point_2d(idx, (x, y));
+                self.line_points = self.line_points.max(idx + 1);
             }
             _ => {
                 let unsupported_type = self.geom.geometry_type();
@@ -101,6 +142,7 @@ impl GeomProcessor for GdalWriter {
         match wkb_base_type(self.geom.geometry_type()) {
             OGRwkbGeometryType::wkbPoint | OGRwkbGeometryType::wkbLineString => {
                 self.geom.set_point(idx, (x, y, z));
+                self.line_points = self.line_points.max(idx + 1);
             }
             OGRwkbGeometryType::wkbMultiPoint => {
                 let mut point = self.empty_geom(OGRwkbGeometryType::wkbPoint)?;
@@ -111,6 +153,7 @@ impl GeomProcessor for GdalWriter {
             | OGRwkbGeometryType::wkbPolygon
             | OGRwkbGeometryType::wkbMultiPolygon => {
                 self.line.set_point(idx, (x, y, z));
+                self.line_points = self.line_points.max(idx + 1);
             }
             _ => {
                 let unsupported_type = self.geom.geometry_type();
@@ -120,16 +163,16 @@ impl GeomProcessor for GdalWriter {
         Ok(())
     }
     fn point_begin(&mut self, _idx: usize) -> Result<()> {
-        self.geom = self.empty_geom(OGRwkbGeometryType::wkbPoint)?;
-        Ok(())
+        self.begin_geom(OGRwkbGeometryType::wkbPoint)
     }
     fn multipoint_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
-        self.geom = self.empty_geom(OGRwkbGeometryType::wkbMultiPoint)?;
-        Ok(())
+        self.begin_geom(OGRwkbGeometryType::wkbMultiPoint)
     }
-    fn linestring_begin(&mut self, tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+    fn linestring_begin(&mut self, tagged: bool, size: usize, _idx: usize) -> Result<()> {
+        self.line_points = 0;
         if tagged {
-            self.geom = self.empty_geom(OGRwkbGeometryType::wkbLineString)?;
+            self.begin_geom(OGRwkbGeometryType::wkbLineString)?;
+            set_point_count(&self.geom, size);
         } else {
             match wkb_base_type(self.geom.geometry_type()) {
                 OGRwkbGeometryType::wkbMultiLineString => {
@@ -159,25 +202,33 @@ impl GeomProcessor for GdalWriter {
                     return Err(GdalError::UnsupportedGeometryType(unsupported_type))?;
                 }
             };
+            set_point_count(&self.line, size);
         }
         Ok(())
     }
-    fn multilinestring_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
-        self.geom = self.empty_geom(OGRwkbGeometryType::wkbMultiLineString)?;
+    fn linestring_end(&mut self, tagged: bool, _idx: usize) -> Result<()> {
+        // Trim preallocated points, if fewer points than announced were written
+        if tagged {
+            set_point_count(&self.geom, self.line_points);
+        } else {
+            set_point_count(&self.line, self.line_points);
+        }
         Ok(())
     }
+    fn multilinestring_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin_geom(OGRwkbGeometryType::wkbMultiLineString)
+    }
     fn polygon_begin(&mut self, tagged: bool, _size: usize, _idx: usize) -> Result<()> {
-        let poly = self.empty_geom(OGRwkbGeometryType::wkbPolygon)?;
         if tagged {
-            self.geom = poly;
+            self.begin_geom(OGRwkbGeometryType::wkbPolygon)?;
         } else {
+            let poly = self.empty_geom(OGRwkbGeometryType::wkbPolygon)?;
             self.geom.add_geometry(poly)?;
         }
         Ok(())
     }
     fn multipolygon_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
-        self.geom = self.empty_geom(OGRwkbGeometryType::wkbMultiPolygon)?;
-        Ok(())
+        self.begin_geom(OGRwkbGeometryType::wkbMultiPolygon)
     }
 }
 
@@ -232,6 +283,16 @@ mod test {
     //     assert_eq!(geom.wkt().unwrap(), wkt);
     // }
 
+    #[test]
+    fn line_geom_fewer_points() {
+        let mut writer = GdalWriter::new();
+        assert!(writer.linestring_begin(true, 3, 0).is_ok());
+        assert!(writer.xy(1.0, 1.0, 0).is_ok());
+        assert!(writer.xy(2.0, 2.0, 1).is_ok());
+        assert!(writer.linestring_end(true, 0).is_ok());
+        assert_eq!(writer.geometry().wkt().unwrap(), "LINESTRING (1 1,2 2)");
+    }
+
     #[test]
     fn multiline_geom() {
         let geojson =
@@ -275,6 +336,29 @@ mod test {
     //     assert_eq!(geom.wkt().unwrap(), wkt);
     // }
 
+    #[test]
+    #[cfg(feature = "with-wkb")]
+    fn spatial_ref() {
+        use crate::wkb::Ewkb;
+        use crate::GeozeroGeometry;
+        // SRID=4326;POINT(10 -20)
+        let ewkb = Ewkb(hex::decode("0101000020E6100000000000000000244000000000000034C0").unwrap());
+        let mut writer = GdalWriter::new();
+        ewkb.process_geom(&mut writer).unwrap();
+        let spatial_ref = writer.geometry().spatial_ref().unwrap();
+        assert_eq!(spatial_ref.auth_code().unwrap(), 4326);
+    }
+
+    #[test]
+    fn unknown_srid() {
+        let mut writer = GdalWriter::new();
+        assert!(writer.srid(Some(999999)).is_ok());
+        assert!(writer.point_begin(0).is_ok());
+        assert!(writer.xy(1.0, 2.0, 0).is_ok());
+        assert!(writer.spatial_ref().is_none());
+        assert!(writer.geometry().spatial_ref().is_none());
+    }
+
     #[test]
     fn gdal_error() {
         let mut geom = GdalWriter::new();
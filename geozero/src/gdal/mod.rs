@@ -1,9 +1,13 @@
 //! GDAL conversions.
+pub(crate) mod gdal_dataset_writer;
 mod gdal_error;
+pub(crate) mod gdal_layer_reader;
 pub(crate) mod gdal_reader;
 pub(crate) mod gdal_writer;
 
+pub use gdal_dataset_writer::*;
 pub use gdal_error::GdalError;
+pub use gdal_layer_reader::*;
 pub use gdal_reader::*;
 pub use gdal_writer::*;
 
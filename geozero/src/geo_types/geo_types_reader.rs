@@ -1,6 +1,9 @@
 use crate::error::Result;
 use crate::{GeomProcessor, GeozeroGeometry};
-use geo_types::{Coord, Geometry, LineString, Polygon};
+use geo_types::{
+    Coord, Geometry, GeometryCollection, Line, LineString, MultiLineString, MultiPoint,
+    MultiPolygon, Point, Polygon, Rect, Triangle,
+};
 
 impl GeozeroGeometry for Geometry<f64> {
     fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
@@ -8,6 +11,66 @@ impl GeozeroGeometry for Geometry<f64> {
     }
 }
 
+impl GeozeroGeometry for Point<f64> {
+    fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
+        process_point(self, 0, processor)
+    }
+}
+
+impl GeozeroGeometry for MultiPoint<f64> {
+    fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
+        process_multipoint(self, 0, processor)
+    }
+}
+
+impl GeozeroGeometry for LineString<f64> {
+    fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
+        process_linestring(self, true, 0, processor)
+    }
+}
+
+impl GeozeroGeometry for MultiLineString<f64> {
+    fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
+        process_multilinestring(self, 0, processor)
+    }
+}
+
+impl GeozeroGeometry for Polygon<f64> {
+    fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
+        process_polygon(self, true, 0, processor)
+    }
+}
+
+impl GeozeroGeometry for MultiPolygon<f64> {
+    fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
+        process_multipolygon(self, 0, processor)
+    }
+}
+
+impl GeozeroGeometry for GeometryCollection<f64> {
+    fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
+        process_geometrycollection(self, 0, processor)
+    }
+}
+
+impl GeozeroGeometry for Rect<f64> {
+    fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
+        process_polygon(&self.to_polygon(), true, 0, processor)
+    }
+}
+
+impl GeozeroGeometry for Line<f64> {
+    fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
+        process_line(self, 0, processor)
+    }
+}
+
+impl GeozeroGeometry for Triangle<f64> {
+    fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
+        process_polygon(&self.to_polygon(), true, 0, processor)
+    }
+}
+
 /// Process geo-types geometry.
 pub fn process_geom<P: GeomProcessor>(geom: &Geometry<f64>, processor: &mut P) -> Result<()> {
     process_geom_n(geom, 0, processor)
@@ -19,52 +82,80 @@ fn process_geom_n<P: GeomProcessor>(
     processor: &mut P,
 ) -> Result<()> {
     match geom {
-        Geometry::Point(ref geom) => {
-            processor.point_begin(idx)?;
-            process_coord(&geom.0, 0, processor)?;
-            processor.point_end(idx)
-        }
-        Geometry::Line(geom) => {
-            processor.linestring_begin(true, 2, idx)?;
-            process_coord(&geom.start, 0, processor)?;
-            process_coord(&geom.end, 1, processor)?;
-            processor.linestring_end(true, idx)
-        }
+        Geometry::Point(ref geom) => process_point(geom, idx, processor),
+        Geometry::Line(ref geom) => process_line(geom, idx, processor),
         Geometry::LineString(ref geom) => process_linestring(geom, true, idx, processor),
         Geometry::Polygon(ref geom) => process_polygon(geom, true, idx, processor),
-        Geometry::MultiPoint(ref geom) => {
-            processor.multipoint_begin(geom.0.len(), idx)?;
-            for (i, pt) in geom.0.iter().enumerate() {
-                process_coord(&pt.0, i, processor)?;
-            }
-            processor.multipoint_end(idx)
-        }
-        Geometry::MultiLineString(ref geom) => {
-            processor.multilinestring_begin(geom.0.len(), idx)?;
-            for (i, line) in geom.0.iter().enumerate() {
-                process_linestring(line, false, i, processor)?;
-            }
-            processor.multilinestring_end(idx)
-        }
-        Geometry::MultiPolygon(ref geom) => {
-            processor.multipolygon_begin(geom.0.len(), idx)?;
-            for (i, poly) in geom.0.iter().enumerate() {
-                process_polygon(poly, false, i, processor)?;
-            }
-            processor.multipolygon_end(idx)
-        }
-        Geometry::GeometryCollection(ref geom) => {
-            processor.geometrycollection_begin(geom.0.len(), idx)?;
-            for (i, g) in geom.0.iter().enumerate() {
-                process_geom_n(g, i, processor)?;
-            }
-            processor.geometrycollection_end(idx)
-        }
+        Geometry::MultiPoint(ref geom) => process_multipoint(geom, idx, processor),
+        Geometry::MultiLineString(ref geom) => process_multilinestring(geom, idx, processor),
+        Geometry::MultiPolygon(ref geom) => process_multipolygon(geom, idx, processor),
+        Geometry::GeometryCollection(ref geom) => process_geometrycollection(geom, idx, processor),
         Geometry::Rect(geom) => process_polygon(&geom.to_polygon(), true, idx, processor),
         Geometry::Triangle(geom) => process_polygon(&geom.to_polygon(), true, idx, processor),
     }
 }
 
+fn process_point<P: GeomProcessor>(geom: &Point<f64>, idx: usize, processor: &mut P) -> Result<()> {
+    processor.point_begin(idx)?;
+    process_coord(&geom.0, 0, processor)?;
+    processor.point_end(idx)
+}
+
+fn process_line<P: GeomProcessor>(geom: &Line<f64>, idx: usize, processor: &mut P) -> Result<()> {
+    processor.linestring_begin(true, 2, idx)?;
+    process_coord(&geom.start, 0, processor)?;
+    process_coord(&geom.end, 1, processor)?;
+    processor.linestring_end(true, idx)
+}
+
+fn process_multipoint<P: GeomProcessor>(
+    geom: &MultiPoint<f64>,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    processor.multipoint_begin(geom.0.len(), idx)?;
+    for (i, pt) in geom.0.iter().enumerate() {
+        process_coord(&pt.0, i, processor)?;
+    }
+    processor.multipoint_end(idx)
+}
+
+fn process_multilinestring<P: GeomProcessor>(
+    geom: &MultiLineString<f64>,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    processor.multilinestring_begin(geom.0.len(), idx)?;
+    for (i, line) in geom.0.iter().enumerate() {
+        process_linestring(line, false, i, processor)?;
+    }
+    processor.multilinestring_end(idx)
+}
+
+fn process_multipolygon<P: GeomProcessor>(
+    geom: &MultiPolygon<f64>,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    processor.multipolygon_begin(geom.0.len(), idx)?;
+    for (i, poly) in geom.0.iter().enumerate() {
+        process_polygon(poly, false, i, processor)?;
+    }
+    processor.multipolygon_end(idx)
+}
+
+fn process_geometrycollection<P: GeomProcessor>(
+    geom: &GeometryCollection<f64>,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    processor.geometrycollection_begin(geom.0.len(), idx)?;
+    for (i, g) in geom.0.iter().enumerate() {
+        process_geom_n(g, i, processor)?;
+    }
+    processor.geometrycollection_end(idx)
+}
+
 fn process_coord<P: GeomProcessor>(
     coord: &Coord<f64>,
     idx: usize,
@@ -174,4 +265,79 @@ mod test {
         let geo = Geometry::try_from(wkt::Wkt::from_str(wkt).unwrap()).unwrap();
         assert_eq!(geo.to_wkt().unwrap(), wkt);
     }
+
+    #[test]
+    fn scalar_types() {
+        use geo_types::{coord, line_string, point, polygon, Line, Rect, Triangle};
+
+        assert_eq!(point!(x: 1.0, y: 1.0).to_wkt().unwrap(), "POINT(1 1)");
+        assert_eq!(
+            line_string![(x: 1.0, y: 1.0), (x: 2.0, y: 2.0)]
+                .to_wkt()
+                .unwrap(),
+            "LINESTRING(1 1,2 2)"
+        );
+        assert_eq!(
+            polygon![(x: 0.0, y: 0.0), (x: 0.0, y: 1.0), (x: 1.0, y: 1.0)]
+                .to_wkt()
+                .unwrap(),
+            "POLYGON((0 0,0 1,1 1,0 0))"
+        );
+        assert_eq!(
+            MultiPoint::from(vec![(1.0, 1.0), (2.0, 2.0)])
+                .to_wkt()
+                .unwrap(),
+            "MULTIPOINT(1 1,2 2)"
+        );
+        assert_eq!(
+            Line::new(coord! { x: 1.0, y: 1.0 }, coord! { x: 2.0, y: 2.0 })
+                .to_wkt()
+                .unwrap(),
+            "LINESTRING(1 1,2 2)"
+        );
+        let rect = Rect::new(coord! { x: 0.0, y: 0.0 }, coord! { x: 1.0, y: 1.0 });
+        assert_eq!(
+            rect.to_wkt().unwrap(),
+            Geometry::Polygon(rect.to_polygon()).to_wkt().unwrap()
+        );
+        assert_eq!(
+            Triangle::new(
+                coord! { x: 0.0, y: 0.0 },
+                coord! { x: 1.0, y: 0.0 },
+                coord! { x: 0.0, y: 1.0 }
+            )
+            .to_wkt()
+            .unwrap(),
+            "POLYGON((0 0,1 0,0 1,0 0))"
+        );
+    }
+
+    #[test]
+    fn geometry_scalar_variants() {
+        use geo_types::{coord, Line, Rect, Triangle};
+
+        let line = Line::new(coord! { x: 1.0, y: 1.0 }, coord! { x: 2.0, y: 2.0 });
+        assert_eq!(
+            Geometry::Line(line).to_wkt().unwrap(),
+            "LINESTRING(1 1,2 2)"
+        );
+        let rect = Rect::new(coord! { x: 0.0, y: 0.0 }, coord! { x: 1.0, y: 1.0 });
+        assert_eq!(
+            Geometry::Rect(rect).to_wkt().unwrap(),
+            Geometry::Polygon(rect.to_polygon()).to_wkt().unwrap()
+        );
+        let triangle = Triangle::new(
+            coord! { x: 0.0, y: 0.0 },
+            coord! { x: 1.0, y: 0.0 },
+            coord! { x: 0.0, y: 1.0 },
+        );
+        let collection = Geometry::GeometryCollection(GeometryCollection(vec![
+            Geometry::Triangle(triangle),
+            Geometry::Line(line),
+        ]));
+        assert_eq!(
+            collection.to_wkt().unwrap(),
+            "GEOMETRYCOLLECTION(POLYGON((0 0,1 0,0 1,0 0)),LINESTRING(1 1,2 2))"
+        );
+    }
 }
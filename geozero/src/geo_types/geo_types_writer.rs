@@ -7,6 +7,9 @@ use geo_types::{
 use std::mem;
 
 /// Generator for geo-types geometry type.
+///
+/// Curve geometries have to be linearized with [LinearizeCurves](crate::LinearizeCurves),
+/// as done by [ToGeo](crate::ToGeo).
 #[derive(Default)]
 pub struct GeoWriter {
     geoms: Vec<Geometry<f64>>,
@@ -18,6 +21,10 @@ pub struct GeoWriter {
     line_strings: Option<Vec<LineString<f64>>>,
     /// In-progress point or line_string
     coords: Option<Vec<Coord<f64>>>,
+    /// Reused coordinate buffer of points
+    point_buffer: Vec<Coord<f64>>,
+    /// Reused ring buffer of polygons
+    ring_buffer: Vec<LineString<f64>>,
 }
 
 impl GeoWriter {
@@ -60,17 +67,20 @@ impl GeomProcessor for GeoWriter {
 
     fn point_begin(&mut self, _idx: usize) -> Result<()> {
         debug_assert!(self.coords.is_none());
-        self.coords = Some(Vec::with_capacity(1));
+        self.coords = Some(mem::take(&mut self.point_buffer));
         Ok(())
     }
 
     fn point_end(&mut self, _idx: usize) -> Result<()> {
-        let coords = self
+        let mut coords = self
             .coords
             .take()
             .ok_or(GeozeroError::Geometry("No coords for Point".to_string()))?;
         debug_assert!(coords.len() == 1);
-        self.finish_geometry(Point(coords[0]).into())
+        let point = Point(coords[0]);
+        coords.clear();
+        self.point_buffer = coords;
+        self.finish_geometry(point.into())
     }
 
     fn multipoint_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
@@ -124,21 +134,22 @@ impl GeomProcessor for GeoWriter {
 
     fn polygon_begin(&mut self, _tagged: bool, size: usize, _idx: usize) -> Result<()> {
         debug_assert!(self.line_strings.is_none());
-        self.line_strings = Some(Vec::with_capacity(size));
+        let mut rings = mem::take(&mut self.ring_buffer);
+        rings.reserve(size);
+        self.line_strings = Some(rings);
         Ok(())
     }
 
     fn polygon_end(&mut self, tagged: bool, _idx: usize) -> Result<()> {
-        let mut line_strings = self.line_strings.take().ok_or(GeozeroError::Geometry(
+        let mut rings = self.line_strings.take().ok_or(GeozeroError::Geometry(
             "Missing LineStrings for Polygon".to_string(),
         ))?;
 
-        let polygon = if line_strings.is_empty() {
-            Polygon::new(LineString(vec![]), vec![])
-        } else {
-            let exterior = line_strings.remove(0);
-            Polygon::new(exterior, mem::take(&mut line_strings))
-        };
+        // Interiors are allocated with their exact size, which is none for most polygons
+        let mut drain = rings.drain(..);
+        let exterior = drain.next().unwrap_or_else(|| LineString(vec![]));
+        let polygon = Polygon::new(exterior, drain.collect());
+        self.ring_buffer = rings;
 
         if tagged {
             self.finish_geometry(polygon.into())?;
@@ -288,10 +299,73 @@ mod test {
         assert!(wkt.to_geo().is_ok());
     }
 
+    #[test]
+    #[cfg(feature = "with-wkb")]
+    fn curves() -> Result<()> {
+        use crate::wkb::Ewkb;
+        // CIRCULARSTRING(0 0,1 1,2 0)
+        let ewkb = Ewkb(hex::decode("01080000000300000000000000000000000000000000000000000000000000F03F000000000000F03F00000000000000400000000000000000").unwrap());
+        match ewkb.to_geo()? {
+            Geometry::LineString(line) => {
+                assert_eq!(line.coords_count(), 65);
+                assert_eq!(line.0[32], coord! { x: 1.0, y: 1.0 });
+            }
+            _ => unreachable!(),
+        }
+        // max. deviation 0.1: 2 segments per quarter circle
+        match ewkb.to_geo_linearized(0.1)? {
+            Geometry::LineString(line) => assert_eq!(line.coords_count(), 5),
+            _ => unreachable!(),
+        }
+
+        // CURVEPOLYGON(COMPOUNDCURVE(CIRCULARSTRING(0 0,1 1,2 0),(2 0,3 0,3 -1,0 -1,0 0)))
+        let ewkb = Ewkb(hex::decode("010A0000000100000001090000000200000001080000000300000000000000000000000000000000000000000000000000F03F000000000000F03F0000000000000040000000000000000001020000000500000000000000000000400000000000000000000000000000084000000000000000000000000000000840000000000000F0BF0000000000000000000000000000F0BF00000000000000000000000000000000").unwrap());
+        match ewkb.to_geo()? {
+            Geometry::Polygon(poly) => {
+                assert_eq!(poly.exterior().coords_count(), 65 + 4);
+                assert!(poly.exterior().is_closed());
+            }
+            _ => unreachable!(),
+        }
+        Ok(())
+    }
+
     #[test]
     fn to_geo() -> Result<()> {
         let geom: Geometry<f64> = Point::new(10.0, 20.0).into();
         assert_eq!(geom.clone().to_geo().unwrap(), geom);
         Ok(())
     }
+
+    #[test]
+    fn reused_writer() -> Result<()> {
+        use crate::wkt::Wkt;
+        use crate::GeozeroGeometry;
+        use geo_types::{point, polygon};
+
+        let mut geo = GeoWriter::new();
+        Wkt("POLYGON((0 0,4 0,4 4,0 4,0 0),(1 1,2 1,2 2,1 1))").process_geom(&mut geo)?;
+        let with_hole = geo.take_geometry().unwrap();
+        Wkt("POLYGON((0 0,1 0,1 1,0 0))").process_geom(&mut geo)?;
+        let without_hole = geo.take_geometry().unwrap();
+        Wkt("POINT(1 2)").process_geom(&mut geo)?;
+        let point = geo.take_geometry().unwrap();
+        Wkt("POINT(3 4)").process_geom(&mut geo)?;
+
+        assert_eq!(
+            with_hole,
+            polygon!(
+                exterior: [(x: 0., y: 0.), (x: 4., y: 0.), (x: 4., y: 4.), (x: 0., y: 4.)],
+                interiors: [[(x: 1., y: 1.), (x: 2., y: 1.), (x: 2., y: 2.)]],
+            )
+            .into()
+        );
+        assert_eq!(
+            without_hole,
+            polygon![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.)].into()
+        );
+        assert_eq!(point, point!(x: 1., y: 2.).into());
+        assert_eq!(geo.take_geometry(), Some(point!(x: 3., y: 4.).into()));
+        Ok(())
+    }
 }
@@ -0,0 +1,365 @@
+use crate::error::{GeozeroError, Result};
+use crate::{CoordDimensions, FeatureProcessor, GeomProcessor, GeozeroGeometry, PropertyProcessor};
+use std::mem;
+
+/// Coordinate with elevation. `z` is NaN for coordinates without Z value.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Coord3D {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Coord3D {
+    pub fn new(x: f64, y: f64, z: f64) -> Self {
+        Coord3D { x, y, z }
+    }
+}
+
+pub type LineString3D = Vec<Coord3D>;
+/// Exterior ring followed by interior rings
+pub type Polygon3D = Vec<LineString3D>;
+
+/// Minimal 3D geometry model, complementing the 2D geo-types.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Geometry3D {
+    /// Point. Coordinates are NaN for empty points.
+    Point(Coord3D),
+    LineString(LineString3D),
+    Polygon(Polygon3D),
+    MultiPoint(Vec<Coord3D>),
+    MultiLineString(Vec<LineString3D>),
+    MultiPolygon(Vec<Polygon3D>),
+    GeometryCollection(Vec<Geometry3D>),
+}
+
+/// Generator for [Geometry3D].
+///
+/// Like [GeoWriter](super::GeoWriter), but keeping Z values.
+#[derive(Default)]
+pub struct Geo3DWriter {
+    geoms: Vec<Geometry3D>,
+    /// Stack of any in-progress (potentially nested) GeometryCollections
+    collections: Vec<Vec<Geometry3D>>,
+    /// In-progress multi-polygon
+    polygons: Option<Vec<Polygon3D>>,
+    /// In-progress polygon or multi_linestring
+    line_strings: Option<Vec<LineString3D>>,
+    /// In-progress point or line_string
+    coords: Option<Vec<Coord3D>>,
+}
+
+impl Geo3DWriter {
+    pub fn new() -> Geo3DWriter {
+        Self::default()
+    }
+
+    pub fn take_geometry(&mut self) -> Option<Geometry3D> {
+        match self.geoms.len() {
+            0 => None,
+            1 => Some(self.geoms.pop().unwrap()),
+            _ => {
+                let geoms = mem::take(&mut self.geoms);
+                Some(Geometry3D::GeometryCollection(geoms))
+            }
+        }
+    }
+
+    fn finish_geometry(&mut self, geometry: Geometry3D) -> Result<()> {
+        if let Some(most_recent_collection) = self.collections.last_mut() {
+            most_recent_collection.push(geometry);
+        } else {
+            self.geoms.push(geometry);
+        }
+        Ok(())
+    }
+
+    fn add_coord(&mut self, coord: Coord3D) -> Result<()> {
+        let coords = self
+            .coords
+            .as_mut()
+            .ok_or(GeozeroError::Geometry("Not ready for coords".to_string()))?;
+        coords.push(coord);
+        Ok(())
+    }
+}
+
+impl GeomProcessor for Geo3DWriter {
+    fn dimensions(&self) -> CoordDimensions {
+        CoordDimensions::xyz()
+    }
+
+    fn xy(&mut self, x: f64, y: f64, _idx: usize) -> Result<()> {
+        self.add_coord(Coord3D::new(x, y, f64::NAN))
+    }
+
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        _m: Option<f64>,
+        _t: Option<f64>,
+        _tm: Option<u64>,
+        _idx: usize,
+    ) -> Result<()> {
+        self.add_coord(Coord3D::new(x, y, z.unwrap_or(f64::NAN)))
+    }
+
+    fn empty_point(&mut self, _idx: usize) -> Result<()> {
+        self.finish_geometry(Geometry3D::Point(Coord3D::new(
+            f64::NAN,
+            f64::NAN,
+            f64::NAN,
+        )))
+    }
+
+    fn point_begin(&mut self, _idx: usize) -> Result<()> {
+        debug_assert!(self.coords.is_none());
+        self.coords = Some(Vec::with_capacity(1));
+        Ok(())
+    }
+
+    fn point_end(&mut self, _idx: usize) -> Result<()> {
+        let coords = self
+            .coords
+            .take()
+            .ok_or(GeozeroError::Geometry("No coords for Point".to_string()))?;
+        debug_assert!(coords.len() == 1);
+        self.finish_geometry(Geometry3D::Point(coords[0]))
+    }
+
+    fn multipoint_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        debug_assert!(self.coords.is_none());
+        self.coords = Some(Vec::with_capacity(size));
+        Ok(())
+    }
+
+    fn multipoint_end(&mut self, _idx: usize) -> Result<()> {
+        let coords = self.coords.take().ok_or(GeozeroError::Geometry(
+            "No coords for MultiPoint".to_string(),
+        ))?;
+        self.finish_geometry(Geometry3D::MultiPoint(coords))
+    }
+
+    fn linestring_begin(&mut self, _tagged: bool, size: usize, _idx: usize) -> Result<()> {
+        debug_assert!(self.coords.is_none());
+        self.coords = Some(Vec::with_capacity(size));
+        Ok(())
+    }
+
+    fn linestring_end(&mut self, tagged: bool, _idx: usize) -> Result<()> {
+        let coords = self.coords.take().ok_or(GeozeroError::Geometry(
+            "No coords for LineString".to_string(),
+        ))?;
+        if tagged {
+            self.finish_geometry(Geometry3D::LineString(coords))?;
+        } else {
+            let line_strings = self.line_strings.as_mut().ok_or(GeozeroError::Geometry(
+                "Missing container for LineString".to_string(),
+            ))?;
+            line_strings.push(coords);
+        }
+        Ok(())
+    }
+
+    fn multilinestring_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        debug_assert!(self.line_strings.is_none());
+        self.line_strings = Some(Vec::with_capacity(size));
+        Ok(())
+    }
+
+    fn multilinestring_end(&mut self, _idx: usize) -> Result<()> {
+        let line_strings = self.line_strings.take().ok_or(GeozeroError::Geometry(
+            "No LineStrings for MultiLineString".to_string(),
+        ))?;
+        self.finish_geometry(Geometry3D::MultiLineString(line_strings))
+    }
+
+    fn polygon_begin(&mut self, _tagged: bool, size: usize, _idx: usize) -> Result<()> {
+        debug_assert!(self.line_strings.is_none());
+        self.line_strings = Some(Vec::with_capacity(size));
+        Ok(())
+    }
+
+    fn polygon_end(&mut self, tagged: bool, _idx: usize) -> Result<()> {
+        let rings = self.line_strings.take().ok_or(GeozeroError::Geometry(
+            "Missing LineStrings for Polygon".to_string(),
+        ))?;
+        if tagged {
+            self.finish_geometry(Geometry3D::Polygon(rings))?;
+        } else {
+            let polygons = self.polygons.as_mut().ok_or(GeozeroError::Geometry(
+                "Missing container for Polygon".to_string(),
+            ))?;
+            polygons.push(rings);
+        }
+        Ok(())
+    }
+
+    fn multipolygon_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        debug_assert!(self.polygons.is_none());
+        self.polygons = Some(Vec::with_capacity(size));
+        Ok(())
+    }
+
+    fn multipolygon_end(&mut self, _idx: usize) -> Result<()> {
+        let polygons = self.polygons.take().ok_or(GeozeroError::Geometry(
+            "Missing polygons for MultiPolygon".to_string(),
+        ))?;
+        self.finish_geometry(Geometry3D::MultiPolygon(polygons))
+    }
+
+    fn geometrycollection_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.collections.push(Vec::with_capacity(size));
+        Ok(())
+    }
+
+    fn geometrycollection_end(&mut self, _idx: usize) -> Result<()> {
+        let geometries = self.collections.pop().ok_or(GeozeroError::Geometry(
+            "Unexpected geometry type".to_string(),
+        ))?;
+        self.finish_geometry(Geometry3D::GeometryCollection(geometries))
+    }
+}
+
+impl PropertyProcessor for Geo3DWriter {}
+
+impl FeatureProcessor for Geo3DWriter {}
+
+impl GeozeroGeometry for Geometry3D {
+    fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
+        process_geom(self, 0, processor)
+    }
+    fn dims(&self) -> CoordDimensions {
+        CoordDimensions::xyz()
+    }
+}
+
+fn process_geom<P: GeomProcessor>(geom: &Geometry3D, idx: usize, processor: &mut P) -> Result<()> {
+    match geom {
+        Geometry3D::Point(coord) => {
+            if coord.x.is_nan() && coord.y.is_nan() {
+                processor.empty_point(idx)
+            } else {
+                processor.point_begin(idx)?;
+                process_coord(coord, 0, processor)?;
+                processor.point_end(idx)
+            }
+        }
+        Geometry3D::LineString(coords) => process_linestring(coords, true, idx, processor),
+        Geometry3D::Polygon(rings) => process_polygon(rings, true, idx, processor),
+        Geometry3D::MultiPoint(coords) => {
+            processor.multipoint_begin(coords.len(), idx)?;
+            for (i, coord) in coords.iter().enumerate() {
+                process_coord(coord, i, processor)?;
+            }
+            processor.multipoint_end(idx)
+        }
+        Geometry3D::MultiLineString(lines) => {
+            processor.multilinestring_begin(lines.len(), idx)?;
+            for (i, line) in lines.iter().enumerate() {
+                process_linestring(line, false, i, processor)?;
+            }
+            processor.multilinestring_end(idx)
+        }
+        Geometry3D::MultiPolygon(polygons) => {
+            processor.multipolygon_begin(polygons.len(), idx)?;
+            for (i, polygon) in polygons.iter().enumerate() {
+                process_polygon(polygon, false, i, processor)?;
+            }
+            processor.multipolygon_end(idx)
+        }
+        Geometry3D::GeometryCollection(geoms) => {
+            processor.geometrycollection_begin(geoms.len(), idx)?;
+            for (i, geom) in geoms.iter().enumerate() {
+                process_geom(geom, i, processor)?;
+            }
+            processor.geometrycollection_end(idx)
+        }
+    }
+}
+
+fn process_coord<P: GeomProcessor>(coord: &Coord3D, idx: usize, processor: &mut P) -> Result<()> {
+    if processor.multi_dim() {
+        let z = if coord.z.is_nan() {
+            None
+        } else {
+            Some(coord.z)
+        };
+        processor.coordinate(coord.x, coord.y, z, None, None, None, idx)
+    } else {
+        processor.xy(coord.x, coord.y, idx)
+    }
+}
+
+fn process_linestring<P: GeomProcessor>(
+    coords: &[Coord3D],
+    tagged: bool,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    processor.linestring_begin(tagged, coords.len(), idx)?;
+    for (i, coord) in coords.iter().enumerate() {
+        process_coord(coord, i, processor)?;
+    }
+    processor.linestring_end(tagged, idx)
+}
+
+fn process_polygon<P: GeomProcessor>(
+    rings: &[LineString3D],
+    tagged: bool,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    processor.polygon_begin(tagged, rings.len(), idx)?;
+    for (i, ring) in rings.iter().enumerate() {
+        process_linestring(ring, false, i, processor)?;
+    }
+    processor.polygon_end(tagged, idx)
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::{GeoJson, GeoJsonWriter};
+    use crate::ToGeo3D;
+
+    #[test]
+    fn line_string_z() -> Result<()> {
+        let geojson = GeoJson(r#"{"type": "LineString", "coordinates": [[1,1,10], [2,2,20]]}"#);
+        let geom = geojson.to_geo3d()?;
+        assert_eq!(
+            geom,
+            Geometry3D::LineString(vec![
+                Coord3D::new(1.0, 1.0, 10.0),
+                Coord3D::new(2.0, 2.0, 20.0)
+            ])
+        );
+        let mut out: Vec<u8> = Vec::new();
+        geom.process_geom(&mut GeoJsonWriter::with_dims(
+            &mut out,
+            CoordDimensions::xyz(),
+        ))?;
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            r#"{"type": "LineString", "coordinates": [[1,1,10],[2,2,20]]}"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn polygon_xy() -> Result<()> {
+        let geojson =
+            GeoJson(r#"{"type": "MultiPolygon", "coordinates": [[[[0,0],[0,1],[1,1],[0,0]]]]}"#);
+        match geojson.to_geo3d()? {
+            Geometry3D::MultiPolygon(polygons) => {
+                assert_eq!(polygons[0][0].len(), 4);
+                assert!(polygons[0][0][1].z.is_nan());
+            }
+            _ => unreachable!(),
+        }
+        Ok(())
+    }
+}
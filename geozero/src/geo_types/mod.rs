@@ -1,26 +1,60 @@
 //! geo-types conversions.
 pub(crate) mod geo_types_reader;
 pub(crate) mod geo_types_writer;
+pub(crate) mod geometry_3d;
 
 pub use geo_types_reader::*;
 pub use geo_types_writer::*;
+pub use geometry_3d::*;
 
 pub(crate) mod conversion {
     use crate::error::{GeozeroError, Result};
-    use crate::geo_types::GeoWriter;
-    use crate::GeozeroGeometry;
+    use crate::geo_types::{Geo3DWriter, GeoWriter, Geometry3D};
+    use crate::{GeomProcessor, GeozeroGeometry};
+    use std::f64::consts::PI;
 
     /// Convert to geo-types Geometry.
     pub trait ToGeo {
         /// Convert to geo-types Geometry.
+        ///
+        /// Curves are linearized with the default settings of [LinearizeCurves](crate::LinearizeCurves).
         fn to_geo(&self) -> Result<geo_types::Geometry<f64>>;
+        /// Convert to geo-types Geometry, linearizing curves with a maximal deviation from the original arc.
+        fn to_geo_linearized(&self, max_deviation: f64) -> Result<geo_types::Geometry<f64>>;
     }
 
     impl<T: GeozeroGeometry> ToGeo for T {
         fn to_geo(&self) -> Result<geo_types::Geometry<f64>> {
-            let mut geo = GeoWriter::new();
+            let mut geo = GeoWriter::new().linearize_curves();
+            self.process_geom(&mut geo)?;
+            geo.inner
+                .take_geometry()
+                .ok_or(GeozeroError::Geometry("Missing Geometry".to_string()))
+        }
+        fn to_geo_linearized(&self, max_deviation: f64) -> Result<geo_types::Geometry<f64>> {
+            let mut geo = GeoWriter::new().linearize_curves();
+            // Deviation replaces the default angle criterion
+            geo.set_max_angle(PI)?;
+            geo.set_max_deviation(Some(max_deviation))?;
+            self.process_geom(&mut geo)?;
+            geo.inner
+                .take_geometry()
+                .ok_or(GeozeroError::Geometry("Missing Geometry".to_string()))
+        }
+    }
+
+    /// Convert to [Geometry3D], keeping Z values.
+    pub trait ToGeo3D {
+        /// Convert to [Geometry3D], keeping Z values.
+        fn to_geo3d(&self) -> Result<Geometry3D>;
+    }
+
+    impl<T: GeozeroGeometry> ToGeo3D for T {
+        fn to_geo3d(&self) -> Result<Geometry3D> {
+            let mut geo = Geo3DWriter::new().linearize_curves();
             self.process_geom(&mut geo)?;
-            geo.take_geometry()
+            geo.inner
+                .take_geometry()
                 .ok_or(GeozeroError::Geometry("Missing Geometry".to_string()))
         }
     }
@@ -31,13 +65,15 @@ mod wkb {
     use crate::error::{GeozeroError, Result};
     use crate::geo_types::GeoWriter;
     use crate::wkb::{FromWkb, WkbDialect};
+    use crate::GeomProcessor;
     use std::io::Read;
 
     impl FromWkb for geo_types::Geometry<f64> {
         fn from_wkb<R: Read>(rdr: &mut R, dialect: WkbDialect) -> Result<Self> {
-            let mut geo = GeoWriter::new();
+            let mut geo = GeoWriter::new().linearize_curves();
             crate::wkb::process_wkb_type_geom(rdr, &mut geo, dialect)?;
-            geo.take_geometry()
+            geo.inner
+                .take_geometry()
                 .ok_or(GeozeroError::Geometry("Missing Geometry".to_string()))
         }
     }
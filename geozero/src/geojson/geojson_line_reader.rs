@@ -3,7 +3,7 @@ use crate::{
     FeatureProcessor, GeomProcessor, GeozeroDatasource, GeozeroGeometry,
 };
 
-use super::{process_geojson_geom_n, process_properties};
+use super::{process_feature_id, process_geojson_geom_n, process_properties};
 
 use std::io::{BufRead, BufReader, Read};
 
@@ -12,6 +12,9 @@ use geojson::{Feature, GeoJson as GeoGeoJson, Geometry};
 /// Line Delimited GeoJSON Reader: One feature per line.
 ///
 /// See <https://jsonlines.org>
+///
+/// GeoJSON Text Sequences ([RFC 8142](https://datatracker.ietf.org/doc/html/rfc8142)), where
+/// each record is prefixed with a record separator (`0x1E`), are detected automatically.
 pub struct GeoJsonLineReader<R: Read>(pub(crate) R);
 impl<R: Read> GeoJsonLineReader<R> {
     pub fn new(read: R) -> Self {
@@ -57,8 +60,8 @@ pub fn read_geojson_line_geometries(
         process_geometry(processor, idx, geometry)
     };
 
-    for (idx, line) in buf_reader.lines().enumerate() {
-        match line?.parse::<GeoGeoJson>()? {
+    read_records(buf_reader, |idx, geojson| {
+        match geojson {
             GeoGeoJson::Feature(feature) => {
                 if let Some(geometry) = feature.geometry {
                     add_geometry_to_collection(idx, &geometry)?;
@@ -69,7 +72,8 @@ pub fn read_geojson_line_geometries(
                 return Err(GeozeroError::Dataset("line-delimited GeoJson ('geojsonl') files must have one Feature or Geometry per line".to_string()));
             }
         };
-    }
+        Ok(())
+    })?;
 
     if !started {
         // No geometries were added, so it's an empty collection.
@@ -84,24 +88,149 @@ pub fn read_geojson_lines(reader: impl Read, processor: &mut impl FeatureProcess
     let buf_reader = BufReader::new(reader);
 
     processor.dataset_begin(None)?;
-    for (idx, line) in buf_reader.lines().enumerate() {
-        match line?.parse::<GeoGeoJson>()? {
-            GeoGeoJson::Feature(feature) => process_feature(processor, idx, &feature)?,
-            GeoGeoJson::Geometry(geometry) => process_geometry(processor, idx, &geometry)?,
-            _ => {
-                return Err(GeozeroError::Dataset("line-delimited GeoJson ('geojsonl') files must have one Feature or Geometry per line".to_string()));
-            }
+    read_records(buf_reader, |idx, geojson| {
+        process_record(processor, idx, geojson)
+    })?;
+    processor.dataset_end()
+}
+
+/// Line Delimited GeoJSON Reader parsing records in parallel.
+///
+/// Records are parsed in batches on the rayon thread pool and processed in input order.
+#[cfg(feature = "with-rayon")]
+pub struct ParallelGeoJsonLineReader<R: Read> {
+    reader: R,
+    batch_size: usize,
+}
+
+#[cfg(feature = "with-rayon")]
+impl<R: Read> ParallelGeoJsonLineReader<R> {
+    pub fn new(read: R) -> Self {
+        ParallelGeoJsonLineReader {
+            reader: read,
+            batch_size: 1024,
         }
     }
+    /// Number of records parsed in parallel (default: 1024)
+    pub fn set_batch_size(&mut self, batch_size: usize) {
+        self.batch_size = batch_size.max(1);
+    }
+}
+
+#[cfg(feature = "with-rayon")]
+impl<R: Read> GeozeroDatasource for ParallelGeoJsonLineReader<R> {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        read_geojson_lines_par(&mut self.reader, processor, self.batch_size)
+    }
+}
+
+/// Read and process line delimited GeoJSON (one object per line), parsing `batch_size` records in parallel.
+#[cfg(feature = "with-rayon")]
+pub fn read_geojson_lines_par(
+    reader: impl Read,
+    processor: &mut impl FeatureProcessor,
+    batch_size: usize,
+) -> Result<()> {
+    let buf_reader = BufReader::new(reader);
+    let mut batch = Vec::with_capacity(batch_size);
+
+    processor.dataset_begin(None)?;
+    read_record_texts(buf_reader, |idx, text| {
+        batch.push((idx, text));
+        if batch.len() >= batch_size {
+            process_batch(processor, &mut batch)?;
+        }
+        Ok(())
+    })?;
+    process_batch(processor, &mut batch)?;
     processor.dataset_end()
 }
 
+#[cfg(feature = "with-rayon")]
+fn process_batch(
+    processor: &mut impl FeatureProcessor,
+    batch: &mut Vec<(usize, String)>,
+) -> Result<()> {
+    use rayon::prelude::*;
+
+    let parsed: Vec<_> = batch
+        .par_iter()
+        .map(|(_, text)| text.parse::<GeoGeoJson>())
+        .collect();
+    for ((idx, _), geojson) in batch.drain(..).zip(parsed) {
+        process_record(processor, idx, geojson?)?;
+    }
+    Ok(())
+}
+
+fn process_record(
+    processor: &mut impl FeatureProcessor,
+    idx: usize,
+    geojson: GeoGeoJson,
+) -> Result<()> {
+    match geojson {
+        GeoGeoJson::Feature(feature) => process_feature(processor, idx, &feature),
+        GeoGeoJson::Geometry(geometry) => process_geometry(processor, idx, &geometry),
+        _ => Err(GeozeroError::Dataset(
+            "line-delimited GeoJson ('geojsonl') files must have one Feature or Geometry per line"
+                .to_string(),
+        )),
+    }
+}
+
+/// RFC 8142 record separator
+pub(crate) const RECORD_SEPARATOR: u8 = 0x1E;
+
+/// Parse GeoJSON records, either one per line or as RFC 8142 GeoJSON Text Sequence.
+fn read_records<R: BufRead>(
+    reader: R,
+    mut process: impl FnMut(usize, GeoGeoJson) -> Result<()>,
+) -> Result<()> {
+    read_record_texts(reader, |idx, text| {
+        process(idx, text.parse::<GeoGeoJson>()?)
+    })
+}
+
+/// Split input into GeoJSON record texts, either one per line or as RFC 8142 GeoJSON Text Sequence.
+fn read_record_texts<R: BufRead>(
+    mut reader: R,
+    mut process: impl FnMut(usize, String) -> Result<()>,
+) -> Result<()> {
+    let is_sequence = loop {
+        let buf = reader.fill_buf()?;
+        match buf.iter().position(|b| !b.is_ascii_whitespace()) {
+            Some(pos) => break buf[pos] == RECORD_SEPARATOR,
+            None if buf.is_empty() => break false,
+            None => {
+                let len = buf.len();
+                reader.consume(len);
+            }
+        }
+    };
+    if is_sequence {
+        let records = reader.split(RECORD_SEPARATOR).filter(
+            |record| !matches!(record, Ok(text) if text.iter().all(u8::is_ascii_whitespace)),
+        );
+        for (idx, record) in records.enumerate() {
+            let text = String::from_utf8(record?)
+                .map_err(|e| GeozeroError::Dataset(format!("invalid GeoJSON text: {e}")))?;
+            process(idx, text)?;
+        }
+    } else {
+        for (idx, line) in reader.lines().enumerate() {
+            process(idx, line?)?;
+        }
+    }
+    Ok(())
+}
+
 fn process_feature(
     processor: &mut impl FeatureProcessor,
     idx: usize,
     feature: &Feature,
 ) -> Result<()> {
     processor.feature_begin(idx as u64)?;
+    process_feature_id(feature, processor)?;
     if let Some(ref properties) = feature.properties {
         processor.properties_begin()?;
         process_properties(properties, processor)?;
@@ -163,6 +292,40 @@ mod tests {
         assert_eq!(json, expected);
     }
 
+    #[test]
+    fn text_sequence() {
+        let input = "\x1e{ \"type\": \"Feature\", \"geometry\": { \"type\": \"Point\", \"coordinates\": [1.1, 1.2] }, \"properties\": { \"name\": \"first\" } }\n\x1e{ \"type\": \"Point\", \"coordinates\": [2.1, 2.2] }\n";
+        let reader = GeoJsonLineReader(input.as_bytes());
+        assert_eq!(
+            reader.to_wkt().unwrap(),
+            "GEOMETRYCOLLECTION(POINT(1.1 1.2),POINT(2.1 2.2))"
+        );
+
+        // records may span multiple lines
+        let input = "\x1e{\n  \"type\": \"Point\",\n  \"coordinates\": [1.1, 1.2]\n}\n\x1e{ \"type\": \"Point\", \"coordinates\": [2.1, 2.2] }\n";
+        let reader = GeoJsonLineReader(input.as_bytes());
+        assert_eq!(
+            reader.to_wkt().unwrap(),
+            "GEOMETRYCOLLECTION(POINT(1.1 1.2),POINT(2.1 2.2))"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "with-rayon")]
+    fn parallel_features() {
+        let input: String = (0..10)
+            .map(|i| format!("{{ \"type\": \"Feature\", \"geometry\": {{ \"type\": \"Point\", \"coordinates\": [{i}, {i}] }}, \"properties\": {{ \"idx\": {i} }} }}\n"))
+            .collect();
+        let mut expected = GeoJsonLineReader::new(input.as_bytes());
+        let mut reader = ParallelGeoJsonLineReader::new(input.as_bytes());
+        reader.set_batch_size(3);
+        assert_eq!(reader.to_json().unwrap(), expected.to_json().unwrap());
+
+        let input = "{ \"type\": \"Point\", \"coordinates\": [1, 1] }\nooops\n";
+        let mut reader = ParallelGeoJsonLineReader::new(input.as_bytes());
+        _ = reader.to_json().unwrap_err();
+    }
+
     #[test]
     fn malformed_json() {
         let input = r#"{ "type": "Feature", "geometry": { "type": "Point", "coordinates": [1.1, 1.2] }, "properties": { "name": "first" } }
@@ -1,12 +1,16 @@
 use std::io::Write;
 
 use crate::error::Result;
+use crate::geojson::geojson_line_reader::RECORD_SEPARATOR;
 use crate::geojson::GeoJsonWriter;
 use crate::{FeatureProcessor, GeomProcessor, PropertyProcessor};
 
 /// Line Delimited GeoJSON Writer: One feature per line.
 ///
 /// See <https://jsonlines.org>
+///
+/// Use [`GeoJsonLineWriter::new_text_sequence`] for writing GeoJSON Text Sequences
+/// ([RFC 8142](https://datatracker.ietf.org/doc/html/rfc8142)).
 pub struct GeoJsonLineWriter<W: Write> {
     /// We use a count of the number of contexts entered to decide when to add a newline character
     /// and finish a line. The [newline-delimited GeoJSON
@@ -18,6 +22,8 @@ pub struct GeoJsonLineWriter<W: Write> {
     /// Note that this approach is not resilient to malformed input. If the number of begin and end
     /// calls do not match, newline characters will not be correctly added.
     open_contexts: usize,
+    /// Prefix each record with a record separator (RFC 8142)
+    text_sequence: bool,
     line_writer: GeoJsonWriter<W>,
 }
 
@@ -25,6 +31,16 @@ impl<W: Write> GeoJsonLineWriter<W> {
     pub fn new(out: W) -> Self {
         Self {
             open_contexts: 0,
+            text_sequence: false,
+            line_writer: GeoJsonWriter::new(out),
+        }
+    }
+
+    /// Writer for GeoJSON Text Sequences, prefixing each record with `0x1E`.
+    pub fn new_text_sequence(out: W) -> Self {
+        Self {
+            open_contexts: 0,
+            text_sequence: true,
             line_writer: GeoJsonWriter::new(out),
         }
     }
@@ -34,8 +50,12 @@ impl<W: Write> GeoJsonLineWriter<W> {
         Ok(())
     }
 
-    fn begin_context(&mut self) {
+    fn begin_context(&mut self) -> Result<()> {
+        if self.open_contexts == 0 && self.text_sequence {
+            self.line_writer.out.write_all(&[RECORD_SEPARATOR])?;
+        }
         self.open_contexts += 1;
+        Ok(())
     }
 
     fn end_context(&mut self) -> Result<()> {
@@ -54,13 +74,25 @@ impl<W: Write> GeoJsonLineWriter<W> {
 }
 
 impl<W: Write> FeatureProcessor for GeoJsonLineWriter<W> {
+    fn crs(&mut self, crs: &crate::Crs) -> Result<()> {
+        self.line_writer.crs(crs)
+    }
+
+    fn schema_begin(&mut self, columns: &[crate::ColumnDef]) -> Result<()> {
+        self.line_writer.schema_begin(columns)
+    }
+
     fn feature_begin(&mut self, _idx: u64) -> Result<()> {
-        self.begin_context();
+        self.begin_context()?;
         // We always pass `0` for `idx` because we want to avoid a preceding comma on this line.
         self.line_writer.feature_begin(0)?;
         Ok(())
     }
 
+    fn feature_id(&mut self, id: &crate::FeatureId) -> Result<()> {
+        self.line_writer.feature_id(id)
+    }
+
     fn feature_end(&mut self, idx: u64) -> Result<()> {
         self.line_writer.feature_end(idx)?;
         self.end_context()?;
@@ -120,7 +152,7 @@ impl<W: Write> GeomProcessor for GeoJsonLineWriter<W> {
             self.comma()?;
         }
 
-        self.begin_context();
+        self.begin_context()?;
         self.line_writer.empty_point(0)?;
         self.end_context()
     }
@@ -130,7 +162,7 @@ impl<W: Write> GeomProcessor for GeoJsonLineWriter<W> {
             self.comma()?;
         }
 
-        self.begin_context();
+        self.begin_context()?;
         self.line_writer.point_begin(0)
     }
 
@@ -144,7 +176,7 @@ impl<W: Write> GeomProcessor for GeoJsonLineWriter<W> {
             self.comma()?;
         }
 
-        self.begin_context();
+        self.begin_context()?;
         self.line_writer.multipoint_begin(size, 0)
     }
 
@@ -158,7 +190,7 @@ impl<W: Write> GeomProcessor for GeoJsonLineWriter<W> {
             self.comma()?;
         }
 
-        self.begin_context();
+        self.begin_context()?;
         self.line_writer.linestring_begin(tagged, size, 0)
     }
 
@@ -172,7 +204,7 @@ impl<W: Write> GeomProcessor for GeoJsonLineWriter<W> {
             self.comma()?;
         }
 
-        self.begin_context();
+        self.begin_context()?;
         self.line_writer.multilinestring_begin(size, 0)
     }
 
@@ -186,7 +218,7 @@ impl<W: Write> GeomProcessor for GeoJsonLineWriter<W> {
             self.comma()?;
         }
 
-        self.begin_context();
+        self.begin_context()?;
         self.line_writer.polygon_begin(tagged, size, 0)
     }
 
@@ -200,7 +232,7 @@ impl<W: Write> GeomProcessor for GeoJsonLineWriter<W> {
             self.comma()?;
         }
 
-        self.begin_context();
+        self.begin_context()?;
         self.line_writer.multipolygon_begin(size, 0)
     }
 
@@ -214,7 +246,7 @@ impl<W: Write> GeomProcessor for GeoJsonLineWriter<W> {
             self.comma()?;
         }
 
-        self.begin_context();
+        self.begin_context()?;
         self.line_writer.geometrycollection_begin(size, 0)
     }
 
@@ -228,6 +260,15 @@ impl<W: Write> PropertyProcessor for GeoJsonLineWriter<W> {
     fn property(&mut self, idx: usize, name: &str, value: &crate::ColumnValue) -> Result<bool> {
         self.line_writer.property(idx, name, value)
     }
+    fn column_property(
+        &mut self,
+        idx: usize,
+        column: usize,
+        name: &str,
+        value: &crate::ColumnValue,
+    ) -> Result<bool> {
+        self.line_writer.column_property(idx, column, name, value)
+    }
 }
 
 #[cfg(test)]
@@ -261,6 +302,32 @@ mod tests {
         assert_json_lines_eq(&out, input);
     }
 
+    #[test]
+    fn text_sequence() {
+        let input = r#"{ "type": "Feature", "geometry": { "type": "Point", "coordinates": [1.1, 1.2] }, "properties": { "name": "first" } }
+{ "type": "Point", "coordinates": [2.1, 2.2] }
+"#;
+        let mut out: Vec<u8> = Vec::new();
+        assert!(read_geojson_lines(
+            input.as_bytes(),
+            &mut GeoJsonLineWriter::new_text_sequence(&mut out)
+        )
+        .is_ok());
+        let output = std::str::from_utf8(&out).unwrap();
+        assert_eq!(output.matches('\x1e').count(), 2);
+        assert!(output
+            .lines()
+            .all(|line| line.starts_with('\x1e') && line.ends_with('}')));
+        assert_json_lines_eq(output.replace('\x1e', "").as_bytes(), input);
+
+        // read back
+        let mut roundtrip: Vec<u8> = Vec::new();
+        assert!(
+            read_geojson_lines(out.as_slice(), &mut GeoJsonLineWriter::new(&mut roundtrip)).is_ok()
+        );
+        assert_json_lines_eq(&roundtrip, input);
+    }
+
     fn assert_json_lines_eq(a: &[u8], b: &str) {
         let a = std::str::from_utf8(a).unwrap();
         a.lines().zip(b.lines()).for_each(|(a_line, b_line)| {
@@ -1,13 +1,14 @@
-use crate::error::{GeozeroError, Result};
+use crate::error::{ErrorPosition, GeozeroError, Result};
+use crate::spool::{replay_feature, FeatureEncoder};
 use crate::{
-    ColumnValue, FeatureProcessor, GeomProcessor, GeozeroDatasource, GeozeroGeometry,
+    ColumnValue, FeatureId, FeatureProcessor, GeomProcessor, GeozeroDatasource, GeozeroGeometry,
     PropertyProcessor,
 };
-use geojson::{Feature, FeatureReader};
+use geojson::{feature::Id, Feature, FeatureReader};
 use geojson::{GeoJson as GeoGeoJson, Geometry, Value};
 use serde_json::map::Map;
 use serde_json::value::Value as JsonValue;
-use std::io::Read;
+use std::io::{BufRead, BufReader, Read};
 
 /// GeoJSON String.
 #[derive(Debug)]
@@ -23,6 +24,13 @@ impl GeozeroDatasource for GeoJsonString {
     fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
         read_geojson(&mut self.0.as_bytes(), processor)
     }
+    fn process_with_recovery<P, F>(&mut self, processor: &mut P, on_error: F) -> Result<()>
+    where
+        P: FeatureProcessor,
+        F: FnMut(u64, GeozeroError) -> Result<()>,
+    {
+        read_geojson_with_recovery(self.0.as_bytes(), processor, on_error)
+    }
 }
 
 /// GeoJSON String slice.
@@ -38,6 +46,13 @@ impl GeozeroDatasource for GeoJson<'_> {
     fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
         read_geojson(&mut self.0.as_bytes(), processor)
     }
+    fn process_with_recovery<P, F>(&mut self, processor: &mut P, on_error: F) -> Result<()>
+    where
+        P: FeatureProcessor,
+        F: FnMut(u64, GeozeroError) -> Result<()>,
+    {
+        read_geojson_with_recovery(self.0.as_bytes(), processor, on_error)
+    }
 }
 
 /// GeoJSON Reader.
@@ -47,22 +62,167 @@ impl<R: Read> GeozeroDatasource for GeoJsonReader<R> {
     fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
         read_geojson(&mut self.0, processor)
     }
+    fn process_with_recovery<P, F>(&mut self, processor: &mut P, on_error: F) -> Result<()>
+    where
+        P: FeatureProcessor,
+        F: FnMut(u64, GeozeroError) -> Result<()>,
+    {
+        read_geojson_with_recovery(&mut self.0, processor, on_error)
+    }
 }
 
 /// Read and process GeoJSON.
-pub fn read_geojson<R: Read, P: FeatureProcessor>(mut reader: R, processor: &mut P) -> Result<()> {
+///
+/// FeatureCollections are processed feature by feature, without reading the whole input into memory.
+pub fn read_geojson<R: Read, P: FeatureProcessor>(reader: R, processor: &mut P) -> Result<()> {
+    read_geojson_opts(reader, processor, false, false, |_, error| Err(error))
+}
+
+/// Read and process GeoJSON, emitting foreign members of features as properties.
+///
+/// Foreign members like `bbox` or custom top-level keys of a feature are processed after its
+/// regular properties.
+pub fn read_geojson_with_foreign_members<R: Read, P: FeatureProcessor>(
+    reader: R,
+    processor: &mut P,
+) -> Result<()> {
+    read_geojson_opts(reader, processor, true, false, |_, error| Err(error))
+}
+
+/// Read and process GeoJSON, passing errors of single features to `on_error` and continuing
+/// with the next feature.
+///
+/// Features are buffered and only passed to `processor` if they are read without errors.
+/// JSON syntax errors and errors of `processor` can't be recovered and abort processing.
+pub fn read_geojson_with_recovery<R: Read, P: FeatureProcessor>(
+    reader: R,
+    processor: &mut P,
+    on_error: impl FnMut(u64, GeozeroError) -> Result<()>,
+) -> Result<()> {
+    read_geojson_opts(reader, processor, false, true, on_error)
+}
+
+fn read_geojson_opts<R: Read, P: FeatureProcessor>(
+    reader: R,
+    processor: &mut P,
+    foreign_members: bool,
+    recover: bool,
+    on_error: impl FnMut(u64, GeozeroError) -> Result<()>,
+) -> Result<()> {
+    let mut reader = BufReader::new(reader);
+    let mut head = Vec::new();
+    if seek_to_features(&mut reader, &mut head)? {
+        // Continue with the features array
+        read_features(reader, processor, foreign_members, recover, on_error)
+    } else {
+        reader.read_to_end(&mut head)?;
+        let geojson_str =
+            std::str::from_utf8(&head).map_err(|e| GeozeroError::Geometry(e.to_string()))?;
+        let geojson = geojson_str.parse::<GeoGeoJson>()?;
+        process_geojson(&geojson, processor, foreign_members, recover, on_error)
+    }
+}
+
+/// Scan a GeoJSON document up to the value of the top-level `"features"` member.
+///
+/// Returns `false` if the document has no `"features"` member or a `"type"` other than
+/// `FeatureCollection`. All bytes read are appended to `head`.
+fn seek_to_features<R: BufRead>(reader: &mut R, head: &mut Vec<u8>) -> Result<bool> {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escape = false;
+    let mut string = Vec::new();
+    // Last completed string at top level, which is a key when followed by `:`
+    let mut last_string: Option<Vec<u8>> = None;
+    // Current top-level key, when reading its value
+    let mut key: Option<Vec<u8>> = None;
+    for byte in reader.bytes() {
+        let byte = byte?;
+        head.push(byte);
+        if in_string {
+            if escape {
+                escape = false;
+            } else if byte == b'\\' {
+                escape = true;
+            } else if byte == b'"' {
+                in_string = false;
+                if depth == 1 {
+                    match key.take() {
+                        Some(key) if key == b"type" && string != b"FeatureCollection" => {
+                            return Ok(false);
+                        }
+                        Some(_) => {}
+                        None => last_string = Some(std::mem::take(&mut string)),
+                    }
+                }
+                string.clear();
+                continue;
+            }
+            string.push(byte);
+            continue;
+        }
+        match byte {
+            b'"' => in_string = true,
+            b'{' | b'[' => depth += 1,
+            b'}' | b']' => depth = depth.saturating_sub(1),
+            b':' if depth == 1 => {
+                key = last_string.take();
+                if key.as_deref() == Some(b"features") {
+                    return Ok(true);
+                }
+            }
+            b',' if depth == 1 => key = None,
+            _ => {}
+        }
+    }
+    Ok(false)
+}
+
+/// Read and process GeoJSON in strict mode.
+///
+/// JSON syntax errors are reported with line and column.
+pub fn read_geojson_strict<R: Read, P: FeatureProcessor>(
+    mut reader: R,
+    processor: &mut P,
+) -> Result<()> {
     let mut geojson_str = String::new();
     reader.read_to_string(&mut geojson_str)?;
-    let geojson = geojson_str.parse::<GeoGeoJson>()?;
-    process_geojson(&geojson, processor)
+    let json: JsonValue = serde_json::from_str(&geojson_str).map_err(|e| {
+        let position = ErrorPosition::LineColumn {
+            line: e.line(),
+            column: e.column(),
+        };
+        GeozeroError::Geometry(e.to_string()).at(position)
+    })?;
+    let geojson = GeoGeoJson::from_json_value(json)?;
+    process_geojson(&geojson, processor, false, false, |_, error| Err(error))
 }
 
+/// Read and process GeoJSON FeatureCollection feature by feature.
 pub fn read_geojson_fc<R: Read, P: FeatureProcessor>(reader: R, processor: &mut P) -> Result<()> {
+    read_features(reader, processor, false, false, |_, error| Err(error))
+}
+
+fn read_features<R: Read, P: FeatureProcessor>(
+    reader: R,
+    processor: &mut P,
+    foreign_members: bool,
+    recover: bool,
+    mut on_error: impl FnMut(u64, GeozeroError) -> Result<()>,
+) -> Result<()> {
+    processor.dataset_begin(None)?;
+    let mut buffer = recover.then(|| FeatureEncoder::new(processor.dimensions()));
     for (idx, feature) in FeatureReader::from_reader(reader).features().enumerate() {
-        process_geojson_feature(&feature?, idx, processor)?;
+        process_feature_recovering(
+            &feature?,
+            idx,
+            processor,
+            foreign_members,
+            buffer.as_mut(),
+            &mut on_error,
+        )?;
     }
-
-    Ok(())
+    processor.dataset_end()
 }
 
 /// Read and process GeoJSON geometry.
@@ -77,27 +237,32 @@ pub fn read_geojson_geom<R: Read, P: GeomProcessor>(
 }
 
 /// Process top-level GeoJSON items
-fn process_geojson<P: FeatureProcessor>(gj: &GeoGeoJson, processor: &mut P) -> Result<()> {
+fn process_geojson<P: FeatureProcessor>(
+    gj: &GeoGeoJson,
+    processor: &mut P,
+    foreign_members: bool,
+    recover: bool,
+    mut on_error: impl FnMut(u64, GeozeroError) -> Result<()>,
+) -> Result<()> {
     match *gj {
         GeoGeoJson::FeatureCollection(ref collection) => {
             processor.dataset_begin(None)?;
+            let mut buffer = recover.then(|| FeatureEncoder::new(processor.dimensions()));
             for (idx, feature) in collection.features.iter().enumerate() {
-                processor.feature_begin(idx as u64)?;
-                if let Some(ref properties) = feature.properties {
-                    processor.properties_begin()?;
-                    process_properties(properties, processor)?;
-                    processor.properties_end()?;
-                }
-                if let Some(ref geometry) = feature.geometry {
-                    processor.geometry_begin()?;
-                    process_geojson_geom_n(geometry, idx, processor)?;
-                    processor.geometry_end()?;
-                }
-                processor.feature_end(idx as u64)?;
+                process_feature_recovering(
+                    feature,
+                    idx,
+                    processor,
+                    foreign_members,
+                    buffer.as_mut(),
+                    &mut on_error,
+                )?;
             }
             processor.dataset_end()
         }
-        GeoGeoJson::Feature(ref feature) => process_geojson_feature(feature, 0, processor),
+        GeoGeoJson::Feature(ref feature) => {
+            process_geojson_feature(feature, 0, processor, foreign_members)
+        }
         GeoGeoJson::Geometry(ref geometry) => process_geojson_geom_n(geometry, 0, processor),
     }
 }
@@ -107,23 +272,71 @@ fn process_geojson_feature<P: FeatureProcessor>(
     feature: &Feature,
     idx: usize,
     processor: &mut P,
+    foreign_members: bool,
 ) -> Result<()> {
     processor.dataset_begin(None)?;
     if feature.geometry.is_some() || feature.properties.is_some() {
-        processor.feature_begin(idx as u64)?;
+        process_feature(feature, idx, processor, foreign_members)?;
+    }
+    processor.dataset_end()
+}
+
+/// Process GeoJSON feature, passing its errors to `on_error`.
+///
+/// With a `buffer`, the feature is only replayed into `processor` if it is read without
+/// errors, so that failed features don't leave partial events.
+fn process_feature_recovering<P: FeatureProcessor>(
+    feature: &Feature,
+    idx: usize,
+    processor: &mut P,
+    foreign_members: bool,
+    buffer: Option<&mut FeatureEncoder>,
+    on_error: &mut impl FnMut(u64, GeozeroError) -> Result<()>,
+) -> Result<()> {
+    let result = match buffer {
+        Some(buffer) => match process_feature(feature, idx, buffer, foreign_members) {
+            // Errors of the processor abort processing
+            Ok(()) => return replay_feature(buffer.data(), idx as u64, processor),
+            Err(error) => Err(error),
+        },
+        None => process_feature(feature, idx, processor, foreign_members),
+    };
+    match result {
+        Err(GeozeroError::Cancelled) => Err(GeozeroError::Cancelled),
+        Err(error) => on_error(idx as u64, error),
+        Ok(()) => Ok(()),
+    }
+}
+
+/// Process GeoJSON feature
+fn process_feature<P: FeatureProcessor>(
+    feature: &Feature,
+    idx: usize,
+    processor: &mut P,
+    foreign_members: bool,
+) -> Result<()> {
+    processor.feature_begin(idx as u64)?;
+    process_feature_id(feature, processor)?;
+    let has_foreign_members =
+        foreign_members && (feature.bbox.is_some() || feature.foreign_members.is_some());
+    if feature.properties.is_some() || has_foreign_members {
+        processor.properties_begin()?;
+        let mut count = 0;
         if let Some(ref properties) = feature.properties {
-            processor.properties_begin()?;
             process_properties(properties, processor)?;
-            processor.properties_end()?;
+            count = properties.len();
         }
-        if let Some(ref geometry) = feature.geometry {
-            processor.geometry_begin()?;
-            process_geojson_geom_n(geometry, idx, processor)?;
-            processor.geometry_end()?;
+        if has_foreign_members {
+            process_foreign_members(feature, count, processor)?;
         }
-        processor.feature_end(idx as u64)?;
+        processor.properties_end()?;
     }
-    processor.dataset_end()
+    if let Some(ref geometry) = feature.geometry {
+        processor.geometry_begin()?;
+        process_geojson_geom_n(geometry, idx, processor)?;
+        processor.geometry_end()?;
+    }
+    processor.feature_end(idx as u64)
 }
 
 /// Process top-level GeoJSON items (geometry only)
@@ -158,33 +371,36 @@ pub(crate) fn process_geojson_geom_n<P: GeomProcessor>(
     idx: usize,
     processor: &mut P,
 ) -> Result<()> {
+    let mut times = CoordTimes::new(geom);
     match geom.value {
         Value::Point(ref geometry) => {
             processor.point_begin(idx)?;
-            process_coord(geometry, processor.multi_dim(), 0, processor)?;
+            process_coord(geometry, processor.multi_dim(), &mut times, 0, processor)?;
             processor.point_end(idx)
         }
         Value::MultiPoint(ref geometry) => {
             processor.multipoint_begin(geometry.len(), idx)?;
             let multi_dim = processor.multi_dim();
             for (idxc, point_type) in geometry.iter().enumerate() {
-                process_coord(point_type, multi_dim, idxc, processor)?;
+                process_coord(point_type, multi_dim, &mut times, idxc, processor)?;
             }
             processor.multipoint_end(idx)
         }
-        Value::LineString(ref geometry) => process_linestring(geometry, true, idx, processor),
+        Value::LineString(ref geometry) => {
+            process_linestring(geometry, true, &mut times, idx, processor)
+        }
         Value::MultiLineString(ref geometry) => {
             processor.multilinestring_begin(geometry.len(), idx)?;
             for (idx2, linestring_type) in geometry.iter().enumerate() {
-                process_linestring(linestring_type, false, idx2, processor)?;
+                process_linestring(linestring_type, false, &mut times, idx2, processor)?;
             }
             processor.multilinestring_end(idx)
         }
-        Value::Polygon(ref geometry) => process_polygon(geometry, true, idx, processor),
+        Value::Polygon(ref geometry) => process_polygon(geometry, true, &mut times, idx, processor),
         Value::MultiPolygon(ref geometry) => {
             processor.multipolygon_begin(geometry.len(), idx)?;
             for (idx2, polygon_type) in geometry.iter().enumerate() {
-                process_polygon(polygon_type, false, idx2, processor)?;
+                process_polygon(polygon_type, false, &mut times, idx2, processor)?;
             }
             processor.multipolygon_end(idx)
         }
@@ -198,6 +414,60 @@ pub(crate) fn process_geojson_geom_n<P: GeomProcessor>(
     }
 }
 
+/// T and TM values of geometry coordinates, stored in `t` and `tm` members of a geometry object
+struct CoordTimes<'a> {
+    t: Option<&'a Vec<JsonValue>>,
+    tm: Option<&'a Vec<JsonValue>>,
+    /// Index of next coordinate
+    next: usize,
+}
+
+impl<'a> CoordTimes<'a> {
+    fn new(geom: &'a Geometry) -> Self {
+        let member = |name: &str| match geom.foreign_members.as_ref()?.get(name) {
+            Some(JsonValue::Array(values)) => Some(values),
+            _ => None,
+        };
+        CoordTimes {
+            t: member("t"),
+            tm: member("tm"),
+            next: 0,
+        }
+    }
+
+    /// T and TM values of the next coordinate
+    fn next(&mut self) -> (Option<f64>, Option<u64>) {
+        let idx = self.next;
+        self.next += 1;
+        (
+            self.t.and_then(|t| t.get(idx)).and_then(JsonValue::as_f64),
+            self.tm
+                .and_then(|tm| tm.get(idx))
+                .and_then(JsonValue::as_u64),
+        )
+    }
+}
+
+/// Process GeoJSON feature id
+pub(crate) fn process_feature_id<P: FeatureProcessor>(
+    feature: &Feature,
+    processor: &mut P,
+) -> Result<()> {
+    match feature.id {
+        Some(Id::String(ref id)) => processor.feature_id(&FeatureId::String(id)),
+        Some(Id::Number(ref id)) => {
+            if let Some(id) = id.as_u64() {
+                processor.feature_id(&FeatureId::ULong(id))
+            } else if let Some(id) = id.as_i64() {
+                processor.feature_id(&FeatureId::Long(id))
+            } else {
+                processor.feature_id(&FeatureId::String(&id.to_string()))
+            }
+        }
+        None => Ok(()),
+    }
+}
+
 /// Process GeoJSON properties
 pub(crate) fn process_properties<P: PropertyProcessor>(
     properties: &Map<String, JsonValue>,
@@ -205,37 +475,67 @@ pub(crate) fn process_properties<P: PropertyProcessor>(
 ) -> Result<()> {
     for (i, (key, value)) in properties.iter().enumerate() {
         // Could we provide a stable property index?
-        match value {
-            JsonValue::String(v) => processor.property(i, key, &ColumnValue::String(v))?,
-            JsonValue::Number(v) => {
-                if v.is_f64() {
-                    processor.property(i, key, &ColumnValue::Double(v.as_f64().unwrap()))?
-                } else if v.is_i64() {
-                    processor.property(i, key, &ColumnValue::Long(v.as_i64().unwrap()))?
-                } else if v.is_u64() {
-                    processor.property(i, key, &ColumnValue::ULong(v.as_u64().unwrap()))?
-                } else {
-                    unreachable!()
-                }
-            }
-            JsonValue::Bool(v) => processor.property(i, key, &ColumnValue::Bool(*v))?,
-            JsonValue::Array(v) => {
-                let json_string =
-                    serde_json::to_string(v).map_err(|_err| GeozeroError::Property(key.clone()))?;
-                processor.property(i, key, &ColumnValue::Json(&json_string))?
-            }
-            JsonValue::Object(v) => {
-                let json_string =
-                    serde_json::to_string(v).map_err(|_err| GeozeroError::Property(key.clone()))?;
-                processor.property(i, key, &ColumnValue::Json(&json_string))?
-            }
-            // For null values omit the property
-            JsonValue::Null => false,
-        };
+        process_property(i, key, value, processor)?;
+    }
+    Ok(())
+}
+
+/// Process `bbox` and foreign members of a feature as properties, starting at index `start`
+fn process_foreign_members<P: PropertyProcessor>(
+    feature: &Feature,
+    start: usize,
+    processor: &mut P,
+) -> Result<()> {
+    let mut i = start;
+    if let Some(ref bbox) = feature.bbox {
+        process_property(i, "bbox", &JsonValue::from(bbox.clone()), processor)?;
+        i += 1;
+    }
+    if let Some(ref members) = feature.foreign_members {
+        for (key, value) in members {
+            process_property(i, key, value, processor)?;
+            i += 1;
+        }
     }
     Ok(())
 }
 
+fn process_property<P: PropertyProcessor>(
+    i: usize,
+    key: &str,
+    value: &JsonValue,
+    processor: &mut P,
+) -> Result<bool> {
+    let abort = match value {
+        JsonValue::String(v) => processor.property(i, key, &ColumnValue::String(v))?,
+        JsonValue::Number(v) => processor.property(i, key, &number_value(v))?,
+        JsonValue::Bool(v) => processor.property(i, key, &ColumnValue::Bool(*v))?,
+        JsonValue::Array(v) => {
+            let json_string =
+                serde_json::to_string(v).map_err(|_err| GeozeroError::Property(key.to_string()))?;
+            processor.property(i, key, &ColumnValue::Json(&json_string))?
+        }
+        JsonValue::Object(v) => {
+            let json_string =
+                serde_json::to_string(v).map_err(|_err| GeozeroError::Property(key.to_string()))?;
+            processor.property(i, key, &ColumnValue::Json(&json_string))?
+        }
+        // For null values omit the property
+        JsonValue::Null => false,
+    };
+    Ok(abort)
+}
+
+fn number_value(v: &serde_json::Number) -> ColumnValue<'static> {
+    if let Some(v) = v.as_i64() {
+        ColumnValue::Long(v)
+    } else if let Some(v) = v.as_u64() {
+        ColumnValue::ULong(v)
+    } else {
+        ColumnValue::Double(v.as_f64().unwrap_or(f64::NAN))
+    }
+}
+
 type Position = Vec<f64>;
 type PointType = Position;
 type LineStringType = Vec<Position>;
@@ -244,34 +544,32 @@ type PolygonType = Vec<Vec<Position>>;
 fn process_coord<P: GeomProcessor>(
     point_type: &PointType,
     multi_dim: bool,
+    times: &mut CoordTimes,
     idx: usize,
     processor: &mut P,
 ) -> Result<()> {
+    let (t, tm) = times.next();
+    let [x, y, ..] = point_type[..] else {
+        return Err(GeozeroError::Coord);
+    };
     if multi_dim {
-        processor.coordinate(
-            point_type[0],
-            point_type[1],
-            point_type.get(2).copied(),
-            None,
-            None,
-            None,
-            idx,
-        )
+        processor.coordinate(x, y, point_type.get(2).copied(), None, t, tm, idx)
     } else {
-        processor.xy(point_type[0], point_type[1], idx)
+        processor.xy(x, y, idx)
     }
 }
 
 fn process_linestring<P: GeomProcessor>(
     linestring_type: &LineStringType,
     tagged: bool,
+    times: &mut CoordTimes,
     idx: usize,
     processor: &mut P,
 ) -> Result<()> {
     processor.linestring_begin(tagged, linestring_type.len(), idx)?;
     let multi_dim = processor.multi_dim();
     for (idxc, point_type) in linestring_type.iter().enumerate() {
-        process_coord(point_type, multi_dim, idxc, processor)?;
+        process_coord(point_type, multi_dim, times, idxc, processor)?;
     }
     processor.linestring_end(tagged, idx)
 }
@@ -279,12 +577,13 @@ fn process_linestring<P: GeomProcessor>(
 fn process_polygon<P: GeomProcessor>(
     polygon_type: &PolygonType,
     tagged: bool,
+    times: &mut CoordTimes,
     idx: usize,
     processor: &mut P,
 ) -> Result<()> {
     processor.polygon_begin(tagged, polygon_type.len(), idx)?;
     for (idx2, linestring_type) in polygon_type.iter().enumerate() {
-        process_linestring(linestring_type, false, idx2, processor)?;
+        process_linestring(linestring_type, false, times, idx2, processor)?;
     }
     processor.polygon_end(tagged, idx)
 }
@@ -297,6 +596,65 @@ mod test {
     use crate::{CoordDimensions, ProcessToSvg, ToJson, ToWkt};
     use std::fs::File;
 
+    #[test]
+    fn strict_mode() {
+        let geojson = "{\n  \"type\": \"Point\",\n  \"coordinates\": [1, 2,]\n}";
+        let err = read_geojson_strict(geojson.as_bytes(), &mut crate::ProcessorSink).unwrap_err();
+        assert!(matches!(
+            err.position(),
+            Some(ErrorPosition::LineColumn { line: 3, .. })
+        ));
+        let geojson = r#"{"type": "Point", "coordinates": [1, 2]}"#;
+        assert!(read_geojson_strict(geojson.as_bytes(), &mut crate::ProcessorSink).is_ok());
+    }
+
+    #[test]
+    fn feature_recovery() -> Result<()> {
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [1, 2]}},
+            {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [1]}},
+            {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [3, 4]}}
+        ]}"#;
+        assert!(matches!(
+            read_geojson(geojson.as_bytes(), &mut crate::ProcessorSink),
+            Err(GeozeroError::Coord)
+        ));
+        let mut errors = Vec::new();
+        GeoJson(geojson).process_with_recovery(&mut crate::ProcessorSink, |idx, error| {
+            errors.push((idx, error));
+            Ok(())
+        })?;
+        assert!(matches!(errors[..], [(1, GeozeroError::Coord)]));
+        Ok(())
+    }
+
+    #[test]
+    fn feature_recovery_output() -> Result<()> {
+        // The second feature fails after emitting its properties and first point
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"name": "a"}, "geometry": {"type": "Point", "coordinates": [1, 2]}},
+            {"type": "Feature", "properties": {"name": "b"}, "geometry": {"type": "MultiPoint", "coordinates": [[1, 2], [3]]}},
+            {"type": "Feature", "properties": {"name": "c"}, "geometry": {"type": "Point", "coordinates": [3, 4]}}
+        ]}"#;
+        let mut out: Vec<u8> = Vec::new();
+        let mut errors = 0;
+        GeoJson(geojson).process_with_recovery(&mut GeoJsonWriter::new(&mut out), |_, _| {
+            errors += 1;
+            Ok(())
+        })?;
+        assert_eq!(errors, 1);
+        let json: JsonValue = serde_json::from_slice(&out).unwrap();
+        let features = json["features"].as_array().unwrap();
+        assert_eq!(features.len(), 2);
+        assert_eq!(features[0]["properties"]["name"], "a");
+        assert_eq!(features[1]["properties"]["name"], "c");
+        assert_eq!(
+            features[1]["geometry"]["coordinates"],
+            serde_json::json!([3, 4])
+        );
+        Ok(())
+    }
+
     #[test]
     fn line_string() -> Result<()> {
         let geojson = r#"{
@@ -425,6 +783,63 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn streaming_detection() -> Result<()> {
+        let geojson = r#"{"bbox": [0, 0, 1, 1], "crs": {"type": "name"}, "name": "with \"features\"", "features": [
+            {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [1, 2]}}
+        ], "type": "FeatureCollection"}"#;
+        let mut head = Vec::new();
+        assert!(seek_to_features(&mut geojson.as_bytes(), &mut head)?);
+        let mut wkt_data: Vec<u8> = Vec::new();
+        read_geojson(geojson.as_bytes(), &mut WktWriter::new(&mut wkt_data))?;
+        assert_eq!(std::str::from_utf8(&wkt_data).unwrap(), "POINT(1 2)");
+
+        let geojson = r#"{"coordinates": [1, 2], "type": "Point"}"#;
+        let mut head = Vec::new();
+        assert!(!seek_to_features(&mut geojson.as_bytes(), &mut head)?);
+        let mut wkt_data: Vec<u8> = Vec::new();
+        read_geojson(geojson.as_bytes(), &mut WktWriter::new(&mut wkt_data))?;
+        assert_eq!(std::str::from_utf8(&wkt_data).unwrap(), "POINT(1 2)");
+        Ok(())
+    }
+
+    #[test]
+    fn feature_id() -> Result<()> {
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "id": "NZL", "properties": {"name": "New Zealand"}, "geometry": null},
+            {"type": "Feature", "id": 42, "geometry": {"type": "Point", "coordinates": [1, 2]}}
+        ]}"#;
+        let mut out: Vec<u8> = Vec::new();
+        read_geojson(geojson.as_bytes(), &mut GeoJsonWriter::new(&mut out))?;
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            r#"{
+"type": "FeatureCollection",
+"features": [{"type": "Feature", "id": "NZL", "properties": {"name": "New Zealand"}},
+{"type": "Feature", "id": 42, "geometry": {"type": "Point", "coordinates": [1,2]}}]}"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn foreign_members() -> Result<()> {
+        let geojson = r#"{"type": "Feature", "bbox": [1, 2, 1, 2], "source": "osm",
+            "properties": {"name": "Bern"}, "geometry": {"type": "Point", "coordinates": [1, 2]}}"#;
+        let mut out: Vec<u8> = Vec::new();
+        read_geojson(geojson.as_bytes(), &mut GeoJsonWriter::new(&mut out))?;
+        assert!(!std::str::from_utf8(&out).unwrap().contains("source"));
+
+        let mut out: Vec<u8> = Vec::new();
+        read_geojson_with_foreign_members(geojson.as_bytes(), &mut GeoJsonWriter::new(&mut out))?;
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            r#"{
+"type": "FeatureCollection",
+"features": [{"type": "Feature", "properties": {"name": "Bern", "bbox": [1.0,2.0,1.0,2.0], "source": "osm"}, "geometry": {"type": "Point", "coordinates": [1,2]}}]}"#
+        );
+        Ok(())
+    }
+
     #[test]
     fn conversions() -> Result<()> {
         let geojson = GeoJson(r#"{"type": "Point", "coordinates": [10,20]}"#);
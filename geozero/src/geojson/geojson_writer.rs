@@ -1,23 +1,285 @@
-use crate::error::Result;
-use crate::{ColumnValue, CoordDimensions, FeatureProcessor, GeomProcessor, PropertyProcessor};
+use crate::bounds::Bounds;
+use crate::error::{GeozeroError, Result};
+use crate::float_format::FloatBuffer;
+use crate::property_processor::{array_json, format_uuid, is_json_number};
+use crate::{
+    ColumnDef, ColumnValue, CoordDimensions, Crs, FeatureId, FeatureProcessor, FloatFormatter,
+    GeomProcessor, PropertyProcessor,
+};
 use std::fmt::Display;
 use std::io::Write;
 
+/// Top-level output structure of [`GeoJsonWriter`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GeoJsonOutput {
+    /// Features wrapped in a `FeatureCollection` (default)
+    #[default]
+    FeatureCollection,
+    /// Geometries of features without feature objects, one per line
+    Geometry,
+    /// Features without wrapper, one per line
+    Sequence,
+}
+
+/// Writing of integers beyond the safe integer range of JavaScript (±2^53) by [`GeoJsonWriter`].
+///
+/// Applies to `Long` and `ULong` property values and feature ids.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LargeIntegers {
+    /// JSON numbers, losing precision in consumers parsing numbers as doubles (default)
+    #[default]
+    Number,
+    /// JSON strings
+    String,
+    /// Fail with an error
+    Error,
+}
+
+/// Largest integer exactly representable as double
+const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
 /// GeoJSON writer.
 pub struct GeoJsonWriter<W: Write> {
     dims: CoordDimensions,
+    /// Top-level output structure
+    output: GeoJsonOutput,
+    /// Additional members of the feature collection
+    foreign_members: Vec<(String, serde_json::Value)>,
+    /// Configured SRID
+    srid: Option<i32>,
+    /// SRID of current feature geometry
+    feature_srid: Option<i32>,
+    /// Replace configured SRID with SRID of processed geometry
+    srid_override: bool,
+    /// Write `crs` member in features
+    write_crs: bool,
+    /// Write `bbox` members of features and feature collection
+    write_bbox: bool,
+    /// Bounds of current feature
+    feature_bounds: Option<Bounds>,
+    /// Bounds of all features
+    dataset_bounds: Option<Bounds>,
+    /// Bounds with longitudes in [0, 360) for detecting antimeridian crossings
+    feature_bounds_360: Option<Bounds>,
+    dataset_bounds_360: Option<Bounds>,
+    /// RFC 7946 compliance mode
+    rfc7946: bool,
+    /// Number of decimal places of coordinates
+    precision: Option<u32>,
+    /// Inside of a polygon
+    in_polygon: bool,
+    /// Buffered coordinates of current polygon ring, when rewinding rings
+    ring: Option<Vec<(f64, f64, Option<f64>, Option<f64>, Option<u64>)>>,
+    /// Index of current polygon ring
+    ring_idx: usize,
+    /// T values of current geometry
+    coord_t: Vec<Option<f64>>,
+    /// TM values of current geometry
+    coord_tm: Vec<Option<u64>>,
+    /// Encoded property keys of schema columns
+    column_keys: Vec<Vec<u8>>,
+    /// Buffer for formatting coordinates
+    float_buf: FloatBuffer,
+    large_integers: LargeIntegers,
     pub(crate) out: W,
 }
 
 impl<W: Write> GeoJsonWriter<W> {
     pub fn new(out: W) -> Self {
+        Self::with_dims(out, CoordDimensions::default())
+    }
+    pub fn with_dims(out: W, dims: CoordDimensions) -> Self {
         GeoJsonWriter {
-            dims: CoordDimensions::default(),
+            dims,
+            output: GeoJsonOutput::default(),
+            foreign_members: Vec::new(),
+            srid: None,
+            feature_srid: None,
+            srid_override: false,
+            write_crs: false,
+            write_bbox: false,
+            feature_bounds: None,
+            dataset_bounds: None,
+            feature_bounds_360: None,
+            dataset_bounds_360: None,
+            rfc7946: false,
+            precision: None,
+            in_polygon: false,
+            ring: None,
+            ring_idx: 0,
+            coord_t: Vec::new(),
+            coord_tm: Vec::new(),
+            column_keys: Vec::new(),
+            float_buf: FloatBuffer::default(),
+            large_integers: LargeIntegers::default(),
             out,
         }
     }
-    pub fn with_dims(out: W, dims: CoordDimensions) -> Self {
-        GeoJsonWriter { dims, out }
+    /// Top-level output structure (default: [`GeoJsonOutput::FeatureCollection`])
+    pub fn set_output(&mut self, output: GeoJsonOutput) {
+        self.output = output;
+    }
+    /// Add a foreign member like `"crs"` or custom metadata to the feature collection
+    pub fn add_foreign_member(&mut self, name: &str, value: serde_json::Value) {
+        self.foreign_members.push((name.to_string(), value));
+    }
+    /// SRID written as `crs` member, if enabled with [set_write_crs](Self::set_write_crs)
+    ///
+    /// Defaults to the EPSG code of the dataset CRS.
+    pub fn set_srid(&mut self, srid: Option<i32>) {
+        self.srid = srid;
+    }
+    /// Let SRID of processed geometry override the configured SRID (default: false)
+    ///
+    /// Without override, the SRID of processed geometries is only used if no SRID is configured.
+    pub fn set_srid_override(&mut self, enabled: bool) {
+        self.srid_override = enabled;
+    }
+    /// Write SRID as (deprecated) `crs` member of features (default: false)
+    pub fn set_write_crs(&mut self, enabled: bool) {
+        self.write_crs = enabled;
+    }
+    /// Write `bbox` member of features and the feature collection (default: false)
+    ///
+    /// The bounds are collected while streaming, so the `bbox` members are written
+    /// after the other members of an object.
+    pub fn set_write_bbox(&mut self, enabled: bool) {
+        self.write_bbox = enabled;
+    }
+    /// RFC 7946 compliance mode (default: false)
+    ///
+    /// Omits `crs` members, rewinds polygon rings to the right-hand rule (exterior rings
+    /// counterclockwise, holes clockwise), writes `bbox` members crossing the antimeridian
+    /// with west > east and limits coordinates to 7 decimal places, unless a precision is set
+    /// with [set_coordinate_precision](Self::set_coordinate_precision).
+    pub fn set_rfc7946(&mut self, enabled: bool) {
+        self.rfc7946 = enabled;
+    }
+    /// Round coordinates to the given number of decimal places (default: no rounding)
+    pub fn set_coordinate_precision(&mut self, decimals: Option<u32>) {
+        self.precision = decimals;
+    }
+    /// Writing of integers exceeding ±2^53 (default: [`LargeIntegers::Number`])
+    pub fn set_large_integers(&mut self, large_integers: LargeIntegers) {
+        self.large_integers = large_integers;
+    }
+    fn round(&self, v: f64) -> f64 {
+        let precision = match (self.precision, self.rfc7946) {
+            (None, true) => Some(7),
+            (precision, _) => precision,
+        };
+        FloatFormatter::with_precision(precision).round(v)
+    }
+    fn extend_bounds(&mut self, x: f64, y: f64) {
+        if self.write_bbox {
+            match self.feature_bounds.as_mut() {
+                Some(bounds) => bounds.extend(x, y),
+                None => self.feature_bounds = Some(Bounds::from_point(x, y)),
+            }
+            let x = if x < 0.0 { x + 360.0 } else { x };
+            match self.feature_bounds_360.as_mut() {
+                Some(bounds) => bounds.extend(x, y),
+                None => self.feature_bounds_360 = Some(Bounds::from_point(x, y)),
+            }
+        }
+    }
+    fn write_bounds(&mut self, bounds: Option<Bounds>, bounds_360: Option<Bounds>) -> Result<()> {
+        if let Some(mut b) = bounds {
+            if let Some(b360) = bounds_360.filter(|b360| self.rfc7946 && b360.width() < b.width()) {
+                // Crossing the antimeridian, west > east
+                let normalize = |x: f64| if x > 180.0 { x - 360.0 } else { x };
+                b.min_x = normalize(b360.min_x);
+                b.max_x = normalize(b360.max_x);
+            }
+            let [min_x, min_y, max_x, max_y] =
+                [b.min_x, b.min_y, b.max_x, b.max_y].map(|v| self.round(v));
+            write!(self.out, r#", "bbox": [{min_x},{min_y},{max_x},{max_y}]"#)?;
+        }
+        Ok(())
+    }
+    fn write_coord(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        if let Some(ring) = self.ring.as_mut() {
+            ring.push((x, y, z, t, tm));
+            return Ok(());
+        }
+        self.comma(idx)?;
+        self.extend_bounds(x, y);
+        self.push_times(t, tm);
+        let (x, y) = (self.round(x), self.round(y));
+        self.out.write_all(b"[")?;
+        self.float_buf.write(&mut self.out, x)?;
+        self.out.write_all(b",")?;
+        self.float_buf.write(&mut self.out, y)?;
+        if let Some(z) = z {
+            let z = self.round(z);
+            self.out.write_all(b",")?;
+            self.float_buf.write(&mut self.out, z)?;
+        }
+        self.out.write_all(b"]")?;
+        Ok(())
+    }
+    /// Write buffered ring, reversed if not following the right-hand rule
+    fn write_ring(
+        &mut self,
+        mut ring: Vec<(f64, f64, Option<f64>, Option<f64>, Option<u64>)>,
+    ) -> Result<()> {
+        let area: f64 = ring
+            .windows(2)
+            .map(|w| w[0].0 * w[1].1 - w[1].0 * w[0].1)
+            .sum();
+        let exterior = self.ring_idx == 0;
+        if (exterior && area < 0.0) || (!exterior && area > 0.0) {
+            ring.reverse();
+        }
+        for (i, (x, y, z, t, tm)) in ring.into_iter().enumerate() {
+            self.write_coord(x, y, z, t, tm, i)?;
+        }
+        Ok(())
+    }
+    fn push_times(&mut self, t: Option<f64>, tm: Option<u64>) {
+        if self.dims.t {
+            self.coord_t.push(t);
+        }
+        if self.dims.tm {
+            self.coord_tm.push(tm);
+        }
+    }
+    /// Write requested T and TM values as `t` and `tm` members of the geometry object
+    fn write_times(&mut self) -> Result<()> {
+        if self.coord_t.iter().any(Option::is_some) {
+            let values: Vec<String> = self
+                .coord_t
+                .iter()
+                .map(|t| t.map_or("null".to_string(), |t| t.to_string()))
+                .collect();
+            write!(self.out, r#", "t": [{}]"#, values.join(","))?;
+        }
+        if self.coord_tm.iter().any(Option::is_some) {
+            let values: Vec<String> = self
+                .coord_tm
+                .iter()
+                .map(|tm| tm.map_or("null".to_string(), |tm| tm.to_string()))
+                .collect();
+            write!(self.out, r#", "tm": [{}]"#, values.join(","))?;
+        }
+        self.coord_t.clear();
+        self.coord_tm.clear();
+        Ok(())
+    }
+    fn feature_crs(&self) -> Option<i32> {
+        if self.srid_override {
+            self.feature_srid.or(self.srid)
+        } else {
+            self.srid.or(self.feature_srid)
+        }
     }
     fn comma(&mut self, idx: usize) -> Result<()> {
         if idx > 0 {
@@ -29,6 +291,9 @@ impl<W: Write> GeoJsonWriter<W> {
 
 impl<W: Write> FeatureProcessor for GeoJsonWriter<W> {
     fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        if self.output != GeoJsonOutput::FeatureCollection {
+            return Ok(());
+        }
         self.out.write_all(
             br#"{
 "type": "FeatureCollection""#,
@@ -36,6 +301,16 @@ impl<W: Write> FeatureProcessor for GeoJsonWriter<W> {
         if let Some(name) = name {
             write!(self.out, ",\n\"name\": \"{name}\"")?;
         }
+        for (member, value) in &self.foreign_members {
+            if self.rfc7946 && member == "crs" {
+                continue;
+            }
+            write!(
+                self.out,
+                ",\n{}: {value}",
+                serde_json::Value::String(member.clone())
+            )?;
+        }
         self.out.write_all(
             br#",
 "features": ["#,
@@ -43,29 +318,106 @@ impl<W: Write> FeatureProcessor for GeoJsonWriter<W> {
         Ok(())
     }
     fn dataset_end(&mut self) -> Result<()> {
-        self.out.write_all(b"]}")?;
+        if self.output != GeoJsonOutput::FeatureCollection {
+            self.out.write_all(b"\n")?;
+            return Ok(());
+        }
+        self.out.write_all(b"]")?;
+        self.write_bounds(self.dataset_bounds.take(), self.dataset_bounds_360.take())?;
+        self.out.write_all(b"}")?;
+        Ok(())
+    }
+    fn crs(&mut self, crs: &Crs) -> Result<()> {
+        if self.srid.is_none() {
+            self.srid = crs.srid();
+        }
+        Ok(())
+    }
+    fn schema_begin(&mut self, columns: &[ColumnDef]) -> Result<()> {
+        self.column_keys = columns.iter().map(|col| property_key(&col.name)).collect();
         Ok(())
     }
     fn feature_begin(&mut self, idx: u64) -> Result<()> {
         if idx > 0 {
-            self.out.write_all(b",\n")?;
+            match self.output {
+                GeoJsonOutput::FeatureCollection => self.out.write_all(b",\n")?,
+                GeoJsonOutput::Geometry | GeoJsonOutput::Sequence => self.out.write_all(b"\n")?,
+            }
+        }
+        self.feature_srid = None;
+        self.feature_bounds = None;
+        self.feature_bounds_360 = None;
+        if self.output != GeoJsonOutput::Geometry {
+            self.out.write_all(br#"{"type": "Feature""#)?;
         }
-        self.out.write_all(br#"{"type": "Feature""#)?;
         Ok(())
     }
+    fn feature_id(&mut self, id: &FeatureId) -> Result<()> {
+        if self.output == GeoJsonOutput::Geometry {
+            return Ok(());
+        }
+        self.out.write_all(b", ")?;
+        match id {
+            FeatureId::String(v) => write_str_prop(&mut self.out, "id", v),
+            FeatureId::Long(v) => write_int_value(
+                &mut self.out,
+                b"\"id\": ",
+                v,
+                v.unsigned_abs(),
+                self.large_integers,
+            ),
+            FeatureId::ULong(v) => {
+                write_int_value(&mut self.out, b"\"id\": ", v, *v, self.large_integers)
+            }
+        }
+    }
     fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        if self.output == GeoJsonOutput::Geometry {
+            return Ok(());
+        }
+        if self.write_crs && !self.rfc7946 {
+            if let Some(srid) = self.feature_crs() {
+                write!(
+                    self.out,
+                    r#", "crs": {{"type": "name", "properties": {{"name": "urn:ogc:def:crs:EPSG::{srid}"}}}}"#
+                )?;
+            }
+        }
+        if let Some(bounds) = self.feature_bounds.take() {
+            match self.dataset_bounds.as_mut() {
+                Some(dataset_bounds) => dataset_bounds.merge(&bounds),
+                None => self.dataset_bounds = Some(bounds),
+            }
+            let bounds_360 = self.feature_bounds_360.take();
+            if let Some(b360) = bounds_360 {
+                match self.dataset_bounds_360.as_mut() {
+                    Some(dataset_bounds) => dataset_bounds.merge(&b360),
+                    None => self.dataset_bounds_360 = Some(b360),
+                }
+            }
+            self.write_bounds(Some(bounds), bounds_360)?;
+        }
         self.out.write_all(b"}")?;
         Ok(())
     }
     fn properties_begin(&mut self) -> Result<()> {
+        if self.output == GeoJsonOutput::Geometry {
+            return Ok(());
+        }
         self.out.write_all(br#", "properties": {"#)?;
         Ok(())
     }
     fn properties_end(&mut self) -> Result<()> {
+        if self.output == GeoJsonOutput::Geometry {
+            return Ok(());
+        }
         self.out.write_all(b"}")?;
         Ok(())
     }
     fn geometry_begin(&mut self) -> Result<()> {
+        if self.output == GeoJsonOutput::Geometry {
+            return Ok(());
+        }
         self.out.write_all(br#", "geometry": "#)?;
         Ok(())
     }
@@ -78,28 +430,24 @@ impl<W: Write> GeomProcessor for GeoJsonWriter<W> {
     fn dimensions(&self) -> CoordDimensions {
         self.dims
     }
-    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
-        self.comma(idx)?;
-        self.out.write_all(format!("[{x},{y}]").as_bytes())?;
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.feature_srid = srid.filter(|srid| *srid != 0);
         Ok(())
     }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.write_coord(x, y, None, None, None, idx)
+    }
     fn coordinate(
         &mut self,
         x: f64,
         y: f64,
         z: Option<f64>,
         _m: Option<f64>,
-        _t: Option<f64>,
-        _tm: Option<u64>,
+        t: Option<f64>,
+        tm: Option<u64>,
         idx: usize,
     ) -> Result<()> {
-        self.comma(idx)?;
-        self.out.write_all(format!("[{x},{y}").as_bytes())?;
-        if let Some(z) = z {
-            self.out.write_all(format!(",{z}").as_bytes())?;
-        }
-        self.out.write_all(b"]")?;
-        Ok(())
+        self.write_coord(x, y, z, t, tm, idx)
     }
     fn empty_point(&mut self, idx: usize) -> Result<()> {
         self.comma(idx)?;
@@ -114,6 +462,7 @@ impl<W: Write> GeomProcessor for GeoJsonWriter<W> {
         Ok(())
     }
     fn point_end(&mut self, _idx: usize) -> Result<()> {
+        self.write_times()?;
         self.out.write_all(b"}")?;
         Ok(())
     }
@@ -124,7 +473,9 @@ impl<W: Write> GeomProcessor for GeoJsonWriter<W> {
         Ok(())
     }
     fn multipoint_end(&mut self, _idx: usize) -> Result<()> {
-        self.out.write_all(b"]}")?;
+        self.out.write_all(b"]")?;
+        self.write_times()?;
+        self.out.write_all(b"}")?;
         Ok(())
     }
     fn linestring_begin(&mut self, tagged: bool, _size: usize, idx: usize) -> Result<()> {
@@ -134,12 +485,21 @@ impl<W: Write> GeomProcessor for GeoJsonWriter<W> {
                 .write_all(br#"{"type": "LineString", "coordinates": ["#)?;
         } else {
             self.out.write_all(b"[")?;
+            if self.rfc7946 && self.in_polygon {
+                self.ring = Some(Vec::new());
+                self.ring_idx = idx;
+            }
         }
         Ok(())
     }
     fn linestring_end(&mut self, tagged: bool, _idx: usize) -> Result<()> {
+        if let Some(ring) = self.ring.take() {
+            self.write_ring(ring)?;
+        }
         if tagged {
-            self.out.write_all(b"]}")?;
+            self.out.write_all(b"]")?;
+            self.write_times()?;
+            self.out.write_all(b"}")?;
         } else {
             self.out.write_all(b"]")?;
         }
@@ -152,11 +512,14 @@ impl<W: Write> GeomProcessor for GeoJsonWriter<W> {
         Ok(())
     }
     fn multilinestring_end(&mut self, _idx: usize) -> Result<()> {
-        self.out.write_all(b"]}")?;
+        self.out.write_all(b"]")?;
+        self.write_times()?;
+        self.out.write_all(b"}")?;
         Ok(())
     }
     fn polygon_begin(&mut self, tagged: bool, _size: usize, idx: usize) -> Result<()> {
         self.comma(idx)?;
+        self.in_polygon = true;
         if tagged {
             self.out
                 .write_all(br#"{"type": "Polygon", "coordinates": ["#)?;
@@ -166,8 +529,11 @@ impl<W: Write> GeomProcessor for GeoJsonWriter<W> {
         Ok(())
     }
     fn polygon_end(&mut self, tagged: bool, _idx: usize) -> Result<()> {
+        self.in_polygon = false;
         if tagged {
-            self.out.write_all(b"]}")?;
+            self.out.write_all(b"]")?;
+            self.write_times()?;
+            self.out.write_all(b"}")?;
         } else {
             self.out.write_all(b"]")?;
         }
@@ -180,7 +546,9 @@ impl<W: Write> GeomProcessor for GeoJsonWriter<W> {
         Ok(())
     }
     fn multipolygon_end(&mut self, _idx: usize) -> Result<()> {
-        self.out.write_all(b"]}")?;
+        self.out.write_all(b"]")?;
+        self.write_times()?;
+        self.out.write_all(b"}")?;
         Ok(())
     }
     fn geometrycollection_begin(&mut self, _size: usize, idx: usize) -> Result<()> {
@@ -195,12 +563,6 @@ impl<W: Write> GeomProcessor for GeoJsonWriter<W> {
     }
 }
 
-fn write_num_prop<W: Write>(mut out: W, colname: &str, v: &dyn Display) -> Result<()> {
-    let colname = colname.replace('\"', "\\\"");
-    out.write_all(format!(r#""{colname}": {v}"#).as_bytes())?;
-    Ok(())
-}
-
 fn write_str_prop<W: Write>(mut out: W, colname: &str, v: &str) -> Result<()> {
     let colname = colname.replace('\"', "\\\"");
     let value = v.replace('\"', "\\\"");
@@ -208,35 +570,119 @@ fn write_str_prop<W: Write>(mut out: W, colname: &str, v: &str) -> Result<()> {
     Ok(())
 }
 
-fn write_json_prop<W: Write>(mut out: W, colname: &str, v: &str) -> Result<()> {
+/// Property key with escaped name, including the separator
+fn property_key(colname: &str) -> Vec<u8> {
     let colname = colname.replace('\"', "\\\"");
-    out.write_all(format!(r#""{colname}": {v}"#).as_bytes())?;
+    format!(r#""{colname}": "#).into_bytes()
+}
+
+/// Write property key and value. Binary values are skipped.
+fn write_prop<W: Write>(
+    mut out: W,
+    key: &[u8],
+    colval: &ColumnValue,
+    large_integers: LargeIntegers,
+) -> Result<()> {
+    match colval {
+        ColumnValue::Byte(v) => write_num_value(out, key, v)?,
+        ColumnValue::UByte(v) => write_num_value(out, key, v)?,
+        ColumnValue::Bool(v) => write_num_value(out, key, v)?,
+        ColumnValue::Short(v) => write_num_value(out, key, v)?,
+        ColumnValue::UShort(v) => write_num_value(out, key, v)?,
+        ColumnValue::Int(v) => write_num_value(out, key, v)?,
+        ColumnValue::UInt(v) => write_num_value(out, key, v)?,
+        ColumnValue::Long(v) => write_int_value(out, key, v, v.unsigned_abs(), large_integers)?,
+        ColumnValue::ULong(v) => write_int_value(out, key, v, *v, large_integers)?,
+        ColumnValue::Float(v) => write_num_value(out, key, v)?,
+        ColumnValue::Double(v) => write_num_value(out, key, v)?,
+        ColumnValue::String(v) | ColumnValue::DateTime(v) => {
+            out.write_all(key)?;
+            let value = v.replace('\"', "\\\"");
+            write!(out, r#""{value}""#)?;
+        }
+        ColumnValue::Json(v) => {
+            out.write_all(key)?;
+            out.write_all(v.as_bytes())?;
+        }
+        ColumnValue::Binary(_v) => (),
+        ColumnValue::Uuid(v) => {
+            out.write_all(key)?;
+            write!(out, r#""{}""#, format_uuid(v))?;
+        }
+        ColumnValue::Decimal(v) if is_json_number(v) => write_num_value(out, key, v)?,
+        ColumnValue::Decimal(v) => {
+            // NaN, Infinity or other non-JSON numbers
+            out.write_all(key)?;
+            write!(out, "{}", serde_json::Value::from(*v))?;
+        }
+        ColumnValue::Array(v) => write_num_value(out, key, &array_json(v))?,
+    };
     Ok(())
 }
 
+fn write_num_value<W: Write>(mut out: W, key: &[u8], v: &dyn Display) -> Result<()> {
+    out.write_all(key)?;
+    write!(out, "{v}")?;
+    Ok(())
+}
+
+/// Write integer with absolute value `magnitude`, handling values exceeding ±2^53
+fn write_int_value<W: Write>(
+    mut out: W,
+    key: &[u8],
+    v: &dyn Display,
+    magnitude: u64,
+    large_integers: LargeIntegers,
+) -> Result<()> {
+    if magnitude <= MAX_SAFE_INTEGER {
+        return write_num_value(out, key, v);
+    }
+    match large_integers {
+        LargeIntegers::Number => write_num_value(out, key, v),
+        LargeIntegers::String => {
+            out.write_all(key)?;
+            write!(out, r#""{v}""#)?;
+            Ok(())
+        }
+        LargeIntegers::Error => Err(GeozeroError::Property(format!(
+            "integer {v} exceeds the safe JSON integer range"
+        ))),
+    }
+}
+
 impl<W: Write> PropertyProcessor for GeoJsonWriter<W> {
     fn property(&mut self, i: usize, colname: &str, colval: &ColumnValue) -> Result<bool> {
+        if self.output == GeoJsonOutput::Geometry {
+            return Ok(false);
+        }
         if i > 0 {
             self.out.write_all(b", ")?;
         }
-        match colval {
-            ColumnValue::Byte(v) => write_num_prop(&mut self.out, colname, &v)?,
-            ColumnValue::UByte(v) => write_num_prop(&mut self.out, colname, &v)?,
-            ColumnValue::Bool(v) => write_num_prop(&mut self.out, colname, &v)?,
-            ColumnValue::Short(v) => write_num_prop(&mut self.out, colname, &v)?,
-            ColumnValue::UShort(v) => write_num_prop(&mut self.out, colname, &v)?,
-            ColumnValue::Int(v) => write_num_prop(&mut self.out, colname, &v)?,
-            ColumnValue::UInt(v) => write_num_prop(&mut self.out, colname, &v)?,
-            ColumnValue::Long(v) => write_num_prop(&mut self.out, colname, &v)?,
-            ColumnValue::ULong(v) => write_num_prop(&mut self.out, colname, &v)?,
-            ColumnValue::Float(v) => write_num_prop(&mut self.out, colname, &v)?,
-            ColumnValue::Double(v) => write_num_prop(&mut self.out, colname, &v)?,
-            ColumnValue::String(v) | ColumnValue::DateTime(v) => {
-                write_str_prop(&mut self.out, colname, v)?;
-            }
-            ColumnValue::Json(v) => write_json_prop(&mut self.out, colname, v)?,
-            ColumnValue::Binary(_v) => (),
+        write_prop(
+            &mut self.out,
+            &property_key(colname),
+            colval,
+            self.large_integers,
+        )?;
+        Ok(false)
+    }
+    fn column_property(
+        &mut self,
+        i: usize,
+        column: usize,
+        colname: &str,
+        colval: &ColumnValue,
+    ) -> Result<bool> {
+        if self.output == GeoJsonOutput::Geometry {
+            return Ok(false);
+        }
+        let Some(key) = self.column_keys.get(column) else {
+            return self.property(i, colname, colval);
         };
+        if i > 0 {
+            self.out.write_all(b", ")?;
+        }
+        write_prop(&mut self.out, key, colval, self.large_integers)?;
         Ok(false)
     }
 }
@@ -246,7 +692,7 @@ mod test {
     use super::*;
     use crate::geojson::read_geojson;
     use crate::wkt::Wkt;
-    use crate::ToJson;
+    use crate::{ColumnType, ColumnValueOwned, ToJson};
 
     #[test]
     fn geometries() -> Result<()> {
@@ -497,10 +943,249 @@ mod test {
         assert_json_eq(&out, geojson);
     }
 
+    #[test]
+    fn schema_column_properties() -> Result<()> {
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer = GeoJsonWriter::new(&mut out);
+        writer.dataset_begin(None)?;
+        writer.schema_begin(&[
+            ColumnDef::new("name", ColumnType::String),
+            ColumnDef::new("say \"hi\"", ColumnType::Int),
+        ])?;
+        writer.feature_begin(0)?;
+        writer.properties_begin()?;
+        writer.column_property(0, 0, "name", &ColumnValue::String("a"))?;
+        writer.column_property(1, 1, "say \"hi\"", &ColumnValue::Int(1))?;
+        // Column not declared in schema
+        writer.column_property(2, 2, "extra", &ColumnValue::Bool(true))?;
+        writer.properties_end()?;
+        writer.feature_end(0)?;
+        writer.dataset_end()?;
+        assert_json_eq(
+            &out,
+            r#"{"type": "FeatureCollection", "features": [{"type": "Feature", "properties": {"name": "a", "say \"hi\"": 1, "extra": true}}]}"#,
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn uuid_decimal_array_properties() -> Result<()> {
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer = GeoJsonWriter::new(&mut out);
+        let values = [ColumnValueOwned::Int(1), ColumnValueOwned::Int(2)];
+        writer.feature_begin(0)?;
+        writer.properties_begin()?;
+        writer.property(0, "id", &ColumnValue::Uuid([0xab; 16]))?;
+        writer.property(1, "amount", &ColumnValue::Decimal("12.50"))?;
+        writer.property(2, "values", &ColumnValue::Array(&values))?;
+        writer.property(3, "ratio", &ColumnValue::Decimal("NaN"))?;
+        writer.properties_end()?;
+        writer.feature_end(0)?;
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            r#"{"type": "Feature", "properties": {"id": "abababab-abab-abab-abab-abababababab", "amount": 12.50, "values": [1,2], "ratio": "NaN"}}"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn output_modes() -> Result<()> {
+        let geojson = r#"{
+            "type": "FeatureCollection",
+            "features": [
+                {"type": "Feature", "properties": {"a": 1}, "geometry": {"type": "Point", "coordinates": [1,2]}},
+                {"type": "Feature", "properties": {"a": 2}, "geometry": {"type": "Point", "coordinates": [3,4]}}
+            ]
+        }"#;
+        let write = |output| -> Result<String> {
+            let mut out: Vec<u8> = Vec::new();
+            let mut writer = GeoJsonWriter::new(&mut out);
+            writer.set_output(output);
+            writer.add_foreign_member("title", serde_json::json!("points"));
+            read_geojson(geojson.as_bytes(), &mut writer)?;
+            Ok(String::from_utf8(out).unwrap())
+        };
+
+        let out = write(GeoJsonOutput::FeatureCollection)?;
+        let collection: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(collection["title"], "points");
+        assert_eq!(collection["features"].as_array().unwrap().len(), 2);
+
+        let out = write(GeoJsonOutput::Geometry)?;
+        let lines: Vec<_> = out.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_json_eq(
+            lines[1].as_bytes(),
+            r#"{"type": "Point", "coordinates": [3,4]}"#,
+        );
+
+        let out = write(GeoJsonOutput::Sequence)?;
+        let lines: Vec<_> = out.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_json_eq(
+            lines[0].as_bytes(),
+            r#"{"type": "Feature", "properties": {"a": 1}, "geometry": {"type": "Point", "coordinates": [1,2]}}"#,
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn feature_crs() -> Result<()> {
+        let write = |srid: Option<i32>, srid_override: bool| -> Result<String> {
+            let mut out: Vec<u8> = Vec::new();
+            let mut writer = GeoJsonWriter::new(&mut out);
+            writer.set_write_crs(true);
+            writer.set_srid(srid);
+            writer.set_srid_override(srid_override);
+            writer.feature_begin(0)?;
+            writer.geometry_begin()?;
+            writer.srid(Some(4326))?;
+            writer.point_begin(0)?;
+            writer.xy(1.0, 2.0, 0)?;
+            writer.point_end(0)?;
+            writer.geometry_end()?;
+            writer.feature_end(0)?;
+            Ok(String::from_utf8(out).unwrap())
+        };
+        let crs = |srid| {
+            format!(
+                r#"{{"type": "Feature", "geometry": {{"type": "Point", "coordinates": [1,2]}}, "crs": {{"type": "name", "properties": {{"name": "urn:ogc:def:crs:EPSG::{srid}"}}}}}}"#
+            )
+        };
+        assert_eq!(write(None, false)?, crs(4326));
+        assert_eq!(write(Some(3857), false)?, crs(3857));
+        assert_eq!(write(Some(3857), true)?, crs(4326));
+
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer = GeoJsonWriter::new(&mut out);
+        writer.set_write_crs(true);
+        writer.crs(&Crs::epsg(2056))?;
+        writer.feature_begin(0)?;
+        writer.feature_end(0)?;
+        assert!(std::str::from_utf8(&out)
+            .unwrap()
+            .contains("urn:ogc:def:crs:EPSG::2056"));
+        Ok(())
+    }
+
+    #[test]
+    fn feature_bbox() -> Result<()> {
+        let geojson = r#"{
+            "type": "FeatureCollection",
+            "features": [
+                {"type": "Feature", "properties": {}, "geometry": {"type": "LineString", "coordinates": [[1,2],[3,-1]]}},
+                {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [-2,5]}}
+            ]
+        }"#;
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer = GeoJsonWriter::new(&mut out);
+        writer.set_write_bbox(true);
+        read_geojson(geojson.as_bytes(), &mut writer)?;
+        assert_json_eq(
+            &out,
+            r#"{
+            "type": "FeatureCollection",
+            "features": [
+                {"type": "Feature", "properties": {}, "geometry": {"type": "LineString", "coordinates": [[1,2],[3,-1]]}, "bbox": [1,-1,3,2]},
+                {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [-2,5]}, "bbox": [-2,5,-2,5]}
+            ],
+            "bbox": [-2,-1,3,5]
+        }"#,
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn coordinate_times() -> Result<()> {
+        let geojson = r#"{"type": "MultiLineString", "coordinates": [[[1,2],[3,4]],[[5,6]]], "t": [2020.5,null,2021], "tm": [1000,2000,3000]}"#;
+        let dims = CoordDimensions {
+            t: true,
+            tm: true,
+            ..CoordDimensions::xy()
+        };
+        let mut out: Vec<u8> = Vec::new();
+        read_geojson(
+            geojson.as_bytes(),
+            &mut GeoJsonWriter::with_dims(&mut out, dims),
+        )?;
+        assert_json_eq(&out, geojson);
+
+        // not requested
+        let mut out: Vec<u8> = Vec::new();
+        read_geojson(geojson.as_bytes(), &mut GeoJsonWriter::new(&mut out))?;
+        assert_json_eq(
+            &out,
+            r#"{"type": "MultiLineString", "coordinates": [[[1,2],[3,4]],[[5,6]]]}"#,
+        );
+
+        let mut out: Vec<u8> = Vec::new();
+        crate::wkt::read_wkt(
+            &mut "POINT(1 2 T=2020.5)".as_bytes(),
+            &mut GeoJsonWriter::with_dims(&mut out, dims),
+        )?;
+        assert_json_eq(
+            &out,
+            r#"{"type": "Point", "coordinates": [1,2], "t": [2020.5]}"#,
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn rfc7946() -> Result<()> {
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {}, "geometry": {"type": "Polygon", "coordinates": [[[0,0],[0,1],[1,1],[1,0],[0,0]]]}},
+            {"type": "Feature", "properties": {}, "geometry": {"type": "LineString", "coordinates": [[179.5,0.123456789],[-179.5,1]]}}
+        ]}"#;
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer = GeoJsonWriter::new(&mut out);
+        writer.set_rfc7946(true);
+        writer.set_write_bbox(true);
+        writer.set_write_crs(true);
+        writer.set_srid(Some(4326));
+        writer.add_foreign_member("crs", serde_json::json!({"type": "name"}));
+        read_geojson(geojson.as_bytes(), &mut writer)?;
+        assert_json_eq(
+            &out,
+            r#"{"type": "FeatureCollection", "features": [
+                {"type": "Feature", "properties": {}, "geometry": {"type": "Polygon", "coordinates": [[[0,0],[1,0],[1,1],[0,1],[0,0]]]}, "bbox": [0,0,1,1]},
+                {"type": "Feature", "properties": {}, "geometry": {"type": "LineString", "coordinates": [[179.5,0.1234568],[-179.5,1]]}, "bbox": [179.5,0.1234568,-179.5,1]}
+            ], "bbox": [0,0,-179.5,1]}"#,
+        );
+        Ok(())
+    }
+
     fn assert_json_eq(a: &[u8], b: &str) {
         let a = std::str::from_utf8(a).unwrap();
         let a: serde_json::Value = serde_json::from_str(a).unwrap();
         let b: serde_json::Value = serde_json::from_str(b).unwrap();
         assert_eq!(a, b);
     }
+
+    #[test]
+    fn large_integers() -> Result<()> {
+        let write = |large_integers| -> Result<String> {
+            let mut out = Vec::new();
+            let mut writer = GeoJsonWriter::new(&mut out);
+            writer.set_output(GeoJsonOutput::Sequence);
+            writer.set_large_integers(large_integers);
+            writer.feature_begin(0)?;
+            writer.feature_id(&FeatureId::ULong(u64::MAX))?;
+            writer.properties_begin()?;
+            writer.property(0, "small", &ColumnValue::Long(-(1 << 53) + 1))?;
+            writer.property(1, "large", &ColumnValue::Long(-(1 << 53)))?;
+            writer.properties_end()?;
+            writer.feature_end(0)?;
+            Ok(String::from_utf8(out).unwrap())
+        };
+        assert_eq!(
+            write(LargeIntegers::Number)?,
+            r#"{"type": "Feature", "id": 18446744073709551615, "properties": {"small": -9007199254740991, "large": -9007199254740992}}"#
+        );
+        assert_eq!(
+            write(LargeIntegers::String)?,
+            r#"{"type": "Feature", "id": "18446744073709551615", "properties": {"small": -9007199254740991, "large": "-9007199254740992"}}"#
+        );
+        assert!(write(LargeIntegers::Error).is_err());
+        Ok(())
+    }
 }
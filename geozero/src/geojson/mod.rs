@@ -53,7 +53,7 @@ impl From<geojson::Error> for crate::error::GeozeroError {
     fn from(geojson_error: geojson::Error) -> Self {
         match geojson_error {
             geojson::Error::Io(io) => Self::IoError(io),
-            other => Self::Geometry(other.to_string()),
+            other => Self::GeoJsonError(other),
         }
     }
 }
@@ -77,12 +77,20 @@ mod wkb {
         }
     }
 
-    // crate::impl_postgres_postgis_decode!(GeoJsonString);
-    // crate::impl_postgres_postgis_encode!(GeoJsonString);
-    // crate::impl_sqlx_postgis_type_info!(GeoJsonString);
-    // crate::impl_sqlx_postgis_decode!(GeoJsonString);
-    // crate::impl_sqlx_postgis_encode!(GeoJsonString);
-    // crate::impl_sqlx_gpkg_type_info!(GeoJsonString);
-    // crate::impl_sqlx_gpkg_decode!(GeoJsonString);
-    // crate::impl_sqlx_gpkg_encode!(GeoJsonString);
+    #[cfg(feature = "with-postgis-postgres")]
+    mod postgis_postgres {
+        use crate::geojson::GeoJsonString;
+
+        crate::impl_postgres_postgis_decode!(GeoJsonString);
+        crate::impl_postgres_postgis_encode!(GeoJsonString);
+    }
+
+    #[cfg(feature = "with-postgis-sqlx")]
+    mod postgis_sqlx {
+        use crate::geojson::GeoJsonString;
+
+        crate::impl_sqlx_postgis_type_info!(GeoJsonString);
+        crate::impl_sqlx_postgis_decode!(GeoJsonString);
+        crate::impl_sqlx_postgis_encode!(GeoJsonString);
+    }
 }
@@ -1,8 +1,8 @@
 use crate::error::{GeozeroError, Result};
-use crate::WrappedXYProcessor;
+use crate::{LinearizeCurves, ValidateProcessor, WrappedXYProcessor};
 
 /// Dimensions requested for processing
-#[derive(Default, Clone, Copy)]
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
 pub struct CoordDimensions {
     /// height
     pub z: bool,
@@ -84,6 +84,22 @@ pub trait GeomProcessor {
         Ok(())
     }
 
+    /// Geometry envelope as `[minx, maxx, miny, maxy]` followed by `[minz, maxz]` and/or
+    /// `[minm, maxm]` according to `dims`
+    ///
+    /// Emitted before geometry begin by readers storing an envelope in the geometry header.
+    fn envelope(&mut self, envelope: &[f64], dims: CoordDimensions, idx: usize) -> Result<()> {
+        Ok(())
+    }
+
+    /// Total number of coordinates of the following geometry
+    ///
+    /// Emitted before geometry begin by readers knowing the vertex count in advance.
+    /// Writers can use it for preallocation.
+    fn size_hint_total_coords(&mut self, count: usize) -> Result<()> {
+        Ok(())
+    }
+
     /// Process coordinate with x,y dimensions
     fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
         Ok(())
@@ -103,6 +119,16 @@ pub trait GeomProcessor {
         Ok(())
     }
 
+    /// Process consecutive coordinates of a geometry
+    ///
+    /// `coords` contains interleaved x, y and the z, m, t ordinates included in `dims`
+    /// (`tm` is not supported). `idx` is the positional index of the first coordinate.
+    /// Emitted by readers with contiguous coordinate arrays. The default implementation
+    /// calls `xy` or `coordinate` for each coordinate, depending on `multi_dim`.
+    fn coords(&mut self, coords: &[f64], dims: CoordDimensions, idx: usize) -> Result<()> {
+        process_coords(self, coords, dims, idx)
+    }
+
     /// Process empty coordinates, like WKT's `POINT EMPTY`
     ///
     /// - `idx` is the positional index inside this geometry. `idx` will usually be 0 except in the
@@ -417,6 +443,51 @@ pub trait GeomProcessor {
     {
         WrappedXYProcessor::new(self, transform_xy)
     }
+
+    /// Combinator which replaces curve geometries by their linear approximation.
+    ///
+    /// Useful for processing curves with writers without curve support, like GEOS, geo-types or MVT.
+    /// See [LinearizeCurves] for configuration options.
+    fn linearize_curves(self) -> LinearizeCurves<Self>
+    where
+        Self: Sized,
+    {
+        LinearizeCurves::new(self)
+    }
+
+    /// Combinator which checks geometries for structural validity.
+    ///
+    /// Aborts processing with an error on the first invalid geometry.
+    /// See [ValidateProcessor] for collecting issues as warnings instead.
+    fn validate(self) -> ValidateProcessor<Self>
+    where
+        Self: Sized,
+    {
+        ValidateProcessor::new(self)
+    }
+}
+
+/// Emit coordinates of an interleaved coordinate slice with `xy` or `coordinate`
+pub(crate) fn process_coords<P: GeomProcessor + ?Sized>(
+    processor: &mut P,
+    coords: &[f64],
+    dims: CoordDimensions,
+    idx: usize,
+) -> Result<()> {
+    let stride = 2 + usize::from(dims.z) + usize::from(dims.m) + usize::from(dims.t);
+    let multi_dim = processor.multi_dim();
+    for (i, coord) in coords.chunks_exact(stride).enumerate() {
+        if multi_dim {
+            let mut ordinates = coord[2..].iter().copied();
+            let z = if dims.z { ordinates.next() } else { None };
+            let m = if dims.m { ordinates.next() } else { None };
+            let t = if dims.t { ordinates.next() } else { None };
+            processor.coordinate(coord[0], coord[1], z, m, t, None, idx + i)?;
+        } else {
+            processor.xy(coord[0], coord[1], idx + i)?;
+        }
+    }
+    Ok(())
 }
 
 #[test]
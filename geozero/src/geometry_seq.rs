@@ -0,0 +1,258 @@
+//! Processing of geometry sequences as GeometryCollection.
+use crate::error::Result;
+use crate::{CoordDimensions, GeomProcessor, GeozeroGeometry};
+
+/// Geometries in a slice are processed as members of a GeometryCollection.
+impl<G: GeozeroGeometry> GeozeroGeometry for &[G] {
+    fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
+        process_geometry_seq(self, processor)
+    }
+    fn dims(&self) -> CoordDimensions {
+        self.first().map(|g| g.dims()).unwrap_or_default()
+    }
+    fn srid(&self) -> Option<i32> {
+        self.first().and_then(|g| g.srid())
+    }
+}
+
+/// Geometries in a vector are processed as members of a GeometryCollection.
+impl<G: GeozeroGeometry> GeozeroGeometry for Vec<G> {
+    fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
+        process_geometry_seq(self, processor)
+    }
+    fn dims(&self) -> CoordDimensions {
+        self.first().map(|g| g.dims()).unwrap_or_default()
+    }
+    fn srid(&self) -> Option<i32> {
+        self.first().and_then(|g| g.srid())
+    }
+}
+
+fn process_geometry_seq<G: GeozeroGeometry, P: GeomProcessor>(
+    geoms: &[G],
+    processor: &mut P,
+) -> Result<()> {
+    processor.geometrycollection_begin(geoms.len(), 0)?;
+    for (i, geom) in geoms.iter().enumerate() {
+        geom.process_geom(&mut MemberProcessor {
+            processor: &mut *processor,
+            idx: i,
+            level: 0,
+        })?;
+    }
+    processor.geometrycollection_end(0)
+}
+
+/// Replaces the positional index of a top-level geometry with its index in the collection.
+struct MemberProcessor<'a, P: GeomProcessor> {
+    processor: &'a mut P,
+    idx: usize,
+    /// Nesting level of the current geometry
+    level: usize,
+}
+
+impl<P: GeomProcessor> MemberProcessor<'_, P> {
+    fn begin_idx(&mut self, idx: usize) -> usize {
+        self.level += 1;
+        if self.level == 1 {
+            self.idx
+        } else {
+            idx
+        }
+    }
+    fn end_idx(&mut self, idx: usize) -> usize {
+        self.level -= 1;
+        if self.level == 0 {
+            self.idx
+        } else {
+            idx
+        }
+    }
+}
+
+impl<P: GeomProcessor> GeomProcessor for MemberProcessor<'_, P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.processor.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.processor.multi_dim()
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.processor.srid(srid)
+    }
+    fn envelope(&mut self, envelope: &[f64], dims: CoordDimensions, idx: usize) -> Result<()> {
+        self.processor.envelope(envelope, dims, idx)
+    }
+    fn size_hint_total_coords(&mut self, count: usize) -> Result<()> {
+        self.processor.size_hint_total_coords(count)
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.processor.xy(x, y, idx)
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.processor.coordinate(x, y, z, m, t, tm, idx)
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        let idx = if self.level == 0 { self.idx } else { idx };
+        self.processor.empty_point(idx)
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        let idx = self.begin_idx(idx);
+        self.processor.point_begin(idx)
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        let idx = self.end_idx(idx);
+        self.processor.point_end(idx)
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        let idx = self.begin_idx(idx);
+        self.processor.multipoint_begin(size, idx)
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        let idx = self.end_idx(idx);
+        self.processor.multipoint_end(idx)
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        let idx = self.begin_idx(idx);
+        self.processor.linestring_begin(tagged, size, idx)
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        let idx = self.end_idx(idx);
+        self.processor.linestring_end(tagged, idx)
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        let idx = self.begin_idx(idx);
+        self.processor.multilinestring_begin(size, idx)
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        let idx = self.end_idx(idx);
+        self.processor.multilinestring_end(idx)
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        let idx = self.begin_idx(idx);
+        self.processor.polygon_begin(tagged, size, idx)
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        let idx = self.end_idx(idx);
+        self.processor.polygon_end(tagged, idx)
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        let idx = self.begin_idx(idx);
+        self.processor.multipolygon_begin(size, idx)
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        let idx = self.end_idx(idx);
+        self.processor.multipolygon_end(idx)
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        let idx = self.begin_idx(idx);
+        self.processor.geometrycollection_begin(size, idx)
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        let idx = self.end_idx(idx);
+        self.processor.geometrycollection_end(idx)
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        let idx = self.begin_idx(idx);
+        self.processor.circularstring_begin(size, idx)
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        let idx = self.end_idx(idx);
+        self.processor.circularstring_end(idx)
+    }
+    fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        let idx = self.begin_idx(idx);
+        self.processor.compoundcurve_begin(size, idx)
+    }
+    fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        let idx = self.end_idx(idx);
+        self.processor.compoundcurve_end(idx)
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        let idx = self.begin_idx(idx);
+        self.processor.curvepolygon_begin(size, idx)
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        let idx = self.end_idx(idx);
+        self.processor.curvepolygon_end(idx)
+    }
+    fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        let idx = self.begin_idx(idx);
+        self.processor.multicurve_begin(size, idx)
+    }
+    fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        let idx = self.end_idx(idx);
+        self.processor.multicurve_end(idx)
+    }
+    fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        let idx = self.begin_idx(idx);
+        self.processor.multisurface_begin(size, idx)
+    }
+    fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        let idx = self.end_idx(idx);
+        self.processor.multisurface_end(idx)
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        let idx = self.begin_idx(idx);
+        self.processor.triangle_begin(tagged, size, idx)
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        let idx = self.end_idx(idx);
+        self.processor.triangle_end(tagged, idx)
+    }
+    fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        let idx = self.begin_idx(idx);
+        self.processor.polyhedralsurface_begin(size, idx)
+    }
+    fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        let idx = self.end_idx(idx);
+        self.processor.polyhedralsurface_end(idx)
+    }
+    fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        let idx = self.begin_idx(idx);
+        self.processor.tin_begin(size, idx)
+    }
+    fn tin_end(&mut self, idx: usize) -> Result<()> {
+        let idx = self.end_idx(idx);
+        self.processor.tin_end(idx)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use crate::wkt::Wkt;
+    use crate::ToWkt;
+
+    #[test]
+    fn geometry_vec() {
+        let geoms = vec![
+            Wkt("POINT(1 1)"),
+            Wkt("LINESTRING(1 1,2 2)"),
+            Wkt("MULTIPOINT((1 1),(2 2))"),
+        ];
+        assert_eq!(
+            geoms.to_wkt().unwrap(),
+            "GEOMETRYCOLLECTION(POINT(1 1),LINESTRING(1 1,2 2),MULTIPOINT(1 1,2 2))"
+        );
+        assert_eq!(
+            (&geoms[1..]).to_wkt().unwrap(),
+            "GEOMETRYCOLLECTION(LINESTRING(1 1,2 2),MULTIPOINT(1 1,2 2))"
+        );
+    }
+
+    #[test]
+    fn empty_vec() {
+        let geoms: Vec<Wkt<&str>> = vec![];
+        assert_eq!(geoms.to_wkt().unwrap(), "GEOMETRYCOLLECTION EMPTY");
+    }
+}
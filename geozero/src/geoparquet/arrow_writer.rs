@@ -0,0 +1,419 @@
+use crate::error::{GeozeroError, Result};
+use crate::wkb::{WkbDialect, WkbWriter};
+use crate::{
+    ColumnDef, ColumnType, ColumnValue, ColumnValueOwned, CoordDimensions, Crs, FeatureProcessor,
+    GeomProcessor, PropertyProcessor,
+};
+use arrow_array::{
+    ArrayRef, BinaryArray, BooleanArray, Float32Array, Float64Array, Int16Array, Int32Array,
+    Int64Array, Int8Array, RecordBatch, StringArray, UInt16Array, UInt32Array, UInt64Array,
+    UInt8Array,
+};
+use arrow_schema::{DataType, Field, Schema};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Name of the geometry column
+const GEOMETRY_COLUMN: &str = "geometry";
+
+/// Arrow writer.
+///
+/// Collects features into a [RecordBatch] with WKB encoded geometries in a `geometry` column
+/// of the `geoarrow.wkb` extension type and GeoParquet `geo` schema metadata.
+/// The record batch can be written as Arrow IPC (Feather) or Parquet file.
+///
+/// Property columns are typed by their schema declaration or their first value.
+/// Columns with values of different types are written as strings.
+/// The dataset CRS is stored as PROJJSON `id` in the `geo` metadata.
+/// All features are kept in memory until [GeoArrowWriter::finish].
+pub struct GeoArrowWriter {
+    columns: Vec<Column>,
+    column_idx: HashMap<String, usize>,
+    geometries: Vec<Option<Vec<u8>>>,
+    /// Geometry of the current feature
+    geometry: Option<Vec<u8>>,
+    wkb: WkbWriter<Vec<u8>>,
+    crs: Option<Crs>,
+}
+
+struct Column {
+    name: String,
+    column_type: Option<ColumnType>,
+    values: Vec<Option<ColumnValueOwned>>,
+}
+
+impl Default for GeoArrowWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GeoArrowWriter {
+    pub fn new() -> Self {
+        GeoArrowWriter {
+            columns: Vec::new(),
+            column_idx: HashMap::new(),
+            geometries: Vec::new(),
+            geometry: None,
+            wkb: WkbWriter::new(Vec::new(), WkbDialect::Wkb),
+            crs: None,
+        }
+    }
+
+    /// Number of written features
+    pub fn len(&self) -> usize {
+        self.geometries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.geometries.is_empty()
+    }
+
+    /// Build record batch of all written features
+    pub fn finish(self) -> Result<RecordBatch> {
+        let rows = self.geometries.len();
+        let geometry_field =
+            Field::new(GEOMETRY_COLUMN, DataType::Binary, true).with_metadata(HashMap::from([(
+                "ARROW:extension:name".to_string(),
+                "geoarrow.wkb".to_string(),
+            )]));
+        let mut fields = vec![geometry_field];
+        let mut arrays: Vec<ArrayRef> = vec![Arc::new(BinaryArray::from_iter(
+            self.geometries.iter().map(Option::as_deref),
+        ))];
+        for mut column in self.columns {
+            if column.name == GEOMETRY_COLUMN {
+                return Err(GeozeroError::Property(format!(
+                    "Property `{GEOMETRY_COLUMN}` conflicts with geometry column"
+                )));
+            }
+            column.values.resize(rows, None);
+            let array = column_array(&column);
+            fields.push(Field::new(&column.name, array.data_type().clone(), true));
+            arrays.push(array);
+        }
+        let mut column = serde_json::json!({"encoding": "WKB", "geometry_types": []});
+        // A missing `crs` key means OGC:CRS84
+        if let Some(crs) = self.crs.filter(|crs| *crs != Crs::crs84()) {
+            column["crs"] = projjson_id(&crs);
+        }
+        let geo = serde_json::json!({
+            "version": "1.1.0",
+            "primary_column": GEOMETRY_COLUMN,
+            "columns": {GEOMETRY_COLUMN: column},
+        })
+        .to_string();
+        let schema = Schema::new(fields).with_metadata(HashMap::from([("geo".to_string(), geo)]));
+        Ok(RecordBatch::try_new(Arc::new(schema), arrays)?)
+    }
+
+    fn column(&mut self, name: &str, column_type: Option<ColumnType>) -> &mut Column {
+        let idx = match self.column_idx.get(name) {
+            Some(idx) => *idx,
+            None => {
+                self.column_idx.insert(name.to_string(), self.columns.len());
+                self.columns.push(Column {
+                    name: name.to_string(),
+                    column_type,
+                    values: Vec::new(),
+                });
+                self.columns.len() - 1
+            }
+        };
+        &mut self.columns[idx]
+    }
+}
+
+/// PROJJSON object with the CRS identifier, `null` (undefined CRS) without identifier
+fn projjson_id(crs: &Crs) -> serde_json::Value {
+    match (&crs.authority, &crs.code) {
+        (Some(authority), Some(code)) => {
+            let code = code
+                .parse::<i64>()
+                .map_or_else(|_| code.as_str().into(), serde_json::Value::from);
+            serde_json::json!({"id": {"authority": authority, "code": code}})
+        }
+        _ => serde_json::Value::Null,
+    }
+}
+
+macro_rules! primitive_array {
+    ($values:expr, $variant:ident, $array:ty) => {
+        Arc::new(
+            $values
+                .iter()
+                .map(|value| match value {
+                    Some(ColumnValueOwned::$variant(v)) => Some(*v),
+                    _ => None,
+                })
+                .collect::<$array>(),
+        )
+    };
+}
+
+/// Arrow array of column values
+fn column_array(column: &Column) -> ArrayRef {
+    let values = &column.values;
+    let value_type = |value: &ColumnValueOwned| value.as_column_value().column_type();
+    let column_type = column
+        .column_type
+        .or_else(|| values.iter().flatten().map(value_type).next());
+    let uniform = values
+        .iter()
+        .flatten()
+        .all(|value| Some(value_type(value)) == column_type);
+    match column_type {
+        Some(ColumnType::Byte) if uniform => primitive_array!(values, Byte, Int8Array),
+        Some(ColumnType::UByte) if uniform => primitive_array!(values, UByte, UInt8Array),
+        Some(ColumnType::Bool) if uniform => primitive_array!(values, Bool, BooleanArray),
+        Some(ColumnType::Short) if uniform => primitive_array!(values, Short, Int16Array),
+        Some(ColumnType::UShort) if uniform => primitive_array!(values, UShort, UInt16Array),
+        Some(ColumnType::Int) if uniform => primitive_array!(values, Int, Int32Array),
+        Some(ColumnType::UInt) if uniform => primitive_array!(values, UInt, UInt32Array),
+        Some(ColumnType::Long) if uniform => primitive_array!(values, Long, Int64Array),
+        Some(ColumnType::ULong) if uniform => primitive_array!(values, ULong, UInt64Array),
+        Some(ColumnType::Float) if uniform => primitive_array!(values, Float, Float32Array),
+        Some(ColumnType::Double) if uniform => primitive_array!(values, Double, Float64Array),
+        Some(ColumnType::Binary) if uniform => Arc::new(BinaryArray::from_iter(values.iter().map(
+            |value| match value {
+                Some(ColumnValueOwned::Binary(v)) => Some(v.as_slice()),
+                _ => None,
+            },
+        ))),
+        _ => Arc::new(StringArray::from_iter(
+            values
+                .iter()
+                .map(|value| value.as_ref().map(ToString::to_string)),
+        )),
+    }
+}
+
+impl FeatureProcessor for GeoArrowWriter {
+    fn crs(&mut self, crs: &Crs) -> Result<()> {
+        self.crs = Some(crs.clone());
+        Ok(())
+    }
+    fn schema_begin(&mut self, columns: &[ColumnDef]) -> Result<()> {
+        for column in columns {
+            self.column(&column.name, Some(column.column_type));
+        }
+        Ok(())
+    }
+    fn feature_begin(&mut self, _idx: u64) -> Result<()> {
+        self.geometry = None;
+        Ok(())
+    }
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        self.geometries.push(self.geometry.take());
+        Ok(())
+    }
+    fn geometry_end(&mut self) -> Result<()> {
+        let wkb = std::mem::replace(&mut self.wkb, WkbWriter::new(Vec::new(), WkbDialect::Wkb));
+        let wkb = wkb.into_inner();
+        self.geometry = (!wkb.is_empty()).then_some(wkb);
+        Ok(())
+    }
+}
+
+impl PropertyProcessor for GeoArrowWriter {
+    fn property(&mut self, _idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        let row = self.geometries.len();
+        let column = self.column(name, None);
+        column.values.resize(row, None);
+        column.values.push(Some(value.into()));
+        Ok(false)
+    }
+}
+
+impl GeomProcessor for GeoArrowWriter {
+    fn dimensions(&self) -> CoordDimensions {
+        self.wkb.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.wkb.multi_dim()
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.wkb.srid(srid)
+    }
+    fn envelope(&mut self, envelope: &[f64], dims: CoordDimensions, idx: usize) -> Result<()> {
+        self.wkb.envelope(envelope, dims, idx)
+    }
+    fn size_hint_total_coords(&mut self, count: usize) -> Result<()> {
+        self.wkb.size_hint_total_coords(count)
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.wkb.xy(x, y, idx)
+    }
+    fn coords(&mut self, coords: &[f64], dims: CoordDimensions, idx: usize) -> Result<()> {
+        self.wkb.coords(coords, dims, idx)
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.wkb.coordinate(x, y, z, m, t, tm, idx)
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.wkb.empty_point(idx)
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.wkb.point_begin(idx)
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.wkb.point_end(idx)
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.wkb.multipoint_begin(size, idx)
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.wkb.multipoint_end(idx)
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.wkb.linestring_begin(tagged, size, idx)
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.wkb.linestring_end(tagged, idx)
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.wkb.multilinestring_begin(size, idx)
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.wkb.multilinestring_end(idx)
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.wkb.polygon_begin(tagged, size, idx)
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.wkb.polygon_end(tagged, idx)
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.wkb.multipolygon_begin(size, idx)
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.wkb.multipolygon_end(idx)
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.wkb.geometrycollection_begin(size, idx)
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.wkb.geometrycollection_end(idx)
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.wkb.circularstring_begin(size, idx)
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        self.wkb.circularstring_end(idx)
+    }
+    fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.wkb.compoundcurve_begin(size, idx)
+    }
+    fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        self.wkb.compoundcurve_end(idx)
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.wkb.curvepolygon_begin(size, idx)
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.wkb.curvepolygon_end(idx)
+    }
+    fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.wkb.multicurve_begin(size, idx)
+    }
+    fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        self.wkb.multicurve_end(idx)
+    }
+    fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.wkb.multisurface_begin(size, idx)
+    }
+    fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        self.wkb.multisurface_end(idx)
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.wkb.triangle_begin(tagged, size, idx)
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.wkb.triangle_end(tagged, idx)
+    }
+    fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.wkb.polyhedralsurface_begin(size, idx)
+    }
+    fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        self.wkb.polyhedralsurface_end(idx)
+    }
+    fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.wkb.tin_begin(size, idx)
+    }
+    fn tin_end(&mut self, idx: usize) -> Result<()> {
+        self.wkb.tin_end(idx)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::GeoJson;
+    use crate::geoparquet::{process_batches, GeoParquetMetadata};
+    use crate::GeozeroDatasource;
+    use arrow_array::Array;
+
+    #[test]
+    fn write_batch() -> Result<()> {
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"name": "a", "count": 1}, "geometry": {"type": "Point", "coordinates": [1, 2]}},
+            {"type": "Feature", "properties": {"count": "many"}, "geometry": null},
+            {"type": "Feature", "properties": {"name": "c"}, "geometry": {"type": "LineString", "coordinates": [[1, 2], [3, 4]]}}
+        ]}"#;
+        let mut writer = GeoArrowWriter::new();
+        GeoJson(geojson).process(&mut writer)?;
+        assert_eq!(writer.len(), 3);
+        let batch = writer.finish()?;
+        let schema = batch.schema();
+        assert_eq!(schema.field(0).name(), "geometry");
+        assert_eq!(
+            schema.field(0).metadata()["ARROW:extension:name"],
+            "geoarrow.wkb"
+        );
+        assert!(schema.metadata().contains_key("geo"));
+        let name = batch.column_by_name("name").unwrap();
+        assert_eq!(name.data_type(), &DataType::Utf8);
+        assert!(name.is_null(1));
+        // Mixed types are written as strings
+        assert_eq!(
+            batch.column_by_name("count").unwrap().data_type(),
+            &DataType::Utf8
+        );
+        assert!(batch.column(0).is_null(1));
+
+        let geo = GeoParquetMetadata::from_json(&schema.metadata()["geo"])?;
+        let mut json = Vec::new();
+        let mut geojson_writer = crate::geojson::GeoJsonWriter::new(&mut json);
+        process_batches([Ok::<_, GeozeroError>(batch)], &geo, &mut geojson_writer)?;
+        let json = String::from_utf8(json).unwrap();
+        assert!(json.contains(r#""coordinates": [[1,2],[3,4]]"#));
+        assert!(json.contains(r#""name": "c""#));
+        Ok(())
+    }
+
+    #[test]
+    fn write_crs() -> Result<()> {
+        let crs = |crs: Option<Crs>| -> Result<Option<Crs>> {
+            let mut writer = GeoArrowWriter::new();
+            if let Some(crs) = crs {
+                writer.crs(&crs)?;
+            }
+            let batch = writer.finish()?;
+            Ok(GeoParquetMetadata::from_json(&batch.schema().metadata()["geo"])?.crs)
+        };
+        assert_eq!(crs(None)?, Some(Crs::crs84()));
+        assert_eq!(crs(Some(Crs::epsg(2056)))?, Some(Crs::epsg(2056)));
+        assert_eq!(crs(Some(Crs::from_wkt("LOCAL_CS[]")))?, None);
+        Ok(())
+    }
+}
@@ -0,0 +1,187 @@
+use crate::error::{GeozeroError, Result};
+use crate::geoparquet::{
+    process_geoparquet_batch, process_geoparquet_schema, projection_indices, Bbox,
+    GeoParquetMetadata, GeoParquetReaderOptionsExt,
+};
+use crate::range_client::RangeClient;
+use crate::FeatureProcessor;
+use bytes::Bytes;
+use futures_util::future::BoxFuture;
+use futures_util::{FutureExt, TryStreamExt};
+use parquet::arrow::async_reader::{AsyncFileReader, ParquetRecordBatchStreamBuilder};
+use parquet::arrow::ProjectionMask;
+use parquet::errors::ParquetError;
+use parquet::file::metadata::{ParquetMetaData, ParquetMetaDataReader};
+use parquet::file::FOOTER_SIZE;
+use std::ops::Range;
+use std::sync::Arc;
+
+fn parquet_err(err: GeozeroError) -> ParquetError {
+    ParquetError::External(Box::new(err))
+}
+
+/// Parquet `AsyncFileReader` reading byte ranges with a [`RangeClient`].
+pub struct RangeClientReader<C: RangeClient> {
+    client: C,
+    size: Option<u64>,
+}
+
+impl<C: RangeClient> RangeClientReader<C> {
+    pub fn new(client: C) -> Self {
+        RangeClientReader { client, size: None }
+    }
+    async fn size(&mut self) -> Result<u64> {
+        match self.size {
+            Some(size) => Ok(size),
+            None => {
+                let size = self.client.size().await?;
+                self.size = Some(size);
+                Ok(size)
+            }
+        }
+    }
+    async fn metadata(&mut self) -> Result<ParquetMetaData> {
+        let size = self.size().await?;
+        if size < FOOTER_SIZE as u64 {
+            return Err(GeozeroError::Dataset(
+                "Parquet file smaller than footer".to_string(),
+            ));
+        }
+        let footer = self
+            .client
+            .get_range(size - FOOTER_SIZE as u64..size)
+            .await?;
+        let footer: [u8; FOOTER_SIZE] = footer[..]
+            .try_into()
+            .map_err(|_| GeozeroError::Dataset("Invalid Parquet footer".to_string()))?;
+        let metadata_len = ParquetMetaDataReader::decode_footer(&footer)? as u64;
+        let footer_start = size - FOOTER_SIZE as u64;
+        if metadata_len > footer_start {
+            return Err(GeozeroError::Dataset(
+                "Invalid Parquet metadata length".to_string(),
+            ));
+        }
+        let metadata = self
+            .client
+            .get_range(footer_start - metadata_len..footer_start)
+            .await?;
+        Ok(ParquetMetaDataReader::decode_metadata(&metadata)?)
+    }
+}
+
+impl<C: RangeClient> AsyncFileReader for RangeClientReader<C> {
+    fn get_bytes(&mut self, range: Range<usize>) -> BoxFuture<'_, parquet::errors::Result<Bytes>> {
+        async move {
+            self.client
+                .get_range(range.start as u64..range.end as u64)
+                .await
+                .map_err(parquet_err)
+        }
+        .boxed()
+    }
+    fn get_metadata(&mut self) -> BoxFuture<'_, parquet::errors::Result<Arc<ParquetMetaData>>> {
+        async move { self.metadata().await.map(Arc::new).map_err(parquet_err) }.boxed()
+    }
+}
+
+/// GeoParquet reader for remote files.
+///
+/// Only the file metadata and the row groups intersecting the selected bbox are requested.
+pub struct AsyncGeoParquetReader<C: RangeClient + 'static> {
+    builder: ParquetRecordBatchStreamBuilder<RangeClientReader<C>>,
+    geo: GeoParquetMetadata,
+    bbox: Option<Bbox>,
+    columns: Option<Vec<String>>,
+}
+
+impl<C: RangeClient + 'static> AsyncGeoParquetReader<C> {
+    /// Open file and read metadata.
+    pub async fn open(client: C) -> Result<Self> {
+        let builder = ParquetRecordBatchStreamBuilder::new(RangeClientReader::new(client)).await?;
+        let geo = GeoParquetMetadata::from_parquet_metadata(builder.metadata())?;
+        Ok(AsyncGeoParquetReader {
+            builder,
+            geo,
+            bbox: None,
+            columns: None,
+        })
+    }
+    /// GeoParquet file metadata.
+    pub fn geo_metadata(&self) -> &GeoParquetMetadata {
+        &self.geo
+    }
+    /// Read only features within bbox.
+    ///
+    /// Like [`GeoParquetReader::select_bbox`](crate::geoparquet::GeoParquetReader::select_bbox),
+    /// row groups outside of bbox are not requested.
+    pub fn select_bbox(mut self, minx: f64, miny: f64, maxx: f64, maxy: f64) -> Self {
+        self.bbox = Some((minx, miny, maxx, maxy));
+        self
+    }
+    /// Read features into processor.
+    pub async fn process<P: FeatureProcessor>(self, processor: &mut P) -> Result<()> {
+        let AsyncGeoParquetReader {
+            mut builder,
+            geo,
+            bbox,
+            columns,
+        } = self;
+        if let Some(bbox) = bbox {
+            let row_groups = geo.row_groups_in_bbox(builder.metadata(), bbox);
+            builder = builder.with_row_groups(row_groups);
+        }
+        let mut schema = builder.schema().clone();
+        if let Some(columns) = &columns {
+            let indices = projection_indices(&schema, &geo, columns)?;
+            let mask = ProjectionMask::roots(builder.parquet_schema(), indices.iter().copied());
+            builder = builder.with_projection(mask);
+            schema = Arc::new(schema.project(&indices)?);
+        }
+        let mut batches = builder.build()?;
+
+        processor.dataset_begin(None)?;
+        process_geoparquet_schema(&schema, &geo, processor)?;
+        let mut feature_idx = 0;
+        while let Some(batch) = batches.try_next().await? {
+            feature_idx = process_geoparquet_batch(&batch, &geo, bbox, processor, feature_idx)?;
+        }
+        processor.dataset_end()
+    }
+}
+
+impl<C: RangeClient + 'static> GeoParquetReaderOptionsExt for AsyncGeoParquetReader<C> {
+    fn select_columns<I, S>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.columns = Some(columns.into_iter().map(Into::into).collect());
+        self
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::GeoJsonWriter;
+    use crate::geoparquet::geoparquet_reader::test::geoparquet_file;
+    use crate::range_client::test::MemoryRangeClient;
+
+    #[tokio::test]
+    async fn read_remote_bbox() -> Result<()> {
+        let client = MemoryRangeClient(geoparquet_file());
+        let reader = AsyncGeoParquetReader::open(client).await?;
+        assert_eq!(reader.geo_metadata().primary_column, "geometry");
+        let mut out = Vec::new();
+        reader
+            .select_bbox(1.5, 1.5, 5.0, 5.0)
+            .process(&mut GeoJsonWriter::new(&mut out))
+            .await?;
+        let json = String::from_utf8(out).unwrap();
+        assert!(!json.contains(r#""name": "a""#));
+        assert!(json.contains(r#""name": "b""#));
+        assert!(!json.contains(r#""name": "c""#));
+        Ok(())
+    }
+}
@@ -0,0 +1,825 @@
+use crate::error::{GeozeroError, Result};
+use crate::wkb::{process_wkb_type_geom, WkbDialect};
+use crate::{
+    ColumnDef, ColumnType, ColumnValue, ColumnValueOwned, Crs, FeatureProcessor, GeozeroDatasource,
+};
+use arrow_array::cast::AsArray;
+use arrow_array::types::{
+    Date32Type, Date64Type, Decimal128Type, Decimal256Type, Float32Type, Float64Type, Int16Type,
+    Int32Type, Int64Type, Int8Type, TimestampMicrosecondType, TimestampMillisecondType,
+    TimestampNanosecondType, TimestampSecondType, UInt16Type, UInt32Type, UInt64Type, UInt8Type,
+};
+use arrow_array::{Array, RecordBatch};
+use arrow_schema::{DataType, Schema, TimeUnit};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::arrow::ProjectionMask;
+use parquet::file::metadata::{ParquetMetaData, ParquetMetaDataReader};
+use parquet::file::reader::ChunkReader;
+use parquet::file::statistics::Statistics;
+use std::sync::Arc;
+
+/// Bounding box (minx, miny, maxx, maxy)
+pub type Bbox = (f64, f64, f64, f64);
+
+/// GeoParquet metadata stored in the `geo` key of the Parquet file metadata.
+#[derive(Clone, PartialEq, Debug)]
+pub struct GeoParquetMetadata {
+    /// Name of the primary geometry column
+    pub primary_column: String,
+    /// Encoding of the primary geometry column
+    pub encoding: String,
+    /// Bounding box covering column paths (xmin, ymin, xmax, ymax)
+    pub bbox_covering: Option<[Vec<String>; 4]>,
+    /// CRS of the primary geometry column (default: `OGC:CRS84`, `None` if undefined)
+    pub crs: Option<Crs>,
+}
+
+impl GeoParquetMetadata {
+    /// Metadata of WKB encoded geometries in `primary_column`.
+    pub fn wkb(primary_column: &str) -> Self {
+        GeoParquetMetadata {
+            primary_column: primary_column.to_string(),
+            encoding: "WKB".to_string(),
+            bbox_covering: None,
+            crs: Some(Crs::crs84()),
+        }
+    }
+
+    /// Read GeoParquet metadata from Parquet file metadata.
+    pub fn from_parquet_metadata(metadata: &ParquetMetaData) -> Result<Self> {
+        let geo = metadata
+            .file_metadata()
+            .key_value_metadata()
+            .and_then(|kv| kv.iter().find(|kv| kv.key == "geo"))
+            .and_then(|kv| kv.value.as_deref())
+            .ok_or_else(|| GeozeroError::Dataset("Missing GeoParquet metadata".to_string()))?;
+        Self::from_json(geo)
+    }
+
+    /// Read GeoParquet metadata from the JSON value of the `geo` metadata key.
+    ///
+    /// Arrow IPC files written by GeoPandas store it in the schema metadata.
+    pub fn from_json(geo: &str) -> Result<Self> {
+        let geo: serde_json::Value = serde_json::from_str(geo)
+            .map_err(|e| GeozeroError::Dataset(format!("Invalid GeoParquet metadata: {e}")))?;
+        let primary_column = geo["primary_column"]
+            .as_str()
+            .ok_or_else(|| GeozeroError::Dataset("Missing GeoParquet primary column".to_string()))?
+            .to_string();
+        let column = &geo["columns"][&primary_column];
+        let encoding = column["encoding"].as_str().unwrap_or("WKB").to_string();
+        let path = |key: &str| -> Option<Vec<String>> {
+            column["covering"]["bbox"][key]
+                .as_array()?
+                .iter()
+                .map(|part| part.as_str().map(str::to_string))
+                .collect()
+        };
+        let bbox_covering = match (path("xmin"), path("ymin"), path("xmax"), path("ymax")) {
+            (Some(xmin), Some(ymin), Some(xmax), Some(ymax)) => Some([xmin, ymin, xmax, ymax]),
+            _ => None,
+        };
+        let crs = match column.get("crs") {
+            None => Some(Crs::crs84()),
+            Some(projjson) => projjson_crs(projjson),
+        };
+        Ok(GeoParquetMetadata {
+            primary_column,
+            encoding,
+            bbox_covering,
+            crs,
+        })
+    }
+
+    /// Row groups which may contain features within `bbox`.
+    ///
+    /// Uses the statistics of the bounding box covering columns. Without covering
+    /// columns or statistics, all row groups are returned.
+    pub fn row_groups_in_bbox(&self, metadata: &ParquetMetaData, bbox: Bbox) -> Vec<usize> {
+        let all = (0..metadata.num_row_groups()).collect();
+        let Some(covering) = &self.bbox_covering else {
+            return all;
+        };
+        let schema = metadata.file_metadata().schema_descr();
+        let leaf_idx = |path: &Vec<String>| {
+            (0..schema.num_columns()).find(|i| schema.column(*i).path().parts() == path.as_slice())
+        };
+        let (Some(xmin), Some(ymin), Some(xmax), Some(ymax)) = (
+            leaf_idx(&covering[0]),
+            leaf_idx(&covering[1]),
+            leaf_idx(&covering[2]),
+            leaf_idx(&covering[3]),
+        ) else {
+            return all;
+        };
+        let (minx, miny, maxx, maxy) = bbox;
+        metadata
+            .row_groups()
+            .iter()
+            .enumerate()
+            .filter(|(_, rg)| {
+                let min = |i: usize| rg.column(i).statistics().and_then(stats_min);
+                let max = |i: usize| rg.column(i).statistics().and_then(stats_max);
+                // Row group is outside if one of its bounds is known to be outside
+                !(min(xmin).is_some_and(|v| v > maxx)
+                    || min(ymin).is_some_and(|v| v > maxy)
+                    || max(xmax).is_some_and(|v| v < minx)
+                    || max(ymax).is_some_and(|v| v < miny))
+            })
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+}
+
+fn stats_min(stats: &Statistics) -> Option<f64> {
+    match stats {
+        Statistics::Double(s) => s.min_opt().copied(),
+        Statistics::Float(s) => s.min_opt().map(|v| *v as f64),
+        _ => None,
+    }
+}
+
+fn stats_max(stats: &Statistics) -> Option<f64> {
+    match stats {
+        Statistics::Double(s) => s.max_opt().copied(),
+        Statistics::Float(s) => s.max_opt().map(|v| *v as f64),
+        _ => None,
+    }
+}
+
+/// Property type of an Arrow data type
+fn column_type(data_type: &DataType) -> Option<ColumnType> {
+    let column_type = match data_type {
+        DataType::Boolean => ColumnType::Bool,
+        DataType::Int8 => ColumnType::Byte,
+        DataType::UInt8 => ColumnType::UByte,
+        DataType::Int16 => ColumnType::Short,
+        DataType::UInt16 => ColumnType::UShort,
+        DataType::Int32 => ColumnType::Int,
+        DataType::UInt32 => ColumnType::UInt,
+        DataType::Int64 => ColumnType::Long,
+        DataType::UInt64 => ColumnType::ULong,
+        DataType::Float32 => ColumnType::Float,
+        DataType::Float64 => ColumnType::Double,
+        DataType::Utf8 | DataType::LargeUtf8 => ColumnType::String,
+        DataType::Binary | DataType::LargeBinary => ColumnType::Binary,
+        DataType::Timestamp(_, _) | DataType::Date32 | DataType::Date64 => ColumnType::DateTime,
+        DataType::Decimal128(_, _) | DataType::Decimal256(_, _) => ColumnType::Decimal,
+        DataType::List(field) | DataType::LargeList(field) | DataType::FixedSizeList(field, _) => {
+            column_type(field.data_type())?;
+            ColumnType::Array
+        }
+        _ => return None,
+    };
+    Some(column_type)
+}
+
+/// Property value of a non-null array element
+fn column_value(array: &dyn Array, row: usize) -> Option<ColumnValue<'_>> {
+    if array.is_null(row) {
+        return None;
+    }
+    let value = match array.data_type() {
+        DataType::Boolean => ColumnValue::Bool(array.as_boolean().value(row)),
+        DataType::Int8 => ColumnValue::Byte(array.as_primitive::<Int8Type>().value(row)),
+        DataType::UInt8 => ColumnValue::UByte(array.as_primitive::<UInt8Type>().value(row)),
+        DataType::Int16 => ColumnValue::Short(array.as_primitive::<Int16Type>().value(row)),
+        DataType::UInt16 => ColumnValue::UShort(array.as_primitive::<UInt16Type>().value(row)),
+        DataType::Int32 => ColumnValue::Int(array.as_primitive::<Int32Type>().value(row)),
+        DataType::UInt32 => ColumnValue::UInt(array.as_primitive::<UInt32Type>().value(row)),
+        DataType::Int64 => ColumnValue::Long(array.as_primitive::<Int64Type>().value(row)),
+        DataType::UInt64 => ColumnValue::ULong(array.as_primitive::<UInt64Type>().value(row)),
+        DataType::Float32 => ColumnValue::Float(array.as_primitive::<Float32Type>().value(row)),
+        DataType::Float64 => ColumnValue::Double(array.as_primitive::<Float64Type>().value(row)),
+        DataType::Utf8 => ColumnValue::String(array.as_string::<i32>().value(row)),
+        DataType::LargeUtf8 => ColumnValue::String(array.as_string::<i64>().value(row)),
+        DataType::Binary => ColumnValue::Binary(array.as_binary::<i32>().value(row)),
+        DataType::LargeBinary => ColumnValue::Binary(array.as_binary::<i64>().value(row)),
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// Property value of a non-null array element, which needs conversion
+fn owned_column_value(array: &dyn Array, row: usize) -> Result<Option<ColumnValueOwned>> {
+    if array.is_null(row) {
+        return Ok(None);
+    }
+    let value = match array.data_type() {
+        DataType::Timestamp(unit, tz) => {
+            let (value, per_second) = match unit {
+                TimeUnit::Second => (array.as_primitive::<TimestampSecondType>().value(row), 1),
+                TimeUnit::Millisecond => (
+                    array.as_primitive::<TimestampMillisecondType>().value(row),
+                    1_000,
+                ),
+                TimeUnit::Microsecond => (
+                    array.as_primitive::<TimestampMicrosecondType>().value(row),
+                    1_000_000,
+                ),
+                TimeUnit::Nanosecond => (
+                    array.as_primitive::<TimestampNanosecondType>().value(row),
+                    1_000_000_000,
+                ),
+            };
+            ColumnValueOwned::DateTime(format_timestamp(value, per_second, tz.is_some()))
+        }
+        DataType::Date32 => ColumnValueOwned::DateTime(format_date(
+            array.as_primitive::<Date32Type>().value(row).into(),
+        )),
+        DataType::Date64 => ColumnValueOwned::DateTime(format_date(
+            array
+                .as_primitive::<Date64Type>()
+                .value(row)
+                .div_euclid(86_400_000),
+        )),
+        DataType::Decimal128(_, _) => {
+            ColumnValueOwned::Decimal(array.as_primitive::<Decimal128Type>().value_as_string(row))
+        }
+        DataType::Decimal256(_, _) => {
+            ColumnValueOwned::Decimal(array.as_primitive::<Decimal256Type>().value_as_string(row))
+        }
+        DataType::List(_) => return array_value(array.as_list::<i32>().value(row).as_ref()),
+        DataType::LargeList(_) => return array_value(array.as_list::<i64>().value(row).as_ref()),
+        DataType::FixedSizeList(_, _) => {
+            return array_value(array.as_fixed_size_list().value(row).as_ref())
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(value))
+}
+
+/// Array property of list elements, failing on null elements
+fn array_value(values: &dyn Array) -> Result<Option<ColumnValueOwned>> {
+    let mut array = Vec::with_capacity(values.len());
+    for i in 0..values.len() {
+        if values.is_null(i) {
+            return Err(GeozeroError::Property(
+                "null list elements are not supported".to_string(),
+            ));
+        }
+        let value = match column_value(values, i) {
+            Some(value) => ColumnValueOwned::from(&value),
+            None => match owned_column_value(values, i)? {
+                Some(value) => value,
+                None => return Ok(None),
+            },
+        };
+        array.push(value);
+    }
+    Ok(Some(ColumnValueOwned::Array(array)))
+}
+
+/// ISO 8601 date of days since 1970-01-01
+fn format_date(days: i64) -> String {
+    // Civil date algorithm of Howard Hinnant
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// ISO 8601 datetime of a timestamp with `per_second` units per second
+fn format_timestamp(value: i64, per_second: i64, utc: bool) -> String {
+    let secs = value.div_euclid(per_second);
+    let fraction = value.rem_euclid(per_second);
+    let time = secs.rem_euclid(86_400);
+    let mut datetime = format!(
+        "{}T{:02}:{:02}:{:02}",
+        format_date(secs.div_euclid(86_400)),
+        time / 3600,
+        time % 3600 / 60,
+        time % 60
+    );
+    if fraction != 0 {
+        let digits = per_second.ilog10() as usize;
+        datetime.push_str(&format!(".{fraction:0digits$}"));
+    }
+    if utc {
+        datetime.push('Z');
+    }
+    datetime
+}
+
+/// Float value of a covering column element
+fn float_value(array: &dyn Array, row: usize) -> Option<f64> {
+    if array.is_null(row) {
+        return None;
+    }
+    match array.data_type() {
+        DataType::Float64 => Some(array.as_primitive::<Float64Type>().value(row)),
+        DataType::Float32 => Some(array.as_primitive::<Float32Type>().value(row) as f64),
+        _ => None,
+    }
+}
+
+/// Column of a record batch, following nested struct fields
+fn batch_column<'a>(batch: &'a RecordBatch, path: &[String]) -> Option<&'a dyn Array> {
+    let (first, rest) = path.split_first()?;
+    let mut array = batch.column_by_name(first)?.as_ref();
+    for name in rest {
+        array = array.as_struct_opt()?.column_by_name(name)?.as_ref();
+    }
+    Some(array)
+}
+
+/// CRS identified by the `id` of a PROJJSON object
+fn projjson_crs(projjson: &serde_json::Value) -> Option<Crs> {
+    let id = &projjson["id"];
+    let authority = id["authority"].as_str()?;
+    match &id["code"] {
+        serde_json::Value::String(code) => Some(Crs::new(authority, code)),
+        serde_json::Value::Number(code) => Some(Crs::new(authority, &code.to_string())),
+        _ => None,
+    }
+}
+
+/// Declare the CRS and the property columns of a GeoParquet schema.
+pub fn process_geoparquet_schema<P: FeatureProcessor>(
+    schema: &arrow_schema::Schema,
+    geo: &GeoParquetMetadata,
+    processor: &mut P,
+) -> Result<()> {
+    if let Some(crs) = &geo.crs {
+        processor.crs(crs)?;
+    }
+    let columns: Vec<ColumnDef> = schema
+        .fields()
+        .iter()
+        .filter(|field| field.name() != &geo.primary_column)
+        .filter_map(|field| {
+            Some(ColumnDef::new(
+                field.name(),
+                column_type(field.data_type())?,
+            ))
+        })
+        .collect();
+    processor.schema_begin(&columns)
+}
+
+/// Process the features of a GeoParquet record batch.
+///
+/// Features are numbered starting with `feature_idx`. Rows with a bounding box covering
+/// outside of `bbox` are skipped. Returns the index of the next feature.
+pub fn process_geoparquet_batch<P: FeatureProcessor>(
+    batch: &RecordBatch,
+    geo: &GeoParquetMetadata,
+    bbox: Option<Bbox>,
+    processor: &mut P,
+    mut feature_idx: u64,
+) -> Result<u64> {
+    if geo.encoding != "WKB" {
+        return Err(GeozeroError::Dataset(format!(
+            "Unsupported GeoParquet geometry encoding `{}`",
+            geo.encoding
+        )));
+    }
+    let schema = batch.schema();
+    let geometry_idx = schema.index_of(&geo.primary_column)?;
+    let geometry = batch.column(geometry_idx);
+    let covering = match (bbox, &geo.bbox_covering) {
+        (Some(bbox), Some(paths)) => {
+            let columns: Option<Vec<_>> = paths.iter().map(|p| batch_column(batch, p)).collect();
+            columns.map(|columns| (bbox, columns))
+        }
+        _ => None,
+    };
+    for row in 0..batch.num_rows() {
+        if let Some(((minx, miny, maxx, maxy), columns)) = &covering {
+            let v = |i: usize| float_value(columns[i], row);
+            if v(0).is_some_and(|v| v > *maxx)
+                || v(1).is_some_and(|v| v > *maxy)
+                || v(2).is_some_and(|v| v < *minx)
+                || v(3).is_some_and(|v| v < *miny)
+            {
+                continue;
+            }
+        }
+        processor.feature_begin(feature_idx)?;
+        processor.properties_begin()?;
+        let mut property_idx = 0;
+        // Index of column declared in `process_geoparquet_schema`
+        let mut column = 0;
+        for (col_idx, field) in schema.fields().iter().enumerate() {
+            if col_idx == geometry_idx || column_type(field.data_type()).is_none() {
+                continue;
+            }
+            let array = batch.column(col_idx).as_ref();
+            let owned;
+            let value = match column_value(array, row) {
+                Some(value) => Some(value),
+                None => {
+                    owned = owned_column_value(array, row)?;
+                    owned.as_ref().map(ColumnValueOwned::as_column_value)
+                }
+            };
+            // Null values are omitted
+            if let Some(value) = value {
+                if processor.column_property(property_idx, column, field.name(), &value)? {
+                    break;
+                }
+                property_idx += 1;
+            }
+            column += 1;
+        }
+        processor.properties_end()?;
+        let wkb = match geometry.data_type() {
+            _ if geometry.is_null(row) => None,
+            DataType::Binary => Some(geometry.as_binary::<i32>().value(row)),
+            DataType::LargeBinary => Some(geometry.as_binary::<i64>().value(row)),
+            data_type => {
+                return Err(GeozeroError::Dataset(format!(
+                    "Unsupported geometry column type `{data_type}`"
+                )))
+            }
+        };
+        if let Some(mut wkb) = wkb {
+            processor.geometry_begin()?;
+            process_wkb_type_geom(&mut wkb, processor, WkbDialect::Wkb)?;
+            processor.geometry_end()?;
+        }
+        processor.feature_end(feature_idx)?;
+        feature_idx += 1;
+    }
+    Ok(feature_idx)
+}
+
+/// Process a stream of record batches with WKB encoded geometries, like Arrow Flight data.
+///
+/// Each batch is processed as soon as it is returned by the iterator, without buffering
+/// or concatenating batches. Properties are declared with the schema of the first batch.
+pub fn process_batches<I, E, P>(
+    batches: I,
+    geo: &GeoParquetMetadata,
+    processor: &mut P,
+) -> Result<()>
+where
+    I: IntoIterator<Item = std::result::Result<RecordBatch, E>>,
+    GeozeroError: From<E>,
+    P: FeatureProcessor,
+{
+    processor.dataset_begin(None)?;
+    let mut feature_idx = 0;
+    for (idx, batch) in batches.into_iter().enumerate() {
+        let batch = batch?;
+        if idx == 0 {
+            process_geoparquet_schema(&batch.schema(), geo, processor)?;
+        }
+        feature_idx = process_geoparquet_batch(&batch, geo, None, processor, feature_idx)?;
+    }
+    processor.dataset_end()
+}
+
+/// Root columns to read for the selected property `columns`.
+///
+/// Includes the primary geometry column and the bounding box covering column.
+pub fn projection_indices(
+    schema: &Schema,
+    geo: &GeoParquetMetadata,
+    columns: &[String],
+) -> Result<Vec<usize>> {
+    let mut indices = vec![schema.index_of(&geo.primary_column)?];
+    if let Some(covering) = &geo.bbox_covering {
+        indices.extend(
+            covering
+                .iter()
+                .filter_map(|path| schema.index_of(path.first()?).ok()),
+        );
+    }
+    for column in columns {
+        let idx = schema
+            .index_of(column)
+            .map_err(|_| GeozeroError::Dataset(format!("Unknown column `{column}`")))?;
+        indices.push(idx);
+    }
+    indices.sort_unstable();
+    indices.dedup();
+    Ok(indices)
+}
+
+/// Options of the GeoParquet readers.
+pub trait GeoParquetReaderOptionsExt: Sized {
+    /// Read only the given property columns.
+    ///
+    /// Other columns are neither decoded nor requested from remote files.
+    /// Properties are processed in the column order of the file.
+    fn select_columns<I, S>(self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>;
+}
+
+/// GeoParquet reader.
+///
+/// Reads features with WKB encoded geometries of the primary geometry column.
+/// Timestamp and date columns are read as `DateTime`, decimal columns as `Decimal` and
+/// list columns as `Array` properties.
+pub struct GeoParquetReader<T: ChunkReader + 'static> {
+    reader: Option<T>,
+    bbox: Option<Bbox>,
+    columns: Option<Vec<String>>,
+}
+
+impl<T: ChunkReader + 'static> GeoParquetReader<T> {
+    pub fn new(reader: T) -> Self {
+        GeoParquetReader {
+            reader: Some(reader),
+            bbox: None,
+            columns: None,
+        }
+    }
+    /// Read only features within bbox.
+    ///
+    /// Row groups are skipped and rows are filtered using the bounding box covering
+    /// columns of GeoParquet 1.1. Without covering columns, all features are read.
+    pub fn select_bbox(mut self, minx: f64, miny: f64, maxx: f64, maxy: f64) -> Self {
+        self.bbox = Some((minx, miny, maxx, maxy));
+        self
+    }
+}
+
+impl<T: ChunkReader + 'static> GeoParquetReaderOptionsExt for GeoParquetReader<T> {
+    fn select_columns<I, S>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.columns = Some(columns.into_iter().map(Into::into).collect());
+        self
+    }
+}
+
+impl<T: ChunkReader + 'static> GeozeroDatasource for GeoParquetReader<T> {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        let reader = self
+            .reader
+            .take()
+            .ok_or_else(|| GeozeroError::Dataset("GeoParquet file already read".to_string()))?;
+        let mut builder = ParquetRecordBatchReaderBuilder::try_new(reader)?;
+        let geo = GeoParquetMetadata::from_parquet_metadata(builder.metadata())?;
+        if let Some(bbox) = self.bbox {
+            let row_groups = geo.row_groups_in_bbox(builder.metadata(), bbox);
+            builder = builder.with_row_groups(row_groups);
+        }
+        let mut schema = builder.schema().clone();
+        if let Some(columns) = &self.columns {
+            let indices = projection_indices(&schema, &geo, columns)?;
+            let mask = ProjectionMask::roots(builder.parquet_schema(), indices.iter().copied());
+            builder = builder.with_projection(mask);
+            schema = Arc::new(schema.project(&indices)?);
+        }
+        let batches = builder.build()?;
+
+        processor.dataset_begin(None)?;
+        process_geoparquet_schema(&schema, &geo, processor)?;
+        let mut feature_idx = 0;
+        for batch in batches {
+            feature_idx =
+                process_geoparquet_batch(&batch?, &geo, self.bbox, processor, feature_idx)?;
+        }
+        processor.dataset_end()
+    }
+    fn dataset_crs(&self) -> Option<Crs> {
+        let metadata = ParquetMetaDataReader::new()
+            .parse_and_finish(self.reader.as_ref()?)
+            .ok()?;
+        GeoParquetMetadata::from_parquet_metadata(&metadata)
+            .ok()?
+            .crs
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+pub(crate) mod test {
+    use super::*;
+    use crate::ProcessToJson;
+    use arrow_array::{
+        ArrayRef, BinaryArray, Date32Array, Decimal128Array, Float64Array, ListArray, StringArray,
+        StructArray, TimestampMillisecondArray,
+    };
+    use arrow_schema::Field;
+    use bytes::Bytes;
+    use parquet::arrow::ArrowWriter;
+    use parquet::file::metadata::KeyValue;
+    use parquet::file::properties::WriterProperties;
+
+    fn wkb_point(x: f64, y: f64) -> Vec<u8> {
+        let mut wkb = vec![1, 1, 0, 0, 0];
+        wkb.extend_from_slice(&x.to_le_bytes());
+        wkb.extend_from_slice(&y.to_le_bytes());
+        wkb
+    }
+
+    /// GeoParquet file with 4 points in 2 row groups
+    pub(crate) fn geoparquet_file() -> Bytes {
+        let coords = [(1.0, 1.0), (2.0, 2.0), (10.0, 10.0), (11.0, 11.0)];
+        let names: ArrayRef = Arc::new(StringArray::from(vec!["a", "b", "c", "d"]));
+        let wkb: Vec<Vec<u8>> = coords.iter().map(|(x, y)| wkb_point(*x, *y)).collect();
+        let geometry: ArrayRef = Arc::new(BinaryArray::from_iter_values(wkb));
+        let xs: ArrayRef = Arc::new(Float64Array::from_iter_values(coords.iter().map(|c| c.0)));
+        let ys: ArrayRef = Arc::new(Float64Array::from_iter_values(coords.iter().map(|c| c.1)));
+        let bbox: ArrayRef = Arc::new(StructArray::from(vec![
+            (
+                Arc::new(Field::new("xmin", DataType::Float64, false)),
+                xs.clone(),
+            ),
+            (
+                Arc::new(Field::new("ymin", DataType::Float64, false)),
+                ys.clone(),
+            ),
+            (Arc::new(Field::new("xmax", DataType::Float64, false)), xs),
+            (Arc::new(Field::new("ymax", DataType::Float64, false)), ys),
+        ]));
+        let batch = RecordBatch::try_from_iter(vec![
+            ("name", names),
+            ("geometry", geometry),
+            ("bbox", bbox),
+        ])
+        .unwrap();
+        let geo = r#"{"version": "1.1.0", "primary_column": "geometry", "columns": {"geometry": {"encoding": "WKB", "geometry_types": ["Point"], "covering": {"bbox": {"xmin": ["bbox", "xmin"], "ymin": ["bbox", "ymin"], "xmax": ["bbox", "xmax"], "ymax": ["bbox", "ymax"]}}}}}"#;
+        write_parquet(&batch, geo, 2)
+    }
+
+    fn write_parquet(batch: &RecordBatch, geo: &str, row_group_size: usize) -> Bytes {
+        let props = WriterProperties::builder()
+            .set_max_row_group_size(row_group_size)
+            .set_key_value_metadata(Some(vec![KeyValue::new(
+                "geo".to_string(),
+                geo.to_string(),
+            )]))
+            .build();
+        let mut out = Vec::new();
+        let mut writer = ArrowWriter::try_new(&mut out, batch.schema(), Some(props)).unwrap();
+        writer.write(batch).unwrap();
+        writer.close().unwrap();
+        Bytes::from(out)
+    }
+
+    #[test]
+    fn read_features() -> Result<()> {
+        let mut reader = GeoParquetReader::new(geoparquet_file());
+        let json = reader.to_json()?;
+        assert!(json.contains("[1,1]"));
+        assert!(json.contains(r#""name": "d""#));
+        Ok(())
+    }
+
+    #[test]
+    fn select_bbox() -> Result<()> {
+        let file = geoparquet_file();
+        let builder = ParquetRecordBatchReaderBuilder::try_new(file.clone())?;
+        let geo = GeoParquetMetadata::from_parquet_metadata(builder.metadata())?;
+        assert_eq!(geo.primary_column, "geometry");
+        assert_eq!(
+            geo.row_groups_in_bbox(builder.metadata(), (0.0, 0.0, 5.0, 5.0)),
+            vec![0]
+        );
+
+        let mut reader = GeoParquetReader::new(file).select_bbox(1.5, 1.5, 5.0, 5.0);
+        let json = reader.to_json()?;
+        assert!(!json.contains(r#""name": "a""#));
+        assert!(json.contains(r#""name": "b""#));
+        assert!(!json.contains(r#""name": "c""#));
+        Ok(())
+    }
+
+    #[test]
+    fn crs() -> Result<()> {
+        let reader = GeoParquetReader::new(geoparquet_file());
+        assert_eq!(reader.dataset_crs(), Some(Crs::crs84()));
+
+        let geo = |crs: &str| {
+            let json = format!(
+                r#"{{"primary_column": "geometry", "columns": {{"geometry": {{"encoding": "WKB", "crs": {crs}}}}}}}"#
+            );
+            GeoParquetMetadata::from_json(&json).map(|geo| geo.crs)
+        };
+        assert_eq!(
+            geo(r#"{"type": "ProjectedCRS", "id": {"authority": "EPSG", "code": 2056}}"#)?,
+            Some(Crs::epsg(2056))
+        );
+        assert_eq!(geo("null")?, None);
+
+        let mut spool = crate::FeatureSpool::new();
+        GeoParquetReader::new(geoparquet_file()).process(&mut spool)?;
+        assert_eq!(spool.crs(), Some(&Crs::crs84()));
+        Ok(())
+    }
+
+    #[test]
+    fn select_columns() -> Result<()> {
+        let mut reader = GeoParquetReader::new(geoparquet_file()).select_columns(["bbox"]);
+        let json = reader.to_json()?;
+        assert!(json.contains("[1,1]"));
+        assert!(!json.contains(r#""name""#));
+
+        let mut reader = GeoParquetReader::new(geoparquet_file()).select_columns(["unknown"]);
+        assert!(reader.to_json().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn stream_batches() -> Result<()> {
+        let batch = |names: Vec<&str>, coords: &[(f64, f64)]| {
+            let names: ArrayRef = Arc::new(StringArray::from(names));
+            let wkb = coords.iter().map(|(x, y)| wkb_point(*x, *y));
+            let geometry: ArrayRef = Arc::new(BinaryArray::from_iter_values(wkb));
+            RecordBatch::try_from_iter(vec![("name", names), ("geom", geometry)]).unwrap()
+        };
+        let batches = vec![
+            Ok(batch(vec!["a", "b"], &[(1.0, 1.0), (2.0, 2.0)])),
+            Ok(batch(vec!["c"], &[(3.0, 3.0)])),
+        ];
+        let mut out = Vec::new();
+        let mut writer = crate::geojson::GeoJsonWriter::new(&mut out);
+        process_batches::<_, arrow_schema::ArrowError, _>(
+            batches,
+            &GeoParquetMetadata::wkb("geom"),
+            &mut writer,
+        )?;
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        let features = json["features"].as_array().unwrap();
+        assert_eq!(features.len(), 3);
+        assert_eq!(features[2]["properties"]["name"], "c");
+        assert_eq!(
+            features[2]["geometry"]["coordinates"],
+            serde_json::json!([3, 3])
+        );
+
+        let batches = vec![
+            Ok(batch(vec!["a"], &[(1.0, 1.0)])),
+            Err(arrow_schema::ArrowError::ComputeError(
+                "connection lost".to_string(),
+            )),
+        ];
+        let mut sink = crate::ProcessorSink;
+        assert!(process_batches(batches, &GeoParquetMetadata::wkb("geom"), &mut sink).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn arrow_types() -> Result<()> {
+        let geometry: ArrayRef = Arc::new(BinaryArray::from_iter_values([
+            wkb_point(1.0, 1.0),
+            wkb_point(2.0, 2.0),
+        ]));
+        let time: ArrayRef = Arc::new(
+            TimestampMillisecondArray::from(vec![1_700_000_000_123, -1]).with_timezone("UTC"),
+        );
+        let date: ArrayRef = Arc::new(Date32Array::from(vec![19_723, -719_162]));
+        let amount: ArrayRef = Arc::new(
+            Decimal128Array::from(vec![Some(1250), None])
+                .with_precision_and_scale(10, 2)
+                .unwrap(),
+        );
+        let values: ArrayRef = Arc::new(ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+            Some(vec![Some(1), Some(3)]),
+            Some(vec![]),
+        ]));
+        let batch = RecordBatch::try_from_iter(vec![
+            ("geometry", geometry),
+            ("time", time),
+            ("date", date),
+            ("amount", amount),
+            ("values", values),
+        ])
+        .unwrap();
+        let geo = r#"{"version": "1.1.0", "primary_column": "geometry", "columns": {"geometry": {"encoding": "WKB", "geometry_types": ["Point"]}}}"#;
+        let mut reader = GeoParquetReader::new(write_parquet(&batch, geo, 10));
+        let json: serde_json::Value = serde_json::from_str(&reader.to_json()?).unwrap();
+        assert_eq!(
+            json["features"][0]["properties"],
+            serde_json::json!({
+                "time": "2023-11-14T22:13:20.123Z",
+                "date": "2024-01-01",
+                "amount": 12.5,
+                "values": [1, 3]
+            })
+        );
+        assert_eq!(
+            json["features"][1]["properties"],
+            serde_json::json!({"time": "1969-12-31T23:59:59.999Z", "date": "0001-01-01", "values": []})
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn null_list_element() {
+        let geometry: ArrayRef = Arc::new(BinaryArray::from_iter_values([wkb_point(1.0, 1.0)]));
+        let values: ArrayRef = Arc::new(ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+            Some(vec![Some(1), None, Some(3)]),
+        ]));
+        let batch =
+            RecordBatch::try_from_iter(vec![("geometry", geometry), ("values", values)]).unwrap();
+        let geo = r#"{"version": "1.1.0", "primary_column": "geometry", "columns": {"geometry": {"encoding": "WKB", "geometry_types": ["Point"]}}}"#;
+        let mut reader = GeoParquetReader::new(write_parquet(&batch, geo, 10));
+        assert!(matches!(reader.to_json(), Err(GeozeroError::Property(_))));
+    }
+}
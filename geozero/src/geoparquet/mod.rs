@@ -0,0 +1,10 @@
+//! GeoParquet reader and Arrow writer.
+mod arrow_writer;
+#[cfg(feature = "with-range-client")]
+mod async_reader;
+mod geoparquet_reader;
+
+pub use arrow_writer::GeoArrowWriter;
+#[cfg(feature = "with-range-client")]
+pub use async_reader::*;
+pub use geoparquet_reader::*;
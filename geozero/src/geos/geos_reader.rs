@@ -17,23 +17,6 @@ impl GeozeroGeometry for geos::Geometry {
     }
 }
 
-impl From<geos::Error> for GeozeroError {
-    fn from(error: geos::Error) -> Self {
-        use geos::Error::*;
-        match error {
-            InvalidGeometry(e)
-            | ImpossibleOperation(e)
-            | GeosError(e)
-            | NoConstructionFromNullPtr(e)
-            | ConversionError(e)
-            | GenericError(e)
-            | VoronoiError(e)
-            | NormalizeError(e) => GeozeroError::Geometry(e),
-            GeosFunctionError(_, _) => GeozeroError::GeometryFormat,
-        }
-    }
-}
-
 /// Process GEOS geometry.
 pub fn process_geom<P: GeomProcessor>(ggeom: &GGeometry, processor: &mut P) -> Result<()> {
     process_geom_n(ggeom, 0, processor)
@@ -1,171 +1,204 @@
 use crate::error::{GeozeroError, Result};
-use crate::{FeatureProcessor, GeomProcessor, PropertyProcessor};
-use geos::{CoordDimensions, CoordSeq, GResult, Geometry as GGeometry};
+use crate::{CoordDimensions, FeatureProcessor, GeomProcessor, PropertyProcessor};
+use geos::{
+    CoordDimensions as GCoordDimensions, CoordSeq, Geom, Geometry as GGeometry, PreparedGeometry,
+};
 
 /// Generator for GEOS geometry type.
+///
+/// Coordinates are written directly into GEOS coordinate sequences.
 pub struct GeosWriter {
     pub(crate) geom: GGeometry,
+    dims: CoordDimensions,
     srid: Option<i32>,
-    // CoordSeq for Points, Lines and Rings
-    cs: Vec<CoordSeq>,
-    // Polygons or MultiPolygons
-    polys: Vec<GGeometry>,
+    /// CoordSeq of in-progress Point, LineString or ring
+    cs: Option<CoordSeq>,
+    /// Rings of in-progress Polygon
+    rings: Option<Vec<GGeometry>>,
+    /// Stack of in-progress (potentially nested) multi geometries and GeometryCollections
+    collections: Vec<Vec<GGeometry>>,
 }
 
 impl GeosWriter {
     pub fn new() -> Self {
         Self::default()
     }
-    fn add_coord_seq(&mut self, len: usize) -> Result<()> {
-        self.cs
-            .push(CoordSeq::new(len as u32, CoordDimensions::TwoD)?);
-        Ok(())
+    /// Writer creating geometries with Z coordinates, if `dims.z` is set
+    pub fn with_dims(dims: CoordDimensions) -> Self {
+        GeosWriter {
+            dims,
+            ..Default::default()
+        }
     }
     pub fn geometry(&self) -> &GGeometry {
         &self.geom
     }
+    /// Prepared geometry for efficient repeated predicate evaluation
+    pub fn prepared_geometry(&self) -> Result<PreparedGeometry<'_>> {
+        Ok(self.geom.to_prepared_geom()?)
+    }
+    fn coord_seq(&self, len: usize) -> Result<CoordSeq> {
+        let dims = if self.dims.z {
+            GCoordDimensions::ThreeD
+        } else {
+            GCoordDimensions::TwoD
+        };
+        Ok(CoordSeq::new(len as u32, dims)?)
+    }
+    fn take_coord_seq(&mut self) -> Result<CoordSeq> {
+        self.cs
+            .take()
+            .ok_or_else(|| GeozeroError::Geometry("CoordSeq missing".to_string()))
+    }
+    fn take_collection(&mut self) -> Result<Vec<GGeometry>> {
+        self.collections
+            .pop()
+            .ok_or_else(|| GeozeroError::Geometry("Collection missing".to_string()))
+    }
+    fn set_coord(&mut self, x: f64, y: f64, z: Option<f64>, idx: usize) -> Result<()> {
+        if let Some(cs) = &mut self.cs {
+            cs.set_x(idx, x)?;
+            cs.set_y(idx, y)?;
+            if self.dims.z {
+                cs.set_z(idx, z.unwrap_or(f64::NAN))?;
+            }
+        } else {
+            // MultiPoint member
+            let mut cs = self.coord_seq(1)?;
+            cs.set_x(0, x)?;
+            cs.set_y(0, y)?;
+            if self.dims.z {
+                cs.set_z(0, z.unwrap_or(f64::NAN))?;
+            }
+            self.finish_geometry(GGeometry::create_point(cs)?)?;
+        }
+        Ok(())
+    }
+    /// Add geometry to the current collection or set it as result
+    fn finish_geometry(&mut self, mut geom: GGeometry) -> Result<()> {
+        if let Some(collection) = self.collections.last_mut() {
+            collection.push(geom);
+        } else {
+            if let Some(srid) = self.srid {
+                geom.set_srid(srid as usize);
+            }
+            self.geom = geom;
+        }
+        Ok(())
+    }
 }
 
 impl Default for GeosWriter {
     fn default() -> Self {
         GeosWriter {
             geom: GGeometry::create_empty_point().unwrap(),
+            dims: CoordDimensions::xy(),
             srid: None,
-            cs: Vec::new(),
-            polys: Vec::new(),
+            cs: None,
+            rings: None,
+            collections: Vec::new(),
         }
     }
 }
 
 impl GeomProcessor for GeosWriter {
+    fn dimensions(&self) -> CoordDimensions {
+        self.dims
+    }
     fn srid(&mut self, srid: Option<i32>) -> Result<()> {
         self.srid = srid;
         Ok(())
     }
     fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
-        if self.cs.is_empty() {
-            return Err(GeozeroError::Geometry("CoordSeq missing".to_string()));
-        }
-        let n = self.cs.len() - 1;
-        let coord_seq = &mut self.cs[n];
-        coord_seq.set_x(idx, x)?;
-        coord_seq.set_y(idx, y)?;
-        Ok(())
+        self.set_coord(x, y, None, idx)
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        _m: Option<f64>,
+        _t: Option<f64>,
+        _tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.set_coord(x, y, z, idx)
+    }
+    fn empty_point(&mut self, _idx: usize) -> Result<()> {
+        self.finish_geometry(GGeometry::create_empty_point()?)
     }
     fn point_begin(&mut self, _idx: usize) -> Result<()> {
-        self.cs = Vec::with_capacity(1);
-        self.add_coord_seq(1)?;
+        self.cs = Some(self.coord_seq(1)?);
         Ok(())
     }
     fn point_end(&mut self, _idx: usize) -> Result<()> {
-        let cs = self
-            .cs
-            .pop()
-            .ok_or_else(|| GeozeroError::Geometry("CoordSeq missing".to_string()))?;
-        self.geom = GGeometry::create_point(cs)?;
-        if let Some(srid) = self.srid {
-            self.geom.set_srid(srid as usize);
-        }
-        Ok(())
+        let cs = self.take_coord_seq()?;
+        self.finish_geometry(GGeometry::create_point(cs)?)
     }
     fn multipoint_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
-        self.cs = Vec::with_capacity(1);
-        self.add_coord_seq(size)
+        self.collections.push(Vec::with_capacity(size));
+        Ok(())
     }
     fn multipoint_end(&mut self, _idx: usize) -> Result<()> {
-        // Create points from CoordSeq elements
-        let cs = self
-            .cs
-            .pop()
-            .ok_or_else(|| GeozeroError::Geometry("CoordSeq missing".to_string()))?;
-        let size = cs.size()?;
-        let ggpts = (0..size)
-            .map(|i| {
-                GGeometry::create_point(
-                    CoordSeq::new_from_vec(&[&[cs.get_x(i).unwrap(), cs.get_y(i).unwrap()]])
-                        .unwrap(),
-                )
-            })
-            .collect::<GResult<Vec<GGeometry>>>()?;
-        self.geom = GGeometry::create_multipoint(ggpts)?;
-        if let Some(srid) = self.srid {
-            self.geom.set_srid(srid as usize);
-        }
+        let points = self.take_collection()?;
+        self.finish_geometry(GGeometry::create_multipoint(points)?)
+    }
+    fn linestring_begin(&mut self, _tagged: bool, size: usize, _idx: usize) -> Result<()> {
+        self.cs = Some(self.coord_seq(size)?);
         Ok(())
     }
-    fn linestring_begin(&mut self, tagged: bool, size: usize, _idx: usize) -> Result<()> {
-        if tagged {
-            self.cs = Vec::with_capacity(1);
-        } // else allocated in multilinestring_begin or polygon_begin
-        self.add_coord_seq(size)
-    }
-    fn linestring_end(&mut self, tagged: bool, _idx: usize) -> Result<()> {
-        if tagged {
-            let cs = self
-                .cs
-                .pop()
-                .ok_or_else(|| GeozeroError::Geometry("CoordSeq missing".to_string()))?;
-            self.geom = GGeometry::create_line_string(cs)?;
-            if let Some(srid) = self.srid {
-                self.geom.set_srid(srid as usize);
-            }
+    fn linestring_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        let cs = self.take_coord_seq()?;
+        if let Some(rings) = &mut self.rings {
+            // TODO: We need to ensure that rings of polygons are closed
+            // to create valid GEOS LinearRings
+            rings.push(GGeometry::create_linear_ring(cs)?);
+            Ok(())
+        } else {
+            self.finish_geometry(GGeometry::create_line_string(cs)?)
         }
-        Ok(())
     }
     fn multilinestring_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
-        self.cs = Vec::with_capacity(size);
+        self.collections.push(Vec::with_capacity(size));
         Ok(())
     }
     fn multilinestring_end(&mut self, _idx: usize) -> Result<()> {
-        let gglines = self
-            .cs
-            .drain(..)
-            .map(GGeometry::create_line_string)
-            .collect::<GResult<Vec<GGeometry>>>()?;
-        self.geom = GGeometry::create_multiline_string(gglines)?;
-        if let Some(srid) = self.srid {
-            self.geom.set_srid(srid as usize);
-        }
-        Ok(())
+        let lines = self.take_collection()?;
+        self.finish_geometry(GGeometry::create_multiline_string(lines)?)
     }
     fn polygon_begin(&mut self, _tagged: bool, size: usize, _idx: usize) -> Result<()> {
-        self.cs = Vec::with_capacity(size);
+        self.rings = Some(Vec::with_capacity(size));
         Ok(())
     }
-    fn polygon_end(&mut self, tagged: bool, _idx: usize) -> Result<()> {
-        if self.cs.is_empty() {
-            return Err(GeozeroError::Geometry("CoordSeq missing".to_string()));
-        }
-        // TODO: We need to ensure that rings of polygons are closed
-        // to create valid GEOS LinearRings
-        let exterior_ring = GGeometry::create_linear_ring(self.cs.remove(0))?;
-        let interiors = self
-            .cs
-            .drain(..)
-            .map(GGeometry::create_linear_ring)
-            .collect::<GResult<Vec<GGeometry>>>()?;
-        let gpoly = GGeometry::create_polygon(exterior_ring, interiors)?;
-        if tagged {
-            self.geom = gpoly;
-            if let Some(srid) = self.srid {
-                self.geom.set_srid(srid as usize);
-            }
+    fn polygon_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        let mut rings = self
+            .rings
+            .take()
+            .ok_or_else(|| GeozeroError::Geometry("Rings missing".to_string()))?;
+        let gpoly = if rings.is_empty() {
+            GGeometry::create_empty_polygon()?
         } else {
-            self.polys.push(gpoly);
-        }
-        Ok(())
+            let exterior_ring = rings.remove(0);
+            GGeometry::create_polygon(exterior_ring, rings)?
+        };
+        self.finish_geometry(gpoly)
     }
     fn multipolygon_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
-        self.polys = Vec::with_capacity(size);
+        self.collections.push(Vec::with_capacity(size));
         Ok(())
     }
     fn multipolygon_end(&mut self, _idx: usize) -> Result<()> {
-        self.geom = GGeometry::create_multipolygon(std::mem::take(&mut self.polys))?;
-        if let Some(srid) = self.srid {
-            self.geom.set_srid(srid as usize);
-        }
+        let polygons = self.take_collection()?;
+        self.finish_geometry(GGeometry::create_multipolygon(polygons)?)
+    }
+    fn geometrycollection_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.collections.push(Vec::with_capacity(size));
         Ok(())
     }
+    fn geometrycollection_end(&mut self, _idx: usize) -> Result<()> {
+        let geoms = self.take_collection()?;
+        self.finish_geometry(GGeometry::create_geometry_collection(geoms)?)
+    }
 }
 
 impl PropertyProcessor for GeosWriter {}
@@ -205,13 +238,16 @@ mod test {
         assert_eq!(geos.to_wkt().unwrap(), wkt);
     }
 
-    // #[test]
-    // fn line_geom_3d() {
-    //     let geojson = GeoJson(r#"{"type": "LineString", "coordinates": [[1,1,10], [2,2,20]]}"#);
-    //     let wkt = "LINESTRING (1 1 10, 2 2 20)";
-    //     let geos = geojson.to_geos().unwrap();
-    //     assert_eq!(geos.to_wkt().unwrap(), wkt);
-    // }
+    #[test]
+    fn line_geom_3d() -> Result<()> {
+        let geojson = GeoJson(r#"{"type": "LineString", "coordinates": [[1,1,10], [2,2,20]]}"#);
+        let mut geos = GeosWriter::with_dims(crate::CoordDimensions::xyz());
+        geojson.process_geom(&mut geos)?;
+        let cs = geos.geometry().get_coord_seq()?;
+        assert_eq!(cs.size()?, 2);
+        assert_eq!(cs.get_z(1)?, 20.0);
+        Ok(())
+    }
 
     #[test]
     fn multiline_geom() {
@@ -252,13 +288,28 @@ mod test {
         assert_eq!(geos.to_wkt().unwrap(), wkt);
     }
 
-    // #[test]
-    // fn geometry_collection_geom() {
-    //     let geojson = GeoJson(r#"{"type": "Point", "coordinates": [1, 1]}"#);
-    //     let wkt = "GEOMETRYCOLLECTION(POINT(1 1), LINESTRING(1 1, 2 2))";
-    //     let geos = geojson.to_geos().unwrap();
-    //     assert_eq!(geos.to_wkt().unwrap(), wkt);
-    // }
+    #[test]
+    fn geometry_collection_geom() {
+        let geojson = GeoJson(
+            r#"{"type": "GeometryCollection", "geometries": [{"type": "Point", "coordinates": [1, 1]}, {"type": "LineString", "coordinates": [[1, 1], [2, 2]]}]}"#,
+        );
+        let wkt = "GEOMETRYCOLLECTION (POINT (1 1), LINESTRING (1 1, 2 2))";
+        let geos = geojson.to_geos().unwrap();
+        assert_eq!(geos.to_wkt().unwrap(), wkt);
+    }
+
+    #[test]
+    fn prepared_geom() -> Result<()> {
+        let geojson = GeoJson(
+            r#"{"type": "Polygon", "coordinates": [[[0, 0], [0, 3], [3, 3], [3, 0], [0, 0]]]}"#,
+        );
+        let mut geos = GeosWriter::new();
+        geojson.process_geom(&mut geos)?;
+        let prepared = geos.prepared_geometry()?;
+        let point = GGeometry::new_from_wkt("POINT (1 1)")?;
+        assert!(prepared.contains(&point)?);
+        Ok(())
+    }
 
     #[test]
     #[cfg(feature = "with-geo")]
@@ -1,9 +1,11 @@
 //! GEOS conversions.
 pub(crate) mod geos_reader;
 pub(crate) mod geos_writer;
+mod strtree;
 
 pub use geos_reader::*;
 pub use geos_writer::*;
+pub use strtree::*;
 
 pub(crate) mod conversion {
     use crate::error::Result;
@@ -14,6 +16,13 @@ pub(crate) mod conversion {
     pub trait ToGeos {
         /// Convert to GEOS geometry.
         fn to_geos(&self) -> Result<geos::Geometry>;
+        /// Convert to GEOS prepared geometry and pass it to `f`.
+        ///
+        /// The prepared geometry borrows the converted geometry, so it is only available
+        /// within `f`.
+        fn to_geos_prepared<R, F>(&self, f: F) -> Result<R>
+        where
+            F: FnOnce(&geos::PreparedGeometry) -> R;
     }
 
     impl<T: GeozeroGeometry> ToGeos for T {
@@ -22,6 +31,14 @@ pub(crate) mod conversion {
             self.process_geom(&mut geos)?;
             Ok(geos.geom)
         }
+        fn to_geos_prepared<R, F>(&self, f: F) -> Result<R>
+        where
+            F: FnOnce(&geos::PreparedGeometry) -> R,
+        {
+            let mut geos = GeosWriter::new();
+            self.process_geom(&mut geos)?;
+            Ok(f(&geos.prepared_geometry()?))
+        }
     }
 }
 
@@ -0,0 +1,144 @@
+use crate::error::Result;
+use crate::geos::GeosWriter;
+use crate::wrap::{delegate_coordinates, delegate_geometry_types};
+use crate::{CoordDimensions, FeatureProcessor, GeomProcessor, PropertyProcessor};
+use geos::{Geom, STRtree, SpatialIndex};
+
+/// Node capacity of the STRtree, the GEOS default
+const NODE_CAPACITY: usize = 10;
+
+/// Bulk loader of feature geometries into a GEOS [STRtree].
+///
+/// Each feature geometry is converted and inserted with its feature index as item, without
+/// keeping the converted geometries. Features without geometry or with empty geometries
+/// are not inserted.
+///
+/// # Usage example
+///
+/// ```
+/// # #[cfg(feature = "with-geojson")]
+/// # {
+/// use geos::SpatialIndex;
+/// use geozero::geojson::GeoJson;
+/// use geozero::geos::GeosSTRtreeBuilder;
+/// use geozero::{GeozeroDatasource, ToGeos};
+///
+/// let mut geojson = GeoJson(r#"{"type": "FeatureCollection", "features": [{"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [1, 1]}}]}"#);
+/// let mut builder = GeosSTRtreeBuilder::new().unwrap();
+/// geojson.process(&mut builder).unwrap();
+/// let tree = builder.finish();
+///
+/// let search = GeoJson(r#"{"type": "Polygon", "coordinates": [[[0, 0], [2, 0], [2, 2], [0, 0]]]}"#);
+/// let mut found = Vec::new();
+/// tree.query(&search.to_geos().unwrap(), |idx| found.push(*idx));
+/// assert_eq!(found, vec![0]);
+/// # }
+/// ```
+pub struct GeosSTRtreeBuilder {
+    tree: STRtree<u64>,
+    writer: GeosWriter,
+    /// Index of the current feature
+    idx: u64,
+    len: usize,
+}
+
+impl GeosSTRtreeBuilder {
+    pub fn new() -> Result<Self> {
+        Ok(GeosSTRtreeBuilder {
+            tree: STRtree::with_capacity(NODE_CAPACITY)?,
+            writer: GeosWriter::new(),
+            idx: 0,
+            len: 0,
+        })
+    }
+    /// Number of inserted geometries
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// STRtree with feature indices as items
+    pub fn finish(self) -> STRtree<u64> {
+        self.tree
+    }
+}
+
+impl FeatureProcessor for GeosSTRtreeBuilder {
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        self.idx = idx;
+        Ok(())
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        self.writer = GeosWriter::new();
+        Ok(())
+    }
+    fn geometry_end(&mut self) -> Result<()> {
+        let geom = self.writer.geometry();
+        if !geom.is_empty()? {
+            self.tree.insert(geom, self.idx);
+            self.len += 1;
+        }
+        Ok(())
+    }
+}
+
+impl PropertyProcessor for GeosSTRtreeBuilder {}
+
+impl GeomProcessor for GeosSTRtreeBuilder {
+    fn dimensions(&self) -> CoordDimensions {
+        self.writer.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.writer.multi_dim()
+    }
+    delegate_coordinates!(writer);
+    delegate_geometry_types!(writer);
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::GeoJson;
+    use crate::{GeozeroDatasource, ToGeos};
+
+    #[test]
+    fn spatial_join() -> Result<()> {
+        let mut geojson = GeoJson(
+            r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [1, 2]}},
+            {"type": "Feature", "properties": {}, "geometry": null},
+            {"type": "Feature", "properties": {}, "geometry": {"type": "LineString", "coordinates": [[5, 5], [6, 8]]}},
+            {"type": "Feature", "properties": {}, "geometry": {"type": "Polygon", "coordinates": [[[0, 0], [3, 0], [3, 3], [0, 0]]]}}
+        ]}"#,
+        );
+        let mut builder = GeosSTRtreeBuilder::new()?;
+        geojson.process(&mut builder)?;
+        assert_eq!(builder.len(), 3);
+        let tree = builder.finish();
+
+        let search = GeoJson(r#"{"type": "Point", "coordinates": [2, 1]}"#);
+        let mut found = Vec::new();
+        tree.query(&search.to_geos()?, |idx| found.push(*idx));
+        found.sort();
+        assert_eq!(found, vec![3]);
+
+        // Exact predicate on candidates with prepared geometry
+        let search =
+            GeoJson(r#"{"type": "Polygon", "coordinates": [[[0, 0], [6, 0], [6, 6], [0, 0]]]}"#);
+        let mut candidates = Vec::new();
+        tree.query(&search.to_geos()?, |idx| candidates.push(*idx));
+        candidates.sort();
+        assert_eq!(candidates, vec![0, 2, 3]);
+        // Within the envelope, but outside of the triangle
+        let point = GeoJson(r#"{"type": "Point", "coordinates": [1, 2]}"#).to_geos()?;
+        let line =
+            GeoJson(r#"{"type": "LineString", "coordinates": [[5, 5], [6, 8]]}"#).to_geos()?;
+        let hits = search.to_geos_prepared(|prepared| {
+            (prepared.intersects(&point), prepared.intersects(&line))
+        })?;
+        assert_eq!(hits, (Ok(false), Ok(true)));
+        Ok(())
+    }
+}
@@ -63,9 +63,7 @@ impl<'q, T: GeozeroGeometry + Sized> Encode<'q, Sqlite> for wkb::Encode<T> {
             self.0.srid(),
             Vec::new(),
         );
-        self.0
-            .process_geom(&mut writer)
-            .expect("Failed to encode Geometry");
+        self.0.process_geom(&mut writer)?;
         args.push(SqliteArgumentValue::Blob(Cow::Owned(wkb_out)));
         Ok(IsNull::No)
     }
@@ -138,8 +136,7 @@ macro_rules! impl_sqlx_gpkg_encode {
                     self.srid(),
                     Vec::new(),
                 );
-                self.process_geom(&mut writer)
-                    .expect("Failed to encode Geometry");
+                self.process_geom(&mut writer)?;
                 args.push(::sqlx::sqlite::SqliteArgumentValue::Blob(
                     ::std::borrow::Cow::Owned(wkb_out),
                 ));
@@ -0,0 +1,318 @@
+use crate::error::{GeozeroError, Result};
+use crate::gpkg::gpkg_writer::quote_ident;
+use crate::wkb::GpkgWkb;
+use crate::{
+    ColumnDef, ColumnType, ColumnValue, Crs, FeatureId, FeatureProcessor, GeozeroDatasource,
+    GeozeroGeometry,
+};
+use futures_util::TryStreamExt;
+use sqlx::query::Query;
+use sqlx::sqlite::{Sqlite, SqliteArguments, SqliteConnection, SqliteRow};
+use sqlx::{Column, Row, TypeInfo, ValueRef};
+
+/// GeoPackage feature table reader.
+///
+/// Features are processed with [read()](GpkgReader::read) while fetching them from the database.
+/// [fetch()](GpkgReader::fetch) loads all selected features into [GpkgFeatures], which can be
+/// processed as [GeozeroDatasource].
+///
+/// # Usage example
+///
+/// ```
+/// use geozero::geojson::GeoJsonWriter;
+/// use geozero::gpkg::GpkgReader;
+/// use sqlx::sqlite::SqliteConnection;
+/// use sqlx::Connection;
+///
+/// # async fn read_gpkg() -> geozero::error::Result<()> {
+/// let mut conn = SqliteConnection::connect("sqlite://countries.gpkg").await?;
+/// let mut reader = GpkgReader::new("countries");
+/// reader.set_bbox(5.9, 45.8, 10.5, 47.8);
+/// let mut json: Vec<u8> = Vec::new();
+/// reader.read(&mut conn, &mut GeoJsonWriter::new(&mut json)).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct GpkgReader {
+    table_name: String,
+    geometry_column: Option<String>,
+    /// [minx, miny, maxx, maxy]
+    bbox: Option<[f64; 4]>,
+    /// Primary key and geometry column are not processed as properties
+    pk_column: Option<String>,
+    /// Property columns
+    columns: Vec<ColumnDef>,
+    /// CRS of geometry column from `gpkg_spatial_ref_sys`
+    crs: Option<Crs>,
+}
+
+impl GpkgReader {
+    pub fn new(table_name: &str) -> Self {
+        GpkgReader {
+            table_name: table_name.to_string(),
+            geometry_column: None,
+            bbox: None,
+            pk_column: None,
+            columns: Vec::new(),
+            crs: None,
+        }
+    }
+
+    /// Name of geometry column (default: from `gpkg_geometry_columns`)
+    pub fn set_geometry_column(&mut self, name: &str) {
+        self.geometry_column = Some(name.to_string());
+    }
+
+    /// Select features intersecting the given bounding box using the RTree spatial index
+    pub fn set_bbox(&mut self, minx: f64, miny: f64, maxx: f64, maxy: f64) {
+        self.bbox = Some([minx, miny, maxx, maxy]);
+    }
+
+    /// CRS of the geometry column, available after [read()](GpkgReader::read)
+    pub fn crs(&self) -> Option<&Crs> {
+        self.crs.as_ref()
+    }
+
+    /// Read features from feature table and process them with `processor`.
+    ///
+    /// Returns the number of processed features.
+    pub async fn read<P: FeatureProcessor>(
+        &mut self,
+        conn: &mut SqliteConnection,
+        processor: &mut P,
+    ) -> Result<usize> {
+        let sql = self.prepare(conn).await?;
+        self.dataset_begin(processor)?;
+        let mut rows = self.query(&sql).fetch(&mut *conn);
+        let mut count = 0;
+        while let Some(row) = rows.try_next().await? {
+            self.process_row(&row, count as u64, processor)?;
+            count += 1;
+        }
+        processor.dataset_end()?;
+        Ok(count)
+    }
+
+    /// Fetch all selected features from feature table.
+    ///
+    /// Use [read()](GpkgReader::read) to process large tables without loading them into memory.
+    pub async fn fetch(mut self, conn: &mut SqliteConnection) -> Result<GpkgFeatures> {
+        let sql = self.prepare(conn).await?;
+        let rows = self.query(&sql).fetch_all(&mut *conn).await?;
+        Ok(GpkgFeatures { reader: self, rows })
+    }
+
+    /// Read table metadata and return the feature query
+    async fn prepare(&mut self, conn: &mut SqliteConnection) -> Result<String> {
+        let geometry_column = match &self.geometry_column {
+            Some(column) => column.clone(),
+            None => {
+                let (column,): (String,) = sqlx::query_as(
+                    "SELECT column_name FROM gpkg_geometry_columns WHERE table_name = ?",
+                )
+                .bind(&self.table_name)
+                .fetch_one(&mut *conn)
+                .await?;
+                self.geometry_column = Some(column.clone());
+                column
+            }
+        };
+        let srs: Option<(String, i32, String)> = sqlx::query_as(
+            "SELECT s.organization, s.organization_coordsys_id, s.definition
+             FROM gpkg_geometry_columns g JOIN gpkg_spatial_ref_sys s ON g.srs_id = s.srs_id
+             WHERE g.table_name = ? AND g.column_name = ?",
+        )
+        .bind(&self.table_name)
+        .bind(&geometry_column)
+        .fetch_optional(&mut *conn)
+        .await?;
+        self.crs = srs
+            .and_then(|(organization, code, definition)| srs_crs(&organization, code, &definition));
+        let table = quote_ident(&self.table_name);
+        let pk_column: Option<(String,)> =
+            sqlx::query_as("SELECT name FROM pragma_table_info(?) WHERE pk = 1")
+                .bind(&self.table_name)
+                .fetch_optional(&mut *conn)
+                .await?;
+        self.pk_column = pk_column.map(|(name,)| name);
+        let columns: Vec<(String, String)> =
+            sqlx::query_as("SELECT name, type FROM pragma_table_info(?) ORDER BY cid")
+                .bind(&self.table_name)
+                .fetch_all(&mut *conn)
+                .await?;
+        self.columns = columns
+            .into_iter()
+            .filter(|(name, _)| {
+                *name != geometry_column && Some(name.as_str()) != self.pk_column.as_deref()
+            })
+            .map(|(name, declared_type)| ColumnDef::new(&name, column_type(&declared_type)))
+            .collect();
+
+        // http://erouault.blogspot.com/2017/03/dealing-with-huge-vector-geopackage.html
+        let mut sql = format!("SELECT t.* FROM {table} t");
+        if self.bbox.is_some() {
+            let pk = self.pk_column.as_deref().ok_or_else(|| {
+                GeozeroError::Dataset(format!("{} has no primary key", self.table_name))
+            })?;
+            let rtree = quote_ident(&format!("rtree_{}_{geometry_column}", self.table_name));
+            sql += &format!(
+                " JOIN {rtree} r ON t.{} = r.id
+                  WHERE r.minx <= ? AND r.maxx >= ? AND r.miny <= ? AND r.maxy >= ?",
+                quote_ident(pk)
+            );
+        }
+        Ok(sql)
+    }
+
+    fn query<'q>(&self, sql: &'q str) -> Query<'q, Sqlite, SqliteArguments<'q>> {
+        let query = sqlx::query(sql);
+        match self.bbox {
+            Some(bbox) => query
+                .bind(bbox[2])
+                .bind(bbox[0])
+                .bind(bbox[3])
+                .bind(bbox[1]),
+            None => query,
+        }
+    }
+
+    fn dataset_begin<P: FeatureProcessor>(&self, processor: &mut P) -> Result<()> {
+        processor.dataset_begin(Some(&self.table_name))?;
+        if let Some(crs) = &self.crs {
+            processor.crs(crs)?;
+        }
+        processor.schema_begin(&self.columns)
+    }
+
+    fn process_row<P: FeatureProcessor>(
+        &self,
+        row: &SqliteRow,
+        idx: u64,
+        processor: &mut P,
+    ) -> Result<()> {
+        processor.feature_begin(idx)?;
+        if let Some(pk) = self.pk_column.as_deref() {
+            if let Some(fid) = row.try_get::<Option<i64>, _>(pk)? {
+                processor.feature_id(&FeatureId::Long(fid))?;
+            }
+        }
+        processor.properties_begin()?;
+        let mut geometry = None;
+        let mut prop_idx = 0;
+        let mut finish = false;
+        for (i, column) in row.columns().iter().enumerate() {
+            if Some(column.name()) == self.geometry_column.as_deref() {
+                geometry = row.try_get_unchecked::<Option<&[u8]>, _>(i)?;
+            } else if !finish
+                && Some(column.name()) != self.pk_column.as_deref()
+                && !row.try_get_raw(i)?.is_null()
+            {
+                finish = process_column(row, i, prop_idx, processor)?;
+                prop_idx += 1;
+            }
+        }
+        processor.properties_end()?;
+        if let Some(geometry) = geometry {
+            processor.geometry_begin()?;
+            GpkgWkb(geometry).process_geom(processor)?;
+            processor.geometry_end()?;
+        }
+        processor.feature_end(idx)
+    }
+}
+
+/// Features fetched with [GpkgReader::fetch].
+pub struct GpkgFeatures {
+    reader: GpkgReader,
+    rows: Vec<SqliteRow>,
+}
+
+impl GpkgFeatures {
+    /// Number of fetched features
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+}
+
+impl GeozeroDatasource for GpkgFeatures {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        self.reader.dataset_begin(processor)?;
+        for (idx, row) in self.rows.iter().enumerate() {
+            self.reader.process_row(row, idx as u64, processor)?;
+        }
+        processor.dataset_end()
+    }
+
+    fn dataset_crs(&self) -> Option<Crs> {
+        self.reader.crs.clone()
+    }
+}
+
+/// CRS of a `gpkg_spatial_ref_sys` entry, `None` for the undefined SRS entries
+fn srs_crs(organization: &str, code: i32, definition: &str) -> Option<Crs> {
+    let mut crs = if organization.eq_ignore_ascii_case("NONE") {
+        Crs::default()
+    } else {
+        Crs::new(&organization.to_uppercase(), &code.to_string())
+    };
+    if definition != "undefined" {
+        crs.set_wkt(definition);
+    }
+    (crs != Crs::default()).then_some(crs)
+}
+
+/// Property type of a declared column type, matching the values of [process_column]
+fn column_type(declared_type: &str) -> ColumnType {
+    match declared_type.to_uppercase().as_str() {
+        "BOOLEAN" => ColumnType::Bool,
+        "TINYINT" | "SMALLINT" | "MEDIUMINT" | "INT" | "INTEGER" => ColumnType::Long,
+        "FLOAT" | "DOUBLE" | "REAL" => ColumnType::Double,
+        "DATE" | "DATETIME" => ColumnType::DateTime,
+        "BLOB" => ColumnType::Binary,
+        _ => ColumnType::String,
+    }
+}
+
+/// Process column value as property. Returns `true` if processing should be aborted.
+fn process_column<P: FeatureProcessor>(
+    row: &SqliteRow,
+    i: usize,
+    prop_idx: usize,
+    processor: &mut P,
+) -> Result<bool> {
+    let column = &row.columns()[i];
+    let value = row.try_get_raw(i)?;
+    let name = column.name();
+    let declared_type = column.type_info().name().to_uppercase();
+    let value_type = value.type_info().name().to_string();
+    match value_type.as_str() {
+        "INTEGER" => {
+            let v: i64 = row.try_get_unchecked(i)?;
+            if declared_type == "BOOLEAN" {
+                processor.property(prop_idx, name, &ColumnValue::Bool(v != 0))
+            } else {
+                processor.property(prop_idx, name, &ColumnValue::Long(v))
+            }
+        }
+        "REAL" => {
+            let v: f64 = row.try_get_unchecked(i)?;
+            processor.property(prop_idx, name, &ColumnValue::Double(v))
+        }
+        "BLOB" => {
+            let v: &[u8] = row.try_get_unchecked(i)?;
+            processor.property(prop_idx, name, &ColumnValue::Binary(v))
+        }
+        _ => {
+            let v: &str = row.try_get_unchecked(i)?;
+            if declared_type == "DATETIME" || declared_type == "DATE" {
+                processor.property(prop_idx, name, &ColumnValue::DateTime(v))
+            } else {
+                processor.property(prop_idx, name, &ColumnValue::String(v))
+            }
+        }
+    }
+}
@@ -0,0 +1,869 @@
+use crate::error::{GeozeroError, Result};
+use crate::wkb::{process_wkb_type_geom_with_limits, WkbDialect, WkbLimits, WkbWriter};
+use crate::{
+    ColumnValue, CoordDimensions, Crs, FeatureId, FeatureProcessor, GeomProcessor,
+    GeozeroDatasource, PropertyProcessor,
+};
+use sqlx::query::Query;
+use sqlx::sqlite::{Sqlite, SqliteArguments, SqliteConnection};
+use sqlx::Connection;
+use std::collections::HashSet;
+
+/// GeoPackage feature table writer.
+///
+/// Creates a feature table including the GeoPackage metadata tables (`gpkg_spatial_ref_sys`,
+/// `gpkg_contents`, `gpkg_geometry_columns`) and an RTree spatial index.
+/// The spatial index is filled while inserting features. It is only kept up to date on later
+/// changes with [RTree triggers](GpkgWriter::set_rtree_triggers).
+///
+/// # Usage example
+///
+/// ```
+/// use geozero::geojson::GeoJson;
+/// use geozero::gpkg::GpkgWriter;
+/// use sqlx::sqlite::SqliteConnectOptions;
+/// use sqlx::ConnectOptions;
+///
+/// # async fn write_gpkg() -> geozero::error::Result<()> {
+/// let mut conn = SqliteConnectOptions::new()
+///     .filename("places.gpkg")
+///     .create_if_missing(true)
+///     .connect()
+///     .await?;
+/// let mut geojson = GeoJson(r#"{"type": "Feature", "properties": {"name": "Bern"}, "geometry": {"type": "Point", "coordinates": [7.44, 46.95]}}"#);
+/// let mut writer = GpkgWriter::new(&mut conn, "places");
+/// writer.write(&mut geojson).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct GpkgWriter<'a> {
+    conn: &'a mut SqliteConnection,
+    table_name: String,
+    geometry_column: String,
+    srs_id: Option<i32>,
+    dims: CoordDimensions,
+    spatial_index: bool,
+    rtree_triggers: bool,
+    preserve_fid: bool,
+}
+
+impl<'a> GpkgWriter<'a> {
+    pub fn new(conn: &'a mut SqliteConnection, table_name: &str) -> Self {
+        GpkgWriter {
+            conn,
+            table_name: table_name.to_string(),
+            geometry_column: "geom".to_string(),
+            srs_id: None,
+            dims: CoordDimensions::default(),
+            spatial_index: true,
+            rtree_triggers: false,
+            preserve_fid: false,
+        }
+    }
+
+    /// Name of geometry column (default: `geom`)
+    pub fn set_geometry_column(&mut self, name: &str) {
+        self.geometry_column = name.to_string();
+    }
+
+    /// Spatial reference system of the feature table.
+    ///
+    /// Defaults to the EPSG code of the dataset CRS, the SRID of the first geometry
+    /// or `0` (undefined geographic SRS). A dataset CRS without EPSG code is registered
+    /// with its WKT definition as custom SRS with an `srs_id` of 100000 or above.
+    pub fn set_srs_id(&mut self, srs_id: i32) {
+        self.srs_id = Some(srs_id);
+    }
+
+    /// Coordinate dimensions to write (default: XY)
+    pub fn set_dimensions(&mut self, dims: CoordDimensions) {
+        self.dims = dims;
+    }
+
+    /// Create RTree spatial index (default: `true`)
+    pub fn set_spatial_index(&mut self, spatial_index: bool) {
+        self.spatial_index = spatial_index;
+    }
+
+    /// Create triggers updating the RTree spatial index on later changes (default: `false`).
+    ///
+    /// The triggers use the `ST_*` SQL functions of GeoPackage implementations like GDAL or
+    /// SpatiaLite. Once created, changes through connections without these functions fail.
+    pub fn set_rtree_triggers(&mut self, rtree_triggers: bool) {
+        self.rtree_triggers = rtree_triggers;
+    }
+
+    /// Write integer feature ids of the datasource as `fid` (default: `false`).
+    ///
+    /// Features without id get an id assigned by SQLite. Writing fails on duplicate ids and
+    /// on ids which are not integers.
+    pub fn set_preserve_fid(&mut self, preserve_fid: bool) {
+        self.preserve_fid = preserve_fid;
+    }
+
+    /// Create feature table and insert all features of `datasource`.
+    ///
+    /// Features are collected while processing the datasource and inserted afterwards, within
+    /// a single transaction. The table has a column for every property of any feature.
+    ///
+    /// Returns the number of inserted features.
+    pub async fn write<D: GeozeroDatasource>(&mut self, datasource: &mut D) -> Result<usize> {
+        let mut tx = self.conn.begin().await?;
+        create_metadata_tables(&mut tx).await?;
+        let mut inserter = FeatureInserter {
+            table_name: self.table_name.clone(),
+            geometry_column: self.geometry_column.clone(),
+            srs_id: self.srs_id,
+            dims: self.dims,
+            spatial_index: self.spatial_index,
+            preserve_fid: self.preserve_fid,
+            crs: None,
+            columns: Vec::new(),
+            feature: Feature::default(),
+            features: Vec::new(),
+            fids: HashSet::new(),
+            count: 0,
+            geometry_type: None,
+            extent: None,
+            writer: None,
+            level: 0,
+        };
+        datasource.process(&mut inserter)?;
+        let count = inserter.insert(&mut tx).await?;
+        if self.spatial_index && self.rtree_triggers {
+            for sql in rtree_triggers(&self.table_name, &self.geometry_column) {
+                sqlx::query(&sql).execute(&mut *tx).await?;
+            }
+        }
+        tx.commit().await?;
+        Ok(count)
+    }
+}
+
+async fn create_metadata_tables(conn: &mut SqliteConnection) -> Result<()> {
+    // 'GPKG' and version 1.3
+    sqlx::query("PRAGMA application_id = 1196444487")
+        .execute(&mut *conn)
+        .await?;
+    sqlx::query("PRAGMA user_version = 10300")
+        .execute(&mut *conn)
+        .await?;
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS gpkg_spatial_ref_sys (
+            srs_name TEXT NOT NULL,
+            srs_id INTEGER NOT NULL PRIMARY KEY,
+            organization TEXT NOT NULL,
+            organization_coordsys_id INTEGER NOT NULL,
+            definition TEXT NOT NULL,
+            description TEXT)",
+    )
+    .execute(&mut *conn)
+    .await?;
+    sqlx::query(
+        r#"INSERT OR IGNORE INTO gpkg_spatial_ref_sys VALUES
+            ('Undefined cartesian SRS', -1, 'NONE', -1, 'undefined', 'undefined cartesian coordinate reference system'),
+            ('Undefined geographic SRS', 0, 'NONE', 0, 'undefined', 'undefined geographic coordinate reference system'),
+            ('WGS 84 geodetic', 4326, 'EPSG', 4326, 'GEOGCS["WGS 84",DATUM["WGS_1984",SPHEROID["WGS 84",6378137,298.257223563,AUTHORITY["EPSG","7030"]],AUTHORITY["EPSG","6326"]],PRIMEM["Greenwich",0,AUTHORITY["EPSG","8901"]],UNIT["degree",0.0174532925199433,AUTHORITY["EPSG","9122"]],AXIS["Latitude",NORTH],AXIS["Longitude",EAST],AUTHORITY["EPSG","4326"]]', 'longitude/latitude coordinates in decimal degrees on the WGS 84 spheroid')"#,
+    )
+    .execute(&mut *conn)
+    .await?;
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS gpkg_contents (
+            table_name TEXT NOT NULL PRIMARY KEY,
+            data_type TEXT NOT NULL,
+            identifier TEXT UNIQUE,
+            description TEXT DEFAULT '',
+            last_change DATETIME NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ','now')),
+            min_x DOUBLE,
+            min_y DOUBLE,
+            max_x DOUBLE,
+            max_y DOUBLE,
+            srs_id INTEGER,
+            CONSTRAINT fk_gc_r_srs_id FOREIGN KEY (srs_id) REFERENCES gpkg_spatial_ref_sys(srs_id))",
+    )
+    .execute(&mut *conn)
+    .await?;
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS gpkg_geometry_columns (
+            table_name TEXT NOT NULL,
+            column_name TEXT NOT NULL,
+            geometry_type_name TEXT NOT NULL,
+            srs_id INTEGER NOT NULL,
+            z TINYINT NOT NULL,
+            m TINYINT NOT NULL,
+            CONSTRAINT pk_geom_cols PRIMARY KEY (table_name, column_name),
+            CONSTRAINT uk_gc_table_name UNIQUE (table_name),
+            CONSTRAINT fk_gc_tn FOREIGN KEY (table_name) REFERENCES gpkg_contents(table_name),
+            CONSTRAINT fk_gc_srs FOREIGN KEY (srs_id) REFERENCES gpkg_spatial_ref_sys (srs_id))",
+    )
+    .execute(&mut *conn)
+    .await?;
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS gpkg_extensions (
+            table_name TEXT,
+            column_name TEXT,
+            extension_name TEXT NOT NULL,
+            definition TEXT NOT NULL,
+            scope TEXT NOT NULL,
+            CONSTRAINT ge_tce UNIQUE (table_name, column_name, extension_name))",
+    )
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+/// RTree triggers according to http://www.geopackage.org/spec130/#extension_rtree
+fn rtree_triggers(table: &str, column: &str) -> Vec<String> {
+    let rtree = quote_ident(&format!("rtree_{table}_{column}"));
+    let trigger = |suffix: &str| quote_ident(&format!("rtree_{table}_{column}_{suffix}"));
+    let t = quote_ident(table);
+    let c = quote_ident(column);
+    vec![
+        format!(
+            "CREATE TRIGGER {} AFTER INSERT ON {t}
+             WHEN (new.{c} NOT NULL AND NOT ST_IsEmpty(NEW.{c}))
+             BEGIN
+               INSERT OR REPLACE INTO {rtree} VALUES (
+                 NEW.fid, ST_MinX(NEW.{c}), ST_MaxX(NEW.{c}), ST_MinY(NEW.{c}), ST_MaxY(NEW.{c}));
+             END",
+            trigger("insert")
+        ),
+        format!(
+            "CREATE TRIGGER {} AFTER UPDATE OF {c} ON {t}
+             WHEN OLD.fid = NEW.fid AND (NEW.{c} NOTNULL AND NOT ST_IsEmpty(NEW.{c}))
+             BEGIN
+               INSERT OR REPLACE INTO {rtree} VALUES (
+                 NEW.fid, ST_MinX(NEW.{c}), ST_MaxX(NEW.{c}), ST_MinY(NEW.{c}), ST_MaxY(NEW.{c}));
+             END",
+            trigger("update1")
+        ),
+        format!(
+            "CREATE TRIGGER {} AFTER UPDATE OF {c} ON {t}
+             WHEN OLD.fid = NEW.fid AND (NEW.{c} ISNULL OR ST_IsEmpty(NEW.{c}))
+             BEGIN
+               DELETE FROM {rtree} WHERE id = OLD.fid;
+             END",
+            trigger("update2")
+        ),
+        format!(
+            "CREATE TRIGGER {} AFTER UPDATE ON {t}
+             WHEN OLD.fid != NEW.fid AND (NEW.{c} NOTNULL AND NOT ST_IsEmpty(NEW.{c}))
+             BEGIN
+               DELETE FROM {rtree} WHERE id = OLD.fid;
+               INSERT OR REPLACE INTO {rtree} VALUES (
+                 NEW.fid, ST_MinX(NEW.{c}), ST_MaxX(NEW.{c}), ST_MinY(NEW.{c}), ST_MaxY(NEW.{c}));
+             END",
+            trigger("update3")
+        ),
+        format!(
+            "CREATE TRIGGER {} AFTER UPDATE ON {t}
+             WHEN OLD.fid != NEW.fid AND (NEW.{c} ISNULL OR ST_IsEmpty(NEW.{c}))
+             BEGIN
+               DELETE FROM {rtree} WHERE id IN (OLD.fid, NEW.fid);
+             END",
+            trigger("update4")
+        ),
+        format!(
+            "CREATE TRIGGER {} AFTER DELETE ON {t}
+             WHEN old.{c} NOT NULL
+             BEGIN
+               DELETE FROM {rtree} WHERE id = OLD.fid;
+             END",
+            trigger("delete")
+        ),
+    ]
+}
+
+/// Convert WKB into GeoPackage geometry including envelope
+fn gpkg_geometry(
+    wkb: &[u8],
+    srs_id: i32,
+    dims: CoordDimensions,
+    bbox: Option<[f64; 4]>,
+) -> Result<Vec<u8>> {
+    // [minx, maxx, miny, maxy]
+    let envelope = bbox
+        .map(|bbox| vec![bbox[0], bbox[2], bbox[1], bbox[3]])
+        .unwrap_or_default();
+    let mut out = Vec::new();
+    let mut writer = WkbWriter::with_opts(
+        &mut out,
+        WkbDialect::Geopackage,
+        dims,
+        Some(srs_id),
+        envelope,
+    );
+    // Our own output is not checked against the default limits
+    process_wkb_type_geom_with_limits(
+        &mut &wkb[..],
+        &mut writer,
+        WkbDialect::Wkb,
+        &WkbLimits::unlimited(),
+    )?;
+    Ok(out)
+}
+
+/// First `srs_id` of CRS registered without EPSG code
+const FIRST_CUSTOM_SRS_ID: i32 = 100000;
+
+pub(crate) fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+type SqliteQuery<'q> = Query<'q, Sqlite, SqliteArguments<'q>>;
+
+fn bind_value(query: SqliteQuery<'_>, value: Option<Value>) -> SqliteQuery<'_> {
+    match value {
+        Some(Value::Integer(v)) => query.bind(v),
+        Some(Value::Real(v)) => query.bind(v),
+        Some(Value::Text(v)) => query.bind(v),
+        Some(Value::Blob(v)) => query.bind(v),
+        None => query.bind(None::<String>),
+    }
+}
+
+/// SQLite storage class of a property value
+enum Value {
+    Integer(i64),
+    Real(f64),
+    Text(String),
+    Blob(Vec<u8>),
+}
+
+impl Value {
+    /// Convert property value into storage value and GeoPackage column type
+    fn from_column_value(value: &ColumnValue) -> Result<(Value, &'static str)> {
+        let value = match value {
+            ColumnValue::Bool(v) => (Value::Integer(*v as i64), "BOOLEAN"),
+            ColumnValue::Byte(v) => (Value::Integer(*v as i64), "TINYINT"),
+            ColumnValue::UByte(v) => (Value::Integer(*v as i64), "SMALLINT"),
+            ColumnValue::Short(v) => (Value::Integer(*v as i64), "SMALLINT"),
+            ColumnValue::UShort(v) => (Value::Integer(*v as i64), "MEDIUMINT"),
+            ColumnValue::Int(v) => (Value::Integer(*v as i64), "MEDIUMINT"),
+            ColumnValue::UInt(v) => (Value::Integer(*v as i64), "INTEGER"),
+            ColumnValue::Long(v) => (Value::Integer(*v), "INTEGER"),
+            ColumnValue::ULong(v) => {
+                let v = i64::try_from(*v)
+                    .map_err(|_| GeozeroError::Property(format!("{v} exceeds INTEGER range")))?;
+                (Value::Integer(v), "INTEGER")
+            }
+            ColumnValue::Float(v) => (Value::Real(*v as f64), "FLOAT"),
+            ColumnValue::Double(v) => (Value::Real(*v), "DOUBLE"),
+            ColumnValue::String(v) | ColumnValue::Json(v) => (Value::Text(v.to_string()), "TEXT"),
+            ColumnValue::DateTime(v) => (Value::Text(v.to_string()), "DATETIME"),
+            ColumnValue::Binary(v) => (Value::Blob(v.to_vec()), "BLOB"),
+            ColumnValue::Uuid(_) | ColumnValue::Decimal(_) | ColumnValue::Array(_) => {
+                (Value::Text(value.to_string()), "TEXT")
+            }
+        };
+        Ok(value)
+    }
+}
+
+struct Column {
+    name: String,
+    sql_type: &'static str,
+}
+
+#[derive(Default)]
+struct Feature {
+    /// Explicit feature id, assigned by SQLite if `None`
+    fid: Option<i64>,
+    geometry: Option<Vec<u8>>,
+    /// Property values in column order
+    values: Vec<Option<Value>>,
+    /// [minx, miny, maxx, maxy]
+    bbox: Option<[f64; 4]>,
+    /// SRID of the geometry
+    srid: Option<i32>,
+}
+
+/// Collects processed features and inserts them into a new feature table
+struct FeatureInserter {
+    table_name: String,
+    geometry_column: String,
+    /// Configured SRS, resolved when creating the table
+    srs_id: Option<i32>,
+    dims: CoordDimensions,
+    spatial_index: bool,
+    preserve_fid: bool,
+    /// Dataset CRS
+    crs: Option<Crs>,
+    columns: Vec<Column>,
+    /// Current feature
+    feature: Feature,
+    /// Processed features
+    features: Vec<Feature>,
+    /// Explicit feature ids, if preserved
+    fids: HashSet<i64>,
+    count: usize,
+    /// Common geometry type of all features
+    geometry_type: Option<&'static str>,
+    /// [minx, miny, maxx, maxy] of all features
+    extent: Option<[f64; 4]>,
+    writer: Option<WkbWriter<Vec<u8>>>,
+    /// Nesting level of the current geometry
+    level: usize,
+}
+
+impl FeatureInserter {
+    fn writer(&mut self) -> Result<&mut WkbWriter<Vec<u8>>> {
+        self.writer
+            .as_mut()
+            .ok_or_else(|| GeozeroError::Geometry("geometry_begin missing".to_string()))
+    }
+
+    fn begin(&mut self, geometry_type: &'static str) -> Result<&mut WkbWriter<Vec<u8>>> {
+        if self.level == 0 {
+            self.geometry_type = match self.geometry_type {
+                None => Some(geometry_type),
+                Some(t) if t == geometry_type => Some(t),
+                _ => Some("GEOMETRY"),
+            };
+        }
+        self.level += 1;
+        self.writer()
+    }
+
+    fn end(&mut self) -> Result<&mut WkbWriter<Vec<u8>>> {
+        self.level -= 1;
+        self.writer()
+    }
+
+    fn add_xy(&mut self, x: f64, y: f64) {
+        let bbox = self.feature.bbox.get_or_insert([x, y, x, y]);
+        bbox[0] = bbox[0].min(x);
+        bbox[1] = bbox[1].min(y);
+        bbox[2] = bbox[2].max(x);
+        bbox[3] = bbox[3].max(y);
+    }
+
+    fn rtree(&self) -> String {
+        quote_ident(&format!(
+            "rtree_{}_{}",
+            self.table_name, self.geometry_column
+        ))
+    }
+
+    /// Register the SRS of the feature table in `gpkg_spatial_ref_sys` and return its `srs_id`.
+    ///
+    /// A dataset CRS without EPSG code is registered with a custom `srs_id`, reusing an
+    /// identical entry of a previous write.
+    async fn register_srs(&self, conn: &mut SqliteConnection, srid: Option<i32>) -> Result<i32> {
+        let crs_srid = self.crs.as_ref().and_then(Crs::srid);
+        let custom_crs = self
+            .crs
+            .as_ref()
+            .filter(|crs| self.srs_id.is_none() && crs_srid.is_none() && **crs != Crs::default());
+        let Some(crs) = custom_crs else {
+            let srs_id = self.srs_id.or(crs_srid).or(srid).unwrap_or(0);
+            let definition = self
+                .crs
+                .as_ref()
+                .filter(|_| crs_srid == Some(srs_id))
+                .and_then(|crs| crs.wkt.as_deref())
+                .unwrap_or("undefined");
+            sqlx::query(
+                "INSERT OR IGNORE INTO gpkg_spatial_ref_sys (srs_name, srs_id, organization, organization_coordsys_id, definition)
+                 VALUES (?, ?, ?, ?, ?)",
+            )
+            .bind(format!("EPSG:{srs_id}"))
+            .bind(srs_id)
+            .bind("EPSG")
+            .bind(srs_id)
+            .bind(definition)
+            .execute(&mut *conn)
+            .await?;
+            return Ok(srs_id);
+        };
+
+        // Authorities with integer codes are kept, other CRS are only defined by their WKT
+        let (organization, code) = match (&crs.authority, crs.code.as_deref().map(str::parse)) {
+            (Some(authority), Some(Ok(code))) => (authority.to_uppercase(), Some(code)),
+            _ => ("NONE".to_string(), None),
+        };
+        let definition = crs.wkt.as_deref().unwrap_or("undefined");
+        let existing: Option<(i32,)> = sqlx::query_as(
+            "SELECT srs_id FROM gpkg_spatial_ref_sys
+             WHERE srs_id >= ? AND organization = ? AND organization_coordsys_id = coalesce(?, srs_id) AND definition = ?",
+        )
+        .bind(FIRST_CUSTOM_SRS_ID)
+        .bind(&organization)
+        .bind(code)
+        .bind(definition)
+        .fetch_optional(&mut *conn)
+        .await?;
+        if let Some((srs_id,)) = existing {
+            return Ok(srs_id);
+        }
+        let (srs_id,): (i32,) =
+            sqlx::query_as("SELECT max(max(srs_id) + 1, ?) FROM gpkg_spatial_ref_sys")
+                .bind(FIRST_CUSTOM_SRS_ID)
+                .fetch_one(&mut *conn)
+                .await?;
+        let srs_name = match code {
+            Some(code) => format!("{organization}:{code}"),
+            None => format!("Custom SRS {srs_id}"),
+        };
+        sqlx::query(
+            "INSERT INTO gpkg_spatial_ref_sys (srs_name, srs_id, organization, organization_coordsys_id, definition)
+             VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(srs_name)
+        .bind(srs_id)
+        .bind(&organization)
+        .bind(code.unwrap_or(srs_id))
+        .bind(definition)
+        .execute(&mut *conn)
+        .await?;
+        Ok(srs_id)
+    }
+
+    /// Create feature table and its metadata, with `srid` of the first geometry as default SRS
+    async fn create_table(&mut self, conn: &mut SqliteConnection, srid: Option<i32>) -> Result<()> {
+        let srs_id = self.register_srs(conn, srid).await?;
+        self.srs_id = Some(srs_id);
+
+        let geometry_type = self.geometry_type.unwrap_or("GEOMETRY");
+        let table = quote_ident(&self.table_name);
+        let geom_column = quote_ident(&self.geometry_column);
+        let mut sql = format!(
+            "CREATE TABLE {table} (fid INTEGER PRIMARY KEY AUTOINCREMENT, {geom_column} {geometry_type}"
+        );
+        for column in &self.columns {
+            sql.push_str(&format!(
+                ", {} {}",
+                quote_ident(&column.name),
+                column.sql_type
+            ));
+        }
+        sql.push(')');
+        sqlx::query(&sql).execute(&mut *conn).await?;
+
+        sqlx::query(
+            "INSERT INTO gpkg_contents (table_name, data_type, identifier, srs_id)
+             VALUES (?, 'features', ?, ?)",
+        )
+        .bind(&self.table_name)
+        .bind(&self.table_name)
+        .bind(srs_id)
+        .execute(&mut *conn)
+        .await?;
+        sqlx::query(
+            "INSERT INTO gpkg_geometry_columns (table_name, column_name, geometry_type_name, srs_id, z, m)
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&self.table_name)
+        .bind(&self.geometry_column)
+        .bind(geometry_type)
+        .bind(srs_id)
+        .bind(self.dims.z as i32)
+        .bind(self.dims.m as i32)
+        .execute(&mut *conn)
+        .await?;
+
+        if self.spatial_index {
+            let rtree = self.rtree();
+            sqlx::query(&format!(
+                "CREATE VIRTUAL TABLE {rtree} USING rtree(id, minx, maxx, miny, maxy)"
+            ))
+            .execute(&mut *conn)
+            .await?;
+            sqlx::query(
+                "INSERT INTO gpkg_extensions (table_name, column_name, extension_name, definition, scope)
+                 VALUES (?, ?, 'gpkg_rtree_index', 'http://www.geopackage.org/spec120/#extension_rtree', 'write-only')",
+            )
+            .bind(&self.table_name)
+            .bind(&self.geometry_column)
+            .execute(&mut *conn)
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Create feature table and insert all processed features
+    async fn insert(mut self, conn: &mut SqliteConnection) -> Result<usize> {
+        let srid = self.features.iter().find_map(|feature| feature.srid);
+        self.create_table(conn, srid).await?;
+        for feature in std::mem::take(&mut self.features) {
+            self.insert_feature(conn, feature).await?;
+        }
+        self.update_extent(conn).await?;
+        Ok(self.count)
+    }
+
+    async fn insert_feature(
+        &mut self,
+        conn: &mut SqliteConnection,
+        feature: Feature,
+    ) -> Result<()> {
+        let table = quote_ident(&self.table_name);
+        let mut columns = quote_ident(&self.geometry_column);
+        let mut placeholders = "?".to_string();
+        if self.preserve_fid {
+            columns.push_str(", fid");
+            placeholders.push_str(", ?");
+        }
+        for (column, value) in self.columns.iter().zip(&feature.values) {
+            if value.is_some() {
+                columns.push_str(&format!(", {}", quote_ident(&column.name)));
+                placeholders.push_str(", ?");
+            }
+        }
+        let geometry = feature
+            .geometry
+            .map(|wkb| gpkg_geometry(&wkb, self.srs_id.unwrap_or(0), self.dims, feature.bbox))
+            .transpose()?;
+        let sql = format!("INSERT INTO {table} ({columns}) VALUES ({placeholders})");
+        let mut query = sqlx::query(&sql).bind(geometry);
+        if self.preserve_fid {
+            query = query.bind(feature.fid);
+        }
+        for value in feature.values.into_iter().flatten() {
+            query = bind_value(query, Some(value));
+        }
+        let fid = query.execute(&mut *conn).await?.last_insert_rowid();
+        if self.preserve_fid && feature.fid.is_none() && self.fids.contains(&fid) {
+            // Assigned ids may collide with explicit ids of later features
+            return Err(GeozeroError::Feature(format!("duplicate feature id {fid}")));
+        }
+
+        if let Some(bbox) = feature.bbox {
+            if self.spatial_index {
+                let rtree = self.rtree();
+                sqlx::query(&format!("INSERT INTO {rtree} VALUES (?, ?, ?, ?, ?)"))
+                    .bind(fid)
+                    .bind(bbox[0])
+                    .bind(bbox[2])
+                    .bind(bbox[1])
+                    .bind(bbox[3])
+                    .execute(&mut *conn)
+                    .await?;
+            }
+            let extent = self.extent.get_or_insert(bbox);
+            extent[0] = extent[0].min(bbox[0]);
+            extent[1] = extent[1].min(bbox[1]);
+            extent[2] = extent[2].max(bbox[2]);
+            extent[3] = extent[3].max(bbox[3]);
+        }
+        self.count += 1;
+        Ok(())
+    }
+
+    /// Update table extent after inserting all features
+    async fn update_extent(&self, conn: &mut SqliteConnection) -> Result<()> {
+        let extent = self.extent;
+        sqlx::query(
+            "UPDATE gpkg_contents SET min_x = ?, min_y = ?, max_x = ?, max_y = ? WHERE table_name = ?",
+        )
+        .bind(extent.map(|e| e[0]))
+        .bind(extent.map(|e| e[1]))
+        .bind(extent.map(|e| e[2]))
+        .bind(extent.map(|e| e[3]))
+        .bind(&self.table_name)
+        .execute(&mut *conn)
+        .await?;
+        Ok(())
+    }
+}
+
+impl FeatureProcessor for FeatureInserter {
+    fn crs(&mut self, crs: &Crs) -> Result<()> {
+        self.crs = Some(crs.clone());
+        Ok(())
+    }
+    fn feature_begin(&mut self, _idx: u64) -> Result<()> {
+        self.feature = Feature::default();
+        Ok(())
+    }
+    fn feature_id(&mut self, id: &FeatureId) -> Result<()> {
+        if !self.preserve_fid {
+            return Ok(());
+        }
+        let fid = match *id {
+            FeatureId::Long(id) => Some(id),
+            FeatureId::ULong(id) => i64::try_from(id).ok(),
+            FeatureId::String(id) => id.parse().ok(),
+        };
+        let fid =
+            fid.ok_or_else(|| GeozeroError::Feature(format!("feature id {id} is not an integer")))?;
+        self.feature.fid = Some(fid);
+        Ok(())
+    }
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        // The datasource is processed synchronously, while sqlx is async only.
+        // Features are inserted by `write` after processing.
+        if let Some(fid) = self.feature.fid {
+            if !self.fids.insert(fid) {
+                return Err(GeozeroError::Feature(format!("duplicate feature id {fid}")));
+            }
+        }
+        self.features.push(std::mem::take(&mut self.feature));
+        Ok(())
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        self.writer = Some(WkbWriter::with_opts(
+            Vec::new(),
+            WkbDialect::Wkb,
+            self.dims,
+            None,
+            Vec::new(),
+        ));
+        Ok(())
+    }
+    fn geometry_end(&mut self) -> Result<()> {
+        let wkb = self.writer.take().map(WkbWriter::into_inner);
+        self.feature.geometry = wkb.filter(|wkb| !wkb.is_empty());
+        Ok(())
+    }
+}
+
+impl PropertyProcessor for FeatureInserter {
+    fn property(&mut self, _idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        let (value, sql_type) = Value::from_column_value(value)?;
+        let col_idx = match self.columns.iter().position(|col| col.name == name) {
+            Some(col_idx) => col_idx,
+            None => {
+                self.columns.push(Column {
+                    name: name.to_string(),
+                    sql_type,
+                });
+                self.columns.len() - 1
+            }
+        };
+        let values = &mut self.feature.values;
+        if values.len() <= col_idx {
+            values.resize_with(col_idx + 1, || None);
+        }
+        values[col_idx] = Some(value);
+        Ok(false)
+    }
+}
+
+impl GeomProcessor for FeatureInserter {
+    fn dimensions(&self) -> CoordDimensions {
+        self.dims
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        if self.feature.srid.is_none() {
+            self.feature.srid = srid;
+        }
+        Ok(())
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.add_xy(x, y);
+        self.writer()?.xy(x, y, idx)
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.add_xy(x, y);
+        self.writer()?.coordinate(x, y, z, m, t, tm, idx)
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.begin("POINT")?;
+        self.level -= 1;
+        self.writer()?.empty_point(idx)
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.begin("POINT")?.point_begin(idx)
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.end()?.point_end(idx)
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin("MULTIPOINT")?.multipoint_begin(size, idx)
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.end()?.multipoint_end(idx)
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.begin("LINESTRING")?
+            .linestring_begin(tagged, size, idx)
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.end()?.linestring_end(tagged, idx)
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin("MULTILINESTRING")?
+            .multilinestring_begin(size, idx)
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.end()?.multilinestring_end(idx)
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.begin("POLYGON")?.polygon_begin(tagged, size, idx)
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.end()?.polygon_end(tagged, idx)
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin("MULTIPOLYGON")?.multipolygon_begin(size, idx)
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.end()?.multipolygon_end(idx)
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin("GEOMETRYCOLLECTION")?
+            .geometrycollection_begin(size, idx)
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.end()?.geometrycollection_end(idx)
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin("CIRCULARSTRING")?
+            .circularstring_begin(size, idx)
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        self.end()?.circularstring_end(idx)
+    }
+    fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin("COMPOUNDCURVE")?.compoundcurve_begin(size, idx)
+    }
+    fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        self.end()?.compoundcurve_end(idx)
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin("CURVEPOLYGON")?.curvepolygon_begin(size, idx)
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.end()?.curvepolygon_end(idx)
+    }
+    fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin("MULTICURVE")?.multicurve_begin(size, idx)
+    }
+    fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        self.end()?.multicurve_end(idx)
+    }
+    fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin("MULTISURFACE")?.multisurface_begin(size, idx)
+    }
+    fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        self.end()?.multisurface_end(idx)
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.begin("GEOMETRY")?.triangle_begin(tagged, size, idx)
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.end()?.triangle_end(tagged, idx)
+    }
+    fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin("GEOMETRY")?.polyhedralsurface_begin(size, idx)
+    }
+    fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        self.end()?.polyhedralsurface_end(idx)
+    }
+    fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin("GEOMETRY")?.tin_begin(size, idx)
+    }
+    fn tin_end(&mut self, idx: usize) -> Result<()> {
+        self.end()?.tin_end(idx)
+    }
+}
@@ -1,9 +1,11 @@
-//! Geopackage geometry type encoding/decoding.
+//! Geopackage geometry type encoding/decoding and feature table reader/writer.
 //!
 //! All geometry types implementing [GeozeroGeometry](crate::GeozeroGeometry) can be encoded as Geopackage WKB geometry using [wkb::Encode](crate::wkb::Encode).
 //!
 //! Geometry types implementing [FromWkb](crate::wkb::FromWkb) can be decoded from Geopackage geometries using [wkb::Decode](crate::wkb::Decode).
 //!
+//! Feature tables can be read with [GpkgReader] and created from any [GeozeroDatasource](crate::GeozeroDatasource) with [GpkgWriter].
+//!
 //! # Usage example
 //!
 //! Select geo-types geometries from a Geopackage:
@@ -28,3 +30,8 @@
 //! ```
 
 mod geopackage;
+mod gpkg_reader;
+mod gpkg_writer;
+
+pub use gpkg_reader::*;
+pub use gpkg_writer::*;
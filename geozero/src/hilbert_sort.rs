@@ -0,0 +1,267 @@
+//! Spatial sorting of features along a Hilbert curve.
+use crate::error::{GeozeroError, Result};
+use crate::packed_rtree::hilbert_index;
+use crate::wrap::{delegate_coordinates, delegate_geometry_types};
+use crate::{
+    ColumnDef, ColumnValue, CoordDimensions, Crs, FeatureId, FeatureProcessor, FeatureSpool,
+    GeomProcessor, PropertyProcessor,
+};
+use std::ops::{Deref, DerefMut};
+
+/// Wraps another [FeatureProcessor], re-emitting features in Hilbert curve order
+/// of their bounding box centers.
+///
+/// Features are buffered in a [FeatureSpool] until the end of the dataset. Buffered features exceeding
+/// the memory limit (default: [DEFAULT_SPOOL_MEMORY](crate::DEFAULT_SPOOL_MEMORY)) are written to a temporary file.
+/// Features are renumbered in output order, features without coordinates are emitted last
+/// in input order.
+///
+/// Buffered features are only emitted on `dataset_end`. Geometries processed outside of
+/// features (e.g. with `process_geom`) can't be sorted. [into_inner](HilbertSort::into_inner)
+/// fails and dropping logs an error, if features or geometries were not emitted.
+///
+/// # Usage example:
+///
+/// ```
+/// # #[cfg(feature = "with-geojson")]
+/// # {
+/// use geozero::geojson::{GeoJson, GeoJsonWriter};
+/// use geozero::{GeozeroDatasource, HilbertSort};
+///
+/// let mut geojson = GeoJson(
+///     r#"{"type": "FeatureCollection", "features": [
+///         {"type": "Feature", "properties": {"name": "b"}, "geometry": {"type": "Point", "coordinates": [10, 10]}},
+///         {"type": "Feature", "properties": {"name": "a"}, "geometry": {"type": "Point", "coordinates": [0, 0]}}
+///     ]}"#,
+/// );
+/// let mut out = Vec::new();
+/// let mut processor = HilbertSort::new(GeoJsonWriter::new(&mut out));
+/// geojson.process(&mut processor).unwrap();
+/// let json = String::from_utf8(out).unwrap();
+/// assert!(json.find(r#""a""#) < json.find(r#""b""#));
+/// # }
+/// ```
+pub struct HilbertSort<P> {
+    /// The underlying processor
+    pub inner: P,
+    spool: SortSpool,
+}
+
+/// Spool reporting events which were never emitted
+struct SortSpool(FeatureSpool);
+
+impl SortSpool {
+    fn check_emitted(&self) -> Result<()> {
+        if !self.0.is_empty() {
+            Err(GeozeroError::Feature(format!(
+                "{} features not emitted, dataset_end missing",
+                self.0.len()
+            )))
+        } else if self.0.has_pending_events() {
+            Err(GeozeroError::Feature(
+                "events outside of features not emitted".to_string(),
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Deref for SortSpool {
+    type Target = FeatureSpool;
+    fn deref(&self) -> &FeatureSpool {
+        &self.0
+    }
+}
+
+impl DerefMut for SortSpool {
+    fn deref_mut(&mut self) -> &mut FeatureSpool {
+        &mut self.0
+    }
+}
+
+impl Drop for SortSpool {
+    fn drop(&mut self) {
+        if let Err(e) = self.check_emitted() {
+            log::error!("HilbertSort dropped: {e}");
+        }
+    }
+}
+
+impl<P: FeatureProcessor> HilbertSort<P> {
+    pub fn new(inner: P) -> Self {
+        let mut spool = FeatureSpool::new();
+        spool.set_dimensions(inner.dimensions());
+        HilbertSort {
+            inner,
+            spool: SortSpool(spool),
+        }
+    }
+    /// Set size of buffered features in bytes kept in memory
+    pub fn set_max_memory(&mut self, max_memory: usize) {
+        self.spool.set_max_memory(max_memory);
+    }
+    /// Buffered features were written to a temporary file
+    pub fn spilled(&self) -> bool {
+        self.spool.spilled()
+    }
+    /// Return the underlying processor.
+    ///
+    /// Fails if buffered features were not emitted with `dataset_end`.
+    pub fn into_inner(mut self) -> Result<P> {
+        let emitted = self.spool.check_emitted();
+        self.spool.clear();
+        emitted.map(|()| self.inner)
+    }
+
+    fn emit_sorted(&mut self) -> Result<()> {
+        let extent = self.spool.extent();
+        let mut order: Vec<(Option<u32>, usize)> = (0..self.spool.len())
+            .map(|idx| {
+                let key = extent
+                    .zip(self.spool.feature_bounds(idx))
+                    .map(|(extent, bounds)| hilbert_index(&bounds, &extent));
+                (key, idx)
+            })
+            .collect();
+        // Stable sort with features without geometry last
+        order.sort_by_key(|(key, _)| (key.is_none(), *key));
+
+        for (out_idx, (_, idx)) in order.into_iter().enumerate() {
+            self.spool
+                .replay_feature(idx, out_idx as u64, &mut self.inner)?;
+        }
+        self.spool.clear();
+        Ok(())
+    }
+}
+
+// Geometry events are buffered, only the dimensions are requested from the inner processor
+impl<P: FeatureProcessor> GeomProcessor for HilbertSort<P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.inner.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.inner.multi_dim()
+    }
+    delegate_coordinates!(spool);
+    delegate_geometry_types!(spool);
+}
+
+impl<P: FeatureProcessor> PropertyProcessor for HilbertSort<P> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue<'_>) -> Result<bool> {
+        self.spool.property(idx, name, value)
+    }
+    fn column_property(
+        &mut self,
+        idx: usize,
+        column: usize,
+        name: &str,
+        value: &ColumnValue<'_>,
+    ) -> Result<bool> {
+        self.spool.column_property(idx, column, name, value)
+    }
+}
+
+impl<P: FeatureProcessor> FeatureProcessor for HilbertSort<P> {
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.inner.dataset_begin(name)
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        self.emit_sorted()?;
+        self.inner.dataset_end()
+    }
+    fn crs(&mut self, crs: &Crs) -> Result<()> {
+        self.inner.crs(crs)
+    }
+    fn schema_begin(&mut self, columns: &[ColumnDef]) -> Result<()> {
+        self.inner.schema_begin(columns)
+    }
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        self.spool.feature_begin(idx)
+    }
+    fn feature_id(&mut self, id: &FeatureId) -> Result<()> {
+        self.spool.feature_id(id)
+    }
+    fn feature_end(&mut self, idx: u64) -> Result<()> {
+        self.spool.feature_end(idx)
+    }
+    fn properties_begin(&mut self) -> Result<()> {
+        self.spool.properties_begin()
+    }
+    fn properties_end(&mut self) -> Result<()> {
+        self.spool.properties_end()
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        self.spool.geometry_begin()
+    }
+    fn geometry_end(&mut self) -> Result<()> {
+        self.spool.geometry_end()
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::{GeoJson, GeoJsonWriter};
+    use crate::{GeozeroDatasource, GeozeroGeometry, DEFAULT_SPOOL_MEMORY};
+
+    fn sorted_names(geojson: &str, max_memory: usize) -> Result<(Vec<String>, bool)> {
+        let mut out = Vec::new();
+        let mut processor = HilbertSort::new(GeoJsonWriter::new(&mut out));
+        processor.set_max_memory(max_memory);
+        GeoJson(geojson).process(&mut processor)?;
+        let spilled = processor.spilled();
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        let names = json["features"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|f| f["properties"]["name"].as_str().unwrap().to_string())
+            .collect();
+        Ok((names, spilled))
+    }
+
+    #[test]
+    fn hilbert_order() -> Result<()> {
+        // Corners of the unit square are visited in the order (0,0), (0,1), (1,1), (1,0)
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"name": "none"}, "geometry": null},
+            {"type": "Feature", "properties": {"name": "ur"}, "geometry": {"type": "Point", "coordinates": [10, 10]}},
+            {"type": "Feature", "properties": {"name": "lr"}, "geometry": {"type": "LineString", "coordinates": [[9, 0], [10, 1]]}},
+            {"type": "Feature", "properties": {"name": "ll"}, "geometry": {"type": "Point", "coordinates": [0, 0]}},
+            {"type": "Feature", "properties": {"name": "ul"}, "geometry": {"type": "Point", "coordinates": [0, 10]}}
+        ]}"#;
+        let expected = ["ll", "ul", "ur", "lr", "none"];
+
+        let (names, spilled) = sorted_names(geojson, DEFAULT_SPOOL_MEMORY)?;
+        assert_eq!(names, expected);
+        assert!(!spilled);
+
+        let (names, spilled) = sorted_names(geojson, 0)?;
+        assert_eq!(names, expected);
+        assert!(spilled);
+        Ok(())
+    }
+
+    #[test]
+    fn pending_features() -> Result<()> {
+        let mut processor = HilbertSort::new(GeoJsonWriter::new(Vec::new()));
+        processor.feature_begin(0)?;
+        processor.properties_begin()?;
+        processor.properties_end()?;
+        processor.feature_end(0)?;
+        assert!(processor.into_inner().is_err());
+
+        let mut processor = HilbertSort::new(GeoJsonWriter::new(Vec::new()));
+        GeoJson(r#"{"type": "Point", "coordinates": [1, 2]}"#).process_geom(&mut processor)?;
+        assert!(processor.into_inner().is_err());
+
+        let mut processor = HilbertSort::new(GeoJsonWriter::new(Vec::new()));
+        GeoJson(r#"{"type": "FeatureCollection", "features": [{"type": "Feature", "properties": {}, "geometry": null}]}"#)
+            .process(&mut processor)?;
+        assert!(processor.into_inner().is_ok());
+        Ok(())
+    }
+}
@@ -20,17 +20,21 @@
 //! |               |                         [`GeozeroGeometry`]                                                                              | Dimensions |                        [`GeozeroDatasource`]                                         | Geometry Conversion |            [`GeomProcessor`]                    |
 //! |---------------|--------------------------------------------------------------------------------------------------------------------------|------------|--------------------------------------------------------------------------------------|---------------------|-------------------------------------------------|
 //! | CSV           | [csv::Csv], [csv::CsvString]                                                                                             | XY         | -                                                                                    | [ProcessToCsv]      | [CsvWriter](csv::CsvWriter)                     |
+//! | DXF           |                                                                                                                          | XYZ        | [DxfReader](dxf::DxfReader)                                                          |                     |                                                 |
+//! | Esri JSON     | [esrijson::EsriJson]                                                                                                     | XYZM       | [esrijson::EsriJsonReader], [esrijson::EsriJson]                                     | [ToEsriJson]        | [EsriJsonWriter](esrijson::EsriJsonWriter)      |
 //! | GDAL          | `gdal::vector::Geometry`                                                                                                 | XYZ        | -                                                                                    | [ToGdal]            | [GdalWriter](gdal::GdalWriter)                  |
-//! | geo-types     | `geo_types::Geometry<f64>`                                                                                               | XY         | -                                                                                    | [ToGeo]             | [GeoWriter](geo_types::GeoWriter)               |
+//! | geo-types     | `geo_types::Geometry<f64>`, `geo_types::Point<f64>`, ...                                                                 | XY         | -                                                                                    | [ToGeo]             | [GeoWriter](geo_types::GeoWriter)               |
 //! | GeoJSON       | [GeoJson](geojson::GeoJson), [GeoJsonString](geojson::GeoJsonString)                                                     | XYZ        | [GeoJsonReader](geojson::GeoJsonReader), [GeoJson](geojson::GeoJson)                 | [ToJson]            | [GeoJsonWriter](geojson::GeoJsonWriter)         |
 //! | GeoJSON Lines |                                                                                                                          | XYZ        | [GeoJsonLineReader](geojson::GeoJsonLineReader)                                      |                     | [GeoJsonLineWriter](geojson::GeoJsonLineWriter) |
+//! | GeoParquet    |                                                                                                                          | XYZM       | [GeoParquetReader](geoparquet::GeoParquetReader)                                     | -                   | [GeoArrowWriter](geoparquet::GeoArrowWriter)    |
 //! | GEOS          | `geos::Geometry`                                                                                                         | XYZ        | -                                                                                    | [ToGeos]            | [GeosWriter](geos::GeosWriter)                  |
 //! | GPX           |                                                                                                                          | XY         | [GpxReader](gpx::GpxReader)                                                          |                     |                                                 |
 //! | MVT           | [mvt::tile::Feature]                                                                                                     | XY         | [mvt::tile::Layer]                                                                   | [ToMvt]             | [MvtWriter](mvt::MvtWriter)                     |
-//! | Shapefile     | -                                                                                                                        | XYZM       | [shp::ShpReader]                                                                     |                     |                                                 |
-//! | SVG           | -                                                                                                                        | XY         | -                                                                                    | [ToSvg]             | [SvgWriter](svg::SvgWriter)                     |
+//! | Shapefile     | -                                                                                                                        | XYZM       | [shp::ShpReader]                                                                     |                     | [ShpWriter](shp::ShpWriter)                     |
+//! | SVG           | [SvgString](svg::SvgString)                                                                                              | XY         | [SvgReader](svg::SvgReader)                                                          | [ToSvg]             | [SvgWriter](svg::SvgWriter)                     |
 //! | WKB           | [Wkb](wkb::Wkb), [Ewkb](wkb::Ewkb), [GpkgWkb](wkb::GpkgWkb), [SpatiaLiteWkb](wkb::SpatiaLiteWkb), [MySQL](wkb::MySQLWkb) | XYZM       | -                                                                                    | [ToWkb]             | [WkbWriter](wkb::WkbWriter)                     |
 //! | WKT           | [wkt::WktStr], [wkt::WktString], [wkt::EwktStr], [wkt::EwktString]                                                       | XYZM       | [wkt::WktReader], [wkt::WktStr], [wkt::WktString], [wkt::EwktStr], [wkt::EwktString] | [ToWkt]             | [WktWriter](wkt::WktWriter)                     |
+//! | WKT Lines     |                                                                                                                          | XYZM       | [wkt::WktLineReader]                                                                 |                     |                                                 |
 
 #![warn(clippy::uninlined_format_args)]
 #![allow(
@@ -48,19 +52,51 @@
     clippy::struct_excessive_bools
 )]
 
+mod add_properties;
 mod api;
+pub mod bounds;
+pub mod builder;
+mod cancel;
+mod coord_visitor;
+mod crs;
 pub mod error;
+pub mod events;
+mod explode;
 mod feature_processor;
+mod fixup;
+mod float_format;
 mod geometry_processor;
+mod geometry_seq;
+mod hilbert_sort;
+mod linearize;
 mod multiplex;
+pub mod packed_rtree;
+mod progress;
 mod property_processor;
+mod rename;
+mod spool;
+mod validate;
 mod wrap;
 
+pub use add_properties::*;
 pub use api::*;
+pub use bounds::ToGeomStats;
+pub use cancel::*;
+pub use coord_visitor::*;
+pub use crs::Crs;
+pub use explode::*;
 pub use feature_processor::*;
+pub use fixup::*;
+pub use float_format::*;
 pub use geometry_processor::*;
+pub use hilbert_sort::*;
+pub use linearize::*;
 pub use multiplex::*;
+pub use progress::*;
 pub use property_processor::*;
+pub use rename::*;
+pub use spool::{FeatureEventBuf, FeatureSpool, DEFAULT_SPOOL_MEMORY};
+pub use validate::*;
 pub use wrap::*;
 
 #[cfg(feature = "with-csv")]
@@ -68,6 +104,14 @@ pub mod csv;
 #[cfg(feature = "with-csv")]
 pub use crate::csv::conversion::*;
 
+#[cfg(feature = "with-dxf")]
+pub mod dxf;
+
+#[cfg(feature = "with-esrijson")]
+pub mod esrijson;
+#[cfg(feature = "with-esrijson")]
+pub use crate::esrijson::conversion::*;
+
 #[cfg(feature = "with-gdal")]
 pub mod gdal;
 #[cfg(feature = "with-gdal")]
@@ -83,6 +127,9 @@ pub mod geojson;
 #[cfg(feature = "with-geojson")]
 pub use crate::geojson::conversion::*;
 
+#[cfg(feature = "with-geoparquet")]
+pub mod geoparquet;
+
 #[cfg(feature = "with-geos")]
 pub mod geos;
 #[cfg(feature = "with-geos")]
@@ -94,6 +141,9 @@ pub mod gpkg;
 #[cfg(feature = "with-gpx")]
 pub mod gpx;
 
+#[cfg(feature = "with-mysql-sqlx")]
+pub mod mysql;
+
 #[cfg(any(
     feature = "with-postgis-diesel",
     feature = "with-postgis-postgres",
@@ -101,6 +151,19 @@ pub mod gpx;
 ))]
 pub mod postgis;
 
+#[cfg(feature = "with-range-client")]
+pub mod range_client;
+
+#[cfg(feature = "with-rstar")]
+pub mod rstar;
+#[cfg(feature = "with-rstar")]
+pub use crate::rstar::ToRTree;
+
+#[cfg(feature = "with-serde")]
+pub mod serde;
+#[cfg(feature = "with-serde")]
+pub use crate::serde::DeserializeFeatures;
+
 #[cfg(feature = "with-shp")]
 pub mod shp;
 
@@ -0,0 +1,529 @@
+//! Linearization of curve geometries.
+use crate::error::{GeozeroError, Result};
+use crate::wrap::delegate_feature_processor;
+use crate::{
+    ColumnDef, ColumnValue, CoordDimensions, Crs, FeatureId, FeatureProcessor, GeomProcessor,
+    PropertyProcessor,
+};
+use std::f64::consts::PI;
+
+/// Maximal number of segments per linearized arc
+pub(crate) const MAX_ARC_SEGMENTS: usize = 1024;
+
+/// Check that a linearization parameter is positive and finite
+pub(crate) fn check_positive(name: &str, value: f64) -> Result<()> {
+    if value > 0.0 && value.is_finite() {
+        Ok(())
+    } else {
+        Err(GeozeroError::Geometry(format!(
+            "{name} must be positive and finite, got {value}"
+        )))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct Coord {
+    x: f64,
+    y: f64,
+    z: Option<f64>,
+    m: Option<f64>,
+}
+
+impl Coord {
+    fn same_xy(&self, other: &Coord) -> bool {
+        self.x == other.x && self.y == other.y
+    }
+}
+
+/// Parent geometry of curves
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Parent {
+    CurvePolygon,
+    MultiCurve,
+    MultiSurface,
+    Other,
+}
+
+/// Wraps another processor, replacing curve geometries with their linear approximation.
+///
+/// `CircularString` and `CompoundCurve` are converted to `LineString`, `CurvePolygon`
+/// to `Polygon`, `MultiCurve` to `MultiLineString` and `MultiSurface` to `MultiPolygon`.
+/// All other geometries are passed through unchanged.
+/// This allows processing curve geometries with writers without curve support,
+/// like GEOS, geo-types or MVT.
+pub struct LinearizeCurves<P> {
+    /// The underlying processor
+    pub inner: P,
+    max_angle: f64,
+    max_segment_length: Option<f64>,
+    max_deviation: Option<f64>,
+    parents: Vec<Parent>,
+    in_compound: bool,
+    in_arc: bool,
+    /// Buffered coordinates of the current curve
+    buf: Vec<Coord>,
+    /// Start of the current segment in `buf`
+    seg_start: usize,
+    /// Coordinates were received with `coordinate` instead of `xy`
+    multi_dim: bool,
+}
+
+impl<P: GeomProcessor> LinearizeCurves<P> {
+    /// Linearize with a maximal angle of 2.8125° (32 segments per quarter circle) per segment.
+    pub fn new(inner: P) -> Self {
+        LinearizeCurves {
+            inner,
+            max_angle: PI / 2.0 / 32.0,
+            max_segment_length: None,
+            max_deviation: None,
+            parents: Vec::new(),
+            in_compound: false,
+            in_arc: false,
+            buf: Vec::new(),
+            seg_start: 0,
+            multi_dim: false,
+        }
+    }
+
+    /// Set maximal angle in radians between the vertices of a linearized arc
+    pub fn set_max_angle(&mut self, max_angle: f64) -> Result<()> {
+        check_positive("max_angle", max_angle)?;
+        self.max_angle = max_angle;
+        Ok(())
+    }
+
+    /// Set maximal length of segments of a linearized arc
+    pub fn set_max_segment_length(&mut self, max_segment_length: Option<f64>) -> Result<()> {
+        if let Some(len) = max_segment_length {
+            check_positive("max_segment_length", len)?;
+        }
+        self.max_segment_length = max_segment_length;
+        Ok(())
+    }
+
+    /// Set maximal distance between a linearized arc and the original arc
+    ///
+    /// Arcs are linearized with at most 1024 segments per arc.
+    pub fn set_max_deviation(&mut self, max_deviation: Option<f64>) -> Result<()> {
+        if let Some(deviation) = max_deviation {
+            check_positive("max_deviation", deviation)?;
+        }
+        self.max_deviation = max_deviation;
+        Ok(())
+    }
+
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+
+    fn buffering(&self) -> bool {
+        self.in_compound || self.in_arc
+    }
+
+    /// Linear geometries are untagged in curve containers
+    fn tagged(&self) -> bool {
+        !matches!(
+            self.parents.last(),
+            Some(Parent::CurvePolygon | Parent::MultiCurve | Parent::MultiSurface)
+        )
+    }
+
+    /// Remove duplicate vertex at the start of the current segment of a compound curve
+    fn join_segment(&mut self) {
+        let start = self.seg_start;
+        if start > 0 && self.buf.len() > start && self.buf[start].same_xy(&self.buf[start - 1]) {
+            self.buf.remove(start);
+        }
+    }
+
+    fn emit_linestring(&mut self, idx: usize) -> Result<()> {
+        let tagged = self.tagged();
+        let mut coords = std::mem::take(&mut self.buf);
+        self.inner.linestring_begin(tagged, coords.len(), idx)?;
+        for (i, c) in coords.iter().enumerate() {
+            if self.multi_dim {
+                self.inner.coordinate(c.x, c.y, c.z, c.m, None, None, i)?;
+            } else {
+                self.inner.xy(c.x, c.y, i)?;
+            }
+        }
+        self.inner.linestring_end(tagged, idx)?;
+        // Reuse buffer allocation
+        coords.clear();
+        self.buf = coords;
+        Ok(())
+    }
+
+    /// Angle step for an arc with the given radius
+    fn angle_step(&self, radius: f64) -> f64 {
+        let mut step = self.max_angle;
+        if let Some(len) = self.max_segment_length {
+            if len < 2.0 * radius {
+                step = step.min(2.0 * (len / (2.0 * radius)).asin());
+            }
+        }
+        if let Some(deviation) = self.max_deviation {
+            if deviation < radius {
+                step = step.min(2.0 * (1.0 - deviation / radius).acos());
+            }
+        }
+        step
+    }
+
+    /// Append linearized arc through `p0`, `p1` and `p2`, excluding `p0`.
+    fn linearize_arc(&self, p0: &Coord, p1: &Coord, p2: &Coord, out: &mut Vec<Coord>) {
+        let (center, ccw) = if p0.same_xy(p2) && !p0.same_xy(p1) {
+            // Full circle
+            let center = ((p0.x + p1.x) / 2.0, (p0.y + p1.y) / 2.0);
+            (center, true)
+        } else {
+            let d = 2.0 * (p0.x * (p1.y - p2.y) + p1.x * (p2.y - p0.y) + p2.x * (p0.y - p1.y));
+            if d.abs() < f64::EPSILON {
+                // Collinear points
+                out.push(*p1);
+                out.push(*p2);
+                return;
+            }
+            let s0 = p0.x * p0.x + p0.y * p0.y;
+            let s1 = p1.x * p1.x + p1.y * p1.y;
+            let s2 = p2.x * p2.x + p2.y * p2.y;
+            let ux = (s0 * (p1.y - p2.y) + s1 * (p2.y - p0.y) + s2 * (p0.y - p1.y)) / d;
+            let uy = (s0 * (p2.x - p1.x) + s1 * (p0.x - p2.x) + s2 * (p1.x - p0.x)) / d;
+            let cross = (p1.x - p0.x) * (p2.y - p1.y) - (p1.y - p0.y) * (p2.x - p1.x);
+            ((ux, uy), cross > 0.0)
+        };
+        let radius = (p0.x - center.0).hypot(p0.y - center.1);
+        let step = self.angle_step(radius);
+        let a0 = (p0.y - center.1).atan2(p0.x - center.0);
+        let a1 = (p1.y - center.1).atan2(p1.x - center.0);
+        let a2 = (p2.y - center.1).atan2(p2.x - center.0);
+        let mut segment = |pa: &Coord, a_start: f64, a_end: f64, pb: &Coord| {
+            let sweep = sweep_angle(a_start, a_end, ccw);
+            let n = ((sweep.abs() / step) - 1e-9)
+                .ceil()
+                .clamp(1.0, MAX_ARC_SEGMENTS as f64) as usize;
+            for i in 1..n {
+                let t = i as f64 / n as f64;
+                let angle = a_start + sweep * t;
+                out.push(Coord {
+                    x: center.0 + radius * angle.cos(),
+                    y: center.1 + radius * angle.sin(),
+                    z: lerp(pa.z, pb.z, t),
+                    m: lerp(pa.m, pb.m, t),
+                });
+            }
+            out.push(*pb);
+        };
+        segment(p0, a0, a1, p1);
+        segment(p1, a1, a2, p2);
+    }
+
+    /// Replace the buffered circular string segment with its linearization
+    fn linearize_segment(&mut self) {
+        let points = self.buf.split_off(self.seg_start);
+        if points.len() < 3 {
+            self.buf.extend(points);
+            return;
+        }
+        let mut out = vec![points[0]];
+        let mut i = 0;
+        while i + 2 < points.len() {
+            self.linearize_arc(&points[i], &points[i + 1], &points[i + 2], &mut out);
+            i += 2;
+        }
+        self.buf.extend(out);
+    }
+}
+
+/// Angle from `from` to `to` in the given direction
+fn sweep_angle(from: f64, to: f64, ccw: bool) -> f64 {
+    let mut d = to - from;
+    if ccw {
+        while d <= 0.0 {
+            d += 2.0 * PI;
+        }
+    } else {
+        while d >= 0.0 {
+            d -= 2.0 * PI;
+        }
+    }
+    d
+}
+
+fn lerp(a: Option<f64>, b: Option<f64>, t: f64) -> Option<f64> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a + (b - a) * t),
+        _ => a,
+    }
+}
+
+impl<P: GeomProcessor> GeomProcessor for LinearizeCurves<P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.inner.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.inner.multi_dim()
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.inner.srid(srid)
+    }
+    fn envelope(&mut self, envelope: &[f64], dims: CoordDimensions, idx: usize) -> Result<()> {
+        self.inner.envelope(envelope, dims, idx)
+    }
+    fn size_hint_total_coords(&mut self, count: usize) -> Result<()> {
+        self.inner.size_hint_total_coords(count)
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        if self.buffering() {
+            self.multi_dim = false;
+            self.buf.push(Coord {
+                x,
+                y,
+                z: None,
+                m: None,
+            });
+            Ok(())
+        } else {
+            self.inner.xy(x, y, idx)
+        }
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        if self.buffering() {
+            self.multi_dim = true;
+            self.buf.push(Coord { x, y, z, m });
+            Ok(())
+        } else {
+            self.inner.coordinate(x, y, z, m, t, tm, idx)
+        }
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.inner.empty_point(idx)
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.inner.point_begin(idx)
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.point_end(idx)
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.multipoint_begin(size, idx)
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.multipoint_end(idx)
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        if self.in_compound {
+            self.seg_start = self.buf.len();
+            Ok(())
+        } else {
+            self.inner.linestring_begin(tagged, size, idx)
+        }
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        if self.in_compound {
+            self.join_segment();
+            Ok(())
+        } else {
+            self.inner.linestring_end(tagged, idx)
+        }
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.multilinestring_begin(size, idx)
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.multilinestring_end(idx)
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.parents.push(Parent::Other);
+        self.inner.polygon_begin(tagged, size, idx)
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.parents.pop();
+        self.inner.polygon_end(tagged, idx)
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.multipolygon_begin(size, idx)
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.multipolygon_end(idx)
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.parents.push(Parent::Other);
+        self.inner.geometrycollection_begin(size, idx)
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.parents.pop();
+        self.inner.geometrycollection_end(idx)
+    }
+    fn circularstring_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.in_arc = true;
+        self.seg_start = self.buf.len();
+        Ok(())
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        self.in_arc = false;
+        self.linearize_segment();
+        if self.in_compound {
+            self.join_segment();
+            Ok(())
+        } else {
+            self.emit_linestring(idx)
+        }
+    }
+    fn compoundcurve_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.in_compound = true;
+        self.buf.clear();
+        self.seg_start = 0;
+        Ok(())
+    }
+    fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        self.in_compound = false;
+        self.emit_linestring(idx)
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        let tagged = self.tagged();
+        self.parents.push(Parent::CurvePolygon);
+        self.inner.polygon_begin(tagged, size, idx)
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.parents.pop();
+        let tagged = self.tagged();
+        self.inner.polygon_end(tagged, idx)
+    }
+    fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.parents.push(Parent::MultiCurve);
+        self.inner.multilinestring_begin(size, idx)
+    }
+    fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        self.parents.pop();
+        self.inner.multilinestring_end(idx)
+    }
+    fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.parents.push(Parent::MultiSurface);
+        self.inner.multipolygon_begin(size, idx)
+    }
+    fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        self.parents.pop();
+        self.inner.multipolygon_end(idx)
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.inner.triangle_begin(tagged, size, idx)
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.inner.triangle_end(tagged, idx)
+    }
+    fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.polyhedralsurface_begin(size, idx)
+    }
+    fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.polyhedralsurface_end(idx)
+    }
+    fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.tin_begin(size, idx)
+    }
+    fn tin_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.tin_end(idx)
+    }
+}
+
+delegate_feature_processor!(LinearizeCurves);
+
+#[cfg(test)]
+#[cfg(all(feature = "with-wkb", feature = "with-wkt"))]
+mod test {
+    use super::*;
+    use crate::wkb::Ewkb;
+    use crate::wkt::WktWriter;
+    use crate::GeozeroGeometry;
+
+    fn linearize(ewkb: &str, max_angle: f64) -> String {
+        let ewkb = Ewkb(hex::decode(ewkb).unwrap());
+        let mut out = Vec::new();
+        let mut processor = LinearizeCurves::new(WktWriter::new(&mut out));
+        processor.set_max_angle(max_angle).unwrap();
+        ewkb.process_geom(&mut processor).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn circularstring() {
+        // CIRCULARSTRING(0 0,1 1,2 0)
+        let ewkb = "01080000000300000000000000000000000000000000000000000000000000F03F000000000000F03F00000000000000400000000000000000";
+        assert_eq!(linearize(ewkb, PI / 2.0), "LINESTRING(0 0,1 1,2 0)");
+
+        let wkt = linearize(ewkb, PI / 2.0 / 32.0);
+        let coords: Vec<(f64, f64)> = wkt
+            .trim_start_matches("LINESTRING(")
+            .trim_end_matches(')')
+            .split(',')
+            .map(|c| {
+                let mut xy = c.split(' ').map(|v| v.parse::<f64>().unwrap());
+                (xy.next().unwrap(), xy.next().unwrap())
+            })
+            .collect();
+        assert_eq!(coords.len(), 65);
+        assert_eq!(coords[32], (1.0, 1.0));
+        for (x, y) in coords {
+            assert!(((x - 1.0).hypot(y) - 1.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn curve_containers() {
+        // COMPOUNDCURVE(CIRCULARSTRING(0 0,1 1,2 0),(2 0,3 0))
+        assert_eq!(
+            linearize("01090000000200000001080000000300000000000000000000000000000000000000000000000000F03F000000000000F03F000000000000004000000000000000000102000000020000000000000000000040000000000000000000000000000008400000000000000000", PI / 2.0),
+            "LINESTRING(0 0,1 1,2 0,3 0)"
+        );
+        // MULTISURFACE(CURVEPOLYGON(COMPOUNDCURVE(CIRCULARSTRING(0 0,1 1,2 0),(2 0,3 0,3 -1,0 -1,0 0))))
+        assert_eq!(
+            linearize("010C00000001000000010A0000000100000001090000000200000001080000000300000000000000000000000000000000000000000000000000F03F000000000000F03F0000000000000040000000000000000001020000000500000000000000000000400000000000000000000000000000084000000000000000000000000000000840000000000000F0BF0000000000000000000000000000F0BF00000000000000000000000000000000", PI / 2.0),
+            "MULTIPOLYGON(((0 0,1 1,2 0,3 0,3 -1,0 -1,0 0)))"
+        );
+        // MULTICURVE((0 0,5 5),CIRCULARSTRING(4 0,4 4,8 4))
+        assert_eq!(
+            linearize("010B000000020000000102000000020000000000000000000000000000000000000000000000000014400000000000001440010800000003000000000000000000104000000000000000000000000000001040000000000000104000000000000020400000000000001040", PI / 2.0),
+            "MULTILINESTRING((0 0,5 5),(4 0,4 4,8 4))"
+        );
+    }
+
+    #[test]
+    fn max_segment_length() -> Result<()> {
+        // CIRCULARSTRING(0 0,1 1,2 0)
+        let ewkb = Ewkb(hex::decode("01080000000300000000000000000000000000000000000000000000000000F03F000000000000F03F00000000000000400000000000000000").unwrap());
+        let mut stats = LinearizeCurves::new(crate::bounds::GeomStats::new());
+        stats.set_max_angle(PI)?;
+        stats.set_max_segment_length(Some(0.5))?;
+        ewkb.process_geom(&mut stats).unwrap();
+        // Chord of 0.5 on unit circle: ~0.505 rad, 4 segments per quarter circle
+        assert_eq!(stats.into_inner().vertex_count(), 9);
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_parameters() -> Result<()> {
+        let mut stats = LinearizeCurves::new(crate::bounds::GeomStats::new());
+        assert!(stats.set_max_angle(0.0).is_err());
+        assert!(stats.set_max_angle(-1.0).is_err());
+        assert!(stats.set_max_angle(f64::NAN).is_err());
+        assert!(stats.set_max_segment_length(Some(f64::INFINITY)).is_err());
+        assert!(stats.set_max_deviation(Some(0.0)).is_err());
+
+        // Tiny deviations are capped at MAX_ARC_SEGMENTS per arc segment
+        // CIRCULARSTRING(0 0,1 1,2 0)
+        let ewkb = Ewkb(hex::decode("01080000000300000000000000000000000000000000000000000000000000F03F000000000000F03F00000000000000400000000000000000").unwrap());
+        stats.set_max_deviation(Some(1e-300))?;
+        ewkb.process_geom(&mut stats)?;
+        assert_eq!(stats.into_inner().vertex_count(), 2 * MAX_ARC_SEGMENTS + 1);
+        Ok(())
+    }
+}
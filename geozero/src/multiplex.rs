@@ -1,7 +1,10 @@
-use crate::error::Result;
-use crate::feature_processor::FeatureProcessor;
-use crate::geometry_processor::GeomProcessor;
-use crate::property_processor::{ColumnValue, PropertyProcessor};
+use crate::crs::Crs;
+use crate::error::{GeozeroError, Result};
+use crate::feature_processor::{FeatureId, FeatureProcessor};
+use crate::geometry_processor::{CoordDimensions, GeomProcessor};
+use crate::property_processor::{ColumnDef, ColumnValue, PropertyProcessor};
+use crate::GeozeroDatasource;
+use std::marker::PhantomData;
 
 #[doc(hidden)]
 pub struct Multiplexer<P1: FeatureProcessor, P2: FeatureProcessor> {
@@ -24,10 +27,22 @@ impl<P1: FeatureProcessor, P2: FeatureProcessor> FeatureProcessor for Multiplexe
         self.p1.dataset_end()?;
         self.p2.dataset_end()
     }
+    fn crs(&mut self, crs: &Crs) -> Result<()> {
+        self.p1.crs(crs)?;
+        self.p2.crs(crs)
+    }
+    fn schema_begin(&mut self, columns: &[ColumnDef]) -> Result<()> {
+        self.p1.schema_begin(columns)?;
+        self.p2.schema_begin(columns)
+    }
     fn feature_begin(&mut self, idx: u64) -> Result<()> {
         self.p1.feature_begin(idx)?;
         self.p2.feature_begin(idx)
     }
+    fn feature_id(&mut self, id: &FeatureId) -> Result<()> {
+        self.p1.feature_id(id)?;
+        self.p2.feature_id(id)
+    }
     fn feature_end(&mut self, idx: u64) -> Result<()> {
         self.p1.feature_end(idx)?;
         self.p2.feature_end(idx)
@@ -50,11 +65,68 @@ impl<P1: FeatureProcessor, P2: FeatureProcessor> FeatureProcessor for Multiplexe
     }
 }
 
+/// Forward coordinate with the dimensions requested by `p`
+#[allow(clippy::too_many_arguments)]
+fn forward_coordinate<P: GeomProcessor + ?Sized>(
+    p: &mut P,
+    x: f64,
+    y: f64,
+    z: Option<f64>,
+    m: Option<f64>,
+    t: Option<f64>,
+    tm: Option<u64>,
+    idx: usize,
+) -> Result<()> {
+    if p.multi_dim() {
+        let dims = p.dimensions();
+        p.coordinate(
+            x,
+            y,
+            z.filter(|_| dims.z),
+            m.filter(|_| dims.m),
+            t.filter(|_| dims.t),
+            tm.filter(|_| dims.tm),
+            idx,
+        )
+    } else {
+        p.xy(x, y, idx)
+    }
+}
+
 impl<P1: FeatureProcessor, P2: FeatureProcessor> GeomProcessor for Multiplexer<P1, P2> {
+    /// Union of the dimensions requested by both processors
+    fn dimensions(&self) -> CoordDimensions {
+        let (d1, d2) = (self.p1.dimensions(), self.p2.dimensions());
+        CoordDimensions {
+            z: d1.z || d2.z,
+            m: d1.m || d2.m,
+            t: d1.t || d2.t,
+            tm: d1.tm || d2.tm,
+        }
+    }
+    fn multi_dim(&self) -> bool {
+        self.p1.multi_dim() || self.p2.multi_dim()
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.p1.srid(srid)?;
+        self.p2.srid(srid)
+    }
+    fn envelope(&mut self, envelope: &[f64], dims: CoordDimensions, idx: usize) -> Result<()> {
+        self.p1.envelope(envelope, dims, idx)?;
+        self.p2.envelope(envelope, dims, idx)
+    }
+    fn size_hint_total_coords(&mut self, count: usize) -> Result<()> {
+        self.p1.size_hint_total_coords(count)?;
+        self.p2.size_hint_total_coords(count)
+    }
     fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
         self.p1.xy(x, y, idx)?;
         self.p2.xy(x, y, idx)
     }
+    fn coords(&mut self, coords: &[f64], dims: CoordDimensions, idx: usize) -> Result<()> {
+        self.p1.coords(coords, dims, idx)?;
+        self.p2.coords(coords, dims, idx)
+    }
     fn coordinate(
         &mut self,
         x: f64,
@@ -65,8 +137,12 @@ impl<P1: FeatureProcessor, P2: FeatureProcessor> GeomProcessor for Multiplexer<P
         tm: Option<u64>,
         idx: usize,
     ) -> Result<()> {
-        self.p1.coordinate(x, y, z, m, t, tm, idx)?;
-        self.p2.coordinate(x, y, z, m, t, tm, idx)
+        forward_coordinate(&mut self.p1, x, y, z, m, t, tm, idx)?;
+        forward_coordinate(&mut self.p2, x, y, z, m, t, tm, idx)
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.p1.empty_point(idx)?;
+        self.p2.empty_point(idx)
     }
     fn point_begin(&mut self, idx: usize) -> Result<()> {
         self.p1.point_begin(idx)?;
@@ -188,4 +264,646 @@ impl<P1: FeatureProcessor, P2: FeatureProcessor> PropertyProcessor for Multiplex
             .property(i, colname, colval)
             .and(self.p2.property(i, colname, colval))
     }
+    fn column_property(
+        &mut self,
+        i: usize,
+        column: usize,
+        colname: &str,
+        colval: &ColumnValue,
+    ) -> Result<bool> {
+        self.p1
+            .column_property(i, column, colname, colval)
+            .and(self.p2.column_property(i, column, colname, colval))
+    }
+}
+
+/// Processor forwarding all events to an arbitrary number of processors.
+///
+/// Processing continues as long as at least one processor succeeds. A processor returning
+/// an error doesn't receive any further events. The collected errors are returned at the end
+/// of the dataset, or as soon as all processors failed.
+///
+/// # Usage example:
+///
+/// ```rust
+/// # #[cfg(all(feature = "with-geojson", feature = "with-wkt"))]
+/// # {
+/// use geozero::geojson::{GeoJsonReader, GeoJsonWriter};
+/// use geozero::wkt::WktWriter;
+/// use geozero::{GeozeroDatasource, Tee};
+///
+/// let geojson = r#"{"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [1, 1]}}"#;
+/// let mut json_out: Vec<u8> = Vec::new();
+/// let mut wkt_out: Vec<u8> = Vec::new();
+/// let mut tee = Tee::new()
+///     .with(GeoJsonWriter::new(&mut json_out))
+///     .with(WktWriter::new(&mut wkt_out));
+/// GeoJsonReader(geojson.as_bytes()).process(&mut tee).unwrap();
+/// drop(tee);
+/// assert_eq!(String::from_utf8(wkt_out).unwrap(), "POINT(1 1)");
+/// # }
+/// ```
+#[derive(Default)]
+pub struct Tee<'a> {
+    processors: Vec<Box<dyn FeatureProcessor + 'a>>,
+    /// Processors which returned an error
+    failed: Vec<bool>,
+    errors: Vec<GeozeroError>,
+}
+
+impl<'a> Tee<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create processor forwarding to all `processors`.
+    pub fn from_processors(processors: Vec<Box<dyn FeatureProcessor + 'a>>) -> Self {
+        let failed = vec![false; processors.len()];
+        Tee {
+            processors,
+            failed,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Add a processor.
+    pub fn with<P: FeatureProcessor + 'a>(mut self, processor: P) -> Self {
+        self.push(Box::new(processor));
+        self
+    }
+
+    /// Add a boxed processor.
+    pub fn push(&mut self, processor: Box<dyn FeatureProcessor + 'a>) {
+        self.processors.push(processor);
+        self.failed.push(false);
+    }
+
+    /// Errors collected so far.
+    pub fn errors(&self) -> &[GeozeroError] {
+        &self.errors
+    }
+
+    /// Consume and return the wrapped processors.
+    pub fn into_inner(self) -> Vec<Box<dyn FeatureProcessor + 'a>> {
+        self.processors
+    }
+
+    fn each<F>(&mut self, mut f: F) -> Result<()>
+    where
+        F: FnMut(&mut dyn FeatureProcessor) -> Result<()>,
+    {
+        for (processor, failed) in self.processors.iter_mut().zip(self.failed.iter_mut()) {
+            if *failed {
+                continue;
+            }
+            if let Err(e) = f(processor.as_mut()) {
+                *failed = true;
+                self.errors.push(e);
+            }
+        }
+        if !self.failed.is_empty() && self.failed.iter().all(|failed| *failed) {
+            return Err(self.take_error());
+        }
+        Ok(())
+    }
+
+    fn take_error(&mut self) -> GeozeroError {
+        if self.errors.len() == 1 {
+            self.errors.remove(0)
+        } else {
+            GeozeroError::Multiple(std::mem::take(&mut self.errors))
+        }
+    }
+}
+
+impl FeatureProcessor for Tee<'_> {
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.each(|p| p.dataset_begin(name))
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        self.each(|p| p.dataset_end())?;
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(self.take_error())
+        }
+    }
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        self.each(|p| p.feature_begin(idx))
+    }
+    fn crs(&mut self, crs: &Crs) -> Result<()> {
+        self.each(|p| p.crs(crs))
+    }
+    fn schema_begin(&mut self, columns: &[ColumnDef]) -> Result<()> {
+        self.each(|p| p.schema_begin(columns))
+    }
+    fn feature_id(&mut self, id: &FeatureId) -> Result<()> {
+        self.each(|p| p.feature_id(id))
+    }
+    fn feature_end(&mut self, idx: u64) -> Result<()> {
+        self.each(|p| p.feature_end(idx))
+    }
+    fn properties_begin(&mut self) -> Result<()> {
+        self.each(|p| p.properties_begin())
+    }
+    fn properties_end(&mut self) -> Result<()> {
+        self.each(|p| p.properties_end())
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        self.each(|p| p.geometry_begin())
+    }
+    fn geometry_end(&mut self) -> Result<()> {
+        self.each(|p| p.geometry_end())
+    }
+}
+
+impl GeomProcessor for Tee<'_> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.processors
+            .iter()
+            .fold(CoordDimensions::xy(), |dims, p| {
+                let d = p.dimensions();
+                CoordDimensions {
+                    z: dims.z || d.z,
+                    m: dims.m || d.m,
+                    t: dims.t || d.t,
+                    tm: dims.tm || d.tm,
+                }
+            })
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.each(|p| p.srid(srid))
+    }
+    fn envelope(&mut self, envelope: &[f64], dims: CoordDimensions, idx: usize) -> Result<()> {
+        self.each(|p| p.envelope(envelope, dims, idx))
+    }
+    fn size_hint_total_coords(&mut self, count: usize) -> Result<()> {
+        self.each(|p| p.size_hint_total_coords(count))
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.each(|p| p.xy(x, y, idx))
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.each(|p| forward_coordinate(p, x, y, z, m, t, tm, idx))
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.empty_point(idx))
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.point_begin(idx))
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.point_end(idx))
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.multipoint_begin(size, idx))
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.multipoint_end(idx))
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.linestring_begin(tagged, size, idx))
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.each(|p| p.linestring_end(tagged, idx))
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.multilinestring_begin(size, idx))
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.multilinestring_end(idx))
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.polygon_begin(tagged, size, idx))
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.each(|p| p.polygon_end(tagged, idx))
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.multipolygon_begin(size, idx))
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.multipolygon_end(idx))
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.geometrycollection_begin(size, idx))
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.geometrycollection_end(idx))
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.circularstring_begin(size, idx))
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.circularstring_end(idx))
+    }
+    fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.compoundcurve_begin(size, idx))
+    }
+    fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.compoundcurve_end(idx))
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.curvepolygon_begin(size, idx))
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.curvepolygon_end(idx))
+    }
+    fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.multicurve_begin(size, idx))
+    }
+    fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.multicurve_end(idx))
+    }
+    fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.multisurface_begin(size, idx))
+    }
+    fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.multisurface_end(idx))
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.triangle_begin(tagged, size, idx))
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.each(|p| p.triangle_end(tagged, idx))
+    }
+    fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.polyhedralsurface_begin(size, idx))
+    }
+    fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.polyhedralsurface_end(idx))
+    }
+    fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.tin_begin(size, idx))
+    }
+    fn tin_end(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.tin_end(idx))
+    }
+}
+
+impl PropertyProcessor for Tee<'_> {
+    /// Processing is aborted, when all processors request it.
+    fn property(&mut self, i: usize, colname: &str, colval: &ColumnValue) -> Result<bool> {
+        let mut abort = true;
+        self.each(|p| {
+            abort &= p.property(i, colname, colval)?;
+            Ok(())
+        })?;
+        Ok(abort)
+    }
+    fn column_property(
+        &mut self,
+        i: usize,
+        column: usize,
+        colname: &str,
+        colval: &ColumnValue,
+    ) -> Result<bool> {
+        let mut abort = true;
+        self.each(|p| {
+            abort &= p.column_property(i, column, colname, colval)?;
+            Ok(())
+        })?;
+        Ok(abort)
+    }
+}
+
+/// Processor using the output of a processor as input of another processor.
+///
+/// All events are passed to the first processor. At the end of the dataset, `convert`
+/// creates a datasource from the first processor, which is then processed by the second
+/// processor.
+///
+/// # Usage example:
+///
+/// ```rust
+/// # #[cfg(all(feature = "with-geo", feature = "with-geojson", feature = "with-wkt"))]
+/// # {
+/// use geozero::geo_types::GeoWriter;
+/// use geozero::geojson::{GeoJsonReader, GeoJsonString};
+/// use geozero::wkt::WktWriter;
+/// use geozero::{error::GeozeroError, Chain, GeozeroDatasource, ToJson};
+///
+/// let geojson = r#"{"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [1, 1]}}"#;
+/// let mut wkt_out: Vec<u8> = Vec::new();
+/// let mut chain = Chain::new(
+///     GeoWriter::new(),
+///     |geo: &mut GeoWriter| {
+///         let geom = geo.take_geometry().ok_or(GeozeroError::GeometryFormat)?;
+///         Ok(GeoJsonString(geom.to_json()?))
+///     },
+///     WktWriter::new(&mut wkt_out),
+/// );
+/// GeoJsonReader(geojson.as_bytes()).process(&mut chain).unwrap();
+/// drop(chain);
+/// assert_eq!(String::from_utf8(wkt_out).unwrap(), "POINT(1 1)");
+/// # }
+/// ```
+pub struct Chain<P1, F, D, P2> {
+    p1: P1,
+    convert: F,
+    p2: P2,
+    datasource: PhantomData<D>,
+}
+
+impl<P1, F, D, P2> Chain<P1, F, D, P2>
+where
+    P1: FeatureProcessor,
+    F: FnMut(&mut P1) -> Result<D>,
+    D: GeozeroDatasource,
+    P2: FeatureProcessor,
+{
+    pub fn new(p1: P1, convert: F, p2: P2) -> Self {
+        Chain {
+            p1,
+            convert,
+            p2,
+            datasource: PhantomData,
+        }
+    }
+
+    /// Consume and return the wrapped processors.
+    pub fn into_inner(self) -> (P1, P2) {
+        (self.p1, self.p2)
+    }
+}
+
+impl<P1, F, D, P2> FeatureProcessor for Chain<P1, F, D, P2>
+where
+    P1: FeatureProcessor,
+    F: FnMut(&mut P1) -> Result<D>,
+    D: GeozeroDatasource,
+    P2: FeatureProcessor,
+{
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.p1.dataset_begin(name)
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        self.p1.dataset_end()?;
+        let mut datasource = (self.convert)(&mut self.p1)?;
+        datasource.process(&mut self.p2)
+    }
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        self.p1.feature_begin(idx)
+    }
+    fn crs(&mut self, crs: &Crs) -> Result<()> {
+        self.p1.crs(crs)
+    }
+    fn schema_begin(&mut self, columns: &[ColumnDef]) -> Result<()> {
+        self.p1.schema_begin(columns)
+    }
+    fn feature_id(&mut self, id: &FeatureId) -> Result<()> {
+        self.p1.feature_id(id)
+    }
+    fn feature_end(&mut self, idx: u64) -> Result<()> {
+        self.p1.feature_end(idx)
+    }
+    fn properties_begin(&mut self) -> Result<()> {
+        self.p1.properties_begin()
+    }
+    fn properties_end(&mut self) -> Result<()> {
+        self.p1.properties_end()
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        self.p1.geometry_begin()
+    }
+    fn geometry_end(&mut self) -> Result<()> {
+        self.p1.geometry_end()
+    }
+}
+
+impl<P1, F, D, P2> GeomProcessor for Chain<P1, F, D, P2>
+where
+    P1: FeatureProcessor,
+    F: FnMut(&mut P1) -> Result<D>,
+    D: GeozeroDatasource,
+    P2: FeatureProcessor,
+{
+    fn dimensions(&self) -> CoordDimensions {
+        self.p1.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.p1.multi_dim()
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.p1.srid(srid)
+    }
+    fn envelope(&mut self, envelope: &[f64], dims: CoordDimensions, idx: usize) -> Result<()> {
+        self.p1.envelope(envelope, dims, idx)
+    }
+    fn size_hint_total_coords(&mut self, count: usize) -> Result<()> {
+        self.p1.size_hint_total_coords(count)
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.p1.xy(x, y, idx)
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.p1.coordinate(x, y, z, m, t, tm, idx)
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.p1.empty_point(idx)
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.p1.point_begin(idx)
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.p1.point_end(idx)
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.p1.multipoint_begin(size, idx)
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.p1.multipoint_end(idx)
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.p1.linestring_begin(tagged, size, idx)
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.p1.linestring_end(tagged, idx)
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.p1.multilinestring_begin(size, idx)
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.p1.multilinestring_end(idx)
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.p1.polygon_begin(tagged, size, idx)
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.p1.polygon_end(tagged, idx)
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.p1.multipolygon_begin(size, idx)
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.p1.multipolygon_end(idx)
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.p1.geometrycollection_begin(size, idx)
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.p1.geometrycollection_end(idx)
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.p1.circularstring_begin(size, idx)
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        self.p1.circularstring_end(idx)
+    }
+    fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.p1.compoundcurve_begin(size, idx)
+    }
+    fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        self.p1.compoundcurve_end(idx)
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.p1.curvepolygon_begin(size, idx)
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.p1.curvepolygon_end(idx)
+    }
+    fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.p1.multicurve_begin(size, idx)
+    }
+    fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        self.p1.multicurve_end(idx)
+    }
+    fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.p1.multisurface_begin(size, idx)
+    }
+    fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        self.p1.multisurface_end(idx)
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.p1.triangle_begin(tagged, size, idx)
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.p1.triangle_end(tagged, idx)
+    }
+    fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.p1.polyhedralsurface_begin(size, idx)
+    }
+    fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        self.p1.polyhedralsurface_end(idx)
+    }
+    fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.p1.tin_begin(size, idx)
+    }
+    fn tin_end(&mut self, idx: usize) -> Result<()> {
+        self.p1.tin_end(idx)
+    }
+}
+
+impl<P1, F, D, P2> PropertyProcessor for Chain<P1, F, D, P2>
+where
+    P1: FeatureProcessor,
+    F: FnMut(&mut P1) -> Result<D>,
+    D: GeozeroDatasource,
+    P2: FeatureProcessor,
+{
+    fn property(&mut self, i: usize, colname: &str, colval: &ColumnValue) -> Result<bool> {
+        self.p1.property(i, colname, colval)
+    }
+    fn column_property(
+        &mut self,
+        i: usize,
+        column: usize,
+        colname: &str,
+        colval: &ColumnValue,
+    ) -> Result<bool> {
+        self.p1.column_property(i, column, colname, colval)
+    }
+}
+
+#[cfg(all(feature = "with-geojson", feature = "with-wkt"))]
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::geojson::GeoJsonReader;
+    use crate::wkt::WktWriter;
+
+    struct FailingProcessor;
+    impl GeomProcessor for FailingProcessor {
+        fn point_begin(&mut self, _idx: usize) -> Result<()> {
+            Err(GeozeroError::Geometry("point".to_string()))
+        }
+    }
+    impl PropertyProcessor for FailingProcessor {}
+    impl FeatureProcessor for FailingProcessor {}
+
+    const POINTS: &str = r#"{"type": "FeatureCollection", "features": [
+        {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [1, 1]}},
+        {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [2, 2]}}
+    ]}"#;
+
+    #[test]
+    fn tee() {
+        let mut out1: Vec<u8> = Vec::new();
+        let mut out2: Vec<u8> = Vec::new();
+        let mut tee = Tee::from_processors(vec![
+            Box::new(WktWriter::new(&mut out1)),
+            Box::new(WktWriter::new(&mut out2)),
+        ]);
+        GeoJsonReader(POINTS.as_bytes()).process(&mut tee).unwrap();
+        drop(tee);
+        assert_eq!(out1, b"POINT(1 1),POINT(2 2)");
+        assert_eq!(out1, out2);
+    }
+
+    #[test]
+    fn tee_errors() {
+        let mut out: Vec<u8> = Vec::new();
+        let mut tee = Tee::new()
+            .with(FailingProcessor)
+            .with(WktWriter::new(&mut out));
+        let result = GeoJsonReader(POINTS.as_bytes()).process(&mut tee);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "processing geometry `point`"
+        );
+        drop(tee);
+        // processing continues with the remaining processor
+        assert_eq!(out, b"POINT(1 1),POINT(2 2)");
+
+        let mut tee = Tee::new().with(FailingProcessor).with(FailingProcessor);
+        let result = GeoJsonReader(POINTS.as_bytes()).process(&mut tee);
+        assert_eq!(result.unwrap_err().to_string(), "errors in 2 processors");
+    }
+
+    #[test]
+    fn multiplexer_dims() {
+        let geojson = r#"{"type": "Feature", "properties": {}, "geometry": {"type": "LineString", "coordinates": [[1, 2, 3], [4, 5, 6]]}}"#;
+        let mut out2d: Vec<u8> = Vec::new();
+        let mut out3d: Vec<u8> = Vec::new();
+        let mut multiplexer = Multiplexer::new(
+            WktWriter::new(&mut out2d),
+            WktWriter::with_dims(&mut out3d, CoordDimensions::xyz()),
+        );
+        assert!(multiplexer.multi_dim());
+        assert!(multiplexer.dimensions().z);
+        GeoJsonReader(geojson.as_bytes())
+            .process(&mut multiplexer)
+            .unwrap();
+        drop(multiplexer);
+        assert_eq!(out2d, b"LINESTRING(1 2,4 5)");
+        assert_eq!(out3d, b"LINESTRING(1 2 3,4 5 6)");
+    }
 }
@@ -0,0 +1,198 @@
+use crate::error::Result;
+use crate::mvt::vector_tile::tile;
+use crate::mvt::{MvtWriter, TagsBuilder, TileValue};
+use crate::{
+    ColumnDef, ColumnValue, FeatureId, FeatureProcessor, GeomProcessor, PropertyProcessor,
+};
+
+/// MVT layer builder.
+///
+/// Encodes all features of a datasource into an MVT layer, including feature ids and
+/// properties as tags with deduplicated keys and values. Features without geometry are skipped.
+///
+/// # Usage example:
+///
+/// ```
+/// # #[cfg(feature = "with-geojson")]
+/// # {
+/// use geozero::geojson::GeoJson;
+/// use geozero::mvt::MvtLayerBuilder;
+/// use geozero::GeozeroDatasource;
+///
+/// let mut geojson = GeoJson(r#"{"type": "Feature", "properties": {"name": "Bern"}, "geometry": {"type": "Point", "coordinates": [7.44, 46.95]}}"#);
+/// let mut builder = MvtLayerBuilder::new("places", 4096, 5.0, 45.0, 11.0, 48.0);
+/// geojson.process(&mut builder).unwrap();
+/// let layer = builder.into_layer();
+/// assert_eq!(layer.keys, ["name"]);
+/// # }
+/// ```
+pub struct MvtLayerBuilder {
+    layer: tile::Layer,
+    mvt: MvtWriter,
+    tags: TagsBuilder<String>,
+    /// Key indexes of schema columns
+    column_keys: Vec<u32>,
+    feature_tags: Vec<u32>,
+}
+
+impl MvtLayerBuilder {
+    /// Create layer builder for tile bounds in map coordinate space, with no buffer.
+    pub fn new(name: &str, extent: u32, left: f64, bottom: f64, right: f64, top: f64) -> Self {
+        MvtLayerBuilder {
+            layer: tile::Layer {
+                version: 2,
+                name: name.to_string(),
+                extent: Some(extent),
+                ..Default::default()
+            },
+            mvt: MvtWriter::new(extent, left, bottom, right, top),
+            tags: TagsBuilder::new(),
+            column_keys: Vec::new(),
+            feature_tags: Vec::new(),
+        }
+    }
+
+    /// Finished layer with features and tag tables
+    pub fn into_layer(self) -> tile::Layer {
+        let mut layer = self.layer;
+        let (keys, values) = self.tags.into_tags();
+        layer.keys = keys;
+        layer.values = values.into_iter().map(Into::into).collect();
+        layer
+    }
+}
+
+impl FeatureProcessor for MvtLayerBuilder {
+    fn schema_begin(&mut self, columns: &[ColumnDef]) -> Result<()> {
+        self.column_keys = columns
+            .iter()
+            .map(|col| self.tags.insert_key(col.name.clone()))
+            .collect();
+        Ok(())
+    }
+    fn feature_begin(&mut self, _idx: u64) -> Result<()> {
+        self.mvt.reset();
+        self.feature_tags.clear();
+        Ok(())
+    }
+    fn feature_id(&mut self, id: &FeatureId) -> Result<()> {
+        self.mvt.feature_id(id)
+    }
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        if self.mvt.feature.geometry.is_empty() {
+            return Ok(());
+        }
+        let mut feature = self.mvt.feature.clone();
+        feature.tags = std::mem::take(&mut self.feature_tags);
+        self.layer.features.push(feature);
+        Ok(())
+    }
+}
+
+impl PropertyProcessor for MvtLayerBuilder {
+    fn property(&mut self, _idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        let (key, value) = self.tags.insert(name.to_string(), TileValue::from(value));
+        self.feature_tags.extend([key, value]);
+        Ok(false)
+    }
+    fn column_property(
+        &mut self,
+        idx: usize,
+        column: usize,
+        name: &str,
+        value: &ColumnValue,
+    ) -> Result<bool> {
+        let Some(&key) = self.column_keys.get(column) else {
+            return self.property(idx, name, value);
+        };
+        let value = self.tags.insert_value(TileValue::from(value));
+        self.feature_tags.extend([key, value]);
+        Ok(false)
+    }
+}
+
+impl GeomProcessor for MvtLayerBuilder {
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.mvt.srid(srid)
+    }
+    fn size_hint_total_coords(&mut self, count: usize) -> Result<()> {
+        self.mvt.size_hint_total_coords(count)
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.mvt.xy(x, y, idx)
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.mvt.point_begin(idx)
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.mvt.point_end(idx)
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.mvt.multipoint_begin(size, idx)
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.mvt.multipoint_end(idx)
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.mvt.linestring_begin(tagged, size, idx)
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.mvt.linestring_end(tagged, idx)
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.mvt.multilinestring_begin(size, idx)
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.mvt.multilinestring_end(idx)
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.mvt.polygon_begin(tagged, size, idx)
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.mvt.polygon_end(tagged, idx)
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.mvt.multipolygon_begin(size, idx)
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.mvt.multipolygon_end(idx)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::GeoJson;
+    use crate::mvt::tile::Value;
+    use crate::GeozeroDatasource;
+
+    #[test]
+    fn layer_with_tags() -> Result<()> {
+        let mut geojson = GeoJson(
+            r#"{"type": "FeatureCollection", "features": [
+                {"type": "Feature", "id": 1, "properties": {"capital": true, "name": "Bern"}, "geometry": {"type": "Point", "coordinates": [10, 10]}},
+                {"type": "Feature", "id": 2, "properties": {"capital": true, "name": "Zurich"}, "geometry": {"type": "Point", "coordinates": [20, 20]}},
+                {"type": "Feature", "properties": {"name": "Nowhere"}, "geometry": null}
+            ]}"#,
+        );
+        let mut builder = MvtLayerBuilder::new("places", 4096, 0.0, 0.0, 4096.0, 4096.0);
+        geojson.process(&mut builder)?;
+        let layer = builder.into_layer();
+        assert_eq!(layer.name, "places");
+        assert_eq!(layer.features.len(), 2);
+        assert_eq!(layer.keys, ["capital", "name"]);
+        assert_eq!(layer.values.len(), 3);
+        assert_eq!(layer.features[0].id, Some(1));
+        assert_eq!(layer.features[0].tags, [0, 0, 1, 1]);
+        assert_eq!(layer.features[1].tags, [0, 0, 1, 2]);
+        assert_eq!(
+            layer.values[2],
+            Value {
+                string_value: Some("Zurich".to_string()),
+                ..Default::default()
+            }
+        );
+        Ok(())
+    }
+}
@@ -3,6 +3,9 @@ mod mvt_commands;
 pub(crate) mod mvt_reader;
 pub(crate) mod mvt_writer;
 
+mod layer_builder;
+pub use layer_builder::MvtLayerBuilder;
+
 mod tag_builder;
 pub use tag_builder::TagsBuilder;
 
@@ -83,4 +86,48 @@ mod wkb {
             Ok(mvt.feature)
         }
     }
+
+    /// Layer named `layer` with a single feature in unscaled tile coordinates
+    impl FromWkb for tile::Layer {
+        fn from_wkb<R: Read>(rdr: &mut R, dialect: WkbDialect) -> Result<Self> {
+            let feature = tile::Feature::from_wkb(rdr, dialect)?;
+            Ok(tile::Layer {
+                version: 2,
+                name: "layer".to_string(),
+                features: vec![feature],
+                extent: Some(4096),
+                ..Default::default()
+            })
+        }
+    }
+
+    #[cfg(feature = "with-postgis-postgres")]
+    mod postgis_postgres {
+        use crate::mvt::tile;
+
+        crate::impl_postgres_postgis_decode!(tile::Layer);
+    }
+
+    #[cfg(feature = "with-postgis-sqlx")]
+    mod postgis_sqlx {
+        use crate::mvt::tile;
+
+        crate::impl_sqlx_postgis_type_info!(tile::Layer);
+        crate::impl_sqlx_postgis_decode!(tile::Layer);
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+
+        #[test]
+        fn layer_from_wkb() -> Result<()> {
+            // SELECT 'POINT(25 17)'::geometry
+            let wkb = hex::decode("010100000000000000000039400000000000003140").unwrap();
+            let layer = tile::Layer::from_wkb(&mut wkb.as_slice(), WkbDialect::Wkb)?;
+            assert_eq!(layer.features.len(), 1);
+            assert_eq!(layer.features[0].geometry, [9, 50, 34]);
+            Ok(())
+        }
+    }
 }
@@ -15,4 +15,18 @@ pub enum MvtError {
     GeometryFormat,
     #[error("too few coordinates in line or ring")]
     TooFewCoordinates,
+    #[error("invalid command integer {integer} at offset {offset}")]
+    InvalidCommand { offset: usize, integer: u32 },
+    #[error("command count {count} at offset {offset} exceeds geometry length")]
+    CommandCountOverflow { offset: usize, count: u32 },
+    #[error("unexpected end of geometry at offset {offset}")]
+    UnexpectedEnd { offset: usize },
+    #[error("interior ring without exterior ring at offset {offset}")]
+    InteriorRingWithoutExterior { offset: usize },
+    #[error("invalid feature {feature}: {error}")]
+    InvalidFeature {
+        feature: usize,
+        #[source]
+        error: Box<MvtError>,
+    },
 }
@@ -1,6 +1,8 @@
-use crate::error::Result;
+use crate::error::{GeozeroError, Result};
 use crate::mvt::vector_tile::{tile, tile::GeomType};
-use crate::{ColumnValue, FeatureProcessor, GeomProcessor, GeozeroDatasource, GeozeroGeometry};
+use crate::{
+    ColumnValue, FeatureId, FeatureProcessor, GeomProcessor, GeozeroDatasource, GeozeroGeometry,
+};
 
 use super::{
     mvt_commands::{Command, CommandInteger, ParameterInteger},
@@ -11,18 +13,65 @@ impl GeozeroDatasource for tile::Layer {
     fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
         process(self, processor)
     }
+    fn process_with_recovery<P, F>(&mut self, processor: &mut P, on_error: F) -> Result<()>
+    where
+        P: FeatureProcessor,
+        F: FnMut(u64, GeozeroError) -> Result<()>,
+    {
+        process_features(self, processor, on_error)
+    }
 }
 
 /// Process MVT layer.
+///
+/// Invalid features are reported as [MvtError::InvalidFeature].
 pub fn process(layer: &tile::Layer, processor: &mut impl FeatureProcessor) -> Result<()> {
+    process_features(layer, processor, |_, error| Err(error))
+}
+
+/// Process MVT layer, skipping features with invalid geometry command sequences or properties.
+pub fn process_lenient(layer: &tile::Layer, processor: &mut impl FeatureProcessor) -> Result<()> {
+    process_features(layer, processor, |idx, error| {
+        log::warn!("skipping MVT feature {idx}: {error}");
+        Ok(())
+    })
+}
+
+fn process_features(
+    layer: &tile::Layer,
+    processor: &mut impl FeatureProcessor,
+    mut on_error: impl FnMut(u64, GeozeroError) -> Result<()>,
+) -> Result<()> {
     processor.dataset_begin(Some(&layer.name))?;
     for (idx, feature) in layer.features.iter().enumerate() {
+        // Decode geometry and validate properties first, so that invalid features don't emit
+        // any events
+        let result = decode_geometry(feature)
+            .and_then(|geometry| {
+                validate_properties(layer, feature)?;
+                Ok(geometry)
+            })
+            .map_err(|error| MvtError::InvalidFeature {
+                feature: idx,
+                error: Box::new(error),
+            });
+        let geometry = match result {
+            Ok(geometry) => geometry,
+            Err(error) => {
+                on_error(idx as u64, error.into())?;
+                continue;
+            }
+        };
+
         processor.feature_begin(idx as u64)?;
+        if let Some(id) = feature.id {
+            processor.feature_id(&FeatureId::ULong(id))?;
+        }
 
         process_properties(layer, feature, processor)?;
 
         processor.geometry_begin()?;
-        process_geom(feature, processor)?;
+        process_decoded(&geometry, 0, processor)?;
         processor.geometry_end()?;
 
         processor.feature_end(idx as u64)?;
@@ -30,6 +79,32 @@ pub fn process(layer: &tile::Layer, processor: &mut impl FeatureProcessor) -> Re
     processor.dataset_end()
 }
 
+fn validate_properties(layer: &tile::Layer, feature: &tile::Feature) -> Result<(), MvtError> {
+    for pair in feature.tags.chunks(2) {
+        let [key_idx, value_idx] = pair else {
+            return Err(MvtError::InvalidFeatureTagsLength(feature.tags.len()));
+        };
+        if layer.keys.get(*key_idx as usize).is_none() {
+            return Err(MvtError::InvalidKeyIndex(*key_idx));
+        }
+        let Some(value) = layer.values.get(*value_idx as usize) else {
+            return Err(MvtError::InvalidValueIndex(*value_idx));
+        };
+        if value.string_value.is_none()
+            && value.float_value.is_none()
+            && value.double_value.is_none()
+            && value.int_value.is_none()
+            && value.uint_value.is_none()
+            && value.sint_value.is_none()
+            && value.bool_value.is_none()
+        {
+            let key = &layer.keys[*key_idx as usize];
+            return Err(MvtError::UnsupportedKeyValueType(key.to_string()));
+        }
+    }
+    Ok(())
+}
+
 fn process_properties(
     layer: &tile::Layer,
     feature: &tile::Feature,
@@ -86,211 +161,255 @@ fn process_geom_n<P: GeomProcessor>(
     idx: usize,
     processor: &mut P,
 ) -> Result<()> {
-    let mut cursor: [i32; 2] = [0, 0];
-    match geom.r#type {
+    let geometry = decode_geometry(geom)?;
+    process_decoded(&geometry, idx, processor)
+}
+
+type Coord = [i32; 2];
+
+/// Geometry with absolute coordinates decoded from a command sequence
+enum DecodedGeometry {
+    Empty,
+    Points(Vec<Coord>),
+    LineStrings(Vec<Vec<Coord>>),
+    /// Polygons with closed rings, exterior ring first
+    Polygons(Vec<Vec<Vec<Coord>>>),
+}
+
+/// Reader of geometry commands with parameters
+struct CommandReader<'a> {
+    geom: &'a [u32],
+    pos: usize,
+    cursor: Coord,
+}
+
+impl<'a> CommandReader<'a> {
+    fn new(geom: &'a [u32]) -> Self {
+        CommandReader {
+            geom,
+            pos: 0,
+            cursor: [0, 0],
+        }
+    }
+
+    /// Next command with its offset and count, checking that all parameters are available
+    fn next_command(&mut self) -> Result<Option<(usize, CommandInteger)>, MvtError> {
+        let Some(&integer) = self.geom.get(self.pos) else {
+            return Ok(None);
+        };
+        let offset = self.pos;
+        self.pos += 1;
+        let command = CommandInteger(integer);
+        let params = match command.id() {
+            id if id == Command::MoveTo as u32 || id == Command::LineTo as u32 => {
+                command.count() as usize * 2
+            }
+            id if id == Command::ClosePath as u32 => 0,
+            _ => return Err(MvtError::InvalidCommand { offset, integer }),
+        };
+        if params > self.geom.len() - self.pos {
+            return Err(MvtError::CommandCountOverflow {
+                offset,
+                count: command.count(),
+            });
+        }
+        Ok(Some((offset, command)))
+    }
+
+    /// Next command, which must have the given id and a count within `counts`
+    fn expect_command(
+        &mut self,
+        id: Command,
+        counts: impl std::ops::RangeBounds<u32>,
+    ) -> Result<Option<usize>, MvtError> {
+        let id = id as u32;
+        match self.next_command()? {
+            Some((_, command)) if command.id() == id && counts.contains(&command.count()) => {
+                Ok(Some(command.count() as usize))
+            }
+            Some((offset, command)) => Err(MvtError::InvalidCommand {
+                offset,
+                integer: command.0,
+            }),
+            None => Ok(None),
+        }
+    }
+
+    /// Read `count` coordinates
+    fn coords(&mut self, count: usize, coords: &mut Vec<Coord>) {
+        for _ in 0..count {
+            let dx = ParameterInteger(self.geom[self.pos]).value();
+            let dy = ParameterInteger(self.geom[self.pos + 1]).value();
+            self.pos += 2;
+            self.cursor = [
+                self.cursor[0].wrapping_add(dx),
+                self.cursor[1].wrapping_add(dy),
+            ];
+            coords.push(self.cursor);
+        }
+    }
+
+    /// Read a line starting with `MoveTo` followed by `LineTo` with at least `min_lineto` points
+    fn line(&mut self, min_lineto: u32) -> Result<Option<Vec<Coord>>, MvtError> {
+        let Some(count) = self.expect_command(Command::MoveTo, 1..=1)? else {
+            return Ok(None);
+        };
+        let mut line = Vec::new();
+        self.coords(count, &mut line);
+        let offset = self.pos;
+        let Some(count) = self.expect_command(Command::LineTo, min_lineto..)? else {
+            return Err(MvtError::UnexpectedEnd { offset });
+        };
+        self.coords(count, &mut line);
+        Ok(Some(line))
+    }
+}
+
+/// Decode and validate the command sequence of a feature geometry.
+fn decode_geometry(feature: &tile::Feature) -> Result<DecodedGeometry, MvtError> {
+    let mut reader = CommandReader::new(&feature.geometry);
+    let geometry = match feature.r#type {
         Some(r#type) if r#type == GeomType::Point as i32 => {
-            process_point(&mut cursor, &geom.geometry, idx, processor)
+            let mut points = Vec::new();
+            while let Some(count) = reader.expect_command(Command::MoveTo, 1..)? {
+                reader.coords(count, &mut points);
+            }
+            DecodedGeometry::Points(points)
         }
         Some(r#type) if r#type == GeomType::Linestring as i32 => {
-            process_linestrings(&mut cursor, geom, idx, processor)
+            let mut lines = Vec::new();
+            while let Some(line) = reader.line(1)? {
+                lines.push(line);
+            }
+            DecodedGeometry::LineStrings(lines)
         }
         Some(r#type) if r#type == GeomType::Polygon as i32 => {
-            process_polygons(&mut cursor, geom, idx, processor)
+            let mut polygons: Vec<Vec<Vec<Coord>>> = Vec::new();
+            loop {
+                let offset = reader.pos;
+                let Some(mut ring) = reader.line(2)? else {
+                    break;
+                };
+                let close_offset = reader.pos;
+                if reader.expect_command(Command::ClosePath, 1..=1)?.is_none() {
+                    return Err(MvtError::UnexpectedEnd {
+                        offset: close_offset,
+                    });
+                }
+                ring.push(ring[0]);
+                if is_area_positive(&ring) {
+                    // new polygon with exterior ring
+                    polygons.push(vec![ring]);
+                } else if let Some(polygon) = polygons.last_mut() {
+                    // add interior ring to previous polygon
+                    polygon.push(ring);
+                } else {
+                    return Err(MvtError::InteriorRingWithoutExterior { offset });
+                }
+            }
+            DecodedGeometry::Polygons(polygons)
         }
-        _ => Ok(()),
-    }
+        _ => DecodedGeometry::Empty,
+    };
+    let empty = match &geometry {
+        DecodedGeometry::Empty => true,
+        DecodedGeometry::Points(points) => points.is_empty(),
+        DecodedGeometry::LineStrings(lines) => lines.is_empty(),
+        DecodedGeometry::Polygons(polygons) => polygons.is_empty(),
+    };
+    Ok(if empty {
+        DecodedGeometry::Empty
+    } else {
+        geometry
+    })
 }
 
-fn process_coord<P: GeomProcessor>(
-    cursor: &mut [i32; 2],
-    coord: &[u32],
+fn process_decoded<P: GeomProcessor>(
+    geometry: &DecodedGeometry,
     idx: usize,
     processor: &mut P,
 ) -> Result<()> {
-    cursor[0] += ParameterInteger(coord[0]).value();
-    cursor[1] += ParameterInteger(coord[1]).value();
-    if processor.multi_dim() {
-        processor.coordinate(
-            cursor[0] as f64,
-            cursor[1] as f64,
-            None,
-            None,
-            None,
-            None,
-            idx,
-        )
-    } else {
-        processor.xy(cursor[0] as f64, cursor[1] as f64, idx)
+    match geometry {
+        DecodedGeometry::Empty => Ok(()),
+        DecodedGeometry::Points(points) if points.len() == 1 => {
+            processor.point_begin(idx)?;
+            process_coord(points[0], 0, processor)?;
+            processor.point_end(idx)
+        }
+        DecodedGeometry::Points(points) => {
+            processor.multipoint_begin(points.len(), idx)?;
+            for (i, coord) in points.iter().enumerate() {
+                process_coord(*coord, i, processor)?;
+            }
+            processor.multipoint_end(idx)
+        }
+        DecodedGeometry::LineStrings(lines) if lines.len() == 1 => {
+            process_linestring(&lines[0], true, idx, processor)
+        }
+        DecodedGeometry::LineStrings(lines) => {
+            processor.multilinestring_begin(lines.len(), idx)?;
+            for (i, line) in lines.iter().enumerate() {
+                process_linestring(line, false, i, processor)?;
+            }
+            processor.multilinestring_end(idx)
+        }
+        DecodedGeometry::Polygons(polygons) if polygons.len() == 1 => {
+            process_polygon(&polygons[0], true, idx, processor)
+        }
+        DecodedGeometry::Polygons(polygons) => {
+            processor.multipolygon_begin(polygons.len(), idx)?;
+            for (i, polygon) in polygons.iter().enumerate() {
+                process_polygon(polygon, false, i, processor)?;
+            }
+            processor.multipolygon_end(idx)
+        }
     }
 }
 
-fn process_point<P: GeomProcessor>(
-    cursor: &mut [i32; 2],
-    geom: &[u32],
-    idx: usize,
-    processor: &mut P,
-) -> Result<()> {
-    let command = CommandInteger(geom[0]);
-    let count = command.count() as usize;
-    if count == 1 {
-        processor.point_begin(idx)?;
-        process_coord(cursor, &geom[1..3], 0, processor)?;
-        processor.point_end(idx)
+fn process_coord<P: GeomProcessor>(coord: Coord, idx: usize, processor: &mut P) -> Result<()> {
+    let [x, y] = coord;
+    if processor.multi_dim() {
+        processor.coordinate(x as f64, y as f64, None, None, None, None, idx)
     } else {
-        processor.multipoint_begin(count, idx)?;
-        for i in 0..count {
-            process_coord(cursor, &geom[1 + i * 2..3 + i * 2], i, processor)?;
-        }
-        processor.multipoint_end(idx)
+        processor.xy(x as f64, y as f64, idx)
     }
 }
 
 fn process_linestring<P: GeomProcessor>(
-    cursor: &mut [i32; 2],
-    geom: &[u32],
+    line: &[Coord],
     tagged: bool,
     idx: usize,
     processor: &mut P,
 ) -> Result<()> {
-    if geom[0] != CommandInteger::from(Command::MoveTo, 1) {
-        return Err(MvtError::GeometryFormat.into());
-    }
-    let lineto = CommandInteger(geom[3]);
-    if lineto.id() != Command::LineTo as u32 {
-        return Err(MvtError::GeometryFormat.into());
-    }
-    processor.linestring_begin(tagged, 1 + lineto.count() as usize, idx)?;
-    process_coord(cursor, &geom[1..3], 0, processor)?;
-    for i in 0..lineto.count() as usize {
-        process_coord(cursor, &geom[4 + i * 2..6 + i * 2], i + 1, processor)?;
+    processor.linestring_begin(tagged, line.len(), idx)?;
+    for (i, coord) in line.iter().enumerate() {
+        process_coord(*coord, i, processor)?;
     }
     processor.linestring_end(tagged, idx)
 }
 
-fn process_linestrings<P: GeomProcessor>(
-    cursor: &mut [i32; 2],
-    geom: &tile::Feature,
-    idx: usize,
-    processor: &mut P,
-) -> Result<()> {
-    let mut line_string_slices: Vec<&[u32]> = vec![];
-    let mut geom: &[u32] = &geom.geometry;
-
-    while !geom.is_empty() {
-        let lineto = CommandInteger(geom[3]);
-        let slice_size = 4 + lineto.count() as usize * 2;
-        let (slice, rest) = geom.split_at(slice_size);
-        line_string_slices.push(slice);
-        geom = rest;
-    }
-
-    if line_string_slices.len() > 1 {
-        processor.multilinestring_begin(line_string_slices.len(), idx)?;
-        for (i, line_string_slice) in line_string_slices.iter().enumerate() {
-            process_linestring(cursor, line_string_slice, false, i, processor)?;
-        }
-        processor.multilinestring_end(idx)
-    } else {
-        process_linestring(cursor, line_string_slices[0], true, idx, processor)
-    }
-}
-
 fn process_polygon<P: GeomProcessor>(
-    cursor: &mut [i32; 2],
-    rings: &[&[u32]],
+    rings: &[Vec<Coord>],
     tagged: bool,
     idx: usize,
     processor: &mut P,
 ) -> Result<()> {
     processor.polygon_begin(tagged, rings.len(), idx)?;
-
     for (i, ring) in rings.iter().enumerate() {
-        if ring[0] != CommandInteger::from(Command::MoveTo, 1) {
-            return Err(MvtError::GeometryFormat.into());
-        }
-        if *ring.last().unwrap() != CommandInteger::from(Command::ClosePath, 1) {
-            return Err(MvtError::GeometryFormat.into());
-        }
-        let lineto = CommandInteger(ring[3]);
-        if lineto.id() != Command::LineTo as u32 {
-            return Err(MvtError::GeometryFormat.into());
-        }
-        processor.linestring_begin(false, 1 + lineto.count() as usize, i)?;
-        let mut start_cursor = *cursor;
-        process_coord(cursor, &ring[1..3], 0, processor)?;
-        for i in 0..lineto.count() as usize {
-            process_coord(cursor, &ring[4 + i * 2..6 + i * 2], i + 1, processor)?;
-        }
-        process_coord(
-            &mut start_cursor,
-            &ring[1..3],
-            1 + lineto.count() as usize,
-            processor,
-        )?;
-        processor.linestring_end(false, i)?;
+        process_linestring(ring, false, i, processor)?;
     }
-
     processor.polygon_end(tagged, idx)
 }
 
-fn process_polygons<P: GeomProcessor>(
-    cursor: &mut [i32; 2],
-    geom: &tile::Feature,
-    idx: usize,
-    processor: &mut P,
-) -> Result<()> {
-    let mut polygon_slices: Vec<Vec<&[u32]>> = vec![];
-    let mut geom: &[u32] = &geom.geometry;
-
-    while !geom.is_empty() {
-        let lineto = CommandInteger(geom[3]);
-        let slice_size = 4 + lineto.count() as usize * 2 + 1;
-        let (slice, rest) = geom.split_at(slice_size);
-        let positive_area = is_area_positive(
-            *cursor,
-            &slice[1..3],
-            &slice[4..4 + lineto.count() as usize * 2],
-        );
-        if positive_area {
-            // new polygon with exterior ring
-            polygon_slices.push(vec![slice]);
-        } else if let Some(last_slice) = polygon_slices.last_mut() {
-            // add interior ring to previous polygon
-            last_slice.push(slice);
-        } else {
-            return Err(MvtError::GeometryFormat.into());
-        }
-        geom = rest;
-    }
-
-    if polygon_slices.len() > 1 {
-        processor.multipolygon_begin(polygon_slices.len(), idx)?;
-        for (i, polygon_slice) in polygon_slices.iter().enumerate() {
-            process_polygon(cursor, polygon_slice, false, i, processor)?;
-        }
-        processor.multipolygon_end(idx)
-    } else {
-        process_polygon(cursor, &polygon_slices[0], true, idx, processor)
-    }
-}
-
 // using surveyor's formula
-fn is_area_positive(mut cursor: [i32; 2], first: &[u32], rest: &[u32]) -> bool {
-    let nb = 1 + rest.len() / 2;
-    let mut area = 0_i64;
-    let mut coords = first
-        .iter()
-        .chain(rest)
-        .chain(first.iter())
-        .map(|&x| ParameterInteger(x).value());
-    cursor[0] += coords.next().unwrap();
-    cursor[1] += coords.next().unwrap();
-    for _i in 0..nb {
-        let [x0, y0] = cursor;
-        cursor[0] += coords.next().unwrap();
-        cursor[1] += coords.next().unwrap();
-        area += (x0 as i64) * (cursor[1] as i64) - (y0 as i64) * (cursor[0] as i64);
-    }
+fn is_area_positive(ring: &[Coord]) -> bool {
+    let area: i64 = ring
+        .windows(2)
+        .map(|w| {
+            let ([x0, y0], [x1, y1]) = (w[0], w[1]);
+            (x0 as i64) * (y1 as i64) - (y0 as i64) * (x1 as i64)
+        })
+        .sum();
     area > 0
 }
 
@@ -298,6 +417,7 @@ fn is_area_positive(mut cursor: [i32; 2], first: &[u32], rest: &[u32]) -> bool {
 #[cfg(feature = "with-geojson")]
 mod test {
     use super::*;
+    use crate::geojson::GeoJsonWriter;
     use crate::{ProcessToJson, ToJson};
     use serde_json::json;
 
@@ -496,6 +616,97 @@ mod test {
         );
     }
 
+    #[test]
+    fn invalid_geom() {
+        let decode = |geom_type: GeomType, geometry: &[u32]| {
+            let mut mvt_feature = tile::Feature::default();
+            mvt_feature.set_type(geom_type);
+            mvt_feature.geometry = geometry.to_vec();
+            decode_geometry(&mvt_feature).err()
+        };
+        // MoveTo with missing parameter
+        assert!(matches!(
+            decode(GeomType::Point, &[9, 50]),
+            Some(MvtError::CommandCountOverflow {
+                offset: 0,
+                count: 1
+            })
+        ));
+        // LineTo count overflow
+        assert!(matches!(
+            decode(GeomType::Linestring, &[9, 4, 4, 0x7FFF_FFF2, 0, 16]),
+            Some(MvtError::CommandCountOverflow { offset: 3, .. })
+        ));
+        // ClosePath in line
+        assert!(matches!(
+            decode(GeomType::Linestring, &[9, 4, 4, 15]),
+            Some(MvtError::InvalidCommand {
+                offset: 3,
+                integer: 15
+            })
+        ));
+        // Unknown command id
+        assert!(matches!(
+            decode(GeomType::Point, &[9, 50, 34, 12]),
+            Some(MvtError::InvalidCommand {
+                offset: 3,
+                integer: 12
+            })
+        ));
+        // Ring without ClosePath
+        assert!(matches!(
+            decode(GeomType::Polygon, &[9, 6, 12, 18, 10, 12, 24, 44]),
+            Some(MvtError::UnexpectedEnd { offset: 8 })
+        ));
+        // Interior ring first
+        assert!(matches!(
+            decode(GeomType::Polygon, &[9, 4, 4, 26, 0, 8, 8, 0, 0, 7, 15]),
+            Some(MvtError::InteriorRingWithoutExterior { offset: 0 })
+        ));
+    }
+
+    #[test]
+    fn lenient_mode() -> Result<()> {
+        let mut mvt_layer = tile::Layer {
+            version: 2,
+            name: String::from("points"),
+            ..Default::default()
+        };
+        for geometry in [vec![9, 50, 34], vec![9, 50], vec![9, 2, 2]] {
+            let mut feature = tile::Feature {
+                geometry,
+                ..Default::default()
+            };
+            feature.set_type(GeomType::Point);
+            mvt_layer.features.push(feature);
+        }
+
+        let err = process(&mvt_layer, &mut crate::ProcessorSink).unwrap_err();
+        assert!(matches!(
+            err,
+            GeozeroError::MvtError(MvtError::InvalidFeature { feature: 1, .. })
+        ));
+        assert_eq!(
+            err.to_string(),
+            "MVT error `invalid feature 1: command count 1 at offset 0 exceeds geometry length`"
+        );
+
+        let mut out: Vec<u8> = Vec::new();
+        process_lenient(&mvt_layer, &mut GeoJsonWriter::new(&mut out))?;
+        let geojson: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(geojson["features"].as_array().unwrap().len(), 2);
+
+        // Features with unsupported property values are skipped before emitting any events
+        mvt_layer.keys.push(String::from("name"));
+        mvt_layer.values.push(tile::Value::default());
+        mvt_layer.features[0].tags = vec![0, 0];
+        let mut out: Vec<u8> = Vec::new();
+        process_lenient(&mvt_layer, &mut GeoJsonWriter::new(&mut out))?;
+        let geojson: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(geojson["features"].as_array().unwrap().len(), 1);
+        Ok(())
+    }
+
     #[test]
     fn big_number_geom() {
         // In some cases, if the extent is large enough, the coordinate parsing threw an error
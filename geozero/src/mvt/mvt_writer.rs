@@ -4,7 +4,7 @@
 use crate::error::Result;
 use crate::mvt::mvt_commands::{Command, CommandInteger, ParameterInteger};
 use crate::mvt::vector_tile::{tile, tile::GeomType};
-use crate::GeomProcessor;
+use crate::{FeatureId, FeatureProcessor, GeomProcessor, PropertyProcessor};
 
 use super::mvt_error::MvtError;
 
@@ -24,6 +24,8 @@ pub struct MvtWriter {
     last_y: i32,
     line_state: LineState,
     is_multiline: bool,
+    /// SRID of processed geometry (not encoded in MVT)
+    srid: Option<i32>,
 }
 
 #[derive(Default, Debug, PartialEq)]
@@ -52,6 +54,31 @@ impl MvtWriter {
         &self.feature
     }
 
+    /// SRID of processed geometry
+    ///
+    /// MVT geometries are in tile coordinates and have no SRID. It has to be checked by the caller
+    /// that input geometries are in the projection of the tile bounds.
+    pub fn geometry_srid(&self) -> Option<i32> {
+        self.srid
+    }
+
+    /// Reset writer state for processing the next geometry.
+    ///
+    /// The allocated command buffer is kept, which avoids reallocations when
+    /// encoding many features with the same writer.
+    pub fn reset(&mut self) {
+        self.feature.id = None;
+        self.feature.tags.clear();
+        self.feature.r#type = None;
+        self.feature.geometry.clear();
+        self.last_x = 0;
+        self.last_y = 0;
+        self.line_state = LineState::None;
+        self.is_multiline = false;
+        self.srid = None;
+    }
+
+    /// Reserve command buffer capacity for the given number of additional integers.
     fn reserve(&mut self, capacity: usize) {
         let total = self.feature.geometry.len() + capacity;
         if total > self.feature.geometry.capacity() {
@@ -63,6 +90,16 @@ impl MvtWriter {
 }
 
 impl GeomProcessor for MvtWriter {
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.srid = srid;
+        Ok(())
+    }
+
+    fn size_hint_total_coords(&mut self, count: usize) -> Result<()> {
+        // Two parameter integers per vertex
+        self.reserve(2 * count);
+        Ok(())
+    }
     fn xy(&mut self, x_coord: f64, y_coord: f64, idx: usize) -> Result<()> {
         // Omit last coord of ring (emit ClosePath instead)
         let last_ring_coord = if let LineState::Ring(size) = self.line_state {
@@ -132,7 +169,7 @@ impl GeomProcessor for MvtWriter {
             self.reserve(2 + 2 * size);
             LineState::Line(size)
         } else {
-            self.reserve(2 + 2 * (size - 1) + 1);
+            self.reserve(2 + 2 * size.saturating_sub(1) + 1);
             LineState::Ring(size)
         };
         self.feature
@@ -175,6 +212,20 @@ impl GeomProcessor for MvtWriter {
     }
 }
 
+impl PropertyProcessor for MvtWriter {}
+
+impl FeatureProcessor for MvtWriter {
+    /// Encode numeric feature ids. MVT has no string or negative ids.
+    fn feature_id(&mut self, id: &FeatureId) -> Result<()> {
+        self.feature.id = match *id {
+            FeatureId::ULong(id) => Some(id),
+            FeatureId::Long(id) => u64::try_from(id).ok(),
+            FeatureId::String(_) => None,
+        };
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod test_mvt {
     use super::*;
@@ -393,7 +444,7 @@ mod test {
     use super::*;
     use crate::geojson::conversion::ToJson;
     use crate::geojson::GeoJson;
-    use crate::ToMvt;
+    use crate::{GeozeroGeometry, ToMvt};
     use serde_json::json;
 
     // https://github.com/mapbox/vector-tile-spec/tree/master/2.1#435-example-geometry-encodings
@@ -405,6 +456,31 @@ mod test {
         assert_eq!(mvt.geometry, [9, 50, 34]);
     }
 
+    #[test]
+    fn reuse_writer() -> Result<()> {
+        let mut mvt = MvtWriter::default();
+        GeoJson(r#"{"type": "LineString", "coordinates": [[2,2], [2,10], [10,10]]}"#)
+            .process_geom(&mut mvt)?;
+        let capacity = mvt.geometry().geometry.capacity();
+        mvt.reset();
+        GeoJson(r#"{"type": "Point", "coordinates": [25, 17]}"#).process_geom(&mut mvt)?;
+        assert_eq!(mvt.geometry().geometry, [9, 50, 34]);
+        assert_eq!(mvt.geometry().r#type, Some(GeomType::Point as i32));
+        assert_eq!(mvt.geometry().geometry.capacity(), capacity);
+        Ok(())
+    }
+
+    #[test]
+    fn size_hint_reserve() -> Result<()> {
+        let mut mvt = MvtWriter::default();
+        mvt.size_hint_total_coords(100)?;
+        assert!(mvt.geometry().geometry.capacity() >= 200);
+        GeoJson(r#"{"type": "LineString", "coordinates": [[2,2], [2,10], [10,10]]}"#)
+            .process_geom(&mut mvt)?;
+        assert_eq!(mvt.geometry().geometry, [9, 4, 4, 18, 0, 16, 16, 0]);
+        Ok(())
+    }
+
     #[test]
     fn multipoint_geom() {
         let geojson = GeoJson(r#"{"type": "MultiPoint", "coordinates": [[5, 7], [3, 2]]}"#);
@@ -487,6 +563,18 @@ mod test {
         );
     }
 
+    #[test]
+    fn feature_id() -> Result<()> {
+        let mut mvt = MvtWriter::default();
+        mvt.feature_id(&FeatureId::Long(42))?;
+        assert_eq!(mvt.geometry().id, Some(42));
+        mvt.feature_id(&FeatureId::Long(-1))?;
+        assert_eq!(mvt.geometry().id, None);
+        mvt.feature_id(&FeatureId::String("a"))?;
+        assert_eq!(mvt.geometry().id, None);
+        Ok(())
+    }
+
     #[test]
     #[cfg(feature = "with-geo")]
     fn geo_screen_coords_to_mvt() -> Result<()> {
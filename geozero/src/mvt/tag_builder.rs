@@ -47,6 +47,17 @@ impl<K: Eq + Hash + PtrRead> TagsBuilder<K> {
         )
     }
 
+    /// Insert key only, e.g. for the columns of a schema.
+    ///
+    /// The returned key index can be combined with value indexes from [`insert_value`](Self::insert_value).
+    pub fn insert_key(&mut self, key: K) -> u32 {
+        self.keys.insert(key) as u32
+    }
+
+    pub fn insert_value(&mut self, value: TileValue) -> u32 {
+        self.values.insert(value) as u32
+    }
+
     pub fn into_tags(self) -> (Vec<K>, Vec<TileValue>) {
         (self.keys.into_vec(), self.values.into_vec())
     }
@@ -73,4 +84,17 @@ mod tests {
         assert_eq!(vec![s("foo"), s("bar")], keys);
         assert_eq!(vec![Str(s("bar")), Str(s("baz")), Int(42)], values);
     }
+
+    #[test]
+    fn test_insert_key() {
+        let mut lb = TagsBuilder::new();
+        assert_eq!(0, lb.insert_key(s("foo")));
+        assert_eq!(1, lb.insert_key(s("bar")));
+        assert_eq!((1, 0), lb.insert(s("bar"), Int(42)));
+        assert_eq!(0, lb.insert_value(Int(42)));
+
+        let (keys, values) = lb.into_tags();
+        assert_eq!(vec![s("foo"), s("bar")], keys);
+        assert_eq!(vec![Int(42)], values);
+    }
 }
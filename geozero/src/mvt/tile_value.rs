@@ -1,4 +1,5 @@
 use crate::mvt::tile::Value;
+use crate::ColumnValue;
 use std::hash::Hash;
 
 /// A wrapper for the MVT value types.
@@ -48,6 +49,26 @@ impl From<TileValue> for Value {
     }
 }
 
+/// Convert property value, with non-numeric values as strings
+impl From<&ColumnValue<'_>> for TileValue {
+    fn from(value: &ColumnValue) -> Self {
+        match *value {
+            ColumnValue::Byte(v) => TileValue::Sint(v as i64),
+            ColumnValue::UByte(v) => TileValue::Uint(v as u64),
+            ColumnValue::Bool(v) => TileValue::Bool(v),
+            ColumnValue::Short(v) => TileValue::Sint(v as i64),
+            ColumnValue::UShort(v) => TileValue::Uint(v as u64),
+            ColumnValue::Int(v) => TileValue::Sint(v as i64),
+            ColumnValue::UInt(v) => TileValue::Uint(v as u64),
+            ColumnValue::Long(v) => TileValue::Sint(v),
+            ColumnValue::ULong(v) => TileValue::Uint(v),
+            ColumnValue::Float(v) => TileValue::Float(v),
+            ColumnValue::Double(v) => TileValue::Double(v),
+            _ => TileValue::Str(value.to_string()),
+        }
+    }
+}
+
 impl TryFrom<Value> for TileValue {
     type Error = ();
 
@@ -0,0 +1,49 @@
+//! MySQL geometry type encoding/decoding.
+//!
+//! All geometry types implementing [GeozeroGeometry](crate::GeozeroGeometry) can be encoded as MySQL geometry using [wkb::Encode](crate::wkb::Encode).
+//!
+//! Geometry types implementing [FromWkb](crate::wkb::FromWkb) can be decoded from MySQL geometries using [wkb::Decode](crate::wkb::Decode).
+//!
+//! MySQL and MariaDB store geometries in their internal format, which is WKB with a leading SRID.
+#[cfg(feature = "with-mysql-sqlx")]
+mod mysql_sqlx;
+
+/// MySQL geometry type encoding/decoding for SQLx. Requires the `with-mysql-sqlx` feature.
+///
+/// # MySQL usage example with SQLx
+///
+/// Select and insert geo-types geometries with SQLx:
+/// ```
+/// use geozero::wkb;
+/// use sqlx::mysql::MySqlPoolOptions;
+/// # use std::env;
+///
+/// # async fn rust_geo_query() -> Result<(), sqlx::Error> {
+/// let pool = MySqlPoolOptions::new()
+///     .max_connections(5)
+///     .connect(&env::var("MYSQL_DATABASE_URL").unwrap())
+///     .await?;
+///
+/// let row: (wkb::Decode<geo_types::Geometry<f64>>,) =
+///     sqlx::query_as("SELECT ST_GeomFromText('POLYGON ((0 0, 2 0, 2 2, 0 2, 0 0))', 4326)")
+///         .fetch_one(&pool)
+///         .await?;
+/// let value = row.0;
+/// if let Some(geo_types::Geometry::Polygon(poly)) = value.geometry {
+///     assert_eq!(
+///         *poly.exterior(),
+///         vec![(0.0, 0.0), (2.0, 0.0), (2.0, 2.0), (0.0, 2.0), (0.0, 0.0)].into()
+///     );
+/// }
+///
+/// // Insert geometry
+/// let geom: geo_types::Geometry<f64> = geo::Point::new(10.0, 20.0).into();
+/// let _ = sqlx::query("INSERT INTO point2d (datetimefield,geom) VALUES(now(),?)")
+///     .bind(wkb::Encode(geom))
+///     .execute(&pool)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "with-mysql-sqlx")]
+pub mod sqlx {}
@@ -0,0 +1,86 @@
+use crate::wkb::{self, FromWkb};
+use crate::GeozeroGeometry;
+use sqlx::decode::Decode;
+use sqlx::encode::{Encode, IsNull};
+use sqlx::mysql::{MySql, MySqlTypeInfo, MySqlValueRef};
+use sqlx::{TypeInfo, ValueRef};
+
+type BoxDynError = Box<dyn std::error::Error + Send + Sync>;
+
+fn geometry_type_info() -> MySqlTypeInfo {
+    <Vec<u8> as sqlx::Type<MySql>>::type_info()
+}
+
+/// Geometry columns are returned as `GEOMETRY` type, geometry functions as `BLOB`.
+fn compatible_geometry_type(ty: &MySqlTypeInfo) -> bool {
+    ty.name() == "GEOMETRY" || <Vec<u8> as sqlx::Type<MySql>>::compatible(ty)
+}
+
+impl<T: FromWkb + Sized> sqlx::Type<MySql> for wkb::Decode<T> {
+    fn type_info() -> MySqlTypeInfo {
+        geometry_type_info()
+    }
+
+    fn compatible(ty: &MySqlTypeInfo) -> bool {
+        compatible_geometry_type(ty)
+    }
+}
+
+impl<'de, T: FromWkb + Sized> Decode<'de, MySql> for wkb::Decode<T> {
+    fn decode(value: MySqlValueRef<'de>) -> Result<Self, BoxDynError> {
+        if value.is_null() {
+            return Ok(wkb::Decode { geometry: None });
+        }
+        let mut blob = <&[u8] as Decode<MySql>>::decode(value)?;
+        let geom = T::from_wkb(&mut blob, wkb::WkbDialect::MySQL)
+            .map_err(|e| sqlx::Error::Decode(e.to_string().into()))?;
+        Ok(wkb::Decode {
+            geometry: Some(geom),
+        })
+    }
+}
+
+impl<B: AsRef<[u8]>> sqlx::Type<MySql> for wkb::MySQLWkb<B> {
+    fn type_info() -> MySqlTypeInfo {
+        geometry_type_info()
+    }
+
+    fn compatible(ty: &MySqlTypeInfo) -> bool {
+        compatible_geometry_type(ty)
+    }
+}
+
+impl<'de> Decode<'de, MySql> for wkb::MySQLWkb<Vec<u8>> {
+    fn decode(value: MySqlValueRef<'de>) -> Result<Self, BoxDynError> {
+        if value.is_null() {
+            return Ok(wkb::MySQLWkb(Vec::new()));
+        }
+        let blob = <&[u8] as Decode<MySql>>::decode(value)?;
+        Ok(wkb::MySQLWkb(blob.to_vec()))
+    }
+}
+
+impl<T: GeozeroGeometry + Sized> sqlx::Type<MySql> for wkb::Encode<T> {
+    fn type_info() -> MySqlTypeInfo {
+        geometry_type_info()
+    }
+
+    fn compatible(ty: &MySqlTypeInfo) -> bool {
+        compatible_geometry_type(ty)
+    }
+}
+
+impl<T: GeozeroGeometry + Sized> Encode<'_, MySql> for wkb::Encode<T> {
+    fn encode_by_ref(&self, buf: &mut Vec<u8>) -> Result<IsNull, BoxDynError> {
+        let mut wkb_out: Vec<u8> = Vec::new();
+        let mut writer = wkb::WkbWriter::with_opts(
+            &mut wkb_out,
+            wkb::WkbDialect::MySQL,
+            self.0.dims(),
+            self.0.srid(),
+            Vec::new(),
+        );
+        self.0.process_geom(&mut writer)?;
+        <Vec<u8> as Encode<MySql>>::encode(wkb_out, buf)
+    }
+}
@@ -0,0 +1,411 @@
+//! Static packed Hilbert R-tree.
+//!
+//! Same layout as the spatial index of [FlatGeobuf](https://flatgeobuf.org/): items are sorted
+//! by the Hilbert value of their bounding box center and packed bottom-up into nodes of fixed size.
+use crate::bounds::Bounds;
+use crate::error::Result;
+use crate::{FeatureProcessor, GeomProcessor, PropertyProcessor};
+use std::ops::Range;
+
+/// Default number of children per node
+pub const DEFAULT_NODE_SIZE: u16 = 16;
+
+const HILBERT_MAX: u32 = (1 << 16) - 1;
+
+/// Hilbert curve value of a point on a 2^16 x 2^16 grid.
+pub fn hilbert(x: u32, y: u32) -> u32 {
+    // Fast Hilbert curve algorithm by http://threadlocalmutex.com/
+    // Ported from C++ https://github.com/rawrunprotected/hilbert_curves (public domain)
+    let mut a = x ^ y;
+    let mut b = 0xFFFF ^ a;
+    let mut c = 0xFFFF ^ (x | y);
+    let mut d = x & (y ^ 0xFFFF);
+
+    let mut aa = a | (b >> 1);
+    let mut bb = (a >> 1) ^ a;
+    let mut cc = ((c >> 1) ^ (b & (d >> 1))) ^ c;
+    let mut dd = ((a & (c >> 1)) ^ (d >> 1)) ^ d;
+
+    a = aa;
+    b = bb;
+    c = cc;
+    d = dd;
+    aa = (a & (a >> 2)) ^ (b & (b >> 2));
+    bb = (a & (b >> 2)) ^ (b & ((a ^ b) >> 2));
+    cc ^= (a & (c >> 2)) ^ (b & (d >> 2));
+    dd ^= (b & (c >> 2)) ^ ((a ^ b) & (d >> 2));
+
+    a = aa;
+    b = bb;
+    c = cc;
+    d = dd;
+    aa = (a & (a >> 4)) ^ (b & (b >> 4));
+    bb = (a & (b >> 4)) ^ (b & ((a ^ b) >> 4));
+    cc ^= (a & (c >> 4)) ^ (b & (d >> 4));
+    dd ^= (b & (c >> 4)) ^ ((a ^ b) & (d >> 4));
+
+    a = aa;
+    b = bb;
+    c = cc;
+    d = dd;
+    cc ^= (a & (c >> 8)) ^ (b & (d >> 8));
+    dd ^= (b & (c >> 8)) ^ ((a ^ b) & (d >> 8));
+
+    a = cc ^ (cc >> 1);
+    b = dd ^ (dd >> 1);
+
+    let mut i0 = x ^ y;
+    let mut i1 = b | (0xFFFF ^ (i0 | a));
+
+    i0 = (i0 | (i0 << 8)) & 0x00FF_00FF;
+    i0 = (i0 | (i0 << 4)) & 0x0F0F_0F0F;
+    i0 = (i0 | (i0 << 2)) & 0x3333_3333;
+    i0 = (i0 | (i0 << 1)) & 0x5555_5555;
+
+    i1 = (i1 | (i1 << 8)) & 0x00FF_00FF;
+    i1 = (i1 | (i1 << 4)) & 0x0F0F_0F0F;
+    i1 = (i1 | (i1 << 2)) & 0x3333_3333;
+    i1 = (i1 | (i1 << 1)) & 0x5555_5555;
+
+    (i1 << 1) | i0
+}
+
+/// Hilbert curve value of the center of `bounds` within `extent`.
+pub fn hilbert_index(bounds: &Bounds, extent: &Bounds) -> u32 {
+    let scale = |v: f64, min: f64, size: f64| {
+        if size > 0.0 {
+            (f64::from(HILBERT_MAX) * (v - min) / size).floor() as u32
+        } else {
+            0
+        }
+    };
+    let x = scale(
+        (bounds.min_x + bounds.max_x) / 2.0,
+        extent.min_x,
+        extent.width(),
+    );
+    let y = scale(
+        (bounds.min_y + bounds.max_y) / 2.0,
+        extent.min_y,
+        extent.height(),
+    );
+    hilbert(x, y)
+}
+
+/// Tree node or indexed item.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct NodeItem {
+    pub bounds: Bounds,
+    /// Item id for leaf nodes, index of the first child node otherwise
+    pub offset: u64,
+}
+
+impl NodeItem {
+    fn empty(offset: u64) -> Self {
+        NodeItem {
+            bounds: Bounds {
+                min_x: f64::INFINITY,
+                min_y: f64::INFINITY,
+                max_x: f64::NEG_INFINITY,
+                max_y: f64::NEG_INFINITY,
+            },
+            offset,
+        }
+    }
+}
+
+/// Search result.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SearchResult {
+    /// Position of the item in [PackedRTree::items]
+    pub index: usize,
+    /// Item id
+    pub id: u64,
+}
+
+/// Packed R-tree builder.
+///
+/// Items can be added with [add](Self::add) or by processing a datasource, which adds the
+/// bounds of each feature geometry with the feature index as id. Features without geometry
+/// are skipped.
+///
+/// # Usage example:
+///
+/// ```
+/// # #[cfg(feature = "with-geojson")]
+/// # {
+/// use geozero::bounds::Bounds;
+/// use geozero::geojson::GeoJson;
+/// use geozero::packed_rtree::PackedRTreeBuilder;
+/// use geozero::GeozeroDatasource;
+///
+/// let mut geojson = GeoJson(r#"{"type": "FeatureCollection", "features": [
+///     {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [1, 1]}},
+///     {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [5, 5]}}
+/// ]}"#);
+/// let mut builder = PackedRTreeBuilder::new();
+/// geojson.process(&mut builder).unwrap();
+/// let tree = builder.build();
+/// let hits = tree.search(&Bounds { min_x: 4.0, min_y: 4.0, max_x: 6.0, max_y: 6.0 });
+/// assert_eq!(hits.iter().map(|hit| hit.id).collect::<Vec<_>>(), vec![1]);
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct PackedRTreeBuilder {
+    node_size: u16,
+    items: Vec<NodeItem>,
+    extent: Option<Bounds>,
+    /// Bounds of current feature geometry
+    feature_bounds: Option<Bounds>,
+}
+
+impl Default for PackedRTreeBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PackedRTreeBuilder {
+    pub fn new() -> Self {
+        PackedRTreeBuilder {
+            node_size: DEFAULT_NODE_SIZE,
+            items: Vec::new(),
+            extent: None,
+            feature_bounds: None,
+        }
+    }
+    /// Number of children per node (default: 16, minimum: 2)
+    pub fn set_node_size(&mut self, node_size: u16) {
+        self.node_size = node_size.max(2);
+    }
+    /// Add item with bounds and id
+    pub fn add(&mut self, bounds: Bounds, id: u64) {
+        match self.extent.as_mut() {
+            Some(extent) => extent.merge(&bounds),
+            None => self.extent = Some(bounds),
+        }
+        self.items.push(NodeItem { bounds, offset: id });
+    }
+    /// Number of added items
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+    /// Sort items in Hilbert order and build the tree
+    pub fn build(mut self) -> PackedRTree {
+        let Some(extent) = self.extent else {
+            return PackedRTree {
+                extent: None,
+                node_items: Vec::new(),
+                num_items: 0,
+                node_size: self.node_size,
+                level_bounds: Vec::new(),
+            };
+        };
+        // Descending order like FlatGeobuf
+        self.items
+            .sort_by_cached_key(|item| std::cmp::Reverse(hilbert_index(&item.bounds, &extent)));
+        let num_items = self.items.len();
+        let level_bounds = generate_level_bounds(num_items, self.node_size);
+        let num_nodes = level_bounds[0].end;
+        let mut node_items = vec![NodeItem::empty(0); num_nodes];
+        let leaf_start = level_bounds[0].start;
+        for (pos, item) in self.items.into_iter().enumerate() {
+            node_items[leaf_start + pos] = item;
+        }
+        let mut tree = PackedRTree {
+            extent: Some(extent),
+            node_items,
+            num_items,
+            node_size: self.node_size,
+            level_bounds,
+        };
+        tree.generate_nodes();
+        tree
+    }
+}
+
+/// Node index ranges of each level, starting with the leaf level
+fn generate_level_bounds(num_items: usize, node_size: u16) -> Vec<Range<usize>> {
+    let node_size = usize::from(node_size);
+    let mut n = num_items;
+    let mut num_nodes = n;
+    let mut level_num_nodes = vec![n];
+    loop {
+        n = n.div_ceil(node_size);
+        num_nodes += n;
+        level_num_nodes.push(n);
+        if n == 1 {
+            break;
+        }
+    }
+    let mut level_bounds = Vec::with_capacity(level_num_nodes.len());
+    let mut end = num_nodes;
+    for size in level_num_nodes {
+        level_bounds.push(end - size..end);
+        end -= size;
+    }
+    level_bounds
+}
+
+/// Static packed Hilbert R-tree, built with [PackedRTreeBuilder].
+#[derive(Clone, Debug)]
+pub struct PackedRTree {
+    extent: Option<Bounds>,
+    /// All nodes, root first and leaves last
+    node_items: Vec<NodeItem>,
+    num_items: usize,
+    node_size: u16,
+    level_bounds: Vec<Range<usize>>,
+}
+
+impl PackedRTree {
+    fn generate_nodes(&mut self) {
+        let node_size = usize::from(self.node_size);
+        for level in 0..self.level_bounds.len() - 1 {
+            let Range { mut start, end } = self.level_bounds[level].clone();
+            let mut parent = self.level_bounds[level + 1].start;
+            while start < end {
+                let mut node = NodeItem::empty(start as u64);
+                for child in &self.node_items[start..end.min(start + node_size)] {
+                    node.bounds.merge(&child.bounds);
+                }
+                self.node_items[parent] = node;
+                parent += 1;
+                start += node_size;
+            }
+        }
+    }
+    /// Bounds of all items. `None` for an empty tree.
+    pub fn extent(&self) -> Option<Bounds> {
+        self.extent
+    }
+    /// Number of indexed items
+    pub fn len(&self) -> usize {
+        self.num_items
+    }
+    pub fn is_empty(&self) -> bool {
+        self.num_items == 0
+    }
+    pub fn node_size(&self) -> u16 {
+        self.node_size
+    }
+    /// Indexed items in (descending) Hilbert order
+    pub fn items(&self) -> &[NodeItem] {
+        match self.level_bounds.first() {
+            Some(leaves) => &self.node_items[leaves.clone()],
+            None => &[],
+        }
+    }
+    /// All tree nodes, root first and items last, as stored in a FlatGeobuf index
+    pub fn node_items(&self) -> &[NodeItem] {
+        &self.node_items
+    }
+    /// Items intersecting `bounds`, ordered by their position in [items](Self::items)
+    pub fn search(&self, bounds: &Bounds) -> Vec<SearchResult> {
+        let mut results = Vec::new();
+        let Some(leaves) = self.level_bounds.first() else {
+            return results;
+        };
+        let node_size = usize::from(self.node_size);
+        let mut stack = vec![(0, self.level_bounds.len() - 1)];
+        while let Some((node_index, level)) = stack.pop() {
+            let is_leaf = node_index >= leaves.start;
+            let end = self.level_bounds[level].end.min(node_index + node_size);
+            for pos in node_index..end {
+                let node = &self.node_items[pos];
+                if !node.bounds.intersects(bounds) {
+                    continue;
+                }
+                if is_leaf {
+                    results.push(SearchResult {
+                        index: pos - leaves.start,
+                        id: node.offset,
+                    });
+                } else {
+                    stack.push((node.offset as usize, level - 1));
+                }
+            }
+        }
+        results.sort_by_key(|result| result.index);
+        results
+    }
+}
+
+impl GeomProcessor for PackedRTreeBuilder {
+    fn xy(&mut self, x: f64, y: f64, _idx: usize) -> Result<()> {
+        match self.feature_bounds.as_mut() {
+            Some(bounds) => bounds.extend(x, y),
+            None => self.feature_bounds = Some(Bounds::from_point(x, y)),
+        }
+        Ok(())
+    }
+    fn empty_point(&mut self, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl PropertyProcessor for PackedRTreeBuilder {}
+
+impl FeatureProcessor for PackedRTreeBuilder {
+    fn feature_begin(&mut self, _idx: u64) -> Result<()> {
+        self.feature_bounds = None;
+        Ok(())
+    }
+    fn feature_end(&mut self, idx: u64) -> Result<()> {
+        if let Some(bounds) = self.feature_bounds.take() {
+            self.add(bounds, idx);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn bbox(min_x: f64, min_y: f64, max_x: f64, max_y: f64) -> Bounds {
+        Bounds {
+            min_x,
+            min_y,
+            max_x,
+            max_y,
+        }
+    }
+
+    #[test]
+    fn level_bounds() {
+        assert_eq!(generate_level_bounds(1, 16), vec![1..2, 0..1]);
+        assert_eq!(generate_level_bounds(20, 16), vec![3..23, 1..3, 0..1]);
+    }
+
+    #[test]
+    fn search_grid() {
+        let mut builder = PackedRTreeBuilder::new();
+        builder.set_node_size(4);
+        for i in 0..100u64 {
+            let (x, y) = ((i % 10) as f64, (i / 10) as f64);
+            builder.add(bbox(x, y, x + 0.5, y + 0.5), i);
+        }
+        let tree = builder.build();
+        assert_eq!(tree.len(), 100);
+        assert_eq!(tree.extent(), Some(bbox(0.0, 0.0, 9.5, 9.5)));
+
+        let query = bbox(2.2, 3.2, 4.2, 4.2);
+        let mut ids: Vec<u64> = tree.search(&query).iter().map(|hit| hit.id).collect();
+        ids.sort_unstable();
+        assert_eq!(ids, vec![32, 33, 34, 42, 43, 44]);
+
+        assert!(tree.search(&bbox(20.0, 20.0, 30.0, 30.0)).is_empty());
+    }
+
+    #[test]
+    fn empty_tree() {
+        let tree = PackedRTreeBuilder::new().build();
+        assert!(tree.is_empty());
+        assert!(tree.items().is_empty());
+        assert!(tree.search(&bbox(0.0, 0.0, 1.0, 1.0)).is_empty());
+    }
+}
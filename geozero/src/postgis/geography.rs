@@ -0,0 +1,31 @@
+//! PostGIS `geography` type.
+use crate::error::{GeozeroError, Result};
+
+/// SRID of PostGIS `geography` values
+pub(crate) const GEOGRAPHY_SRID: i32 = 4326;
+
+/// SRID for encoding a geometry as `geography`.
+///
+/// Missing SRIDs default to 4326, other SRIDs are rejected.
+pub(crate) fn geography_srid(srid: Option<i32>) -> Result<i32> {
+    match srid {
+        None | Some(0) | Some(GEOGRAPHY_SRID) => Ok(GEOGRAPHY_SRID),
+        Some(srid) => Err(GeozeroError::Srid(srid)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn srid() {
+        assert_eq!(geography_srid(None).unwrap(), 4326);
+        assert_eq!(geography_srid(Some(0)).unwrap(), 4326);
+        assert_eq!(geography_srid(Some(4326)).unwrap(), 4326);
+        assert!(matches!(
+            geography_srid(Some(3857)),
+            Err(GeozeroError::Srid(3857))
+        ));
+    }
+}
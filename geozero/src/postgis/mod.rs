@@ -3,6 +3,14 @@
 //! All geometry types implementing [GeozeroGeometry](crate::GeozeroGeometry) can be encoded as PostGIS EWKB geometry using [wkb::Encode](crate::wkb::Encode).
 //!
 //! Geometry types implementing [FromWkb](crate::wkb::FromWkb) can be decoded from PostGIS geometries using [wkb::Decode](crate::wkb::Decode).
+#[cfg(any(
+    feature = "with-postgis-diesel",
+    feature = "with-postgis-postgres",
+    feature = "with-postgis-sqlx"
+))]
+mod geography;
+#[cfg(any(feature = "with-postgis-postgres", feature = "with-postgis-sqlx"))]
+mod pg_values;
 #[cfg(feature = "with-postgis-diesel")]
 mod postgis_diesel;
 #[cfg(feature = "with-postgis-postgres")]
@@ -44,7 +52,10 @@ mod postgis_sqlx;
 /// # Ok(())
 /// # }
 ///```
-pub mod postgres {}
+pub mod postgres {
+    #[cfg(feature = "with-postgis-postgres")]
+    pub use super::postgis_postgres::PgDatasource;
+}
 
 /// PostGIS geometry type encoding/decoding for SQLx. Requires the `with-postgis-sqlx` feature.
 ///
@@ -86,7 +97,9 @@ pub mod postgres {}
 /// # }
 /// ```
 #[cfg(feature = "with-postgis-sqlx")]
-pub mod sqlx {}
+pub mod sqlx {
+    pub use super::postgis_sqlx::PgDatasource;
+}
 
 /// Postgis geometry type encoding for Diesel. Requires the `with-postgis-diesel` feature.
 ///
@@ -147,6 +160,39 @@ pub mod sqlx {}
 /// # Ok(())
 /// # }
 /// ```
+///
+/// Select and insert geo-types geometries with [wkb::Decode](crate::wkb::Decode) and [wkb::Encode](crate::wkb::Encode):
+///
+/// ```
+/// use diesel::pg::PgConnection;
+/// use diesel::prelude::*;
+/// use geozero::wkb;
+///
+/// diesel::table! {
+///     use diesel::sql_types::*;
+///     use geozero::postgis::diesel::sql_types::*;
+///
+///     geometries (name) {
+///         name -> Varchar,
+///         geom -> Geometry,
+///     }
+/// }
+///
+/// # fn rust_geo_query(conn: &mut PgConnection) -> Result<(), diesel::result::Error> {
+/// let geom: geo_types::Geometry<f64> = geo_types::Point::new(10.0, 20.0).into();
+/// diesel::insert_into(geometries::table)
+///     .values((
+///         geometries::name.eq("point"),
+///         geometries::geom.eq(wkb::Encode(geom)),
+///     ))
+///     .execute(conn)?;
+///
+/// let geoms: Vec<wkb::Decode<geo_types::Geometry<f64>>> = geometries::table
+///     .select(geometries::geom)
+///     .load(conn)?;
+/// # Ok(())
+/// # }
+/// ```
 #[cfg(feature = "with-postgis-diesel")]
 pub mod diesel {
     pub use super::postgis_diesel::*;
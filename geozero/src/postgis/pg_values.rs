@@ -0,0 +1,181 @@
+//! Decoding of PostgreSQL binary property values.
+use crate::error::{GeozeroError, Result};
+use crate::ColumnValueOwned;
+
+const BOOL_OID: u32 = 16;
+const INT8_OID: u32 = 20;
+const INT2_OID: u32 = 21;
+const INT4_OID: u32 = 23;
+const TEXT_OID: u32 = 25;
+const FLOAT4_OID: u32 = 700;
+const FLOAT8_OID: u32 = 701;
+const BPCHAR_OID: u32 = 1042;
+const VARCHAR_OID: u32 = 1043;
+const NUMERIC_OID: u32 = 1700;
+const UUID_OID: u32 = 2950;
+
+fn format_err(type_name: &str) -> GeozeroError {
+    GeozeroError::Property(format!("invalid {type_name} value"))
+}
+
+fn be_bytes<const N: usize>(raw: &[u8], pos: usize, type_name: &str) -> Result<[u8; N]> {
+    raw.get(pos..pos + N)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| format_err(type_name))
+}
+
+/// Decode `uuid` value
+pub(crate) fn uuid(raw: &[u8]) -> Result<[u8; 16]> {
+    raw.try_into().map_err(|_| format_err("uuid"))
+}
+
+/// Decode `numeric` value into its decimal string representation
+pub(crate) fn numeric(raw: &[u8]) -> Result<String> {
+    let header = |pos| be_bytes::<2>(raw, pos, "numeric");
+    let ndigits = i16::from_be_bytes(header(0)?).max(0) as usize;
+    let weight = i16::from_be_bytes(header(2)?) as i32;
+    let sign = u16::from_be_bytes(header(4)?);
+    let dscale = u16::from_be_bytes(header(6)?) as usize;
+    match sign {
+        0x0000 | 0x4000 => {}
+        0xC000 => return Ok("NaN".to_string()),
+        0xD000 => return Ok("Infinity".to_string()),
+        0xF000 => return Ok("-Infinity".to_string()),
+        _ => return Err(format_err("numeric")),
+    }
+    // Base 10000 digits
+    let digits = (0..ndigits)
+        .map(|i| header(8 + 2 * i).map(i16::from_be_bytes))
+        .collect::<Result<Vec<_>>>()?;
+    let digit = |i: i32| {
+        usize::try_from(i)
+            .ok()
+            .and_then(|i| digits.get(i))
+            .copied()
+            .unwrap_or(0)
+    };
+
+    let mut s = String::new();
+    if sign == 0x4000 {
+        s.push('-');
+    }
+    if weight < 0 {
+        s.push('0');
+    } else {
+        s.push_str(&digit(0).to_string());
+        for i in 1..=weight {
+            s.push_str(&format!("{:04}", digit(i)));
+        }
+    }
+    if dscale > 0 {
+        let mut fraction = String::new();
+        let mut i = weight + 1;
+        while fraction.len() < dscale {
+            fraction.push_str(&format!("{:04}", digit(i)));
+            i += 1;
+        }
+        s.push('.');
+        s.push_str(&fraction[..dscale]);
+    }
+    Ok(s)
+}
+
+/// Decode one-dimensional array of a supported element type.
+///
+/// Returns `None` for unsupported element types, multi-dimensional arrays and arrays containing NULL.
+pub(crate) fn array(raw: &[u8]) -> Result<Option<Vec<ColumnValueOwned>>> {
+    let int = |pos| be_bytes::<4>(raw, pos, "array").map(i32::from_be_bytes);
+    let ndim = int(0)?;
+    let has_null = int(4)?;
+    let elem_oid = int(8)? as u32;
+    if ndim == 0 {
+        return Ok(Some(Vec::new()));
+    }
+    if ndim != 1 || has_null != 0 {
+        return Ok(None);
+    }
+    let len = int(12)?.max(0) as usize;
+    let mut pos = 20;
+    let mut values = Vec::with_capacity(len);
+    for _ in 0..len {
+        let size = usize::try_from(int(pos)?).map_err(|_| format_err("array"))?;
+        pos += 4;
+        let elem = raw
+            .get(pos..pos + size)
+            .ok_or_else(|| format_err("array"))?;
+        pos += size;
+        let value = match elem_oid {
+            BOOL_OID => ColumnValueOwned::Bool(elem.first() == Some(&1)),
+            INT2_OID => ColumnValueOwned::Short(i16::from_be_bytes(be_bytes(elem, 0, "int2")?)),
+            INT4_OID => ColumnValueOwned::Int(i32::from_be_bytes(be_bytes(elem, 0, "int4")?)),
+            INT8_OID => ColumnValueOwned::Long(i64::from_be_bytes(be_bytes(elem, 0, "int8")?)),
+            FLOAT4_OID => ColumnValueOwned::Float(f32::from_be_bytes(be_bytes(elem, 0, "float4")?)),
+            FLOAT8_OID => {
+                ColumnValueOwned::Double(f64::from_be_bytes(be_bytes(elem, 0, "float8")?))
+            }
+            TEXT_OID | VARCHAR_OID | BPCHAR_OID => ColumnValueOwned::String(
+                String::from_utf8(elem.to_vec())
+                    .map_err(|e| GeozeroError::Property(e.to_string()))?,
+            ),
+            NUMERIC_OID => ColumnValueOwned::Decimal(numeric(elem)?),
+            UUID_OID => ColumnValueOwned::Uuid(uuid(elem)?),
+            _ => return Ok(None),
+        };
+        values.push(value);
+    }
+    Ok(Some(values))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn numeric_bytes(weight: i16, sign: u16, dscale: u16, digits: &[i16]) -> Vec<u8> {
+        let mut raw = Vec::new();
+        raw.extend((digits.len() as i16).to_be_bytes());
+        raw.extend(weight.to_be_bytes());
+        raw.extend(sign.to_be_bytes());
+        raw.extend(dscale.to_be_bytes());
+        for digit in digits {
+            raw.extend(digit.to_be_bytes());
+        }
+        raw
+    }
+
+    #[test]
+    fn decode_numeric() -> Result<()> {
+        assert_eq!(numeric(&numeric_bytes(0, 0, 0, &[]))?, "0");
+        // 12345678.9
+        assert_eq!(
+            numeric(&numeric_bytes(1, 0, 1, &[1234, 5678, 9000]))?,
+            "12345678.9"
+        );
+        // -0.0012
+        assert_eq!(numeric(&numeric_bytes(-1, 0x4000, 4, &[12]))?, "-0.0012");
+        // 10000 with trailing zero digit groups stripped
+        assert_eq!(numeric(&numeric_bytes(1, 0, 2, &[1]))?, "10000.00");
+        assert_eq!(numeric(&numeric_bytes(0, 0xC000, 0, &[]))?, "NaN");
+        Ok(())
+    }
+
+    #[test]
+    fn decode_array() -> Result<()> {
+        let mut raw = Vec::new();
+        for v in [1i32, 0, INT4_OID as i32, 2, 1] {
+            raw.extend(v.to_be_bytes());
+        }
+        for v in [7i32, -3] {
+            raw.extend(4i32.to_be_bytes());
+            raw.extend(v.to_be_bytes());
+        }
+        assert_eq!(
+            array(&raw)?,
+            Some(vec![ColumnValueOwned::Int(7), ColumnValueOwned::Int(-3)])
+        );
+
+        // Containing NULL
+        raw[7] = 1;
+        assert_eq!(array(&raw)?, None);
+        Ok(())
+    }
+}
@@ -1,5 +1,7 @@
+use crate::postgis::geography::geography_srid;
 use crate::postgis::postgis_diesel::sql_types::{Geography, Geometry};
-use crate::wkb::Ewkb;
+use crate::wkb::{self, Ewkb, FromWkb};
+use crate::GeozeroGeometry;
 use std::io::Write as _;
 
 use diesel::deserialize::{self, FromSql};
@@ -44,3 +46,70 @@ impl FromSql<Geography, Pg> for Ewkb<Vec<u8>> {
         Ok(Self(bytes.as_bytes().to_vec()))
     }
 }
+
+impl<T: GeozeroGeometry> ToSql<Geometry, Pg> for wkb::Encode<T> {
+    fn to_sql(&self, out: &mut Output<Pg>) -> serialize::Result {
+        encode(&self.0, out)
+    }
+}
+
+impl<T: GeozeroGeometry> ToSql<Geography, Pg> for wkb::Encode<T> {
+    fn to_sql(&self, out: &mut Output<Pg>) -> serialize::Result {
+        encode(&self.0, out)
+    }
+}
+
+impl<T: GeozeroGeometry> ToSql<Geography, Pg> for wkb::EncodeGeography<T> {
+    fn to_sql(&self, out: &mut Output<Pg>) -> serialize::Result {
+        let srid = geography_srid(self.0.srid())?;
+        let mut writer = wkb::WkbWriter::with_opts(
+            out,
+            wkb::WkbDialect::Ewkb,
+            self.0.dims(),
+            Some(srid),
+            Vec::new(),
+        );
+        self.0.process_geom(&mut writer)?;
+        Ok(IsNull::No)
+    }
+}
+
+fn encode<T: GeozeroGeometry>(geom: &T, out: &mut Output<Pg>) -> serialize::Result {
+    let mut writer = wkb::WkbWriter::with_opts(
+        out,
+        wkb::WkbDialect::Ewkb,
+        geom.dims(),
+        geom.srid(),
+        Vec::new(),
+    );
+    geom.process_geom(&mut writer)?;
+    Ok(IsNull::No)
+}
+
+impl<T: FromWkb> FromSql<Geometry, Pg> for wkb::Decode<T> {
+    fn from_sql(bytes: pg::PgValue) -> deserialize::Result<Self> {
+        decode(bytes)
+    }
+
+    fn from_nullable_sql(bytes: Option<pg::PgValue>) -> deserialize::Result<Self> {
+        bytes.map_or(Ok(wkb::Decode { geometry: None }), decode)
+    }
+}
+
+impl<T: FromWkb> FromSql<Geography, Pg> for wkb::Decode<T> {
+    fn from_sql(bytes: pg::PgValue) -> deserialize::Result<Self> {
+        decode(bytes)
+    }
+
+    fn from_nullable_sql(bytes: Option<pg::PgValue>) -> deserialize::Result<Self> {
+        bytes.map_or(Ok(wkb::Decode { geometry: None }), decode)
+    }
+}
+
+fn decode<T: FromWkb>(bytes: pg::PgValue) -> deserialize::Result<wkb::Decode<T>> {
+    let mut rdr = bytes.as_bytes();
+    let geom = T::from_wkb(&mut rdr, wkb::WkbDialect::Ewkb)?;
+    Ok(wkb::Decode {
+        geometry: Some(geom),
+    })
+}
@@ -1,7 +1,11 @@
+use crate::error::GeozeroError;
+use crate::postgis::geography::geography_srid;
+use crate::postgis::pg_values;
 use crate::wkb::{self, FromWkb};
-use crate::GeozeroGeometry;
+use crate::{ColumnValue, FeatureProcessor, GeozeroDatasource, GeozeroGeometry, PropertyProcessor};
 use bytes::{BufMut, BytesMut};
-use postgres_types::{to_sql_checked, FromSql, IsNull, ToSql, Type};
+use postgres::fallible_iterator::FallibleIterator;
+use postgres_types::{to_sql_checked, FromSql, IsNull, Kind, ToSql, Type};
 
 impl<T: FromWkb + Sized> FromSql<'_> for wkb::Decode<T> {
     fn from_sql(_ty: &Type, raw: &[u8]) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
@@ -60,6 +64,32 @@ impl<T: GeozeroGeometry + Sized> ToSql for wkb::Encode<T> {
     to_sql_checked!();
 }
 
+impl<T: GeozeroGeometry + Sized> ToSql for wkb::EncodeGeography<T> {
+    fn to_sql(
+        &self,
+        _ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn std::error::Error + Sync + Send>> {
+        let srid = geography_srid(self.0.srid())?;
+        let pgout = &mut out.writer();
+        let mut writer = wkb::WkbWriter::with_opts(
+            pgout,
+            wkb::WkbDialect::Ewkb,
+            self.0.dims(),
+            Some(srid),
+            Vec::new(),
+        );
+        self.0.process_geom(&mut writer)?;
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        ty.name() == "geography"
+    }
+
+    to_sql_checked!();
+}
+
 // Same as macros for geometry types without wrapper
 // Limitations:
 // - Can only be used with self defined types
@@ -129,3 +159,169 @@ macro_rules! impl_postgres_postgis_encode {
         }
     };
 }
+
+/// Raw binary column value
+struct RawValue<'a>(&'a [u8]);
+
+impl<'a> FromSql<'a> for RawValue<'a> {
+    fn from_sql(
+        _ty: &Type,
+        raw: &'a [u8],
+    ) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+        Ok(RawValue(raw))
+    }
+
+    fn accepts(_ty: &Type) -> bool {
+        true
+    }
+}
+
+/// PostGIS query result as datasource.
+///
+/// Non-geometry columns are processed as properties, columns with unsupported types are skipped.
+/// The geometry column defaults to the first column of type `geometry` or `geography`.
+///
+/// # Usage example
+///
+/// ```
+/// use geozero::geojson::GeoJsonWriter;
+/// use geozero::postgis::postgres::PgDatasource;
+/// use geozero::GeozeroDatasource;
+/// use postgres::{Client, NoTls};
+///
+/// # fn export_geojson() -> geozero::error::Result<()> {
+/// let mut client = Client::connect(&std::env::var("DATABASE_URL").unwrap(), NoTls)?;
+/// let mut datasource = PgDatasource::new(&mut client, "SELECT name, geom FROM countries");
+/// let mut json: Vec<u8> = Vec::new();
+/// datasource.process(&mut GeoJsonWriter::new(&mut json))?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct PgDatasource<'a> {
+    client: &'a mut postgres::Client,
+    sql: String,
+    geometry_column: Option<String>,
+}
+
+impl<'a> PgDatasource<'a> {
+    pub fn new(client: &'a mut postgres::Client, sql: &str) -> Self {
+        PgDatasource {
+            client,
+            sql: sql.to_string(),
+            geometry_column: None,
+        }
+    }
+
+    /// Name of geometry column
+    pub fn set_geometry_column(&mut self, name: &str) {
+        self.geometry_column = Some(name.to_string());
+    }
+}
+
+impl GeozeroDatasource for PgDatasource<'_> {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> crate::error::Result<()> {
+        let params: [&(dyn ToSql + Sync); 0] = [];
+        let mut rows = self.client.query_raw(self.sql.as_str(), params)?;
+        let mut geom_idx = None;
+        let mut idx = 0;
+        processor.dataset_begin(None)?;
+        while let Some(row) = rows.next()? {
+            if idx == 0 {
+                geom_idx = row
+                    .columns()
+                    .iter()
+                    .position(|col| match &self.geometry_column {
+                        Some(name) => col.name() == name,
+                        None => matches!(col.type_().name(), "geometry" | "geography"),
+                    });
+            }
+            processor.feature_begin(idx)?;
+            processor.properties_begin()?;
+            process_properties(&row, geom_idx, processor)?;
+            processor.properties_end()?;
+            if let Some(i) = geom_idx {
+                if let Some(geom) = row.try_get::<_, Option<RawValue>>(i)? {
+                    processor.geometry_begin()?;
+                    wkb::Ewkb(geom.0).process_geom(processor)?;
+                    processor.geometry_end()?;
+                }
+            }
+            processor.feature_end(idx)?;
+            idx += 1;
+        }
+        processor.dataset_end()
+    }
+}
+
+fn process_properties<P: PropertyProcessor>(
+    row: &postgres::Row,
+    geom_idx: Option<usize>,
+    processor: &mut P,
+) -> crate::error::Result<()> {
+    let mut prop_idx = 0;
+    for (i, column) in row.columns().iter().enumerate() {
+        if Some(i) == geom_idx {
+            continue;
+        }
+        let name = column.name();
+        let mut property = |value: &ColumnValue| processor.property(prop_idx, name, value);
+        let finish = match *column.type_() {
+            Type::BOOL => row
+                .try_get::<_, Option<bool>>(i)?
+                .map(|v| property(&ColumnValue::Bool(v))),
+            Type::INT2 => row
+                .try_get::<_, Option<i16>>(i)?
+                .map(|v| property(&ColumnValue::Short(v))),
+            Type::INT4 => row
+                .try_get::<_, Option<i32>>(i)?
+                .map(|v| property(&ColumnValue::Int(v))),
+            Type::INT8 => row
+                .try_get::<_, Option<i64>>(i)?
+                .map(|v| property(&ColumnValue::Long(v))),
+            Type::FLOAT4 => row
+                .try_get::<_, Option<f32>>(i)?
+                .map(|v| property(&ColumnValue::Float(v))),
+            Type::FLOAT8 => row
+                .try_get::<_, Option<f64>>(i)?
+                .map(|v| property(&ColumnValue::Double(v))),
+            Type::TEXT | Type::VARCHAR | Type::BPCHAR | Type::NAME => row
+                .try_get::<_, Option<&str>>(i)?
+                .map(|v| property(&ColumnValue::String(v))),
+            Type::JSON | Type::JSONB => row.try_get::<_, Option<RawValue>>(i)?.map(|v| {
+                // JSONB binary format starts with a version byte
+                let json = if *column.type_() == Type::JSONB {
+                    v.0.get(1..).unwrap_or_default()
+                } else {
+                    v.0
+                };
+                let json =
+                    std::str::from_utf8(json).map_err(|e| GeozeroError::Property(e.to_string()))?;
+                property(&ColumnValue::Json(json))
+            }),
+            Type::BYTEA => row
+                .try_get::<_, Option<&[u8]>>(i)?
+                .map(|v| property(&ColumnValue::Binary(v))),
+            Type::UUID => row
+                .try_get::<_, Option<RawValue>>(i)?
+                .map(|v| property(&ColumnValue::Uuid(pg_values::uuid(v.0)?))),
+            Type::NUMERIC => row
+                .try_get::<_, Option<RawValue>>(i)?
+                .map(|v| property(&ColumnValue::Decimal(&pg_values::numeric(v.0)?))),
+            _ if matches!(column.type_().kind(), Kind::Array(_)) => {
+                match row.try_get::<_, Option<RawValue>>(i)? {
+                    Some(v) => {
+                        pg_values::array(v.0)?.map(|values| property(&ColumnValue::Array(&values)))
+                    }
+                    None => None,
+                }
+            }
+            _ => None,
+        };
+        match finish.transpose()? {
+            Some(true) => break,
+            Some(false) => prop_idx += 1,
+            None => {}
+        }
+    }
+    Ok(())
+}
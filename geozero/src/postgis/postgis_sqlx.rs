@@ -1,16 +1,29 @@
+use crate::error::GeozeroError;
+use crate::postgis::geography::geography_srid;
+use crate::postgis::pg_values;
 use crate::wkb::{self, FromWkb};
-use crate::GeozeroGeometry;
+use crate::{ColumnValue, FeatureProcessor, GeozeroDatasource, GeozeroGeometry, PropertyProcessor};
+use futures_util::TryStreamExt;
 use sqlx::decode::Decode;
 use sqlx::encode::{Encode, IsNull};
-use sqlx::postgres::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueRef, Postgres};
-use sqlx::ValueRef;
+use sqlx::postgres::{PgArgumentBuffer, PgHasArrayType, PgRow, PgTypeInfo, PgValueRef, Postgres};
+use sqlx::{Column, Executor, Row, TypeInfo, ValueRef};
 
 type BoxDynError = Box<dyn std::error::Error + Send + Sync>;
 
+/// Column of type `geometry` or `geography`
+fn is_geometry_type(ty: &PgTypeInfo) -> bool {
+    matches!(ty.name(), "geometry" | "geography")
+}
+
 impl<T: FromWkb + Sized> sqlx::Type<Postgres> for wkb::Decode<T> {
     fn type_info() -> PgTypeInfo {
         PgTypeInfo::with_name("geometry")
     }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        is_geometry_type(ty)
+    }
 }
 
 impl<T: FromWkb + Sized> PgHasArrayType for wkb::Decode<T> {
@@ -37,6 +50,10 @@ impl<B: AsRef<[u8]>> sqlx::Type<Postgres> for wkb::Ewkb<B> {
     fn type_info() -> PgTypeInfo {
         PgTypeInfo::with_name("geometry")
     }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        is_geometry_type(ty)
+    }
 }
 
 impl<B: AsRef<[u8]>> PgHasArrayType for wkb::Ewkb<B> {
@@ -77,15 +94,43 @@ impl<T: GeozeroGeometry + Sized> Encode<'_, Postgres> for wkb::Encode<T> {
             self.0.srid(),
             Vec::new(),
         );
-        self.0
-            .process_geom(&mut writer)
-            .expect("Failed to encode Geometry");
+        self.0.process_geom(&mut writer)?;
         buf.extend(&wkb_out); // Is there a way to write directly into PgArgumentBuffer?
 
         Ok(IsNull::No)
     }
 }
 
+impl<T: GeozeroGeometry + Sized> sqlx::Type<Postgres> for wkb::EncodeGeography<T> {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("geography")
+    }
+}
+
+impl<T: GeozeroGeometry + Sized> PgHasArrayType for wkb::EncodeGeography<T> {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("_geography")
+    }
+}
+
+impl<T: GeozeroGeometry + Sized> Encode<'_, Postgres> for wkb::EncodeGeography<T> {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        let srid = geography_srid(self.0.srid())?;
+        let mut wkb_out: Vec<u8> = Vec::new();
+        let mut writer = wkb::WkbWriter::with_opts(
+            &mut wkb_out,
+            wkb::WkbDialect::Ewkb,
+            self.0.dims(),
+            Some(srid),
+            Vec::new(),
+        );
+        self.0.process_geom(&mut writer)?;
+        buf.extend(&wkb_out);
+
+        Ok(IsNull::No)
+    }
+}
+
 // Same as macros for geometry types without wrapper
 // Limitations:
 // - Can only be used with self defined types
@@ -99,6 +144,11 @@ macro_rules! impl_sqlx_postgis_type_info {
             fn type_info() -> sqlx::postgres::PgTypeInfo {
                 sqlx::postgres::PgTypeInfo::with_name("geometry")
             }
+
+            fn compatible(ty: &sqlx::postgres::PgTypeInfo) -> bool {
+                use sqlx::TypeInfo;
+                matches!(ty.name(), "geometry" | "geography")
+            }
         }
 
         impl sqlx::postgres::PgHasArrayType for $t {
@@ -157,8 +207,7 @@ macro_rules! impl_sqlx_postgis_encode {
                     self.srid(),
                     Vec::new(),
                 );
-                self.process_geom(&mut writer)
-                    .expect("Failed to encode Geometry");
+                self.process_geom(&mut writer)?;
                 buf.extend(&wkb_out); // Is there a way to write directly into PgArgumentBuffer?
 
                 std::result::Result::Ok(sqlx::encode::IsNull::No)
@@ -166,3 +215,232 @@ macro_rules! impl_sqlx_postgis_encode {
         }
     };
 }
+
+/// PostGIS query result as datasource.
+///
+/// Rows are processed with [read()](PgDatasource::read) while fetching them from the database.
+/// [fetch()](PgDatasource::fetch) loads all rows into [PgFeatures], which can be processed as
+/// [GeozeroDatasource].
+/// Non-geometry columns are processed as properties, columns with unsupported types are skipped.
+/// The geometry column defaults to the first column of type `geometry` or `geography`.
+///
+/// # Usage example
+///
+/// ```
+/// use geozero::geojson::GeoJsonWriter;
+/// use geozero::postgis::sqlx::PgDatasource;
+/// use sqlx::postgres::PgPoolOptions;
+///
+/// # async fn export_geojson() -> geozero::error::Result<()> {
+/// let pool = PgPoolOptions::new()
+///     .connect(&std::env::var("DATABASE_URL").unwrap())
+///     .await?;
+/// let mut datasource = PgDatasource::new("SELECT name, geom FROM countries");
+/// let mut json: Vec<u8> = Vec::new();
+/// datasource.read(&pool, &mut GeoJsonWriter::new(&mut json)).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct PgDatasource {
+    sql: String,
+    geometry_column: Option<String>,
+}
+
+impl PgDatasource {
+    pub fn new(sql: &str) -> Self {
+        PgDatasource {
+            sql: sql.to_string(),
+            geometry_column: None,
+        }
+    }
+
+    /// Name of geometry column
+    pub fn set_geometry_column(&mut self, name: &str) {
+        self.geometry_column = Some(name.to_string());
+    }
+
+    /// Execute query and process rows with `processor`.
+    ///
+    /// Returns the number of processed rows.
+    pub async fn read<'e, E, P>(
+        &self,
+        executor: E,
+        processor: &mut P,
+    ) -> crate::error::Result<usize>
+    where
+        E: Executor<'e, Database = Postgres>,
+        P: FeatureProcessor,
+    {
+        let mut rows = sqlx::query(&self.sql).fetch(executor);
+        let mut geom_idx = None;
+        let mut idx = 0;
+        processor.dataset_begin(None)?;
+        while let Some(row) = rows.try_next().await? {
+            if idx == 0 {
+                geom_idx = self.geometry_index(&row);
+            }
+            process_row(&row, geom_idx, idx, processor)?;
+            idx += 1;
+        }
+        processor.dataset_end()?;
+        Ok(idx as usize)
+    }
+
+    /// Execute query and fetch all rows.
+    ///
+    /// Use [read()](PgDatasource::read) to process large results without loading them into memory.
+    pub async fn fetch<'e, E>(&self, executor: E) -> crate::error::Result<PgFeatures>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let rows = sqlx::query(&self.sql).fetch_all(executor).await?;
+        let geom_idx = rows.first().and_then(|row| self.geometry_index(row));
+        Ok(PgFeatures { rows, geom_idx })
+    }
+
+    fn geometry_index(&self, row: &PgRow) -> Option<usize> {
+        row.columns()
+            .iter()
+            .position(|col| match &self.geometry_column {
+                Some(name) => col.name() == name,
+                None => is_geometry_type(col.type_info()),
+            })
+    }
+}
+
+/// Rows fetched with [PgDatasource::fetch].
+pub struct PgFeatures {
+    rows: Vec<PgRow>,
+    geom_idx: Option<usize>,
+}
+
+impl PgFeatures {
+    /// Number of fetched rows
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+}
+
+impl GeozeroDatasource for PgFeatures {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> crate::error::Result<()> {
+        processor.dataset_begin(None)?;
+        for (idx, row) in self.rows.iter().enumerate() {
+            process_row(row, self.geom_idx, idx as u64, processor)?;
+        }
+        processor.dataset_end()
+    }
+}
+
+fn process_row<P: FeatureProcessor>(
+    row: &PgRow,
+    geom_idx: Option<usize>,
+    idx: u64,
+    processor: &mut P,
+) -> crate::error::Result<()> {
+    processor.feature_begin(idx)?;
+    processor.properties_begin()?;
+    process_properties(row, geom_idx, processor)?;
+    processor.properties_end()?;
+    if let Some(i) = geom_idx {
+        let value = row.try_get_raw(i)?;
+        if !value.is_null() {
+            let wkb = <&[u8] as Decode<Postgres>>::decode(value)
+                .map_err(|e| GeozeroError::Geometry(e.to_string()))?;
+            processor.geometry_begin()?;
+            wkb::Ewkb(wkb).process_geom(processor)?;
+            processor.geometry_end()?;
+        }
+    }
+    processor.feature_end(idx)
+}
+
+/// Binary value of a column
+fn raw_bytes(row: &PgRow, i: usize) -> crate::error::Result<Option<&[u8]>> {
+    let value = row.try_get_raw(i)?;
+    if value.is_null() {
+        return Ok(None);
+    }
+    <&[u8] as Decode<Postgres>>::decode(value)
+        .map(Some)
+        .map_err(|e| GeozeroError::Property(e.to_string()))
+}
+
+fn process_properties<P: PropertyProcessor>(
+    row: &PgRow,
+    geom_idx: Option<usize>,
+    processor: &mut P,
+) -> crate::error::Result<()> {
+    let mut prop_idx = 0;
+    for (i, column) in row.columns().iter().enumerate() {
+        if Some(i) == geom_idx {
+            continue;
+        }
+        let name = column.name();
+        let mut property = |value: &ColumnValue| processor.property(prop_idx, name, value);
+        let finish = match column.type_info().name() {
+            "BOOL" => row
+                .try_get::<Option<bool>, _>(i)?
+                .map(|v| property(&ColumnValue::Bool(v))),
+            "INT2" => row
+                .try_get::<Option<i16>, _>(i)?
+                .map(|v| property(&ColumnValue::Short(v))),
+            "INT4" => row
+                .try_get::<Option<i32>, _>(i)?
+                .map(|v| property(&ColumnValue::Int(v))),
+            "INT8" => row
+                .try_get::<Option<i64>, _>(i)?
+                .map(|v| property(&ColumnValue::Long(v))),
+            "FLOAT4" => row
+                .try_get::<Option<f32>, _>(i)?
+                .map(|v| property(&ColumnValue::Float(v))),
+            "FLOAT8" => row
+                .try_get::<Option<f64>, _>(i)?
+                .map(|v| property(&ColumnValue::Double(v))),
+            "TEXT" | "VARCHAR" | "BPCHAR" | "NAME" => row
+                .try_get::<Option<&str>, _>(i)?
+                .map(|v| property(&ColumnValue::String(v))),
+            type_name @ ("JSON" | "JSONB") => {
+                let value = row.try_get_raw(i)?;
+                if value.is_null() {
+                    None
+                } else {
+                    let json = <&[u8] as Decode<Postgres>>::decode(value)
+                        .map_err(|e| GeozeroError::Property(e.to_string()))?;
+                    // JSONB binary format starts with a version byte
+                    let json = if type_name == "JSONB" {
+                        json.get(1..).unwrap_or_default()
+                    } else {
+                        json
+                    };
+                    let json = std::str::from_utf8(json)
+                        .map_err(|e| GeozeroError::Property(e.to_string()))?;
+                    Some(property(&ColumnValue::Json(json)))
+                }
+            }
+            "BYTEA" => row
+                .try_get::<Option<&[u8]>, _>(i)?
+                .map(|v| property(&ColumnValue::Binary(v))),
+            "UUID" => raw_bytes(row, i)?.map(|v| property(&ColumnValue::Uuid(pg_values::uuid(v)?))),
+            "NUMERIC" => {
+                raw_bytes(row, i)?.map(|v| property(&ColumnValue::Decimal(&pg_values::numeric(v)?)))
+            }
+            type_name if type_name.ends_with("[]") => match raw_bytes(row, i)? {
+                Some(v) => {
+                    pg_values::array(v)?.map(|values| property(&ColumnValue::Array(&values)))
+                }
+                None => None,
+            },
+            _ => None,
+        };
+        match finish.transpose()? {
+            Some(true) => break,
+            Some(false) => prop_idx += 1,
+            None => {}
+        }
+    }
+    Ok(())
+}
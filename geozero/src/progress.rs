@@ -0,0 +1,355 @@
+//! Progress reporting for long running conversions.
+use crate::{
+    error::Result, ColumnDef, ColumnValue, CoordDimensions, Crs, FeatureId, FeatureProcessor,
+    GeomProcessor, PropertyProcessor,
+};
+use std::io::{Read, Seek, SeekFrom};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Processing progress
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Progress {
+    /// Number of processed features
+    pub features: u64,
+    /// Number of bytes read from the input, if tracked with a [ProgressReader]
+    pub bytes_read: Option<u64>,
+    /// Total size of the input in bytes, if known
+    pub total_bytes: Option<u64>,
+}
+
+impl Progress {
+    /// Fraction of the input read, if the total size is known
+    pub fn fraction(&self) -> Option<f64> {
+        match (self.bytes_read, self.total_bytes) {
+            (Some(read), Some(total)) if total > 0 => Some((read as f64 / total as f64).min(1.0)),
+            _ => None,
+        }
+    }
+}
+
+/// Receiver of progress notifications.
+///
+/// Implemented for closures taking a [Progress] reference.
+pub trait ProgressObserver {
+    fn progress(&mut self, progress: &Progress);
+}
+
+impl<F: FnMut(&Progress)> ProgressObserver for F {
+    fn progress(&mut self, progress: &Progress) {
+        self(progress);
+    }
+}
+
+/// Reader counting the bytes read from the inner reader.
+///
+/// The counter can be shared with a [ProgressProcessor] processing the features of the input.
+pub struct ProgressReader<R> {
+    inner: R,
+    bytes_read: Arc<AtomicU64>,
+}
+
+impl<R> ProgressReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self::with_counter(inner, Arc::default())
+    }
+    /// Count bytes read with an existing counter
+    pub fn with_counter(inner: R, bytes_read: Arc<AtomicU64>) -> Self {
+        Self { inner, bytes_read }
+    }
+    /// Counter of bytes read
+    pub fn counter(&self) -> Arc<AtomicU64> {
+        self.bytes_read.clone()
+    }
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for ProgressReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.bytes_read.fetch_add(n as u64, Ordering::Relaxed);
+        Ok(n)
+    }
+}
+
+impl<R: Seek> Seek for ProgressReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
+/// Wraps another [FeatureProcessor], reporting progress to a [ProgressObserver].
+///
+/// The observer is notified every `interval` features (default: 1000) and at the end of the dataset.
+pub struct ProgressProcessor<P, O: ProgressObserver> {
+    inner: P,
+    observer: O,
+    interval: u64,
+    progress: Progress,
+    bytes_read: Option<Arc<AtomicU64>>,
+}
+
+impl<P, O: ProgressObserver> ProgressProcessor<P, O> {
+    pub fn new(inner: P, observer: O) -> Self {
+        ProgressProcessor {
+            inner,
+            observer,
+            interval: 1000,
+            progress: Progress::default(),
+            bytes_read: None,
+        }
+    }
+    /// Set number of features between notifications
+    pub fn set_interval(&mut self, interval: u64) {
+        self.interval = interval.max(1);
+    }
+    /// Report bytes read from the counter of a [ProgressReader] and the total input size
+    pub fn set_bytes_read(&mut self, bytes_read: Arc<AtomicU64>, total_bytes: Option<u64>) {
+        self.bytes_read = Some(bytes_read);
+        self.progress.total_bytes = total_bytes;
+    }
+    /// Current progress
+    pub fn progress(&self) -> Progress {
+        Progress {
+            bytes_read: self
+                .bytes_read
+                .as_ref()
+                .map(|bytes_read| bytes_read.load(Ordering::Relaxed)),
+            ..self.progress
+        }
+    }
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+
+    fn notify(&mut self) {
+        let progress = self.progress();
+        self.observer.progress(&progress);
+    }
+}
+
+// The trait has many default implementations, but every single call must be specified here to
+// delegate
+impl<P: GeomProcessor, O: ProgressObserver> GeomProcessor for ProgressProcessor<P, O> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.inner.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.inner.multi_dim()
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.inner.srid(srid)
+    }
+    fn envelope(&mut self, envelope: &[f64], dims: CoordDimensions, idx: usize) -> Result<()> {
+        self.inner.envelope(envelope, dims, idx)
+    }
+    fn size_hint_total_coords(&mut self, count: usize) -> Result<()> {
+        self.inner.size_hint_total_coords(count)
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.inner.xy(x, y, idx)
+    }
+    fn coords(&mut self, coords: &[f64], dims: CoordDimensions, idx: usize) -> Result<()> {
+        self.inner.coords(coords, dims, idx)
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.inner.coordinate(x, y, z, m, t, tm, idx)
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.inner.empty_point(idx)
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.inner.point_begin(idx)
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.point_end(idx)
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.multipoint_begin(size, idx)
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.multipoint_end(idx)
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.inner.linestring_begin(tagged, size, idx)
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.inner.linestring_end(tagged, idx)
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.multilinestring_begin(size, idx)
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.multilinestring_end(idx)
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.inner.polygon_begin(tagged, size, idx)
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.inner.polygon_end(tagged, idx)
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.multipolygon_begin(size, idx)
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.multipolygon_end(idx)
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.geometrycollection_begin(size, idx)
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.geometrycollection_end(idx)
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.circularstring_begin(size, idx)
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.circularstring_end(idx)
+    }
+    fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.compoundcurve_begin(size, idx)
+    }
+    fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.compoundcurve_end(idx)
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.curvepolygon_begin(size, idx)
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.curvepolygon_end(idx)
+    }
+    fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.multicurve_begin(size, idx)
+    }
+    fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.multicurve_end(idx)
+    }
+    fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.multisurface_begin(size, idx)
+    }
+    fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.multisurface_end(idx)
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.inner.triangle_begin(tagged, size, idx)
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.inner.triangle_end(tagged, idx)
+    }
+    fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.polyhedralsurface_begin(size, idx)
+    }
+    fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.polyhedralsurface_end(idx)
+    }
+    fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.tin_begin(size, idx)
+    }
+    fn tin_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.tin_end(idx)
+    }
+}
+
+impl<P: PropertyProcessor, O: ProgressObserver> PropertyProcessor for ProgressProcessor<P, O> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue<'_>) -> Result<bool> {
+        self.inner.property(idx, name, value)
+    }
+    fn column_property(
+        &mut self,
+        idx: usize,
+        column: usize,
+        name: &str,
+        value: &ColumnValue<'_>,
+    ) -> Result<bool> {
+        self.inner.column_property(idx, column, name, value)
+    }
+}
+
+impl<P: FeatureProcessor, O: ProgressObserver> FeatureProcessor for ProgressProcessor<P, O> {
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.inner.dataset_begin(name)
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        self.inner.dataset_end()?;
+        self.notify();
+        Ok(())
+    }
+    fn crs(&mut self, crs: &Crs) -> Result<()> {
+        self.inner.crs(crs)
+    }
+    fn schema_begin(&mut self, columns: &[ColumnDef]) -> Result<()> {
+        self.inner.schema_begin(columns)
+    }
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        self.inner.feature_begin(idx)
+    }
+    fn feature_id(&mut self, id: &FeatureId) -> Result<()> {
+        self.inner.feature_id(id)
+    }
+    fn feature_end(&mut self, idx: u64) -> Result<()> {
+        self.inner.feature_end(idx)?;
+        self.progress.features += 1;
+        if self.progress.features % self.interval == 0 {
+            self.notify();
+        }
+        Ok(())
+    }
+    fn properties_begin(&mut self) -> Result<()> {
+        self.inner.properties_begin()
+    }
+    fn properties_end(&mut self) -> Result<()> {
+        self.inner.properties_end()
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        self.inner.geometry_begin()
+    }
+    fn geometry_end(&mut self) -> Result<()> {
+        self.inner.geometry_end()
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::GeoJsonReader;
+    use crate::{GeozeroDatasource, ProcessorSink};
+
+    #[test]
+    fn progress_notifications() -> Result<()> {
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [1, 2]}},
+            {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [3, 4]}},
+            {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [5, 6]}}
+        ]}"#;
+        let reader = ProgressReader::new(geojson.as_bytes());
+        let bytes_read = reader.counter();
+        let mut notifications = Vec::new();
+        let mut processor = ProgressProcessor::new(ProcessorSink, |progress: &Progress| {
+            notifications.push(*progress)
+        });
+        processor.set_interval(2);
+        processor.set_bytes_read(bytes_read, Some(geojson.len() as u64));
+        GeoJsonReader(reader).process(&mut processor)?;
+        let progress = processor.progress();
+        drop(processor);
+
+        assert_eq!(notifications.len(), 2);
+        assert_eq!(notifications[0].features, 2);
+        assert_eq!(notifications[1].features, 3);
+        assert_eq!(progress.bytes_read, Some(geojson.len() as u64));
+        assert_eq!(progress.fraction(), Some(1.0));
+        Ok(())
+    }
+}
@@ -23,6 +23,77 @@ pub enum ColumnValue<'a> {
     /// A datetime stored as an ISO8601-formatted string
     DateTime(&'a str),
     Binary(&'a [u8]),
+    /// A UUID in big-endian byte order
+    Uuid([u8; 16]),
+    /// An arbitrary precision decimal number stored as string
+    Decimal(&'a str),
+    /// An array of values of the same type
+    Array(&'a [ColumnValueOwned]),
+}
+
+/// Feature property type.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ColumnType {
+    Byte,
+    UByte,
+    Bool,
+    Short,
+    UShort,
+    Int,
+    UInt,
+    Long,
+    ULong,
+    Float,
+    Double,
+    String,
+    Json,
+    DateTime,
+    Binary,
+    Uuid,
+    Decimal,
+    Array,
+}
+
+/// Column declaration of a dataset schema.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ColumnDef {
+    pub name: String,
+    pub column_type: ColumnType,
+}
+
+impl ColumnDef {
+    pub fn new(name: &str, column_type: ColumnType) -> Self {
+        ColumnDef {
+            name: name.to_string(),
+            column_type,
+        }
+    }
+}
+
+impl ColumnValue<'_> {
+    /// Type of property value
+    pub fn column_type(&self) -> ColumnType {
+        match self {
+            ColumnValue::Byte(_) => ColumnType::Byte,
+            ColumnValue::UByte(_) => ColumnType::UByte,
+            ColumnValue::Bool(_) => ColumnType::Bool,
+            ColumnValue::Short(_) => ColumnType::Short,
+            ColumnValue::UShort(_) => ColumnType::UShort,
+            ColumnValue::Int(_) => ColumnType::Int,
+            ColumnValue::UInt(_) => ColumnType::UInt,
+            ColumnValue::Long(_) => ColumnType::Long,
+            ColumnValue::ULong(_) => ColumnType::ULong,
+            ColumnValue::Float(_) => ColumnType::Float,
+            ColumnValue::Double(_) => ColumnType::Double,
+            ColumnValue::String(_) => ColumnType::String,
+            ColumnValue::Json(_) => ColumnType::Json,
+            ColumnValue::DateTime(_) => ColumnType::DateTime,
+            ColumnValue::Binary(_) => ColumnType::Binary,
+            ColumnValue::Uuid(_) => ColumnType::Uuid,
+            ColumnValue::Decimal(_) => ColumnType::Decimal,
+            ColumnValue::Array(_) => ColumnType::Array,
+        }
+    }
 }
 
 /// Feature property processing trait.
@@ -60,6 +131,22 @@ pub trait PropertyProcessor {
     fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
         Ok(true)
     }
+    /// Process property value of a column declared with `schema_begin`. Abort processing, if return value is true.
+    ///
+    /// - `column`: the index of the column in the declared schema.
+    ///
+    /// Readers with a schema call this method instead of [`property`](Self::property), so
+    /// processors can look up key data prepared in `schema_begin` by column index instead of
+    /// handling `name` for every feature. The default implementation calls `property`.
+    fn column_property(
+        &mut self,
+        idx: usize,
+        column: usize,
+        name: &str,
+        value: &ColumnValue,
+    ) -> Result<bool> {
+        self.property(idx, name, value)
+    }
 }
 
 impl fmt::Display for ColumnValue<'_> {
@@ -76,14 +163,181 @@ impl fmt::Display for ColumnValue<'_> {
             ColumnValue::ULong(v) => write!(f, "{v}"),
             ColumnValue::Float(v) => write!(f, "{v}"),
             ColumnValue::Double(v) => write!(f, "{v}"),
-            ColumnValue::String(v) | ColumnValue::Json(v) | ColumnValue::DateTime(v) => {
-                write!(f, "{v}")
-            }
+            ColumnValue::String(v)
+            | ColumnValue::Json(v)
+            | ColumnValue::DateTime(v)
+            | ColumnValue::Decimal(v) => write!(f, "{v}"),
             ColumnValue::Binary(_v) => write!(f, "[BINARY]"),
+            ColumnValue::Uuid(v) => write!(f, "{}", format_uuid(v)),
+            ColumnValue::Array(v) => write!(f, "{}", array_json(v)),
         }
     }
 }
 
+/// Owned feature property value.
+///
+/// Like [ColumnValue], but without lifetime bound to the underlying data.
+#[derive(Clone, PartialEq, Debug)]
+pub enum ColumnValueOwned {
+    Byte(i8),
+    UByte(u8),
+    Bool(bool),
+    Short(i16),
+    UShort(u16),
+    Int(i32),
+    UInt(u32),
+    Long(i64),
+    ULong(u64),
+    Float(f32),
+    Double(f64),
+    String(String),
+    /// A JSON-formatted string
+    Json(String),
+    /// A datetime stored as an ISO8601-formatted string
+    DateTime(String),
+    Binary(Vec<u8>),
+    /// A UUID in big-endian byte order
+    Uuid([u8; 16]),
+    /// An arbitrary precision decimal number stored as string
+    Decimal(String),
+    /// An array of values of the same type
+    Array(Vec<ColumnValueOwned>),
+}
+
+impl ColumnValueOwned {
+    /// Borrowed property value
+    pub fn as_column_value(&self) -> ColumnValue<'_> {
+        match self {
+            ColumnValueOwned::Byte(v) => ColumnValue::Byte(*v),
+            ColumnValueOwned::UByte(v) => ColumnValue::UByte(*v),
+            ColumnValueOwned::Bool(v) => ColumnValue::Bool(*v),
+            ColumnValueOwned::Short(v) => ColumnValue::Short(*v),
+            ColumnValueOwned::UShort(v) => ColumnValue::UShort(*v),
+            ColumnValueOwned::Int(v) => ColumnValue::Int(*v),
+            ColumnValueOwned::UInt(v) => ColumnValue::UInt(*v),
+            ColumnValueOwned::Long(v) => ColumnValue::Long(*v),
+            ColumnValueOwned::ULong(v) => ColumnValue::ULong(*v),
+            ColumnValueOwned::Float(v) => ColumnValue::Float(*v),
+            ColumnValueOwned::Double(v) => ColumnValue::Double(*v),
+            ColumnValueOwned::String(v) => ColumnValue::String(v),
+            ColumnValueOwned::Json(v) => ColumnValue::Json(v),
+            ColumnValueOwned::DateTime(v) => ColumnValue::DateTime(v),
+            ColumnValueOwned::Binary(v) => ColumnValue::Binary(v),
+            ColumnValueOwned::Uuid(v) => ColumnValue::Uuid(*v),
+            ColumnValueOwned::Decimal(v) => ColumnValue::Decimal(v),
+            ColumnValueOwned::Array(v) => ColumnValue::Array(v),
+        }
+    }
+
+    /// Property value as JSON value.
+    ///
+    /// `Json` values are parsed, binary values are converted to an array of bytes.
+    /// `Decimal` values are numbers if they are represented exactly, otherwise strings.
+    pub fn to_json(&self) -> serde_json::Value {
+        use serde_json::Value;
+        match self {
+            ColumnValueOwned::Byte(v) => Value::from(*v),
+            ColumnValueOwned::UByte(v) => Value::from(*v),
+            ColumnValueOwned::Bool(v) => Value::from(*v),
+            ColumnValueOwned::Short(v) => Value::from(*v),
+            ColumnValueOwned::UShort(v) => Value::from(*v),
+            ColumnValueOwned::Int(v) => Value::from(*v),
+            ColumnValueOwned::UInt(v) => Value::from(*v),
+            ColumnValueOwned::Long(v) => Value::from(*v),
+            ColumnValueOwned::ULong(v) => Value::from(*v),
+            ColumnValueOwned::Float(v) => Value::from(*v),
+            ColumnValueOwned::Double(v) => Value::from(*v),
+            ColumnValueOwned::String(v) | ColumnValueOwned::DateTime(v) => Value::from(v.as_str()),
+            ColumnValueOwned::Json(v) => {
+                serde_json::from_str(v).unwrap_or_else(|_| Value::from(v.as_str()))
+            }
+            ColumnValueOwned::Binary(v) => Value::from(v.as_slice()),
+            ColumnValueOwned::Uuid(v) => Value::from(format_uuid(v)),
+            ColumnValueOwned::Decimal(v) => match serde_json::from_str::<Value>(v) {
+                Ok(Value::Number(n)) if n.to_string() == *v => Value::Number(n),
+                _ => Value::from(v.as_str()),
+            },
+            ColumnValueOwned::Array(v) => array_json(v),
+        }
+    }
+}
+
+impl From<&ColumnValue<'_>> for ColumnValueOwned {
+    fn from(value: &ColumnValue) -> Self {
+        match value {
+            ColumnValue::Byte(v) => ColumnValueOwned::Byte(*v),
+            ColumnValue::UByte(v) => ColumnValueOwned::UByte(*v),
+            ColumnValue::Bool(v) => ColumnValueOwned::Bool(*v),
+            ColumnValue::Short(v) => ColumnValueOwned::Short(*v),
+            ColumnValue::UShort(v) => ColumnValueOwned::UShort(*v),
+            ColumnValue::Int(v) => ColumnValueOwned::Int(*v),
+            ColumnValue::UInt(v) => ColumnValueOwned::UInt(*v),
+            ColumnValue::Long(v) => ColumnValueOwned::Long(*v),
+            ColumnValue::ULong(v) => ColumnValueOwned::ULong(*v),
+            ColumnValue::Float(v) => ColumnValueOwned::Float(*v),
+            ColumnValue::Double(v) => ColumnValueOwned::Double(*v),
+            ColumnValue::String(v) => ColumnValueOwned::String((*v).to_string()),
+            ColumnValue::Json(v) => ColumnValueOwned::Json((*v).to_string()),
+            ColumnValue::DateTime(v) => ColumnValueOwned::DateTime((*v).to_string()),
+            ColumnValue::Binary(v) => ColumnValueOwned::Binary(v.to_vec()),
+            ColumnValue::Uuid(v) => ColumnValueOwned::Uuid(*v),
+            ColumnValue::Decimal(v) => ColumnValueOwned::Decimal((*v).to_string()),
+            ColumnValue::Array(v) => ColumnValueOwned::Array(v.to_vec()),
+        }
+    }
+}
+
+/// UUID in hyphenated lowercase format
+pub(crate) fn format_uuid(v: &[u8; 16]) -> String {
+    let hex: String = v.iter().map(|b| format!("{b:02x}")).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+/// Check if `v` is a number according to the JSON grammar
+pub(crate) fn is_json_number(v: &str) -> bool {
+    let digits = |s: &str| s.len() - s.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let mut rest = v.strip_prefix('-').unwrap_or(v);
+    let int = digits(rest);
+    if int == 0 || (int > 1 && rest.starts_with('0')) {
+        return false;
+    }
+    rest = &rest[int..];
+    if let Some(frac) = rest.strip_prefix('.') {
+        let n = digits(frac);
+        if n == 0 {
+            return false;
+        }
+        rest = &frac[n..];
+    }
+    if let Some(exp) = rest.strip_prefix(['e', 'E']) {
+        let exp = exp.strip_prefix(['+', '-']).unwrap_or(exp);
+        let n = digits(exp);
+        if n == 0 {
+            return false;
+        }
+        rest = &exp[n..];
+    }
+    rest.is_empty()
+}
+
+/// Array values as JSON array
+pub(crate) fn array_json(values: &[ColumnValueOwned]) -> serde_json::Value {
+    serde_json::Value::Array(values.iter().map(ColumnValueOwned::to_json).collect())
+}
+
+impl fmt::Display for ColumnValueOwned {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_column_value().fmt(f)
+    }
+}
+
 #[doc(hidden)]
 pub struct PropertyReader<'a, T: PropertyReadType> {
     pub name: &'a str,
@@ -177,6 +431,23 @@ impl<S: BuildHasher> PropertyProcessor for HashMap<String, String, S> {
     }
 }
 
+impl<S: BuildHasher> PropertyProcessor for HashMap<String, ColumnValueOwned, S> {
+    fn property(&mut self, _idx: usize, colname: &str, colval: &ColumnValue) -> Result<bool> {
+        self.insert(colname.to_string(), colval.into());
+        Ok(false)
+    }
+}
+
+impl PropertyProcessor for serde_json::Map<String, serde_json::Value> {
+    fn property(&mut self, _idx: usize, colname: &str, colval: &ColumnValue) -> Result<bool> {
+        self.insert(
+            colname.to_string(),
+            ColumnValueOwned::from(colval).to_json(),
+        );
+        Ok(false)
+    }
+}
+
 #[test]
 fn convert_column_value() {
     let v = &ColumnValue::Int(42);
@@ -194,3 +465,51 @@ fn convert_column_value() {
         r#"expected a `ColumnValue::Int` value but found `String("Yes")`"#
     );
 }
+
+#[test]
+fn owned_column_value() {
+    let v = ColumnValueOwned::from(&ColumnValue::Json(r#"{"a": [1, 2]}"#));
+    assert_eq!(v.as_column_value(), ColumnValue::Json(r#"{"a": [1, 2]}"#));
+    assert_eq!(v.to_json(), serde_json::json!({"a": [1, 2]}));
+    assert_eq!(v.to_string(), r#"{"a": [1, 2]}"#);
+
+    let v = ColumnValueOwned::from(&ColumnValue::ULong(42));
+    assert_eq!(v, ColumnValueOwned::ULong(42));
+    assert_eq!(v.to_json(), serde_json::json!(42));
+}
+
+#[test]
+fn uuid_decimal_array_values() {
+    let uuid = [
+        0x55, 0x0e, 0x84, 0x00, 0xe2, 0x9b, 0x41, 0xd4, 0xa7, 0x16, 0x44, 0x66, 0x55, 0x44, 0x00,
+        0x00,
+    ];
+    let v = ColumnValue::Uuid(uuid);
+    assert_eq!(v.to_string(), "550e8400-e29b-41d4-a716-446655440000");
+    assert_eq!(v.column_type(), ColumnType::Uuid);
+
+    let v = ColumnValueOwned::from(&ColumnValue::Decimal("12345678901234567890.123"));
+    assert_eq!(v.to_string(), "12345678901234567890.123");
+    // Not representable as f64
+    assert_eq!(v.to_json(), serde_json::json!("12345678901234567890.123"));
+    let v = ColumnValueOwned::Decimal("-2.5".to_string());
+    assert_eq!(v.to_json(), serde_json::json!(-2.5));
+
+    for v in ["0", "-1", "12.50", "1e10", "-0.5E-3"] {
+        assert!(is_json_number(v), "{v}");
+    }
+    for v in [
+        "", "-", "NaN", "Infinity", "01", "1.", ".5", "1e", "+1", "1 ",
+    ] {
+        assert!(!is_json_number(v), "{v}");
+    }
+
+    let values = [
+        ColumnValueOwned::String("a".to_string()),
+        ColumnValueOwned::String("b".to_string()),
+    ];
+    let v = ColumnValueOwned::from(&ColumnValue::Array(&values));
+    assert_eq!(v.as_column_value(), ColumnValue::Array(&values));
+    assert_eq!(v.to_json(), serde_json::json!(["a", "b"]));
+    assert_eq!(v.to_string(), r#"["a","b"]"#);
+}
@@ -0,0 +1,192 @@
+//! Byte range access to remote objects.
+//!
+//! Readers of formats supporting partial reads, like GeoParquet, request only the
+//! required parts of a file with a [`RangeClient`].
+use crate::error::{GeozeroError, Result};
+use async_trait::async_trait;
+use bytes::Bytes;
+use std::ops::Range;
+
+/// Client for reading byte ranges of a remote object.
+#[async_trait]
+pub trait RangeClient: Send + Sync {
+    /// Read bytes of `range`.
+    async fn get_range(&self, range: Range<u64>) -> Result<Bytes>;
+    /// Object size in bytes.
+    async fn size(&self) -> Result<u64>;
+    /// Read the whole object.
+    async fn get_all(&self) -> Result<Bytes> {
+        let size = self.size().await?;
+        self.get_range(0..size).await
+    }
+}
+
+#[async_trait]
+impl<C: RangeClient + ?Sized> RangeClient for Box<C> {
+    async fn get_range(&self, range: Range<u64>) -> Result<Bytes> {
+        (**self).get_range(range).await
+    }
+    async fn size(&self) -> Result<u64> {
+        (**self).size().await
+    }
+}
+
+/// [`RangeClient`] using HTTP range requests.
+#[cfg(feature = "with-reqwest")]
+pub struct ReqwestRangeClient {
+    client: reqwest::Client,
+    url: String,
+}
+
+#[cfg(feature = "with-reqwest")]
+impl ReqwestRangeClient {
+    pub fn new(url: &str) -> Self {
+        Self::with_client(reqwest::Client::new(), url)
+    }
+    /// Create range client using a configured `reqwest::Client`.
+    pub fn with_client(client: reqwest::Client, url: &str) -> Self {
+        ReqwestRangeClient {
+            client,
+            url: url.to_string(),
+        }
+    }
+}
+
+#[cfg(feature = "with-reqwest")]
+fn reqwest_err(err: reqwest::Error) -> GeozeroError {
+    GeozeroError::HttpError(err.to_string())
+}
+
+#[cfg(feature = "with-reqwest")]
+#[async_trait]
+impl RangeClient for ReqwestRangeClient {
+    async fn get_range(&self, range: Range<u64>) -> Result<Bytes> {
+        if range.is_empty() {
+            return Ok(Bytes::new());
+        }
+        let response = self
+            .client
+            .get(&self.url)
+            .header(
+                reqwest::header::RANGE,
+                format!("bytes={}-{}", range.start, range.end - 1),
+            )
+            .send()
+            .await
+            .map_err(reqwest_err)?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(GeozeroError::HttpStatus(status.as_u16()));
+        }
+        let bytes = response.bytes().await.map_err(reqwest_err)?;
+        if status == reqwest::StatusCode::PARTIAL_CONTENT {
+            Ok(bytes)
+        } else {
+            // Server ignored the range header and sent the whole object
+            let end = (range.end as usize).min(bytes.len());
+            let start = (range.start as usize).min(end);
+            Ok(bytes.slice(start..end))
+        }
+    }
+    async fn size(&self) -> Result<u64> {
+        let response = self
+            .client
+            .head(&self.url)
+            .send()
+            .await
+            .map_err(reqwest_err)?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(GeozeroError::HttpStatus(status.as_u16()));
+        }
+        response
+            .headers()
+            .get(reqwest::header::CONTENT_LENGTH)
+            .and_then(|len| len.to_str().ok()?.parse().ok())
+            .ok_or_else(|| GeozeroError::HttpError("Missing Content-Length".to_string()))
+    }
+    async fn get_all(&self) -> Result<Bytes> {
+        let response = self
+            .client
+            .get(&self.url)
+            .send()
+            .await
+            .map_err(reqwest_err)?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(GeozeroError::HttpStatus(status.as_u16()));
+        }
+        response.bytes().await.map_err(reqwest_err)
+    }
+}
+
+/// [`RangeClient`] reading from an `object_store::ObjectStore`.
+#[cfg(feature = "with-object-store")]
+pub struct ObjectStoreRangeClient {
+    store: std::sync::Arc<dyn object_store::ObjectStore>,
+    path: object_store::path::Path,
+}
+
+#[cfg(feature = "with-object-store")]
+impl ObjectStoreRangeClient {
+    pub fn new(
+        store: std::sync::Arc<dyn object_store::ObjectStore>,
+        path: object_store::path::Path,
+    ) -> Self {
+        ObjectStoreRangeClient { store, path }
+    }
+}
+
+#[cfg(feature = "with-object-store")]
+fn object_store_err(err: object_store::Error) -> GeozeroError {
+    GeozeroError::HttpError(err.to_string())
+}
+
+#[cfg(feature = "with-object-store")]
+#[async_trait]
+impl RangeClient for ObjectStoreRangeClient {
+    async fn get_range(&self, range: Range<u64>) -> Result<Bytes> {
+        self.store
+            .get_range(&self.path, range.start as usize..range.end as usize)
+            .await
+            .map_err(object_store_err)
+    }
+    async fn size(&self) -> Result<u64> {
+        let meta = self
+            .store
+            .head(&self.path)
+            .await
+            .map_err(object_store_err)?;
+        Ok(meta.size as u64)
+    }
+    async fn get_all(&self) -> Result<Bytes> {
+        let result = self.store.get(&self.path).await.map_err(object_store_err)?;
+        result.bytes().await.map_err(object_store_err)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::*;
+
+    /// In-memory object
+    pub(crate) struct MemoryRangeClient(pub Bytes);
+
+    #[async_trait]
+    impl RangeClient for MemoryRangeClient {
+        async fn get_range(&self, range: Range<u64>) -> Result<Bytes> {
+            Ok(self.0.slice(range.start as usize..range.end as usize))
+        }
+        async fn size(&self) -> Result<u64> {
+            Ok(self.0.len() as u64)
+        }
+    }
+
+    #[tokio::test]
+    async fn get_all() -> Result<()> {
+        let client: Box<dyn RangeClient> = Box::new(MemoryRangeClient(Bytes::from("geozero")));
+        assert_eq!(client.get_range(3..7).await?, Bytes::from("zero"));
+        assert_eq!(client.get_all().await?, Bytes::from("geozero"));
+        Ok(())
+    }
+}
@@ -0,0 +1,299 @@
+//! Renaming of property keys.
+use crate::error::Result;
+use crate::wrap::{delegate_coordinates, delegate_geometry_types};
+use crate::{
+    ColumnDef, ColumnValue, CoordDimensions, Crs, FeatureId, FeatureProcessor, GeomProcessor,
+    PropertyProcessor,
+};
+use std::collections::{HashMap, HashSet};
+
+/// Casing policy for property keys of [RenameProperties].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PropertyCase {
+    /// Keep keys unchanged (default)
+    #[default]
+    Keep,
+    /// Lowercase keys, e.g. `RoadName` -> `roadname`
+    Lowercase,
+    /// Lowercase words separated by underscores, e.g. `RoadName` -> `road_name`
+    SnakeCase,
+}
+
+/// Wraps another [FeatureProcessor], renaming property keys and schema columns.
+///
+/// Keys found in the name map are replaced by their mapped name, all other keys are
+/// converted with the casing policy. With a maximal length, keys are truncated and
+/// made unique by appending a numeric suffix, e.g. for the 10 character limit of
+/// shapefile DBF fields.
+///
+/// # Usage example
+///
+/// ```
+/// # #[cfg(feature = "with-geojson")]
+/// # {
+/// use geozero::geojson::{GeoJson, GeoJsonWriter};
+/// use geozero::{GeozeroDatasource, PropertyCase, RenameProperties};
+///
+/// let mut geojson = GeoJson(r#"{"type": "Feature", "properties": {"RoadName": "Main St", "ID": 1}, "geometry": null}"#);
+/// let mut out = Vec::new();
+/// let mut processor = RenameProperties::new(GeoJsonWriter::new(&mut out));
+/// processor.set_name("ID", "road_id");
+/// processor.set_case(PropertyCase::SnakeCase);
+/// geojson.process(&mut processor).unwrap();
+/// # }
+/// ```
+pub struct RenameProperties<P> {
+    inner: P,
+    renamer: Renamer,
+}
+
+/// Renamed property keys
+#[derive(Default)]
+pub(crate) struct Renamer {
+    names: HashMap<String, String>,
+    case: PropertyCase,
+    max_length: Option<usize>,
+    /// Renamed keys by original key
+    renamed: HashMap<String, String>,
+    /// Assigned keys
+    used: HashSet<String>,
+}
+
+impl<P> RenameProperties<P> {
+    pub fn new(inner: P) -> Self {
+        RenameProperties {
+            inner,
+            renamer: Renamer::default(),
+        }
+    }
+    /// Rename property `from` to `to`
+    pub fn set_name(&mut self, from: &str, to: &str) {
+        self.renamer.names.insert(from.to_string(), to.to_string());
+        self.renamer.reset();
+    }
+    /// Rename properties by a map of original to new names
+    pub fn set_names(&mut self, names: HashMap<String, String>) {
+        self.renamer.names = names;
+        self.renamer.reset();
+    }
+    /// Casing policy for keys not found in the name map (default: [PropertyCase::Keep])
+    pub fn set_case(&mut self, case: PropertyCase) {
+        self.renamer.case = case;
+        self.renamer.reset();
+    }
+    /// Maximal key length in bytes (default: unlimited)
+    pub fn set_max_length(&mut self, max_length: Option<usize>) {
+        self.renamer.max_length = max_length;
+        self.renamer.reset();
+    }
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+}
+
+impl Renamer {
+    /// Renamer truncating keys to `max_length` bytes
+    #[cfg(feature = "with-shp")]
+    pub(crate) fn with_max_length(max_length: usize) -> Self {
+        Renamer {
+            max_length: Some(max_length),
+            ..Default::default()
+        }
+    }
+    fn reset(&mut self) {
+        self.renamed.clear();
+        self.used.clear();
+    }
+    pub(crate) fn rename(&mut self, name: &str) -> &str {
+        if !self.renamed.contains_key(name) {
+            let key = match self.names.get(name) {
+                Some(key) => key.clone(),
+                None => match self.case {
+                    PropertyCase::Keep => name.to_string(),
+                    PropertyCase::Lowercase => name.to_lowercase(),
+                    PropertyCase::SnakeCase => snake_case(name),
+                },
+            };
+            let key = self.unique(key);
+            self.used.insert(key.clone());
+            self.renamed.insert(name.to_string(), key);
+        }
+        &self.renamed[name]
+    }
+    /// Truncated key, with numeric suffix if already assigned
+    fn unique(&self, key: String) -> String {
+        let max_length = self.max_length.unwrap_or(usize::MAX);
+        let key = truncate(&key, max_length).to_string();
+        if !self.used.contains(&key) {
+            return key;
+        }
+        let mut n = 1;
+        loop {
+            let suffix = format!("_{n}");
+            let len = max_length.saturating_sub(suffix.len());
+            let candidate = format!("{}{suffix}", truncate(&key, len));
+            if !self.used.contains(&candidate) {
+                return candidate;
+            }
+            n += 1;
+        }
+    }
+}
+
+/// Longest prefix with at most `len` bytes
+fn truncate(s: &str, len: usize) -> &str {
+    if s.len() <= len {
+        return s;
+    }
+    let end = (0..=len)
+        .rev()
+        .find(|i| s.is_char_boundary(*i))
+        .unwrap_or(0);
+    &s[..end]
+}
+
+/// Convert `PopulationTotal`, `HTTPServer` or `road-name` to snake_case
+fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::with_capacity(name.len() + 4);
+    let mut separator = false;
+    for (i, c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            separator = true;
+            continue;
+        }
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|c| c.is_lowercase());
+            if prev.is_lowercase() || prev.is_numeric() || (prev.is_uppercase() && next_lower) {
+                separator = true;
+            }
+        }
+        if separator && !out.is_empty() {
+            out.push('_');
+        }
+        separator = false;
+        out.extend(c.to_lowercase());
+    }
+    out
+}
+
+impl<P: GeomProcessor> GeomProcessor for RenameProperties<P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.inner.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.inner.multi_dim()
+    }
+    delegate_coordinates!(inner);
+    delegate_geometry_types!();
+}
+
+impl<P: PropertyProcessor> PropertyProcessor for RenameProperties<P> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue<'_>) -> Result<bool> {
+        let name = self.renamer.rename(name);
+        self.inner.property(idx, name, value)
+    }
+    fn column_property(
+        &mut self,
+        idx: usize,
+        column: usize,
+        name: &str,
+        value: &ColumnValue<'_>,
+    ) -> Result<bool> {
+        let name = self.renamer.rename(name);
+        self.inner.column_property(idx, column, name, value)
+    }
+}
+
+impl<P: FeatureProcessor> FeatureProcessor for RenameProperties<P> {
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.inner.dataset_begin(name)
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        self.inner.dataset_end()
+    }
+    fn crs(&mut self, crs: &Crs) -> Result<()> {
+        self.inner.crs(crs)
+    }
+    fn schema_begin(&mut self, columns: &[ColumnDef]) -> Result<()> {
+        let columns: Vec<ColumnDef> = columns
+            .iter()
+            .map(|column| ColumnDef::new(self.renamer.rename(&column.name), column.column_type))
+            .collect();
+        self.inner.schema_begin(&columns)
+    }
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        self.inner.feature_begin(idx)
+    }
+    fn feature_id(&mut self, id: &FeatureId) -> Result<()> {
+        self.inner.feature_id(id)
+    }
+    fn feature_end(&mut self, idx: u64) -> Result<()> {
+        self.inner.feature_end(idx)
+    }
+    fn properties_begin(&mut self) -> Result<()> {
+        self.inner.properties_begin()
+    }
+    fn properties_end(&mut self) -> Result<()> {
+        self.inner.properties_end()
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        self.inner.geometry_begin()
+    }
+    fn geometry_end(&mut self) -> Result<()> {
+        self.inner.geometry_end()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn casing() {
+        assert_eq!(snake_case("PopulationTotal"), "population_total");
+        assert_eq!(snake_case("HTTPServer2"), "http_server2");
+        assert_eq!(snake_case("road-name"), "road_name");
+        assert_eq!(snake_case(" Already_snake "), "already_snake");
+        assert_eq!(snake_case("ÄußereFläche"), "äußere_fläche");
+        assert_eq!(truncate("Fläche", 3), "Fl");
+    }
+
+    #[test]
+    fn unique_truncated_names() {
+        let mut renamer = Renamer {
+            case: PropertyCase::SnakeCase,
+            max_length: Some(10),
+            ..Default::default()
+        };
+        renamer.names.insert("ID".to_string(), "fid".to_string());
+        assert_eq!(renamer.rename("ID"), "fid");
+        assert_eq!(renamer.rename("PopulationTotal"), "population");
+        assert_eq!(renamer.rename("PopulationTotalMale"), "populati_1");
+        assert_eq!(renamer.rename("PopulationTotal"), "population");
+        assert_eq!(renamer.rename("Name"), "name");
+    }
+
+    #[test]
+    #[cfg(feature = "with-geojson")]
+    fn rename_properties() -> Result<()> {
+        use crate::geojson::{GeoJson, GeoJsonWriter};
+        use crate::GeozeroDatasource;
+
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"RoadName": "Main St", "ID": 1}, "geometry": null}
+        ]}"#;
+        let mut out = Vec::new();
+        let mut processor = RenameProperties::new(GeoJsonWriter::new(&mut out));
+        processor.set_names(HashMap::from([("ID".to_string(), "road_id".to_string())]));
+        processor.set_case(PropertyCase::Lowercase);
+        GeoJson(geojson).process(&mut processor)?;
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(
+            json["features"][0]["properties"],
+            serde_json::json!({"roadname": "Main St", "road_id": 1})
+        );
+        Ok(())
+    }
+}
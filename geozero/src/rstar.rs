@@ -0,0 +1,220 @@
+//! Spatial indexing of features with rstar.
+use crate::bounds::{Bounds, GeomStats};
+use crate::error::Result;
+use crate::geo_types::GeoWriter;
+use crate::wrap::{delegate_coordinates, delegate_geometry_types};
+use crate::{
+    CoordDimensions, FeatureProcessor, GeomProcessor, GeozeroDatasource, LinearizeCurves,
+    PropertyProcessor,
+};
+use ::rstar::{RTree, RTreeObject, AABB};
+use geo_types::Geometry;
+
+/// Feature entry of an [RTree] built by [RTreeBuilder].
+#[derive(Clone, PartialEq, Debug)]
+pub struct RTreeFeature {
+    /// Feature index within the datasource
+    pub idx: u64,
+    /// 2D bounding box of the feature geometry
+    pub bounds: Bounds,
+    /// Feature geometry, `None` if not kept
+    pub geometry: Option<Geometry<f64>>,
+}
+
+impl RTreeObject for RTreeFeature {
+    type Envelope = AABB<[f64; 2]>;
+
+    fn envelope(&self) -> Self::Envelope {
+        AABB::from_corners(
+            [self.bounds.min_x, self.bounds.min_y],
+            [self.bounds.max_x, self.bounds.max_y],
+        )
+    }
+}
+
+/// Bulk loader of features into an rstar [RTree].
+///
+/// Each feature with a non-empty geometry is inserted with its feature index, bounding box and
+/// geo-types geometry. Curves are linearized like in [ToGeo](crate::ToGeo).
+///
+/// # Usage example
+///
+/// ```
+/// # #[cfg(feature = "with-geojson")]
+/// # {
+/// use geozero::geojson::GeoJson;
+/// use geozero::rstar::RTreeBuilder;
+/// use geozero::GeozeroDatasource;
+/// use rstar::AABB;
+///
+/// let mut geojson = GeoJson(r#"{"type": "FeatureCollection", "features": [{"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [1, 1]}}]}"#);
+/// let mut builder = RTreeBuilder::new();
+/// builder.set_keep_geometries(false);
+/// geojson.process(&mut builder).unwrap();
+/// let tree = builder.finish();
+/// let found = tree.locate_in_envelope_intersecting(&AABB::from_corners([0.0, 0.0], [2.0, 2.0]));
+/// assert_eq!(found.map(|feature| feature.idx).collect::<Vec<_>>(), vec![0]);
+/// # }
+/// ```
+pub struct RTreeBuilder {
+    features: Vec<RTreeFeature>,
+    keep_geometries: bool,
+    /// Index of the current feature
+    idx: u64,
+    stats: GeomStats,
+    geometry: Option<LinearizeCurves<GeoWriter>>,
+}
+
+impl Default for RTreeBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RTreeBuilder {
+    pub fn new() -> Self {
+        RTreeBuilder {
+            features: Vec::new(),
+            keep_geometries: true,
+            idx: 0,
+            stats: GeomStats::new(),
+            geometry: None,
+        }
+    }
+    /// Store geo-types geometries in the tree entries (default: `true`).
+    ///
+    /// Without geometries, only feature index and bounding box are stored.
+    pub fn set_keep_geometries(&mut self, keep: bool) {
+        self.keep_geometries = keep;
+    }
+    /// Number of collected features
+    pub fn len(&self) -> usize {
+        self.features.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.features.is_empty()
+    }
+    /// Bulk load collected features into an R*-tree
+    pub fn finish(self) -> RTree<RTreeFeature> {
+        RTree::bulk_load(self.features)
+    }
+}
+
+impl FeatureProcessor for RTreeBuilder {
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        self.idx = idx;
+        Ok(())
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        self.stats = GeomStats::new();
+        self.geometry = self
+            .keep_geometries
+            .then(|| GeoWriter::new().linearize_curves());
+        Ok(())
+    }
+    fn geometry_end(&mut self) -> Result<()> {
+        let geometry = self
+            .geometry
+            .take()
+            .and_then(|mut geometry| geometry.inner.take_geometry());
+        if let Some(bounds) = self.stats.bounds() {
+            self.features.push(RTreeFeature {
+                idx: self.idx,
+                bounds,
+                geometry,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl PropertyProcessor for RTreeBuilder {}
+
+// Geometry events are processed by the stats and the optional geometry writer
+impl GeomProcessor for RTreeBuilder {
+    fn dimensions(&self) -> CoordDimensions {
+        CoordDimensions::xy()
+    }
+    delegate_coordinates!(stats, Some(geometry));
+    delegate_geometry_types!(stats, Some(geometry));
+}
+
+/// Spatial indexing with rstar.
+pub trait ToRTree {
+    /// Bulk load features with their geo-types geometry into an R*-tree.
+    fn to_rtree(&mut self) -> Result<RTree<RTreeFeature>>;
+}
+
+impl<D: GeozeroDatasource> ToRTree for D {
+    fn to_rtree(&mut self) -> Result<RTree<RTreeFeature>> {
+        let mut builder = RTreeBuilder::new();
+        self.process(&mut builder)?;
+        Ok(builder.finish())
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::GeoJson;
+    use geo_types::line_string;
+
+    const FEATURES: &str = r#"{"type": "FeatureCollection", "features": [
+        {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [1, 1]}},
+        {"type": "Feature", "properties": {}, "geometry": null},
+        {"type": "Feature", "properties": {}, "geometry": {"type": "LineString", "coordinates": [[5, 5], [6, 8]]}},
+        {"type": "Feature", "properties": {}, "geometry": {"type": "Polygon", "coordinates": [[[0, 0], [3, 0], [3, 3], [0, 0]]]}}
+    ]}"#;
+
+    #[test]
+    fn to_rtree() -> Result<()> {
+        let tree = GeoJson(FEATURES).to_rtree()?;
+        assert_eq!(tree.size(), 3);
+        let mut found = tree
+            .locate_in_envelope_intersecting(&AABB::from_corners([0.5, 0.5], [5.5, 5.5]))
+            .map(|feature| feature.idx)
+            .collect::<Vec<_>>();
+        found.sort();
+        assert_eq!(found, vec![0, 2, 3]);
+
+        let found = tree
+            .locate_in_envelope(&AABB::from_corners([4.0, 4.0], [7.0, 9.0]))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            vec![&RTreeFeature {
+                idx: 2,
+                bounds: Bounds {
+                    min_x: 5.0,
+                    min_y: 5.0,
+                    max_x: 6.0,
+                    max_y: 8.0,
+                },
+                geometry: Some(line_string![(x: 5.0, y: 5.0), (x: 6.0, y: 8.0)].into()),
+            }]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn bounds_only() -> Result<()> {
+        let mut builder = RTreeBuilder::new();
+        builder.set_keep_geometries(false);
+        GeoJson(FEATURES).process(&mut builder)?;
+        assert_eq!(builder.len(), 3);
+        let tree = builder.finish();
+        let found = tree
+            .locate_in_envelope(&AABB::from_point([1.0, 1.0]))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            vec![&RTreeFeature {
+                idx: 0,
+                bounds: Bounds::from_point(1.0, 1.0),
+                geometry: None,
+            }]
+        );
+        Ok(())
+    }
+}
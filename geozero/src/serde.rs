@@ -0,0 +1,310 @@
+//! Typed property deserialization with serde.
+//!
+//! # Usage example:
+//!
+//! ```rust
+//! use geozero::geojson::GeoJson;
+//! use geozero::DeserializeFeatures;
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct City {
+//!     name: String,
+//!     population: u64,
+//! }
+//!
+//! let mut geojson = GeoJson(r#"{"type": "FeatureCollection", "features": [
+//!     {"type": "Feature", "properties": {"name": "Bern", "population": 121631}, "geometry": {"type": "Point", "coordinates": [7.4, 46.9]}}
+//! ]}"#);
+//! let cities = geojson.deserialize_features::<City>().unwrap();
+//! assert_eq!(cities[0].properties.name, "Bern");
+//! assert!(cities[0].geometry.is_some());
+//! ```
+use crate::error::{GeozeroError, Result};
+use crate::geo_types::GeoWriter;
+use crate::property_processor::format_uuid;
+use crate::{
+    ColumnValue, ColumnValueOwned, CoordDimensions, FeatureProcessor, GeomProcessor,
+    GeozeroDatasource, PropertyProcessor,
+};
+use ::serde::de::value::{MapDeserializer, SeqDeserializer};
+use ::serde::de::{self, DeserializeOwned, IntoDeserializer, Visitor};
+use ::serde::forward_to_deserialize_any;
+use std::fmt::Display;
+
+impl de::Error for GeozeroError {
+    fn custom<T: Display>(msg: T) -> Self {
+        GeozeroError::Property(msg.to_string())
+    }
+}
+
+impl<'de> de::Deserializer<'de> for ColumnValueOwned {
+    type Error = GeozeroError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self {
+            ColumnValueOwned::Byte(v) => visitor.visit_i8(v),
+            ColumnValueOwned::UByte(v) => visitor.visit_u8(v),
+            ColumnValueOwned::Bool(v) => visitor.visit_bool(v),
+            ColumnValueOwned::Short(v) => visitor.visit_i16(v),
+            ColumnValueOwned::UShort(v) => visitor.visit_u16(v),
+            ColumnValueOwned::Int(v) => visitor.visit_i32(v),
+            ColumnValueOwned::UInt(v) => visitor.visit_u32(v),
+            ColumnValueOwned::Long(v) => visitor.visit_i64(v),
+            ColumnValueOwned::ULong(v) => visitor.visit_u64(v),
+            ColumnValueOwned::Float(v) => visitor.visit_f32(v),
+            ColumnValueOwned::Double(v) => visitor.visit_f64(v),
+            ColumnValueOwned::String(v) | ColumnValueOwned::DateTime(v) => visitor.visit_string(v),
+            ColumnValueOwned::Json(v) => {
+                let json: serde_json::Value =
+                    serde_json::from_str(&v).map_err(de::Error::custom)?;
+                de::Deserializer::deserialize_any(json, visitor).map_err(de::Error::custom)
+            }
+            ColumnValueOwned::Binary(v) => visitor.visit_byte_buf(v),
+            ColumnValueOwned::Uuid(v) => visitor.visit_string(format_uuid(&v)),
+            ColumnValueOwned::Decimal(v) => visitor.visit_string(v),
+            ColumnValueOwned::Array(v) => {
+                let mut seq = SeqDeserializer::new(v.into_iter());
+                let value = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+                Ok(value)
+            }
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_some(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl IntoDeserializer<'_, GeozeroError> for ColumnValueOwned {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+/// Deserializer for the properties of a feature.
+///
+/// Collects properties as [PropertyProcessor] and deserializes them as map
+/// into any type implementing [serde::Deserialize](::serde::Deserialize).
+/// Missing properties can be deserialized into `Option` fields.
+#[derive(Default, Debug)]
+pub struct FeatureDeserializer {
+    properties: Vec<(String, ColumnValueOwned)>,
+}
+
+impl FeatureDeserializer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Deserialize collected properties
+    pub fn deserialize<T: DeserializeOwned>(self) -> Result<T> {
+        T::deserialize(self)
+    }
+}
+
+impl PropertyProcessor for FeatureDeserializer {
+    fn property(&mut self, _idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        self.properties.push((name.to_string(), value.into()));
+        Ok(false)
+    }
+}
+
+impl<'de> de::Deserializer<'de> for FeatureDeserializer {
+    type Error = GeozeroError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let mut map = MapDeserializer::new(self.properties.into_iter());
+        let value = visitor.visit_map(&mut map)?;
+        map.end()?;
+        Ok(value)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+/// Feature with deserialized properties.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Feature<T> {
+    pub properties: T,
+    pub geometry: Option<geo_types::Geometry<f64>>,
+}
+
+/// Collects deserialized features.
+struct FeatureCollector<T> {
+    features: Vec<Feature<T>>,
+    properties: FeatureDeserializer,
+    geometry: GeoWriter,
+}
+
+impl<T: DeserializeOwned> FeatureProcessor for FeatureCollector<T> {
+    fn feature_begin(&mut self, _idx: u64) -> Result<()> {
+        self.properties = FeatureDeserializer::new();
+        self.geometry = GeoWriter::new();
+        Ok(())
+    }
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        let properties = std::mem::take(&mut self.properties).deserialize()?;
+        self.features.push(Feature {
+            properties,
+            geometry: self.geometry.take_geometry(),
+        });
+        Ok(())
+    }
+}
+
+impl<T> PropertyProcessor for FeatureCollector<T> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        self.properties.property(idx, name, value)
+    }
+}
+
+// Geometry events are forwarded to the GeoWriter
+impl<T> GeomProcessor for FeatureCollector<T> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.geometry.dimensions()
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.geometry.xy(x, y, idx)
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.geometry.coordinate(x, y, z, m, t, tm, idx)
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.geometry.empty_point(idx)
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.geometry.point_begin(idx)
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.geometry.point_end(idx)
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geometry.multipoint_begin(size, idx)
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.geometry.multipoint_end(idx)
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.geometry.linestring_begin(tagged, size, idx)
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.geometry.linestring_end(tagged, idx)
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geometry.multilinestring_begin(size, idx)
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.geometry.multilinestring_end(idx)
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.geometry.polygon_begin(tagged, size, idx)
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.geometry.polygon_end(tagged, idx)
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geometry.multipolygon_begin(size, idx)
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.geometry.multipolygon_end(idx)
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geometry.geometrycollection_begin(size, idx)
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.geometry.geometrycollection_end(idx)
+    }
+}
+
+/// Deserialize features with serde.
+pub trait DeserializeFeatures {
+    /// Deserialize properties of all features into `T`, together with their geo-types geometry.
+    fn deserialize_features<T: DeserializeOwned>(&mut self) -> Result<Vec<Feature<T>>>;
+}
+
+impl<D: GeozeroDatasource> DeserializeFeatures for D {
+    fn deserialize_features<T: DeserializeOwned>(&mut self) -> Result<Vec<Feature<T>>> {
+        let mut collector = FeatureCollector {
+            features: Vec::new(),
+            properties: FeatureDeserializer::new(),
+            geometry: GeoWriter::new(),
+        };
+        self.process(&mut collector)?;
+        Ok(collector.features)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::GeoJson;
+    use ::serde::Deserialize;
+
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct City {
+        name: String,
+        population: u32,
+        area: Option<f64>,
+        tags: Option<Vec<String>>,
+    }
+
+    #[test]
+    fn deserialize_features() -> Result<()> {
+        let mut geojson = GeoJson(
+            r#"{"type": "FeatureCollection", "features": [
+                {"type": "Feature", "properties": {"name": "Bern", "population": 121631, "tags": ["capital"]}, "geometry": {"type": "Point", "coordinates": [7.4, 46.9]}},
+                {"type": "Feature", "properties": {"name": "Zurich", "population": 402762, "area": 87.88, "canton": "ZH"}, "geometry": null}
+            ]}"#,
+        );
+        let cities = geojson.deserialize_features::<City>()?;
+        assert_eq!(cities.len(), 2);
+        assert_eq!(
+            cities[0].properties,
+            City {
+                name: "Bern".to_string(),
+                population: 121631,
+                area: None,
+                tags: Some(vec!["capital".to_string()]),
+            }
+        );
+        assert_eq!(
+            cities[0].geometry,
+            Some(geo_types::Point::new(7.4, 46.9).into())
+        );
+        assert_eq!(cities[1].properties.area, Some(87.88));
+        assert_eq!(cities[1].geometry, None);
+        Ok(())
+    }
+
+    #[test]
+    fn type_mismatch() {
+        let mut geojson = GeoJson(
+            r#"{"type": "Feature", "properties": {"name": "Bern", "population": "many"}, "geometry": null}"#,
+        );
+        assert!(geojson.deserialize_features::<City>().is_err());
+    }
+}
@@ -1,8 +1,8 @@
 use crate::shp::point_z::BBoxZ;
 use crate::shp::Error;
-use byteorder::{BigEndian, LittleEndian, ReadBytesExt};
+use byteorder::{BigEndian, LittleEndian, ReadBytesExt, WriteBytesExt};
 use std::fmt;
-use std::io::Read;
+use std::io::{Read, Write};
 
 pub(crate) const HEADER_SIZE: i32 = 100;
 const FILE_CODE: i32 = 9994;
@@ -71,6 +71,23 @@ impl Header {
 
         Ok(hdr)
     }
+
+    pub(crate) fn write_to<T: Write>(&self, dest: &mut T) -> Result<(), std::io::Error> {
+        dest.write_i32::<BigEndian>(FILE_CODE)?;
+        dest.write_all(&[0; SIZE_OF_SKIP])?;
+        dest.write_i32::<BigEndian>(self.file_length)?;
+        dest.write_i32::<LittleEndian>(self.version)?;
+        dest.write_i32::<LittleEndian>(self.shape_type as i32)?;
+        dest.write_f64::<LittleEndian>(self.bbox.min.x)?;
+        dest.write_f64::<LittleEndian>(self.bbox.min.y)?;
+        dest.write_f64::<LittleEndian>(self.bbox.max.x)?;
+        dest.write_f64::<LittleEndian>(self.bbox.max.y)?;
+        dest.write_f64::<LittleEndian>(self.bbox.min.z)?;
+        dest.write_f64::<LittleEndian>(self.bbox.max.z)?;
+        dest.write_f64::<LittleEndian>(self.bbox.min.m)?;
+        dest.write_f64::<LittleEndian>(self.bbox.max.m)?;
+        Ok(())
+    }
 }
 
 /// The enum for the ShapeType as defined in the
@@ -192,9 +209,27 @@ impl fmt::Display for ShapeType {
 mod tests {
     use super::*;
 
-    use byteorder::WriteBytesExt;
     use std::io::{Seek, SeekFrom};
 
+    #[test]
+    fn write_read_header() {
+        use std::io::Cursor;
+
+        let mut header = Header {
+            shape_type: ShapeType::PolygonZ,
+            file_length: 136,
+            ..Default::default()
+        };
+        header.bbox.min.x = 1.0;
+        header.bbox.max.y = 5.0;
+        let mut src = Cursor::new(vec![]);
+        header.write_to(&mut src).unwrap();
+        assert_eq!(src.get_ref().len(), HEADER_SIZE as usize);
+
+        src.seek(SeekFrom::Start(0)).unwrap();
+        assert!(Header::read_from(&mut src).unwrap() == header);
+    }
+
     #[test]
     fn wrong_file_code() {
         use std::io::Cursor;
@@ -1,14 +1,13 @@
-//! Shapefile reader.
+//! Shapefile reader and writer.
 //!
 //! Features:
 //! - [x] Read support for OGC simple feature types
 //! - [x] Convert to GeoJSON, WKB (PostGIS/GeoPackage), WKT, GEOS, GDAL formats and more
+//! - [x] Write support for OGC simple feature types with [ShpWriter]
 //! - [ ] Support for Multipatch types
-//! - [ ] Read spatial index
+//! - [x] Read spatial index (.shx and .qix) for bounding box queries
 //! - [ ] Read projection files
 //!
-//! For writing Shapefiles either use [shapefile-rs](https://crates.io/crates/shapefile) or the GDAL driver.
-//!
 //! Originally based on shapefile-rs from Thomas Montaigu.
 //!
 //! # Usage example:
@@ -27,13 +26,16 @@
 mod header;
 mod point_z;
 mod property_processor;
+mod qix_reader;
 pub mod reader;
 mod shp_reader;
+mod shp_writer;
 mod shx_reader;
 
 pub use crate::shp::header::ShapeType;
 pub use crate::shp::reader::ShpReader;
 pub use crate::shp::shp_reader::NO_DATA;
+pub use crate::shp::shp_writer::{DbfField, ShpWriter};
 
 /// All Errors that can happen when using this library
 #[derive(thiserror::Error, Debug)]
@@ -68,6 +70,8 @@ pub enum Error {
     MissingDbf,
     #[error("Index file missing")]
     MissingIndexFile,
+    #[error("Invalid spatial index file")]
+    InvalidSpatialIndex,
     #[error("Geozero error")]
     GeozeroError(#[from] crate::error::GeozeroError),
 }
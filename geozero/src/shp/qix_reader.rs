@@ -0,0 +1,139 @@
+use crate::shp::Error;
+use byteorder::{BigEndian, ByteOrder, LittleEndian, ReadBytesExt};
+use std::io::{Cursor, Read, Seek, SeekFrom};
+
+const MAGIC: &[u8; 3] = b"SQT";
+const LSB_ORDER: u8 = 1;
+const MSB_ORDER: u8 = 2;
+
+/// Quadtree spatial index (.qix) as written by MapServer `shptree` and GDAL.
+pub(crate) struct QuadTree {
+    data: Vec<u8>,
+    big_endian: bool,
+}
+
+impl QuadTree {
+    /// Read the content of a .qix file
+    pub(crate) fn read_from<T: Read>(mut source: T) -> Result<QuadTree, Error> {
+        let mut data = Vec::new();
+        source.read_to_end(&mut data)?;
+        if data.len() < 16 || &data[0..3] != MAGIC {
+            return Err(Error::InvalidSpatialIndex);
+        }
+        let big_endian = match data[3] {
+            LSB_ORDER => false,
+            MSB_ORDER => true,
+            _ => return Err(Error::InvalidSpatialIndex),
+        };
+        Ok(QuadTree { data, big_endian })
+    }
+
+    /// Ids of shapes in nodes intersecting the given bounding box
+    ///
+    /// The returned ids are sorted, the shape bounding boxes still have to be checked.
+    pub(crate) fn select_bbox(&self, bbox: &[f64; 4]) -> Result<Vec<usize>, Error> {
+        let mut cursor = Cursor::new(&self.data[..]);
+        // Skip header, number of shapes and max depth
+        cursor.seek(SeekFrom::Start(16))?;
+        let mut ids = Vec::new();
+        if self.big_endian {
+            collect_ids::<BigEndian>(&mut cursor, bbox, &mut ids)?;
+        } else {
+            collect_ids::<LittleEndian>(&mut cursor, bbox, &mut ids)?;
+        }
+        ids.sort_unstable();
+        ids.dedup();
+        Ok(ids)
+    }
+}
+
+fn collect_ids<B: ByteOrder>(
+    cursor: &mut Cursor<&[u8]>,
+    bbox: &[f64; 4],
+    ids: &mut Vec<usize>,
+) -> Result<(), Error> {
+    let offset = cursor.read_i32::<B>()?;
+    let mut node_bbox = [0.0; 4];
+    for v in &mut node_bbox {
+        *v = cursor.read_f64::<B>()?;
+    }
+    let num_shapes = cursor.read_i32::<B>()?;
+    if !intersects(&node_bbox, bbox) {
+        // Skip shape ids, number of subnodes and subnodes
+        cursor.seek(SeekFrom::Current(
+            i64::from(num_shapes) * 4 + 4 + i64::from(offset),
+        ))?;
+        return Ok(());
+    }
+    for _ in 0..num_shapes {
+        ids.push(cursor.read_i32::<B>()? as usize);
+    }
+    let num_subnodes = cursor.read_i32::<B>()?;
+    for _ in 0..num_subnodes {
+        collect_ids::<B>(cursor, bbox, ids)?;
+    }
+    Ok(())
+}
+
+/// Check intersection of two bounding boxes `[minx, miny, maxx, maxy]`
+pub(crate) fn intersects(a: &[f64; 4], b: &[f64; 4]) -> bool {
+    a[0] <= b[2] && a[2] >= b[0] && a[1] <= b[3] && a[3] >= b[1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use byteorder::WriteBytesExt;
+
+    fn write_node(
+        out: &mut Vec<u8>,
+        bbox: [f64; 4],
+        ids: &[i32],
+        subnodes: &[Vec<u8>],
+    ) -> std::io::Result<()> {
+        let subnodes_size: usize = subnodes.iter().map(Vec::len).sum();
+        out.write_i32::<LittleEndian>(subnodes_size as i32)?;
+        for v in bbox {
+            out.write_f64::<LittleEndian>(v)?;
+        }
+        out.write_i32::<LittleEndian>(ids.len() as i32)?;
+        for id in ids {
+            out.write_i32::<LittleEndian>(*id)?;
+        }
+        out.write_i32::<LittleEndian>(subnodes.len() as i32)?;
+        for node in subnodes {
+            out.extend_from_slice(node);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn select_nodes() -> std::io::Result<()> {
+        let mut left = Vec::new();
+        write_node(&mut left, [0.0, 0.0, 5.0, 10.0], &[0, 2], &[])?;
+        let mut right = Vec::new();
+        write_node(&mut right, [5.0, 0.0, 10.0, 10.0], &[1], &[])?;
+        let mut qix = vec![b'S', b'Q', b'T', LSB_ORDER, 1, 0, 0, 0];
+        qix.write_i32::<LittleEndian>(4)?;
+        qix.write_i32::<LittleEndian>(2)?;
+        write_node(&mut qix, [0.0, 0.0, 10.0, 10.0], &[3], &[left, right])?;
+
+        let tree = QuadTree::read_from(qix.as_slice()).unwrap();
+        assert_eq!(
+            tree.select_bbox(&[0.0, 0.0, 10.0, 10.0]).unwrap(),
+            [0, 1, 2, 3]
+        );
+        assert_eq!(tree.select_bbox(&[6.0, 1.0, 7.0, 2.0]).unwrap(), [1, 3]);
+        assert_eq!(tree.select_bbox(&[1.0, 1.0, 2.0, 2.0]).unwrap(), [0, 2, 3]);
+        assert!(tree
+            .select_bbox(&[20.0, 20.0, 30.0, 30.0])
+            .unwrap()
+            .is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_index() {
+        assert!(QuadTree::read_from(&b"XYZ\x01\x01\0\0\0"[..]).is_err());
+    }
+}
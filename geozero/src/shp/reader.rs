@@ -1,10 +1,11 @@
-use crate::shp::shp_reader::{read_shape, RecordHeader};
+use crate::shp::qix_reader::{intersects, QuadTree};
+use crate::shp::shp_reader::{read_shape, read_shape_bbox, RecordHeader};
 use crate::shp::shx_reader::{read_index_file, ShapeIndex};
 use crate::shp::{header, Error};
-use crate::{FeatureProcessor, FeatureProperties, GeomProcessor};
+use crate::{ColumnDef, ColumnType, Crs, FeatureProcessor, FeatureProperties, GeomProcessor};
 pub use dbase::{FieldInfo, FieldType};
 use std::fs::File;
-use std::io::{BufReader, Read, Seek};
+use std::io::{BufReader, Read, Seek, SeekFrom};
 use std::iter::FusedIterator;
 use std::path::Path;
 
@@ -14,12 +15,22 @@ pub struct ShapeIterator<'a, P: GeomProcessor, T: Read> {
     source: T,
     current_pos: usize,
     file_length: usize,
+    /// Selected shapes (record number and offset)
+    selection: Option<std::vec::IntoIter<(usize, ShapeIndex)>>,
 }
 
-impl<'a, P: GeomProcessor, T: Read + 'a> Iterator for ShapeIterator<'a, P, T> {
+impl<'a, P: GeomProcessor, T: Read + Seek + 'a> Iterator for ShapeIterator<'a, P, T> {
     type Item = Result<(), Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if let Some(selection) = &mut self.selection {
+            let (_, index) = selection.next()?;
+            let pos = index.offset as u64 * 2;
+            if let Err(e) = self.source.seek(SeekFrom::Start(pos)) {
+                return Some(Err(e.into()));
+            }
+            return Some(read_shape(self.processor, &mut self.source).map(|_| ()));
+        }
         if self.current_pos >= self.file_length {
             None
         } else {
@@ -39,6 +50,7 @@ impl<'a, P: FeatureProcessor, T: Read + Seek + 'a> FusedIterator for ShapeIterat
 pub struct ShapeRecordIterator<'a, P: FeatureProcessor, T: Read + Seek> {
     shape_iter: ShapeIterator<'a, P, T>,
     dbf_reader: dbase::Reader<T>,
+    crs: Option<Crs>,
     featno: u64,
 }
 
@@ -52,6 +64,26 @@ impl<'a, P: FeatureProcessor, T: Read + Seek + 'a> Iterator for ShapeRecordItera
     fn next(&mut self) -> Option<Self::Item> {
         if self.featno == 0 {
             self.shape_iter.processor.dataset_begin(None).ok();
+            if let Some(crs) = &self.crs {
+                self.shape_iter.processor.crs(crs).ok();
+            }
+            let columns: Vec<ColumnDef> = self
+                .dbf_reader
+                .fields()
+                .iter()
+                .filter(|f| f.name() != "DeletionFlag")
+                .map(|f| ColumnDef::new(f.name(), column_type(f.field_type())))
+                .collect();
+            self.shape_iter.processor.schema_begin(&columns).ok();
+        }
+        if let Some(selection) = &self.shape_iter.selection {
+            let Some((record_no, _)) = selection.as_slice().first() else {
+                self.shape_iter.processor.dataset_end().ok();
+                return None;
+            };
+            if let Err(e) = self.dbf_reader.seek(*record_no) {
+                return Some(Err(Error::DbaseError(e)));
+            }
         }
         let record = match self.dbf_reader.iter_records().next() {
             None => {
@@ -90,12 +122,27 @@ impl<'a, P: FeatureProcessor, T: Read + Seek + 'a> Iterator for ShapeRecordItera
 
 impl<'a, P: FeatureProcessor, T: Read + Seek + 'a> FusedIterator for ShapeRecordIterator<'a, P, T> {}
 
+/// Property type of a dBASE field, matching the values of `process_properties`
+fn column_type(field_type: FieldType) -> ColumnType {
+    match field_type {
+        FieldType::Character | FieldType::Memo => ColumnType::String,
+        FieldType::Numeric | FieldType::Double | FieldType::Currency => ColumnType::Double,
+        FieldType::Logical => ColumnType::Bool,
+        FieldType::Date | FieldType::DateTime => ColumnType::DateTime,
+        FieldType::Float => ColumnType::Float,
+        FieldType::Integer => ColumnType::Int,
+    }
+}
+
 /// struct that reads the content of a shapefile
 pub struct ShpReader<T: Read + Seek> {
     source: T,
     header: header::Header,
     shapes_index: Option<Vec<ShapeIndex>>,
+    spatial_index: Option<QuadTree>,
     dbf_reader: Option<dbase::Reader<T>>,
+    selection: Option<Vec<(usize, ShapeIndex)>>,
+    crs: Option<Crs>,
 }
 
 impl<T: Read + Seek> ShpReader<T> {
@@ -117,7 +164,10 @@ impl<T: Read + Seek> ShpReader<T> {
             source,
             header,
             shapes_index: None,
+            spatial_index: None,
             dbf_reader: None,
+            selection: None,
+            crs: None,
         })
     }
 
@@ -126,6 +176,11 @@ impl<T: Read + Seek> ShpReader<T> {
         &self.header
     }
 
+    /// CRS read from the .prj file
+    pub fn crs(&self) -> Option<&Crs> {
+        self.crs.as_ref()
+    }
+
     /// Read and return _only_ the records contained in the *.dbf* file
     pub fn read_records(self) -> Result<Vec<dbase::Record>, Error> {
         let mut dbf_reader = self.dbf_reader.ok_or(Error::MissingDbf)?;
@@ -150,7 +205,42 @@ impl<T: Read + Seek> ShpReader<T> {
             source: self.source,
             current_pos: header::HEADER_SIZE as usize,
             file_length: (self.header.file_length * 2) as usize,
+            selection: self.selection.map(|selection| selection.into_iter()),
+        }
+    }
+
+    /// Select shapes intersecting the given bounding box
+    ///
+    /// Requires the index file (.shx). A quadtree spatial index (.qix) is used, if available.
+    /// Subsequent iterations only return the selected shapes.
+    pub fn select_bbox(
+        &mut self,
+        minx: f64,
+        miny: f64,
+        maxx: f64,
+        maxy: f64,
+    ) -> Result<usize, Error> {
+        let bbox = [minx, miny, maxx, maxy];
+        let shapes_index = self.shapes_index.as_ref().ok_or(Error::MissingIndexFile)?;
+        let candidates = match &self.spatial_index {
+            Some(qix) => qix.select_bbox(&bbox)?,
+            None => (0..shapes_index.len()).collect(),
+        };
+        let mut selection = Vec::new();
+        for record_no in candidates {
+            let Some(index) = shapes_index.get(record_no) else {
+                continue;
+            };
+            self.source.seek(SeekFrom::Start(index.offset as u64 * 2))?;
+            if let Some(shape_bbox) = read_shape_bbox(&mut self.source)? {
+                if intersects(&shape_bbox, &bbox) {
+                    selection.push((record_no, index.clone()));
+                }
+            }
         }
+        let count = selection.len();
+        self.selection = Some(selection);
+        Ok(count)
     }
 
     /// Returns an iterator over the Shapes and their Records
@@ -168,6 +258,7 @@ impl<T: Read + Seek> ShpReader<T> {
             Ok(ShapeRecordIterator {
                 shape_iter,
                 dbf_reader,
+                crs: self.crs,
                 featno: 0,
             })
         } else {
@@ -184,24 +275,44 @@ impl<T: Read + Seek> ShpReader<T> {
         Ok(())
     }
 
+    /// Reads a quadtree spatial index (.qix) used for [select_bbox()](Self::select_bbox)
+    pub fn add_spatial_index_source(&mut self, source: T) -> Result<(), Error> {
+        self.spatial_index = Some(QuadTree::read_from(source)?);
+        Ok(())
+    }
+
     /// Adds the `source` as the source where the dbf record will be read from
     pub fn add_dbf_source(&mut self, source: T) -> Result<(), Error> {
         let dbf_reader = dbase::Reader::new(source)?;
         self.dbf_reader = Some(dbf_reader);
         Ok(())
     }
+
+    /// Read CRS from the WKT of a .prj file
+    pub fn add_prj_source<R: Read>(&mut self, mut source: R) -> Result<(), Error> {
+        let mut wkt = String::new();
+        source.read_to_string(&mut wkt)?;
+        let wkt = wkt.trim();
+        self.crs = (!wkt.is_empty()).then(|| Crs::from_wkt(wkt));
+        Ok(())
+    }
 }
 
 impl ShpReader<BufReader<File>> {
     /// Creates a reader from a path to a file
     ///
-    /// Will attempt to read both the .shx and .dbf associated with the file,
+    /// Will attempt to read the .shx, .dbf and .prj associated with the file,
     /// if they do not exists the function will not fail, and you will get an error later
     /// if you try to use a function that requires the file to be present.
     pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
         let shape_path = path.as_ref().to_path_buf();
         let shx_path = shape_path.with_extension("shx");
         let dbf_path = shape_path.with_extension("dbf");
+        let qix_path = shape_path.with_extension("qix");
+        let prj_path = ["prj", "PRJ"]
+            .map(|ext| shape_path.with_extension(ext))
+            .into_iter()
+            .find(|path| path.exists());
 
         let source = BufReader::new(File::open(shape_path)?);
         let mut reader = Self::new(source)?;
@@ -211,10 +322,19 @@ impl ShpReader<BufReader<File>> {
             reader.add_index_source(index_source)?;
         }
 
+        if qix_path.exists() {
+            let qix_source = BufReader::new(File::open(qix_path)?);
+            reader.add_spatial_index_source(qix_source)?;
+        }
+
         if dbf_path.exists() {
             let dbf_source = BufReader::new(File::open(dbf_path)?);
             reader.add_dbf_source(dbf_source)?;
         }
+
+        if let Some(prj_path) = prj_path {
+            reader.add_prj_source(File::open(prj_path)?)?;
+        }
         Ok(reader)
     }
 }
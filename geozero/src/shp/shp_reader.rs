@@ -42,6 +42,27 @@ pub(crate) fn read_shape<'a, P: GeomProcessor + 'a, T: Read>(
     Ok(hdr)
 }
 
+/// Read bounding box `[minx, miny, maxx, maxy]` of a shape record
+///
+/// Returns `None` for null shapes.
+pub(crate) fn read_shape_bbox<T: Read>(mut source: &mut T) -> Result<Option<[f64; 4]>, Error> {
+    let _hdr = RecordHeader::read_from(&mut source)?;
+    let shape_type = ShapeType::read_from(&mut source)?;
+    let bbox = match shape_type {
+        ShapeType::NullShape => None,
+        ShapeType::Point | ShapeType::PointM | ShapeType::PointZ => {
+            let x = source.read_f64::<LittleEndian>()?;
+            let y = source.read_f64::<LittleEndian>()?;
+            Some([x, y, x, y])
+        }
+        _ => {
+            let bbox = read_bbox(source, 2)?;
+            Some([bbox[0], bbox[1], bbox[2], bbox[3]])
+        }
+    };
+    Ok(bbox)
+}
+
 fn read_shape_rec<P: GeomProcessor, T: Read>(
     processor: &mut P,
     mut source: &mut T,
@@ -163,6 +184,7 @@ fn read_multipoint<P: GeomProcessor, T: Read>(
     let get_z = dimensions.z && !z_values.is_empty();
     let get_m = dimensions.m && !m_values.is_empty();
 
+    processor.size_hint_total_coords(num_points)?;
     processor.multipoint_begin(num_points, 0)?;
     for idx in 0..num_points {
         let coord = &coords[idx];
@@ -298,6 +320,7 @@ impl MultiPartShape {
             vec![0, self.parts_index.len() - 1]
         };
 
+        processor.size_hint_total_coords(self.coords.len())?;
         if as_poly {
             processor.multipolygon_begin(geom_parts_indices.len() - 1, 0)?;
         } else {
@@ -0,0 +1,719 @@
+use crate::error::{GeozeroError, Result};
+use crate::rename::Renamer;
+use crate::shp::header::{Header, HEADER_SIZE};
+use crate::shp::point_z::{BBoxZ, PointZ};
+use crate::shp::shp_reader::{is_no_data, NO_DATA};
+use crate::shp::ShapeType;
+use crate::{
+    ColumnDef, ColumnType, ColumnValue, CoordDimensions, FeatureProcessor, GeomProcessor,
+    PropertyProcessor,
+};
+use byteorder::{BigEndian, LittleEndian, WriteBytesExt};
+use dbase::{FieldName, FieldType, FieldValue, TableWriter, TableWriterBuilder};
+use std::fs::File;
+use std::io::{BufWriter, Seek, SeekFrom, Write};
+use std::path::Path;
+
+/// Maximal length of dBASE field names
+const MAX_FIELD_NAME_LEN: usize = 10;
+
+/// dBASE field definition
+#[derive(Clone, Debug, PartialEq)]
+pub struct DbfField {
+    /// Property name
+    pub name: String,
+    /// Supported types: `Character`, `Numeric`, `Float`, `Integer`, `Double` and `Logical`
+    pub field_type: FieldType,
+    /// Field length for `Character`, `Numeric` and `Float` fields
+    pub length: u8,
+    /// Number of decimals for `Numeric` and `Float` fields
+    pub decimals: u8,
+}
+
+impl DbfField {
+    pub fn new(name: &str, field_type: FieldType, length: u8, decimals: u8) -> Self {
+        DbfField {
+            name: name.to_string(),
+            field_type,
+            length,
+            decimals,
+        }
+    }
+
+    /// Field definition derived from a property value
+    pub fn from_value(name: &str, value: &ColumnValue) -> Self {
+        DbfField::from_column(&ColumnDef::new(name, value.column_type()))
+    }
+
+    /// Field definition derived from a column declaration
+    pub fn from_column(column: &ColumnDef) -> Self {
+        let name = &column.name;
+        match column.column_type {
+            ColumnType::Bool => DbfField::new(name, FieldType::Logical, 1, 0),
+            ColumnType::Byte
+            | ColumnType::UByte
+            | ColumnType::Short
+            | ColumnType::UShort
+            | ColumnType::Int
+            | ColumnType::UInt => DbfField::new(name, FieldType::Numeric, 11, 0),
+            ColumnType::Long | ColumnType::ULong => DbfField::new(name, FieldType::Numeric, 20, 0),
+            ColumnType::Float | ColumnType::Double => {
+                DbfField::new(name, FieldType::Numeric, 24, 15)
+            }
+            ColumnType::String
+            | ColumnType::Json
+            | ColumnType::DateTime
+            | ColumnType::Binary
+            | ColumnType::Uuid
+            | ColumnType::Decimal
+            | ColumnType::Array => DbfField::new(name, FieldType::Character, 254, 0),
+        }
+    }
+
+    fn null_value(&self) -> FieldValue {
+        match self.field_type {
+            FieldType::Numeric => FieldValue::Numeric(None),
+            FieldType::Float => FieldValue::Float(None),
+            FieldType::Logical => FieldValue::Logical(None),
+            FieldType::Integer => FieldValue::Integer(0),
+            FieldType::Double => FieldValue::Double(0.0),
+            _ => FieldValue::Character(None),
+        }
+    }
+
+    fn field_value(&self, value: &ColumnValue) -> Result<FieldValue> {
+        let number = || -> Result<f64> {
+            match value {
+                ColumnValue::Byte(v) => Ok(f64::from(*v)),
+                ColumnValue::UByte(v) => Ok(f64::from(*v)),
+                ColumnValue::Bool(v) => Ok(f64::from(u8::from(*v))),
+                ColumnValue::Short(v) => Ok(f64::from(*v)),
+                ColumnValue::UShort(v) => Ok(f64::from(*v)),
+                ColumnValue::Int(v) => Ok(f64::from(*v)),
+                ColumnValue::UInt(v) => Ok(f64::from(*v)),
+                ColumnValue::Long(v) => Ok(*v as f64),
+                ColumnValue::ULong(v) => Ok(*v as f64),
+                ColumnValue::Float(v) => Ok(f64::from(*v)),
+                ColumnValue::Double(v) => Ok(*v),
+                ColumnValue::String(v) | ColumnValue::Decimal(v) => {
+                    v.parse().map_err(|_| self.type_error(value))
+                }
+                _ => Err(self.type_error(value)),
+            }
+        };
+        let field_value = match self.field_type {
+            FieldType::Character => FieldValue::Character(Some(value.to_string())),
+            FieldType::Numeric => FieldValue::Numeric(Some(number()?)),
+            FieldType::Float => FieldValue::Float(Some(number()? as f32)),
+            FieldType::Integer => FieldValue::Integer(number()? as i32),
+            FieldType::Double => FieldValue::Double(number()?),
+            FieldType::Logical => match value {
+                ColumnValue::Bool(v) => FieldValue::Logical(Some(*v)),
+                _ => FieldValue::Logical(Some(number()? != 0.0)),
+            },
+            _ => {
+                return Err(GeozeroError::Property(format!(
+                    "unsupported dBASE field type {:?}",
+                    self.field_type
+                )))
+            }
+        };
+        Ok(field_value)
+    }
+
+    fn type_error(&self, value: &ColumnValue) -> GeozeroError {
+        GeozeroError::ColumnType(format!("{:?}", self.field_type), format!("{value:?}"))
+    }
+}
+
+/// ESRI Shapefile writer.
+///
+/// Writes `.shp`, `.shx` and `.dbf` files. All geometries must be compatible with the
+/// configured [ShapeType]. Polygon rings are oriented as required by the specification
+/// (exterior rings clockwise, interior rings counterclockwise).
+///
+/// dBASE fields are either configured with [`ShpWriter::add_field`], derived from the
+/// dataset schema or derived from the properties of the first feature. Records are written on `feature_end`.
+///
+/// # Usage example:
+///
+/// ```rust,ignore
+/// use geozero::shp::{ShapeType, ShpWriter};
+/// use geozero::GeozeroDatasource;
+///
+/// let mut writer = ShpWriter::from_path("places.shp", ShapeType::Point)?;
+/// geojson_reader.process(&mut writer)?;
+/// ```
+pub struct ShpWriter<W: Write + Seek> {
+    shape_type: ShapeType,
+    shp: W,
+    shx: W,
+    dbf_dest: Option<W>,
+    dbf: Option<TableWriter<W>>,
+    fields: Vec<DbfField>,
+    /// dBASE field names, truncated and made unique
+    dbf_names: Vec<String>,
+    header_written: bool,
+    /// Total length of the .shp file in 16-bit words
+    shp_length: i32,
+    num_records: i32,
+    bbox: Option<BBoxZ>,
+    // current feature
+    parts: Vec<Vec<PointZ>>,
+    /// Index of the first ring of the current polygon
+    polygon_start: usize,
+    values: Vec<Option<FieldValue>>,
+    first_properties: Vec<(String, DbfField, FieldValue)>,
+}
+
+impl<W: Write + Seek> ShpWriter<W> {
+    /// Create writer for `.shp`, `.shx` and optional `.dbf` output.
+    pub fn new(shp: W, shx: W, dbf: Option<W>, shape_type: ShapeType) -> Self {
+        ShpWriter {
+            shape_type,
+            shp,
+            shx,
+            dbf_dest: dbf,
+            dbf: None,
+            fields: Vec::new(),
+            dbf_names: Vec::new(),
+            header_written: false,
+            shp_length: HEADER_SIZE / 2,
+            num_records: 0,
+            bbox: None,
+            parts: Vec::new(),
+            polygon_start: 0,
+            values: Vec::new(),
+            first_properties: Vec::new(),
+        }
+    }
+
+    /// Add dBASE field definition.
+    ///
+    /// Properties without field definition are ignored, if at least one field is defined.
+    pub fn add_field(&mut self, field: DbfField) {
+        self.fields.push(field);
+    }
+
+    /// Configured shape type
+    pub fn shape_type(&self) -> ShapeType {
+        self.shape_type
+    }
+
+    fn write_header(&mut self) -> Result<()> {
+        let mut header = Header {
+            file_length: self.shp_length,
+            shape_type: self.shape_type,
+            ..Default::default()
+        };
+        if let Some(bbox) = self.bbox {
+            header.bbox = bbox;
+        }
+        if !self.shape_type.has_m() {
+            header.bbox.min.m = 0.0;
+            header.bbox.max.m = 0.0;
+        }
+        header.write_to(&mut self.shp)?;
+        header.file_length = HEADER_SIZE / 2 + self.num_records * 4;
+        header.write_to(&mut self.shx)?;
+        Ok(())
+    }
+
+    fn begin_dbf(&mut self) -> Result<()> {
+        let Some(dest) = self.dbf_dest.take() else {
+            return Ok(());
+        };
+        if self.fields.is_empty() {
+            self.fields = self
+                .first_properties
+                .iter()
+                .map(|(_, field, _)| field.clone())
+                .collect();
+        }
+        // Names truncated to the same prefix get a numeric suffix, e.g. `populati_1`
+        let mut renamer = Renamer::with_max_length(MAX_FIELD_NAME_LEN);
+        self.dbf_names = self
+            .fields
+            .iter()
+            .map(|field| renamer.rename(&field.name).to_string())
+            .collect();
+        let mut builder = TableWriterBuilder::new();
+        for (field, dbf_name) in self.fields.iter().zip(&self.dbf_names) {
+            let name = FieldName::try_from(dbf_name.as_str())
+                .map_err(|e| GeozeroError::Property(format!("{e:?}")))?;
+            builder = match field.field_type {
+                FieldType::Character => builder.add_character_field(name, field.length),
+                FieldType::Numeric => builder.add_numeric_field(name, field.length, field.decimals),
+                FieldType::Float => builder.add_float_field(name, field.length, field.decimals),
+                FieldType::Integer => builder.add_integer_field(name),
+                FieldType::Double => builder.add_double_field(name),
+                FieldType::Logical => builder.add_logical_field(name),
+                _ => {
+                    return Err(GeozeroError::Property(format!(
+                        "unsupported dBASE field type {:?}",
+                        field.field_type
+                    )))
+                }
+            };
+        }
+        self.dbf = Some(builder.build_with_dest(dest));
+        // Values of the first feature were collected before the fields were known
+        self.values = vec![None; self.fields.len()];
+        for (name, _, value) in self.first_properties.drain(..) {
+            if let Some(i) = self.fields.iter().position(|f| f.name == name) {
+                self.values[i] = Some(value);
+            }
+        }
+        Ok(())
+    }
+
+    fn write_dbf_record(&mut self) -> Result<()> {
+        if self.dbf_dest.is_some() {
+            self.begin_dbf()?;
+        }
+        let Some(dbf) = self.dbf.as_mut() else {
+            return Ok(());
+        };
+        let mut record = dbase::Record::default();
+        for (i, (field, dbf_name)) in self.fields.iter().zip(&self.dbf_names).enumerate() {
+            let value = self
+                .values
+                .get_mut(i)
+                .and_then(Option::take)
+                .unwrap_or_else(|| field.null_value());
+            record.insert(dbf_name.clone(), value);
+        }
+        dbf.write_record(&record)
+            .map_err(|e| GeozeroError::Property(e.to_string()))
+    }
+
+    fn write_shape_record(&mut self) -> Result<()> {
+        if !self.header_written {
+            // Placeholder, rewritten on `dataset_end`
+            self.write_header()?;
+            self.header_written = true;
+        }
+        let parts = std::mem::take(&mut self.parts);
+        let mut content = Vec::new();
+        if parts.iter().all(Vec::is_empty) {
+            content.write_i32::<LittleEndian>(ShapeType::NullShape as i32)?;
+        } else {
+            self.write_shape(&parts, &mut content)?;
+            self.update_bbox(&parts);
+        }
+        let content_length = (content.len() / 2) as i32;
+        self.num_records += 1;
+        self.shx.write_i32::<BigEndian>(self.shp_length)?;
+        self.shx.write_i32::<BigEndian>(content_length)?;
+        self.shp.write_i32::<BigEndian>(self.num_records)?;
+        self.shp.write_i32::<BigEndian>(content_length)?;
+        self.shp.write_all(&content)?;
+        self.shp_length += 4 + content_length;
+        Ok(())
+    }
+
+    fn write_shape(&self, parts: &[Vec<PointZ>], out: &mut Vec<u8>) -> Result<()> {
+        let shape_type = self.shape_type;
+        out.write_i32::<LittleEndian>(shape_type as i32)?;
+        let points: Vec<&PointZ> = parts.iter().flatten().collect();
+        match shape_type {
+            ShapeType::Point | ShapeType::PointM | ShapeType::PointZ => {
+                if points.len() != 1 {
+                    return Err(GeozeroError::Geometry(format!(
+                        "{shape_type} shape requires exactly one point"
+                    )));
+                }
+                let point = points[0];
+                out.write_f64::<LittleEndian>(point.x)?;
+                out.write_f64::<LittleEndian>(point.y)?;
+                if shape_type.has_z() {
+                    out.write_f64::<LittleEndian>(point.z)?;
+                }
+                if shape_type.has_m() {
+                    out.write_f64::<LittleEndian>(point.m)?;
+                }
+            }
+            _ => {
+                let bbox = bbox(points.iter().copied());
+                out.write_f64::<LittleEndian>(bbox.min.x)?;
+                out.write_f64::<LittleEndian>(bbox.min.y)?;
+                out.write_f64::<LittleEndian>(bbox.max.x)?;
+                out.write_f64::<LittleEndian>(bbox.max.y)?;
+                if shape_type.is_multipart() {
+                    out.write_i32::<LittleEndian>(parts.len() as i32)?;
+                }
+                out.write_i32::<LittleEndian>(points.len() as i32)?;
+                if shape_type.is_multipart() {
+                    let mut start = 0;
+                    for part in parts {
+                        out.write_i32::<LittleEndian>(start)?;
+                        start += part.len() as i32;
+                    }
+                }
+                for point in &points {
+                    out.write_f64::<LittleEndian>(point.x)?;
+                    out.write_f64::<LittleEndian>(point.y)?;
+                }
+                if shape_type.has_z() {
+                    out.write_f64::<LittleEndian>(bbox.min.z)?;
+                    out.write_f64::<LittleEndian>(bbox.max.z)?;
+                    for point in &points {
+                        out.write_f64::<LittleEndian>(point.z)?;
+                    }
+                }
+                if shape_type.has_m() {
+                    out.write_f64::<LittleEndian>(bbox.min.m)?;
+                    out.write_f64::<LittleEndian>(bbox.max.m)?;
+                    for point in &points {
+                        out.write_f64::<LittleEndian>(point.m)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn update_bbox(&mut self, parts: &[Vec<PointZ>]) {
+        let shape_bbox = bbox(parts.iter().flatten());
+        self.bbox = Some(match self.bbox {
+            Some(total) => bbox([total.min, total.max, shape_bbox.min, shape_bbox.max].iter()),
+            None => shape_bbox,
+        });
+    }
+
+    /// Check whether a geometry type is compatible with the configured shape type.
+    fn check_shape_type(&self, geometry_type: &str, compatible: &[ShapeType]) -> Result<()> {
+        if compatible.contains(&self.shape_type) {
+            Ok(())
+        } else {
+            Err(GeozeroError::Geometry(format!(
+                "{geometry_type} not supported for shape type {}",
+                self.shape_type
+            )))
+        }
+    }
+
+    fn add_point(&mut self, point: PointZ) -> Result<()> {
+        match self.parts.last_mut() {
+            Some(part) => {
+                part.push(point);
+                Ok(())
+            }
+            None => Err(GeozeroError::Geometry(
+                "coordinate outside of geometry".to_string(),
+            )),
+        }
+    }
+}
+
+impl ShpWriter<BufWriter<File>> {
+    /// Create `.shp`, `.shx` and `.dbf` files.
+    pub fn from_path<P: AsRef<Path>>(path: P, shape_type: ShapeType) -> Result<Self> {
+        let shape_path = path.as_ref().to_path_buf();
+        let shp = BufWriter::new(File::create(&shape_path)?);
+        let shx = BufWriter::new(File::create(shape_path.with_extension("shx"))?);
+        let dbf = BufWriter::new(File::create(shape_path.with_extension("dbf"))?);
+        Ok(Self::new(shp, shx, Some(dbf), shape_type))
+    }
+}
+
+/// Bounding box of all points, with unset measures (`NO_DATA`) ignored
+fn bbox<'a>(points: impl Iterator<Item = &'a PointZ>) -> BBoxZ {
+    let mut bbox = BBoxZ {
+        min: PointZ::new(f64::INFINITY, f64::INFINITY, f64::INFINITY, f64::INFINITY),
+        max: PointZ::new(
+            f64::NEG_INFINITY,
+            f64::NEG_INFINITY,
+            f64::NEG_INFINITY,
+            f64::NEG_INFINITY,
+        ),
+    };
+    for p in points {
+        bbox.min.x = bbox.min.x.min(p.x);
+        bbox.min.y = bbox.min.y.min(p.y);
+        bbox.min.z = bbox.min.z.min(p.z);
+        bbox.max.x = bbox.max.x.max(p.x);
+        bbox.max.y = bbox.max.y.max(p.y);
+        bbox.max.z = bbox.max.z.max(p.z);
+        if !is_no_data(p.m) {
+            bbox.min.m = bbox.min.m.min(p.m);
+            bbox.max.m = bbox.max.m.max(p.m);
+        }
+    }
+    if bbox.min.m > bbox.max.m {
+        bbox.min.m = NO_DATA;
+        bbox.max.m = NO_DATA;
+    }
+    bbox
+}
+
+/// Twice the signed area of a ring (positive for counterclockwise rings)
+fn signed_area(ring: &[PointZ]) -> f64 {
+    -ring
+        .windows(2)
+        .map(|pts| (pts[1].x - pts[0].x) * (pts[1].y + pts[0].y))
+        .sum::<f64>()
+}
+
+impl<W: Write + Seek> GeomProcessor for ShpWriter<W> {
+    fn dimensions(&self) -> CoordDimensions {
+        CoordDimensions {
+            z: self.shape_type.has_z(),
+            m: self.shape_type.has_m(),
+            t: false,
+            tm: false,
+        }
+    }
+    fn xy(&mut self, x: f64, y: f64, _idx: usize) -> Result<()> {
+        self.add_point(PointZ::new(x, y, 0.0, NO_DATA))
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        _t: Option<f64>,
+        _tm: Option<u64>,
+        _idx: usize,
+    ) -> Result<()> {
+        self.add_point(PointZ::new(x, y, z.unwrap_or(0.0), m.unwrap_or(NO_DATA)))
+    }
+    fn point_begin(&mut self, _idx: usize) -> Result<()> {
+        self.check_shape_type(
+            "Point",
+            &[
+                ShapeType::Point,
+                ShapeType::PointM,
+                ShapeType::PointZ,
+                ShapeType::Multipoint,
+                ShapeType::MultipointM,
+                ShapeType::MultipointZ,
+            ],
+        )?;
+        self.parts.push(Vec::with_capacity(1));
+        Ok(())
+    }
+    fn multipoint_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.check_shape_type(
+            "MultiPoint",
+            &[
+                ShapeType::Multipoint,
+                ShapeType::MultipointM,
+                ShapeType::MultipointZ,
+            ],
+        )?;
+        self.parts.push(Vec::with_capacity(size));
+        Ok(())
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, _idx: usize) -> Result<()> {
+        if tagged {
+            self.check_shape_type(
+                "LineString",
+                &[
+                    ShapeType::Polyline,
+                    ShapeType::PolylineM,
+                    ShapeType::PolylineZ,
+                ],
+            )?;
+        }
+        self.parts.push(Vec::with_capacity(size));
+        Ok(())
+    }
+    fn multilinestring_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.check_shape_type(
+            "MultiLineString",
+            &[
+                ShapeType::Polyline,
+                ShapeType::PolylineM,
+                ShapeType::PolylineZ,
+            ],
+        )
+    }
+    fn polygon_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        self.check_shape_type(
+            "Polygon",
+            &[ShapeType::Polygon, ShapeType::PolygonM, ShapeType::PolygonZ],
+        )?;
+        self.polygon_start = self.parts.len();
+        Ok(())
+    }
+    fn polygon_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        // Exterior ring must be clockwise, interior rings counterclockwise
+        for (i, ring) in self.parts[self.polygon_start..].iter_mut().enumerate() {
+            let ccw = signed_area(ring) > 0.0;
+            if (i == 0) == ccw {
+                ring.reverse();
+            }
+        }
+        Ok(())
+    }
+    fn multipolygon_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.check_shape_type(
+            "MultiPolygon",
+            &[ShapeType::Polygon, ShapeType::PolygonM, ShapeType::PolygonZ],
+        )
+    }
+    fn geometrycollection_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        Err(GeozeroError::Geometry(
+            "GeometryCollection not supported by Shapefile".to_string(),
+        ))
+    }
+}
+
+impl<W: Write + Seek> PropertyProcessor for ShpWriter<W> {
+    fn property(&mut self, _idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        if self.dbf.is_none() && self.dbf_dest.is_some() {
+            // Fields are created before writing the first record
+            let field = self
+                .fields
+                .iter()
+                .find(|f| f.name == name)
+                .cloned()
+                .unwrap_or_else(|| DbfField::from_value(name, value));
+            let value = field.field_value(value)?;
+            self.first_properties.push((name.to_string(), field, value));
+        } else if self.dbf.is_some() {
+            if let Some(i) = self.fields.iter().position(|f| f.name == name) {
+                self.values[i] = Some(self.fields[i].field_value(value)?);
+            }
+        }
+        Ok(false)
+    }
+}
+
+impl<W: Write + Seek> FeatureProcessor for ShpWriter<W> {
+    fn dataset_end(&mut self) -> Result<()> {
+        if !self.header_written {
+            self.write_header()?;
+            self.header_written = true;
+        }
+        if self.dbf_dest.is_some() {
+            self.begin_dbf()?;
+        }
+        if let Some(mut dbf) = self.dbf.take() {
+            // Writes the record count and flushes the .dbf output
+            dbf.finalize()
+                .map_err(|e| GeozeroError::Property(e.to_string()))?;
+        }
+        self.shp.seek(SeekFrom::Start(0))?;
+        self.shx.seek(SeekFrom::Start(0))?;
+        self.write_header()?;
+        self.shp.seek(SeekFrom::End(0))?;
+        self.shx.seek(SeekFrom::End(0))?;
+        self.shp.flush()?;
+        self.shx.flush()?;
+        Ok(())
+    }
+    fn schema_begin(&mut self, columns: &[ColumnDef]) -> Result<()> {
+        if self.fields.is_empty() {
+            self.fields = columns.iter().map(DbfField::from_column).collect();
+        }
+        Ok(())
+    }
+    fn feature_begin(&mut self, _idx: u64) -> Result<()> {
+        self.parts.clear();
+        Ok(())
+    }
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        self.write_shape_record()?;
+        self.write_dbf_record()
+    }
+}
+
+#[cfg(test)]
+#[cfg(all(feature = "with-geojson", feature = "with-wkt"))]
+mod test {
+    use super::*;
+    use crate::geojson::GeoJson;
+    use crate::shp::ShpReader;
+    use crate::wkt::WktWriter;
+    use crate::{FeatureProperties, GeozeroDatasource};
+    use std::io::Cursor;
+
+    fn write_shp(geojson: &str, shape_type: ShapeType) -> Result<(Vec<u8>, Vec<u8>, Vec<u8>)> {
+        let mut shp = Cursor::new(Vec::new());
+        let mut shx = Cursor::new(Vec::new());
+        let mut dbf = Cursor::new(Vec::new());
+        {
+            let mut writer = ShpWriter::new(&mut shp, &mut shx, Some(&mut dbf), shape_type);
+            GeoJson(geojson).process(&mut writer)?;
+        }
+        Ok((shp.into_inner(), shx.into_inner(), dbf.into_inner()))
+    }
+
+    #[test]
+    fn points() -> Result<()> {
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"name": "first", "population": 100}, "geometry": {"type": "Point", "coordinates": [1, 2]}},
+            {"type": "Feature", "properties": {"name": "second", "population": 200}, "geometry": {"type": "Point", "coordinates": [3, 4]}}
+        ]}"#;
+        let (shp, shx, dbf) = write_shp(geojson, ShapeType::Point)?;
+        // header + 2 * (record header + shape type + xy)
+        assert_eq!(shp.len(), 100 + 2 * (8 + 4 + 16));
+        assert_eq!(shx.len(), 100 + 2 * 8);
+
+        let mut reader = ShpReader::new(Cursor::new(shp)).unwrap();
+        assert_eq!(reader.header().shape_type, ShapeType::Point);
+        assert_eq!(reader.header().bbox.x_range(), [1.0, 3.0]);
+        assert_eq!(reader.header().bbox.y_range(), [2.0, 4.0]);
+        reader.add_dbf_source(Cursor::new(dbf)).unwrap();
+
+        let mut wkt: Vec<u8> = Vec::new();
+        let mut writer = WktWriter::new(&mut wkt);
+        let mut names = Vec::new();
+        for feature in reader.iter_features(&mut writer).unwrap() {
+            let feature = feature.unwrap();
+            names.push(feature.property::<String>("name")?);
+            assert!(feature.property::<f64>("population")? >= 100.0);
+        }
+        assert_eq!(names, vec!["first", "second"]);
+        assert_eq!(std::str::from_utf8(&wkt).unwrap(), "POINT(1 2)POINT(3 4)");
+        Ok(())
+    }
+
+    #[test]
+    fn truncated_field_names() -> Result<()> {
+        let geojson = r#"{"type": "Feature", "properties": {"population_a": 100, "population_b": 40}, "geometry": {"type": "Point", "coordinates": [1, 2]}}"#;
+        let (shp, _shx, dbf) = write_shp(geojson, ShapeType::Point)?;
+        let mut reader = ShpReader::new(Cursor::new(shp)).unwrap();
+        reader.add_dbf_source(Cursor::new(dbf)).unwrap();
+        let mut wkt: Vec<u8> = Vec::new();
+        let mut writer = WktWriter::new(&mut wkt);
+        for feature in reader.iter_features(&mut writer).unwrap() {
+            let feature = feature.unwrap();
+            assert_eq!(feature.property::<f64>("population")?, 100.0);
+            assert_eq!(feature.property::<f64>("populati_1")?, 40.0);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn polygon_orientation() -> Result<()> {
+        // counterclockwise exterior ring
+        let geojson = r#"{"type": "Feature", "properties": {}, "geometry": {"type": "Polygon", "coordinates": [[[0, 0], [1, 0], [1, 1], [0, 1], [0, 0]]]}}"#;
+        let (shp, _shx, _dbf) = write_shp(geojson, ShapeType::Polygon)?;
+        let reader = ShpReader::new(Cursor::new(shp)).unwrap();
+        let mut wkt: Vec<u8> = Vec::new();
+        let mut writer = WktWriter::new(&mut wkt);
+        for result in reader.iter_geometries(&mut writer) {
+            result.unwrap();
+        }
+        assert_eq!(
+            std::str::from_utf8(&wkt).unwrap(),
+            "MULTIPOLYGON(((0 0,0 1,1 1,1 0,0 0)))"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn incompatible_shape_type() {
+        let geojson = r#"{"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [1, 2]}}"#;
+        assert_eq!(
+            write_shp(geojson, ShapeType::Polygon)
+                .unwrap_err()
+                .to_string(),
+            "processing geometry `Point not supported for shape type Polygon`"
+        );
+    }
+}
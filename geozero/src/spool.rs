@@ -0,0 +1,1435 @@
+//! Feature buffering in a binary encoding with spilling to a temporary file.
+use crate::bounds::Bounds;
+use crate::error::{GeozeroError, Result};
+use crate::wrap::{delegate_coordinates, delegate_geometry_types};
+use crate::{
+    ColumnDef, ColumnType, ColumnValue, ColumnValueOwned, CoordDimensions, Crs, FeatureId,
+    FeatureProcessor, GeomProcessor, GeozeroDatasource, PropertyProcessor,
+};
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// Event tags
+const FEATURE_ID_ULONG: u8 = 1;
+const FEATURE_ID_LONG: u8 = 2;
+const FEATURE_ID_STRING: u8 = 3;
+const PROPERTIES_BEGIN: u8 = 4;
+const PROPERTIES_END: u8 = 5;
+const PROPERTY: u8 = 6;
+const COLUMN_PROPERTY: u8 = 7;
+const GEOMETRY_BEGIN: u8 = 8;
+const GEOMETRY_END: u8 = 9;
+const SRID: u8 = 10;
+const ENVELOPE: u8 = 11;
+const SIZE_HINT: u8 = 12;
+const XY: u8 = 13;
+const COORDINATE: u8 = 14;
+const EMPTY_POINT: u8 = 15;
+const GEOM_BEGIN: u8 = 16;
+const GEOM_END: u8 = 17;
+
+// Geometry types of GEOM_BEGIN and GEOM_END
+const POINT: u8 = 1;
+const MULTIPOINT: u8 = 2;
+const LINESTRING: u8 = 3;
+const MULTILINESTRING: u8 = 4;
+const POLYGON: u8 = 5;
+const MULTIPOLYGON: u8 = 6;
+const GEOMETRYCOLLECTION: u8 = 7;
+const CIRCULARSTRING: u8 = 8;
+const COMPOUNDCURVE: u8 = 9;
+const CURVEPOLYGON: u8 = 10;
+const MULTICURVE: u8 = 11;
+const MULTISURFACE: u8 = 12;
+const TRIANGLE: u8 = 13;
+const POLYHEDRALSURFACE: u8 = 14;
+const TIN: u8 = 15;
+
+// Dataset event tags of FeatureEventBuf
+const DATASET_BEGIN: u8 = 1;
+const DATASET_END: u8 = 2;
+const SCHEMA: u8 = 3;
+const FEATURE: u8 = 4;
+const CRS: u8 = 5;
+const GEOMETRY: u8 = 6;
+
+/// Column types by encoded value type
+const COLUMN_TYPES: [ColumnType; 18] = [
+    ColumnType::Byte,
+    ColumnType::UByte,
+    ColumnType::Bool,
+    ColumnType::Short,
+    ColumnType::UShort,
+    ColumnType::Int,
+    ColumnType::UInt,
+    ColumnType::Long,
+    ColumnType::ULong,
+    ColumnType::Float,
+    ColumnType::Double,
+    ColumnType::String,
+    ColumnType::Json,
+    ColumnType::DateTime,
+    ColumnType::Binary,
+    ColumnType::Uuid,
+    ColumnType::Decimal,
+    ColumnType::Array,
+];
+
+/// Value type of arrays, other value types are tagged 0..=16
+const ARRAY: u8 = 17;
+
+/// Encodes the events of a single feature.
+pub(crate) struct FeatureEncoder {
+    dims: CoordDimensions,
+    buf: Vec<u8>,
+    /// Bounds of the feature geometry
+    bounds: Option<Bounds>,
+}
+
+impl FeatureEncoder {
+    pub fn new(dims: CoordDimensions) -> Self {
+        FeatureEncoder {
+            dims,
+            buf: Vec::new(),
+            bounds: None,
+        }
+    }
+    /// Encoded feature
+    pub fn data(&self) -> &[u8] {
+        &self.buf
+    }
+    /// Bounds of the feature geometry
+    pub fn bounds(&self) -> Option<Bounds> {
+        self.bounds
+    }
+
+    fn tag(&mut self, tag: u8) {
+        self.buf.push(tag);
+    }
+    fn varint(&mut self, mut v: u64) {
+        while v >= 0x80 {
+            self.buf.push((v as u8) | 0x80);
+            v >>= 7;
+        }
+        self.buf.push(v as u8);
+    }
+    fn f64(&mut self, v: f64) {
+        self.buf.extend_from_slice(&v.to_le_bytes());
+    }
+    fn bytes(&mut self, v: &[u8]) {
+        self.varint(v.len() as u64);
+        self.buf.extend_from_slice(v);
+    }
+    fn extend_bounds(&mut self, x: f64, y: f64) {
+        match self.bounds.as_mut() {
+            Some(bounds) => bounds.extend(x, y),
+            None => self.bounds = Some(Bounds::from_point(x, y)),
+        }
+    }
+    fn geom_begin(&mut self, geom_type: u8, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.buf
+            .extend_from_slice(&[GEOM_BEGIN, geom_type, u8::from(tagged)]);
+        self.varint(size as u64);
+        self.varint(idx as u64);
+        Ok(())
+    }
+    fn geom_end(&mut self, geom_type: u8, tagged: bool, idx: usize) -> Result<()> {
+        self.buf
+            .extend_from_slice(&[GEOM_END, geom_type, u8::from(tagged)]);
+        self.varint(idx as u64);
+        Ok(())
+    }
+    fn value(&mut self, value: &ColumnValue) {
+        match value {
+            ColumnValue::Byte(v) => self.buf.extend_from_slice(&[0, *v as u8]),
+            ColumnValue::UByte(v) => self.buf.extend_from_slice(&[1, *v]),
+            ColumnValue::Bool(v) => self.buf.extend_from_slice(&[2, u8::from(*v)]),
+            ColumnValue::Short(v) => {
+                self.tag(3);
+                self.buf.extend_from_slice(&v.to_le_bytes());
+            }
+            ColumnValue::UShort(v) => {
+                self.tag(4);
+                self.buf.extend_from_slice(&v.to_le_bytes());
+            }
+            ColumnValue::Int(v) => {
+                self.tag(5);
+                self.buf.extend_from_slice(&v.to_le_bytes());
+            }
+            ColumnValue::UInt(v) => {
+                self.tag(6);
+                self.buf.extend_from_slice(&v.to_le_bytes());
+            }
+            ColumnValue::Long(v) => {
+                self.tag(7);
+                self.buf.extend_from_slice(&v.to_le_bytes());
+            }
+            ColumnValue::ULong(v) => {
+                self.tag(8);
+                self.buf.extend_from_slice(&v.to_le_bytes());
+            }
+            ColumnValue::Float(v) => {
+                self.tag(9);
+                self.buf.extend_from_slice(&v.to_le_bytes());
+            }
+            ColumnValue::Double(v) => {
+                self.tag(10);
+                self.f64(*v);
+            }
+            ColumnValue::String(v) => {
+                self.tag(11);
+                self.bytes(v.as_bytes());
+            }
+            ColumnValue::Json(v) => {
+                self.tag(12);
+                self.bytes(v.as_bytes());
+            }
+            ColumnValue::DateTime(v) => {
+                self.tag(13);
+                self.bytes(v.as_bytes());
+            }
+            ColumnValue::Binary(v) => {
+                self.tag(14);
+                self.bytes(v);
+            }
+            ColumnValue::Uuid(v) => {
+                self.tag(15);
+                self.buf.extend_from_slice(v);
+            }
+            ColumnValue::Decimal(v) => {
+                self.tag(16);
+                self.bytes(v.as_bytes());
+            }
+            ColumnValue::Array(v) => {
+                self.tag(ARRAY);
+                self.varint(v.len() as u64);
+                for value in v.iter() {
+                    self.value(&value.as_column_value());
+                }
+            }
+        }
+    }
+}
+
+impl GeomProcessor for FeatureEncoder {
+    fn dimensions(&self) -> CoordDimensions {
+        self.dims
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.tag(SRID);
+        match srid {
+            Some(srid) => {
+                self.tag(1);
+                self.buf.extend_from_slice(&srid.to_le_bytes());
+            }
+            None => self.tag(0),
+        }
+        Ok(())
+    }
+    fn envelope(&mut self, envelope: &[f64], dims: CoordDimensions, idx: usize) -> Result<()> {
+        self.tag(ENVELOPE);
+        let dims = u8::from(dims.z) | u8::from(dims.m) << 1 | u8::from(dims.t) << 2;
+        self.tag(dims);
+        self.varint(envelope.len() as u64);
+        for v in envelope {
+            self.f64(*v);
+        }
+        self.varint(idx as u64);
+        Ok(())
+    }
+    fn size_hint_total_coords(&mut self, count: usize) -> Result<()> {
+        self.tag(SIZE_HINT);
+        self.varint(count as u64);
+        Ok(())
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.extend_bounds(x, y);
+        self.tag(XY);
+        self.f64(x);
+        self.f64(y);
+        self.varint(idx as u64);
+        Ok(())
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.extend_bounds(x, y);
+        self.tag(COORDINATE);
+        let flags = u8::from(z.is_some())
+            | u8::from(m.is_some()) << 1
+            | u8::from(t.is_some()) << 2
+            | u8::from(tm.is_some()) << 3;
+        self.tag(flags);
+        self.f64(x);
+        self.f64(y);
+        for v in [z, m, t].into_iter().flatten() {
+            self.f64(v);
+        }
+        if let Some(tm) = tm {
+            self.varint(tm);
+        }
+        self.varint(idx as u64);
+        Ok(())
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.tag(EMPTY_POINT);
+        self.varint(idx as u64);
+        Ok(())
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.geom_begin(POINT, true, 0, idx)
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_end(POINT, true, idx)
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_begin(MULTIPOINT, true, size, idx)
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_end(MULTIPOINT, true, idx)
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.geom_begin(LINESTRING, tagged, size, idx)
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.geom_end(LINESTRING, tagged, idx)
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_begin(MULTILINESTRING, true, size, idx)
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_end(MULTILINESTRING, true, idx)
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.geom_begin(POLYGON, tagged, size, idx)
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.geom_end(POLYGON, tagged, idx)
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_begin(MULTIPOLYGON, true, size, idx)
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_end(MULTIPOLYGON, true, idx)
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_begin(GEOMETRYCOLLECTION, true, size, idx)
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_end(GEOMETRYCOLLECTION, true, idx)
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_begin(CIRCULARSTRING, true, size, idx)
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_end(CIRCULARSTRING, true, idx)
+    }
+    fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_begin(COMPOUNDCURVE, true, size, idx)
+    }
+    fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_end(COMPOUNDCURVE, true, idx)
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_begin(CURVEPOLYGON, true, size, idx)
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_end(CURVEPOLYGON, true, idx)
+    }
+    fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_begin(MULTICURVE, true, size, idx)
+    }
+    fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_end(MULTICURVE, true, idx)
+    }
+    fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_begin(MULTISURFACE, true, size, idx)
+    }
+    fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_end(MULTISURFACE, true, idx)
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.geom_begin(TRIANGLE, tagged, size, idx)
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.geom_end(TRIANGLE, tagged, idx)
+    }
+    fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_begin(POLYHEDRALSURFACE, true, size, idx)
+    }
+    fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_end(POLYHEDRALSURFACE, true, idx)
+    }
+    fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_begin(TIN, true, size, idx)
+    }
+    fn tin_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_end(TIN, true, idx)
+    }
+}
+
+impl PropertyProcessor for FeatureEncoder {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        self.tag(PROPERTY);
+        self.varint(idx as u64);
+        self.bytes(name.as_bytes());
+        self.value(value);
+        Ok(false)
+    }
+    fn column_property(
+        &mut self,
+        idx: usize,
+        column: usize,
+        name: &str,
+        value: &ColumnValue,
+    ) -> Result<bool> {
+        self.tag(COLUMN_PROPERTY);
+        self.varint(idx as u64);
+        self.varint(column as u64);
+        self.bytes(name.as_bytes());
+        self.value(value);
+        Ok(false)
+    }
+}
+
+impl FeatureProcessor for FeatureEncoder {
+    fn feature_begin(&mut self, _idx: u64) -> Result<()> {
+        self.buf.clear();
+        self.bounds = None;
+        Ok(())
+    }
+    fn feature_id(&mut self, id: &FeatureId) -> Result<()> {
+        match id {
+            FeatureId::ULong(id) => {
+                self.tag(FEATURE_ID_ULONG);
+                self.varint(*id);
+            }
+            FeatureId::Long(id) => {
+                self.tag(FEATURE_ID_LONG);
+                self.buf.extend_from_slice(&id.to_le_bytes());
+            }
+            FeatureId::String(id) => {
+                self.tag(FEATURE_ID_STRING);
+                self.bytes(id.as_bytes());
+            }
+        }
+        Ok(())
+    }
+    fn properties_begin(&mut self) -> Result<()> {
+        self.tag(PROPERTIES_BEGIN);
+        Ok(())
+    }
+    fn properties_end(&mut self) -> Result<()> {
+        self.tag(PROPERTIES_END);
+        Ok(())
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        self.tag(GEOMETRY_BEGIN);
+        Ok(())
+    }
+    fn geometry_end(&mut self) -> Result<()> {
+        self.tag(GEOMETRY_END);
+        Ok(())
+    }
+}
+
+/// Reader of encoded feature data
+struct Decoder<'a> {
+    data: &'a [u8],
+}
+
+fn invalid() -> GeozeroError {
+    GeozeroError::Feature("invalid encoded feature data".to_string())
+}
+
+impl<'a> Decoder<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        if len > self.data.len() {
+            return Err(invalid());
+        }
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(bytes)
+    }
+    fn array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        self.take(N)?.try_into().map_err(|_| invalid())
+    }
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.array::<1>()?[0])
+    }
+    fn varint(&mut self) -> Result<u64> {
+        let mut v = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.u8()?;
+            v |= u64::from(byte & 0x7F) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(v);
+            }
+        }
+        Err(invalid())
+    }
+    fn usize(&mut self) -> Result<usize> {
+        usize::try_from(self.varint()?).map_err(|_| invalid())
+    }
+    fn f64(&mut self) -> Result<f64> {
+        Ok(f64::from_le_bytes(self.array()?))
+    }
+    fn bytes(&mut self) -> Result<&'a [u8]> {
+        let len = self.usize()?;
+        self.take(len)
+    }
+    fn str(&mut self) -> Result<&'a str> {
+        std::str::from_utf8(self.bytes()?).map_err(|_| invalid())
+    }
+    fn value(&mut self) -> Result<ColumnValue<'a>> {
+        let value = match self.u8()? {
+            0 => ColumnValue::Byte(self.u8()? as i8),
+            1 => ColumnValue::UByte(self.u8()?),
+            2 => ColumnValue::Bool(self.u8()? != 0),
+            3 => ColumnValue::Short(i16::from_le_bytes(self.array()?)),
+            4 => ColumnValue::UShort(u16::from_le_bytes(self.array()?)),
+            5 => ColumnValue::Int(i32::from_le_bytes(self.array()?)),
+            6 => ColumnValue::UInt(u32::from_le_bytes(self.array()?)),
+            7 => ColumnValue::Long(i64::from_le_bytes(self.array()?)),
+            8 => ColumnValue::ULong(u64::from_le_bytes(self.array()?)),
+            9 => ColumnValue::Float(f32::from_le_bytes(self.array()?)),
+            10 => ColumnValue::Double(self.f64()?),
+            11 => ColumnValue::String(self.str()?),
+            12 => ColumnValue::Json(self.str()?),
+            13 => ColumnValue::DateTime(self.str()?),
+            14 => ColumnValue::Binary(self.bytes()?),
+            15 => ColumnValue::Uuid(self.array()?),
+            16 => ColumnValue::Decimal(self.str()?),
+            _ => return Err(invalid()),
+        };
+        Ok(value)
+    }
+    fn owned_array(&mut self) -> Result<Vec<ColumnValueOwned>> {
+        if self.u8()? != ARRAY {
+            return Err(invalid());
+        }
+        let len = self.usize()?;
+        let mut values = Vec::with_capacity(len.min(self.data.len()));
+        for _ in 0..len {
+            if self.data.first() == Some(&ARRAY) {
+                values.push(ColumnValueOwned::Array(self.owned_array()?));
+            } else {
+                values.push((&self.value()?).into());
+            }
+        }
+        Ok(values)
+    }
+}
+
+/// Replay encoded feature into `processor` as feature `idx`
+///
+/// Coordinates are emitted with `coordinate` or `xy` depending on `processor.multi_dim()`.
+pub(crate) fn replay_feature<P: FeatureProcessor>(
+    data: &[u8],
+    idx: u64,
+    processor: &mut P,
+) -> Result<()> {
+    processor.feature_begin(idx)?;
+    replay_events(data, processor)?;
+    processor.feature_end(idx)
+}
+
+/// Replay encoded events into `processor`
+fn replay_events<P: FeatureProcessor>(data: &[u8], processor: &mut P) -> Result<()> {
+    let mut dec = Decoder { data };
+    let mut skip_properties = false;
+    while !dec.data.is_empty() {
+        match dec.u8()? {
+            FEATURE_ID_ULONG => processor.feature_id(&FeatureId::ULong(dec.varint()?))?,
+            FEATURE_ID_LONG => {
+                processor.feature_id(&FeatureId::Long(i64::from_le_bytes(dec.array()?)))?
+            }
+            FEATURE_ID_STRING => processor.feature_id(&FeatureId::String(dec.str()?))?,
+            PROPERTIES_BEGIN => {
+                skip_properties = false;
+                processor.properties_begin()?
+            }
+            PROPERTIES_END => processor.properties_end()?,
+            tag @ (PROPERTY | COLUMN_PROPERTY) => {
+                let idx = dec.usize()?;
+                let column = if tag == COLUMN_PROPERTY {
+                    Some(dec.usize()?)
+                } else {
+                    None
+                };
+                let name = dec.str()?;
+                let array;
+                let value = if dec.data.first() == Some(&ARRAY) {
+                    array = dec.owned_array()?;
+                    ColumnValue::Array(&array)
+                } else {
+                    dec.value()?
+                };
+                if !skip_properties {
+                    skip_properties = match column {
+                        Some(column) => processor.column_property(idx, column, name, &value)?,
+                        None => processor.property(idx, name, &value)?,
+                    };
+                }
+            }
+            GEOMETRY_BEGIN => processor.geometry_begin()?,
+            GEOMETRY_END => processor.geometry_end()?,
+            SRID => {
+                let srid = match dec.u8()? {
+                    0 => None,
+                    _ => Some(i32::from_le_bytes(dec.array()?)),
+                };
+                processor.srid(srid)?
+            }
+            ENVELOPE => {
+                let flags = dec.u8()?;
+                let dims = CoordDimensions {
+                    z: flags & 1 != 0,
+                    m: flags & 2 != 0,
+                    t: flags & 4 != 0,
+                    tm: false,
+                };
+                let len = dec.usize()?;
+                let envelope = (0..len).map(|_| dec.f64()).collect::<Result<Vec<_>>>()?;
+                processor.envelope(&envelope, dims, dec.usize()?)?
+            }
+            SIZE_HINT => processor.size_hint_total_coords(dec.usize()?)?,
+            XY => {
+                let (x, y) = (dec.f64()?, dec.f64()?);
+                let idx = dec.usize()?;
+                if processor.multi_dim() {
+                    processor.coordinate(x, y, None, None, None, None, idx)?
+                } else {
+                    processor.xy(x, y, idx)?
+                }
+            }
+            COORDINATE => {
+                let flags = dec.u8()?;
+                let (x, y) = (dec.f64()?, dec.f64()?);
+                let mut opt = |bit: u8| -> Result<Option<f64>> {
+                    if flags & bit != 0 {
+                        dec.f64().map(Some)
+                    } else {
+                        Ok(None)
+                    }
+                };
+                let (z, m, t) = (opt(1)?, opt(2)?, opt(4)?);
+                let tm = if flags & 8 != 0 {
+                    Some(dec.varint()?)
+                } else {
+                    None
+                };
+                let idx = dec.usize()?;
+                if processor.multi_dim() {
+                    processor.coordinate(x, y, z, m, t, tm, idx)?
+                } else {
+                    processor.xy(x, y, idx)?
+                }
+            }
+            EMPTY_POINT => processor.empty_point(dec.usize()?)?,
+            GEOM_BEGIN => {
+                let geom_type = dec.u8()?;
+                let tagged = dec.u8()? != 0;
+                let size = dec.usize()?;
+                let idx = dec.usize()?;
+                match geom_type {
+                    POINT => processor.point_begin(idx)?,
+                    MULTIPOINT => processor.multipoint_begin(size, idx)?,
+                    LINESTRING => processor.linestring_begin(tagged, size, idx)?,
+                    MULTILINESTRING => processor.multilinestring_begin(size, idx)?,
+                    POLYGON => processor.polygon_begin(tagged, size, idx)?,
+                    MULTIPOLYGON => processor.multipolygon_begin(size, idx)?,
+                    GEOMETRYCOLLECTION => processor.geometrycollection_begin(size, idx)?,
+                    CIRCULARSTRING => processor.circularstring_begin(size, idx)?,
+                    COMPOUNDCURVE => processor.compoundcurve_begin(size, idx)?,
+                    CURVEPOLYGON => processor.curvepolygon_begin(size, idx)?,
+                    MULTICURVE => processor.multicurve_begin(size, idx)?,
+                    MULTISURFACE => processor.multisurface_begin(size, idx)?,
+                    TRIANGLE => processor.triangle_begin(tagged, size, idx)?,
+                    POLYHEDRALSURFACE => processor.polyhedralsurface_begin(size, idx)?,
+                    TIN => processor.tin_begin(size, idx)?,
+                    _ => return Err(invalid()),
+                }
+            }
+            GEOM_END => {
+                let geom_type = dec.u8()?;
+                let tagged = dec.u8()? != 0;
+                let idx = dec.usize()?;
+                match geom_type {
+                    POINT => processor.point_end(idx)?,
+                    MULTIPOINT => processor.multipoint_end(idx)?,
+                    LINESTRING => processor.linestring_end(tagged, idx)?,
+                    MULTILINESTRING => processor.multilinestring_end(idx)?,
+                    POLYGON => processor.polygon_end(tagged, idx)?,
+                    MULTIPOLYGON => processor.multipolygon_end(idx)?,
+                    GEOMETRYCOLLECTION => processor.geometrycollection_end(idx)?,
+                    CIRCULARSTRING => processor.circularstring_end(idx)?,
+                    COMPOUNDCURVE => processor.compoundcurve_end(idx)?,
+                    CURVEPOLYGON => processor.curvepolygon_end(idx)?,
+                    MULTICURVE => processor.multicurve_end(idx)?,
+                    MULTISURFACE => processor.multisurface_end(idx)?,
+                    TRIANGLE => processor.triangle_end(tagged, idx)?,
+                    POLYHEDRALSURFACE => processor.polyhedralsurface_end(idx)?,
+                    TIN => processor.tin_end(idx)?,
+                    _ => return Err(invalid()),
+                }
+            }
+            _ => return Err(invalid()),
+        }
+    }
+    Ok(())
+}
+
+/// Location of a stored blob
+enum Blob {
+    Memory(Vec<u8>),
+    File { offset: u64, len: usize },
+}
+
+/// Temporary file, removed on drop
+struct TempFile {
+    path: PathBuf,
+    file: File,
+    len: u64,
+}
+
+impl TempFile {
+    fn create() -> Result<Self> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let name = format!(
+            "geozero-spool-{}-{}.bin",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        );
+        let path = std::env::temp_dir().join(name);
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(&path)?;
+        Ok(TempFile { path, file, len: 0 })
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Blob storage, keeping blobs in memory up to a size limit and spilling
+/// further blobs into a temporary file.
+pub(crate) struct BlobStore {
+    max_memory: usize,
+    memory: usize,
+    blobs: Vec<Blob>,
+    file: Option<TempFile>,
+}
+
+impl BlobStore {
+    pub fn new(max_memory: usize) -> Self {
+        BlobStore {
+            max_memory,
+            memory: 0,
+            blobs: Vec::new(),
+            file: None,
+        }
+    }
+    pub fn set_max_memory(&mut self, max_memory: usize) {
+        self.max_memory = max_memory;
+    }
+    /// Number of stored blobs
+    pub fn len(&self) -> usize {
+        self.blobs.len()
+    }
+    /// Blobs were spilled to a temporary file
+    pub fn spilled(&self) -> bool {
+        self.file.is_some()
+    }
+    /// Store blob and return its index
+    pub fn push(&mut self, data: &[u8]) -> Result<usize> {
+        if self.memory + data.len() <= self.max_memory {
+            self.memory += data.len();
+            self.blobs.push(Blob::Memory(data.to_vec()));
+        } else {
+            if self.file.is_none() {
+                self.file = Some(TempFile::create()?);
+            }
+            let file = self.file.as_mut().ok_or_else(invalid)?;
+            file.file.seek(SeekFrom::Start(file.len))?;
+            file.file.write_all(data)?;
+            self.blobs.push(Blob::File {
+                offset: file.len,
+                len: data.len(),
+            });
+            file.len += data.len() as u64;
+        }
+        Ok(self.blobs.len() - 1)
+    }
+    /// Read blob with index `idx`, using `buf` for blobs stored in the temporary file
+    pub fn get<'a>(&'a mut self, idx: usize, buf: &'a mut Vec<u8>) -> Result<&'a [u8]> {
+        match self.blobs.get(idx) {
+            Some(Blob::Memory(data)) => Ok(data.as_slice()),
+            Some(Blob::File { offset, len }) => {
+                let file = self.file.as_mut().ok_or_else(invalid)?;
+                buf.resize(*len, 0);
+                file.file.seek(SeekFrom::Start(*offset))?;
+                file.file.read_exact(buf)?;
+                Ok(buf.as_slice())
+            }
+            None => Err(invalid()),
+        }
+    }
+    /// Remove all blobs
+    pub fn clear(&mut self) {
+        self.blobs.clear();
+        self.memory = 0;
+        self.file = None;
+    }
+}
+
+/// Default size of spooled features kept in memory (256 MiB)
+pub const DEFAULT_SPOOL_MEMORY: usize = 256 * 1024 * 1024;
+
+/// Feature buffer, replaying buffered features into any [FeatureProcessor].
+///
+/// Features are stored in a compact internal binary encoding. Encoded features exceeding
+/// the memory limit (default: [DEFAULT_SPOOL_MEMORY]) are written to a temporary file,
+/// which is removed when the spool is dropped. The spool records the dataset name, the schema
+/// and the bounds of each feature, making it usable for operations requiring two passes
+/// over the input, like computing the extent before writing.
+///
+/// Coordinates are recorded with all dimensions by default. Geometry envelopes and
+/// size hints are recorded as well. Only complete features are spooled, geometries
+/// processed outside of features (e.g. with `process_geom`) are not recorded.
+///
+/// # Usage example:
+///
+/// ```
+/// # #[cfg(feature = "with-geojson")]
+/// # {
+/// use geozero::geojson::{GeoJson, GeoJsonWriter};
+/// use geozero::{FeatureSpool, GeozeroDatasource};
+///
+/// let mut geojson = GeoJson(
+///     r#"{"type": "Feature", "properties": {"name": "Bern"}, "geometry": {"type": "Point", "coordinates": [7.4, 46.9]}}"#,
+/// );
+/// let mut spool = FeatureSpool::new();
+/// geojson.process(&mut spool).unwrap();
+/// assert_eq!(spool.len(), 1);
+/// assert_eq!(spool.extent().unwrap().min_x, 7.4);
+///
+/// let mut out = Vec::new();
+/// spool.process(&mut GeoJsonWriter::new(&mut out)).unwrap();
+/// # }
+/// ```
+pub struct FeatureSpool {
+    encoder: FeatureEncoder,
+    store: BlobStore,
+    /// Bounds of spooled features
+    bounds: Vec<Option<Bounds>>,
+    name: Option<String>,
+    crs: Option<Crs>,
+    schema: Option<Vec<ColumnDef>>,
+}
+
+impl Default for FeatureSpool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FeatureSpool {
+    pub fn new() -> Self {
+        let dims = CoordDimensions {
+            z: true,
+            m: true,
+            t: true,
+            tm: true,
+        };
+        FeatureSpool {
+            encoder: FeatureEncoder::new(dims),
+            store: BlobStore::new(DEFAULT_SPOOL_MEMORY),
+            bounds: Vec::new(),
+            name: None,
+            crs: None,
+            schema: None,
+        }
+    }
+    /// Set coordinate dimensions requested from readers
+    pub fn set_dimensions(&mut self, dims: CoordDimensions) {
+        self.encoder.dims = dims;
+    }
+    /// Set size of encoded features in bytes kept in memory
+    pub fn set_max_memory(&mut self, max_memory: usize) {
+        self.store.set_max_memory(max_memory);
+    }
+    /// Number of spooled features
+    pub fn len(&self) -> usize {
+        self.store.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.store.len() == 0
+    }
+    /// Spooled features were written to a temporary file
+    pub fn spilled(&self) -> bool {
+        self.store.spilled()
+    }
+    /// Geometry or feature events received outside of a completed feature
+    pub(crate) fn has_pending_events(&self) -> bool {
+        !self.encoder.data().is_empty()
+    }
+    /// Dataset name passed to `dataset_begin`
+    pub fn dataset_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+    /// CRS passed to `crs`
+    pub fn crs(&self) -> Option<&Crs> {
+        self.crs.as_ref()
+    }
+    /// Schema passed to `schema_begin`
+    pub fn schema(&self) -> Option<&[ColumnDef]> {
+        self.schema.as_deref()
+    }
+    /// XY bounds of feature `idx`, if it has coordinates
+    pub fn feature_bounds(&self, idx: usize) -> Option<Bounds> {
+        self.bounds.get(idx).copied().flatten()
+    }
+    /// XY bounds of all spooled features
+    pub fn extent(&self) -> Option<Bounds> {
+        self.bounds
+            .iter()
+            .flatten()
+            .fold(None, |extent: Option<Bounds>, bounds| {
+                let mut extent = extent.unwrap_or(*bounds);
+                extent.merge(bounds);
+                Some(extent)
+            })
+    }
+    /// Replay feature `idx` into `processor` with feature index `out_idx`
+    pub fn replay_feature<P: FeatureProcessor>(
+        &mut self,
+        idx: usize,
+        out_idx: u64,
+        processor: &mut P,
+    ) -> Result<()> {
+        let mut buf = Vec::new();
+        let data = self.store.get(idx, &mut buf)?;
+        replay_feature(data, out_idx, processor)
+    }
+    /// Remove all spooled features
+    pub fn clear(&mut self) {
+        self.encoder.buf.clear();
+        self.store.clear();
+        self.bounds.clear();
+        self.name = None;
+        self.crs = None;
+        self.schema = None;
+    }
+}
+
+impl GeozeroDatasource for FeatureSpool {
+    /// Replay dataset with all spooled features. The spool can be replayed multiple times.
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        processor.dataset_begin(self.name.as_deref())?;
+        if let Some(crs) = &self.crs {
+            processor.crs(crs)?;
+        }
+        if let Some(schema) = &self.schema {
+            processor.schema_begin(schema)?;
+        }
+        let mut buf = Vec::new();
+        for idx in 0..self.store.len() {
+            let data = self.store.get(idx, &mut buf)?;
+            replay_feature(data, idx as u64, processor)?;
+        }
+        processor.dataset_end()
+    }
+    fn dataset_crs(&self) -> Option<Crs> {
+        self.crs.clone()
+    }
+}
+
+impl GeomProcessor for FeatureSpool {
+    fn dimensions(&self) -> CoordDimensions {
+        self.encoder.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.encoder.multi_dim()
+    }
+    delegate_coordinates!(encoder);
+    delegate_geometry_types!(encoder);
+}
+
+impl PropertyProcessor for FeatureSpool {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue<'_>) -> Result<bool> {
+        self.encoder.property(idx, name, value)
+    }
+    fn column_property(
+        &mut self,
+        idx: usize,
+        column: usize,
+        name: &str,
+        value: &ColumnValue<'_>,
+    ) -> Result<bool> {
+        self.encoder.column_property(idx, column, name, value)
+    }
+}
+
+impl FeatureProcessor for FeatureSpool {
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.name = name.map(str::to_string);
+        Ok(())
+    }
+    fn crs(&mut self, crs: &Crs) -> Result<()> {
+        self.crs = Some(crs.clone());
+        Ok(())
+    }
+    fn schema_begin(&mut self, columns: &[ColumnDef]) -> Result<()> {
+        self.schema = Some(columns.to_vec());
+        Ok(())
+    }
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        self.encoder.feature_begin(idx)
+    }
+    fn feature_id(&mut self, id: &FeatureId) -> Result<()> {
+        self.encoder.feature_id(id)
+    }
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        self.store.push(self.encoder.data())?;
+        self.bounds.push(self.encoder.bounds());
+        self.encoder.buf.clear();
+        Ok(())
+    }
+    fn properties_begin(&mut self) -> Result<()> {
+        self.encoder.properties_begin()
+    }
+    fn properties_end(&mut self) -> Result<()> {
+        self.encoder.properties_end()
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        self.encoder.geometry_begin()
+    }
+    fn geometry_end(&mut self) -> Result<()> {
+        self.encoder.geometry_end()
+    }
+}
+
+/// Buffer of encoded feature events, which can be sent to other threads and replayed.
+///
+/// All dataset and feature events, and geometries processed outside of features, are
+/// recorded in a compact internal binary encoding, which is not stable across versions. This enables pipelines where reading, transforming
+/// and writing run on different threads: a producer records events and passes
+/// the buffer (or batches taken with [FeatureEventBuf::take]) through a channel,
+/// the consumer replays them into its processor in the original order.
+///
+/// Coordinates are recorded with all dimensions by default.
+///
+/// # Usage example:
+///
+/// ```
+/// # #[cfg(feature = "with-geojson")]
+/// # {
+/// use geozero::geojson::{GeoJson, GeoJsonWriter};
+/// use geozero::{FeatureEventBuf, GeozeroDatasource};
+/// use std::sync::mpsc;
+/// use std::thread;
+///
+/// let (tx, rx) = mpsc::channel();
+/// let reader = thread::spawn(move || {
+///     let mut buf = FeatureEventBuf::new();
+///     GeoJson(r#"{"type": "Point", "coordinates": [1, 2]}"#)
+///         .process(&mut buf)
+///         .unwrap();
+///     tx.send(buf).unwrap();
+/// });
+/// let mut out = Vec::new();
+/// let mut writer = GeoJsonWriter::new(&mut out);
+/// for buf in rx {
+///     buf.replay(&mut writer).unwrap();
+/// }
+/// reader.join().unwrap();
+/// assert_eq!(
+///     std::str::from_utf8(&out).unwrap(),
+///     r#"{"type": "Point", "coordinates": [1,2]}"#
+/// );
+/// # }
+/// ```
+pub struct FeatureEventBuf {
+    data: Vec<u8>,
+    /// Events of the current feature, or of geometries outside of features
+    encoder: FeatureEncoder,
+    in_feature: bool,
+    feature_count: usize,
+}
+
+impl Default for FeatureEventBuf {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FeatureEventBuf {
+    pub fn new() -> Self {
+        let dims = CoordDimensions {
+            z: true,
+            m: true,
+            t: true,
+            tm: true,
+        };
+        FeatureEventBuf {
+            data: Vec::new(),
+            encoder: FeatureEncoder::new(dims),
+            in_feature: false,
+            feature_count: 0,
+        }
+    }
+    /// Record pending geometry events outside of features
+    fn flush_geometry(&mut self) {
+        if self.in_feature || self.encoder.buf.is_empty() {
+            return;
+        }
+        let geometry = std::mem::take(&mut self.encoder.buf);
+        self.encoder.bytes(&geometry);
+        self.data.push(GEOMETRY);
+        self.data.extend_from_slice(&self.encoder.buf);
+        self.encoder.buf = geometry;
+        self.encoder.buf.clear();
+    }
+    /// Set coordinate dimensions requested from readers
+    pub fn set_dimensions(&mut self, dims: CoordDimensions) {
+        self.encoder.dims = dims;
+    }
+    /// Number of recorded features
+    pub fn feature_count(&self) -> usize {
+        self.feature_count
+    }
+    /// Encoded size in bytes
+    pub fn len(&self) -> usize {
+        self.data.len() + self.pending_geometry().len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Geometry events outside of features, which are not recorded yet
+    fn pending_geometry(&self) -> &[u8] {
+        if self.in_feature {
+            &[]
+        } else {
+            self.encoder.data()
+        }
+    }
+    /// Take the recorded events, leaving an empty buffer for recording further events.
+    ///
+    /// Should be called between features, e.g. after every `n`th `feature_end`.
+    pub fn take(&mut self) -> FeatureEventBuf {
+        self.flush_geometry();
+        let mut batch = FeatureEventBuf::new();
+        batch.encoder.dims = self.encoder.dims;
+        batch.feature_count = std::mem::take(&mut self.feature_count);
+        batch.data = std::mem::take(&mut self.data);
+        batch
+    }
+    /// Remove all recorded events
+    pub fn clear(&mut self) {
+        self.data.clear();
+        self.encoder.buf.clear();
+        self.in_feature = false;
+        self.feature_count = 0;
+    }
+    /// Replay recorded events into `processor`
+    pub fn replay<P: FeatureProcessor>(&self, processor: &mut P) -> Result<()> {
+        let mut dec = Decoder { data: &self.data };
+        while !dec.data.is_empty() {
+            match dec.u8()? {
+                DATASET_BEGIN => {
+                    let name = match dec.u8()? {
+                        0 => None,
+                        _ => Some(dec.str()?),
+                    };
+                    processor.dataset_begin(name)?
+                }
+                DATASET_END => processor.dataset_end()?,
+                CRS => {
+                    let mut field = || -> Result<Option<String>> {
+                        Ok(match dec.u8()? {
+                            0 => None,
+                            _ => Some(dec.str()?.to_string()),
+                        })
+                    };
+                    let crs = Crs {
+                        authority: field()?,
+                        code: field()?,
+                        wkt: field()?,
+                    };
+                    processor.crs(&crs)?
+                }
+                SCHEMA => {
+                    let len = dec.usize()?;
+                    let mut columns = Vec::with_capacity(len.min(dec.data.len()));
+                    for _ in 0..len {
+                        let name = dec.str()?;
+                        let column_type = *COLUMN_TYPES
+                            .get(usize::from(dec.u8()?))
+                            .ok_or_else(invalid)?;
+                        columns.push(ColumnDef::new(name, column_type));
+                    }
+                    processor.schema_begin(&columns)?
+                }
+                FEATURE => {
+                    let idx = dec.varint()?;
+                    let feature = dec.bytes()?;
+                    replay_feature(feature, idx, processor)?
+                }
+                GEOMETRY => replay_events(dec.bytes()?, processor)?,
+                _ => return Err(invalid()),
+            }
+        }
+        replay_events(self.pending_geometry(), processor)
+    }
+}
+
+impl GeozeroDatasource for FeatureEventBuf {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        self.replay(processor)
+    }
+}
+
+impl GeomProcessor for FeatureEventBuf {
+    fn dimensions(&self) -> CoordDimensions {
+        self.encoder.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.encoder.multi_dim()
+    }
+    delegate_coordinates!(encoder);
+    delegate_geometry_types!(encoder);
+}
+
+impl PropertyProcessor for FeatureEventBuf {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue<'_>) -> Result<bool> {
+        self.encoder.property(idx, name, value)
+    }
+    fn column_property(
+        &mut self,
+        idx: usize,
+        column: usize,
+        name: &str,
+        value: &ColumnValue<'_>,
+    ) -> Result<bool> {
+        self.encoder.column_property(idx, column, name, value)
+    }
+}
+
+impl FeatureProcessor for FeatureEventBuf {
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.flush_geometry();
+        self.data.push(DATASET_BEGIN);
+        match name {
+            Some(name) => {
+                self.data.push(1);
+                self.encoder.buf.clear();
+                self.encoder.bytes(name.as_bytes());
+                self.data.extend_from_slice(&self.encoder.buf);
+                self.encoder.buf.clear();
+            }
+            None => self.data.push(0),
+        }
+        Ok(())
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        self.flush_geometry();
+        self.data.push(DATASET_END);
+        Ok(())
+    }
+    fn crs(&mut self, crs: &Crs) -> Result<()> {
+        self.flush_geometry();
+        self.data.push(CRS);
+        self.encoder.buf.clear();
+        for field in [&crs.authority, &crs.code, &crs.wkt] {
+            match field {
+                Some(value) => {
+                    self.encoder.tag(1);
+                    self.encoder.bytes(value.as_bytes());
+                }
+                None => self.encoder.tag(0),
+            }
+        }
+        self.data.extend_from_slice(&self.encoder.buf);
+        self.encoder.buf.clear();
+        Ok(())
+    }
+    fn schema_begin(&mut self, columns: &[ColumnDef]) -> Result<()> {
+        self.flush_geometry();
+        self.data.push(SCHEMA);
+        self.encoder.buf.clear();
+        self.encoder.varint(columns.len() as u64);
+        for column in columns {
+            self.encoder.bytes(column.name.as_bytes());
+            self.encoder.tag(column.column_type as u8);
+        }
+        self.data.extend_from_slice(&self.encoder.buf);
+        self.encoder.buf.clear();
+        Ok(())
+    }
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        self.flush_geometry();
+        self.in_feature = true;
+        self.encoder.feature_begin(idx)
+    }
+    fn feature_id(&mut self, id: &FeatureId) -> Result<()> {
+        self.encoder.feature_id(id)
+    }
+    fn feature_end(&mut self, idx: u64) -> Result<()> {
+        self.in_feature = false;
+        let feature = std::mem::take(&mut self.encoder.buf);
+        self.encoder.varint(idx);
+        self.encoder.bytes(&feature);
+        self.data.push(FEATURE);
+        self.data.extend_from_slice(&self.encoder.buf);
+        self.encoder.buf = feature;
+        self.encoder.buf.clear();
+        self.feature_count += 1;
+        Ok(())
+    }
+    fn properties_begin(&mut self) -> Result<()> {
+        self.encoder.properties_begin()
+    }
+    fn properties_end(&mut self) -> Result<()> {
+        self.encoder.properties_end()
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        self.encoder.geometry_begin()
+    }
+    fn geometry_end(&mut self) -> Result<()> {
+        self.encoder.geometry_end()
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::{GeoJson, GeoJsonWriter};
+
+    #[test]
+    fn roundtrip_with_spilling() -> Result<()> {
+        let geojson = r#"{"type": "FeatureCollection", "features": [{"type": "Feature", "id": "a", "properties": {"flag": true, "n": -3, "name": "Bern", "tags": [1, 2], "x": 1.5}, "geometry": {"type": "Point", "coordinates": [7.4, 46.9]}}, {"type": "Feature", "properties": {"n": 4}, "geometry": {"type": "Polygon", "coordinates": [[[0, 0], [1, 0], [1, 1], [0, 0]]]}}, {"type": "Feature", "properties": {"n": 5}, "geometry": {"type": "LineString", "coordinates": [[0, 0, 1], [2, 2, 3]]}}]}"#;
+        let mut spool = FeatureSpool::new();
+        spool.set_max_memory(10);
+        GeoJson(geojson).process(&mut spool)?;
+        assert_eq!(spool.len(), 3);
+        assert!(spool.spilled());
+        assert_eq!(
+            spool.feature_bounds(1),
+            Some(Bounds {
+                min_x: 0.0,
+                min_y: 0.0,
+                max_x: 1.0,
+                max_y: 1.0
+            })
+        );
+        assert_eq!(
+            spool.extent(),
+            Some(Bounds {
+                min_x: 0.0,
+                min_y: 0.0,
+                max_x: 7.4,
+                max_y: 46.9
+            })
+        );
+
+        let mut expected = Vec::new();
+        GeoJson(geojson).process(&mut GeoJsonWriter::new(&mut expected))?;
+        // Replay twice
+        for _ in 0..2 {
+            let mut out = Vec::new();
+            spool.process(&mut GeoJsonWriter::new(&mut out))?;
+            assert_eq!(
+                String::from_utf8(out).unwrap(),
+                String::from_utf8(expected.clone()).unwrap()
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn event_buf_across_threads() -> Result<()> {
+        let geojson = r#"{"type": "FeatureCollection", "features": [{"type": "Feature", "id": 7, "properties": {"name": "Bern"}, "geometry": {"type": "Point", "coordinates": [7.4, 46.9]}}, {"type": "Feature", "properties": {"n": 4}, "geometry": {"type": "LineString", "coordinates": [[0, 0, 1], [2, 2, 3]]}}]}"#;
+        let mut expected = Vec::new();
+        GeoJson(geojson).process(&mut GeoJsonWriter::new(&mut expected))?;
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let reader = std::thread::spawn(move || {
+            let mut buf = FeatureEventBuf::new();
+            GeoJson(geojson).process(&mut buf)?;
+            assert_eq!(buf.feature_count(), 2);
+            tx.send(buf.take()).unwrap();
+            assert!(buf.is_empty());
+            Ok::<_, GeozeroError>(())
+        });
+        let mut out = Vec::new();
+        let mut writer = GeoJsonWriter::new(&mut out);
+        for buf in rx {
+            buf.replay(&mut writer)?;
+        }
+        reader.join().unwrap()?;
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            String::from_utf8(expected).unwrap()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn event_buf_geometries() -> Result<()> {
+        let geojson = r#"{"type": "LineString", "coordinates": [[1, 2], [3, 4]]}"#;
+        let mut expected = Vec::new();
+        GeoJson(geojson).process(&mut GeoJsonWriter::new(&mut expected))?;
+
+        let mut buf = FeatureEventBuf::new();
+        GeoJson(geojson).process(&mut buf)?;
+        assert_eq!(buf.feature_count(), 0);
+        assert!(!buf.is_empty());
+        let mut out = Vec::new();
+        buf.replay(&mut GeoJsonWriter::new(&mut out))?;
+        assert_eq!(out, expected);
+
+        let batch = buf.take();
+        assert!(buf.is_empty());
+        let mut out = Vec::new();
+        batch.replay(&mut GeoJsonWriter::new(&mut out))?;
+        assert_eq!(out, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn event_buf_schema() -> Result<()> {
+        let mut buf = FeatureEventBuf::new();
+        buf.dataset_begin(Some("cities"))?;
+        buf.crs(&Crs::epsg(2056))?;
+        buf.schema_begin(&[
+            ColumnDef::new("name", ColumnType::String),
+            ColumnDef::new("population", ColumnType::ULong),
+        ])?;
+        buf.dataset_end()?;
+        let mut spool = FeatureSpool::new();
+        buf.replay(&mut spool)?;
+        assert_eq!(spool.dataset_name(), Some("cities"));
+        assert_eq!(spool.crs(), Some(&Crs::epsg(2056)));
+        assert_eq!(
+            spool.schema().unwrap()[1],
+            ColumnDef::new("population", ColumnType::ULong)
+        );
+        Ok(())
+    }
+}
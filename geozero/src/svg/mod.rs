@@ -1,5 +1,7 @@
 //! SVG conversions.
+mod reader;
 mod writer;
+pub use reader::{read_svg, SvgReader};
 pub use writer::SvgWriter;
 
 /// SVG String.
@@ -34,7 +36,7 @@ pub(crate) mod conversion {
     pub trait ToSvg {
         /// Convert to SVG geometry.
         fn to_svg(&self) -> Result<String>;
-        /// Convert to SVG document.
+        /// Convert to SVG document with viewBox set to the geometry bounds.
         fn to_svg_document(&self) -> Result<String>;
     }
 
@@ -50,7 +52,9 @@ pub(crate) mod conversion {
         fn to_svg_document(&self) -> Result<String> {
             let mut svg_data: Vec<u8> = Vec::new();
             let mut svg = SvgWriter::new(&mut svg_data, false);
-            // svg.set_dimensions(bbox.get(0), bbox.get(1), bbox.get(2), bbox.get(3), 800, 400);
+            if let Some([xmin, ymin, xmax, ymax]) = self.bounds()? {
+                svg.set_view_box(xmin, ymin, xmax, ymax);
+            }
             svg.dataset_begin(None)?;
             svg.feature_begin(0)?;
             self.process_geom(&mut svg)?;
@@ -98,4 +102,19 @@ mod wkb {
             Ok(SvgString(svg))
         }
     }
+
+    #[cfg(feature = "with-postgis-postgres")]
+    mod postgis_postgres {
+        use crate::svg::SvgString;
+
+        crate::impl_postgres_postgis_decode!(SvgString);
+    }
+
+    #[cfg(feature = "with-postgis-sqlx")]
+    mod postgis_sqlx {
+        use crate::svg::SvgString;
+
+        crate::impl_sqlx_postgis_type_info!(SvgString);
+        crate::impl_sqlx_postgis_decode!(SvgString);
+    }
 }
@@ -0,0 +1,547 @@
+use crate::error::{GeozeroError, Result};
+use crate::svg::SvgString;
+use crate::{ColumnValue, FeatureProcessor, GeomProcessor, GeozeroDatasource, GeozeroGeometry};
+use std::io::Read;
+
+/// Maximal number of segments of a flattened curve
+const MAX_CURVE_SEGMENTS: usize = 1024;
+
+/// SVG reader.
+///
+/// Reads `<path>`, `<polygon>` and `<polyline>` elements as features with their `id`
+/// attribute as property. Path data supports the `M`, `L`, `H`, `V`, `C` and `Z` commands,
+/// cubic Bézier curves are flattened into line segments.
+///
+/// Paths consisting of closed subpaths are read as polygon, with the first subpath
+/// as exterior ring. Paths with only single coordinate subpaths are read as (multi)points,
+/// all other paths as (multi)linestrings.
+/// Transformations, styles and other elements are ignored.
+pub struct SvgReader<R: Read> {
+    reader: R,
+    opts: SvgOptions,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct SvgOptions {
+    tolerance: f64,
+    invert_y: bool,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        SvgOptions {
+            tolerance: 0.1,
+            invert_y: false,
+        }
+    }
+}
+
+impl<R: Read> SvgReader<R> {
+    pub fn new(reader: R) -> Self {
+        SvgReader {
+            reader,
+            opts: SvgOptions::default(),
+        }
+    }
+    /// Maximal distance between curves and their flattened line segments (default: 0.1)
+    pub fn set_tolerance(&mut self, tolerance: f64) {
+        self.opts.tolerance = tolerance;
+    }
+    /// Negate y coordinates, as [SvgWriter](crate::svg::SvgWriter) does with `invert_y`
+    pub fn set_invert_y(&mut self, invert_y: bool) {
+        self.opts.invert_y = invert_y;
+    }
+}
+
+impl<R: Read> GeozeroDatasource for SvgReader<R> {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        let mut svg = String::new();
+        self.reader.read_to_string(&mut svg)?;
+        let shapes = parse_svg(&svg, &self.opts)?;
+        processor.dataset_begin(None)?;
+        for (idx, shape) in shapes.iter().enumerate() {
+            processor.feature_begin(idx as u64)?;
+            if let Some(id) = shape.id {
+                processor.properties_begin()?;
+                processor.property(0, "id", &ColumnValue::String(id))?;
+                processor.properties_end()?;
+            }
+            processor.geometry_begin()?;
+            process_shape(shape, self.opts.invert_y, 0, processor)?;
+            processor.geometry_end()?;
+            processor.feature_end(idx as u64)?;
+        }
+        processor.dataset_end()
+    }
+}
+
+impl GeozeroGeometry for SvgString {
+    fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
+        process_svg_geom(&self.0, &SvgOptions::default(), processor)
+    }
+}
+
+/// Read SVG shapes as geometry collection.
+pub fn read_svg<R: Read, P: GeomProcessor>(reader: &mut R, processor: &mut P) -> Result<()> {
+    let mut svg = String::new();
+    reader.read_to_string(&mut svg)?;
+    process_svg_geom(&svg, &SvgOptions::default(), processor)
+}
+
+fn process_svg_geom<P: GeomProcessor>(
+    svg: &str,
+    opts: &SvgOptions,
+    processor: &mut P,
+) -> Result<()> {
+    let shapes = parse_svg(svg, opts)?;
+    processor.geometrycollection_begin(shapes.len(), 0)?;
+    for (idx, shape) in shapes.iter().enumerate() {
+        process_shape(shape, opts.invert_y, idx, processor)?;
+    }
+    processor.geometrycollection_end(0)
+}
+
+/// SVG element with its subpaths
+struct Shape<'a> {
+    id: Option<&'a str>,
+    subpaths: Vec<Subpath>,
+}
+
+#[derive(Default)]
+struct Subpath {
+    coords: Vec<(f64, f64)>,
+    closed: bool,
+}
+
+fn svg_error(msg: &str) -> GeozeroError {
+    GeozeroError::Geometry(msg.to_string())
+}
+
+fn parse_svg<'a>(svg: &'a str, opts: &SvgOptions) -> Result<Vec<Shape<'a>>> {
+    let mut shapes = Vec::new();
+    let mut rest = svg;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start + 1..];
+        if let Some(comment) = rest.strip_prefix("!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        if let Some(cdata) = rest.strip_prefix("![CDATA[") {
+            rest = cdata.find("]]>").map_or("", |end| &cdata[end + 3..]);
+            continue;
+        }
+        let end = tag_end(rest).ok_or_else(|| svg_error("unterminated SVG tag"))?;
+        let tag = &rest[..end];
+        rest = &rest[end + 1..];
+        let name_end = tag
+            .find(|c: char| c.is_whitespace() || c == '/')
+            .unwrap_or(tag.len());
+        let name = tag[..name_end].rsplit(':').next().unwrap_or_default();
+        let attrs = &tag[name_end..];
+        let subpaths = match name {
+            "path" => parse_path(attribute(attrs, "d").unwrap_or_default(), opts.tolerance)?,
+            "polygon" => parse_points(attribute(attrs, "points").unwrap_or_default(), true)?,
+            "polyline" => parse_points(attribute(attrs, "points").unwrap_or_default(), false)?,
+            _ => continue,
+        };
+        if !subpaths.is_empty() {
+            shapes.push(Shape {
+                id: attribute(attrs, "id"),
+                subpaths,
+            });
+        }
+    }
+    Ok(shapes)
+}
+
+/// Position of the closing `>`, skipping quoted attribute values
+fn tag_end(tag: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in tag.char_indices() {
+        match (quote, c) {
+            (None, '>') => return Some(i),
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), c) if q == c => quote = None,
+            _ => {}
+        }
+    }
+    None
+}
+
+fn attribute<'a>(attrs: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = attrs;
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == '/');
+        let eq = rest.find('=')?;
+        let key = rest[..eq].trim();
+        let value = rest[eq + 1..].trim_start();
+        let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+        let value = &value[1..];
+        let end = value.find(quote)?;
+        if key == name {
+            return Some(&value[..end]);
+        }
+        rest = &value[end + 1..];
+    }
+}
+
+/// Tokenizer for path data and point lists
+struct PathData<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> PathData<'a> {
+    fn new(data: &'a str) -> Self {
+        PathData {
+            data: data.as_bytes(),
+            pos: 0,
+        }
+    }
+    fn peek(&mut self) -> Option<u8> {
+        while self.pos < self.data.len()
+            && (self.data[self.pos].is_ascii_whitespace() || self.data[self.pos] == b',')
+        {
+            self.pos += 1;
+        }
+        self.data.get(self.pos).copied()
+    }
+    fn command(&mut self) -> Result<Option<u8>> {
+        match self.peek() {
+            None => Ok(None),
+            Some(c) if c.is_ascii_alphabetic() => {
+                self.pos += 1;
+                Ok(Some(c))
+            }
+            Some(_) => Err(svg_error("expected SVG path command")),
+        }
+    }
+    fn has_number(&mut self) -> bool {
+        matches!(self.peek(), Some(c) if c.is_ascii_digit() || matches!(c, b'.' | b'-' | b'+'))
+    }
+    fn number(&mut self) -> Result<f64> {
+        if !self.has_number() {
+            return Err(svg_error("expected number in SVG data"));
+        }
+        let start = self.pos;
+        if matches!(self.data[self.pos], b'-' | b'+') {
+            self.pos += 1;
+        }
+        self.skip_digits();
+        if self.data.get(self.pos) == Some(&b'.') {
+            self.pos += 1;
+            self.skip_digits();
+        }
+        if matches!(self.data.get(self.pos), Some(b'e' | b'E'))
+            && matches!(self.data.get(self.pos + 1), Some(c) if c.is_ascii_digit() || matches!(c, b'-' | b'+'))
+        {
+            self.pos += 2;
+            self.skip_digits();
+        }
+        std::str::from_utf8(&self.data[start..self.pos])
+            .ok()
+            .and_then(|num| num.parse().ok())
+            .ok_or_else(|| svg_error("invalid number in SVG data"))
+    }
+    fn skip_digits(&mut self) {
+        while self.data.get(self.pos).is_some_and(u8::is_ascii_digit) {
+            self.pos += 1;
+        }
+    }
+    fn point(&mut self) -> Result<(f64, f64)> {
+        Ok((self.number()?, self.number()?))
+    }
+}
+
+/// Collects subpaths of path data
+#[derive(Default)]
+struct PathBuilder {
+    subpaths: Vec<Subpath>,
+    current: Subpath,
+    pos: (f64, f64),
+    start: (f64, f64),
+}
+
+impl PathBuilder {
+    fn move_to(&mut self, p: (f64, f64)) {
+        self.finish_subpath();
+        self.current.coords.push(p);
+        self.pos = p;
+        self.start = p;
+    }
+    fn line_to(&mut self, p: (f64, f64)) {
+        if self.current.coords.is_empty() {
+            // Drawing after `Z` starts a new subpath at the previous start point
+            self.current.coords.push(self.pos);
+        }
+        self.current.coords.push(p);
+        self.pos = p;
+    }
+    fn cubic_to(&mut self, c1: (f64, f64), c2: (f64, f64), p: (f64, f64), tolerance: f64) {
+        let p0 = self.pos;
+        let dd = |a: (f64, f64), b: (f64, f64), c: (f64, f64)| {
+            (a.0 - 2.0 * b.0 + c.0).hypot(a.1 - 2.0 * b.1 + c.1)
+        };
+        let dd_max = dd(p0, c1, c2).max(dd(c1, c2, p));
+        let n = ((0.75 * dd_max / tolerance).sqrt().ceil() as usize).clamp(1, MAX_CURVE_SEGMENTS);
+        for i in 1..=n {
+            let t = i as f64 / n as f64;
+            let mt = 1.0 - t;
+            let (a, b, c, d) = (mt * mt * mt, 3.0 * mt * mt * t, 3.0 * mt * t * t, t * t * t);
+            self.line_to((
+                a * p0.0 + b * c1.0 + c * c2.0 + d * p.0,
+                a * p0.1 + b * c1.1 + c * c2.1 + d * p.1,
+            ));
+        }
+    }
+    fn close(&mut self) {
+        if let (Some(first), Some(last)) = (self.current.coords.first(), self.current.coords.last())
+        {
+            if first != last {
+                self.current.coords.push(*first);
+            }
+            self.current.closed = true;
+            self.finish_subpath();
+        }
+        self.pos = self.start;
+    }
+    fn finish_subpath(&mut self) {
+        if !self.current.coords.is_empty() {
+            self.subpaths.push(std::mem::take(&mut self.current));
+        }
+    }
+}
+
+fn parse_path(d: &str, tolerance: f64) -> Result<Vec<Subpath>> {
+    let mut data = PathData::new(d);
+    let mut path = PathBuilder::default();
+    while let Some(cmd) = data.command()? {
+        let relative = cmd.is_ascii_lowercase();
+        let abs = |path: &PathBuilder, (x, y): (f64, f64)| {
+            if relative {
+                (path.pos.0 + x, path.pos.1 + y)
+            } else {
+                (x, y)
+            }
+        };
+        match cmd.to_ascii_uppercase() {
+            b'Z' => path.close(),
+            b'M' => {
+                let p = abs(&path, data.point()?);
+                path.move_to(p);
+                // Additional coordinate pairs are implicit lineto commands
+                while data.has_number() {
+                    let p = abs(&path, data.point()?);
+                    path.line_to(p);
+                }
+            }
+            b'L' => loop {
+                let p = abs(&path, data.point()?);
+                path.line_to(p);
+                if !data.has_number() {
+                    break;
+                }
+            },
+            b'H' => loop {
+                let x = data.number()?;
+                let x = if relative { path.pos.0 + x } else { x };
+                path.line_to((x, path.pos.1));
+                if !data.has_number() {
+                    break;
+                }
+            },
+            b'V' => loop {
+                let y = data.number()?;
+                let y = if relative { path.pos.1 + y } else { y };
+                path.line_to((path.pos.0, y));
+                if !data.has_number() {
+                    break;
+                }
+            },
+            b'C' => loop {
+                let c1 = abs(&path, data.point()?);
+                let c2 = abs(&path, data.point()?);
+                let p = abs(&path, data.point()?);
+                path.cubic_to(c1, c2, p, tolerance);
+                if !data.has_number() {
+                    break;
+                }
+            },
+            _ => {
+                return Err(GeozeroError::Geometry(format!(
+                    "unsupported SVG path command `{}`",
+                    cmd as char
+                )))
+            }
+        }
+    }
+    path.finish_subpath();
+    Ok(path.subpaths)
+}
+
+fn parse_points(points: &str, closed: bool) -> Result<Vec<Subpath>> {
+    let mut data = PathData::new(points);
+    let mut path = PathBuilder::default();
+    while data.peek().is_some() {
+        let p = data.point()?;
+        if path.current.coords.is_empty() {
+            path.move_to(p);
+        } else {
+            path.line_to(p);
+        }
+    }
+    if closed {
+        path.close();
+    }
+    path.finish_subpath();
+    Ok(path.subpaths)
+}
+
+fn process_shape<P: GeomProcessor>(
+    shape: &Shape,
+    invert_y: bool,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    let subpaths = &shape.subpaths;
+    if subpaths.iter().all(|s| s.coords.len() == 1) {
+        if let [point] = subpaths.as_slice() {
+            processor.point_begin(idx)?;
+            process_coords(&point.coords, invert_y, processor)?;
+            processor.point_end(idx)
+        } else {
+            processor.multipoint_begin(subpaths.len(), idx)?;
+            for (i, point) in subpaths.iter().enumerate() {
+                let (x, y) = point.coords[0];
+                processor.xy(x, if invert_y { -y } else { y }, i)?;
+            }
+            processor.multipoint_end(idx)
+        }
+    } else if subpaths.iter().all(|s| s.closed) {
+        processor.polygon_begin(true, subpaths.len(), idx)?;
+        for (i, ring) in subpaths.iter().enumerate() {
+            process_linestring(ring, false, invert_y, i, processor)?;
+        }
+        processor.polygon_end(true, idx)
+    } else if let [line] = subpaths.as_slice() {
+        process_linestring(line, true, invert_y, idx, processor)
+    } else {
+        processor.multilinestring_begin(subpaths.len(), idx)?;
+        for (i, line) in subpaths.iter().enumerate() {
+            process_linestring(line, false, invert_y, i, processor)?;
+        }
+        processor.multilinestring_end(idx)
+    }
+}
+
+fn process_linestring<P: GeomProcessor>(
+    line: &Subpath,
+    tagged: bool,
+    invert_y: bool,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    processor.linestring_begin(tagged, line.coords.len(), idx)?;
+    process_coords(&line.coords, invert_y, processor)?;
+    processor.linestring_end(tagged, idx)
+}
+
+fn process_coords<P: GeomProcessor>(
+    coords: &[(f64, f64)],
+    invert_y: bool,
+    processor: &mut P,
+) -> Result<()> {
+    for (i, (x, y)) in coords.iter().enumerate() {
+        processor.xy(*x, if invert_y { -y } else { *y }, i)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use super::*;
+    use crate::{ToSvg, ToWkt};
+
+    #[test]
+    fn path_data() -> Result<()> {
+        let svg = SvgString(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+<!-- <path d="M 0 0"/> -->
+<path id="square" d="M10,10 L20,10 l0 10 H10 z"/>
+<polyline points="0,0 1,1 2,0"/>
+<path d='M0 0 5 0 M 10 0 v5'/>
+<polygon points="0 0 4 0 0 4"/>
+<path d="M1-2Z m1.5.5z"/>
+</svg>"#
+                .to_string(),
+        );
+        assert_eq!(
+            svg.to_wkt()?,
+            "GEOMETRYCOLLECTION(POLYGON((10 10,20 10,20 20,10 20,10 10)),LINESTRING(0 0,1 1,2 0),MULTILINESTRING((0 0,5 0),(10 0,10 5)),POLYGON((0 0,4 0,0 4,0 0)),MULTIPOINT(1 -2,2.5 -1.5))"
+        );
+
+        assert!(SvgString(r#"<path d="M0 0 A 1 1 0 0 0 2 2"/>"#.to_string())
+            .to_wkt()
+            .is_err());
+        assert!(SvgString(r#"<path d="M0 0 L 1"/>"#.to_string())
+            .to_wkt()
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn flatten_curve() -> Result<()> {
+        let svg = r#"<path d="M0,0 C0,100 100,100 100,0"/>"#;
+        let coords = |tolerance: f64| -> Result<Vec<(f64, f64)>> {
+            let shapes = parse_svg(
+                svg,
+                &SvgOptions {
+                    tolerance,
+                    invert_y: false,
+                },
+            )?;
+            Ok(shapes[0].subpaths[0].coords.clone())
+        };
+        let coarse = coords(10.0)?;
+        let fine = coords(0.1)?;
+        assert!(coarse.len() > 2);
+        assert!(fine.len() > coarse.len());
+        assert_eq!(fine.first(), Some(&(0.0, 0.0)));
+        assert_eq!(fine.last(), Some(&(100.0, 0.0)));
+        let top = fine.iter().map(|c| c.1).fold(f64::MIN, f64::max);
+        assert!((top - 75.0).abs() < 0.1);
+        Ok(())
+    }
+
+    #[test]
+    fn svg_writer_roundtrip() -> Result<()> {
+        for wkt in ["POINT(1 2)", "POLYGON((0 0,1 0,1 1,0 0))"] {
+            let svg = crate::wkt::WktStr(wkt).to_svg()?;
+            assert_eq!(
+                SvgString(svg).to_wkt()?,
+                format!("GEOMETRYCOLLECTION({wkt})")
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "with-geojson")]
+    fn read_features() -> Result<()> {
+        use crate::ProcessToJson;
+
+        let svg = r#"<svg><path id="line" d="M0 0 L1 1"/><circle r="1"/></svg>"#;
+        let mut reader = SvgReader::new(svg.as_bytes());
+        reader.set_invert_y(true);
+        let json: serde_json::Value = serde_json::from_str(&reader.to_json()?).unwrap();
+        let features = json["features"].as_array().unwrap();
+        assert_eq!(features.len(), 1);
+        assert_eq!(features[0]["properties"]["id"], "line");
+        assert_eq!(
+            features[0]["geometry"]["coordinates"],
+            serde_json::json!([[0, 0], [1, -1]])
+        );
+        Ok(())
+    }
+}
@@ -1,3 +1,4 @@
+use crate::bounds::Bounds;
 use crate::error::Result;
 use crate::{FeatureProcessor, GeomProcessor, PropertyProcessor};
 use std::io::Write;
@@ -8,6 +9,8 @@ pub struct SvgWriter<W: Write> {
     invert_y: bool,
     view_box: Option<(f64, f64, f64, f64)>,
     size: Option<(u32, u32)>,
+    /// Data extent (xmin, ymin, xmax, ymax) mapped into pixel space
+    pixel_extent: Option<(f64, f64, f64, f64)>,
 }
 
 impl<W: Write> SvgWriter<W> {
@@ -17,6 +20,7 @@ impl<W: Write> SvgWriter<W> {
             invert_y,
             view_box: None,
             size: None,
+            pixel_extent: None,
         }
     }
     pub fn set_dimensions(
@@ -34,6 +38,67 @@ impl<W: Write> SvgWriter<W> {
             Some((xmin, ymin, xmax, ymax))
         };
         self.size = Some((width, height));
+        self.pixel_extent = None;
+    }
+    /// Set viewBox without fixing the output size.
+    pub fn set_view_box(&mut self, xmin: f64, ymin: f64, xmax: f64, ymax: f64) {
+        self.view_box = if self.invert_y {
+            Some((xmin, -ymax, xmax, -ymin))
+        } else {
+            Some((xmin, ymin, xmax, ymax))
+        };
+        self.pixel_extent = None;
+    }
+    /// Set output size and viewBox from the bounds of the data.
+    pub fn set_dimensions_from_bounds(&mut self, bounds: &Bounds, width: u32, height: u32) {
+        self.set_dimensions(
+            bounds.min_x,
+            bounds.min_y,
+            bounds.max_x,
+            bounds.max_y,
+            width,
+            height,
+        );
+    }
+    /// Scale coordinates within the given extent into pixel space.
+    ///
+    /// The viewBox is set to `0 0 width height` and coordinates are mapped into it,
+    /// with `ymax` at the top of the image when `invert_y` is set.
+    pub fn set_pixel_dimensions(
+        &mut self,
+        xmin: f64,
+        ymin: f64,
+        xmax: f64,
+        ymax: f64,
+        width: u32,
+        height: u32,
+    ) {
+        self.view_box = Some((0.0, 0.0, width as f64, height as f64));
+        self.size = Some((width, height));
+        self.pixel_extent = Some((xmin, ymin, xmax, ymax));
+    }
+    fn transform(&self, x: f64, y: f64) -> (f64, f64) {
+        match (self.pixel_extent, self.size) {
+            (Some((xmin, ymin, xmax, ymax)), Some((width, height))) => {
+                let sx = if xmax > xmin {
+                    width as f64 / (xmax - xmin)
+                } else {
+                    1.0
+                };
+                let sy = if ymax > ymin {
+                    height as f64 / (ymax - ymin)
+                } else {
+                    1.0
+                };
+                let py = if self.invert_y {
+                    (ymax - y) * sy
+                } else {
+                    (y - ymin) * sy
+                };
+                ((x - xmin) * sx, py)
+            }
+            _ => (x, if self.invert_y { -y } else { y }),
+        }
     }
 }
 
@@ -78,7 +143,7 @@ impl<W: Write> FeatureProcessor for SvgWriter<W> {
 
 impl<W: Write> GeomProcessor for SvgWriter<W> {
     fn xy(&mut self, x: f64, y: f64, _idx: usize) -> Result<()> {
-        let y = if self.invert_y { -y } else { y };
+        let (x, y) = self.transform(x, y);
         self.out.write_all(format!("{x} {y} ").as_bytes())?;
         Ok(())
     }
@@ -278,6 +343,35 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn pixel_dimensions() -> Result<()> {
+        let geojson = r#"{"type": "LineString", "coordinates": [[10,40],[30,20],[20,30]]}"#;
+        let mut out: Vec<u8> = Vec::new();
+        let mut svg = SvgWriter::new(&mut out, true);
+        svg.set_pixel_dimensions(10.0, 20.0, 30.0, 40.0, 200, 100);
+        svg.dataset_begin(None)?;
+        read_geojson(geojson.as_bytes(), &mut svg)?;
+        svg.dataset_end()?;
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            r#"<?xml version="1.0"?>
+<svg xmlns="http://www.w3.org/2000/svg" version="1.2" baseProfile="tiny" width="200" height="100" viewBox="0 0 200 100" stroke-linecap="round" stroke-linejoin="round">
+<g id=""><path d="0 0 200 100 100 50 "/>
+</g>
+</svg>"#
+        );
+
+        let mut out: Vec<u8> = Vec::new();
+        let mut svg = SvgWriter::new(&mut out, false);
+        svg.set_pixel_dimensions(10.0, 20.0, 30.0, 40.0, 200, 100);
+        read_geojson(geojson.as_bytes(), &mut svg)?;
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            r#"<path d="0 100 200 0 100 50 "/>"#
+        );
+        Ok(())
+    }
+
     #[test]
     fn conversions() {
         let geom: geo_types::Geometry<f64> = polygon![
@@ -296,7 +390,7 @@ mod test {
         assert_eq!(
             &geom.to_svg_document().unwrap(),
             r#"<?xml version="1.0"?>
-<svg xmlns="http://www.w3.org/2000/svg" version="1.2" baseProfile="tiny" stroke-linecap="round" stroke-linejoin="round">
+<svg xmlns="http://www.w3.org/2000/svg" version="1.2" baseProfile="tiny" viewBox="123 10 177 240" stroke-linecap="round" stroke-linejoin="round">
 <g id="">
 <path d="M 220 10 300 210 170 250 123 234 220 10 Z "/>
 </g>
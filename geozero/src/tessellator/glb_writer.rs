@@ -0,0 +1,242 @@
+use crate::error::Result;
+use crate::tessellator::VertexOutput;
+use serde_json::{json, Value};
+use std::io::Write;
+
+const GLB_MAGIC: u32 = 0x4654_6C67; // "glTF"
+const CHUNK_JSON: u32 = 0x4E4F_534A;
+const CHUNK_BIN: u32 = 0x004E_4942;
+const ARRAY_BUFFER: u32 = 34962;
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+const FLOAT: u32 = 5126;
+const UNSIGNED_INT: u32 = 5125;
+
+/// Triangles sharing a material
+struct Primitive {
+    material: Option<usize>,
+    /// First index in index buffer
+    start: usize,
+}
+
+/// Binary glTF (GLB) writer for tessellated output.
+///
+/// Vertices and triangles are collected in memory and written as a single mesh by
+/// [`GlbWriter::finish`]. Each material set with [`GlbWriter::set_material`] starts
+/// a new mesh primitive, e.g. for each layer.
+///
+/// Positions are stored relative to the first vertex, which becomes the node translation.
+/// The Z axis of the input is mapped to the Y-up axis of glTF.
+pub struct GlbWriter<W: Write> {
+    out: W,
+    origin: Option<[f64; 3]>,
+    positions: Vec<[f32; 3]>,
+    indices: Vec<u32>,
+    primitives: Vec<Primitive>,
+    /// Material names and RGBA base colors
+    materials: Vec<(String, [f32; 4])>,
+}
+
+impl<W: Write> GlbWriter<W> {
+    pub fn new(out: W) -> Self {
+        GlbWriter {
+            out,
+            origin: None,
+            positions: Vec::new(),
+            indices: Vec::new(),
+            primitives: vec![Primitive {
+                material: None,
+                start: 0,
+            }],
+            materials: Vec::new(),
+        }
+    }
+    /// Use material `name` with RGBA base color for the following triangles
+    pub fn set_material(&mut self, name: &str, color: [f32; 4]) {
+        let material = match self.materials.iter().position(|(n, _)| n == name) {
+            Some(idx) => {
+                self.materials[idx].1 = color;
+                idx
+            }
+            None => {
+                self.materials.push((name.to_string(), color));
+                self.materials.len() - 1
+            }
+        };
+        let start = self.indices.len();
+        match self.primitives.last_mut() {
+            Some(primitive) if primitive.start == start => primitive.material = Some(material),
+            _ => self.primitives.push(Primitive {
+                material: Some(material),
+                start,
+            }),
+        }
+    }
+    /// Write GLB output.
+    pub fn finish(&mut self) -> Result<()> {
+        let json = self.gltf_json();
+        let mut json_chunk = serde_json::to_vec(&json)
+            .map_err(|e| crate::error::GeozeroError::Geometry(e.to_string()))?;
+        while json_chunk.len() % 4 != 0 {
+            json_chunk.push(b' ');
+        }
+        let mut bin_chunk = Vec::with_capacity(self.indices.len() * 4 + self.positions.len() * 12);
+        for idx in &self.indices {
+            bin_chunk.extend_from_slice(&idx.to_le_bytes());
+        }
+        for pos in &self.positions {
+            for v in pos {
+                bin_chunk.extend_from_slice(&v.to_le_bytes());
+            }
+        }
+        let total_len = 12 + 8 + json_chunk.len() + 8 + bin_chunk.len();
+
+        self.out.write_all(&GLB_MAGIC.to_le_bytes())?;
+        self.out.write_all(&2u32.to_le_bytes())?;
+        self.out.write_all(&(total_len as u32).to_le_bytes())?;
+        self.out
+            .write_all(&(json_chunk.len() as u32).to_le_bytes())?;
+        self.out.write_all(&CHUNK_JSON.to_le_bytes())?;
+        self.out.write_all(&json_chunk)?;
+        self.out
+            .write_all(&(bin_chunk.len() as u32).to_le_bytes())?;
+        self.out.write_all(&CHUNK_BIN.to_le_bytes())?;
+        self.out.write_all(&bin_chunk)?;
+        Ok(())
+    }
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+    fn gltf_json(&self) -> Value {
+        let indices_len = self.indices.len() * 4;
+        let positions_len = self.positions.len() * 12;
+        let mut min = [f32::MAX; 3];
+        let mut max = [f32::MIN; 3];
+        for pos in &self.positions {
+            for (i, v) in pos.iter().enumerate() {
+                min[i] = min[i].min(*v);
+                max[i] = max[i].max(*v);
+            }
+        }
+        let mut accessors = vec![json!({
+            "bufferView": 1,
+            "componentType": FLOAT,
+            "count": self.positions.len(),
+            "type": "VEC3",
+            "min": min,
+            "max": max,
+        })];
+        let mut primitives = Vec::new();
+        for (i, primitive) in self.primitives.iter().enumerate() {
+            let end = self
+                .primitives
+                .get(i + 1)
+                .map_or(self.indices.len(), |next| next.start);
+            if end == primitive.start {
+                continue;
+            }
+            accessors.push(json!({
+                "bufferView": 0,
+                "byteOffset": primitive.start * 4,
+                "componentType": UNSIGNED_INT,
+                "count": end - primitive.start,
+                "type": "SCALAR",
+            }));
+            let mut prim = json!({
+                "attributes": { "POSITION": 0 },
+                "indices": accessors.len() - 1,
+                "mode": 4,
+            });
+            if let Some(material) = primitive.material {
+                prim["material"] = json!(material);
+            }
+            primitives.push(prim);
+        }
+        let materials: Vec<Value> = self
+            .materials
+            .iter()
+            .map(|(name, color)| {
+                json!({
+                    "name": name,
+                    "pbrMetallicRoughness": { "baseColorFactor": color },
+                    "doubleSided": true,
+                })
+            })
+            .collect();
+        let origin = self.origin.unwrap_or([0.0; 3]);
+
+        let mut gltf = json!({
+            "asset": { "version": "2.0", "generator": "geozero" },
+            "scene": 0,
+            "scenes": [{ "nodes": [0] }],
+            "buffers": [{ "byteLength": indices_len + positions_len }],
+        });
+        if primitives.is_empty() {
+            gltf["nodes"] = json!([{}]);
+        } else {
+            gltf["nodes"] = json!([
+                // Z-up to Y-up
+                { "rotation": [-std::f64::consts::FRAC_1_SQRT_2, 0.0, 0.0, std::f64::consts::FRAC_1_SQRT_2], "children": [1] },
+                { "mesh": 0, "translation": origin },
+            ]);
+            gltf["meshes"] = json!([{ "primitives": primitives }]);
+            gltf["accessors"] = json!(accessors);
+            gltf["bufferViews"] = json!([
+                { "buffer": 0, "byteOffset": 0, "byteLength": indices_len, "target": ELEMENT_ARRAY_BUFFER },
+                { "buffer": 0, "byteOffset": indices_len, "byteLength": positions_len, "target": ARRAY_BUFFER },
+            ]);
+        }
+        if !materials.is_empty() {
+            gltf["materials"] = json!(materials);
+        }
+        gltf
+    }
+}
+
+impl<W: Write> VertexOutput<u32> for GlbWriter<W> {
+    fn vertex(&mut self, x: f64, y: f64, z: f64) -> Result<()> {
+        let [ox, oy, oz] = *self.origin.get_or_insert([x, y, z]);
+        self.positions
+            .push([(x - ox) as f32, (y - oy) as f32, (z - oz) as f32]);
+        Ok(())
+    }
+    fn triangle(&mut self, idx0: u32, idx1: u32, idx2: u32) -> Result<()> {
+        self.indices.extend_from_slice(&[idx0, idx1, idx2]);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::read_geojson;
+    use crate::tessellator::Tessellator;
+
+    #[test]
+    fn glb_layout() -> Result<()> {
+        let geojson = r#"{"type": "Polygon", "coordinates": [[[0,0],[0,1],[1,1],[0,0]]]}"#;
+        let mut glb = GlbWriter::new(Vec::new());
+        glb.set_material("buildings", [0.8, 0.8, 0.8, 1.0]);
+        read_geojson(geojson.as_bytes(), &mut Tessellator::new(&mut glb))?;
+        glb.finish()?;
+        let out = glb.into_inner();
+
+        let u32_at = |pos: usize| u32::from_le_bytes(out[pos..pos + 4].try_into().unwrap());
+        assert_eq!(&out[0..4], b"glTF");
+        assert_eq!(u32_at(4), 2);
+        assert_eq!(u32_at(8) as usize, out.len());
+        let json_len = u32_at(12) as usize;
+        assert_eq!(json_len % 4, 0);
+        assert_eq!(u32_at(16), CHUNK_JSON);
+        let gltf: Value = serde_json::from_slice(&out[20..20 + json_len]).unwrap();
+        assert_eq!(gltf["materials"][0]["name"], "buildings");
+        assert_eq!(gltf["meshes"][0]["primitives"][0]["material"], 0);
+        // One triangle
+        assert_eq!(gltf["accessors"][1]["count"], 3);
+        let vertex_count = gltf["accessors"][0]["count"].as_u64().unwrap() as usize;
+        let bin_len = u32_at(20 + json_len) as usize;
+        assert_eq!(u32_at(24 + json_len), CHUNK_BIN);
+        assert_eq!(bin_len, 3 * 4 + vertex_count * 12);
+        Ok(())
+    }
+}
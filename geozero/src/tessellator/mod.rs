@@ -1,5 +1,12 @@
-use crate::error::Result;
-use crate::{FeatureProcessor, GeomProcessor, PropertyProcessor};
+//! Tessellation of geometries into triangle meshes.
+mod glb_writer;
+mod obj_writer;
+
+pub use glb_writer::GlbWriter;
+pub use obj_writer::ObjWriter;
+
+use crate::error::{GeozeroError, Result};
+use crate::{ColumnValue, CoordDimensions, FeatureProcessor, GeomProcessor, PropertyProcessor};
 use lyon::math::{point, Point};
 use lyon::path::path::Builder;
 use lyon::path::Path;
@@ -8,51 +15,248 @@ use lyon::tessellation::*;
 use std::mem;
 
 /// Triangle generator output
+///
+/// Vertices are numbered in output order, starting at 0. The index type `I`
+/// (`u16` or `u32`) limits the number of vertices which can be referenced.
+///
+/// When processing features, the triangles of each feature are enclosed by
+/// `feature_begin` and `feature_end` calls, and the feature properties are passed
+/// to `property`.
 #[allow(unused_variables)]
-pub trait VertexOutput {
-    fn vertex(&self, x: f32, y: f32, z: f32) {}
-    fn triangle(&self, idx0: u16, idx1: u16, idx2: u16) {}
+pub trait VertexOutput<I = u16> {
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        Ok(())
+    }
+    fn feature_end(&mut self, idx: u64) -> Result<()> {
+        Ok(())
+    }
+    /// Feature property. Abort processing, if return value is true.
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        Ok(false)
+    }
+    fn vertex(&mut self, x: f64, y: f64, z: f64) -> Result<()> {
+        Ok(())
+    }
+    fn triangle(&mut self, idx0: I, idx1: I, idx2: I) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Height source for extruding polygons.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Extrusion {
+    /// Maximal Z value of the polygon coordinates
+    Z,
+    /// Numeric feature property with the given name
+    Property(String),
+}
+
+/// Tessellated polygon waiting for its extrusion height
+struct PendingPolygon {
+    buffers: VertexBuffers<Point, u32>,
+    origin: (f64, f64),
+    rings: Vec<Vec<(f64, f64)>>,
 }
 
 /// Tessellator.
-pub struct Tessellator<'a> {
-    vertex_out: &'a dyn VertexOutput,
+///
+/// With extrusion enabled, polygons are extruded from z=0 to their height,
+/// producing roof and side wall triangles.
+pub struct Tessellator<'a, I = u16> {
+    vertex_out: &'a mut dyn VertexOutput<I>,
     has_started: bool,
     builder: Builder,
+    /// Offset subtracted from path coordinates to keep f32 precision
+    origin: Option<(f64, f64)>,
     num_rings: usize,
+    /// Number of vertices written
+    vertex_count: usize,
+    extrusion: Option<Extrusion>,
+    /// Rings of current polygon, collected for extrusion
+    rings: Vec<Vec<(f64, f64)>>,
+    max_z: Option<f64>,
+    in_feature: bool,
+    feature_height: Option<f64>,
+    /// Polygons of current feature, when extruding by property
+    pending: Vec<PendingPolygon>,
 }
 
-impl<'a> Tessellator<'a> {
-    pub fn new(out: &'a dyn VertexOutput) -> Self {
+impl<'a, I: TryFrom<usize>> Tessellator<'a, I> {
+    pub fn new(out: &'a mut dyn VertexOutput<I>) -> Self {
         Tessellator {
             vertex_out: out,
             has_started: false,
             builder: Path::builder(),
+            origin: None,
             num_rings: 0,
+            vertex_count: 0,
+            extrusion: None,
+            rings: Vec::new(),
+            max_z: None,
+            in_feature: false,
+            feature_height: None,
+            pending: Vec::new(),
         }
     }
-}
-
-impl GeomProcessor for Tessellator<'_> {
-    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+    /// Extrude polygons to a height (default: no extrusion)
+    ///
+    /// Polygons without height (e.g. missing property) are written flat.
+    pub fn set_extrusion(&mut self, extrusion: Option<Extrusion>) {
+        self.extrusion = extrusion;
+    }
+    fn add_point(&mut self, x: f64, y: f64, idx: usize) {
+        if self.extrusion.is_some() {
+            if idx == 0 {
+                self.rings.push(Vec::new());
+            }
+            if let Some(ring) = self.rings.last_mut() {
+                ring.push((x, y));
+            }
+        }
+        let (ox, oy) = *self.origin.get_or_insert((x, y));
+        let pt = point((x - ox) as f32, (y - oy) as f32);
         if idx == 0 {
             self.has_started = true;
-            self.builder.begin(point(x as f32, y as f32));
+            self.builder.begin(pt);
+        } else {
+            self.builder.line_to(pt);
+        }
+    }
+    fn take_path(&mut self) -> (Path, (f64, f64)) {
+        let mut builder = mem::replace(&mut self.builder, Path::builder());
+        if self.has_started {
+            self.has_started = false;
+            builder.close();
+        }
+        self.rings.clear();
+        self.max_z = None;
+        (builder.build(), self.origin.take().unwrap_or((0.0, 0.0)))
+    }
+    fn index(&self, idx: u32) -> Result<I> {
+        I::try_from(self.vertex_count + idx as usize).map_err(|_| {
+            GeozeroError::Geometry("vertex index exceeds index type range".to_string())
+        })
+    }
+    fn write_buffers(
+        &mut self,
+        buffers: &VertexBuffers<Point, u32>,
+        origin: (f64, f64),
+        z: f64,
+    ) -> Result<()> {
+        for tri in buffers.indices.chunks(3) {
+            // Check index range before writing any vertex
+            self.index(tri[0].max(tri[1]).max(tri[2]))?;
+        }
+        for pos in &buffers.vertices {
+            self.vertex_out
+                .vertex(origin.0 + pos.x as f64, origin.1 + pos.y as f64, z)?;
+        }
+        for tri in buffers.indices.chunks(3) {
+            let (i0, i1, i2) = (
+                self.index(tri[0])?,
+                self.index(tri[1])?,
+                self.index(tri[2])?,
+            );
+            self.vertex_out.triangle(i0, i1, i2)?;
+        }
+        self.vertex_count += buffers.vertices.len();
+        Ok(())
+    }
+    fn write_extruded(&mut self, polygon: &PendingPolygon, height: f64) -> Result<()> {
+        self.write_buffers(&polygon.buffers, polygon.origin, height)?;
+        if height == 0.0 {
+            return Ok(());
+        }
+        for ring in &polygon.rings {
+            let closing = match (ring.first(), ring.last()) {
+                (Some(first), Some(last)) if first != last => Some(*first),
+                _ => None,
+            };
+            let points: Vec<_> = ring.iter().copied().chain(closing).collect();
+            for edge in points.windows(2) {
+                let ((x0, y0), (x1, y1)) = (edge[0], edge[1]);
+                if (x0, y0) == (x1, y1) {
+                    continue;
+                }
+                let idx = [
+                    self.index(0)?,
+                    self.index(1)?,
+                    self.index(2)?,
+                    self.index(3)?,
+                ];
+                self.vertex_out.vertex(x0, y0, 0.0)?;
+                self.vertex_out.vertex(x1, y1, 0.0)?;
+                self.vertex_out.vertex(x1, y1, height)?;
+                self.vertex_out.vertex(x0, y0, height)?;
+                let [i0, i1, i2, i3] = idx;
+                self.vertex_out.triangle(i0, i1, i2)?;
+                self.vertex_out.triangle(i0, i2, i3)?;
+                self.vertex_count += 4;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Numeric value of a property
+fn numeric_value(value: &ColumnValue) -> Option<f64> {
+    match *value {
+        ColumnValue::Byte(v) => Some(v as f64),
+        ColumnValue::UByte(v) => Some(v as f64),
+        ColumnValue::Short(v) => Some(v as f64),
+        ColumnValue::UShort(v) => Some(v as f64),
+        ColumnValue::Int(v) => Some(v as f64),
+        ColumnValue::UInt(v) => Some(v as f64),
+        ColumnValue::Long(v) => Some(v as f64),
+        ColumnValue::ULong(v) => Some(v as f64),
+        ColumnValue::Float(v) => Some(v as f64),
+        ColumnValue::Double(v) => Some(v),
+        ColumnValue::String(v) | ColumnValue::Decimal(v) => v.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+impl<I: TryFrom<usize>> GeomProcessor for Tessellator<'_, I> {
+    fn dimensions(&self) -> CoordDimensions {
+        if self.extrusion == Some(Extrusion::Z) {
+            CoordDimensions::xyz()
         } else {
-            self.builder.line_to(point(x as f32, y as f32));
+            CoordDimensions::xy()
+        }
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.add_point(x, y, idx);
+        Ok(())
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        _m: Option<f64>,
+        _t: Option<f64>,
+        _tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        if let Some(z) = z {
+            self.max_z = Some(self.max_z.map_or(z, |max_z| max_z.max(z)));
         }
+        self.add_point(x, y, idx);
         Ok(())
     }
     fn point_begin(&mut self, _idx: usize) -> Result<()> {
         Ok(())
     }
     fn point_end(&mut self, _idx: usize) -> Result<()> {
+        // Points are not tessellated
+        self.take_path();
         Ok(())
     }
     fn multipoint_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
         Ok(())
     }
     fn multipoint_end(&mut self, _idx: usize) -> Result<()> {
+        self.take_path();
         Ok(())
     }
     fn linestring_begin(&mut self, tagged: bool, _size: usize, _idx: usize) -> Result<()> {
@@ -67,8 +271,9 @@ impl GeomProcessor for Tessellator<'_> {
             self.builder.close();
         }
         if tagged {
-            let builder = mem::replace(&mut self.builder, Path::builder());
-            tessellate_line(&builder.build());
+            let (path, origin) = self.take_path();
+            let buffers = tessellate_line(&path)?;
+            self.write_buffers(&buffers, origin, 0.0)?;
         }
         Ok(())
     }
@@ -76,66 +281,91 @@ impl GeomProcessor for Tessellator<'_> {
         Ok(())
     }
     fn multilinestring_end(&mut self, _idx: usize) -> Result<()> {
-        Ok(())
+        let (path, origin) = self.take_path();
+        let buffers = tessellate_line(&path)?;
+        self.write_buffers(&buffers, origin, 0.0)
     }
     fn polygon_begin(&mut self, _tagged: bool, size: usize, _idx: usize) -> Result<()> {
         self.num_rings = size;
         Ok(())
     }
     fn polygon_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
-        let mut builder = mem::replace(&mut self.builder, Path::builder());
-        if self.has_started {
-            self.has_started = false;
-            builder.close();
+        let rings = mem::take(&mut self.rings);
+        let max_z = self.max_z;
+        let (path, origin) = self.take_path();
+        let buffers = tessellate_poly(&path)?;
+        let polygon = PendingPolygon {
+            buffers,
+            origin,
+            rings,
+        };
+        match self.extrusion {
+            None => self.write_buffers(&polygon.buffers, origin, 0.0),
+            Some(Extrusion::Z) => self.write_extruded(&polygon, max_z.unwrap_or(0.0)),
+            Some(Extrusion::Property(_)) if self.in_feature => {
+                // Property may follow the geometry
+                self.pending.push(polygon);
+                Ok(())
+            }
+            Some(Extrusion::Property(_)) => self.write_extruded(&polygon, 0.0),
         }
-        tessellate_poly(&builder.build(), self.vertex_out);
-        Ok(())
     }
 }
 
-fn tessellate_line(path: &Path) {
-    let mut geometry: VertexBuffers<Point, u16> = VertexBuffers::new();
-    let mut vertex_builder = simple_builder(&mut geometry);
+fn tessellation_error(e: TessellationError) -> GeozeroError {
+    GeozeroError::Geometry(format!("tessellation failed: {e:?}"))
+}
+
+fn tessellate_line(path: &Path) -> Result<VertexBuffers<Point, u32>> {
+    let mut buffers: VertexBuffers<Point, u32> = VertexBuffers::new();
     let mut tessellator = StrokeTessellator::new();
     tessellator
-        .tessellate(path, &StrokeOptions::default(), &mut vertex_builder)
-        .unwrap();
-    println!(
-        " -- {:?} vertices {:?} indices",
-        geometry.vertices, geometry.indices
-    );
+        .tessellate_path(
+            path,
+            &StrokeOptions::default(),
+            &mut simple_builder(&mut buffers),
+        )
+        .map_err(tessellation_error)?;
+    Ok(buffers)
 }
 
-fn tessellate_poly(path: &Path, out: &dyn VertexOutput) {
-    let mut buffers: VertexBuffers<(), u16> = VertexBuffers::new();
+fn tessellate_poly(path: &Path) -> Result<VertexBuffers<Point, u32>> {
+    let mut buffers: VertexBuffers<Point, u32> = VertexBuffers::new();
     let mut tessellator = FillTessellator::new();
     tessellator
         .tessellate_path(
             path,
             &FillOptions::default(),
-            &mut BuffersBuilder::new(&mut buffers, |pos: FillVertex| {
-                let pos = pos.position();
-                out.vertex(pos.x, pos.y, 0.0);
-            }),
+            &mut simple_builder(&mut buffers),
         )
-        .unwrap();
-    for tri in buffers.indices.chunks(3) {
-        out.triangle(tri[0], tri[1], tri[2]);
-    }
+        .map_err(tessellation_error)?;
+    Ok(buffers)
 }
 
-impl PropertyProcessor for Tessellator<'_> {}
-impl FeatureProcessor for Tessellator<'_> {}
-
-/// OBJ writer
-pub struct ObjWriter;
+impl<I: TryFrom<usize>> PropertyProcessor for Tessellator<'_, I> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        if let Some(Extrusion::Property(height_name)) = &self.extrusion {
+            if name == height_name {
+                self.feature_height = numeric_value(value);
+            }
+        }
+        self.vertex_out.property(idx, name, value)
+    }
+}
 
-impl VertexOutput for ObjWriter {
-    fn vertex(&self, x: f32, y: f32, z: f32) {
-        println!("v {x} {y} {z}");
+impl<I: TryFrom<usize>> FeatureProcessor for Tessellator<'_, I> {
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        self.in_feature = true;
+        self.feature_height = None;
+        self.vertex_out.feature_begin(idx)
     }
-    fn triangle(&self, idx0: u16, idx1: u16, idx2: u16) {
-        println!("f {} {} {}", idx0 + 1, idx1 + 1, idx2 + 1);
+    fn feature_end(&mut self, idx: u64) -> Result<()> {
+        let height = self.feature_height.take().unwrap_or(0.0);
+        for polygon in mem::take(&mut self.pending) {
+            self.write_extruded(&polygon, height)?;
+        }
+        self.in_feature = false;
+        self.vertex_out.feature_end(idx)
     }
 }
 
@@ -147,48 +377,48 @@ mod test {
     #[test]
     fn point_geom() {
         let geojson = r#"{"type": "Point", "coordinates": [1, 1]}"#;
-        let out = ObjWriter {};
-        let mut tessellator = Tessellator::new(&out);
+        let mut out = ObjWriter::new(Vec::new());
+        let mut tessellator = Tessellator::new(&mut out);
         assert!(read_geojson(geojson.as_bytes(), &mut tessellator).is_ok());
     }
 
     #[test]
     fn multipoint_geom() {
         let geojson = r#"{"type": "MultiPoint", "coordinates": [[1, 1], [2, 2]]}"#;
-        let out = ObjWriter {};
-        let mut tessellator = Tessellator::new(&out);
+        let mut out = ObjWriter::new(Vec::new());
+        let mut tessellator = Tessellator::new(&mut out);
         assert!(read_geojson(geojson.as_bytes(), &mut tessellator).is_ok());
     }
 
     #[test]
     fn multipoint_empty_geom() {
         let geojson = r#"{"type": "MultiPoint", "coordinates": []}"#;
-        let out = ObjWriter {};
-        let mut tessellator = Tessellator::new(&out);
+        let mut out = ObjWriter::new(Vec::new());
+        let mut tessellator = Tessellator::new(&mut out);
         assert!(read_geojson(geojson.as_bytes(), &mut tessellator).is_ok());
     }
 
     #[test]
     fn line_geom() {
         let geojson = r#"{"type": "LineString", "coordinates": [[1,1], [2,2]]}"#;
-        let out = ObjWriter {};
-        let mut tessellator = Tessellator::new(&out);
+        let mut out = ObjWriter::new(Vec::new());
+        let mut tessellator = Tessellator::new(&mut out);
         assert!(read_geojson(geojson.as_bytes(), &mut tessellator).is_ok());
     }
 
     #[test]
     fn line_empty_geom() {
         let geojson = r#"{"type": "LineString", "coordinates": []}"#;
-        let out = ObjWriter {};
-        let mut tessellator = Tessellator::new(&out);
+        let mut out = ObjWriter::new(Vec::new());
+        let mut tessellator = Tessellator::new(&mut out);
         assert!(read_geojson(geojson.as_bytes(), &mut tessellator).is_ok());
     }
 
     // #[test]
     // fn line_geom_3d() {
     //     let geojson = r#"{"type": "LineString", "coordinates": [[1,1,10], [2,2,20]]}"#;
-    //     let out = ObjWriter {};
-    //     let mut tessellator = Tessellator::new(&out);
+    //     let mut out = ObjWriter::new(Vec::new());
+    //     let mut tessellator = Tessellator::new(&mut out);
     //     assert!(read_geojson(geojson.as_bytes(), &mut tessellator).is_ok());
     // }
 
@@ -196,42 +426,42 @@ mod test {
     fn multiline_geom() {
         let geojson =
             r#"{"type": "MultiLineString", "coordinates": [[[1,1],[2,2]],[[3,3],[4,4]]]}"#;
-        let out = ObjWriter {};
-        let mut tessellator = Tessellator::new(&out);
+        let mut out = ObjWriter::new(Vec::new());
+        let mut tessellator = Tessellator::new(&mut out);
         assert!(read_geojson(geojson.as_bytes(), &mut tessellator).is_ok());
     }
 
     #[test]
     fn multiline_empty_geom() {
         let geojson = r#"{"type": "MultiLineString", "coordinates": [[],[]]}"#;
-        let out = ObjWriter {};
-        let mut tessellator = Tessellator::new(&out);
+        let mut out = ObjWriter::new(Vec::new());
+        let mut tessellator = Tessellator::new(&mut out);
         assert!(read_geojson(geojson.as_bytes(), &mut tessellator).is_ok());
 
         let geojson = r#"{"type": "MultiLineString", "coordinates": []}"#;
-        let out = ObjWriter {};
-        let mut tessellator = Tessellator::new(&out);
+        let mut out = ObjWriter::new(Vec::new());
+        let mut tessellator = Tessellator::new(&mut out);
         assert!(read_geojson(geojson.as_bytes(), &mut tessellator).is_ok());
     }
 
     #[test]
     fn polygon_geom() {
         let geojson = r#"{"type": "Polygon", "coordinates": [[[0, 0], [0, 3], [3, 3], [3, 0], [0, 0]],[[0.2, 0.2], [0.2, 2], [2, 2], [2, 0.2], [0.2, 0.2]]]}"#;
-        let out = ObjWriter {};
-        let mut tessellator = Tessellator::new(&out);
+        let mut out = ObjWriter::new(Vec::new());
+        let mut tessellator = Tessellator::new(&mut out);
         assert!(read_geojson(geojson.as_bytes(), &mut tessellator).is_ok());
     }
 
     #[test]
     fn polygon_empty_geom() {
         let geojson = r#"{"type": "Polygon", "coordinates": [[],[]]}"#;
-        let out = ObjWriter {};
-        let mut tessellator = Tessellator::new(&out);
+        let mut out = ObjWriter::new(Vec::new());
+        let mut tessellator = Tessellator::new(&mut out);
         assert!(read_geojson(geojson.as_bytes(), &mut tessellator).is_ok());
 
         let geojson = r#"{"type": "Polygon", "coordinates": []}"#;
-        let out = ObjWriter {};
-        let mut tessellator = Tessellator::new(&out);
+        let mut out = ObjWriter::new(Vec::new());
+        let mut tessellator = Tessellator::new(&mut out);
         assert!(read_geojson(geojson.as_bytes(), &mut tessellator).is_ok());
     }
 
@@ -239,32 +469,142 @@ mod test {
     fn multipolygon_geom() {
         let geojson =
             r#"{"type": "MultiPolygon", "coordinates": [[[[0,0],[0,1],[1,1],[1,0],[0,0]]]]}"#;
-        let out = ObjWriter {};
-        let mut tessellator = Tessellator::new(&out);
+        let mut out = ObjWriter::new(Vec::new());
+        let mut tessellator = Tessellator::new(&mut out);
         assert!(read_geojson(geojson.as_bytes(), &mut tessellator).is_ok());
     }
 
+    /// Collects tessellator output
+    #[derive(Default)]
+    struct Mesh<I> {
+        vertices: Vec<[f64; 3]>,
+        triangles: Vec<[I; 3]>,
+        /// Feature name and triangle range
+        features: Vec<(String, std::ops::Range<usize>)>,
+    }
+
+    impl<I> VertexOutput<I> for Mesh<I> {
+        fn feature_begin(&mut self, _idx: u64) -> Result<()> {
+            let start = self.triangles.len();
+            self.features.push((String::new(), start..start));
+            Ok(())
+        }
+        fn feature_end(&mut self, _idx: u64) -> Result<()> {
+            let end = self.triangles.len();
+            if let Some((_, range)) = self.features.last_mut() {
+                range.end = end;
+            }
+            Ok(())
+        }
+        fn property(&mut self, _idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+            if let (Some((feature_name, _)), "name") = (self.features.last_mut(), name) {
+                *feature_name = value.to_string();
+            }
+            Ok(false)
+        }
+        fn vertex(&mut self, x: f64, y: f64, z: f64) -> Result<()> {
+            self.vertices.push([x, y, z]);
+            Ok(())
+        }
+        fn triangle(&mut self, idx0: I, idx1: I, idx2: I) -> Result<()> {
+            self.triangles.push([idx0, idx1, idx2]);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn mesh_output() -> Result<()> {
+        let geojson = r#"{"type": "Polygon", "coordinates": [[[2600000,1200000],[2600000,1200001],[2600001,1200001],[2600001,1200000],[2600000,1200000]]]}"#;
+        let mut mesh = Mesh::<u32>::default();
+        read_geojson(geojson.as_bytes(), &mut Tessellator::new(&mut mesh))?;
+        let first_count = mesh.vertices.len() as u32;
+
+        let geojson = r#"{"type": "MultiPolygon", "coordinates": [[[[2600000,1200000],[2600000,1200001],[2600001,1200001],[2600001,1200000],[2600000,1200000]]],[[[2600002,1200000],[2600002,1200001],[2600003,1200001],[2600002,1200000]]]]}"#;
+        let mut mesh = Mesh::<u32>::default();
+        read_geojson(geojson.as_bytes(), &mut Tessellator::new(&mut mesh))?;
+        assert_eq!(mesh.triangles.len(), 3);
+        // Second polygon references vertices after the first one
+        assert!(mesh.triangles[2]
+            .iter()
+            .all(|idx| *idx >= first_count && (*idx as usize) < mesh.vertices.len()));
+        // Coordinates keep full precision
+        assert!(mesh
+            .vertices
+            .iter()
+            .all(|v| v[0] == v[0].round() && v[1] == v[1].round()));
+        assert!(mesh.vertices.contains(&[2600003.0, 1200001.0, 0.0]));
+        Ok(())
+    }
+
+    #[test]
+    fn features() -> Result<()> {
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"name": "square"}, "geometry": {"type": "Polygon", "coordinates": [[[0,0],[0,1],[1,1],[1,0],[0,0]]]}},
+            {"type": "Feature", "properties": {"name": "triangle"}, "geometry": {"type": "Polygon", "coordinates": [[[2,0],[2,1],[3,1],[2,0]]]}}
+        ]}"#;
+        let mut mesh = Mesh::<u32>::default();
+        read_geojson(geojson.as_bytes(), &mut Tessellator::new(&mut mesh))?;
+        assert_eq!(
+            mesh.features,
+            vec![("square".to_string(), 0..2), ("triangle".to_string(), 2..3)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn extrusion() -> Result<()> {
+        let geojson = r#"{"type": "Feature", "geometry": {"type": "Polygon", "coordinates": [[[0,0],[0,1],[1,1],[1,0],[0,0]]]}, "properties": {"height": 10}}"#;
+        let mut mesh = Mesh::<u32>::default();
+        let mut tessellator = Tessellator::new(&mut mesh);
+        tessellator.set_extrusion(Some(Extrusion::Property("height".to_string())));
+        read_geojson(geojson.as_bytes(), &mut tessellator)?;
+        // Roof and 4 walls
+        assert_eq!(mesh.triangles.len(), 2 + 4 * 2);
+        assert_eq!(mesh.features[0].1, 0..10);
+        assert!(mesh.vertices[..4].iter().all(|v| v[2] == 10.0));
+        assert!(mesh.vertices.contains(&[1.0, 1.0, 0.0]));
+
+        let geojson = r#"{"type": "Polygon", "coordinates": [[[0,0,5],[0,1,5],[1,1,5],[0,0,5]]]}"#;
+        let mut mesh = Mesh::<u32>::default();
+        let mut tessellator = Tessellator::new(&mut mesh);
+        tessellator.set_extrusion(Some(Extrusion::Z));
+        read_geojson(geojson.as_bytes(), &mut tessellator)?;
+        assert_eq!(mesh.triangles.len(), 1 + 3 * 2);
+        assert!(mesh.vertices.contains(&[1.0, 1.0, 5.0]));
+        Ok(())
+    }
+
+    #[test]
+    fn index_overflow() {
+        let mut mesh = Mesh::<u16>::default();
+        let mut tessellator = Tessellator::new(&mut mesh);
+        tessellator.vertex_count = u16::MAX as usize;
+        let geojson = r#"{"type": "Polygon", "coordinates": [[[0,0],[0,1],[1,1],[1,0],[0,0]]]}"#;
+        assert!(read_geojson(geojson.as_bytes(), &mut tessellator).is_err());
+        assert!(mesh.vertices.is_empty());
+    }
+
     #[test]
     fn multipolygon_empty_geom() {
         let geojson = r#"{"type": "MultiPolygon", "coordinates": [[[]]]}"#;
-        let out = ObjWriter {};
-        let mut tessellator = Tessellator::new(&out);
+        let mut out = ObjWriter::new(Vec::new());
+        let mut tessellator = Tessellator::new(&mut out);
         assert!(read_geojson(geojson.as_bytes(), &mut tessellator).is_ok());
         let geojson = r#"{"type": "MultiPolygon", "coordinates": [[]]}"#;
-        let out = ObjWriter {};
-        let mut tessellator = Tessellator::new(&out);
+        let mut out = ObjWriter::new(Vec::new());
+        let mut tessellator = Tessellator::new(&mut out);
         assert!(read_geojson(geojson.as_bytes(), &mut tessellator).is_ok());
         let geojson = r#"{"type": "MultiPolygon", "coordinates": []}"#;
-        let out = ObjWriter {};
-        let mut tessellator = Tessellator::new(&out);
+        let mut out = ObjWriter::new(Vec::new());
+        let mut tessellator = Tessellator::new(&mut out);
         assert!(read_geojson(geojson.as_bytes(), &mut tessellator).is_ok());
     }
 
     // #[test]
     // fn geometry_collection_geom() {
     //     let geojson = r#"{"type": "Point", "coordinates": [1, 1]}"#;
-    //     let out = ObjWriter {};
-    //     let mut tessellator = Tessellator::new(&out);
+    //     let mut out = ObjWriter::new(Vec::new());
+    //     let mut tessellator = Tessellator::new(&mut out);
     //     assert!(read_geojson(geojson.as_bytes(), &mut tessellator).is_ok());
     // }
 }
@@ -0,0 +1,57 @@
+use crate::error::Result;
+use crate::tessellator::VertexOutput;
+use std::io::Write;
+
+/// Wavefront OBJ writer for tessellated output.
+pub struct ObjWriter<W: Write> {
+    out: W,
+}
+
+impl<W: Write> ObjWriter<W> {
+    pub fn new(out: W) -> Self {
+        ObjWriter { out }
+    }
+    /// Use material `name` for the following faces (`usemtl` statement)
+    pub fn set_material(&mut self, name: &str) -> Result<()> {
+        writeln!(self.out, "usemtl {name}")?;
+        Ok(())
+    }
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
+
+impl<W: Write> VertexOutput<u32> for ObjWriter<W> {
+    fn vertex(&mut self, x: f64, y: f64, z: f64) -> Result<()> {
+        writeln!(self.out, "v {x} {y} {z}")?;
+        Ok(())
+    }
+    fn triangle(&mut self, idx0: u32, idx1: u32, idx2: u32) -> Result<()> {
+        // OBJ indices start at 1
+        writeln!(self.out, "f {} {} {}", idx0 + 1, idx1 + 1, idx2 + 1)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::read_geojson;
+    use crate::tessellator::Tessellator;
+
+    #[test]
+    fn polygon() -> Result<()> {
+        let geojson = r#"{"type": "Polygon", "coordinates": [[[0,0],[0,1],[1,1],[0,0]]]}"#;
+        let mut obj = ObjWriter::new(Vec::new());
+        obj.set_material("buildings")?;
+        read_geojson(geojson.as_bytes(), &mut Tessellator::new(&mut obj))?;
+        let out = String::from_utf8(obj.into_inner()).unwrap();
+        let lines: Vec<_> = out.lines().collect();
+        assert_eq!(lines[0], "usemtl buildings");
+        assert!(lines.iter().filter(|l| l.starts_with("v ")).count() >= 3);
+        assert_eq!(lines.iter().filter(|l| l.starts_with("f ")).count(), 1);
+        assert!(lines.contains(&"v 1 1 0"));
+        Ok(())
+    }
+}
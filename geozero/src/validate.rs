@@ -0,0 +1,548 @@
+//! Structural validation of geometries.
+use crate::error::{GeozeroError, Result};
+use crate::{
+    ColumnDef, ColumnValue, CoordDimensions, Crs, FeatureId, FeatureProcessor, GeomProcessor,
+    PropertyProcessor,
+};
+use std::fmt;
+
+/// Kind of validation issue
+#[derive(Clone, PartialEq, Debug)]
+pub enum ValidationIssueKind {
+    /// First and last point of a ring differ
+    UnclosedRing,
+    /// Linestring or ring with too few points
+    TooFewPoints { found: usize, min: usize },
+    /// NaN or infinite coordinate value
+    InvalidCoordinate,
+    /// Coordinates with and without Z or M values in the same geometry
+    InconsistentDimensions,
+}
+
+/// Validation issue with the position of the offending geometry
+#[derive(Clone, PartialEq, Debug)]
+pub struct ValidationIssue {
+    pub kind: ValidationIssueKind,
+    /// Index of the feature, if processed as feature
+    pub feature: Option<u64>,
+    /// Geometry indices from the top-level geometry down to the offending part
+    pub path: Vec<usize>,
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ValidationIssueKind::UnclosedRing => write!(f, "unclosed ring")?,
+            ValidationIssueKind::TooFewPoints { found, min } => {
+                write!(f, "too few points ({found}, expected at least {min})")?;
+            }
+            ValidationIssueKind::InvalidCoordinate => write!(f, "invalid coordinate value")?,
+            ValidationIssueKind::InconsistentDimensions => {
+                write!(f, "inconsistent coordinate dimensions")?;
+            }
+        }
+        if let Some(feature) = self.feature {
+            write!(f, " in feature {feature}")?;
+        }
+        if !self.path.is_empty() {
+            let path: Vec<String> = self.path.iter().map(|idx| idx.to_string()).collect();
+            write!(f, " at geometry {}", path.join("/"))?;
+        }
+        Ok(())
+    }
+}
+
+/// Handling of validation issues
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ValidationMode {
+    /// Abort processing with an error on the first issue
+    #[default]
+    Error,
+    /// Collect issues and continue processing
+    Warn,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum PartKind {
+    Point,
+    LineString,
+    CircularString,
+    CompoundCurve,
+    /// Polygon, Triangle or CurvePolygon
+    Surface,
+    /// Multi geometries and collections
+    Collection,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Part {
+    kind: PartKind,
+    idx: usize,
+    /// Curve is a ring of a surface
+    ring: bool,
+    count: usize,
+    first: Option<(f64, f64)>,
+    last: Option<(f64, f64)>,
+}
+
+/// Wraps another processor, checking geometries for structural validity.
+///
+/// Checked are unclosed rings, linestrings and rings with too few points,
+/// NaN or infinite coordinates and mixed coordinate dimensions within a geometry.
+/// Depending on the [ValidationMode], processing is aborted with an error
+/// or the issues are collected while all events are passed to the inner processor.
+///
+/// # Usage example:
+///
+/// ```
+/// # #[cfg(feature = "with-wkt")]
+/// # {
+/// use geozero::wkt::{WktStr, WktWriter};
+/// use geozero::{GeozeroGeometry, ValidateProcessor};
+///
+/// let mut out = Vec::new();
+/// let mut processor = ValidateProcessor::new(WktWriter::new(&mut out));
+/// let result = WktStr("POLYGON((0 0,1 0,1 1))").process_geom(&mut processor);
+/// assert!(result.is_err());
+/// # }
+/// ```
+pub struct ValidateProcessor<P> {
+    /// The underlying processor
+    pub inner: P,
+    mode: ValidationMode,
+    issues: Vec<ValidationIssue>,
+    feature: Option<u64>,
+    parts: Vec<Part>,
+    /// Z and M presence of the current geometry
+    dims: Option<(bool, bool)>,
+}
+
+impl<P: GeomProcessor> ValidateProcessor<P> {
+    pub fn new(inner: P) -> Self {
+        Self::with_mode(inner, ValidationMode::default())
+    }
+    pub fn with_mode(inner: P, mode: ValidationMode) -> Self {
+        ValidateProcessor {
+            inner,
+            mode,
+            issues: Vec::new(),
+            feature: None,
+            parts: Vec::new(),
+            dims: None,
+        }
+    }
+    pub fn set_mode(&mut self, mode: ValidationMode) {
+        self.mode = mode;
+    }
+    /// Issues collected in [ValidationMode::Warn]
+    pub fn issues(&self) -> &[ValidationIssue] {
+        &self.issues
+    }
+    /// Take collected issues
+    pub fn take_issues(&mut self) -> Vec<ValidationIssue> {
+        std::mem::take(&mut self.issues)
+    }
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+
+    fn report(&mut self, kind: ValidationIssueKind) -> Result<()> {
+        let issue = ValidationIssue {
+            kind,
+            feature: self.feature,
+            path: self.parts.iter().map(|part| part.idx).collect(),
+        };
+        match self.mode {
+            ValidationMode::Error => Err(GeozeroError::Geometry(issue.to_string())),
+            ValidationMode::Warn => {
+                self.issues.push(issue);
+                Ok(())
+            }
+        }
+    }
+
+    fn begin(&mut self, kind: PartKind, idx: usize) {
+        if self.parts.is_empty() {
+            self.dims = None;
+        }
+        let ring = matches!(self.parts.last(), Some(parent) if parent.kind == PartKind::Surface);
+        self.parts.push(Part {
+            kind,
+            idx,
+            ring,
+            count: 0,
+            first: None,
+            last: None,
+        });
+    }
+
+    /// Check the finished part, which is still on the stack for the issue path
+    fn end(&mut self) -> Result<()> {
+        let Some(part) = self.parts.last().copied() else {
+            return Ok(());
+        };
+        let min = match part.kind {
+            PartKind::LineString if part.ring => 4,
+            PartKind::LineString | PartKind::CompoundCurve => 2,
+            PartKind::CircularString => 3,
+            _ => 0,
+        };
+        // Empty curves are valid
+        if part.count > 0 && part.count < min {
+            self.report(ValidationIssueKind::TooFewPoints {
+                found: part.count,
+                min,
+            })?;
+        }
+        if part.ring && part.count >= min && part.first != part.last {
+            self.report(ValidationIssueKind::UnclosedRing)?;
+        }
+        self.parts.pop();
+        Ok(())
+    }
+
+    fn coord(&mut self, x: f64, y: f64, dims: (bool, bool), values: &[Option<f64>]) -> Result<()> {
+        if !x.is_finite() || !y.is_finite() || values.iter().flatten().any(|v| !v.is_finite()) {
+            self.report(ValidationIssueKind::InvalidCoordinate)?;
+        }
+        match self.dims {
+            None => self.dims = Some(dims),
+            Some(geom_dims) if geom_dims != dims => {
+                self.report(ValidationIssueKind::InconsistentDimensions)?;
+            }
+            Some(_) => {}
+        }
+        // Update the current curve and an enclosing compound curve
+        for part in self.parts.iter_mut().rev() {
+            if matches!(part.kind, PartKind::Surface | PartKind::Collection) {
+                break;
+            }
+            part.count += 1;
+            part.first.get_or_insert((x, y));
+            part.last = Some((x, y));
+        }
+        Ok(())
+    }
+}
+
+impl<P: GeomProcessor> GeomProcessor for ValidateProcessor<P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.inner.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.inner.multi_dim()
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.inner.srid(srid)
+    }
+    fn envelope(&mut self, envelope: &[f64], dims: CoordDimensions, idx: usize) -> Result<()> {
+        self.inner.envelope(envelope, dims, idx)
+    }
+    fn size_hint_total_coords(&mut self, count: usize) -> Result<()> {
+        self.inner.size_hint_total_coords(count)
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.coord(x, y, (false, false), &[])?;
+        self.inner.xy(x, y, idx)
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.coord(x, y, (z.is_some(), m.is_some()), &[z, m, t])?;
+        self.inner.coordinate(x, y, z, m, t, tm, idx)
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.inner.empty_point(idx)
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.begin(PartKind::Point, idx);
+        self.inner.point_begin(idx)
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.end()?;
+        self.inner.point_end(idx)
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(PartKind::Collection, idx);
+        self.inner.multipoint_begin(size, idx)
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.end()?;
+        self.inner.multipoint_end(idx)
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.begin(PartKind::LineString, idx);
+        self.inner.linestring_begin(tagged, size, idx)
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.end()?;
+        self.inner.linestring_end(tagged, idx)
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(PartKind::Collection, idx);
+        self.inner.multilinestring_begin(size, idx)
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.end()?;
+        self.inner.multilinestring_end(idx)
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.begin(PartKind::Surface, idx);
+        self.inner.polygon_begin(tagged, size, idx)
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.end()?;
+        self.inner.polygon_end(tagged, idx)
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(PartKind::Collection, idx);
+        self.inner.multipolygon_begin(size, idx)
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.end()?;
+        self.inner.multipolygon_end(idx)
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(PartKind::Collection, idx);
+        self.inner.geometrycollection_begin(size, idx)
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.end()?;
+        self.inner.geometrycollection_end(idx)
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(PartKind::CircularString, idx);
+        self.inner.circularstring_begin(size, idx)
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        self.end()?;
+        self.inner.circularstring_end(idx)
+    }
+    fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(PartKind::CompoundCurve, idx);
+        self.inner.compoundcurve_begin(size, idx)
+    }
+    fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        self.end()?;
+        self.inner.compoundcurve_end(idx)
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(PartKind::Surface, idx);
+        self.inner.curvepolygon_begin(size, idx)
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.end()?;
+        self.inner.curvepolygon_end(idx)
+    }
+    fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(PartKind::Collection, idx);
+        self.inner.multicurve_begin(size, idx)
+    }
+    fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        self.end()?;
+        self.inner.multicurve_end(idx)
+    }
+    fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(PartKind::Collection, idx);
+        self.inner.multisurface_begin(size, idx)
+    }
+    fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        self.end()?;
+        self.inner.multisurface_end(idx)
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.begin(PartKind::Surface, idx);
+        self.inner.triangle_begin(tagged, size, idx)
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.end()?;
+        self.inner.triangle_end(tagged, idx)
+    }
+    fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(PartKind::Collection, idx);
+        self.inner.polyhedralsurface_begin(size, idx)
+    }
+    fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        self.end()?;
+        self.inner.polyhedralsurface_end(idx)
+    }
+    fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin(PartKind::Collection, idx);
+        self.inner.tin_begin(size, idx)
+    }
+    fn tin_end(&mut self, idx: usize) -> Result<()> {
+        self.end()?;
+        self.inner.tin_end(idx)
+    }
+}
+
+impl<P: PropertyProcessor> PropertyProcessor for ValidateProcessor<P> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue<'_>) -> Result<bool> {
+        self.inner.property(idx, name, value)
+    }
+    fn column_property(
+        &mut self,
+        idx: usize,
+        column: usize,
+        name: &str,
+        value: &ColumnValue<'_>,
+    ) -> Result<bool> {
+        self.inner.column_property(idx, column, name, value)
+    }
+}
+
+impl<P: FeatureProcessor> FeatureProcessor for ValidateProcessor<P> {
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.inner.dataset_begin(name)
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        self.inner.dataset_end()
+    }
+    fn crs(&mut self, crs: &Crs) -> Result<()> {
+        self.inner.crs(crs)
+    }
+    fn schema_begin(&mut self, columns: &[ColumnDef]) -> Result<()> {
+        self.inner.schema_begin(columns)
+    }
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        self.feature = Some(idx);
+        self.inner.feature_begin(idx)
+    }
+    fn feature_id(&mut self, id: &FeatureId) -> Result<()> {
+        self.inner.feature_id(id)
+    }
+    fn feature_end(&mut self, idx: u64) -> Result<()> {
+        self.feature = None;
+        self.inner.feature_end(idx)
+    }
+    fn properties_begin(&mut self) -> Result<()> {
+        self.inner.properties_begin()
+    }
+    fn properties_end(&mut self) -> Result<()> {
+        self.inner.properties_end()
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        self.parts.clear();
+        self.inner.geometry_begin()
+    }
+    fn geometry_end(&mut self) -> Result<()> {
+        self.inner.geometry_end()
+    }
+}
+
+#[cfg(test)]
+#[cfg(all(feature = "with-wkt", feature = "with-geojson"))]
+mod test {
+    use super::*;
+    use crate::bounds::GeomStats;
+    use crate::geojson::GeoJson;
+    use crate::wkt::WktStr;
+    use crate::{GeozeroDatasource, GeozeroGeometry};
+
+    fn validate(wkt: &str) -> Vec<ValidationIssue> {
+        let mut processor = ValidateProcessor::with_mode(GeomStats::new(), ValidationMode::Warn);
+        WktStr(wkt).process_geom(&mut processor).unwrap();
+        processor.take_issues()
+    }
+
+    fn kinds(wkt: &str) -> Vec<ValidationIssueKind> {
+        validate(wkt).into_iter().map(|issue| issue.kind).collect()
+    }
+
+    #[test]
+    fn valid_geometries() {
+        assert!(kinds("POINT(1 2)").is_empty());
+        assert!(kinds("LINESTRING(0 0,1 1)").is_empty());
+        assert!(kinds("POLYGON((0 0,1 0,1 1,0 0))").is_empty());
+        assert!(kinds("POLYGON EMPTY").is_empty());
+        assert!(kinds("LINESTRING Z(0 0 0,1 1 1)").is_empty());
+    }
+
+    #[test]
+    fn rings() {
+        assert_eq!(
+            kinds("POLYGON((0 0,1 0,1 1,0 1))"),
+            vec![ValidationIssueKind::UnclosedRing]
+        );
+        assert_eq!(
+            kinds("POLYGON((0 0,1 0,0 0))"),
+            vec![ValidationIssueKind::TooFewPoints { found: 3, min: 4 }]
+        );
+        let issues = validate("MULTIPOLYGON(((0 0,1 0,1 1,0 0)),((0 0,1 0,1 1,0 1)))");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].path, vec![0, 1, 0]);
+        assert_eq!(issues[0].to_string(), "unclosed ring at geometry 0/1/0");
+    }
+
+    #[test]
+    fn linestrings() {
+        assert_eq!(
+            kinds("LINESTRING(0 0)"),
+            vec![ValidationIssueKind::TooFewPoints { found: 1, min: 2 }]
+        );
+        assert!(kinds("LINESTRING EMPTY").is_empty());
+    }
+
+    #[test]
+    fn coordinates() -> Result<()> {
+        let mut processor = ValidateProcessor::with_mode(GeomStats::new(), ValidationMode::Warn);
+        processor.linestring_begin(true, 2, 0)?;
+        processor.xy(0.0, 0.0, 0)?;
+        processor.xy(1.0, f64::NAN, 1)?;
+        processor.linestring_end(true, 0)?;
+        processor.geometrycollection_begin(2, 0)?;
+        processor.point_begin(0)?;
+        processor.coordinate(1.0, 2.0, Some(3.0), None, None, None, 0)?;
+        processor.point_end(0)?;
+        processor.point_begin(1)?;
+        processor.coordinate(1.0, 2.0, None, None, None, None, 0)?;
+        processor.point_end(1)?;
+        processor.geometrycollection_end(0)?;
+        let kinds: Vec<_> = processor
+            .take_issues()
+            .into_iter()
+            .map(|issue| issue.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ValidationIssueKind::InvalidCoordinate,
+                ValidationIssueKind::InconsistentDimensions
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn error_mode() {
+        let mut processor = ValidateProcessor::new(GeomStats::new());
+        let result = WktStr("POLYGON((0 0,1 0,1 1,0 1))").process_geom(&mut processor);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn feature_index() {
+        let mut geojson = GeoJson(
+            r#"{"type": "FeatureCollection", "features": [
+                {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [1, 2]}},
+                {"type": "Feature", "properties": {}, "geometry": {"type": "LineString", "coordinates": [[1, 2]]}}
+            ]}"#,
+        );
+        let mut processor = ValidateProcessor::with_mode(GeomStats::new(), ValidationMode::Warn);
+        geojson.process(&mut processor).unwrap();
+        let issues = processor.issues();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].feature, Some(1));
+        assert_eq!(
+            issues[0].to_string(),
+            "too few points (1, expected at least 2) in feature 1 at geometry 0"
+        );
+    }
+}
@@ -11,10 +11,14 @@
 //! assert_eq!(wkb.to_wkt().unwrap(), "POINT(10 -20)");
 //! ```
 pub(crate) mod wkb_common;
+mod wkb_error;
+pub(crate) mod wkb_limits;
 pub(crate) mod wkb_reader;
 pub(crate) mod wkb_writer;
 
 pub use wkb_common::*;
+pub use wkb_error::WkbError;
+pub use wkb_limits::WkbLimits;
 pub use wkb_reader::*;
 pub use wkb_writer::*;
 
@@ -3,12 +3,28 @@ use crate::{GeozeroGeometry, ToWkt};
 use std::fmt;
 use std::io::Read;
 
+#[cfg(feature = "with-postgis-diesel")]
+use crate::postgis::diesel::sql_types::{Geography, Geometry};
+#[cfg(feature = "with-postgis-diesel")]
+use diesel::{deserialize::FromSqlRow, expression::AsExpression};
+
 /// Encode to WKB
 // Used to impl encoding for foreign types
+#[cfg_attr(feature = "with-postgis-diesel", derive(AsExpression))]
+#[cfg_attr(feature = "with-postgis-diesel", diesel(sql_type = Geometry))]
+#[cfg_attr(feature = "with-postgis-diesel", diesel(sql_type = Geography))]
 pub struct Encode<T: GeozeroGeometry>(pub T);
 
+/// Encode to WKB as PostGIS `geography`
+///
+/// Geometries without SRID are encoded with SRID 4326, other SRIDs are rejected.
+#[cfg_attr(feature = "with-postgis-diesel", derive(AsExpression))]
+#[cfg_attr(feature = "with-postgis-diesel", diesel(sql_type = Geography))]
+pub struct EncodeGeography<T: GeozeroGeometry>(pub T);
+
 /// Decode from WKB
 // Used to impl decoding for foreign types
+#[cfg_attr(feature = "with-postgis-diesel", derive(FromSqlRow))]
 pub struct Decode<T: FromWkb> {
     /// Decoded geometry. `None` for `NULL` value.
     pub geometry: Option<T>,
@@ -21,6 +37,12 @@ impl<T: GeozeroGeometry + Sized> fmt::Debug for Encode<T> {
     }
 }
 
+impl<T: GeozeroGeometry + Sized> fmt::Debug for EncodeGeography<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0.to_wkt().unwrap_or("<unknown geometry>".to_string()))
+    }
+}
+
 // required by SQLx macros
 impl<T: FromWkb + Sized> fmt::Debug for Decode<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
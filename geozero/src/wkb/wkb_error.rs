@@ -0,0 +1,13 @@
+//! WKB error type.
+use crate::wkb::WKBGeometryType;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum WkbError {
+    #[error("invalid {0} header")]
+    InvalidHeader(&'static str),
+    #[error("unexpected geometry type {0:?}")]
+    UnexpectedGeometryType(WKBGeometryType),
+    #[error("trailing bytes after geometry")]
+    TrailingBytes,
+}
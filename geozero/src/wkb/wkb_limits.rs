@@ -0,0 +1,259 @@
+use crate::error::{GeozeroError, Result};
+use crate::{CoordDimensions, GeomProcessor};
+use std::cell::Cell;
+
+/// Limits protecting against malformed or hostile WKB input.
+///
+/// Declared element counts and nesting are checked before they are passed to the processor,
+/// so a corrupted header can't trigger excessive allocations or deep recursion.
+/// When reading from a byte buffer, declared element counts passed to the processor are
+/// additionally clamped to the number of elements fitting into the remaining input.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct WkbLimits {
+    /// Maximal nesting depth of geometries
+    pub max_depth: usize,
+    /// Maximal declared number of elements (coordinates, rings or members) of a geometry
+    pub max_elements: usize,
+    /// Maximal total number of coordinates
+    pub max_coords: usize,
+}
+
+impl Default for WkbLimits {
+    fn default() -> Self {
+        WkbLimits {
+            max_depth: 64,
+            max_elements: 1 << 24,
+            max_coords: 1 << 26,
+        }
+    }
+}
+
+impl WkbLimits {
+    /// No limits
+    pub const fn unlimited() -> Self {
+        WkbLimits {
+            max_depth: usize::MAX,
+            max_elements: usize::MAX,
+            max_coords: usize::MAX,
+        }
+    }
+}
+
+/// Checks geometry events against [WkbLimits] before forwarding them.
+pub(crate) struct LimitedProcessor<'a, P: GeomProcessor> {
+    inner: &'a mut P,
+    limits: &'a WkbLimits,
+    depth: usize,
+    coords: usize,
+    /// Input length and number of bytes read, if known
+    input: Option<(u64, &'a Cell<u64>)>,
+}
+
+/// Minimal encoded size of a coordinate (compressed SpatiaLite XY)
+const MIN_COORD_SIZE: u64 = 8;
+/// Minimal encoded size of a ring or member geometry
+const MIN_ELEMENT_SIZE: u64 = 4;
+
+impl<'a, P: GeomProcessor> LimitedProcessor<'a, P> {
+    pub(crate) fn new(inner: &'a mut P, limits: &'a WkbLimits) -> Self {
+        LimitedProcessor {
+            inner,
+            limits,
+            depth: 0,
+            coords: 0,
+            input: None,
+        }
+    }
+
+    /// Clamp declared sizes to the remaining input of length `len`, with `offset` bytes read.
+    pub(crate) fn with_input(mut self, len: usize, offset: &'a Cell<u64>) -> Self {
+        self.input = Some((len as u64, offset));
+        self
+    }
+
+    /// Check nesting and declared size, returning the size clamped to the remaining input
+    fn begin(&mut self, size: usize, element_size: u64) -> Result<usize> {
+        self.depth += 1;
+        if self.depth > self.limits.max_depth || size > self.limits.max_elements {
+            return Err(GeozeroError::GeometryFormat);
+        }
+        Ok(self.clamp(size, element_size))
+    }
+
+    fn clamp(&self, size: usize, element_size: u64) -> usize {
+        match self.input {
+            Some((len, offset)) => {
+                let remaining = len.saturating_sub(offset.get()) / element_size;
+                size.min(usize::try_from(remaining).unwrap_or(usize::MAX))
+            }
+            None => size,
+        }
+    }
+
+    fn coord(&mut self) -> Result<()> {
+        self.coords += 1;
+        if self.coords > self.limits.max_coords {
+            return Err(GeozeroError::GeometryFormat);
+        }
+        Ok(())
+    }
+}
+
+impl<P: GeomProcessor> GeomProcessor for LimitedProcessor<'_, P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.inner.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.inner.multi_dim()
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.inner.srid(srid)
+    }
+    fn envelope(&mut self, envelope: &[f64], dims: CoordDimensions, idx: usize) -> Result<()> {
+        self.inner.envelope(envelope, dims, idx)
+    }
+    fn size_hint_total_coords(&mut self, count: usize) -> Result<()> {
+        self.inner
+            .size_hint_total_coords(self.clamp(count, MIN_COORD_SIZE))
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.coord()?;
+        self.inner.xy(x, y, idx)
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.coord()?;
+        self.inner.coordinate(x, y, z, m, t, tm, idx)
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.inner.empty_point(idx)
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.begin(0, MIN_COORD_SIZE)?;
+        self.inner.point_begin(idx)
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.depth -= 1;
+        self.inner.point_end(idx)
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        let size = self.begin(size, MIN_COORD_SIZE)?;
+        self.inner.multipoint_begin(size, idx)
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.depth -= 1;
+        self.inner.multipoint_end(idx)
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        let size = self.begin(size, MIN_COORD_SIZE)?;
+        self.inner.linestring_begin(tagged, size, idx)
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.depth -= 1;
+        self.inner.linestring_end(tagged, idx)
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        let size = self.begin(size, MIN_ELEMENT_SIZE)?;
+        self.inner.multilinestring_begin(size, idx)
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.depth -= 1;
+        self.inner.multilinestring_end(idx)
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        let size = self.begin(size, MIN_ELEMENT_SIZE)?;
+        self.inner.polygon_begin(tagged, size, idx)
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.depth -= 1;
+        self.inner.polygon_end(tagged, idx)
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        let size = self.begin(size, MIN_ELEMENT_SIZE)?;
+        self.inner.multipolygon_begin(size, idx)
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.depth -= 1;
+        self.inner.multipolygon_end(idx)
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        let size = self.begin(size, MIN_ELEMENT_SIZE)?;
+        self.inner.geometrycollection_begin(size, idx)
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.depth -= 1;
+        self.inner.geometrycollection_end(idx)
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        let size = self.begin(size, MIN_COORD_SIZE)?;
+        self.inner.circularstring_begin(size, idx)
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        self.depth -= 1;
+        self.inner.circularstring_end(idx)
+    }
+    fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        let size = self.begin(size, MIN_ELEMENT_SIZE)?;
+        self.inner.compoundcurve_begin(size, idx)
+    }
+    fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        self.depth -= 1;
+        self.inner.compoundcurve_end(idx)
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        let size = self.begin(size, MIN_ELEMENT_SIZE)?;
+        self.inner.curvepolygon_begin(size, idx)
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.depth -= 1;
+        self.inner.curvepolygon_end(idx)
+    }
+    fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        let size = self.begin(size, MIN_ELEMENT_SIZE)?;
+        self.inner.multicurve_begin(size, idx)
+    }
+    fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        self.depth -= 1;
+        self.inner.multicurve_end(idx)
+    }
+    fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        let size = self.begin(size, MIN_ELEMENT_SIZE)?;
+        self.inner.multisurface_begin(size, idx)
+    }
+    fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        self.depth -= 1;
+        self.inner.multisurface_end(idx)
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        let size = self.begin(size, MIN_ELEMENT_SIZE)?;
+        self.inner.triangle_begin(tagged, size, idx)
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.depth -= 1;
+        self.inner.triangle_end(tagged, idx)
+    }
+    fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        let size = self.begin(size, MIN_ELEMENT_SIZE)?;
+        self.inner.polyhedralsurface_begin(size, idx)
+    }
+    fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        self.depth -= 1;
+        self.inner.polyhedralsurface_end(idx)
+    }
+    fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        let size = self.begin(size, MIN_ELEMENT_SIZE)?;
+        self.inner.tin_begin(size, idx)
+    }
+    fn tin_end(&mut self, idx: usize) -> Result<()> {
+        self.depth -= 1;
+        self.inner.tin_end(idx)
+    }
+}
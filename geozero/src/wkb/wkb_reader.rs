@@ -1,8 +1,10 @@
-use crate::error::{GeozeroError, Result};
-use crate::wkb::{WKBGeometryType, WkbDialect};
-use crate::{GeomProcessor, GeozeroGeometry};
+use crate::error::{ErrorPosition, GeozeroError, Result};
+use crate::wkb::wkb_limits::LimitedProcessor;
+use crate::wkb::{WKBGeometryType, WkbDialect, WkbError, WkbLimits};
+use crate::{CoordDimensions, GeomProcessor, GeozeroGeometry, SwapXY};
 use scroll::ctx::{FromCtx, SizeWith};
 use scroll::{Endian, IOread};
+use std::cell::Cell;
 use std::io::Read;
 
 #[cfg(feature = "with-postgis-diesel")]
@@ -15,7 +17,7 @@ pub struct Wkb<B: AsRef<[u8]>>(pub B);
 
 impl<B: AsRef<[u8]>> GeozeroGeometry for Wkb<B> {
     fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
-        process_wkb_geom(&mut self.0.as_ref(), processor)
+        process_wkb_blob(self.0.as_ref(), processor, WkbDialect::Wkb)
     }
 }
 
@@ -30,7 +32,10 @@ pub struct Ewkb<B: AsRef<[u8]>>(pub B);
 
 impl<B: AsRef<[u8]>> GeozeroGeometry for Ewkb<B> {
     fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
-        process_ewkb_geom(&mut self.0.as_ref(), processor)
+        process_wkb_blob(self.0.as_ref(), processor, WkbDialect::Ewkb)
+    }
+    fn srid(&self) -> Option<i32> {
+        read_ewkb_header(&mut self.0.as_ref()).ok()?.srid
     }
 }
 
@@ -39,7 +44,7 @@ pub struct GpkgWkb<B: AsRef<[u8]>>(pub B);
 
 impl<B: AsRef<[u8]>> GeozeroGeometry for GpkgWkb<B> {
     fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
-        process_gpkg_geom(&mut self.0.as_ref(), processor)
+        process_wkb_blob(self.0.as_ref(), processor, WkbDialect::Geopackage)
     }
 }
 
@@ -48,7 +53,7 @@ pub struct SpatiaLiteWkb<B: AsRef<[u8]>>(pub B);
 
 impl<B: AsRef<[u8]>> GeozeroGeometry for SpatiaLiteWkb<B> {
     fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
-        process_spatialite_geom(&mut self.0.as_ref(), processor)
+        process_wkb_blob(self.0.as_ref(), processor, WkbDialect::SpatiaLite)
     }
 }
 
@@ -57,73 +62,270 @@ pub struct MySQLWkb<B: AsRef<[u8]>>(pub B);
 
 impl<B: AsRef<[u8]>> GeozeroGeometry for MySQLWkb<B> {
     fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
-        process_mysql_geom(&mut self.0.as_ref(), processor)
+        process_wkb_blob(self.0.as_ref(), processor, WkbDialect::MySQL)
     }
 }
 
+/// Process WKB type geometry from a byte buffer, clamping declared sizes to its length.
+fn process_wkb_blob<P: GeomProcessor>(
+    blob: &[u8],
+    processor: &mut P,
+    dialect: WkbDialect,
+) -> Result<()> {
+    let limits = WkbLimits::default();
+    process_wkb_type_geom_opts(
+        &mut &blob[..],
+        processor,
+        dialect,
+        &limits,
+        false,
+        Some(blob.len()),
+    )
+}
+
 /// Process WKB geometry.
 pub fn process_wkb_geom<R: Read, P: GeomProcessor>(raw: &mut R, processor: &mut P) -> Result<()> {
-    let info = read_wkb_header(raw)?;
-    processor.srid(info.srid)?;
-    process_wkb_geom_n(raw, &info, read_wkb_nested_header, 0, processor)
+    process_wkb_type_geom(raw, processor, WkbDialect::Wkb)
 }
 
 /// Process EWKB geometry.
 pub fn process_ewkb_geom<R: Read, P: GeomProcessor>(raw: &mut R, processor: &mut P) -> Result<()> {
-    let info = read_ewkb_header(raw)?;
-    processor.srid(info.srid)?;
-    process_wkb_geom_n(raw, &info, read_ewkb_nested_header, 0, processor)
+    process_wkb_type_geom(raw, processor, WkbDialect::Ewkb)
 }
 
 /// Process GPKG geometry.
 pub fn process_gpkg_geom<R: Read, P: GeomProcessor>(raw: &mut R, processor: &mut P) -> Result<()> {
-    let info = read_gpkg_header(raw)?;
-    processor.srid(info.srid)?;
-    process_wkb_geom_n(raw, &info, read_wkb_nested_header, 0, processor)
+    process_wkb_type_geom(raw, processor, WkbDialect::Geopackage)
 }
 
-/// Process MySQL WKB geometry.
+/// Process SpatiaLite geometry.
 pub fn process_spatialite_geom<R: Read, P: GeomProcessor>(
     raw: &mut R,
     processor: &mut P,
 ) -> Result<()> {
-    let info = read_spatialite_header(raw)?;
-    processor.srid(info.srid)?;
-    process_wkb_geom_n(raw, &info, read_spatialite_nested_header, 0, processor)
+    process_wkb_type_geom(raw, processor, WkbDialect::SpatiaLite)
 }
 
 /// Process MySQL WKB geometry.
 pub fn process_mysql_geom<R: Read, P: GeomProcessor>(raw: &mut R, processor: &mut P) -> Result<()> {
+    process_wkb_type_geom(raw, processor, WkbDialect::MySQL)
+}
+
+/// Process MySQL WKB geometry with coordinates in latitude/longitude order, swapping X and Y.
+pub fn process_mysql_geom_swap_xy<R: Read, P: GeomProcessor>(
+    raw: &mut R,
+    processor: &mut P,
+) -> Result<()> {
+    let limits = WkbLimits::default();
+    let mut processor = SwapXY::new(LimitedProcessor::new(processor, &limits));
     let info = read_mysql_header(raw)?;
     processor.srid(info.srid)?;
-    process_wkb_geom_n(raw, &info, read_wkb_nested_header, 0, processor)
+    process_wkb_geom_n(raw, &info, read_wkb_nested_header, 0, &mut processor)
 }
 
 /// Process WKB type geometry..
+///
+/// Input is checked against the default [WkbLimits].
 pub fn process_wkb_type_geom<R: Read, P: GeomProcessor>(
     raw: &mut R,
     processor: &mut P,
     dialect: WkbDialect,
 ) -> Result<()> {
+    process_wkb_type_geom_with_limits(raw, processor, dialect, &WkbLimits::default())
+}
+
+/// Process WKB type geometry, returning [GeozeroError::GeometryFormat] if `limits` are exceeded.
+pub fn process_wkb_type_geom_with_limits<R: Read, P: GeomProcessor>(
+    raw: &mut R,
+    processor: &mut P,
+    dialect: WkbDialect,
+    limits: &WkbLimits,
+) -> Result<()> {
+    process_wkb_type_geom_opts(raw, processor, dialect, limits, false, None)
+}
+
+fn process_wkb_type_geom_opts<R: Read, P: GeomProcessor>(
+    raw: &mut R,
+    processor: &mut P,
+    dialect: WkbDialect,
+    limits: &WkbLimits,
+    strict: bool,
+    input_len: Option<usize>,
+) -> Result<()> {
+    let offset = Cell::new(0);
+    let raw = &mut OffsetReader {
+        inner: raw,
+        offset: &offset,
+    };
+    let mut processor = LimitedProcessor::new(processor, limits);
+    if let Some(len) = input_len {
+        processor = processor.with_input(len, &offset);
+    }
+    let processor = &mut processor;
     match dialect {
-        WkbDialect::Wkb => process_wkb_geom(raw, processor),
-        WkbDialect::Ewkb => process_ewkb_geom(raw, processor),
-        WkbDialect::Geopackage => process_gpkg_geom(raw, processor),
-        WkbDialect::SpatiaLite => process_spatialite_geom(raw, processor),
-        WkbDialect::MySQL => process_mysql_geom(raw, processor),
+        WkbDialect::Wkb => {
+            let info = read_wkb_header(raw)?;
+            processor.srid(info.srid)?;
+            process_wkb_geom_n(raw, &info, read_wkb_nested_header, 0, processor)
+        }
+        WkbDialect::Ewkb => {
+            let info = read_ewkb_header(raw)?;
+            processor.srid(info.srid)?;
+            if strict {
+                process_wkb_geom_n(raw, &info, read_ewkb_nested_header_strict, 0, processor)
+            } else {
+                process_wkb_geom_n(raw, &info, read_ewkb_nested_header, 0, processor)
+            }
+        }
+        WkbDialect::Geopackage => {
+            let info = read_gpkg_header(raw)?;
+            processor.srid(info.srid)?;
+            if !info.envelope.is_empty() {
+                processor.envelope(&info.envelope, info.envelope_dims, 0)?;
+            }
+            process_wkb_geom_n(raw, &info, read_wkb_nested_header, 0, processor)
+        }
+        WkbDialect::SpatiaLite => {
+            let info = read_spatialite_header(raw)?;
+            processor.srid(info.srid)?;
+            if let [minx, miny, maxx, maxy] = info.envelope[..] {
+                // Spatialite stores envelope as [minx, miny, maxx, maxy]
+                processor.envelope(&[minx, maxx, miny, maxy], info.envelope_dims, 0)?;
+            }
+            process_wkb_geom_n(raw, &info, read_spatialite_nested_header, 0, processor)
+        }
+        WkbDialect::MySQL => {
+            let info = read_mysql_header(raw)?;
+            processor.srid(info.srid)?;
+            process_wkb_geom_n(raw, &info, read_wkb_nested_header, 0, processor)
+        }
+    }
+}
+
+/// Process WKB type geometry in strict mode.
+///
+/// Errors are reported with the byte offset in the input where they occurred and trailing
+/// data after the geometry is rejected. SRIDs of nested EWKB geometries conflicting with
+/// the SRID of the parent geometry are reported as [GeozeroError::Srid].
+pub fn process_wkb_type_geom_strict<R: Read, P: GeomProcessor>(
+    raw: &mut R,
+    processor: &mut P,
+    dialect: WkbDialect,
+) -> Result<()> {
+    let offset = Cell::new(0);
+    let mut reader = OffsetReader {
+        inner: raw,
+        offset: &offset,
+    };
+    let limits = WkbLimits::default();
+    process_wkb_type_geom_opts(&mut reader, processor, dialect, &limits, true, None)
+        .map_err(|e| e.at(ErrorPosition::Offset(offset.get())))?;
+    let mut trailing = [0; 1];
+    if reader.read(&mut trailing)? > 0 {
+        return Err(
+            GeozeroError::from(WkbError::TrailingBytes).at(ErrorPosition::Offset(offset.get() - 1))
+        );
+    }
+    Ok(())
+}
+
+/// Reader keeping track of the number of bytes read
+struct OffsetReader<'a, R: Read> {
+    inner: &'a mut R,
+    offset: &'a Cell<u64>,
+}
+
+impl<R: Read> Read for OffsetReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.offset.set(self.offset.get() + n as u64);
+        Ok(n)
     }
 }
 
+/// Summary of a WKB geometry header.
+#[derive(Clone, PartialEq, Debug)]
+pub struct WkbHeader {
+    pub dialect: WkbDialect,
+    pub geometry_type: WKBGeometryType,
+    pub srid: Option<i32>,
+    pub has_z: bool,
+    pub has_m: bool,
+    /// Compressed linestrings and polygons (SpatiaLite)
+    pub compressed: bool,
+    /// TinyPoint encoding (SpatiaLite)
+    pub tiny_point: bool,
+    /// Envelope in the order of [GeomProcessor::envelope] (`[minx, maxx, miny, maxy, ...]`)
+    pub envelope: Vec<f64>,
+    pub envelope_dims: CoordDimensions,
+}
+
+/// Read the header of a WKB geometry without processing its coordinates.
+///
+/// For SpatiaLite collections, `compressed` is taken from the first member geometry.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::wkb::{self, WKBGeometryType, WkbDialect};
+///
+/// let blob = hex::decode("0001E6100000000000000000244000000000000034C0000000000000244000000000000034C07C01000000000000000000244000000000000034C0FE").unwrap();
+/// let header = wkb::header(&blob, WkbDialect::SpatiaLite).unwrap();
+/// assert_eq!(header.geometry_type, WKBGeometryType::Point);
+/// assert_eq!(header.srid, Some(4326));
+/// assert!(!header.compressed);
+/// ```
+pub fn header(blob: &[u8], dialect: WkbDialect) -> Result<WkbHeader> {
+    let raw = &mut &blob[..];
+    let mut info = match dialect {
+        WkbDialect::Wkb => read_wkb_header(raw)?,
+        WkbDialect::Ewkb => read_ewkb_header(raw)?,
+        WkbDialect::Geopackage => read_gpkg_header(raw)?,
+        WkbDialect::SpatiaLite => read_spatialite_header(raw)?,
+        WkbDialect::MySQL => read_mysql_header(raw)?,
+    };
+    let tiny_point =
+        dialect == WkbDialect::SpatiaLite && blob.get(1).is_some_and(|flags| flags & 0x80 != 0);
+    if dialect == WkbDialect::SpatiaLite
+        && !info.is_compressed
+        && matches!(
+            info.base_type,
+            WKBGeometryType::MultiLineString
+                | WKBGeometryType::MultiPolygon
+                | WKBGeometryType::GeometryCollection
+        )
+        && raw.ioread_with::<u32>(info.endian)? > 0
+    {
+        info.is_compressed = read_spatialite_nested_header(raw, &info)?.is_compressed;
+    }
+    let envelope = match (dialect, &info.envelope[..]) {
+        // Spatialite stores envelope as [minx, miny, maxx, maxy]
+        (WkbDialect::SpatiaLite, [minx, miny, maxx, maxy]) => vec![*minx, *maxx, *miny, *maxy],
+        _ => info.envelope,
+    };
+    Ok(WkbHeader {
+        dialect,
+        geometry_type: info.base_type,
+        srid: info.srid,
+        has_z: info.has_z,
+        has_m: info.has_m,
+        compressed: info.is_compressed,
+        tiny_point,
+        envelope,
+        envelope_dims: info.envelope_dims,
+    })
+}
+
 #[derive(Debug)]
 pub(crate) struct WkbInfo {
     endian: Endian,
     base_type: WKBGeometryType,
     has_z: bool,
     has_m: bool,
-    #[allow(dead_code)]
     srid: Option<i32>,
-    #[allow(dead_code)]
+    /// Envelope in header order of the dialect
     envelope: Vec<f64>,
+    envelope_dims: CoordDimensions,
     is_compressed: bool,
 }
 
@@ -143,6 +345,7 @@ pub(crate) fn read_wkb_header<R: Read>(raw: &mut R) -> Result<WkbInfo> {
         srid: None,
         is_compressed: false,
         envelope: Vec::new(),
+        envelope_dims: CoordDimensions::xy(),
     };
     Ok(info)
 }
@@ -171,6 +374,7 @@ fn read_ewkb_header<R: Read>(raw: &mut R) -> Result<WkbInfo> {
         srid,
         is_compressed: false,
         envelope: Vec::new(),
+        envelope_dims: CoordDimensions::xy(),
     };
     Ok(info)
 }
@@ -179,23 +383,37 @@ pub(crate) fn read_ewkb_nested_header<R: Read>(raw: &mut R, _info: &WkbInfo) ->
     read_ewkb_header(raw)
 }
 
+/// Nested EWKB header, rejecting SRIDs different from the parent SRID
+fn read_ewkb_nested_header_strict<R: Read>(raw: &mut R, info: &WkbInfo) -> Result<WkbInfo> {
+    let mut nested = read_ewkb_header(raw)?;
+    match (info.srid, nested.srid) {
+        (Some(srid), Some(nested_srid)) if srid != nested_srid => {
+            return Err(GeozeroError::Srid(nested_srid))
+        }
+        (Some(_), None) => nested.srid = info.srid,
+        _ => {}
+    }
+    Ok(nested)
+}
+
 /// GPKG geometry header according to http://www.geopackage.org/spec/#gpb_format
 fn read_gpkg_header<R: Read>(raw: &mut R) -> Result<WkbInfo> {
     let magic = [raw.ioread::<u8>()?, raw.ioread::<u8>()?];
     if &magic != b"GP" {
-        return Err(GeozeroError::GeometryFormat);
+        return Err(WkbError::InvalidHeader("GeoPackage").into());
     }
     let _version = raw.ioread::<u8>()?;
     let flags = raw.ioread::<u8>()?;
     // println!("flags: {flags:#010b}");
     let _extended = (flags & 0b0010_0000) >> 5 == 1;
     let _empty = (flags & 0b0001_0000) >> 4 == 1;
-    let env_len = match (flags & 0b0000_1110) >> 1 {
-        0 => 0,
-        1 => 4,
-        2 | 3 => 6,
-        4 => 8,
-        _ => Err(GeozeroError::GeometryFormat)?,
+    let (env_len, envelope_dims) = match (flags & 0b0000_1110) >> 1 {
+        0 => (0, CoordDimensions::xy()),
+        1 => (4, CoordDimensions::xy()),
+        2 => (6, CoordDimensions::xyz()),
+        3 => (6, CoordDimensions::xym()),
+        4 => (8, CoordDimensions::xyzm()),
+        _ => Err(WkbError::InvalidHeader("GeoPackage"))?,
     };
     let is_little_endian = flags & 0b0000_0001 != 0;
     let endian = Endian::from(is_little_endian);
@@ -215,6 +433,7 @@ fn read_gpkg_header<R: Read>(raw: &mut R) -> Result<WkbInfo> {
         srid: Some(srid),
         is_compressed: false,
         envelope,
+        envelope_dims,
     };
     Ok(info)
 }
@@ -223,7 +442,7 @@ fn read_gpkg_header<R: Read>(raw: &mut R) -> Result<WkbInfo> {
 pub(crate) fn read_spatialite_header<R: Read>(raw: &mut R) -> Result<WkbInfo> {
     let start = raw.ioread::<u8>()?;
     if start != 0 {
-        return Err(GeozeroError::GeometryFormat);
+        return Err(WkbError::InvalidHeader("SpatiaLite").into());
     }
     let flags = raw.ioread::<u8>()?;
     let is_little_endian = flags & 0b0000_0001 != 0;
@@ -241,6 +460,7 @@ pub(crate) fn read_spatialite_header<R: Read>(raw: &mut R) -> Result<WkbInfo> {
             endian,
             srid,
             envelope: Vec::<f64>::new(),
+            envelope_dims: CoordDimensions::xy(),
             base_type: WKBGeometryType::Point,
             has_z: matches!(type_id_dim, 2 | 4),
             has_m: matches!(type_id_dim, 3 | 4),
@@ -252,7 +472,7 @@ pub(crate) fn read_spatialite_header<R: Read>(raw: &mut R) -> Result<WkbInfo> {
             .collect::<std::result::Result<Vec<f64>, _>>()?;
         let mbr_end = raw.ioread_with::<u8>(endian)?;
         if mbr_end != 0x7C {
-            return Err(GeozeroError::GeometryFormat);
+            return Err(WkbError::InvalidHeader("SpatiaLite").into());
         }
         let type_id = raw.ioread_with::<u32>(endian)?;
         let type_id_dim = (type_id % 1000000) / 1000;
@@ -260,6 +480,7 @@ pub(crate) fn read_spatialite_header<R: Read>(raw: &mut R) -> Result<WkbInfo> {
             endian,
             srid,
             envelope,
+            envelope_dims: CoordDimensions::xy(),
             base_type: WKBGeometryType::from_u32(type_id % 1000),
             has_z: matches!(type_id_dim, 1 | 3),
             has_m: matches!(type_id_dim, 2 | 3),
@@ -276,7 +497,7 @@ pub(crate) fn read_spatialite_nested_header<R: Read>(
 ) -> Result<WkbInfo> {
     let start = raw.ioread::<u8>()?;
     if start != 0x69 {
-        return Err(GeozeroError::GeometryFormat);
+        return Err(WkbError::InvalidHeader("SpatiaLite").into());
     }
     let type_id = raw.ioread_with::<u32>(info.endian)?;
     Ok(WkbInfo {
@@ -285,6 +506,7 @@ pub(crate) fn read_spatialite_nested_header<R: Read>(
         endian: info.endian,
         srid: info.srid,
         envelope: Vec::new(),
+        envelope_dims: CoordDimensions::xy(),
         has_m: info.has_m,
         has_z: info.has_z,
     })
@@ -296,10 +518,10 @@ pub(crate) fn read_mysql_header<R: Read>(raw: &mut R) -> Result<WkbInfo> {
     let srid: i32 = raw
         .ioread_with::<u32>(valid_endian)?
         .try_into()
-        .map_err(|_| GeozeroError::GeometryFormat)?;
+        .map_err(|_| WkbError::InvalidHeader("MySQL"))?;
     let mut info = read_wkb_header(raw)?;
     if info.endian != valid_endian {
-        return Err(GeozeroError::GeometryFormat);
+        return Err(WkbError::InvalidHeader("MySQL").into());
     }
     info.srid = Some(srid);
     Ok(info)
@@ -403,7 +625,9 @@ pub(crate) fn process_wkb_geom_n<R: Read, P: GeomProcessor>(
                     WKBGeometryType::Polygon => {
                         process_polygon(raw, &info, false, i, processor)?;
                     }
-                    _ => return Err(GeozeroError::GeometryFormat),
+                    _ => {
+                        return Err(WkbError::UnexpectedGeometryType(info.base_type.clone()).into())
+                    }
                 }
             }
             processor.multisurface_end(idx)
@@ -418,7 +642,7 @@ pub(crate) fn process_wkb_geom_n<R: Read, P: GeomProcessor>(
             }
             processor.geometrycollection_end(idx)
         }
-        _ => Err(GeozeroError::GeometryFormat),
+        _ => Err(WkbError::UnexpectedGeometryType(info.base_type.clone()).into()),
     }
 }
 
@@ -472,6 +696,44 @@ fn process_compressed_coord<R: Read, P: GeomProcessor>(
     }
 }
 
+/// Number of coordinates read at once by `process_coords`
+const COORDS_CHUNK_SIZE: usize = 1024;
+
+/// Read `length` uncompressed coordinates in chunks, emitted with `coords`
+fn process_coords<R: Read, P: GeomProcessor>(
+    raw: &mut R,
+    info: &WkbInfo,
+    length: usize,
+    processor: &mut P,
+) -> Result<()> {
+    let dims = CoordDimensions {
+        z: info.has_z,
+        m: info.has_m,
+        ..Default::default()
+    };
+    let stride = 2 + usize::from(info.has_z) + usize::from(info.has_m);
+    let mut bytes = vec![0; length.min(COORDS_CHUNK_SIZE) * stride * 8];
+    let mut coords = Vec::with_capacity(length.min(COORDS_CHUNK_SIZE) * stride);
+    let mut idx = 0;
+    while idx < length {
+        let count = (length - idx).min(COORDS_CHUNK_SIZE);
+        let bytes = &mut bytes[..count * stride * 8];
+        raw.read_exact(bytes)?;
+        coords.clear();
+        coords.extend(bytes.chunks_exact(8).map(|b| {
+            let b = [b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]];
+            if info.endian.is_little() {
+                f64::from_le_bytes(b)
+            } else {
+                f64::from_be_bytes(b)
+            }
+        }));
+        processor.coords(&coords, dims, idx)?;
+        idx += count;
+    }
+    Ok(())
+}
+
 fn read_coord_as<R: Read, T: Into<f64> + FromCtx<Endian> + SizeWith<Endian>>(
     raw: &mut R,
     info: &WkbInfo,
@@ -508,9 +770,7 @@ fn process_linestring<R: Read, P: GeomProcessor>(
         }
         process_coord(raw, info, multi, length, processor)?;
     } else {
-        for i in 0..length {
-            process_coord(raw, info, multi, i, processor)?;
-        }
+        process_coords(raw, info, length, processor)?;
     }
     processor.linestring_end(tagged, idx)
 }
@@ -523,10 +783,7 @@ fn process_circularstring<R: Read, P: GeomProcessor>(
 ) -> Result<()> {
     let length = raw.ioread_with::<u32>(info.endian)? as usize;
     processor.circularstring_begin(length, idx)?;
-    let multi = processor.multi_dim();
-    for i in 0..length {
-        process_coord(raw, info, multi, i, processor)?;
-    }
+    process_coords(raw, info, length, processor)?;
     processor.circularstring_end(idx)
 }
 
@@ -578,7 +835,7 @@ fn process_compoundcurve<R: Read, P: GeomProcessor>(
             WKBGeometryType::LineString => {
                 process_linestring(raw, &info, false, i, processor)?;
             }
-            _ => return Err(GeozeroError::GeometryFormat),
+            _ => return Err(WkbError::UnexpectedGeometryType(info.base_type.clone()).into()),
         }
     }
     processor.compoundcurve_end(idx)
@@ -598,7 +855,7 @@ fn process_curve<R: Read, P: GeomProcessor>(
         WKBGeometryType::CompoundCurve => {
             process_compoundcurve(raw, &info, read_header, idx, processor)
         }
-        _ => Err(GeozeroError::GeometryFormat),
+        _ => Err(WkbError::UnexpectedGeometryType(info.base_type.clone()).into()),
     }
 }
 
@@ -624,6 +881,162 @@ mod test {
     use crate::wkt::WktWriter;
     use crate::{CoordDimensions, ToWkt};
 
+    #[test]
+    fn hostile_input() {
+        // LINESTRING claiming u32::MAX points
+        let wkb = hex::decode("0102000000FFFFFFFF").unwrap();
+        let mut wkt_data: Vec<u8> = Vec::new();
+        let result = process_wkb_geom(&mut wkb.as_slice(), &mut WktWriter::new(&mut wkt_data));
+        assert!(matches!(result, Err(GeozeroError::GeometryFormat)));
+        assert!(wkt_data.is_empty());
+
+        // 100 nested GEOMETRYCOLLECTIONs
+        let mut wkb = hex::decode("010700000001000000").unwrap().repeat(100);
+        wkb.extend(hex::decode("0101000000000000000000F03F0000000000000040").unwrap());
+        let result = process_wkb_geom(&mut wkb.as_slice(), &mut WktWriter::new(Vec::new()));
+        assert!(matches!(result, Err(GeozeroError::GeometryFormat)));
+        let limits = WkbLimits::unlimited();
+        let mut writer = WktWriter::new(Vec::new());
+        let result = process_wkb_type_geom_with_limits(
+            &mut wkb.as_slice(),
+            &mut writer,
+            WkbDialect::Wkb,
+            &limits,
+        );
+        assert!(result.is_ok());
+
+        // MULTIPOINT(1 2,3 4)
+        let wkb = hex::decode("0104000000020000000101000000000000000000F03F0000000000000040010100000000000000000008400000000000001040").unwrap();
+        let limits = WkbLimits {
+            max_coords: 1,
+            ..Default::default()
+        };
+        let mut writer = WktWriter::new(Vec::new());
+        let result = process_wkb_type_geom_with_limits(
+            &mut wkb.as_slice(),
+            &mut writer,
+            WkbDialect::Wkb,
+            &limits,
+        );
+        assert!(matches!(result, Err(GeozeroError::GeometryFormat)));
+    }
+
+    #[test]
+    fn clamp_declared_size() {
+        struct SizeRecorder(Vec<usize>);
+        impl GeomProcessor for SizeRecorder {
+            fn linestring_begin(&mut self, _tagged: bool, size: usize, _idx: usize) -> Result<()> {
+                self.0.push(size);
+                Ok(())
+            }
+        }
+
+        // LINESTRING claiming 1000 points, containing one
+        let wkb = hex::decode("0102000000E8030000000000000000F03F0000000000000040").unwrap();
+        let mut recorder = SizeRecorder(Vec::new());
+        assert!(Wkb(&wkb).process_geom(&mut recorder).is_err());
+        assert_eq!(recorder.0, vec![2]);
+    }
+
+    #[test]
+    fn strict_mode() {
+        // POINT(1 2) truncated after x
+        let wkb = hex::decode("0101000000000000000000F03F00").unwrap();
+        let mut writer = WktWriter::new(Vec::new());
+        let err = process_wkb_type_geom_strict(&mut wkb.as_slice(), &mut writer, WkbDialect::Wkb)
+            .unwrap_err();
+        assert_eq!(err.position(), Some(ErrorPosition::Offset(14)));
+
+        // POINT(1 2) with trailing byte
+        let wkb = hex::decode("0101000000000000000000F03F000000000000004000").unwrap();
+        let mut writer = WktWriter::new(Vec::new());
+        let err = process_wkb_type_geom_strict(&mut wkb.as_slice(), &mut writer, WkbDialect::Wkb)
+            .unwrap_err();
+        assert_eq!(err.position(), Some(ErrorPosition::Offset(21)));
+        assert_eq!(
+            err.to_string(),
+            "WKB error `trailing bytes after geometry` at byte offset 21"
+        );
+
+        let wkb = hex::decode("0101000000000000000000F03F0000000000000040").unwrap();
+        let mut writer = WktWriter::new(Vec::new());
+        assert!(
+            process_wkb_type_geom_strict(&mut wkb.as_slice(), &mut writer, WkbDialect::Wkb).is_ok()
+        );
+    }
+
+    #[test]
+    fn typed_errors() {
+        use std::error::Error;
+
+        // Invalid GeoPackage magic
+        let wkb = hex::decode("5850000101000000000000000000F03F0000000000000040").unwrap();
+        let mut writer = WktWriter::new(Vec::new());
+        let err = process_gpkg_geom(&mut wkb.as_slice(), &mut writer).unwrap_err();
+        assert!(matches!(
+            err,
+            GeozeroError::WkbError(WkbError::InvalidHeader("GeoPackage"))
+        ));
+
+        // CURVEPOLYGON containing a POINT
+        let wkb =
+            hex::decode("010A000000010000000101000000000000000000F03F0000000000000040").unwrap();
+        let err = process_wkb_geom(&mut wkb.as_slice(), &mut writer).unwrap_err();
+        assert!(matches!(
+            err,
+            GeozeroError::WkbError(WkbError::UnexpectedGeometryType(WKBGeometryType::Point))
+        ));
+
+        // Source chain of positioned error
+        let wkb = hex::decode("0101000000000000000000F03F000000000000004000").unwrap();
+        let err = process_wkb_type_geom_strict(&mut wkb.as_slice(), &mut writer, WkbDialect::Wkb)
+            .unwrap_err();
+        let source = err.source().unwrap();
+        assert!(matches!(
+            source.downcast_ref::<GeozeroError>(),
+            Some(GeozeroError::WkbError(WkbError::TrailingBytes))
+        ));
+        assert!(source.source().unwrap().is::<WkbError>());
+    }
+
+    #[test]
+    fn ewkb_srid() {
+        // SELECT 'SRID=3857;POINT(10 20)'::geometry
+        let ewkb = hex::decode("0101000020110F000000000000000024400000000000003440").unwrap();
+        assert_eq!(Ewkb(&ewkb).srid(), Some(3857));
+        // SELECT 'POINT(10 20)'::geometry
+        let ewkb = hex::decode("010100000000000000000024400000000000003440").unwrap();
+        assert_eq!(Ewkb(&ewkb).srid(), None);
+    }
+
+    #[test]
+    fn strict_nested_srid() {
+        // SRID=4326;MULTIPOINT(10 -20, 0 -0.5) with SRID in nested headers
+        let ewkb = hex::decode("0104000020E6100000020000000101000020E6100000000000000000244000000000000034C00101000020E61000000000000000000000000000000000E0BF").unwrap();
+        let mut writer = WktWriter::new(Vec::new());
+        assert!(
+            process_wkb_type_geom_strict(&mut ewkb.as_slice(), &mut writer, WkbDialect::Ewkb)
+                .is_ok()
+        );
+        assert_eq!(
+            std::str::from_utf8(&writer.out).unwrap(),
+            "MULTIPOINT(10 -20,0 -0.5)"
+        );
+
+        // Second point with SRID 3857
+        let ewkb = hex::decode("0104000020E6100000020000000101000020E6100000000000000000244000000000000034C00101000020110F00000000000000000000000000000000E0BF").unwrap();
+        let mut writer = WktWriter::new(Vec::new());
+        let err = process_wkb_type_geom_strict(&mut ewkb.as_slice(), &mut writer, WkbDialect::Ewkb)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid SRID value `3857` at byte offset 47"
+        );
+        // Accepted in non-strict mode
+        let mut writer = WktWriter::new(Vec::new());
+        assert!(process_ewkb_geom(&mut ewkb.as_slice(), &mut writer).is_ok());
+    }
+
     #[test]
     fn ewkb_format() {
         // SELECT 'POINT(10 -20 100 1)'::geometry
@@ -921,6 +1334,11 @@ mod test {
         );
         assert_eq!(std::str::from_utf8(&wkt_data).unwrap(), "POINT(10 -20)");
 
+        let mut wkt_data: Vec<u8> = Vec::new();
+        process_mysql_geom_swap_xy(&mut ewkb.as_slice(), &mut WktWriter::new(&mut wkt_data))
+            .unwrap();
+        assert_eq!(std::str::from_utf8(&wkt_data).unwrap(), "POINT(-20 10)");
+
         // SELECT HEX(ST_GeomFromText('MULTILINESTRING((20 10,10 20))', 0, 'axis-order=long-lat'));
         let wkb = hex::decode("000000000105000000010000000102000000020000000000000000003440000000000000244000000000000024400000000000003440").unwrap();
         let info = read_mysql_header(&mut wkb.as_slice()).unwrap();
@@ -952,6 +1370,51 @@ mod test {
         );
     }
 
+    #[test]
+    fn header_summary() {
+        use crate::wkb::WkbWriter;
+
+        let spatialite = |wkt: &str, compress: bool, tiny_point: bool| {
+            let mut blob = Vec::new();
+            let mut writer = WkbWriter::with_opts(
+                &mut blob,
+                WkbDialect::SpatiaLite,
+                CoordDimensions::xy(),
+                Some(4326),
+                vec![1.0, 2.0, 3.0, 4.0],
+            );
+            writer.set_compress(compress);
+            writer.set_tiny_point(tiny_point);
+            crate::wkt::read_wkt(&mut wkt.as_bytes(), &mut writer).unwrap();
+            header(&blob, WkbDialect::SpatiaLite).unwrap()
+        };
+
+        let info = spatialite("LINESTRING(1 2,2 3,3 4)", true, false);
+        assert_eq!(info.geometry_type, WKBGeometryType::LineString);
+        assert_eq!(info.srid, Some(4326));
+        assert!(info.compressed);
+        assert!(!info.tiny_point);
+        assert_eq!(info.envelope, vec![1.0, 3.0, 2.0, 4.0]);
+
+        let info = spatialite("MULTILINESTRING((1 2,2 3,3 4))", true, false);
+        assert_eq!(info.geometry_type, WKBGeometryType::MultiLineString);
+        assert!(info.compressed);
+        let info = spatialite("MULTILINESTRING((1 2,2 3,3 4))", false, false);
+        assert!(!info.compressed);
+
+        let info = spatialite("POINT(1 2)", false, true);
+        assert!(info.tiny_point);
+        assert!(info.envelope.is_empty());
+
+        // mln3dzm
+        let wkb = hex::decode("47500003E6100000000000000000244000000000000034400000000000002440000000000000344001BD0B00000100000001BA0B0000020000000000000000003440000000000000244000000000000008400000000000001440000000000000244000000000000034400000000000001C400000000000000040").unwrap();
+        let info = header(&wkb, WkbDialect::Geopackage).unwrap();
+        assert_eq!(info.geometry_type, WKBGeometryType::MultiLineString);
+        assert!(info.has_z && info.has_m);
+        assert_eq!(info.envelope, vec![10.0, 20.0, 10.0, 20.0]);
+        assert!(!info.compressed);
+    }
+
     #[test]
     fn gpkg_geometries() {
         // pt2d
@@ -1,16 +1,32 @@
 use crate::error::{GeozeroError, Result};
+use crate::geometry_processor::process_coords;
 use crate::wkb::{WKBByteOrder, WKBGeometryType, WkbDialect};
 use crate::{CoordDimensions, FeatureProcessor, GeomProcessor, PropertyProcessor};
 use scroll::IOwrite;
 use std::io::Write;
 
 /// WKB writer.
+///
+/// Small writes are batched in an internal buffer, which is written to the output at the end of
+/// each linestring, ring and geometry. Wrapping the output into a `BufWriter` is not required.
 pub struct WkbWriter<W: Write> {
     /// Coordinate dimensions to write
     dims: CoordDimensions,
     /// Coordinate dimensions which should be read
     read_dims: CoordDimensions,
     srid: Option<i32>,
+    /// Replace configured SRID with SRID of processed geometry
+    srid_override: bool,
+    /// Write SRID in nested EWKB headers
+    nested_srid: bool,
+    /// Compressed linestrings and polygons (SpatiaLite)
+    compress: bool,
+    /// Write points as TinyPoint (SpatiaLite)
+    tiny_point: bool,
+    /// Position and size of current linestring or ring, if compressed
+    compressed_line: Option<(usize, usize)>,
+    /// Previous coordinate of compressed linestring or ring
+    prev_coord: (f64, f64, f64),
     /// Geometry envelope (GPKG)
     envelope: Vec<f64>,
     /// Envelope dimensions (GPKG)
@@ -24,6 +40,8 @@ pub struct WkbWriter<W: Write> {
     first_header: bool,
     geom_state: GeomState,
     nesting_level: u32,
+    /// Scratch buffer, flushed at the end of each geometry part
+    buf: Vec<u8>,
     out: W,
 }
 
@@ -83,6 +101,12 @@ impl<W: Write> WkbWriter<W> {
             dims,
             read_dims,
             srid,
+            srid_override: false,
+            nested_srid: false,
+            compress: false,
+            tiny_point: false,
+            compressed_line: None,
+            prev_coord: (0.0, 0.0, 0.0),
             envelope,
             envelope_dims,
             extended_gpkg,
@@ -92,10 +116,46 @@ impl<W: Write> WkbWriter<W> {
             first_header: true,
             geom_state: GeomState::Normal,
             nesting_level: 0,
+            buf: Vec::new(),
             out,
         }
     }
 
+    /// Let SRID of processed geometry override the configured SRID (default: false)
+    ///
+    /// Without override, the SRID of processed geometries is only used if no SRID is configured.
+    pub fn set_srid_override(&mut self, enabled: bool) {
+        self.srid_override = enabled;
+    }
+
+    /// Write SRID in EWKB headers of nested geometries (default: false)
+    ///
+    /// PostGIS writes the SRID in the outer header only, but some consumers expect
+    /// the SRID flag in the headers of all sub-geometries.
+    pub fn set_nested_srid(&mut self, enabled: bool) {
+        self.nested_srid = enabled;
+    }
+
+    /// Write compressed linestrings and polygons (default: false)
+    ///
+    /// Intermediate vertices are stored as `f32` deltas like SpatiaLite's `CompressGeometry`.
+    /// Only supported by the SpatiaLite dialect.
+    pub fn set_compress(&mut self, enabled: bool) {
+        self.compress = enabled;
+    }
+
+    /// Write points in the compact TinyPoint encoding (default: false)
+    ///
+    /// Only supported by the SpatiaLite dialect.
+    pub fn set_tiny_point(&mut self, enabled: bool) {
+        self.tiny_point = enabled;
+    }
+
+    /// Consume the writer and return the underlying output
+    pub(crate) fn into_inner(self) -> W {
+        self.out
+    }
+
     /// Write header in selected format
     fn write_header(&mut self, wkb_type: WKBGeometryType) -> Result<()> {
         match self.dialect {
@@ -122,7 +182,7 @@ impl<W: Write> WkbWriter<W> {
     /// OGC WKB header
     fn write_wkb_header(&mut self, wkb_type: WKBGeometryType) -> Result<()> {
         let byte_order: WKBByteOrder = self.endian.into();
-        self.out.iowrite(byte_order as u8)?;
+        self.buf.iowrite(byte_order as u8)?;
         let mut type_id = wkb_type as u32;
         if self.dims.z {
             type_id += 1000;
@@ -130,14 +190,14 @@ impl<W: Write> WkbWriter<W> {
         if self.dims.m {
             type_id += 2000;
         }
-        self.out.iowrite_with(type_id, self.endian)?;
+        self.buf.iowrite_with(type_id, self.endian)?;
         Ok(())
     }
 
     /// EWKB header according to https://git.osgeo.org/gitea/postgis/postgis/src/branch/master/doc/ZMSgeoms.txt
     fn write_ewkb_header(&mut self, wkb_type: WKBGeometryType) -> Result<()> {
         let byte_order: WKBByteOrder = self.endian.into();
-        self.out.iowrite(byte_order as u8)?;
+        self.buf.iowrite(byte_order as u8)?;
 
         let mut type_id = wkb_type as u32;
         if self.dims.z {
@@ -146,18 +206,16 @@ impl<W: Write> WkbWriter<W> {
         if self.dims.m {
             type_id |= 0x4000_0000;
         }
-        if self.srid.is_some() && self.first_header {
+        // write SRID in main header only, unless nested SRIDs are enabled
+        let srid = self.srid.filter(|_| self.first_header || self.nested_srid);
+        if srid.is_some() {
             type_id |= 0x2000_0000;
         }
-        self.out.iowrite_with(type_id, self.endian)?;
-
-        if self.first_header {
-            // write SRID in main header only
-            if let Some(srid) = self.srid {
-                self.out.iowrite_with(srid, self.endian)?;
-            }
-            self.first_header = false;
+        self.buf.iowrite_with(type_id, self.endian)?;
+        if let Some(srid) = srid {
+            self.buf.iowrite_with(srid, self.endian)?;
         }
+        self.first_header = false;
 
         Ok(())
     }
@@ -165,9 +223,9 @@ impl<W: Write> WkbWriter<W> {
     /// GPKG geometry header according to http://www.geopackage.org/spec/#gpb_format
     fn write_gpkg_header(&mut self) -> Result<()> {
         let magic = b"GP";
-        self.out.write_all(magic)?;
+        self.buf.write_all(magic)?;
         let version: u8 = 0;
-        self.out.iowrite(version)?;
+        self.buf.iowrite(version)?;
 
         let mut flags: u8 = 0;
         if self.extended_gpkg {
@@ -175,10 +233,13 @@ impl<W: Write> WkbWriter<W> {
         }
         if self.empty {
             flags |= 0b0001_0000;
+            // Empty geometries have no envelope
+            self.envelope.clear();
         }
         let env_info: u8 = if self.envelope.is_empty() {
             0 // no envelope
         } else {
+            self.envelope_dims = self.gpkg_envelope_dims()?;
             match (self.envelope_dims.z, self.envelope_dims.m) {
                 (false, false) => 1, // [minx, maxx, miny, maxy]
                 (true, false) => 2,  // [minx, maxx, miny, maxy, minz, maxz]
@@ -191,38 +252,72 @@ impl<W: Write> WkbWriter<W> {
             flags |= 0b0000_0001;
         }
         // println!("flags: {flags:#010b}");
-        self.out.iowrite(flags)?;
+        self.buf.iowrite(flags)?;
 
         // srs_id
         // 0: undefined geographic coordinate reference systems
         // -1: undefined Cartesian coordinate reference systems
-        self.out.iowrite_with(self.srid.unwrap_or(0), self.endian)?;
+        self.buf.iowrite_with(self.srid.unwrap_or(0), self.endian)?;
 
         for val in &self.envelope {
-            self.out.iowrite_with(*val, self.endian)?;
+            self.buf.iowrite_with(*val, self.endian)?;
         }
 
         Ok(())
     }
 
+    /// GPKG envelope dimensions matching the envelope length
+    fn gpkg_envelope_dims(&self) -> Result<CoordDimensions> {
+        match self.envelope.len() {
+            4 => Ok(CoordDimensions::xy()),
+            6 => {
+                // Prefer configured envelope dimensions, then written dimensions
+                let dims = if self.envelope_dims.z || self.envelope_dims.m {
+                    self.envelope_dims
+                } else {
+                    self.dims
+                };
+                if dims.m && !dims.z {
+                    Ok(CoordDimensions::xym())
+                } else {
+                    Ok(CoordDimensions::xyz())
+                }
+            }
+            8 => Ok(CoordDimensions::xyzm()),
+            len => Err(GeozeroError::Geometry(format!(
+                "Invalid GPKG envelope length {len}"
+            ))),
+        }
+    }
+
+    /// Mark geometry as empty (GPKG), if the outermost geometry has no elements
+    fn check_empty(&mut self, size: usize) {
+        if self.first_header && size == 0 {
+            self.empty = true;
+        }
+    }
+
     /// Spatialite WKB header according to https://www.gaia-gis.it/gaia-sins/BLOB-Geometry.html
     fn write_spatialite_header(&mut self, wkb_type: WKBGeometryType) -> Result<()> {
+        if self.first_header && self.tiny_point && wkb_type == WKBGeometryType::Point {
+            return self.write_tiny_point_header();
+        }
         if self.first_header {
-            self.out.iowrite::<u8>(0)?;
+            self.buf.iowrite::<u8>(0)?;
             let byte_order: WKBByteOrder = self.endian.into();
-            self.out.iowrite(byte_order as u8)?;
-            self.out.iowrite(self.srid.unwrap_or(0))?;
+            self.buf.iowrite(byte_order as u8)?;
+            self.buf.iowrite(self.srid.unwrap_or(0))?;
 
             let envelope = Some(&self.envelope).filter(|e| !e.is_empty());
             for val in envelope.unwrap_or(&vec![0.0, 0.0, 0.0, 0.0]) {
-                self.out.iowrite_with(*val, self.endian)?;
+                self.buf.iowrite_with(*val, self.endian)?;
             }
 
-            self.out.iowrite::<u8>(0x7C)?;
+            self.buf.iowrite::<u8>(0x7C)?;
 
             self.first_header = false;
         } else {
-            self.out.iowrite::<u8>(0x69)?;
+            self.buf.iowrite::<u8>(0x69)?;
         }
 
         let mut type_id = wkb_type as u32;
@@ -232,34 +327,80 @@ impl<W: Write> WkbWriter<W> {
         if self.dims.m {
             type_id += 2000;
         }
+        if self.compress
+            && matches!(
+                wkb_type,
+                WKBGeometryType::LineString | WKBGeometryType::Polygon
+            )
+        {
+            type_id += 1_000_000;
+        }
         if self.srid.is_some() && self.first_header {
             type_id |= 0x2000_0000;
         }
-        self.out.iowrite_with(type_id, self.endian)?;
+        self.buf.iowrite_with(type_id, self.endian)?;
 
         Ok(())
     }
 
+    /// Write coordinate as delta to the previous vertex, if not first or last vertex of a compressed line
+    fn write_compressed_coord(&mut self, x: f64, y: f64, z: f64) -> Result<bool> {
+        let Some((pos, size)) = self.compressed_line.as_mut() else {
+            return Ok(false);
+        };
+        let delta = *pos > 0 && *pos + 1 < *size;
+        *pos += 1;
+        if delta {
+            let (prev_x, prev_y, prev_z) = self.prev_coord;
+            self.buf.iowrite_with((x - prev_x) as f32, self.endian)?;
+            self.buf.iowrite_with((y - prev_y) as f32, self.endian)?;
+            if self.dims.z {
+                self.buf.iowrite_with((z - prev_z) as f32, self.endian)?;
+            }
+        }
+        self.prev_coord = (x, y, z);
+        Ok(delta)
+    }
+
+    /// Spatialite TinyPoint header
+    fn write_tiny_point_header(&mut self) -> Result<()> {
+        self.buf.iowrite::<u8>(0)?;
+        let byte_order: WKBByteOrder = self.endian.into();
+        self.buf.iowrite(byte_order as u8 | 0x80)?;
+        self.buf.iowrite_with(self.srid.unwrap_or(0), self.endian)?;
+        let type_id: u8 = match (self.dims.z, self.dims.m) {
+            (false, false) => 1,
+            (true, false) => 2,
+            (false, true) => 3,
+            (true, true) => 4,
+        };
+        self.buf.iowrite(type_id)?;
+        self.first_header = false;
+        Ok(())
+    }
+
     /// MySQL WKB header according to https://dev.mysql.com/doc/refman/8.0/en/gis-data-formats.html
     fn write_mysql_header(&mut self) -> Result<()> {
         let srid: u32 = match self.srid {
             None => 0,
             Some(v) => v.try_into().map_err(|_| GeozeroError::Srid(v))?,
         };
-        self.out.iowrite_with(srid, self.endian)?;
+        self.buf.iowrite_with(srid, self.endian)?;
         Ok(())
     }
 
-    /// Write header in selected format
+    /// Write footer in selected format and flush buffered output
     fn write_footer(&mut self) -> Result<()> {
         match self.dialect {
             WkbDialect::SpatiaLite => {
                 if self.nesting_level == 0 {
-                    self.out.iowrite::<u8>(0xFE)?;
+                    self.buf.iowrite::<u8>(0xFE)?;
                 }
             }
             WkbDialect::Wkb | WkbDialect::Ewkb | WkbDialect::Geopackage | WkbDialect::MySQL => {}
         }
+        self.out.write_all(&self.buf)?;
+        self.buf.clear();
         Ok(())
     }
 }
@@ -268,34 +409,99 @@ impl<W: Write> GeomProcessor for WkbWriter<W> {
     fn dimensions(&self) -> CoordDimensions {
         self.read_dims
     }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        if self.first_header && (self.srid.is_none() || self.srid_override) {
+            self.srid = srid.filter(|srid| *srid != 0).or(self.srid);
+        }
+        Ok(())
+    }
+    fn envelope(&mut self, envelope: &[f64], dims: CoordDimensions, _idx: usize) -> Result<()> {
+        // Configured envelope takes precedence
+        if !self.first_header || !self.envelope.is_empty() {
+            return Ok(());
+        }
+        match self.dialect {
+            WkbDialect::Geopackage => {
+                self.envelope = envelope.to_vec();
+                self.envelope_dims = dims;
+            }
+            WkbDialect::SpatiaLite => {
+                if let [minx, maxx, miny, maxy, ..] = envelope[..] {
+                    self.envelope = vec![minx, miny, maxx, maxy];
+                }
+            }
+            WkbDialect::Wkb | WkbDialect::Ewkb | WkbDialect::MySQL => {}
+        }
+        Ok(())
+    }
     fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
         self.coordinate(x, y, None, None, None, None, idx)
     }
+    fn coords(&mut self, coords: &[f64], dims: CoordDimensions, idx: usize) -> Result<()> {
+        // Copy ordinates directly if the layout matches the output
+        if dims.z != self.dims.z
+            || dims.m != self.dims.m
+            || dims.t
+            || self.compressed_line.is_some()
+            || self.geom_state == GeomState::MultiPointGeom
+        {
+            return process_coords(self, coords, dims, idx);
+        }
+        self.buf.reserve(coords.len() * 8);
+        if self.endian.is_little() {
+            for v in coords {
+                self.buf.extend_from_slice(&v.to_le_bytes());
+            }
+        } else {
+            for v in coords {
+                self.buf.extend_from_slice(&v.to_be_bytes());
+            }
+        }
+        Ok(())
+    }
     fn coordinate(
         &mut self,
         x: f64,
         y: f64,
         z: Option<f64>,
         m: Option<f64>,
-        _t: Option<f64>,
-        _tm: Option<u64>,
+        t: Option<f64>,
+        tm: Option<u64>,
         _idx: usize,
     ) -> Result<()> {
+        if (self.dims.t && t.is_some()) || (self.dims.tm && tm.is_some()) {
+            return Err(GeozeroError::Geometry(
+                "WKB doesn't support T and TM dimensions".to_string(),
+            ));
+        }
         if self.geom_state == GeomState::MultiPointGeom {
             self.write_header(WKBGeometryType::Point)?;
         }
-        self.out.iowrite_with(x, self.endian)?;
-        self.out.iowrite_with(y, self.endian)?;
-        if self.dims.z {
-            let z = z.unwrap_or(0.0);
-            self.out.iowrite_with(z, self.endian)?;
+        let z = z.unwrap_or(0.0);
+        if !self.write_compressed_coord(x, y, z)? {
+            self.buf.iowrite_with(x, self.endian)?;
+            self.buf.iowrite_with(y, self.endian)?;
+            if self.dims.z {
+                self.buf.iowrite_with(z, self.endian)?;
+            }
         }
         if self.dims.m {
             let m = m.unwrap_or(0.0);
-            self.out.iowrite_with(m, self.endian)?;
+            self.buf.iowrite_with(m, self.endian)?;
         }
         Ok(())
     }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        // Empty points are encoded with NaN coordinates
+        let nan = Some(f64::NAN);
+        if self.geom_state == GeomState::MultiPointGeom {
+            return self.coordinate(f64::NAN, f64::NAN, nan, nan, None, None, idx);
+        }
+        self.check_empty(0);
+        self.point_begin(idx)?;
+        self.coordinate(f64::NAN, f64::NAN, nan, nan, None, None, idx)?;
+        self.point_end(idx)
+    }
     fn point_begin(&mut self, _idx: usize) -> Result<()> {
         self.write_header(WKBGeometryType::Point)
     }
@@ -303,9 +509,10 @@ impl<W: Write> GeomProcessor for WkbWriter<W> {
         self.write_footer()
     }
     fn multipoint_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.check_empty(size);
         self.nesting_level += 1;
         self.write_header(WKBGeometryType::MultiPoint)?;
-        self.out.iowrite_with(size as u32, self.endian)?;
+        self.buf.iowrite_with(size as u32, self.endian)?;
         self.geom_state = GeomState::MultiPointGeom;
         Ok(())
     }
@@ -315,19 +522,25 @@ impl<W: Write> GeomProcessor for WkbWriter<W> {
         self.write_footer()
     }
     fn linestring_begin(&mut self, _tagged: bool, size: usize, _idx: usize) -> Result<()> {
+        self.check_empty(size);
         if self.geom_state != GeomState::RingGeom {
             self.write_header(WKBGeometryType::LineString)?;
         }
-        self.out.iowrite_with(size as u32, self.endian)?;
+        self.buf.iowrite_with(size as u32, self.endian)?;
+        if self.compress && self.dialect == WkbDialect::SpatiaLite {
+            self.compressed_line = Some((0, size));
+        }
         Ok(())
     }
     fn linestring_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        self.compressed_line = None;
         self.write_footer()
     }
     fn multilinestring_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.check_empty(size);
         self.nesting_level += 1;
         self.write_header(WKBGeometryType::MultiLineString)?;
-        self.out.iowrite_with(size as u32, self.endian)?;
+        self.buf.iowrite_with(size as u32, self.endian)?;
         Ok(())
     }
     fn multilinestring_end(&mut self, _idx: usize) -> Result<()> {
@@ -335,8 +548,9 @@ impl<W: Write> GeomProcessor for WkbWriter<W> {
         self.write_footer()
     }
     fn polygon_begin(&mut self, _tagged: bool, size: usize, _idx: usize) -> Result<()> {
+        self.check_empty(size);
         self.write_header(WKBGeometryType::Polygon)?;
-        self.out.iowrite_with(size as u32, self.endian)?;
+        self.buf.iowrite_with(size as u32, self.endian)?;
         self.geom_state = GeomState::RingGeom;
         Ok(())
     }
@@ -345,9 +559,10 @@ impl<W: Write> GeomProcessor for WkbWriter<W> {
         self.write_footer()
     }
     fn multipolygon_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.check_empty(size);
         self.nesting_level += 1;
         self.write_header(WKBGeometryType::MultiPolygon)?;
-        self.out.iowrite_with(size as u32, self.endian)?;
+        self.buf.iowrite_with(size as u32, self.endian)?;
         Ok(())
     }
     fn multipolygon_end(&mut self, _idx: usize) -> Result<()> {
@@ -355,9 +570,10 @@ impl<W: Write> GeomProcessor for WkbWriter<W> {
         self.write_footer()
     }
     fn geometrycollection_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.check_empty(size);
         self.nesting_level += 1;
         self.write_header(WKBGeometryType::GeometryCollection)?;
-        self.out.iowrite_with(size as u32, self.endian)?;
+        self.buf.iowrite_with(size as u32, self.endian)?;
         Ok(())
     }
     fn geometrycollection_end(&mut self, _idx: usize) -> Result<()> {
@@ -365,33 +581,37 @@ impl<W: Write> GeomProcessor for WkbWriter<W> {
         self.write_footer()
     }
     fn circularstring_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.check_empty(size);
         self.write_header(WKBGeometryType::CircularString)?;
-        self.out.iowrite_with(size as u32, self.endian)?;
+        self.buf.iowrite_with(size as u32, self.endian)?;
         Ok(())
     }
     fn circularstring_end(&mut self, _idx: usize) -> Result<()> {
         self.write_footer()
     }
     fn compoundcurve_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.check_empty(size);
         self.write_header(WKBGeometryType::CompoundCurve)?;
-        self.out.iowrite_with(size as u32, self.endian)?;
+        self.buf.iowrite_with(size as u32, self.endian)?;
         Ok(())
     }
     fn compoundcurve_end(&mut self, _idx: usize) -> Result<()> {
         self.write_footer()
     }
     fn curvepolygon_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.check_empty(size);
         self.write_header(WKBGeometryType::CurvePolygon)?;
-        self.out.iowrite_with(size as u32, self.endian)?;
+        self.buf.iowrite_with(size as u32, self.endian)?;
         Ok(())
     }
     fn curvepolygon_end(&mut self, _idx: usize) -> Result<()> {
         self.write_footer()
     }
     fn multicurve_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.check_empty(size);
         self.nesting_level += 1;
         self.write_header(WKBGeometryType::MultiCurve)?;
-        self.out.iowrite_with(size as u32, self.endian)?;
+        self.buf.iowrite_with(size as u32, self.endian)?;
         Ok(())
     }
     fn multicurve_end(&mut self, _idx: usize) -> Result<()> {
@@ -399,9 +619,10 @@ impl<W: Write> GeomProcessor for WkbWriter<W> {
         self.write_footer()
     }
     fn multisurface_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.check_empty(size);
         self.nesting_level += 1;
         self.write_header(WKBGeometryType::MultiSurface)?;
-        self.out.iowrite_with(size as u32, self.endian)?;
+        self.buf.iowrite_with(size as u32, self.endian)?;
         Ok(())
     }
     fn multisurface_end(&mut self, _idx: usize) -> Result<()> {
@@ -409,8 +630,9 @@ impl<W: Write> GeomProcessor for WkbWriter<W> {
         self.write_footer()
     }
     fn triangle_begin(&mut self, _tagged: bool, size: usize, _idx: usize) -> Result<()> {
+        self.check_empty(size);
         self.write_header(WKBGeometryType::Triangle)?;
-        self.out.iowrite_with(size as u32, self.endian)?;
+        self.buf.iowrite_with(size as u32, self.endian)?;
         self.geom_state = GeomState::RingGeom;
         Ok(())
     }
@@ -419,16 +641,18 @@ impl<W: Write> GeomProcessor for WkbWriter<W> {
         self.write_footer()
     }
     fn polyhedralsurface_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.check_empty(size);
         self.write_header(WKBGeometryType::PolyhedralSurface)?;
-        self.out.iowrite_with(size as u32, self.endian)?;
+        self.buf.iowrite_with(size as u32, self.endian)?;
         Ok(())
     }
     fn polyhedralsurface_end(&mut self, _idx: usize) -> Result<()> {
         self.write_footer()
     }
     fn tin_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.check_empty(size);
         self.write_header(WKBGeometryType::Tin)?;
-        self.out.iowrite_with(size as u32, self.endian)?;
+        self.buf.iowrite_with(size as u32, self.endian)?;
         Ok(())
     }
     fn tin_end(&mut self, _idx: usize) -> Result<()> {
@@ -584,6 +808,45 @@ mod test {
         assert_eq!(out, "0102000020e610000002000000000000000000244000000000000034c00000000000000000000000000000e0bf");
     }
 
+    #[test]
+    fn srid_capture() {
+        // SELECT 'SRID=4326;POINT(10 -20)'::geometry
+        let ewkb = hex::decode("0101000020E6100000000000000000244000000000000034C0").unwrap();
+        let write = |srid: Option<i32>, srid_override: bool| {
+            let mut wkb_out: Vec<u8> = Vec::new();
+            let mut writer = WkbWriter::with_opts(&mut wkb_out, Ewkb, DIM_XY, srid, Vec::new());
+            writer.set_srid_override(srid_override);
+            process_wkb_type_geom(&mut ewkb.as_slice(), &mut writer, Ewkb).unwrap();
+            hex::encode(wkb_out)
+        };
+        // SRID from input
+        assert_eq!(
+            write(None, false),
+            "0101000020e6100000000000000000244000000000000034c0"
+        );
+        // Configured SRID 3857
+        assert_eq!(
+            write(Some(3857), false),
+            "0101000020110f0000000000000000244000000000000034c0"
+        );
+        // Configured SRID overridden by input
+        assert_eq!(
+            write(Some(3857), true),
+            "0101000020e6100000000000000000244000000000000034c0"
+        );
+    }
+
+    #[test]
+    fn ewkb_nested_srid() {
+        // SELECT 'SRID=4326;MULTIPOINT (10 -20, 0 -0.5)'::geometry
+        let ewkb = hex::decode("0104000020E6100000020000000101000000000000000000244000000000000034C001010000000000000000000000000000000000E0BF").unwrap();
+        let mut wkb_out: Vec<u8> = Vec::new();
+        let mut writer = WkbWriter::new(&mut wkb_out, Ewkb);
+        writer.set_nested_srid(true);
+        process_wkb_type_geom(&mut ewkb.as_slice(), &mut writer, Ewkb).unwrap();
+        assert_eq!(hex::encode(wkb_out), "0104000020e6100000020000000101000020e6100000000000000000244000000000000034c00101000020e61000000000000000000000000000000000e0bf");
+    }
+
     #[test]
     fn gpkg_geometries() {
         // pt2d
@@ -599,6 +862,32 @@ mod test {
                   "47500003e6100000000000000000f03f0000000000003640000000000000084000000000000036400107000000020000000101000000000000000000f03f00000000000008400103000000010000000400000000000000000035400000000000003540000000000000364000000000000035400000000000003540000000000000364000000000000035400000000000003540");
     }
 
+    #[test]
+    fn gpkg_header_flags() {
+        let write = |wkt: &str, dims: CoordDimensions, envelope: Vec<f64>| {
+            let mut wkb_out: Vec<u8> = Vec::new();
+            let mut writer = WkbWriter::with_opts(&mut wkb_out, Geopackage, dims, None, envelope);
+            crate::wkt::read_wkt(&mut wkt.as_bytes(), &mut writer).unwrap();
+            hex::encode(wkb_out)
+        };
+        // Empty flag set, no envelope
+        assert_eq!(
+            write("POINT EMPTY", DIM_XY, vec![0.0, 0.0, 0.0, 0.0]),
+            "47500011000000000101000000000000000000f87f000000000000f87f"
+        );
+        assert_eq!(
+            write("MULTIPOLYGON EMPTY", DIM_XY, Vec::new()),
+            "4750001100000000010600000000000000"
+        );
+        // XYZ envelope selected from written dimensions
+        let gpkg = write(
+            "POINT Z(1 2 3)",
+            DIM_XYZ,
+            vec![1.0, 1.0, 2.0, 2.0, 3.0, 3.0],
+        );
+        assert_eq!(&gpkg[6..8], "05");
+    }
+
     #[test]
     fn spatialite_geometries() {
         // SELECT HEX(ST_GeomFromText('POINT(1.1 1.1)', 4326));
@@ -618,6 +907,99 @@ mod test {
                   "000100000000000000000000F03F0000000000000840000000000000364000000000000036407C07000000020000006901000000000000000000F03F00000000000008406903000000010000000400000000000000000035400000000000003540000000000000364000000000000035400000000000003540000000000000364000000000000035400000000000003540FE");
     }
 
+    #[test]
+    fn spatialite_compressed() {
+        // SELECT HEX(CompressGeometry(ST_GeomFromText('LINESTRINGZM(0 0 0 0,10 0 2 20,10 10 1 -40,51 69 13 37)', 4326)));
+        let spatialite = "0001E610000000000000000000000000000000000000000000000080494000000000004051407CFA4D0F0004000000000000000000000000000000000000000000000000000000000000000000000000002041000000000000004000000000000034400000000000002041000080BF00000000000044C0000000000080494000000000004051400000000000002A400000000000804240FE";
+        let wkb_in = hex::decode(spatialite).unwrap();
+        let mut wkb_out: Vec<u8> = Vec::new();
+        let mut writer = WkbWriter::with_opts(&mut wkb_out, SpatiaLite, DIM_XYZM, None, Vec::new());
+        writer.set_compress(true);
+        process_wkb_type_geom(&mut wkb_in.as_slice(), &mut writer, SpatiaLite).unwrap();
+        assert_eq!(hex::encode(wkb_out), spatialite.to_lowercase());
+
+        // Compressed polygon roundtrip
+        let mut wkb_out: Vec<u8> = Vec::new();
+        let mut writer = WkbWriter::with_opts(
+            &mut wkb_out,
+            SpatiaLite,
+            DIM_XY,
+            None,
+            vec![21.0, 21.0, 22.0, 22.0],
+        );
+        writer.set_compress(true);
+        crate::wkt::read_wkt(
+            &mut "POLYGON((21 21,22 21,21 22,21 21))".as_bytes(),
+            &mut writer,
+        )
+        .unwrap();
+        // Type id 1000003
+        assert_eq!(&wkb_out[39..43], &1_000_003u32.to_le_bytes());
+        let mut wkt_out: Vec<u8> = Vec::new();
+        process_wkb_type_geom(
+            &mut wkb_out.as_slice(),
+            &mut crate::wkt::WktWriter::new(&mut wkt_out),
+            SpatiaLite,
+        )
+        .unwrap();
+        assert_eq!(
+            std::str::from_utf8(&wkt_out).unwrap(),
+            "POLYGON((21 21,22 21,21 22,21 21))"
+        );
+    }
+
+    #[test]
+    fn spatialite_tiny_point() {
+        // SELECT HEX(TinyPointEncode(ST_GeomFromText('POINTZM(10 -20 100 1)', 4326)));
+        let spatialite =
+            "0081E610000004000000000000244000000000000034C00000000000005940000000000000F03FFE";
+        let wkb_in = hex::decode(spatialite).unwrap();
+        let mut wkb_out: Vec<u8> = Vec::new();
+        let mut writer = WkbWriter::with_opts(&mut wkb_out, SpatiaLite, DIM_XYZM, None, Vec::new());
+        writer.set_tiny_point(true);
+        process_wkb_type_geom(&mut wkb_in.as_slice(), &mut writer, SpatiaLite).unwrap();
+        assert_eq!(hex::encode(wkb_out), spatialite.to_lowercase());
+
+        // Multipoints are written as regular BLOB
+        let mut wkb_out: Vec<u8> = Vec::new();
+        let mut writer = WkbWriter::with_opts(
+            &mut wkb_out,
+            SpatiaLite,
+            DIM_XY,
+            None,
+            vec![1.0, 2.0, 3.0, 4.0],
+        );
+        writer.set_tiny_point(true);
+        crate::wkt::read_wkt(&mut "MULTIPOINT(1 2,3 4)".as_bytes(), &mut writer).unwrap();
+        assert_eq!(hex::encode(wkb_out), "000100000000000000000000f03f0000000000000040000000000000084000000000000010407c04000000020000006901000000000000000000f03f0000000000000040690100000000000000000008400000000000001040fe");
+    }
+
+    #[test]
+    fn envelope_propagation() {
+        // GPKG POINT(1.1 1.1) with envelope
+        let gpkg = "47500003E61000009A9999999999F13F9A9999999999F13F9A9999999999F13F9A9999999999F13F01010000009A9999999999F13F9A9999999999F13F";
+        assert_eq!(
+            with_ext_opts(Geopackage, DIM_XY, DIM_XY, Some(4326), Vec::new(), gpkg),
+            gpkg.to_lowercase()
+        );
+
+        // Spatialite MULTIPOINT(1 2,3 4) with envelope [minx, miny, maxx, maxy]
+        let spatialite = "000100000000000000000000F03F0000000000000040000000000000084000000000000010407C04000000020000006901000000000000000000F03F0000000000000040690100000000000000000008400000000000001040FE";
+        assert_eq!(
+            with_ext_opts(SpatiaLite, DIM_XY, DIM_XY, None, Vec::new(), spatialite),
+            spatialite.to_lowercase()
+        );
+    }
+
+    #[test]
+    fn unsupported_time_dimensions() {
+        let dims = CoordDimensions { t: true, ..DIM_XY };
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer = WkbWriter::with_opts(&mut out, Ewkb, dims, None, Vec::new());
+        let result = crate::wkt::read_wkt(&mut "POINT(1 2 T=2020.5)".as_bytes(), &mut writer);
+        assert!(matches!(result, Err(GeozeroError::Geometry(_))));
+    }
+
     #[test]
     fn mysql_geometries() {
         // SELECT HEX(ST_GeomFromText('POINT(10 -20)', 4326, 'axis-order=long-lat'));
@@ -683,4 +1065,64 @@ mod test {
             &hex::decode("E61000000101000000000000000000244000000000000034C0").unwrap()
         );
     }
+
+    #[test]
+    fn batched_writes() {
+        use crate::GeozeroGeometry;
+
+        /// Writer counting write calls
+        #[derive(Default)]
+        struct CountingWriter {
+            writes: usize,
+            out: Vec<u8>,
+        }
+        impl Write for CountingWriter {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.writes += 1;
+                self.out.write(buf)
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut out = CountingWriter::default();
+        let mut writer = WkbWriter::new(&mut out, WkbDialect::Wkb);
+        crate::wkt::WktStr("MULTILINESTRING((0 0,1 1,2 2),(3 3,4 4))")
+            .process_geom(&mut writer)
+            .unwrap();
+        // One write per linestring
+        assert_eq!(out.writes, 2);
+        assert_eq!(
+            hex::encode_upper(out.out),
+            "01050000000200000001020000000300000000000000000000000000000000000000000000000000F03F000000000000F03F000000000000004000000000000000400102000000020000000000000000000840000000000000084000000000000010400000000000001040"
+        );
+    }
+
+    #[test]
+    fn coords_slice() -> Result<()> {
+        let write_line = |dims: CoordDimensions, coords: &[f64], slice_dims| -> Result<Vec<u8>> {
+            let mut out = Vec::new();
+            let mut writer = WkbWriter::with_opts(&mut out, WkbDialect::Wkb, dims, None, vec![]);
+            writer.linestring_begin(true, 2, 0)?;
+            writer.coords(coords, slice_dims, 0)?;
+            writer.linestring_end(true, 0)?;
+            Ok(out)
+        };
+        let mut expected = Vec::new();
+        let mut writer = WkbWriter::new(&mut expected, WkbDialect::Wkb);
+        writer.linestring_begin(true, 2, 0)?;
+        writer.xy(1.0, 2.0, 0)?;
+        writer.xy(3.0, 4.0, 1)?;
+        writer.linestring_end(true, 0)?;
+
+        // Copied ordinates
+        assert_eq!(write_line(DIM_XY, &[1.0, 2.0, 3.0, 4.0], DIM_XY)?, expected);
+        // Z ordinates dropped by per-coordinate processing
+        assert_eq!(
+            write_line(DIM_XY, &[1.0, 2.0, 9.0, 3.0, 4.0, 9.0], DIM_XYZ)?,
+            expected
+        );
+        Ok(())
+    }
 }
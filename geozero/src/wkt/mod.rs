@@ -1,9 +1,12 @@
 //! Well-Known Text (WKT) conversions.
 //!
 //! OpenGIS Simple Features Specification For SQL Revision 1.1, Chapter 3.2.5
+pub(crate) mod wkt_line_reader;
+pub(crate) mod wkt_parser;
 pub(crate) mod wkt_reader;
 pub(crate) mod wkt_writer;
 
+pub use wkt_line_reader::*;
 pub use wkt_reader::*;
 pub use wkt_writer::*;
 
@@ -120,6 +123,36 @@ mod wkb {
             Ok(EwktString(wkt))
         }
     }
+
+    #[cfg(feature = "with-postgis-postgres")]
+    #[allow(deprecated)]
+    mod postgis_postgres {
+        use crate::wkt::{Ewkt, EwktString, Wkt, WktString};
+
+        crate::impl_postgres_postgis_decode!(Wkt<String>);
+        crate::impl_postgres_postgis_encode!(Wkt<String>);
+        crate::impl_postgres_postgis_decode!(Ewkt<String>);
+        crate::impl_postgres_postgis_decode!(WktString);
+        crate::impl_postgres_postgis_encode!(WktString);
+        crate::impl_postgres_postgis_decode!(EwktString);
+    }
+
+    #[cfg(feature = "with-postgis-sqlx")]
+    #[allow(deprecated)]
+    mod postgis_sqlx {
+        use crate::wkt::{Ewkt, EwktString, Wkt, WktString};
+
+        crate::impl_sqlx_postgis_type_info!(Wkt<String>);
+        crate::impl_sqlx_postgis_decode!(Wkt<String>);
+        crate::impl_sqlx_postgis_encode!(Wkt<String>);
+        crate::impl_sqlx_postgis_type_info!(Ewkt<String>);
+        crate::impl_sqlx_postgis_decode!(Ewkt<String>);
+        crate::impl_sqlx_postgis_type_info!(WktString);
+        crate::impl_sqlx_postgis_decode!(WktString);
+        crate::impl_sqlx_postgis_encode!(WktString);
+        crate::impl_sqlx_postgis_type_info!(EwktString);
+        crate::impl_sqlx_postgis_decode!(EwktString);
+    }
 }
 
 /// WKB dialect.
@@ -0,0 +1,177 @@
+use crate::error::{ErrorPosition, GeozeroError, Result};
+use crate::wkt::wkt_parser::WktParser;
+use crate::{FeatureProcessor, GeomProcessor, GeozeroDatasource, GeozeroGeometry};
+
+use std::io::{BufRead, BufReader, Read};
+
+/// Line delimited WKT Reader: One WKT or EWKT geometry per line.
+///
+/// Each geometry is processed as a feature without properties. Empty lines are skipped.
+pub struct WktLineReader<R: Read>(pub(crate) R);
+
+impl<R: Read> WktLineReader<R> {
+    pub fn new(read: R) -> Self {
+        Self(read)
+    }
+}
+
+impl<R: Read + Clone> GeozeroGeometry for WktLineReader<R> {
+    fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()>
+    where
+        Self: Sized,
+    {
+        read_wkt_line_geometries(&mut self.0.clone(), processor)
+    }
+}
+
+impl<R: Read> GeozeroDatasource for WktLineReader<R> {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        read_wkt_lines(&mut self.0, processor)
+    }
+}
+
+/// Read and process line delimited WKT as GeometryCollection.
+pub fn read_wkt_line_geometries(
+    reader: impl Read,
+    processor: &mut impl GeomProcessor,
+) -> Result<()> {
+    let mut started = false;
+    read_lines(BufReader::new(reader), |idx, srid, wkt| {
+        if !started {
+            started = true;
+            // The number of lines is unknown, but it's non-zero
+            processor.geometrycollection_begin(1, 0)?;
+        }
+        if srid.is_some() {
+            processor.srid(srid)?;
+        }
+        process_line(wkt, idx, processor)
+    })?;
+    if !started {
+        processor.geometrycollection_begin(0, 0)?;
+    }
+    processor.geometrycollection_end(0)
+}
+
+/// Read and process line delimited WKT (one geometry per line).
+pub fn read_wkt_lines(reader: impl Read, processor: &mut impl FeatureProcessor) -> Result<()> {
+    processor.dataset_begin(None)?;
+    read_lines(BufReader::new(reader), |idx, srid, wkt| {
+        processor.feature_begin(idx as u64)?;
+        processor.geometry_begin()?;
+        if srid.is_some() {
+            processor.srid(srid)?;
+        }
+        process_line(wkt, 0, processor)?;
+        processor.geometry_end()?;
+        processor.feature_end(idx as u64)
+    })?;
+    processor.dataset_end()
+}
+
+/// Call `process` with feature index, SRID and WKT text of each non-empty line.
+///
+/// Parse errors are reported with the line and column in the input.
+fn read_lines<R: BufRead>(
+    reader: R,
+    mut process: impl FnMut(usize, Option<i32>, &str) -> Result<()>,
+) -> Result<()> {
+    let mut idx = 0;
+    for (line_no, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (srid, wkt) = split_srid(&line).map_err(|e| {
+            e.at(ErrorPosition::LineColumn {
+                line: line_no + 1,
+                column: 1,
+            })
+        })?;
+        let offset = line[..line.len() - wkt.len()].chars().count();
+        process(idx, srid, wkt).map_err(|e| match e {
+            GeozeroError::Positioned {
+                position: ErrorPosition::LineColumn { column, .. },
+                error,
+            } => GeozeroError::Positioned {
+                position: ErrorPosition::LineColumn {
+                    line: line_no + 1,
+                    column: column + offset,
+                },
+                error,
+            },
+            e => e,
+        })?;
+        idx += 1;
+    }
+    Ok(())
+}
+
+/// Split optional EWKT `SRID=<srid>;` prefix
+fn split_srid(line: &str) -> Result<(Option<i32>, &str)> {
+    let trimmed = line.trim_start();
+    let has_srid = trimmed.len() >= 5 && trimmed.as_bytes()[..5].eq_ignore_ascii_case(b"SRID=");
+    if !has_srid {
+        return Ok((None, line));
+    }
+    let (srid, wkt) = trimmed[5..]
+        .split_once(';')
+        .ok_or_else(|| GeozeroError::Geometry("missing `;` after SRID".to_string()))?;
+    let srid = srid
+        .trim()
+        .parse()
+        .map_err(|_| GeozeroError::Geometry(format!("invalid SRID `{srid}`")))?;
+    Ok((Some(srid), wkt))
+}
+
+fn process_line<P: GeomProcessor>(wkt: &str, idx: usize, processor: &mut P) -> Result<()> {
+    let mut parser = WktParser::new(wkt.as_bytes()).with_positions();
+    parser.process_next(idx, processor)?;
+    parser.expect_eof()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::wkt::WktWriter;
+    use crate::ProcessorSink;
+
+    #[test]
+    fn lines() -> Result<()> {
+        let input = "POINT(1 2)\n\nSRID=4326;LINESTRING(1 2,3 4)\r\nPOLYGON EMPTY\n";
+        let mut out: Vec<u8> = Vec::new();
+        WktLineReader::new(input.as_bytes()).process(&mut WktWriter::new(&mut out))?;
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            "POINT(1 2)LINESTRING(1 2,3 4)POLYGON EMPTY"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn geometries() -> Result<()> {
+        let input = "POINT(1 2)\nPOINT(3 4)\n";
+        let mut out: Vec<u8> = Vec::new();
+        WktLineReader::new(input.as_bytes()).process_geom(&mut WktWriter::new(&mut out))?;
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            "GEOMETRYCOLLECTION(POINT(1 2),POINT(3 4))"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn error_position() {
+        let input = "POINT(1 2)\nSRID=4326;POINT(1 x)\n";
+        let err = WktLineReader::new(input.as_bytes())
+            .process(&mut ProcessorSink)
+            .unwrap_err();
+        assert_eq!(
+            err.position(),
+            Some(ErrorPosition::LineColumn {
+                line: 2,
+                column: 19
+            })
+        );
+    }
+}
@@ -0,0 +1,519 @@
+//! Streaming WKT parser.
+//!
+//! Geometries are tokenized directly from a [BufRead] stream. Only the coordinates of the
+//! geometry currently parsed are buffered, since the [GeomProcessor] API requires the number
+//! of parts in `*_begin` events.
+use crate::error::{ErrorPosition, GeozeroError, Result};
+use crate::GeomProcessor;
+use std::io::BufRead;
+
+#[derive(Clone, PartialEq, Debug)]
+enum Token {
+    Word(String),
+    LParen,
+    RParen,
+    Comma,
+    Eof,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::Word(word) => format!("`{word}`"),
+            Token::LParen => "`(`".to_string(),
+            Token::RParen => "`)`".to_string(),
+            Token::Comma => "`,`".to_string(),
+            Token::Eof => "end of input".to_string(),
+        }
+    }
+
+    fn is_word(&self, keyword: &str) -> bool {
+        matches!(self, Token::Word(word) if word.eq_ignore_ascii_case(keyword))
+    }
+}
+
+/// Incremental tokenizer keeping track of line and column
+struct Tokenizer<R: BufRead> {
+    reader: R,
+    peeked: Option<(Token, ErrorPosition)>,
+    line: usize,
+    column: usize,
+}
+
+impl<R: BufRead> Tokenizer<R> {
+    fn new(reader: R) -> Self {
+        Tokenizer {
+            reader,
+            peeked: None,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    fn position(&self) -> ErrorPosition {
+        ErrorPosition::LineColumn {
+            line: self.line,
+            column: self.column,
+        }
+    }
+
+    fn peek_byte(&mut self) -> Result<Option<u8>> {
+        Ok(self.reader.fill_buf()?.first().copied())
+    }
+
+    fn bump(&mut self, b: u8) {
+        self.reader.consume(1);
+        if b == b'\n' {
+            self.line += 1;
+            self.column = 1;
+        } else if b & 0xC0 != 0x80 {
+            // count characters, not UTF-8 continuation bytes
+            self.column += 1;
+        }
+    }
+
+    fn next(&mut self) -> Result<(Token, ErrorPosition)> {
+        if let Some(peeked) = self.peeked.take() {
+            return Ok(peeked);
+        }
+        while let Some(b) = self.peek_byte()? {
+            if !b.is_ascii_whitespace() {
+                break;
+            }
+            self.bump(b);
+        }
+        let position = self.position();
+        let token = match self.peek_byte()? {
+            None => Token::Eof,
+            Some(b @ (b'(' | b')' | b',')) => {
+                self.bump(b);
+                match b {
+                    b'(' => Token::LParen,
+                    b')' => Token::RParen,
+                    _ => Token::Comma,
+                }
+            }
+            Some(_) => {
+                let mut word = Vec::new();
+                while let Some(b) = self.peek_byte()? {
+                    if b.is_ascii_whitespace() || matches!(b, b'(' | b')' | b',') {
+                        break;
+                    }
+                    self.bump(b);
+                    word.push(b);
+                }
+                Token::Word(String::from_utf8_lossy(&word).into_owned())
+            }
+        };
+        Ok((token, position))
+    }
+
+    fn peek(&mut self) -> Result<&Token> {
+        if self.peeked.is_none() {
+            self.peeked = Some(self.next()?);
+        }
+        Ok(&self.peeked.as_ref().expect("peeked token").0)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct Coord {
+    x: f64,
+    y: f64,
+    z: Option<f64>,
+    m: Option<f64>,
+    t: Option<f64>,
+    tm: Option<u64>,
+}
+
+/// Dimension tag of a geometry
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Dims {
+    /// No tag, inferred from number of ordinates
+    Any,
+    Z,
+    M,
+    Zm,
+}
+
+/// Parsed geometry. `None` and empty vectors represent `EMPTY`.
+#[derive(Debug)]
+enum Geometry {
+    Point(Option<Coord>),
+    LineString(Vec<Coord>),
+    Polygon(Vec<Vec<Coord>>),
+    MultiPoint(Vec<Option<Coord>>),
+    MultiLineString(Vec<Vec<Coord>>),
+    MultiPolygon(Vec<Vec<Vec<Coord>>>),
+    GeometryCollection(Vec<Geometry>),
+}
+
+/// Maximal nesting depth of geometries, like the default of `WkbLimits::max_depth`
+const MAX_DEPTH: usize = 64;
+
+/// Streaming WKT parser
+pub(crate) struct WktParser<R: BufRead> {
+    tokens: Tokenizer<R>,
+    /// Attach input position to errors
+    positioned: bool,
+    /// Number of enclosing geometry collections
+    depth: usize,
+}
+
+impl<R: BufRead> WktParser<R> {
+    pub(crate) fn new(reader: R) -> Self {
+        WktParser {
+            tokens: Tokenizer::new(reader),
+            positioned: false,
+            depth: 0,
+        }
+    }
+
+    /// Report line and column of parse errors
+    pub(crate) fn with_positions(mut self) -> Self {
+        self.positioned = true;
+        self
+    }
+
+    /// Parse and process the next geometry. Returns `false` at end of input.
+    pub(crate) fn process_next<P: GeomProcessor>(
+        &mut self,
+        idx: usize,
+        processor: &mut P,
+    ) -> Result<bool> {
+        if *self.tokens.peek()? == Token::Eof {
+            return Ok(false);
+        }
+        let geometry = self.parse_geometry()?;
+        process_geometry(&geometry, idx, processor)?;
+        Ok(true)
+    }
+
+    /// Fail if there is remaining input
+    pub(crate) fn expect_eof(&mut self) -> Result<()> {
+        let (token, position) = self.tokens.next()?;
+        if token == Token::Eof {
+            Ok(())
+        } else {
+            Err(self.error(format!("unexpected {}", token.describe()), position))
+        }
+    }
+
+    fn error(&self, message: String, position: ErrorPosition) -> GeozeroError {
+        let error = GeozeroError::Geometry(message);
+        if self.positioned {
+            error.at(position)
+        } else {
+            error
+        }
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<()> {
+        let (token, position) = self.tokens.next()?;
+        if token == expected {
+            Ok(())
+        } else {
+            Err(self.error(
+                format!(
+                    "expected {}, found {}",
+                    expected.describe(),
+                    token.describe()
+                ),
+                position,
+            ))
+        }
+    }
+
+    /// Consume `EMPTY` keyword, if present
+    fn empty(&mut self) -> Result<bool> {
+        if self.tokens.peek()?.is_word("EMPTY") {
+            self.tokens.next()?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Consume `,` and return `true`, or `)` and return `false`
+    fn separator(&mut self) -> Result<bool> {
+        let (token, position) = self.tokens.next()?;
+        match token {
+            Token::Comma => Ok(true),
+            Token::RParen => Ok(false),
+            _ => Err(self.error(
+                format!("expected `,` or `)`, found {}", token.describe()),
+                position,
+            )),
+        }
+    }
+
+    fn parse_geometry(&mut self) -> Result<Geometry> {
+        let (token, position) = self.tokens.next()?;
+        if self.depth >= MAX_DEPTH {
+            return Err(self.error(
+                format!("geometry nesting exceeds maximal depth of {MAX_DEPTH}"),
+                position,
+            ));
+        }
+        let keyword = match token {
+            Token::Word(keyword) => keyword,
+            _ => {
+                return Err(self.error(
+                    format!("expected geometry type, found {}", token.describe()),
+                    position,
+                ))
+            }
+        };
+        let dims = self.parse_dims()?;
+        let geometry = match keyword.to_ascii_uppercase().as_str() {
+            "POINT" => {
+                if self.empty()? {
+                    Geometry::Point(None)
+                } else {
+                    self.expect(Token::LParen)?;
+                    let coord = self.parse_coord(dims)?;
+                    self.expect(Token::RParen)?;
+                    Geometry::Point(Some(coord))
+                }
+            }
+            "LINESTRING" => Geometry::LineString(self.parse_coords(dims)?),
+            "POLYGON" => Geometry::Polygon(self.parse_rings(dims)?),
+            "MULTIPOINT" => {
+                let mut points = Vec::new();
+                if !self.empty()? {
+                    self.expect(Token::LParen)?;
+                    loop {
+                        if self.empty()? {
+                            points.push(None);
+                        } else if *self.tokens.peek()? == Token::LParen {
+                            self.tokens.next()?;
+                            points.push(Some(self.parse_coord(dims)?));
+                            self.expect(Token::RParen)?;
+                        } else {
+                            points.push(Some(self.parse_coord(dims)?));
+                        }
+                        if !self.separator()? {
+                            break;
+                        }
+                    }
+                }
+                Geometry::MultiPoint(points)
+            }
+            "MULTILINESTRING" => Geometry::MultiLineString(self.parse_rings(dims)?),
+            "MULTIPOLYGON" => {
+                let mut polygons = Vec::new();
+                if !self.empty()? {
+                    self.expect(Token::LParen)?;
+                    loop {
+                        polygons.push(self.parse_rings(dims)?);
+                        if !self.separator()? {
+                            break;
+                        }
+                    }
+                }
+                Geometry::MultiPolygon(polygons)
+            }
+            "GEOMETRYCOLLECTION" => {
+                self.depth += 1;
+                let geometries = self.parse_collection();
+                self.depth -= 1;
+                Geometry::GeometryCollection(geometries?)
+            }
+            _ => return Err(self.error(format!("unsupported geometry type `{keyword}`"), position)),
+        };
+        Ok(geometry)
+    }
+
+    fn parse_collection(&mut self) -> Result<Vec<Geometry>> {
+        let mut geometries = Vec::new();
+        if !self.empty()? {
+            self.expect(Token::LParen)?;
+            loop {
+                geometries.push(self.parse_geometry()?);
+                if !self.separator()? {
+                    break;
+                }
+            }
+        }
+        Ok(geometries)
+    }
+
+    fn parse_dims(&mut self) -> Result<Dims> {
+        let dims = match self.tokens.peek()? {
+            token if token.is_word("Z") => Dims::Z,
+            token if token.is_word("M") => Dims::M,
+            token if token.is_word("ZM") => Dims::Zm,
+            _ => return Ok(Dims::Any),
+        };
+        self.tokens.next()?;
+        Ok(dims)
+    }
+
+    /// Parse `(x y, ...)` or `EMPTY`
+    fn parse_coords(&mut self, dims: Dims) -> Result<Vec<Coord>> {
+        let mut coords = Vec::new();
+        if !self.empty()? {
+            self.expect(Token::LParen)?;
+            loop {
+                coords.push(self.parse_coord(dims)?);
+                if !self.separator()? {
+                    break;
+                }
+            }
+        }
+        Ok(coords)
+    }
+
+    /// Parse `((x y, ...), ...)` or `EMPTY`
+    fn parse_rings(&mut self, dims: Dims) -> Result<Vec<Vec<Coord>>> {
+        let mut rings = Vec::new();
+        if !self.empty()? {
+            self.expect(Token::LParen)?;
+            loop {
+                rings.push(self.parse_coords(dims)?);
+                if !self.separator()? {
+                    break;
+                }
+            }
+        }
+        Ok(rings)
+    }
+
+    /// Parse ordinates, followed by optional `T=<t>` and `TM=<tm>` values
+    fn parse_coord(&mut self, dims: Dims) -> Result<Coord> {
+        let mut ordinates = [0.0; 4];
+        let mut count = 0;
+        let mut t = None;
+        let mut tm = None;
+        while let Token::Word(_) = self.tokens.peek()? {
+            let (Token::Word(word), position) = self.tokens.next()? else {
+                unreachable!()
+            };
+            if let Some((name, value)) = word.split_once('=') {
+                let invalid = || self.error(format!("invalid {name} value `{value}`"), position);
+                if name.eq_ignore_ascii_case("T") && t.is_none() && tm.is_none() {
+                    t = Some(value.parse().map_err(|_| invalid())?);
+                } else if name.eq_ignore_ascii_case("TM") && tm.is_none() {
+                    tm = Some(value.parse().map_err(|_| invalid())?);
+                } else {
+                    return Err(self.error(format!("unexpected `{word}`"), position));
+                }
+                continue;
+            }
+            if t.is_some() || tm.is_some() {
+                return Err(self.error(format!("unexpected `{word}` after time values"), position));
+            }
+            if count == ordinates.len() {
+                return Err(self.error("too many ordinates".to_string(), position));
+            }
+            ordinates[count] = word
+                .parse()
+                .map_err(|_| self.error(format!("invalid number `{word}`"), position))?;
+            count += 1;
+        }
+        let [x, y, o3, o4] = ordinates;
+        let (z, m) = match (dims, count) {
+            (Dims::Any, 2) => (None, None),
+            (Dims::Any | Dims::Z, 3) => (Some(o3), None),
+            (Dims::M, 3) => (None, Some(o3)),
+            (Dims::Any | Dims::Zm, 4) => (Some(o3), Some(o4)),
+            _ => {
+                let (token, position) = self.tokens.next()?;
+                return Err(self.error(
+                    format!("unexpected {} after {count} ordinates", token.describe()),
+                    position,
+                ));
+            }
+        };
+        Ok(Coord { x, y, z, m, t, tm })
+    }
+}
+
+fn process_geometry<P: GeomProcessor>(
+    geometry: &Geometry,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    match geometry {
+        Geometry::Point(Some(coord)) => {
+            processor.point_begin(idx)?;
+            process_coord(coord, processor.multi_dim(), 0, processor)?;
+            processor.point_end(idx)
+        }
+        Geometry::Point(None) => processor.empty_point(idx),
+        Geometry::LineString(coords) => process_linestring(coords, true, idx, processor),
+        Geometry::Polygon(rings) => process_polygon(rings, true, idx, processor),
+        Geometry::MultiPoint(points) => {
+            processor.multipoint_begin(points.len(), idx)?;
+            let multi_dim = processor.multi_dim();
+            for (idxc, point) in points.iter().enumerate() {
+                // empty points are not supported by other formats
+                if let Some(coord) = point {
+                    process_coord(coord, multi_dim, idxc, processor)?;
+                }
+            }
+            processor.multipoint_end(idx)
+        }
+        Geometry::MultiLineString(lines) => {
+            processor.multilinestring_begin(lines.len(), idx)?;
+            for (idxc, line) in lines.iter().enumerate() {
+                process_linestring(line, false, idxc, processor)?;
+            }
+            processor.multilinestring_end(idx)
+        }
+        Geometry::MultiPolygon(polygons) => {
+            processor.multipolygon_begin(polygons.len(), idx)?;
+            for (idxp, rings) in polygons.iter().enumerate() {
+                process_polygon(rings, false, idxp, processor)?;
+            }
+            processor.multipolygon_end(idx)
+        }
+        Geometry::GeometryCollection(geometries) => {
+            processor.geometrycollection_begin(geometries.len(), idx)?;
+            for (idxg, geometry) in geometries.iter().enumerate() {
+                process_geometry(geometry, idxg, processor)?;
+            }
+            processor.geometrycollection_end(idx)
+        }
+    }
+}
+
+fn process_coord<P: GeomProcessor>(
+    coord: &Coord,
+    multi_dim: bool,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    if multi_dim {
+        processor.coordinate(coord.x, coord.y, coord.z, coord.m, coord.t, coord.tm, idx)
+    } else {
+        processor.xy(coord.x, coord.y, idx)
+    }
+}
+
+fn process_linestring<P: GeomProcessor>(
+    coords: &[Coord],
+    tagged: bool,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    processor.linestring_begin(tagged, coords.len(), idx)?;
+    let multi_dim = processor.multi_dim();
+    for (idxc, coord) in coords.iter().enumerate() {
+        process_coord(coord, multi_dim, idxc, processor)?;
+    }
+    processor.linestring_end(tagged, idx)
+}
+
+fn process_polygon<P: GeomProcessor>(
+    rings: &[Vec<Coord>],
+    tagged: bool,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    processor.polygon_begin(tagged, rings.len(), idx)?;
+    for (idxr, ring) in rings.iter().enumerate() {
+        process_linestring(ring, false, idxr, processor)?;
+    }
+    processor.polygon_end(tagged, idx)
+}
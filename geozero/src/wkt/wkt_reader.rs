@@ -1,7 +1,8 @@
 use crate::error::{GeozeroError, Result};
+use crate::wkt::wkt_parser::WktParser;
 use crate::{FeatureProcessor, GeomProcessor, GeozeroDatasource, GeozeroGeometry};
 
-use std::io::Read;
+use std::io::{BufRead, BufReader, Read};
 use std::str::FromStr;
 use wkt::types::{Coord, LineString, Polygon};
 
@@ -69,6 +70,9 @@ pub struct EwktString(pub String);
 pub struct EwktStr<'a>(pub &'a str);
 
 /// Wkt Reader.
+///
+/// Supports the non-standard `T=<t>` and `TM=<tm>` coordinate suffixes written by
+/// [WktWriter](super::WktWriter).
 pub struct WktReader<R: Read>(pub R);
 
 impl<R: Read> GeozeroDatasource for WktReader<R> {
@@ -78,13 +82,38 @@ impl<R: Read> GeozeroDatasource for WktReader<R> {
 }
 
 /// Read and process WKT geometry.
+///
+/// The input is parsed incrementally, without reading it into memory first.
 pub fn read_wkt<R: Read, P: GeomProcessor>(reader: &mut R, processor: &mut P) -> Result<()> {
-    // PERF: it would be good to avoid copying data into this string when we already
-    // have a string as input. Maybe the wkt crate needs a from_reader implementation.
-    let mut wkt_string = String::new();
-    reader.read_to_string(&mut wkt_string)?;
-    let wkt = wkt::Wkt::from_str(&wkt_string).map_err(|e| GeozeroError::Geometry(e.to_string()))?;
-    process_wkt_geom(&wkt, processor)
+    read_wkt_buffered(BufReader::new(reader), processor)
+}
+
+/// Read and process WKT geometry from buffered input
+pub(crate) fn read_wkt_buffered<R: BufRead, P: GeomProcessor>(
+    reader: R,
+    processor: &mut P,
+) -> Result<()> {
+    let mut parser = WktParser::new(reader);
+    process_single(&mut parser, processor)
+}
+
+/// Read and process WKT geometry in strict mode.
+///
+/// Parse errors are reported with line and column of the first invalid token, or the end of
+/// the input if it ends prematurely.
+pub fn read_wkt_strict<R: Read, P: GeomProcessor>(reader: &mut R, processor: &mut P) -> Result<()> {
+    let mut parser = WktParser::new(BufReader::new(reader)).with_positions();
+    process_single(&mut parser, processor)
+}
+
+fn process_single<R: BufRead, P: GeomProcessor>(
+    parser: &mut WktParser<R>,
+    processor: &mut P,
+) -> Result<()> {
+    if !parser.process_next(0, processor)? {
+        return Err(GeozeroError::Geometry("empty WKT input".to_string()));
+    }
+    parser.expect_eof()
 }
 
 /// Process WKT geometry
@@ -191,10 +220,115 @@ fn process_polygon<P: GeomProcessor>(
 #[cfg(all(test, feature = "with-geo"))]
 mod test {
     use super::*;
+    use crate::error::ErrorPosition;
     use crate::geo_types::conversion::ToGeo;
     use crate::ToWkt;
     use geo_types::{line_string, point, polygon};
 
+    #[test]
+    fn strict_mode() {
+        let position = |wkt: &str| {
+            read_wkt_strict(&mut wkt.as_bytes(), &mut crate::ProcessorSink)
+                .unwrap_err()
+                .position()
+        };
+        assert_eq!(
+            position("POINT(1 x2)"),
+            Some(ErrorPosition::LineColumn { line: 1, column: 9 })
+        );
+        assert_eq!(
+            position("POINT(1 2"),
+            Some(ErrorPosition::LineColumn {
+                line: 1,
+                column: 10
+            })
+        );
+        assert_eq!(
+            position("LINESTRING(1 2,\n3 x)"),
+            Some(ErrorPosition::LineColumn { line: 2, column: 3 })
+        );
+        assert!(read_wkt_strict(&mut "POINT(1 2)".as_bytes(), &mut crate::ProcessorSink).is_ok());
+    }
+
+    #[test]
+    fn nesting_depth() {
+        let nested = |depth: usize| {
+            let mut wkt = "GEOMETRYCOLLECTION(".repeat(depth - 1);
+            wkt.push_str("POINT(1 2)");
+            wkt.push_str(&")".repeat(depth - 1));
+            read_wkt(&mut wkt.as_bytes(), &mut crate::ProcessorSink)
+        };
+        assert!(nested(64).is_ok());
+        assert!(matches!(nested(65), Err(GeozeroError::Geometry(_))));
+        assert!(matches!(nested(100_000), Err(GeozeroError::Geometry(_))));
+    }
+
+    #[test]
+    fn streaming() -> Result<()> {
+        let wkt = "MULTIPOLYGON (((40 40, 20 45, 45 30, 40 40)),
+            ((35 10, 45 45, 15 40, 10 20, 35 10), (20 30, 35 35, 30 20, 20 30)))";
+        let mut out: Vec<u8> = Vec::new();
+        // buffer smaller than tokens
+        let reader = std::io::BufReader::with_capacity(3, wkt.as_bytes());
+        read_wkt_buffered(reader, &mut crate::wkt::WktWriter::new(&mut out))?;
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            "MULTIPOLYGON(((40 40,20 45,45 30,40 40)),((35 10,45 45,15 40,10 20,35 10),(20 30,35 35,30 20,20 30)))"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn streaming_dimensions() -> Result<()> {
+        let to_wkt = |wkt: &str| -> Result<String> {
+            let mut out: Vec<u8> = Vec::new();
+            let mut writer =
+                crate::wkt::WktWriter::with_dims(&mut out, crate::CoordDimensions::xyzm());
+            read_wkt(&mut wkt.as_bytes(), &mut writer)?;
+            Ok(String::from_utf8(out).unwrap())
+        };
+        assert_eq!(to_wkt("POINT Z (1 2 3)")?, "POINT(1 2 3)");
+        assert_eq!(to_wkt("POINT(1 2 3)")?, "POINT(1 2 3)");
+        assert_eq!(to_wkt("POINT M (1 2 4)")?, "POINT(1 2 4)");
+        assert_eq!(
+            to_wkt("LINESTRING ZM (1 2 3 4,5 6 7 8)")?,
+            "LINESTRING(1 2 3 4,5 6 7 8)"
+        );
+        assert!(to_wkt("POINT Z (1 2)").is_err());
+        assert!(to_wkt("POINT(1 2) POINT(3 4)").is_err());
+        assert!(to_wkt("").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn streaming_time() -> Result<()> {
+        let to_wkt = |wkt: &str, dims: crate::CoordDimensions| -> Result<String> {
+            let mut out: Vec<u8> = Vec::new();
+            let mut writer = crate::wkt::WktWriter::with_dims(&mut out, dims);
+            read_wkt(&mut wkt.as_bytes(), &mut writer)?;
+            Ok(String::from_utf8(out).unwrap())
+        };
+        let xyzt = crate::CoordDimensions {
+            t: true,
+            tm: true,
+            ..crate::CoordDimensions::xyz()
+        };
+        assert_eq!(
+            to_wkt("LINESTRING(1 2 3 T=2020.5,4 5 6 t=2021 TM=1000)", xyzt)?,
+            "LINESTRING(1 2 3 T=2020.5,4 5 6 T=2021 TM=1000)"
+        );
+        assert_eq!(to_wkt("POINT(1 2 TM=1000)", xyzt)?, "POINT(1 2 TM=1000)");
+        // not requested
+        assert_eq!(
+            to_wkt("POINT(1 2 3 T=2020.5)", crate::CoordDimensions::xyz())?,
+            "POINT(1 2 3)"
+        );
+        assert!(to_wkt("POINT(1 2 T=2020.5 3)", xyzt).is_err());
+        assert!(to_wkt("POINT(1 2 TM=-1)", xyzt).is_err());
+        assert!(to_wkt("POINT(1 2 X=1)", xyzt).is_err());
+        Ok(())
+    }
+
     #[test]
     fn point() {
         let wkt = Wkt("POINT(1.0 2.0)");
@@ -1,18 +1,24 @@
 use crate::error::Result;
-use crate::{CoordDimensions, FeatureProcessor, GeomProcessor, PropertyProcessor};
+use crate::{CoordDimensions, FeatureProcessor, FloatFormatter, GeomProcessor, PropertyProcessor};
 use std::io::Write;
 use std::vec;
 
 use super::WktDialect;
 
 /// WKT Writer.
+///
+/// Requested T and TM values are written as non-standard `T=<t>` and `TM=<tm>` suffixes
+/// of coordinates, e.g. `POINT(1 2 T=2020.5)`, which can be read by [WktReader](super::WktReader).
 pub struct WktWriter<W: Write> {
     dims: CoordDimensions,
     srid: Option<i32>,
+    /// Replace configured SRID with SRID of processed geometry
+    srid_override: bool,
     dialect: WktDialect,
     first_header: bool,
     /// Stack of in-progress geometry sizes
     geometry_sizes: Vec<usize>,
+    float_format: FloatFormatter,
     pub(crate) out: W,
 }
 
@@ -34,13 +40,29 @@ impl<W: Write> WktWriter<W> {
         Self {
             dims,
             srid,
+            srid_override: false,
             dialect,
             first_header: true,
             geometry_sizes: vec![],
+            float_format: FloatFormatter::new(),
             out,
         }
     }
 
+    /// Let SRID of processed geometry override the configured SRID (default: false)
+    ///
+    /// Without override, the SRID of processed geometries is only used if no SRID is configured.
+    pub fn set_srid_override(&mut self, enabled: bool) {
+        self.srid_override = enabled;
+    }
+
+    /// Round coordinates to the given number of decimal places (default: no rounding)
+    ///
+    /// Coordinates are always written in plain decimal notation.
+    pub fn set_coordinate_precision(&mut self, decimals: Option<u32>) {
+        self.float_format = FloatFormatter::with_precision(decimals);
+    }
+
     fn header(&mut self, srid: Option<i32>) -> Result<()> {
         if self.first_header && self.dialect == WktDialect::Ewkt {
             self.first_header = false;
@@ -92,7 +114,12 @@ impl<W: Write> GeomProcessor for WktWriter<W> {
     }
 
     fn srid(&mut self, srid: Option<i32>) -> Result<()> {
-        self.srid = self.srid.or(srid);
+        let srid = srid.filter(|srid| *srid != 0);
+        if self.srid_override {
+            self.srid = srid.or(self.srid);
+        } else {
+            self.srid = self.srid.or(srid);
+        }
         Ok(())
     }
 
@@ -101,6 +128,7 @@ impl<W: Write> GeomProcessor for WktWriter<W> {
         if f64::is_nan(x) && f64::is_nan(y) {
             self.out.write_all(b"EMPTY")?;
         } else {
+            let [x, y] = [x, y].map(|v| self.float_format.round(v));
             self.out.write_all(format!("{x} {y}").as_bytes())?;
         }
         Ok(())
@@ -112,8 +140,8 @@ impl<W: Write> GeomProcessor for WktWriter<W> {
         y: f64,
         z: Option<f64>,
         m: Option<f64>,
-        _t: Option<f64>,
-        _tm: Option<u64>,
+        t: Option<f64>,
+        tm: Option<u64>,
         idx: usize,
     ) -> Result<()> {
         self.comma(idx)?;
@@ -124,12 +152,23 @@ impl<W: Write> GeomProcessor for WktWriter<W> {
         {
             self.out.write_all(b"EMPTY")?;
         } else {
+            let fmt = self.float_format;
+            let [x, y] = [x, y].map(|v| fmt.round(v));
             self.out.write_all(format!("{x} {y}").as_bytes())?;
             if let Some(z) = z {
-                self.out.write_all(format!(" {z}").as_bytes())?;
+                self.out
+                    .write_all(format!(" {}", fmt.round(z)).as_bytes())?;
             }
             if let Some(m) = m {
-                self.out.write_all(format!(" {m}").as_bytes())?;
+                self.out
+                    .write_all(format!(" {}", fmt.round(m)).as_bytes())?;
+            }
+            // Non-standard extension, supported by `WktReader`
+            if let (true, Some(t)) = (self.dims.t, t) {
+                self.out.write_all(format!(" T={t}").as_bytes())?;
+            }
+            if let (true, Some(tm)) = (self.dims.tm, tm) {
+                self.out.write_all(format!(" TM={tm}").as_bytes())?;
             }
         }
         Ok(())
@@ -251,6 +290,23 @@ mod test {
         assert_eq!(&geom.to_ewkt(Some(4326)).unwrap(), "SRID=4326;POINT(10 20)");
     }
 
+    #[test]
+    fn coordinate_precision() {
+        use crate::wkt::{Wkt, WktWriter};
+        use crate::GeozeroGeometry;
+
+        let mut out = Vec::new();
+        let mut writer = WktWriter::new(&mut out);
+        writer.set_coordinate_precision(Some(3));
+        Wkt("LINESTRING(1.23456 -0.0001,1e-20 1e21)")
+            .process_geom(&mut writer)
+            .unwrap();
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            "LINESTRING(1.235 0,0 1000000000000000000000)"
+        );
+    }
+
     #[test]
     #[cfg(feature = "with-wkb")]
     fn from_wkb() {
@@ -259,4 +315,57 @@ mod test {
         let ewkt = Ewkt::from_wkb(&mut cursor, WkbDialect::Ewkb).unwrap();
         assert_eq!(ewkt.0, "SRID=4326;MULTIPOINT(10 -20 100,0 -0.5 101)")
     }
+
+    #[test]
+    #[cfg(feature = "with-wkb")]
+    fn srid_override() {
+        use crate::wkb::Ewkb;
+        use crate::wkt::{WktDialect, WktWriter};
+        use crate::{CoordDimensions, GeozeroGeometry};
+
+        // SRID=4326;POINT(10 -20)
+        let ewkb = Ewkb(hex::decode("0101000020E6100000000000000000244000000000000034C0").unwrap());
+        let mut out = Vec::new();
+        let mut writer = WktWriter::with_opts(
+            &mut out,
+            WktDialect::Ewkt,
+            CoordDimensions::xy(),
+            Some(3857),
+        );
+        ewkb.process_geom(&mut writer).unwrap();
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            "SRID=3857;POINT(10 -20)"
+        );
+
+        let mut out = Vec::new();
+        let mut writer = WktWriter::with_opts(
+            &mut out,
+            WktDialect::Ewkt,
+            CoordDimensions::xy(),
+            Some(3857),
+        );
+        writer.set_srid_override(true);
+        ewkb.process_geom(&mut writer).unwrap();
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            "SRID=4326;POINT(10 -20)"
+        );
+
+        // SRID=0;POINT(10 -20)
+        let ewkb = Ewkb(hex::decode("010100002000000000000000000000244000000000000034C0").unwrap());
+        let mut out = Vec::new();
+        let mut writer = WktWriter::with_opts(
+            &mut out,
+            WktDialect::Ewkt,
+            CoordDimensions::xy(),
+            Some(3857),
+        );
+        writer.set_srid_override(true);
+        ewkb.process_geom(&mut writer).unwrap();
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            "SRID=3857;POINT(10 -20)"
+        );
+    }
 }
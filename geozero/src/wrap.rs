@@ -1,5 +1,7 @@
+//! Processor adapters transforming coordinates before forwarding them to an inner processor.
 use crate::{
-    error::Result, ColumnValue, CoordDimensions, FeatureProcessor, GeomProcessor, PropertyProcessor,
+    error::Result, ColumnDef, ColumnValue, CoordDimensions, Crs, FeatureId, FeatureProcessor,
+    GeomProcessor, PropertyProcessor,
 };
 
 /// Wraps another [`FeatureProcessor`], first transforming coordinates.
@@ -36,6 +38,34 @@ impl<T: GeomProcessor, F: Fn(&mut f64, &mut f64)> GeomProcessor for WrappedXYPro
     fn srid(&mut self, srid: Option<i32>) -> Result<()> {
         self.inner.srid(srid)
     }
+    // The envelope of the transformed corners is exact for affine transformations
+    fn envelope(&mut self, envelope: &[f64], dims: CoordDimensions, idx: usize) -> Result<()> {
+        if envelope.len() < 4 {
+            return self.inner.envelope(envelope, dims, idx);
+        }
+        let mut transformed = envelope.to_vec();
+        let mut bounds = [
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+        ];
+        let [minx, maxx, miny, maxy] = [envelope[0], envelope[1], envelope[2], envelope[3]];
+        for (mut x, mut y) in [(minx, miny), (minx, maxy), (maxx, miny), (maxx, maxy)] {
+            (self.pre_process_xy)(&mut x, &mut y);
+            bounds = [
+                bounds[0].min(x),
+                bounds[1].max(x),
+                bounds[2].min(y),
+                bounds[3].max(y),
+            ];
+        }
+        transformed[..4].copy_from_slice(&bounds);
+        self.inner.envelope(&transformed, dims, idx)
+    }
+    fn size_hint_total_coords(&mut self, count: usize) -> Result<()> {
+        self.inner.size_hint_total_coords(count)
+    }
     fn xy(&mut self, mut x: f64, mut y: f64, idx: usize) -> Result<()> {
         (self.pre_process_xy)(&mut x, &mut y);
         self.inner.xy(x, y, idx)
@@ -154,6 +184,15 @@ impl<T: PropertyProcessor, F: Fn(&mut f64, &mut f64)> PropertyProcessor
     fn property(&mut self, idx: usize, name: &str, value: &ColumnValue<'_>) -> Result<bool> {
         self.inner.property(idx, name, value)
     }
+    fn column_property(
+        &mut self,
+        idx: usize,
+        column: usize,
+        name: &str,
+        value: &ColumnValue<'_>,
+    ) -> Result<bool> {
+        self.inner.column_property(idx, column, name, value)
+    }
 }
 
 impl<T: FeatureProcessor, F: Fn(&mut f64, &mut f64)> FeatureProcessor for WrappedXYProcessor<T, F> {
@@ -163,9 +202,18 @@ impl<T: FeatureProcessor, F: Fn(&mut f64, &mut f64)> FeatureProcessor for Wrappe
     fn dataset_end(&mut self) -> Result<()> {
         self.inner.dataset_end()
     }
+    fn crs(&mut self, crs: &Crs) -> Result<()> {
+        self.inner.crs(crs)
+    }
+    fn schema_begin(&mut self, columns: &[ColumnDef]) -> Result<()> {
+        self.inner.schema_begin(columns)
+    }
     fn feature_begin(&mut self, idx: u64) -> Result<()> {
         self.inner.feature_begin(idx)
     }
+    fn feature_id(&mut self, id: &FeatureId) -> Result<()> {
+        self.inner.feature_id(id)
+    }
     fn feature_end(&mut self, idx: u64) -> Result<()> {
         self.inner.feature_end(idx)
     }
@@ -183,12 +231,453 @@ impl<T: FeatureProcessor, F: Fn(&mut f64, &mut f64)> FeatureProcessor for Wrappe
     }
 }
 
+/// Call `$method` on a delegation target, on `Some(field)` targets only if the field is set
+macro_rules! delegate_call {
+    ($self:ident, Some($field:ident), $method:ident($($arg:expr),*)) => {
+        if let Some(target) = &mut $self.$field {
+            target.$method($($arg),*)?;
+        }
+    };
+    ($self:ident, $field:ident, $method:ident($($arg:expr),*)) => {
+        $self.$field.$method($($arg),*)?;
+    };
+}
+
+pub(crate) use delegate_call;
+
+/// Delegate SRID, envelope and coordinate events to each of the given fields in order
+macro_rules! delegate_coordinates {
+    ($($target:ident $(($field:ident))?),+) => {
+        fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+            $($crate::wrap::delegate_call!(self, $target $(($field))?, srid(srid));)+
+            Ok(())
+        }
+        fn envelope(&mut self, envelope: &[f64], dims: CoordDimensions, idx: usize) -> Result<()> {
+            $($crate::wrap::delegate_call!(self, $target $(($field))?, envelope(envelope, dims, idx));)+
+            Ok(())
+        }
+        fn size_hint_total_coords(&mut self, count: usize) -> Result<()> {
+            $($crate::wrap::delegate_call!(self, $target $(($field))?, size_hint_total_coords(count));)+
+            Ok(())
+        }
+        fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+            $($crate::wrap::delegate_call!(self, $target $(($field))?, xy(x, y, idx));)+
+            Ok(())
+        }
+        fn coords(&mut self, coords: &[f64], dims: CoordDimensions, idx: usize) -> Result<()> {
+            $($crate::wrap::delegate_call!(self, $target $(($field))?, coords(coords, dims, idx));)+
+            Ok(())
+        }
+        fn coordinate(
+            &mut self,
+            x: f64,
+            y: f64,
+            z: Option<f64>,
+            m: Option<f64>,
+            t: Option<f64>,
+            tm: Option<u64>,
+            idx: usize,
+        ) -> Result<()> {
+            $($crate::wrap::delegate_call!(self, $target $(($field))?, coordinate(x, y, z, m, t, tm, idx));)+
+            Ok(())
+        }
+    };
+}
+
+pub(crate) use delegate_coordinates;
+
+/// Delegate all geometry type events to `self.inner`, or to each of the given fields in order
+macro_rules! delegate_geometry_types {
+    () => {
+        $crate::wrap::delegate_geometry_types!(inner);
+    };
+    ($($target:ident $(($field:ident))?),+) => {
+        fn empty_point(&mut self, idx: usize) -> Result<()> {
+            $($crate::wrap::delegate_call!(self, $target $(($field))?, empty_point(idx));)+
+            Ok(())
+        }
+        fn point_begin(&mut self, idx: usize) -> Result<()> {
+            $($crate::wrap::delegate_call!(self, $target $(($field))?, point_begin(idx));)+
+            Ok(())
+        }
+        fn point_end(&mut self, idx: usize) -> Result<()> {
+            $($crate::wrap::delegate_call!(self, $target $(($field))?, point_end(idx));)+
+            Ok(())
+        }
+        fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+            $($crate::wrap::delegate_call!(self, $target $(($field))?, multipoint_begin(size, idx));)+
+            Ok(())
+        }
+        fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+            $($crate::wrap::delegate_call!(self, $target $(($field))?, multipoint_end(idx));)+
+            Ok(())
+        }
+        fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+            $($crate::wrap::delegate_call!(self, $target $(($field))?, linestring_begin(tagged, size, idx));)+
+            Ok(())
+        }
+        fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+            $($crate::wrap::delegate_call!(self, $target $(($field))?, linestring_end(tagged, idx));)+
+            Ok(())
+        }
+        fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+            $($crate::wrap::delegate_call!(self, $target $(($field))?, multilinestring_begin(size, idx));)+
+            Ok(())
+        }
+        fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+            $($crate::wrap::delegate_call!(self, $target $(($field))?, multilinestring_end(idx));)+
+            Ok(())
+        }
+        fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+            $($crate::wrap::delegate_call!(self, $target $(($field))?, polygon_begin(tagged, size, idx));)+
+            Ok(())
+        }
+        fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+            $($crate::wrap::delegate_call!(self, $target $(($field))?, polygon_end(tagged, idx));)+
+            Ok(())
+        }
+        fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+            $($crate::wrap::delegate_call!(self, $target $(($field))?, multipolygon_begin(size, idx));)+
+            Ok(())
+        }
+        fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+            $($crate::wrap::delegate_call!(self, $target $(($field))?, multipolygon_end(idx));)+
+            Ok(())
+        }
+        fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+            $($crate::wrap::delegate_call!(self, $target $(($field))?, geometrycollection_begin(size, idx));)+
+            Ok(())
+        }
+        fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+            $($crate::wrap::delegate_call!(self, $target $(($field))?, geometrycollection_end(idx));)+
+            Ok(())
+        }
+        fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+            $($crate::wrap::delegate_call!(self, $target $(($field))?, circularstring_begin(size, idx));)+
+            Ok(())
+        }
+        fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+            $($crate::wrap::delegate_call!(self, $target $(($field))?, circularstring_end(idx));)+
+            Ok(())
+        }
+        fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+            $($crate::wrap::delegate_call!(self, $target $(($field))?, compoundcurve_begin(size, idx));)+
+            Ok(())
+        }
+        fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+            $($crate::wrap::delegate_call!(self, $target $(($field))?, compoundcurve_end(idx));)+
+            Ok(())
+        }
+        fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+            $($crate::wrap::delegate_call!(self, $target $(($field))?, curvepolygon_begin(size, idx));)+
+            Ok(())
+        }
+        fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+            $($crate::wrap::delegate_call!(self, $target $(($field))?, curvepolygon_end(idx));)+
+            Ok(())
+        }
+        fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+            $($crate::wrap::delegate_call!(self, $target $(($field))?, multicurve_begin(size, idx));)+
+            Ok(())
+        }
+        fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+            $($crate::wrap::delegate_call!(self, $target $(($field))?, multicurve_end(idx));)+
+            Ok(())
+        }
+        fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+            $($crate::wrap::delegate_call!(self, $target $(($field))?, multisurface_begin(size, idx));)+
+            Ok(())
+        }
+        fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+            $($crate::wrap::delegate_call!(self, $target $(($field))?, multisurface_end(idx));)+
+            Ok(())
+        }
+        fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+            $($crate::wrap::delegate_call!(self, $target $(($field))?, triangle_begin(tagged, size, idx));)+
+            Ok(())
+        }
+        fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+            $($crate::wrap::delegate_call!(self, $target $(($field))?, triangle_end(tagged, idx));)+
+            Ok(())
+        }
+        fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+            $($crate::wrap::delegate_call!(self, $target $(($field))?, polyhedralsurface_begin(size, idx));)+
+            Ok(())
+        }
+        fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+            $($crate::wrap::delegate_call!(self, $target $(($field))?, polyhedralsurface_end(idx));)+
+            Ok(())
+        }
+        fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+            $($crate::wrap::delegate_call!(self, $target $(($field))?, tin_begin(size, idx));)+
+            Ok(())
+        }
+        fn tin_end(&mut self, idx: usize) -> Result<()> {
+            $($crate::wrap::delegate_call!(self, $target $(($field))?, tin_end(idx));)+
+            Ok(())
+        }
+    };
+}
+
+pub(crate) use delegate_geometry_types;
+
+/// Delegate [FeatureProcessor] and [PropertyProcessor] events to `self.inner`
+macro_rules! delegate_feature_processor {
+    ($wrapper:ident) => {
+        impl<T: PropertyProcessor> PropertyProcessor for $wrapper<T> {
+            fn property(
+                &mut self,
+                idx: usize,
+                name: &str,
+                value: &ColumnValue<'_>,
+            ) -> Result<bool> {
+                self.inner.property(idx, name, value)
+            }
+            fn column_property(
+                &mut self,
+                idx: usize,
+                column: usize,
+                name: &str,
+                value: &ColumnValue<'_>,
+            ) -> Result<bool> {
+                self.inner.column_property(idx, column, name, value)
+            }
+        }
+
+        impl<T: FeatureProcessor> FeatureProcessor for $wrapper<T> {
+            fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+                self.inner.dataset_begin(name)
+            }
+            fn dataset_end(&mut self) -> Result<()> {
+                self.inner.dataset_end()
+            }
+            fn crs(&mut self, crs: &Crs) -> Result<()> {
+                self.inner.crs(crs)
+            }
+            fn schema_begin(&mut self, columns: &[ColumnDef]) -> Result<()> {
+                self.inner.schema_begin(columns)
+            }
+            fn feature_begin(&mut self, idx: u64) -> Result<()> {
+                self.inner.feature_begin(idx)
+            }
+            fn feature_id(&mut self, id: &FeatureId) -> Result<()> {
+                self.inner.feature_id(id)
+            }
+            fn feature_end(&mut self, idx: u64) -> Result<()> {
+                self.inner.feature_end(idx)
+            }
+            fn properties_begin(&mut self) -> Result<()> {
+                self.inner.properties_begin()
+            }
+            fn properties_end(&mut self) -> Result<()> {
+                self.inner.properties_end()
+            }
+            fn geometry_begin(&mut self) -> Result<()> {
+                self.inner.geometry_begin()
+            }
+            fn geometry_end(&mut self) -> Result<()> {
+                self.inner.geometry_end()
+            }
+        }
+    };
+}
+
+pub(crate) use delegate_feature_processor;
+
+/// Remove the envelope range of the dimension at `pos` (0: Z, 1: M) from `envelope`
+fn strip_envelope_dim(envelope: &[f64], dims: CoordDimensions, pos: usize) -> Vec<f64> {
+    let mut envelope = envelope.to_vec();
+    let present = if pos == 0 { dims.z } else { dims.m };
+    let start = 4 + 2 * usize::from(pos == 1 && dims.z);
+    if present && envelope.len() >= start + 2 {
+        envelope.drain(start..start + 2);
+    }
+    envelope
+}
+
+/// Wraps another processor, dropping Z values.
+///
+/// ```
+/// # #[cfg(feature = "with-wkt")]
+/// # {
+/// use geozero::wkt::{Wkt, WktWriter};
+/// use geozero::{CoordDimensions, GeozeroGeometry, StripZ};
+///
+/// let mut out = Vec::new();
+/// let mut writer = StripZ::new(WktWriter::with_dims(&mut out, CoordDimensions::xyzm()));
+/// Wkt("POINT ZM (1 2 3 4)").process_geom(&mut writer).unwrap();
+/// drop(writer);
+/// assert_eq!(out, b"POINT(1 2 4)");
+/// # }
+/// ```
+pub struct StripZ<T> {
+    pub inner: T,
+}
+
+impl<T> StripZ<T> {
+    pub fn new(inner: T) -> Self {
+        StripZ { inner }
+    }
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: GeomProcessor> GeomProcessor for StripZ<T> {
+    fn dimensions(&self) -> CoordDimensions {
+        CoordDimensions {
+            z: false,
+            ..self.inner.dimensions()
+        }
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.inner.srid(srid)
+    }
+    fn envelope(&mut self, envelope: &[f64], dims: CoordDimensions, idx: usize) -> Result<()> {
+        let envelope = strip_envelope_dim(envelope, dims, 0);
+        let dims = CoordDimensions { z: false, ..dims };
+        self.inner.envelope(&envelope, dims, idx)
+    }
+    fn size_hint_total_coords(&mut self, count: usize) -> Result<()> {
+        self.inner.size_hint_total_coords(count)
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.inner.xy(x, y, idx)
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        _z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.inner.coordinate(x, y, None, m, t, tm, idx)
+    }
+    delegate_geometry_types!();
+}
+
+delegate_feature_processor!(StripZ);
+
+/// Wraps another processor, dropping M values.
+pub struct StripM<T> {
+    pub inner: T,
+}
+
+impl<T> StripM<T> {
+    pub fn new(inner: T) -> Self {
+        StripM { inner }
+    }
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: GeomProcessor> GeomProcessor for StripM<T> {
+    fn dimensions(&self) -> CoordDimensions {
+        CoordDimensions {
+            m: false,
+            ..self.inner.dimensions()
+        }
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.inner.srid(srid)
+    }
+    fn envelope(&mut self, envelope: &[f64], dims: CoordDimensions, idx: usize) -> Result<()> {
+        let envelope = strip_envelope_dim(envelope, dims, 1);
+        let dims = CoordDimensions { m: false, ..dims };
+        self.inner.envelope(&envelope, dims, idx)
+    }
+    fn size_hint_total_coords(&mut self, count: usize) -> Result<()> {
+        self.inner.size_hint_total_coords(count)
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.inner.xy(x, y, idx)
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        _m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.inner.coordinate(x, y, z, None, t, tm, idx)
+    }
+    delegate_geometry_types!();
+}
+
+delegate_feature_processor!(StripM);
+
+/// Wraps another processor, swapping X and Y coordinates.
+///
+/// Fixes the axis order of sources returning latitude/longitude, like some WMS servers
+/// or MySQL with geographic SRS.
+pub struct SwapXY<T> {
+    pub inner: T,
+}
+
+impl<T> SwapXY<T> {
+    pub fn new(inner: T) -> Self {
+        SwapXY { inner }
+    }
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: GeomProcessor> GeomProcessor for SwapXY<T> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.inner.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.inner.multi_dim()
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.inner.srid(srid)
+    }
+    fn envelope(&mut self, envelope: &[f64], dims: CoordDimensions, idx: usize) -> Result<()> {
+        let mut envelope = envelope.to_vec();
+        if envelope.len() >= 4 {
+            // [minx, maxx, miny, maxy]
+            envelope[..4].rotate_left(2);
+        }
+        self.inner.envelope(&envelope, dims, idx)
+    }
+    fn size_hint_total_coords(&mut self, count: usize) -> Result<()> {
+        self.inner.size_hint_total_coords(count)
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.inner.xy(y, x, idx)
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.inner.coordinate(y, x, z, m, t, tm, idx)
+    }
+    delegate_geometry_types!();
+}
+
+delegate_feature_processor!(SwapXY);
+
 #[cfg(all(feature = "with-csv", feature = "with-geojson"))]
 #[cfg(test)]
 mod test {
     use crate::csv::CsvWriter;
+    use crate::error::Result;
     use crate::geojson::GeoJsonString;
-    use crate::{GeomProcessor, GeozeroDatasource};
+    use crate::{CoordDimensions, GeomProcessor, GeozeroDatasource};
     use serde_json::json;
 
     fn geojson_fixture_data() -> GeoJsonString {
@@ -240,6 +729,30 @@ mod test {
         );
     }
 
+    #[test]
+    fn transform_envelope() -> Result<()> {
+        struct Envelope(Vec<f64>);
+        impl GeomProcessor for Envelope {
+            fn envelope(
+                &mut self,
+                envelope: &[f64],
+                _dims: CoordDimensions,
+                _idx: usize,
+            ) -> Result<()> {
+                self.0 = envelope.to_vec();
+                Ok(())
+            }
+        }
+        // Rotation by 90 degrees
+        let mut processor = Envelope(Vec::new()).pre_process_xy(|x: &mut f64, y: &mut f64| {
+            (*x, *y) = (-*y, *x);
+        });
+        let dims = CoordDimensions::xyz();
+        processor.envelope(&[1.0, 2.0, 3.0, 5.0, 6.0, 7.0], dims, 0)?;
+        assert_eq!(processor.inner.0, [-5.0, -3.0, 1.0, 2.0, 6.0, 7.0]);
+        Ok(())
+    }
+
     #[test]
     fn multiple_transforms() {
         let mut geojson = geojson_fixture_data();
@@ -265,4 +778,37 @@ mod test {
             "geometry,population\nPOINT(3 6),100\nPOINT(7 10),200\n"
         );
     }
+
+    #[test]
+    fn strip_and_swap() {
+        use crate::wkt::{Wkt, WktWriter};
+        use crate::{CoordDimensions, GeozeroGeometry, StripM, StripZ, SwapXY};
+
+        let wkt = Wkt("LINESTRING ZM (1 2 3 4,5 6 7 8)");
+        let mut out = Vec::new();
+        wkt.process_geom(&mut StripM::new(WktWriter::with_dims(
+            &mut out,
+            CoordDimensions::xyzm(),
+        )))
+        .unwrap();
+        assert_eq!(out, b"LINESTRING(1 2 3,5 6 7)");
+
+        let mut out = Vec::new();
+        wkt.process_geom(&mut StripM::new(StripZ::new(WktWriter::with_dims(
+            &mut out,
+            CoordDimensions::xyzm(),
+        ))))
+        .unwrap();
+        assert_eq!(out, b"LINESTRING(1 2,5 6)");
+
+        let mut out = Vec::new();
+        let mut geojson = geojson_fixture_data();
+        geojson
+            .process(&mut SwapXY::new(CsvWriter::new(&mut out)))
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "geometry,population\nPOINT(2 1),100\nPOINT(4 3),200\n"
+        );
+    }
 }
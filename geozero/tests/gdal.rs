@@ -23,3 +23,22 @@ fn ogr_to_svg() -> Result<(), gdal::errors::GdalError> {
     );
     Ok(())
 }
+
+#[test]
+fn ogr_layer_to_geojson() -> Result<(), Box<dyn std::error::Error>> {
+    use geozero::gdal::GdalLayerReader;
+    use geozero::geojson::GeoJsonWriter;
+    use geozero::GeozeroDatasource;
+
+    let dataset = Dataset::open(Path::new("tests/data/places.json"))?;
+    let mut reader = GdalLayerReader::new(dataset.layer(0)?);
+    let mut out: Vec<u8> = Vec::new();
+    reader.process(&mut GeoJsonWriter::new(&mut out))?;
+    let json: serde_json::Value = serde_json::from_slice(&out)?;
+    assert_eq!(json["name"], "ne_50m_populated_places");
+    let features = json["features"].as_array().unwrap();
+    assert_eq!(features.len(), 1249);
+    assert_eq!(features[0]["properties"]["NAME"], "Bombo");
+    assert_eq!(features[0]["geometry"]["type"], "Point");
+    Ok(())
+}
@@ -103,3 +103,238 @@ async fn geos_query() -> Result<(), sqlx::Error> {
 
     Ok(())
 }
+
+#[tokio::test]
+#[cfg(feature = "with-geojson")]
+async fn write_features() -> Result<(), geozero::error::GeozeroError> {
+    use geozero::geojson::GeoJson;
+    use geozero::gpkg::GpkgWriter;
+    use geozero::ToWkt;
+    use sqlx::sqlite::SqliteConnectOptions;
+    use sqlx::ConnectOptions;
+    use std::str::FromStr;
+
+    let mut conn = SqliteConnectOptions::from_str("sqlite::memory:")?
+        .connect()
+        .await?;
+    let mut geojson = GeoJson(
+        r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"name": "A", "population": 10}, "geometry": {"type": "Point", "coordinates": [1, 2]}},
+            {"type": "Feature", "properties": {"name": "B"}, "geometry": {"type": "Point", "coordinates": [3, 4]}},
+            {"type": "Feature", "properties": {"name": "C", "capital": true}, "geometry": null}
+        ]}"#,
+    );
+    let mut writer = GpkgWriter::new(&mut conn, "places");
+    writer.set_srs_id(4326);
+    assert_eq!(writer.write(&mut geojson).await?, 3);
+
+    let row: (String, String, i32) = sqlx::query_as(
+        "SELECT column_name, geometry_type_name, srs_id FROM gpkg_geometry_columns WHERE table_name = 'places'",
+    )
+    .fetch_one(&mut conn)
+    .await?;
+    assert_eq!(row, ("geom".to_string(), "POINT".to_string(), 4326));
+
+    let row: (f64, f64, f64, f64) = sqlx::query_as(
+        "SELECT min_x, min_y, max_x, max_y FROM gpkg_contents WHERE table_name = 'places'",
+    )
+    .fetch_one(&mut conn)
+    .await?;
+    assert_eq!(row, (1.0, 2.0, 3.0, 4.0));
+
+    let rows: Vec<(String, Option<i64>, wkb::GpkgWkb<Vec<u8>>)> =
+        sqlx::query_as("SELECT name, population, geom FROM places ORDER BY fid")
+            .fetch_all(&mut conn)
+            .await?;
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows[0].0, "A");
+    assert_eq!(rows[0].1, Some(10));
+    assert_eq!(rows[1].1, None);
+    assert_eq!(rows[1].2.to_wkt()?, "POINT(3 4)");
+    assert!(rows[2].2 .0.is_empty());
+
+    let row: (i64,) =
+        sqlx::query_as("SELECT count(*) FROM rtree_places_geom WHERE minx <= 2 AND maxy >= 1")
+            .fetch_one(&mut conn)
+            .await?;
+    assert_eq!(row.0, 1);
+
+    // Column of property after first feature
+    let row: (bool,) = sqlx::query_as("SELECT capital FROM places WHERE name = 'C'")
+        .fetch_one(&mut conn)
+        .await?;
+    assert!(row.0);
+
+    // Without RTree triggers, features can be inserted without GeoPackage SQL functions
+    sqlx::query("INSERT INTO places (name) VALUES ('D')")
+        .execute(&mut conn)
+        .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+#[cfg(feature = "with-geojson")]
+async fn write_custom_crs() -> Result<(), geozero::error::GeozeroError> {
+    use geozero::geojson::GeoJson;
+    use geozero::gpkg::{GpkgReader, GpkgWriter};
+    use geozero::{Crs, FeatureProcessor, FeatureSpool, GeozeroDatasource, ProcessorSink};
+    use sqlx::sqlite::SqliteConnectOptions;
+    use sqlx::ConnectOptions;
+    use std::str::FromStr;
+
+    let mut conn = SqliteConnectOptions::from_str("sqlite::memory:")?
+        .connect()
+        .await?;
+    let wkt = r#"LOCAL_CS["local"]"#;
+    let mut spool = FeatureSpool::new();
+    GeoJson(
+        r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"name": "A"}, "geometry": {"type": "Point", "coordinates": [1, 2]}}
+        ]}"#,
+    )
+    .process(&mut spool)?;
+
+    let mut srs_ids = Vec::new();
+    for (table, crs) in [
+        ("local", Crs::from_wkt(wkt)),
+        ("local2", Crs::from_wkt(wkt)),
+        ("esri", Crs::new("ESRI", "102100")),
+    ] {
+        FeatureProcessor::crs(&mut spool, &crs)?;
+        GpkgWriter::new(&mut conn, table).write(&mut spool).await?;
+        let (srs_id,): (i32,) =
+            sqlx::query_as("SELECT srs_id FROM gpkg_geometry_columns WHERE table_name = ?")
+                .bind(table)
+                .fetch_one(&mut conn)
+                .await?;
+        srs_ids.push(srs_id);
+    }
+    // Identical CRS are registered once
+    assert_eq!(srs_ids, vec![100000, 100000, 100001]);
+
+    let row: (String, i32, String) = sqlx::query_as(
+        "SELECT organization, organization_coordsys_id, definition FROM gpkg_spatial_ref_sys WHERE srs_id = 100000",
+    )
+    .fetch_one(&mut conn)
+    .await?;
+    assert_eq!(row, ("NONE".to_string(), 100000, wkt.to_string()));
+    let row: (String, i32) = sqlx::query_as(
+        "SELECT organization, organization_coordsys_id FROM gpkg_spatial_ref_sys WHERE srs_id = 100001",
+    )
+    .fetch_one(&mut conn)
+    .await?;
+    assert_eq!(row, ("ESRI".to_string(), 102100));
+
+    let mut reader = GpkgReader::new("local");
+    reader.read(&mut conn, &mut ProcessorSink).await?;
+    assert_eq!(reader.crs(), Some(&Crs::from_wkt(wkt)));
+
+    Ok(())
+}
+
+#[tokio::test]
+#[cfg(feature = "with-geojson")]
+async fn write_feature_ids() -> Result<(), geozero::error::GeozeroError> {
+    use geozero::geojson::GeoJson;
+    use geozero::gpkg::GpkgWriter;
+    use sqlx::sqlite::SqliteConnectOptions;
+    use sqlx::ConnectOptions;
+    use std::str::FromStr;
+
+    let mut conn = SqliteConnectOptions::from_str("sqlite::memory:")?
+        .connect()
+        .await?;
+    let features = r#"{"type": "FeatureCollection", "features": [
+        {"type": "Feature", "id": 7, "properties": {"name": "A"}, "geometry": null},
+        {"type": "Feature", "properties": {"name": "B"}, "geometry": null},
+        {"type": "Feature", "id": 7, "properties": {"name": "C"}, "geometry": null}
+    ]}"#;
+
+    // Ids are assigned by SQLite by default
+    let mut writer = GpkgWriter::new(&mut conn, "assigned");
+    assert_eq!(writer.write(&mut GeoJson(features)).await?, 3);
+    let fids: Vec<(i64,)> = sqlx::query_as("SELECT fid FROM assigned ORDER BY fid")
+        .fetch_all(&mut conn)
+        .await?;
+    assert_eq!(fids, vec![(1,), (2,), (3,)]);
+
+    let mut writer = GpkgWriter::new(&mut conn, "preserved");
+    writer.set_preserve_fid(true);
+    let result = writer.write(&mut GeoJson(features)).await;
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "processing feature: `duplicate feature id 7`"
+    );
+
+    let mut writer = GpkgWriter::new(&mut conn, "preserved");
+    writer.set_preserve_fid(true);
+    let features = features.replace(
+        r#""id": 7, "properties": {"name": "C"}"#,
+        r#""id": 2, "properties": {"name": "C"}"#,
+    );
+    assert_eq!(writer.write(&mut GeoJson(&features)).await?, 3);
+    let fids: Vec<(i64, String)> = sqlx::query_as("SELECT fid, name FROM preserved ORDER BY fid")
+        .fetch_all(&mut conn)
+        .await?;
+    assert_eq!(
+        fids,
+        vec![
+            (2, "C".to_string()),
+            (7, "A".to_string()),
+            (8, "B".to_string())
+        ]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+#[cfg(feature = "with-geojson")]
+async fn read_features() -> Result<(), geozero::error::GeozeroError> {
+    use geozero::geojson::GeoJsonWriter;
+    use geozero::gpkg::GpkgReader;
+    use geozero::GeozeroDatasource;
+    use sqlx::sqlite::SqliteConnection;
+    use sqlx::Connection;
+
+    let mut conn = SqliteConnection::connect("sqlite://tests/data/gpkg_test.gpkg").await?;
+
+    let mut reader = GpkgReader::new("pt2d");
+    let mut json: Vec<u8> = Vec::new();
+    let count = reader
+        .read(&mut conn, &mut GeoJsonWriter::new(&mut json))
+        .await?;
+    assert_eq!(count, 4);
+    assert_eq!(reader.crs().and_then(|crs| crs.srid()), Some(4326));
+    let json = String::from_utf8(json).unwrap();
+    assert!(json.contains(r#""name": "alpha""#));
+    assert!(json.contains(r#""coordinates": [1.1,1.1]"#));
+    assert!(!json.contains(r#""id""#));
+
+    let mut reader = GpkgReader::new("pt2d");
+    reader.set_bbox(1.5, 2.0, 2.0, 3.0);
+    let mut json: Vec<u8> = Vec::new();
+    let count = reader
+        .read(&mut conn, &mut GeoJsonWriter::new(&mut json))
+        .await?;
+    assert_eq!(count, 2);
+    let json = String::from_utf8(json).unwrap();
+    assert!(!json.contains("alpha"));
+    assert!(json.contains("beta"));
+    assert!(json.contains("gamma"));
+
+    let mut reader = GpkgReader::new("pt2d");
+    reader.set_bbox(1.5, 2.0, 2.0, 3.0);
+    let mut features = reader.fetch(&mut conn).await?;
+    assert_eq!(features.len(), 2);
+    assert_eq!(
+        features.dataset_crs().and_then(|crs| crs.srid()),
+        Some(4326)
+    );
+    let mut fetched: Vec<u8> = Vec::new();
+    features.process(&mut GeoJsonWriter::new(&mut fetched))?;
+    assert_eq!(String::from_utf8(fetched).unwrap(), json);
+
+    Ok(())
+}
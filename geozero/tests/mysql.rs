@@ -0,0 +1,62 @@
+use geozero::wkb;
+use geozero::ToWkt as _;
+use sqlx::mysql::{MySqlPool, MySqlPoolOptions};
+
+async fn get_pool() -> MySqlPool {
+    MySqlPoolOptions::new()
+        .max_connections(5)
+        .connect(&std::env::var("MYSQL_DATABASE_URL").unwrap())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+#[ignore]
+async fn blob_query() -> Result<(), sqlx::Error> {
+    let pool = get_pool().await;
+
+    let row: (wkb::MySQLWkb<Vec<u8>>,) =
+        sqlx::query_as("SELECT ST_GeomFromText('POLYGON ((0 0, 2 0, 2 2, 0 2, 0 0))', 4326)")
+            .fetch_one(&pool)
+            .await?;
+
+    let wkt = row.0.to_wkt().expect("to_wkt failed");
+    assert_eq!(&wkt, "POLYGON((0 0,2 0,2 2,0 2,0 0))");
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn rust_geo_query() -> Result<(), sqlx::Error> {
+    let pool = get_pool().await;
+
+    let row: (wkb::Decode<geo_types::Geometry<f64>>,) =
+        sqlx::query_as("SELECT ST_GeomFromText('POLYGON ((0 0, 2 0, 2 2, 0 2, 0 0))')")
+            .fetch_one(&pool)
+            .await?;
+    if let Some(geo_types::Geometry::Polygon(poly)) = row.0.geometry {
+        assert_eq!(
+            *poly.exterior(),
+            vec![(0.0, 0.0), (2.0, 0.0), (2.0, 2.0), (0.0, 2.0), (0.0, 0.0)].into()
+        );
+    } else {
+        panic!("Conversion to geo_types::Geometry failed");
+    }
+
+    let row: (wkb::Decode<geo_types::Geometry<f64>>,) =
+        sqlx::query_as("SELECT CAST(NULL AS GEOMETRY)")
+            .fetch_one(&pool)
+            .await?;
+    assert!(row.0.geometry.is_none());
+
+    // Roundtrip
+    let geom: geo_types::Geometry<f64> = geo::Point::new(10.0, 20.0).into();
+    let row: (String,) = sqlx::query_as("SELECT ST_AsText(?)")
+        .bind(wkb::Encode(geom))
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(row.0, "POINT(10 20)");
+
+    Ok(())
+}
@@ -138,13 +138,41 @@ mod postgis_postgres {
             Ok(())
         }
     }
+
+    #[test]
+    #[ignore]
+    #[cfg(feature = "with-geojson")]
+    fn datasource() -> Result<(), geozero::error::GeozeroError> {
+        use geozero::geojson::GeoJsonWriter;
+        use geozero::postgis::postgres::PgDatasource;
+        use geozero::GeozeroDatasource;
+
+        let mut client = postgres::Client::connect(&get_db_string(), postgres::NoTls)?;
+        let mut datasource = PgDatasource::new(
+            &mut client,
+            "SELECT * FROM (VALUES ('a'::text, 1::int4, 'SRID=4326;POINT(1 2)'::geometry), ('b', NULL, NULL)) AS t(name, num, geom)",
+        );
+        let mut json: Vec<u8> = Vec::new();
+        datasource.process(&mut GeoJsonWriter::new(&mut json))?;
+        assert_eq!(
+            std::str::from_utf8(&json).unwrap(),
+            r#"{
+"type": "FeatureCollection",
+"features": [{"type": "Feature", "properties": {"name": "a", "num": 1}, "geometry": {"type": "Point", "coordinates": [1,2]}},
+{"type": "Feature", "properties": {"name": "b"}}]}"#
+        );
+        Ok(())
+    }
 }
 
 #[cfg(feature = "with-postgis-sqlx")]
 mod postgis_sqlx {
     use super::PointZ;
     use crate::pg;
+    use geozero::geojson::GeoJsonString;
+    use geozero::svg::SvgString;
     use geozero::wkb;
+    use geozero::wkt::{Ewkt, Wkt};
     use geozero::ToWkt as _;
 
     #[tokio::test]
@@ -194,6 +222,29 @@ mod postgis_sqlx {
         Ok(())
     }
 
+    #[tokio::test]
+    #[ignore]
+    async fn string_query() -> Result<(), sqlx::Error> {
+        let pool = pg::get_pool().await;
+
+        let row: (GeoJsonString, Wkt<String>, Ewkt<String>, SvgString) = sqlx::query_as(
+            "SELECT geom, geom, geom, geom FROM (SELECT 'SRID=4326;POINT(1 2)'::geometry AS geom) t",
+        )
+        .fetch_one(&pool)
+        .await?;
+        assert_eq!(row.0 .0, r#"{"type": "Point", "coordinates": [1,2]}"#);
+        assert_eq!(row.1 .0, "POINT(1 2)");
+        assert_eq!(row.2 .0, "SRID=4326;POINT(1 2)");
+        assert_eq!(row.3 .0, r#"<path d="M 1 2 Z"/>"#);
+
+        let row: (String,) = sqlx::query_as("SELECT ST_AsText($1)")
+            .bind(Wkt("POINT(1 2)".to_string()))
+            .fetch_one(&pool)
+            .await?;
+        assert_eq!(row.0, "POINT(1 2)");
+        Ok(())
+    }
+
     #[tokio::test]
     #[ignore]
     async fn rust_geo_query() -> Result<(), sqlx::Error> {
@@ -232,6 +283,34 @@ mod postgis_sqlx {
         Ok(())
     }
 
+    #[tokio::test]
+    #[ignore]
+    async fn geography_query() -> Result<(), sqlx::Error> {
+        let pool = pg::get_pool().await;
+
+        let row: (wkb::Decode<geo_types::Geometry<f64>>,) =
+            sqlx::query_as("SELECT 'POINT(1 2)'::geography")
+                .fetch_one(&pool)
+                .await?;
+        assert_eq!(row.0.geometry, Some(geo::Point::new(1.0, 2.0).into()));
+
+        let geom: geo_types::Geometry<f64> = geo::Point::new(10.0, 20.0).into();
+        let row: (i32,) = sqlx::query_as("SELECT ST_SRID($1)")
+            .bind(wkb::EncodeGeography(geom))
+            .fetch_one(&pool)
+            .await?;
+        assert_eq!(row.0, 4326);
+
+        let geom =
+            wkb::Ewkb(hex::decode("0101000020110F000000000000000024400000000000003440").unwrap());
+        let result = sqlx::query("SELECT ST_SRID($1)")
+            .bind(wkb::EncodeGeography(geom))
+            .fetch_one(&pool)
+            .await;
+        assert!(result.is_err());
+        Ok(())
+    }
+
     #[tokio::test]
     #[ignore]
     async fn bulk_insert() -> Result<(), sqlx::Error> {
@@ -394,6 +473,39 @@ mod postgis_sqlx {
             Ok(())
         }
     }
+
+    #[tokio::test]
+    #[ignore]
+    #[cfg(feature = "with-geojson")]
+    async fn datasource() -> Result<(), geozero::error::GeozeroError> {
+        use geozero::geojson::GeoJsonWriter;
+        use geozero::postgis::sqlx::PgDatasource;
+        use geozero::GeozeroDatasource;
+
+        let pool = pg::get_pool().await;
+        let datasource = PgDatasource::new(
+            "SELECT * FROM (VALUES ('a'::text, 1::int4, 'SRID=4326;POINT(1 2)'::geometry), ('b', NULL, NULL)) AS t(name, num, geom)",
+        );
+        let mut json: Vec<u8> = Vec::new();
+        let count = datasource
+            .read(&pool, &mut GeoJsonWriter::new(&mut json))
+            .await?;
+        assert_eq!(count, 2);
+        assert_eq!(
+            std::str::from_utf8(&json).unwrap(),
+            r#"{
+"type": "FeatureCollection",
+"features": [{"type": "Feature", "properties": {"name": "a", "num": 1}, "geometry": {"type": "Point", "coordinates": [1,2]}},
+{"type": "Feature", "properties": {"name": "b"}}]}"#
+        );
+
+        let mut features = datasource.fetch(&pool).await?;
+        assert_eq!(features.len(), 2);
+        let mut fetched: Vec<u8> = Vec::new();
+        features.process(&mut GeoJsonWriter::new(&mut fetched))?;
+        assert_eq!(fetched, json);
+        Ok(())
+    }
 }
 
 // --- Minimal geometry implementation with PostGIS/GPKG support
@@ -16,6 +16,19 @@ fn read_header() {
     assert_eq!(header.bbox.x_range(), [1.0, 5.0]);
 }
 
+#[test]
+fn read_prj() -> Result<(), geozero::shp::Error> {
+    let reader = ShpReader::from_path("./tests/data/shp/poly.shp")?;
+    let wkt = reader.crs().and_then(|crs| crs.wkt.as_deref());
+    assert!(wkt
+        .unwrap()
+        .starts_with(r#"PROJCS["OSGB 1936 / British National Grid""#));
+
+    let reader = ShpReader::from_path("./tests/data/shp/line.shp")?;
+    assert!(reader.crs().is_none());
+    Ok(())
+}
+
 #[test]
 fn iterate() -> Result<(), geozero::shp::Error> {
     let reader = ShpReader::from_path("./tests/data/shp/poly.shp")?;
@@ -46,6 +59,28 @@ fn iterate() -> Result<(), geozero::shp::Error> {
     Ok(())
 }
 
+#[test]
+fn select_bbox() -> Result<(), geozero::shp::Error> {
+    let mut reader = ShpReader::from_path("./tests/data/shp/poly.shp")?;
+    let cnt = reader.select_bbox(479000.0, 4764000.0, 479300.0, 4765000.0)?;
+    assert_eq!(cnt, 5);
+    let mut cnt = 0;
+    for feat in reader.iter_features(&mut ProcessorSink::new())? {
+        assert!(feat?.property::<f64>("EAS_ID").unwrap() > 100.0);
+        cnt += 1;
+    }
+    assert_eq!(cnt, 5);
+
+    let mut reader = ShpReader::from_path("./tests/data/shp/poly.shp")?;
+    reader.select_bbox(0.0, 0.0, 1.0, 1.0)?;
+    assert_eq!(reader.iter_geometries(&mut ProcessorSink::new()).count(), 0);
+
+    let source = BufReader::new(File::open("./tests/data/shp/poly.shp")?);
+    let mut reader = ShpReader::new(source)?;
+    assert!(reader.select_bbox(0.0, 0.0, 1.0, 1.0).is_err());
+    Ok(())
+}
+
 #[test]
 fn shp_to_json() -> Result<(), geozero::shp::Error> {
     let reader = ShpReader::from_path("./tests/data/shp/poly.shp")?;